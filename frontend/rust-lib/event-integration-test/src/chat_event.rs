@@ -1,8 +1,10 @@
 use crate::event_builder::EventBuilder;
 use crate::EventIntegrationTest;
 use flowy_ai::entities::{
-  ChatMessageListPB, ChatMessageTypePB, CompleteTextPB, CompleteTextTaskPB, CompletionTypePB,
-  LoadNextChatMessagePB, LoadPrevChatMessagePB, SendChatPayloadPB,
+  AIPromptIdPB, AIPromptPB, AIPromptScopePB, AIPromptsDataPB, ChatMessageListPB, ChatMessageTypePB,
+  CompleteTextPB, CompleteTextTaskPB, CompletionTypePB, CreateAIPromptPB, ExportAIPromptsPB,
+  ImportAIPromptsPB, LoadNextChatMessagePB, LoadPrevChatMessagePB, RepeatedAIPromptPB,
+  SendChatPayloadPB,
 };
 use flowy_ai::event_map::AIEvent;
 use flowy_folder::entities::{CreateViewPayloadPB, ViewLayoutPB, ViewPB};
@@ -97,6 +99,7 @@ impl EventIntegrationTest {
       text: text.to_string(),
       completion_type,
       stream_port: 0,
+      ..Default::default()
     };
     EventBuilder::new(self.clone())
       .event(AIEvent::CompleteText)
@@ -105,4 +108,62 @@ impl EventIntegrationTest {
       .await
       .parse::<CompleteTextTaskPB>()
   }
+
+  pub async fn create_ai_prompt(
+    &self,
+    name: &str,
+    template: &str,
+    scope: AIPromptScopePB,
+  ) -> AIPromptPB {
+    let payload = CreateAIPromptPB {
+      name: name.to_string(),
+      template: template.to_string(),
+      scope,
+    };
+    EventBuilder::new(self.clone())
+      .event(AIEvent::CreateAIPrompt)
+      .payload(payload)
+      .async_send()
+      .await
+      .parse::<AIPromptPB>()
+  }
+
+  pub async fn get_ai_prompts(&self) -> RepeatedAIPromptPB {
+    EventBuilder::new(self.clone())
+      .event(AIEvent::GetAIPrompts)
+      .async_send()
+      .await
+      .parse::<RepeatedAIPromptPB>()
+  }
+
+  pub async fn delete_ai_prompt(&self, prompt_id: &str) {
+    let payload = AIPromptIdPB {
+      value: prompt_id.to_string(),
+    };
+    EventBuilder::new(self.clone())
+      .event(AIEvent::DeleteAIPrompt)
+      .payload(payload)
+      .async_send()
+      .await;
+  }
+
+  pub async fn export_ai_prompts(&self, prompt_ids: Vec<String>) -> String {
+    EventBuilder::new(self.clone())
+      .event(AIEvent::ExportAIPrompts)
+      .payload(ExportAIPromptsPB { prompt_ids })
+      .async_send()
+      .await
+      .parse::<AIPromptsDataPB>()
+      .json
+  }
+
+  pub async fn import_ai_prompts(&self, json: String) -> RepeatedAIPromptPB {
+    let payload = ImportAIPromptsPB { json, scope: None };
+    EventBuilder::new(self.clone())
+      .event(AIEvent::ImportAIPrompts)
+      .payload(payload)
+      .async_send()
+      .await
+      .parse::<RepeatedAIPromptPB>()
+  }
 }
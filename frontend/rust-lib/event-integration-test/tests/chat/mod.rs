@@ -1,2 +1,3 @@
 mod ai_tool_test;
 mod chat_message_test;
+mod prompt_library_test;
@@ -0,0 +1,51 @@
+use event_integration_test::EventIntegrationTest;
+use flowy_ai::entities::AIPromptScopePB;
+
+#[tokio::test]
+async fn create_and_delete_prompt_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let prompt = test
+    .create_ai_prompt(
+      "House style",
+      "Rewrite {{selected_text}} in our house style",
+      AIPromptScopePB::Workspace,
+    )
+    .await;
+  assert_eq!(prompt.scope, AIPromptScopePB::Workspace);
+
+  let prompts = test.get_ai_prompts().await;
+  assert_eq!(prompts.items.len(), 1);
+  assert_eq!(prompts.items[0].name, "House style");
+
+  test.delete_ai_prompt(&prompt.id).await;
+  assert!(test.get_ai_prompts().await.items.is_empty());
+}
+
+#[tokio::test]
+async fn export_and_import_prompts_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  test
+    .create_ai_prompt("Shorter", "Make it shorter", AIPromptScopePB::User)
+    .await;
+  test
+    .create_ai_prompt(
+      "Formal",
+      "Rewrite {{selected_text}} formally",
+      AIPromptScopePB::Workspace,
+    )
+    .await;
+
+  let json = test.export_ai_prompts(vec![]).await;
+  for prompt in test.get_ai_prompts().await.items {
+    test.delete_ai_prompt(&prompt.id).await;
+  }
+
+  let imported = test.import_ai_prompts(json).await;
+  assert_eq!(imported.items.len(), 2);
+  let prompts = test.get_ai_prompts().await.items;
+  let names = prompts
+    .iter()
+    .map(|prompt| prompt.name.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(names, vec!["Formal", "Shorter"]);
+}
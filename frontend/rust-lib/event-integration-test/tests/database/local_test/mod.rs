@@ -1,3 +1,4 @@
 mod calculate_test;
 mod event_test;
 mod group_test;
+mod view_query_test;
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::services::ai::AI_VIEW_QUERY_KEY;
+use flowy_database_pub::cloud::DatabaseAIService;
+use flowy_folder::entities::{CreateViewPayloadPB, ViewLayoutPB, ViewPB};
+use flowy_folder::event_map::FolderEvent;
+use flowy_user::errors::FlowyError;
+use lib_infra::async_trait::async_trait;
+
+struct MockDatabaseAIService(&'static str);
+
+#[async_trait]
+impl DatabaseAIService for MockDatabaseAIService {
+  async fn complete_database_prompt(
+    &self,
+    _workspace_id: &str,
+    _prompt: &str,
+  ) -> Result<String, FlowyError> {
+    Ok(self.0.to_string())
+  }
+}
+
+const DONE_TASKS_RESPONSE: &str = r#"{"filters": [{"field": "Done", "condition": "is_checked"}],
+"sorts": [{"field": "Name", "direction": "desc"}],
+"group_by": null}"#;
+
+fn linked_view_payload(parent_view_id: &str, meta: HashMap<String, String>) -> CreateViewPayloadPB {
+  CreateViewPayloadPB {
+    parent_view_id: parent_view_id.to_string(),
+    name: "Done tasks".to_string(),
+    thumbnail: None,
+    layout: ViewLayoutPB::Grid,
+    initial_data: vec![],
+    meta,
+    set_as_current: false,
+    index: None,
+    section: None,
+    view_id: None,
+    extra: None,
+  }
+}
+
+#[tokio::test]
+async fn create_linked_view_with_query_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database_id = test.get_database(&grid_view.id).await.id;
+  test
+    .appflowy_core
+    .database_manager
+    .set_ai_service(Arc::new(MockDatabaseAIService(DONE_TASKS_RESPONSE)));
+
+  let meta = HashMap::from([
+    ("database_id".to_string(), database_id),
+    (
+      AI_VIEW_QUERY_KEY.to_string(),
+      "done tasks sorted by name".to_string(),
+    ),
+  ]);
+  let linked_view = EventBuilder::new(test.clone())
+    .event(FolderEvent::CreateView)
+    .payload(linked_view_payload(&grid_view.id, meta))
+    .async_send()
+    .await
+    .parse::<ViewPB>();
+
+  let editor = test
+    .appflowy_core
+    .database_manager
+    .get_database_editor_with_view_id(&linked_view.id)
+    .await
+    .unwrap();
+  assert_eq!(editor.get_all_filters(&linked_view.id).await.items.len(), 1);
+  assert_eq!(editor.get_all_sorts(&linked_view.id).await.items.len(), 1);
+
+  // The view the linked view is created from keeps its settings
+  assert!(editor.get_all_filters(&grid_view.id).await.items.is_empty());
+  assert!(editor.get_all_sorts(&grid_view.id).await.items.is_empty());
+}
+
+#[tokio::test]
+async fn create_linked_view_with_invalid_query_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database_id = test.get_database(&grid_view.id).await.id;
+  test
+    .appflowy_core
+    .database_manager
+    .set_ai_service(Arc::new(MockDatabaseAIService(
+      r#"{"filters": [{"field": "Assignee", "condition": "is_empty"}]}"#,
+    )));
+
+  let meta = HashMap::from([
+    ("database_id".to_string(), database_id),
+    (
+      AI_VIEW_QUERY_KEY.to_string(),
+      "tasks without assignee".to_string(),
+    ),
+  ]);
+  let error = EventBuilder::new(test.clone())
+    .event(FolderEvent::CreateView)
+    .payload(linked_view_payload(&grid_view.id, meta))
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_some());
+
+  let child_views = test.get_view(&grid_view.id).await.child_views;
+  assert!(child_views.is_empty());
+}
@@ -1,8 +1,16 @@
 use crate::document::generate_random_bytes;
+use event_integration_test::document::document_event::DocumentEventTest;
+use event_integration_test::event_builder::EventBuilder;
 use event_integration_test::user_event::use_localhost_af_cloud;
 use event_integration_test::{EventIntegrationTest, SINGLE_FILE_UPLOAD_SIZE};
+use flowy_database2::entities::{
+  CellIdPB, FieldType, FileUploadTypePB, MediaCellChangesetPB, MediaFilePB, MediaFileTypePB,
+};
+use flowy_database2::event_map::DatabaseEvent;
 use flowy_storage_pub::storage::FileUploadState;
 use lib_infra::util::md5;
+use serde_json::json;
+use std::collections::HashMap;
 use std::env::temp_dir;
 use std::sync::Arc;
 use std::time::Duration;
@@ -195,6 +203,81 @@ async fn af_cloud_delete_upload_file_test() {
   assert!(matches!(state, FileUploadState::Finished { .. }));
 }
 
+#[tokio::test]
+async fn af_cloud_purge_expired_trash_files_test() {
+  use_localhost_af_cloud().await;
+  let test = EventIntegrationTest::new().await;
+  test.af_cloud_sign_up().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  test.storage_manager.update_network_reachable(false);
+
+  // a document with an image block and a grid with a media cell, both pointing to uploaded files
+  let document = test
+    .create_and_open_document(&workspace_id, "Old notes".to_string(), vec![])
+    .await;
+  let document_file_url = upload_file(&test, &workspace_id, &document.id).await;
+  let document_test = DocumentEventTest::new_with_core(test.clone());
+  let block_id = document_test.insert_index(&document.id, "", 1, None).await;
+  document_test
+    .update_data(
+      &document.id,
+      &block_id,
+      HashMap::from([("url".to_string(), json!(document_file_url))]),
+    )
+    .await;
+
+  let grid = test
+    .create_grid(&workspace_id, "Old tasks".to_string(), vec![])
+    .await;
+  let media_field = test.create_field(&grid.id, FieldType::Media).await;
+  let row_id = test.get_database(&grid.id).await.rows[0].id.clone();
+  let grid_file_url = upload_file(&test, &workspace_id, &grid.id).await;
+  EventBuilder::new(test.clone())
+    .event(DatabaseEvent::UpdateMediaCell)
+    .payload(MediaCellChangesetPB {
+      view_id: grid.id.clone(),
+      cell_id: CellIdPB {
+        view_id: grid.id.clone(),
+        field_id: media_field.id.clone(),
+        row_id,
+      },
+      inserted_files: vec![MediaFilePB {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: "file".to_string(),
+        url: grid_file_url.clone(),
+        upload_type: FileUploadTypePB::CloudFile,
+        file_type: MediaFileTypePB::Other,
+      }],
+      removed_ids: vec![],
+    })
+    .async_send()
+    .await;
+
+  let storage_service = test.storage_manager.storage_service.clone();
+  for url in [&document_file_url, &grid_file_url] {
+    assert!(storage_service.get_object_size(url).await.unwrap().unwrap() > 0);
+  }
+
+  test.delete_view(&document.id).await;
+  test.delete_view(&grid.id).await;
+  assert_eq!(test.folder_manager.purge_trash(0).await.unwrap(), 2);
+  for url in [&document_file_url, &grid_file_url] {
+    assert_eq!(storage_service.get_object_size(url).await.unwrap(), Some(0));
+  }
+}
+
+async fn upload_file(test: &EventIntegrationTest, workspace_id: &str, parent_dir: &str) -> String {
+  let file_path = generate_file_with_bytes_len(1024).await.0;
+  let (created_upload, _) = test
+    .storage_manager
+    .storage_service
+    .create_upload(workspace_id, parent_dir, &file_path)
+    .await
+    .unwrap();
+  let _ = fs::remove_file(file_path).await;
+  created_upload.url
+}
+
 async fn generate_file_with_bytes_len(len: usize) -> (String, Vec<u8>) {
   let data = generate_random_bytes(len);
   let file_dir = temp_dir().join(uuid::Uuid::new_v4().to_string());
@@ -0,0 +1,317 @@
+use std::time::Duration;
+
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_folder::entities::icon::UpdateViewIconPayloadPB;
+use flowy_folder::entities::{
+  BatchMoveViewsPB, BatchSetFavoritePB, BatchUpdateViewIconPB, GetWorkspaceViewPB,
+  RepeatedViewIdPB, RepeatedViewPB, UpdateViewVisibilityStatusPayloadPB, ViewIconPB,
+  ViewIconTypePB, ViewSectionPB,
+};
+use flowy_folder::event_map::FolderEvent::*;
+use flowy_folder::notification::FolderNotification;
+use flowy_user::errors::FlowyError;
+use tokio::sync::mpsc::Receiver;
+use tokio::time::timeout;
+
+async fn get_private_view_ids(test: &EventIntegrationTest, workspace_id: &str) -> Vec<String> {
+  EventBuilder::new(test.clone())
+    .event(ReadPrivateViews)
+    .payload(GetWorkspaceViewPB {
+      value: workspace_id.to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+    .into_iter()
+    .map(|view| view.id)
+    .collect()
+}
+
+/// Returns the number of notifications received until none arrives for a while.
+async fn count_notifications(rx: &mut Receiver<()>) -> usize {
+  let mut count = 0;
+  while let Ok(Some(())) = timeout(Duration::from_millis(500), rx.recv()).await {
+    count += 1;
+  }
+  count
+}
+
+async fn batch_move_views_to_trash(
+  test: &EventIntegrationTest,
+  view_ids: Vec<String>,
+) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(BatchMoveViewsToTrash)
+    .payload(RepeatedViewIdPB { items: view_ids })
+    .async_send()
+    .await
+    .error()
+}
+
+#[tokio::test]
+async fn batch_trash_and_restore_views_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let document_1 = test.create_document("Notes 1").await;
+  let document_2 = test.create_document("Notes 2").await;
+  let document_3 = test.create_document("Notes 3").await;
+
+  EventBuilder::new(test.clone())
+    .event(BatchSetFavorite)
+    .payload(BatchSetFavoritePB {
+      view_ids: vec![document_1.id.clone(), document_3.id.clone()],
+      is_favorite: true,
+    })
+    .async_send()
+    .await;
+  assert!(test.get_view(&document_1.id).await.is_favorite);
+  assert!(!test.get_view(&document_2.id).await.is_favorite);
+
+  let error =
+    batch_move_views_to_trash(&test, vec![document_1.id.clone(), document_2.id.clone()]).await;
+  assert!(error.is_none());
+  assert_eq!(test.get_trash().await.items.len(), 2);
+  // The trashed views are removed from the favorites
+  assert!(!test.get_view(&document_1.id).await.is_favorite);
+  assert!(test.get_view(&document_3.id).await.is_favorite);
+
+  EventBuilder::new(test.clone())
+    .event(BatchRestoreTrash)
+    .payload(RepeatedViewIdPB {
+      items: vec![document_1.id.clone(), document_2.id.clone()],
+    })
+    .async_send()
+    .await;
+  assert!(test.get_trash().await.items.is_empty());
+}
+
+#[tokio::test]
+async fn batch_with_invalid_view_changes_nothing_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let document = test.create_document("Notes").await;
+
+  let error =
+    batch_move_views_to_trash(&test, vec![document.id.clone(), "not_exist".to_string()]).await;
+  assert!(error.is_some());
+  assert!(test.get_trash().await.items.is_empty());
+}
+
+#[tokio::test]
+async fn batch_move_views_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let parent = test.create_document("Archive").await;
+  let document_1 = test.create_document("Notes 1").await;
+  let document_2 = test.create_document("Notes 2").await;
+
+  let move_views = |view_ids: Vec<String>, new_parent_id: String| {
+    EventBuilder::new(test.clone())
+      .event(BatchMoveViews)
+      .payload(BatchMoveViewsPB {
+        view_ids,
+        new_parent_id,
+        prev_view_id: None,
+        from_section: None,
+        to_section: None,
+      })
+      .async_send()
+  };
+  let error = move_views(
+    vec![document_1.id.clone(), document_2.id.clone()],
+    parent.id.clone(),
+  )
+  .await
+  .error();
+  assert!(error.is_none());
+  let child_view_ids = test
+    .get_view(&parent.id)
+    .await
+    .child_views
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(child_view_ids, vec![document_1.id.clone(), document_2.id]);
+
+  // A view can't be moved under its child view
+  let error = move_views(vec![parent.id.clone()], document_1.id.clone())
+    .await
+    .error();
+  assert!(error.is_some());
+}
+
+#[tokio::test]
+async fn batch_update_view_icon_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let document_1 = test.create_document("Notes 1").await;
+  let document_2 = test.create_document("Notes 2").await;
+  let icon = ViewIconPB {
+    ty: ViewIconTypePB::Emoji,
+    value: "📌".to_string(),
+  };
+
+  EventBuilder::new(test.clone())
+    .event(BatchUpdateViewIcon)
+    .payload(BatchUpdateViewIconPB {
+      view_ids: vec![document_1.id.clone(), document_2.id.clone()],
+      icon: Some(icon.clone()),
+    })
+    .async_send()
+    .await;
+  assert_eq!(test.get_view(&document_1.id).await.icon, Some(icon.clone()));
+  assert_eq!(test.get_view(&document_2.id).await.icon, Some(icon));
+}
+
+#[tokio::test]
+async fn batch_move_views_to_private_section_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let parent = test.create_document("Archive").await;
+  let document_1 = test.create_document("Notes 1").await;
+  let document_2 = test.create_document("Notes 2").await;
+  let move_views = |view_ids: Vec<String>, from_section, to_section| {
+    EventBuilder::new(test.clone())
+      .event(BatchMoveViews)
+      .payload(BatchMoveViewsPB {
+        view_ids,
+        new_parent_id: workspace_id.clone(),
+        prev_view_id: Some(parent.id.clone()),
+        from_section: Some(from_section),
+        to_section: Some(to_section),
+      })
+      .async_send()
+  };
+
+  let error = move_views(
+    vec![document_1.id.clone(), document_2.id.clone()],
+    ViewSectionPB::Public,
+    ViewSectionPB::Private,
+  )
+  .await
+  .error();
+  assert!(error.is_none());
+  let private_view_ids = get_private_view_ids(&test, &workspace_id).await;
+  assert!(private_view_ids.contains(&document_1.id));
+  assert!(private_view_ids.contains(&document_2.id));
+  assert!(!private_view_ids.contains(&parent.id));
+
+  let error = move_views(
+    vec![document_1.id.clone()],
+    ViewSectionPB::Private,
+    ViewSectionPB::Public,
+  )
+  .await
+  .error();
+  assert!(error.is_none());
+  let private_view_ids = get_private_view_ids(&test, &workspace_id).await;
+  assert_eq!(private_view_ids, vec![document_2.id.clone()]);
+  let public_view_ids = test
+    .get_all_workspace_views()
+    .await
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert!(public_view_ids.contains(&document_1.id));
+  assert!(!public_view_ids.contains(&document_2.id));
+}
+
+#[tokio::test]
+async fn batch_set_views_visibility_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let document_1 = test.create_document("Notes 1").await;
+  let document_2 = test.create_document("Notes 2").await;
+  let document_3 = test.create_document("Notes 3").await;
+  let set_visibility = |view_ids: Vec<String>, is_public: bool| {
+    EventBuilder::new(test.clone())
+      .event(BatchUpdateViewVisibility)
+      .payload(UpdateViewVisibilityStatusPayloadPB {
+        view_ids,
+        is_public,
+      })
+      .async_send()
+  };
+
+  let error = set_visibility(vec![document_1.id.clone(), document_2.id.clone()], false)
+    .await
+    .error();
+  assert!(error.is_none());
+  let private_view_ids = get_private_view_ids(&test, &workspace_id).await;
+  assert_eq!(private_view_ids.len(), 2);
+  assert!(private_view_ids.contains(&document_1.id));
+  assert!(private_view_ids.contains(&document_2.id));
+
+  let error = set_visibility(vec![document_1.id.clone(), document_2.id.clone()], true)
+    .await
+    .error();
+  assert!(error.is_none());
+  assert!(get_private_view_ids(&test, &workspace_id).await.is_empty());
+
+  // Nothing is changed if any of the views doesn't exist
+  let error = set_visibility(vec![document_3.id.clone(), "not_exist".to_string()], false)
+    .await
+    .error();
+  assert!(error.is_some());
+  assert!(get_private_view_ids(&test, &workspace_id).await.is_empty());
+}
+
+#[tokio::test]
+async fn batch_sends_one_notification_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let document_1 = test.create_document("Notes 1").await;
+  let document_2 = test.create_document("Notes 2").await;
+  let document_3 = test.create_document("Notes 3").await;
+  let view_ids = vec![
+    document_1.id.clone(),
+    document_2.id.clone(),
+    document_3.id.clone(),
+  ];
+  // Skip the notifications of the created views
+  tokio::time::sleep(Duration::from_millis(500)).await;
+
+  let mut batch_rx = test
+    .notification_sender
+    .subscribe_without_payload(&workspace_id, FolderNotification::DidBatchUpdateViews);
+  let mut child_views_rx = test
+    .notification_sender
+    .subscribe_without_payload(&workspace_id, FolderNotification::DidUpdateChildViews);
+  let mut view_rxs = view_ids
+    .iter()
+    .map(|view_id| {
+      test
+        .notification_sender
+        .subscribe_without_payload(view_id, FolderNotification::DidUpdateView)
+    })
+    .collect::<Vec<_>>();
+  let icon = ViewIconPB {
+    ty: ViewIconTypePB::Emoji,
+    value: "📌".to_string(),
+  };
+  let error = EventBuilder::new(test.clone())
+    .event(BatchUpdateViewIcon)
+    .payload(BatchUpdateViewIconPB {
+      view_ids: view_ids.clone(),
+      icon: Some(icon),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  assert_eq!(count_notifications(&mut batch_rx).await, 1);
+  assert_eq!(count_notifications(&mut child_views_rx).await, 0);
+  for view_rx in view_rxs.iter_mut() {
+    assert_eq!(count_notifications(view_rx).await, 0);
+  }
+
+  // The updates made after the batch are notified per view again
+  let error = test
+    .update_view_icon(UpdateViewIconPayloadPB {
+      view_id: document_1.id.clone(),
+      icon: None,
+    })
+    .await;
+  assert!(error.is_none());
+  assert!(count_notifications(&mut view_rxs[0]).await > 0);
+  assert_eq!(count_notifications(&mut batch_rx).await, 0);
+}
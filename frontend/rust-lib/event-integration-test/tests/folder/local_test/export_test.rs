@@ -0,0 +1,121 @@
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{FieldPB, GroupPB};
+use flowy_folder::entities::{
+  ExportFormatPB, ExportZipPB, ImportZipPB, UpdateViewIconPayloadPB, ViewIconPB, ViewIconTypePB,
+  ViewLayoutPB,
+};
+use flowy_folder::event_map::FolderEvent::{ExportZipFile, ImportZipFile};
+use tempdir::TempDir;
+
+#[tokio::test]
+async fn import_exported_zip_file_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let notes = test
+    .create_and_open_document(&workspace_id, "Notes".to_string(), vec![])
+    .await;
+  test.insert_document_text(&notes.id, "The tasks", 0).await;
+  let tasks = test
+    .create_grid(&notes.id, "Tasks".to_string(), vec![])
+    .await;
+  let board = test
+    .create_view_with_layout(&notes.id, "Board".to_string(), ViewLayoutPB::Board)
+    .await;
+  let icon = ViewIconPB {
+    ty: ViewIconTypePB::Emoji,
+    value: "📝".to_string(),
+  };
+  test
+    .update_view_icon(UpdateViewIconPayloadPB {
+      view_id: notes.id.clone(),
+      icon: Some(icon.clone()),
+    })
+    .await;
+
+  let dir = TempDir::new("export_zip").unwrap();
+  let zip_path = dir.path().join("export.zip").to_str().unwrap().to_string();
+  let error = EventBuilder::new(test.clone())
+    .event(ExportZipFile)
+    .payload(ExportZipPB {
+      view_id: Some(notes.id.clone()),
+      file_path: zip_path.clone(),
+      document_format: ExportFormatPB::Markdown,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let error = EventBuilder::new(test.clone())
+    .event(ImportZipFile)
+    .payload(ImportZipPB {
+      file_path: zip_path,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  // The views are imported with new ids, their hierarchy and their icons.
+  let views = test.get_all_workspace_views().await;
+  let imported = views
+    .iter()
+    .find(|view| view.name == "Notes" && view.id != notes.id)
+    .unwrap();
+  assert_eq!(imported.layout, ViewLayoutPB::Document);
+  assert_eq!(imported.icon, Some(icon));
+  let imported = test.get_view(&imported.id).await;
+  let children = imported
+    .child_views
+    .iter()
+    .map(|view| (view.name.as_str(), view.layout.clone()))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    children,
+    vec![
+      ("Tasks", ViewLayoutPB::Grid),
+      ("Board", ViewLayoutPB::Board)
+    ]
+  );
+
+  let document = test.get_document_data(&imported.id).await;
+  let texts = document.meta.text_map.unwrap_or_default();
+  assert!(texts.values().any(|delta| delta.contains("The tasks")));
+
+  // The databases keep their rows and their field types.
+  for (view_id, imported_view_id) in [
+    (&tasks.id, &imported.child_views[0].id),
+    (&board.id, &imported.child_views[1].id),
+  ] {
+    let database = test.get_database(view_id).await;
+    let imported_database = test.get_database(imported_view_id).await;
+    assert_eq!(imported_database.rows.len(), database.rows.len());
+    let field_types = |fields: Vec<FieldPB>| {
+      fields
+        .into_iter()
+        .map(|field| (field.name, field.field_type))
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(
+      field_types(test.get_all_database_fields(imported_view_id).await.items),
+      field_types(test.get_all_database_fields(view_id).await.items),
+    );
+    assert_eq!(imported_database.layout_type, database.layout_type);
+  }
+
+  // The board is grouped by the same field as the exported board.
+  let group_field_ids = |groups: Vec<GroupPB>| {
+    groups
+      .into_iter()
+      .map(|group| group.field_id)
+      .collect::<Vec<_>>()
+  };
+  let groups = test.get_groups(&board.id).await;
+  assert!(!groups.is_empty());
+  assert_eq!(
+    group_field_ids(test.get_groups(&imported.child_views[1].id).await),
+    group_field_ids(groups),
+  );
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use collab_database::rows::database_row_document_id_from_row_id;
+use event_integration_test::document::document_event::DocumentEventTest;
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{CellChangesetPB, FieldType, FillAIColumnPayloadPB, SummaryRowPB};
+use flowy_database2::event_map::DatabaseEvent;
+use flowy_document::entities::{CreateDocumentPayloadPB, TextDeltaPayloadPB};
+use flowy_document::event_map::DocumentEvent;
+use flowy_folder::entities::{CreateViewPayloadPB, ViewIdPB, ViewLayoutPB, ViewPB};
+use flowy_folder::event_map::FolderEvent::*;
+use flowy_user::errors::{ErrorCode, FlowyError};
+
+async fn set_view_locked(test: &EventIntegrationTest, view_id: &str, locked: bool) {
+  let error = EventBuilder::new(test.clone())
+    .event(if locked { LockView } else { UnlockView })
+    .payload(ViewIdPB {
+      value: view_id.to_string(),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+}
+
+async fn append_text(
+  test: &EventIntegrationTest,
+  document_id: &str,
+  text_id: &str,
+) -> Option<FlowyError> {
+  EventBuilder::new(test.clone())
+    .event(DocumentEvent::ApplyTextDeltaEvent)
+    .payload(TextDeltaPayloadPB {
+      document_id: document_id.to_string(),
+      text_id: text_id.to_string(),
+      delta: Some(r#"[{"retain":5},{"insert":" world"}]"#.to_string()),
+    })
+    .async_send()
+    .await
+    .error()
+}
+
+#[tokio::test]
+async fn lock_and_unlock_view_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let document = test.create_document("Spec").await;
+  assert!(document.lock.is_none());
+
+  set_view_locked(&test, &document.id, true).await;
+  let lock = test.get_view(&document.id).await.lock.unwrap();
+  assert!(lock.locked_at > 0);
+
+  set_view_locked(&test, &document.id, false).await;
+  assert!(test.get_view(&document.id).await.lock.is_none());
+}
+
+#[tokio::test]
+async fn edit_locked_document_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let document = test.create_document("Spec").await;
+  let document_event = DocumentEventTest::new_with_core(test.clone());
+  let block_id = document_event
+    .insert_index(&document.id, "Hello", 1, None)
+    .await;
+  let text_id = document_event
+    .get_text_id(&document.id, &block_id)
+    .await
+    .unwrap();
+
+  set_view_locked(&test, &document.id, true).await;
+  let error = append_text(&test, &document.id, &text_id).await.unwrap();
+  assert_eq!(error.code, ErrorCode::ViewLocked);
+  let delta = document_event
+    .get_delta(&document.id, &text_id)
+    .await
+    .unwrap();
+  assert!(!delta.contains("world"));
+
+  set_view_locked(&test, &document.id, false).await;
+  assert!(append_text(&test, &document.id, &text_id).await.is_none());
+  let delta = document_event
+    .get_delta(&document.id, &text_id)
+    .await
+    .unwrap();
+  assert!(delta.contains("world"));
+}
+
+#[tokio::test]
+async fn edit_locked_grid_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "Tasks".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let changeset = CellChangesetPB {
+    view_id: grid_view.id.clone(),
+    row_id: database.rows[0].id.clone(),
+    field_id: fields[0].id.clone(),
+    cell_changeset: "hello world".to_string(),
+  };
+
+  set_view_locked(&test, &grid_view.id, true).await;
+  let error = test.update_cell(changeset.clone()).await.unwrap();
+  assert_eq!(error.code, ErrorCode::ViewLocked);
+  let error = test
+    .delete_row(&grid_view.id, &database.rows[0].id)
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewLocked);
+
+  set_view_locked(&test, &grid_view.id, false).await;
+  assert!(test.update_cell(changeset).await.is_none());
+}
+
+#[tokio::test]
+async fn edit_linked_view_of_locked_grid_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "Tasks".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let linked_view = EventBuilder::new(test.clone())
+    .event(CreateView)
+    .payload(CreateViewPayloadPB {
+      parent_view_id: grid_view.id.clone(),
+      name: "Board".to_string(),
+      thumbnail: None,
+      layout: ViewLayoutPB::Board,
+      initial_data: vec![],
+      meta: HashMap::from([("database_id".to_string(), database.id.clone())]),
+      set_as_current: false,
+      index: None,
+      section: None,
+      view_id: None,
+      extra: None,
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>();
+  let fields = test.get_all_database_fields(&linked_view.id).await.items;
+  let changeset = CellChangesetPB {
+    view_id: linked_view.id.clone(),
+    row_id: database.rows[0].id.clone(),
+    field_id: fields[0].id.clone(),
+    cell_changeset: "hello world".to_string(),
+  };
+
+  // locking one view of the database locks the edits through all of its views
+  set_view_locked(&test, &grid_view.id, true).await;
+  let error = test.update_cell(changeset.clone()).await.unwrap();
+  assert_eq!(error.code, ErrorCode::ViewLocked);
+
+  set_view_locked(&test, &grid_view.id, false).await;
+  assert!(test.update_cell(changeset).await.is_none());
+}
+
+#[tokio::test]
+async fn fill_ai_column_of_locked_grid_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "Tasks".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let summary_field = test.create_field(&grid_view.id, FieldType::Summary).await;
+
+  set_view_locked(&test, &grid_view.id, true).await;
+  let error = EventBuilder::new(test.clone())
+    .event(DatabaseEvent::FillAIColumn)
+    .payload(FillAIColumnPayloadPB {
+      view_id: grid_view.id.clone(),
+      field_id: summary_field.id.clone(),
+      only_empty: false,
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewLocked);
+
+  let error = EventBuilder::new(test.clone())
+    .event(DatabaseEvent::SummarizeRow)
+    .payload(SummaryRowPB {
+      view_id: grid_view.id.clone(),
+      row_id: database.rows[0].id.clone(),
+      field_id: summary_field.id.clone(),
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::ViewLocked);
+}
+
+#[tokio::test]
+async fn edit_row_document_of_locked_grid_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "Tasks".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let document_id = database_row_document_id_from_row_id(&database.rows[0].id);
+  let error = EventBuilder::new(test.clone())
+    .event(DocumentEvent::CreateDocument)
+    .payload(CreateDocumentPayloadPB {
+      document_id: document_id.clone(),
+      initial_data: None,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+  let document_event = DocumentEventTest::new_with_core(test.clone());
+  let block_id = document_event
+    .insert_index(&document_id, "Hello", 1, None)
+    .await;
+  let text_id = document_event
+    .get_text_id(&document_id, &block_id)
+    .await
+    .unwrap();
+
+  set_view_locked(&test, &grid_view.id, true).await;
+  let error = append_text(&test, &document_id, &text_id).await.unwrap();
+  assert_eq!(error.code, ErrorCode::ViewLocked);
+
+  set_view_locked(&test, &grid_view.id, false).await;
+  assert!(append_text(&test, &document_id, &text_id).await.is_none());
+}
@@ -1,8 +1,17 @@
+mod batch_test;
+mod export_test;
 mod folder_test;
 mod import_test;
+mod lock_test;
+mod notion_import_test;
 mod script;
+mod static_site_test;
 mod subscription_test;
+mod tag_test;
+mod template_test;
 mod test;
+mod transfer_test;
+mod trash_retention_test;
 
 mod publish_database_test;
 mod publish_document_test;
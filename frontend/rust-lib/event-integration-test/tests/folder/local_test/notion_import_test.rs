@@ -0,0 +1,146 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use collab_folder::ViewLayout;
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::FieldType;
+use flowy_folder::entities::{ImportZipPB, ViewLayoutPB};
+use flowy_folder::event_map::FolderEvent::ImportZipFile;
+use flowy_folder::share::collect_notion_pages;
+use tempdir::TempDir;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+const NOTES_ID: &str = "0123456789abcdef0123456789abcdef";
+const TASKS_ID: &str = "fedcba9876543210fedcba9876543210";
+const EMPTY_ID: &str = "00112233445566778899aabbccddeeff";
+
+const TASKS_CSV: &str = r#"Name,Status,Estimate,Due,Done
+Task 1,Todo,1,"May 26, 2023",Yes
+Task 2,Done,2,"May 22, 2023",No
+Task 3,Todo,3,"May 20, 2023",No"#;
+
+/// The files of a Notion export, by their paths in the zip file.
+fn notion_export_files() -> Vec<(String, String)> {
+  vec![
+    (
+      format!("Notes {}.md", NOTES_ID),
+      "# Notes\n\nThe tasks of the week.".to_string(),
+    ),
+    (
+      format!("Notes {}/Tasks {}.csv", NOTES_ID, TASKS_ID),
+      "Name,Status\nTask 1,Todo".to_string(),
+    ),
+    (
+      format!("Notes {}/Tasks {}_all.csv", NOTES_ID, TASKS_ID),
+      TASKS_CSV.to_string(),
+    ),
+    (
+      format!("Archive {}/Old {}.md", EMPTY_ID, TASKS_ID),
+      "# Old".to_string(),
+    ),
+    (".DS_Store".to_string(), String::new()),
+  ]
+}
+
+fn write_notion_export(dir: &Path) {
+  for (path, content) in notion_export_files() {
+    let path = dir.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+  }
+}
+
+fn zip_notion_export(zip_path: &Path) {
+  let mut zip = ZipWriter::new(fs::File::create(zip_path).unwrap());
+  for (path, content) in notion_export_files() {
+    zip.start_file(path, FileOptions::<()>::default()).unwrap();
+    zip.write_all(content.as_bytes()).unwrap();
+  }
+  zip.finish().unwrap();
+}
+
+#[test]
+fn collect_notion_pages_test() {
+  let dir = TempDir::new("notion_export").unwrap();
+  write_notion_export(dir.path());
+
+  let pages = collect_notion_pages(dir.path()).unwrap();
+  let names = pages
+    .iter()
+    .map(|page| page.name.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(names, vec!["Notes", "Tasks", "Archive", "Old"]);
+
+  let notes = &pages[0];
+  assert_eq!(notes.layout, ViewLayout::Document);
+  assert_eq!(notes.parent, None);
+
+  // The export with all the rows is imported, and the other export is kept for the links.
+  let tasks = &pages[1];
+  assert_eq!(tasks.layout, ViewLayout::Grid);
+  assert_eq!(tasks.parent, Some(0));
+  assert_eq!(tasks.files.len(), 2);
+  assert!(tasks.files[0].ends_with(format!("Tasks {}_all.csv", TASKS_ID)));
+
+  // A directory without its page file becomes an empty document.
+  let archive = &pages[2];
+  assert_eq!(archive.layout, ViewLayout::Document);
+  assert!(archive.files.is_empty());
+  assert_eq!(pages[3].parent, Some(2));
+}
+
+#[tokio::test]
+async fn import_notion_zip_file_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let dir = TempDir::new("notion_zip").unwrap();
+  let zip_path = dir.path().join("notion.zip");
+  zip_notion_export(&zip_path);
+
+  let error = EventBuilder::new(test.clone())
+    .event(ImportZipFile)
+    .payload(ImportZipPB {
+      file_path: zip_path.to_str().unwrap().to_string(),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let views = test.get_all_workspace_views().await;
+  let notes = views.iter().find(|view| view.name == "Notes").unwrap();
+  assert_eq!(notes.layout, ViewLayoutPB::Document);
+  let notes = test.get_view(&notes.id).await;
+  assert_eq!(notes.child_views.len(), 1);
+  let tasks = &notes.child_views[0];
+  assert_eq!(tasks.name, "Tasks");
+  assert_eq!(tasks.layout, ViewLayoutPB::Grid);
+
+  let archive = views.iter().find(|view| view.name == "Archive").unwrap();
+  let archive = test.get_view(&archive.id).await;
+  assert_eq!(archive.child_views[0].name, "Old");
+
+  // The rows come from the export with all the rows, and the field types are inferred.
+  let database = test.get_database(&tasks.id).await;
+  assert_eq!(database.rows.len(), 3);
+  let field_types = test
+    .get_all_database_fields(&tasks.id)
+    .await
+    .items
+    .into_iter()
+    .map(|field| (field.name, field.field_type))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    field_types,
+    vec![
+      ("Name".to_string(), FieldType::RichText),
+      ("Status".to_string(), FieldType::SingleSelect),
+      ("Estimate".to_string(), FieldType::Number),
+      ("Due".to_string(), FieldType::DateTime),
+      ("Done".to_string(), FieldType::Checkbox),
+    ]
+  );
+}
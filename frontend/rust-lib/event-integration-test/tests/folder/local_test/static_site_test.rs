@@ -0,0 +1,114 @@
+use std::fs;
+
+use event_integration_test::document::document_event::DocumentEventTest;
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_document::entities::TextDeltaPayloadPB;
+use flowy_document::event_map::DocumentEvent;
+use flowy_folder::entities::{PublishStaticSitePayloadPB, PublishStaticSiteResultPB};
+use flowy_folder::event_map::FolderEvent::PublishStaticSite;
+use flowy_folder::publish_util::generate_publish_name;
+use flowy_folder::share::{STATIC_SITE_INDEX_FILE_NAME, STATIC_SITE_STYLE_FILE_NAME};
+use serde_json::json;
+use tempdir::TempDir;
+
+#[tokio::test]
+async fn publish_static_site_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let notes = test
+    .create_and_open_document(&workspace_id, "Notes & <Tasks>".to_string(), vec![])
+    .await;
+  test.insert_document_text(&notes.id, "The tasks", 0).await;
+  let tasks = test
+    .create_grid(&notes.id, "Tasks".to_string(), vec![])
+    .await;
+
+  let dir = TempDir::new("static_site").unwrap();
+  let result = EventBuilder::new(test.clone())
+    .event(PublishStaticSite)
+    .payload(PublishStaticSitePayloadPB {
+      view_id: notes.id.clone(),
+      dir_path: dir.path().to_str().unwrap().to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<PublishStaticSiteResultPB>();
+  assert_eq!(result.page_count, 2);
+  assert!(dir.path().join(STATIC_SITE_STYLE_FILE_NAME).is_file());
+
+  // The index redirects to the page of the published view.
+  let notes_path = format!("{}.html", generate_publish_name(&notes.id, &notes.name));
+  let tasks_path = format!("{}.html", generate_publish_name(&tasks.id, &tasks.name));
+  let index = fs::read_to_string(dir.path().join(STATIC_SITE_INDEX_FILE_NAME)).unwrap();
+  assert!(index.contains(&format!("url={}", notes_path)));
+
+  // The pages have the escaped names, their content and the navigation to the other pages.
+  let notes_page = fs::read_to_string(dir.path().join(&notes_path)).unwrap();
+  assert!(notes_page.contains("<title>Notes &amp; &lt;Tasks&gt;</title>"));
+  assert!(notes_page.contains("The tasks"));
+  assert!(notes_page.contains(&format!("<a href=\"{}\">Tasks</a>", tasks_path)));
+
+  let tasks_page = fs::read_to_string(dir.path().join(&tasks_path)).unwrap();
+  assert!(tasks_page.contains("<table class=\"database\">"));
+  assert!(tasks_page.contains("<th>Name</th>"));
+  assert!(tasks_page.contains(&format!(
+    "<a href=\"{}\" class=\"current\">Tasks</a>",
+    tasks_path
+  )));
+}
+
+#[tokio::test]
+async fn publish_static_site_escapes_document_content_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let notes = test
+    .create_and_open_document(&workspace_id, "Notes".to_string(), vec![])
+    .await;
+  let document_event = DocumentEventTest::new_with_core(test.clone());
+  let block_id = document_event
+    .insert_index(&notes.id, "<script>alert(1)</script>", 1, None)
+    .await;
+  let text_id = document_event
+    .get_text_id(&notes.id, &block_id)
+    .await
+    .unwrap();
+  let delta = json!([
+    { "retain": 25 },
+    { "insert": "unsafe", "attributes": { "href": "javascript:alert(1)" } },
+    { "insert": "safe", "attributes": { "href": "https://appflowy.io/?a=\"b\"" } },
+  ]);
+  let error = EventBuilder::new(test.clone())
+    .event(DocumentEvent::ApplyTextDeltaEvent)
+    .payload(TextDeltaPayloadPB {
+      document_id: notes.id.clone(),
+      text_id,
+      delta: Some(delta.to_string()),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let dir = TempDir::new("static_site").unwrap();
+  EventBuilder::new(test.clone())
+    .event(PublishStaticSite)
+    .payload(PublishStaticSitePayloadPB {
+      view_id: notes.id.clone(),
+      dir_path: dir.path().to_str().unwrap().to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<PublishStaticSiteResultPB>();
+
+  let notes_path = format!("{}.html", generate_publish_name(&notes.id, &notes.name));
+  let notes_page = fs::read_to_string(dir.path().join(&notes_path)).unwrap();
+  assert!(!notes_page.contains("<script>"));
+  assert!(notes_page.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+  // The javascript link is rendered as plain text, and the other links are quoted
+  assert!(!notes_page.contains("javascript:"));
+  assert!(notes_page.contains("unsafe"));
+  assert!(notes_page.contains("<a href=\"https://appflowy.io/?a=&quot;b&quot;\">safe</a>"));
+}
@@ -0,0 +1,217 @@
+use collab::preclude::updates::decoder::Decode;
+use collab::preclude::{Collab, ReadTxn, StateVector, Update};
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_folder::entities::{
+  CreateTagPB, MergeTagsPB, RepeatedTagPB, RepeatedViewPB, SetViewTagsPB, TagIdPB, TagPB,
+  UpdateTagPB, ViewIdPB, ViewTagIdsPB,
+};
+use flowy_folder::event_map::FolderEvent::*;
+use flowy_folder::tag::{workspace_tags, write_workspace_tags, ViewTag};
+
+async fn create_tag(test: &EventIntegrationTest, name: &str) -> TagPB {
+  EventBuilder::new(test.clone())
+    .event(CreateTag)
+    .payload(CreateTagPB {
+      name: name.to_string(),
+      color: "#FF0000".to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<TagPB>()
+}
+
+async fn get_tags(test: &EventIntegrationTest) -> Vec<TagPB> {
+  EventBuilder::new(test.clone())
+    .event(GetTags)
+    .async_send()
+    .await
+    .parse::<RepeatedTagPB>()
+    .items
+}
+
+async fn set_view_tags(test: &EventIntegrationTest, view_id: &str, tag_ids: Vec<String>) {
+  EventBuilder::new(test.clone())
+    .event(SetViewTags)
+    .payload(SetViewTagsPB {
+      view_id: view_id.to_string(),
+      tag_ids,
+    })
+    .async_send()
+    .await;
+}
+
+async fn get_view_tag_ids(test: &EventIntegrationTest, view_id: &str) -> Vec<String> {
+  EventBuilder::new(test.clone())
+    .event(GetViewTags)
+    .payload(ViewIdPB {
+      value: view_id.to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<ViewTagIdsPB>()
+    .tag_ids
+}
+
+async fn get_view_ids_with_tag(test: &EventIntegrationTest, tag_id: &str) -> Vec<String> {
+  EventBuilder::new(test.clone())
+    .event(GetViewsWithTag)
+    .payload(TagIdPB {
+      tag_id: tag_id.to_string(),
+    })
+    .async_send()
+    .await
+    .parse::<RepeatedViewPB>()
+    .items
+    .into_iter()
+    .map(|view| view.id)
+    .collect()
+}
+
+#[tokio::test]
+async fn create_and_update_tag_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let tag = create_tag(&test, "Project A").await;
+  assert_eq!(tag.name, "Project A");
+
+  // The names of the tags are unique
+  let error = EventBuilder::new(test.clone())
+    .event(CreateTag)
+    .payload(CreateTagPB {
+      name: "project a".to_string(),
+      color: "".to_string(),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_some());
+
+  EventBuilder::new(test.clone())
+    .event(UpdateTag)
+    .payload(UpdateTagPB {
+      tag_id: tag.id.clone(),
+      name: Some("Project B".to_string()),
+      color: None,
+    })
+    .async_send()
+    .await;
+  let tags = get_tags(&test).await;
+  assert_eq!(tags.len(), 1);
+  assert_eq!(tags[0].name, "Project B");
+  assert_eq!(tags[0].color, "#FF0000");
+}
+
+#[tokio::test]
+async fn tag_views_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let tag = create_tag(&test, "Roadmap").await;
+  let document_1 = test.create_document("Q1").await;
+  let document_2 = test.create_document("Q2").await;
+
+  set_view_tags(&test, &document_1.id, vec![tag.id.clone()]).await;
+  set_view_tags(&test, &document_2.id, vec![tag.id.clone()]).await;
+  let view_ids = get_view_ids_with_tag(&test, &tag.id).await;
+  assert_eq!(view_ids.len(), 2);
+  assert!(view_ids.contains(&document_1.id));
+  assert_eq!(
+    get_view_tag_ids(&test, &document_1.id).await,
+    vec![tag.id.clone()]
+  );
+
+  // The tags are not stored in the extra of the view, which is edited by the other devices
+  let view = test.get_view(&document_1.id).await;
+  assert!(!view.extra.unwrap_or_default().contains(&tag.id));
+
+  // The views in the trash are not listed
+  test.delete_view(&document_2.id).await;
+  assert_eq!(
+    get_view_ids_with_tag(&test, &tag.id).await,
+    vec![document_1.id.clone()]
+  );
+
+  EventBuilder::new(test.clone())
+    .event(DeleteTag)
+    .payload(TagIdPB {
+      tag_id: tag.id.clone(),
+    })
+    .async_send()
+    .await;
+  assert!(get_tags(&test).await.is_empty());
+  assert!(get_view_ids_with_tag(&test, &tag.id).await.is_empty());
+}
+
+#[tokio::test]
+async fn merge_tags_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let design = create_tag(&test, "Design").await;
+  let ux = create_tag(&test, "UX").await;
+  let document_1 = test.create_document("Wireframes").await;
+  let document_2 = test.create_document("Research").await;
+  set_view_tags(&test, &document_1.id, vec![design.id.clone()]).await;
+  set_view_tags(
+    &test,
+    &document_2.id,
+    vec![design.id.clone(), ux.id.clone()],
+  )
+  .await;
+
+  EventBuilder::new(test.clone())
+    .event(MergeTags)
+    .payload(MergeTagsPB {
+      source_tag_ids: vec![ux.id.clone()],
+      target_tag_id: design.id.clone(),
+    })
+    .async_send()
+    .await;
+
+  let tags = get_tags(&test).await;
+  assert_eq!(tags.len(), 1);
+  assert_eq!(tags[0].id, design.id);
+  assert_eq!(get_view_ids_with_tag(&test, &design.id).await.len(), 2);
+  assert!(get_view_ids_with_tag(&test, &ux.id).await.is_empty());
+  assert_eq!(
+    get_view_tag_ids(&test, &document_2.id).await,
+    vec![design.id.clone()]
+  );
+}
+
+#[test]
+fn merge_tags_created_on_two_devices_test() {
+  // Both devices create the first tag of the workspace at the same time.
+  let mut collab_1 = Collab::new(1, "folder", "device_1", vec![], false);
+  let mut collab_2 = Collab::new(2, "folder", "device_2", vec![], false);
+  for (collab, tag_id) in [(&mut collab_1, "tag_1"), (&mut collab_2, "tag_2")] {
+    let tag = ViewTag {
+      id: tag_id.to_string(),
+      name: tag_id.to_string(),
+      color: "#FF0000".to_string(),
+    };
+    write_workspace_tags(collab, &[], &[tag]).unwrap();
+  }
+
+  let update_1 = collab_1
+    .transact()
+    .encode_state_as_update_v1(&StateVector::default());
+  let update_2 = collab_2
+    .transact()
+    .encode_state_as_update_v1(&StateVector::default());
+  collab_1
+    .transact_mut()
+    .apply_update(Update::decode_v1(&update_2).unwrap())
+    .unwrap();
+  collab_2
+    .transact_mut()
+    .apply_update(Update::decode_v1(&update_1).unwrap())
+    .unwrap();
+
+  for collab in [&collab_1, &collab_2] {
+    let tag_ids = workspace_tags(collab)
+      .into_iter()
+      .map(|tag| tag.id)
+      .collect::<Vec<_>>();
+    assert_eq!(tag_ids, vec!["tag_1", "tag_2"]);
+  }
+}
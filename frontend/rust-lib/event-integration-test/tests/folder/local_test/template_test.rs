@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use event_integration_test::document::document_event::DocumentEventTest;
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{
+  CellIdPB, FieldType, RelationCellChangesetPB, RelationTypeOptionPB, TypeOptionChangesetPB,
+};
+use flowy_database2::event_map::DatabaseEvent;
+use flowy_folder::entities::{
+  CreateViewFromTemplatePB, RenameViewTemplatePB, RepeatedViewTemplatePB, SaveViewTemplatePB,
+  ViewLayoutPB, ViewPB, ViewTemplateIdPB, ViewTemplatePB,
+};
+use flowy_folder::event_map::FolderEvent::*;
+use flowy_user::entities::UpdateUserProfilePayloadPB;
+use lib_dispatch::prelude::ToBytes;
+
+async fn save_template(test: &EventIntegrationTest, view_id: &str) -> ViewTemplatePB {
+  EventBuilder::new(test.clone())
+    .event(SaveViewAsTemplate)
+    .payload(SaveViewTemplatePB {
+      view_id: view_id.to_string(),
+      name: None,
+      include_rows: true,
+    })
+    .async_send()
+    .await
+    .parse::<ViewTemplatePB>()
+}
+
+async fn get_templates(test: &EventIntegrationTest) -> Vec<ViewTemplatePB> {
+  EventBuilder::new(test.clone())
+    .event(GetViewTemplates)
+    .async_send()
+    .await
+    .parse::<RepeatedViewTemplatePB>()
+    .items
+}
+
+#[tokio::test]
+async fn create_view_from_template_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let profile = test.get_user_profile().await.unwrap();
+  test
+    .update_user_profile(UpdateUserProfilePayloadPB::new(profile.id).name("Lucas"))
+    .await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let document = test.create_document("Meeting {{date}}").await;
+  test
+    .insert_document_text(&document.id, "Notes by {{user}}", 0)
+    .await;
+  let grid = test
+    .create_view_with_layout(&document.id, "Tasks".to_string(), ViewLayoutPB::Grid)
+    .await;
+  let row_count = test.get_database(&grid.id).await.rows.len();
+
+  let template = save_template(&test, &document.id).await;
+  assert_eq!(template.name, "Meeting {{date}}");
+  assert_eq!(template.view_count, 2);
+  assert_eq!(template.layout, ViewLayoutPB::Document);
+
+  let view = EventBuilder::new(test.clone())
+    .event(CreateViewFromTemplate)
+    .payload(CreateViewFromTemplatePB {
+      template_id: template.id,
+      parent_view_id: workspace_id.clone(),
+      // The user defaults to the name of the current user
+      variables: HashMap::new(),
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>();
+  assert_ne!(view.id, document.id);
+  assert_eq!(view.parent_view_id, workspace_id);
+  assert!(view.name.starts_with("Meeting "));
+  assert!(!view.name.contains("{{"));
+
+  let texts = test
+    .get_document_data(&view.id)
+    .await
+    .meta
+    .text_map
+    .unwrap_or_default();
+  assert!(texts.values().any(|delta| delta.contains("Notes by Lucas")));
+
+  assert_eq!(view.child_views.len(), 1);
+  let child = &view.child_views[0];
+  assert_ne!(child.id, grid.id);
+  assert_eq!(child.name, "Tasks");
+  assert_eq!(child.layout, ViewLayoutPB::Grid);
+  assert_eq!(test.get_database(&child.id).await.rows.len(), row_count);
+}
+
+async fn create_view_from_template(
+  test: &EventIntegrationTest,
+  template_id: String,
+  parent_view_id: &str,
+) -> ViewPB {
+  EventBuilder::new(test.clone())
+    .event(CreateViewFromTemplate)
+    .payload(CreateViewFromTemplatePB {
+      template_id,
+      parent_view_id: parent_view_id.to_string(),
+      variables: HashMap::new(),
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>()
+}
+
+#[tokio::test]
+async fn create_view_from_template_remaps_mentions_and_relations_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let document = test.create_document("Project").await;
+  let tasks = test
+    .create_view_with_layout(&document.id, "Tasks".to_string(), ViewLayoutPB::Grid)
+    .await;
+  let plans = test
+    .create_view_with_layout(&document.id, "Plans".to_string(), ViewLayoutPB::Grid)
+    .await;
+
+  // The document mentions the tasks.
+  let document_event = DocumentEventTest::new_with_core(test.clone());
+  let block_id = document_event.insert_index(&document.id, "", 1, None).await;
+  let delta = serde_json::json!([{
+    "insert": "$",
+    "attributes": { "mention": { "type": "page", "page_id": tasks.id } }
+  }]);
+  document_event
+    .apply_delta_for_block(&document.id, &block_id, delta.to_string())
+    .await;
+
+  // The first plan is related to the first task.
+  let tasks_database = test.get_database(&tasks.id).await;
+  let plans_database = test.get_database(&plans.id).await;
+  let relation_field = test.create_field(&plans.id, FieldType::Relation).await;
+  let type_option = RelationTypeOptionPB {
+    database_id: tasks_database.id.clone(),
+  };
+  let error = EventBuilder::new(test.clone())
+    .event(DatabaseEvent::UpdateFieldTypeOption)
+    .payload(TypeOptionChangesetPB {
+      view_id: plans.id.clone(),
+      field_id: relation_field.id.clone(),
+      type_option_data: type_option.into_bytes().unwrap().to_vec(),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+  test
+    .update_relation_cell(RelationCellChangesetPB {
+      view_id: plans.id.clone(),
+      cell_id: CellIdPB {
+        view_id: plans.id.clone(),
+        field_id: relation_field.id.clone(),
+        row_id: plans_database.rows[0].id.clone(),
+      },
+      inserted_row_ids: vec![tasks_database.rows[0].id.clone()],
+      ..Default::default()
+    })
+    .await;
+
+  let template = save_template(&test, &document.id).await;
+  assert_eq!(template.view_count, 3);
+  let view = create_view_from_template(&test, template.id, &workspace_id).await;
+  let new_tasks = &view.child_views[0];
+  let new_plans = &view.child_views[1];
+  assert_eq!(new_tasks.name, "Tasks");
+  assert_eq!(new_plans.name, "Plans");
+
+  // The mention points to the new tasks.
+  let texts = test
+    .get_document_data(&view.id)
+    .await
+    .meta
+    .text_map
+    .unwrap_or_default();
+  assert!(texts.values().any(|delta| delta.contains(&new_tasks.id)));
+  assert!(!texts.values().any(|delta| delta.contains(&tasks.id)));
+
+  // The relation points to the new database and its rows.
+  let new_tasks_database = test.get_database(&new_tasks.id).await;
+  assert_ne!(new_tasks_database.id, tasks_database.id);
+  let new_relation_field = test
+    .get_all_database_fields(&new_plans.id)
+    .await
+    .items
+    .into_iter()
+    .find(|field| field.field_type == FieldType::Relation)
+    .unwrap();
+  let type_option =
+    RelationTypeOptionPB::try_from(Bytes::from(new_relation_field.type_option_data)).unwrap();
+  assert_eq!(type_option.database_id, new_tasks_database.id);
+
+  let new_plans_database = test.get_database(&new_plans.id).await;
+  let cell = test
+    .get_relation_cell(
+      &new_plans.id,
+      &new_relation_field.id,
+      &new_plans_database.rows[0].id,
+    )
+    .await;
+  assert_eq!(cell.row_ids, vec![new_tasks_database.rows[0].id.clone()]);
+}
+
+#[tokio::test]
+async fn rename_and_delete_template_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let document = test.create_document("Weekly report").await;
+  let template = save_template(&test, &document.id).await;
+  assert_eq!(get_templates(&test).await.len(), 1);
+
+  EventBuilder::new(test.clone())
+    .event(RenameViewTemplate)
+    .payload(RenameViewTemplatePB {
+      template_id: template.id.clone(),
+      name: "Report".to_string(),
+    })
+    .async_send()
+    .await;
+  assert_eq!(get_templates(&test).await[0].name, "Report");
+
+  EventBuilder::new(test.clone())
+    .event(DeleteViewTemplate)
+    .payload(ViewTemplateIdPB {
+      template_id: template.id.clone(),
+    })
+    .async_send()
+    .await;
+  assert!(get_templates(&test).await.is_empty());
+
+  let error = EventBuilder::new(test.clone())
+    .event(DeleteViewTemplate)
+    .payload(ViewTemplateIdPB {
+      template_id: template.id,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_some());
+}
@@ -0,0 +1,147 @@
+use collab::core::origin::CollabOrigin;
+use collab::preclude::Collab;
+use collab_folder::{Folder, FolderData, Workspace};
+use collab_plugins::local_storage::kv::doc::CollabKVAction;
+use collab_plugins::local_storage::kv::KVTransactionDB;
+use collab_plugins::CollabKVDB;
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_folder::entities::{TransferViewToWorkspacePB, ViewLayoutPB, ViewPB};
+use flowy_folder::event_map::FolderEvent;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Writes the folder of a new workspace into the collab db, as if the workspace was opened before.
+fn create_workspace_folder(uid: i64, db: &CollabKVDB, workspace_id: &str) {
+  let collab = Collab::new_with_origin(CollabOrigin::Empty, workspace_id, vec![], false);
+  let workspace = Workspace::new(workspace_id.to_string(), "Second".to_string(), uid);
+  let folder = Folder::create(uid, collab, None, FolderData::new(workspace));
+  let encoded_collab = folder.encode_collab().unwrap();
+  let write_txn = db.write_txn();
+  write_txn
+    .flush_doc(
+      uid,
+      workspace_id,
+      workspace_id,
+      encoded_collab.state_vector.to_vec(),
+      encoded_collab.doc_state.to_vec(),
+    )
+    .unwrap();
+  write_txn.commit_transaction().unwrap();
+}
+
+fn is_collab_exist(db: &Arc<CollabKVDB>, uid: i64, workspace_id: &str, object_id: &str) -> bool {
+  db.read_txn().is_exist(uid, workspace_id, object_id)
+}
+
+#[tokio::test]
+async fn move_view_to_workspace_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let uid = test.get_user_profile().await.unwrap().id;
+  let workspace_id = test.get_current_workspace().await.id;
+  let db = test
+    .user_manager
+    .get_collab_db(uid)
+    .unwrap()
+    .upgrade()
+    .unwrap();
+  let document = test
+    .create_and_open_document(&workspace_id, "Notes".to_string(), vec![])
+    .await;
+  test
+    .insert_document_text(&document.id, "The tasks", 0)
+    .await;
+  let grid = test
+    .create_view_with_layout(&document.id, "Tasks".to_string(), ViewLayoutPB::Grid)
+    .await;
+  let database = test.get_database(&grid.id).await;
+  let row_ids = database
+    .rows
+    .iter()
+    .map(|row| row.id.clone())
+    .collect::<Vec<_>>();
+  assert!(!row_ids.is_empty());
+
+  let target_workspace_id = Uuid::new_v4().to_string();
+  create_workspace_folder(uid, &db, &target_workspace_id);
+  let moved = EventBuilder::new(test.clone())
+    .event(FolderEvent::MoveViewToWorkspace)
+    .payload(TransferViewToWorkspacePB {
+      view_id: document.id.clone(),
+      workspace_id: target_workspace_id.clone(),
+      parent_view_id: None,
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>();
+
+  // the moved views get new ids in the target workspace
+  assert_ne!(moved.id, document.id);
+  assert_eq!(moved.parent_view_id, target_workspace_id);
+  assert_eq!(moved.child_views.len(), 1);
+  assert_ne!(moved.child_views[0].id, grid.id);
+  assert!(is_collab_exist(&db, uid, &target_workspace_id, &moved.id));
+  assert!(!is_collab_exist(
+    &db,
+    uid,
+    &target_workspace_id,
+    &document.id
+  ));
+
+  // the views and their collabs are removed from the current workspace
+  let views = test.get_all_workspace_views().await;
+  assert!(views
+    .iter()
+    .all(|view| view.id != document.id && view.id != grid.id));
+  assert!(!is_collab_exist(&db, uid, &workspace_id, &document.id));
+  assert!(!is_collab_exist(&db, uid, &workspace_id, &database.id));
+  for row_id in &row_ids {
+    assert!(!is_collab_exist(&db, uid, &workspace_id, row_id));
+  }
+}
+
+#[tokio::test]
+async fn move_view_to_missing_parent_view_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let uid = test.get_user_profile().await.unwrap().id;
+  let workspace_id = test.get_current_workspace().await.id;
+  let db = test
+    .user_manager
+    .get_collab_db(uid)
+    .unwrap()
+    .upgrade()
+    .unwrap();
+  let document = test
+    .create_and_open_document(&workspace_id, "Notes".to_string(), vec![])
+    .await;
+
+  let target_workspace_id = Uuid::new_v4().to_string();
+  create_workspace_folder(uid, &db, &target_workspace_id);
+  let error = EventBuilder::new(test.clone())
+    .event(FolderEvent::MoveViewToWorkspace)
+    .payload(TransferViewToWorkspacePB {
+      view_id: document.id.clone(),
+      workspace_id: target_workspace_id.clone(),
+      parent_view_id: Some(Uuid::new_v4().to_string()),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_some());
+
+  // nothing is written into the target workspace and the view stays in the current one
+  let object_ids = db
+    .read_txn()
+    .get_all_object_ids(uid, &target_workspace_id)
+    .map(|iter| iter.collect::<Vec<String>>())
+    .unwrap_or_default();
+  assert_eq!(object_ids, vec![target_workspace_id.clone()]);
+  assert!(test
+    .get_all_workspace_views()
+    .await
+    .iter()
+    .any(|view| view.id == document.id));
+  assert!(is_collab_exist(&db, uid, &workspace_id, &document.id));
+}
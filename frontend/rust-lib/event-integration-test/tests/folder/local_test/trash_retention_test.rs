@@ -0,0 +1,117 @@
+use collab_plugins::local_storage::kv::doc::CollabKVAction;
+use collab_plugins::local_storage::kv::KVTransactionDB;
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_folder::entities::{PreviewTrashPurgePB, TrashPurgePreviewPB, TrashRetentionPB};
+use flowy_folder::event_map::FolderEvent::*;
+
+async fn get_retention(test: &EventIntegrationTest) -> i64 {
+  EventBuilder::new(test.clone())
+    .event(GetTrashRetention)
+    .async_send()
+    .await
+    .parse::<TrashRetentionPB>()
+    .days
+}
+
+async fn preview_purge(test: &EventIntegrationTest, days: Option<i64>) -> TrashPurgePreviewPB {
+  EventBuilder::new(test.clone())
+    .event(PreviewTrashPurge)
+    .payload(PreviewTrashPurgePB { days })
+    .async_send()
+    .await
+    .parse::<TrashPurgePreviewPB>()
+}
+
+#[tokio::test]
+async fn set_trash_retention_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  assert_eq!(get_retention(&test).await, 0);
+
+  EventBuilder::new(test.clone())
+    .event(SetTrashRetention)
+    .payload(TrashRetentionPB { days: 30 })
+    .async_send()
+    .await;
+  assert_eq!(get_retention(&test).await, 30);
+
+  let error = EventBuilder::new(test.clone())
+    .event(SetTrashRetention)
+    .payload(TrashRetentionPB { days: -1 })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_some());
+  assert_eq!(get_retention(&test).await, 30);
+}
+
+#[tokio::test]
+async fn preview_trash_purge_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let document = test.create_document("Old notes").await;
+  test
+    .insert_document_text(&document.id, "Some notes", 0)
+    .await;
+  test.delete_view(&document.id).await;
+
+  // Nothing is purged while the workspace keeps the trash forever.
+  assert!(preview_purge(&test, None).await.items.is_empty());
+
+  let preview = preview_purge(&test, Some(0)).await;
+  assert_eq!(preview.items.len(), 1);
+  assert_eq!(preview.items[0].id, document.id);
+  assert_eq!(preview.items[0].view_count, 1);
+  assert!(preview.items[0].size > 0);
+  assert_eq!(preview.total_size, preview.items[0].size);
+
+  // The document was deleted just now, so a retention of a day doesn't purge it.
+  EventBuilder::new(test.clone())
+    .event(SetTrashRetention)
+    .payload(TrashRetentionPB { days: 1 })
+    .async_send()
+    .await;
+  assert!(preview_purge(&test, None).await.items.is_empty());
+  assert_eq!(test.folder_manager.purge_expired_trash().await.unwrap(), 0);
+  assert_eq!(test.get_trash().await.items.len(), 1);
+}
+
+#[tokio::test]
+async fn purge_expired_trash_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let uid = test.get_user_profile().await.unwrap().id;
+  let workspace_id = test.get_current_workspace().await.id;
+  let document = test.create_document("Old notes").await;
+  test
+    .insert_document_text(&document.id, "Some notes", 0)
+    .await;
+  let grid = test
+    .create_grid(&workspace_id, "Old tasks".to_string(), vec![])
+    .await;
+  let database = test.get_database(&grid.id).await;
+  assert!(!database.rows.is_empty());
+  test.delete_view(&document.id).await;
+  test.delete_view(&grid.id).await;
+
+  let object_ids = std::iter::once(document.id.clone())
+    .chain(std::iter::once(database.id.clone()))
+    .chain(database.rows.iter().map(|row| row.id.clone()))
+    .collect::<Vec<_>>();
+  let db = test
+    .user_manager
+    .get_collab_db(uid)
+    .unwrap()
+    .upgrade()
+    .unwrap();
+  for object_id in &object_ids {
+    assert!(db.read_txn().is_exist(uid, &workspace_id, object_id));
+  }
+
+  assert_eq!(test.folder_manager.purge_trash(0).await.unwrap(), 2);
+  assert!(test.get_trash().await.items.is_empty());
+  for object_id in &object_ids {
+    assert!(!db.read_txn().is_exist(uid, &workspace_id, object_id));
+  }
+}
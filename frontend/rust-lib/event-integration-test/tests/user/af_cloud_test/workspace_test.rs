@@ -2,8 +2,11 @@ use collab::core::collab::DataSource::DocStateV1;
 use collab::core::origin::CollabOrigin;
 use collab_entity::CollabType;
 use collab_folder::Folder;
+use event_integration_test::event_builder::EventBuilder;
 use event_integration_test::user_event::use_localhost_af_cloud;
 use event_integration_test::EventIntegrationTest;
+use flowy_folder::entities::{TransferViewToWorkspacePB, ViewLayoutPB, ViewPB};
+use flowy_folder::event_map::FolderEvent;
 use std::time::Duration;
 use tokio::task::LocalSet;
 use tokio::time::sleep;
@@ -249,3 +252,77 @@ async fn af_cloud_different_open_same_workspace_test() {
   assert_eq!(views.len(), 2, "only get: {:?}", views); // Expecting two views.
   assert_eq!(views[0].name, "General");
 }
+
+async fn transfer_view_to_workspace(
+  test: &EventIntegrationTest,
+  view_id: &str,
+  workspace_id: &str,
+  event: FolderEvent,
+) -> ViewPB {
+  EventBuilder::new(test.clone())
+    .event(event)
+    .payload(TransferViewToWorkspacePB {
+      view_id: view_id.to_string(),
+      workspace_id: workspace_id.to_string(),
+      parent_view_id: None,
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>()
+}
+
+#[tokio::test]
+async fn af_cloud_copy_and_move_view_to_workspace_test() {
+  use_localhost_af_cloud().await;
+  let test = EventIntegrationTest::new().await;
+  let _ = test.af_cloud_sign_up().await;
+  let first_workspace = test.get_current_workspace().await;
+  let document = test.create_document("A").await;
+  let grid = test
+    .create_view_with_layout(&document.id, "Tasks".to_string(), ViewLayoutPB::Grid)
+    .await;
+  let row_count = test.get_database(&grid.id).await.rows.len();
+  let second_workspace = test.create_workspace("second workspace").await;
+
+  // the copy gets new ids and the views stay in the first workspace
+  let copied = transfer_view_to_workspace(
+    &test,
+    &document.id,
+    &second_workspace.workspace_id,
+    FolderEvent::CopyViewToWorkspace,
+  )
+  .await;
+  assert_ne!(copied.id, document.id);
+  assert_eq!(copied.parent_view_id, second_workspace.workspace_id);
+  assert_eq!(copied.child_views.len(), 1);
+  assert_ne!(copied.child_views[0].id, grid.id);
+
+  // the move also gets new ids and removes the views from the first workspace
+  let moved = transfer_view_to_workspace(
+    &test,
+    &document.id,
+    &second_workspace.workspace_id,
+    FolderEvent::MoveViewToWorkspace,
+  )
+  .await;
+  assert_ne!(moved.id, document.id);
+  assert_ne!(moved.child_views[0].id, grid.id);
+  let views = test.get_all_workspace_views().await;
+  assert!(views.iter().all(|view| view.id != document.id));
+
+  test.open_workspace(&second_workspace.workspace_id).await;
+  let views = test.get_all_workspace_views().await;
+  assert!(views.iter().any(|view| view.id == copied.id));
+  assert!(views.iter().any(|view| view.id == moved.id));
+  let copied_grid_id = &copied.child_views[0].id;
+  assert_eq!(
+    test.get_database(copied_grid_id).await.rows.len(),
+    row_count
+  );
+  let moved_grid_id = &moved.child_views[0].id;
+  assert_eq!(test.get_database(moved_grid_id).await.rows.len(), row_count);
+
+  test.open_workspace(&first_workspace.id).await;
+  let views = test.get_all_workspace_views().await;
+  assert!(views.iter().all(|view| view.id != document.id));
+}
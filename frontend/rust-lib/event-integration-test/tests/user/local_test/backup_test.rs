@@ -0,0 +1,198 @@
+use std::path::Path;
+
+use collab_plugins::local_storage::kv::doc::CollabKVAction;
+use collab_plugins::local_storage::kv::KVTransactionDB;
+use collab_plugins::CollabKVDB;
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_core::integrate::backup::BackupManager;
+use flowy_user::entities::{BackupSettingPB, RepeatedUserBackupPB, RestoreBackupPB, UserBackupPB};
+use flowy_user::errors::ErrorCode;
+use flowy_user::event_map::UserEvent::*;
+
+async fn create_backup(test: &EventIntegrationTest) -> UserBackupPB {
+  EventBuilder::new(test.clone())
+    .event(CreateBackup)
+    .async_send()
+    .await
+    .parse::<UserBackupPB>()
+}
+
+async fn list_backups(test: &EventIntegrationTest) -> Vec<UserBackupPB> {
+  EventBuilder::new(test.clone())
+    .event(ListBackups)
+    .async_send()
+    .await
+    .parse::<RepeatedUserBackupPB>()
+    .items
+}
+
+#[tokio::test]
+async fn create_and_list_backups_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  assert!(list_backups(&test).await.is_empty());
+
+  let backup = create_backup(&test).await;
+  assert!(backup.size > 0);
+  assert!(!backup.encrypted);
+
+  let backups = list_backups(&test).await;
+  assert_eq!(backups.len(), 1);
+  assert_eq!(backups[0].backup_id, backup.backup_id);
+}
+
+#[tokio::test]
+async fn backup_setting_test() {
+  let test = EventIntegrationTest::new().await;
+  test.init_anon_user().await;
+  let setting = EventBuilder::new(test.clone())
+    .event(GetBackupSetting)
+    .async_send()
+    .await
+    .parse::<BackupSettingPB>();
+  assert!(!setting.enabled);
+
+  EventBuilder::new(test.clone())
+    .event(SetBackupSetting)
+    .payload(BackupSettingPB {
+      enabled: true,
+      interval_hours: 12,
+      max_backups: 3,
+      encrypt: false,
+    })
+    .async_send()
+    .await;
+  let setting = EventBuilder::new(test.clone())
+    .event(GetBackupSetting)
+    .async_send()
+    .await
+    .parse::<BackupSettingPB>();
+  assert!(setting.enabled);
+  assert_eq!(setting.interval_hours, 12);
+  assert_eq!(setting.max_backups, 3);
+}
+
+#[tokio::test]
+async fn restore_backup_test() {
+  let test = EventIntegrationTest::new().await;
+  let user = test.init_anon_user().await;
+  let backup = create_backup(&test).await;
+
+  let error = EventBuilder::new(test.clone())
+    .event(RestoreBackup)
+    .payload(RestoreBackupPB {
+      backup_id: backup.backup_id.clone(),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let backup_dir = Path::new(&test.appflowy_core.config.storage_path)
+    .join("backups")
+    .join(user.id.to_string());
+  assert!(backup_dir.join("pending_restore.json").exists());
+  assert!(backup_dir.join("pending_restore").join("user").is_dir());
+}
+
+#[tokio::test]
+async fn apply_pending_restore_test() {
+  let test = EventIntegrationTest::new().await;
+  let user = test.init_anon_user().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let before = test.create_document("Before").await;
+  let backup = create_backup(&test).await;
+  let after = test.create_document("After").await;
+
+  let error = EventBuilder::new(test.clone())
+    .event(RestoreBackup)
+    .payload(RestoreBackupPB {
+      backup_id: backup.backup_id,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  // The restore is applied on next launch, before the databases are opened.
+  let storage_path = test.appflowy_core.config.storage_path.clone();
+  BackupManager::apply_pending_restores(&storage_path);
+  let backup_dir = Path::new(&storage_path)
+    .join("backups")
+    .join(user.id.to_string());
+  assert!(!backup_dir.join("pending_restore.json").exists());
+  assert!(backup_dir
+    .join("before_restore")
+    .join("user")
+    .join("collab_db")
+    .is_dir());
+
+  // The data of the user is the data at the time of the backup.
+  let user_data_dir = Path::new(&storage_path).join(user.id.to_string());
+  assert!(user_data_dir.join("flowy-database.db").is_file());
+  let collab_db = CollabKVDB::open(user_data_dir.join("collab_db")).unwrap();
+  let read_txn = collab_db.read_txn();
+  assert!(read_txn.is_exist(user.id, &workspace_id, &workspace_id));
+  assert!(read_txn.is_exist(user.id, &workspace_id, &before.id));
+  assert!(!read_txn.is_exist(user.id, &workspace_id, &after.id));
+}
+
+#[tokio::test]
+async fn restore_backup_keeps_shared_files_test() {
+  let test = EventIntegrationTest::new().await;
+  let user = test.init_anon_user().await;
+  // The upload cache of the storage path is shared by all the users.
+  let storage_path = test.appflowy_core.config.storage_path.clone();
+  let cache_dir = Path::new(&storage_path).join("cache_files");
+  std::fs::create_dir_all(&cache_dir).unwrap();
+  std::fs::write(cache_dir.join("before.png"), b"before").unwrap();
+  let backup = create_backup(&test).await;
+  std::fs::write(cache_dir.join("after.png"), b"after").unwrap();
+
+  let error = EventBuilder::new(test.clone())
+    .event(RestoreBackup)
+    .payload(RestoreBackupPB {
+      backup_id: backup.backup_id,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+  BackupManager::apply_pending_restores(&storage_path);
+
+  // The files that don't belong to the user are neither backed up nor replaced.
+  assert!(cache_dir.join("before.png").is_file());
+  assert!(cache_dir.join("after.png").is_file());
+  let backup_dir = Path::new(&storage_path)
+    .join("backups")
+    .join(user.id.to_string());
+  assert!(!backup_dir
+    .join("before_restore")
+    .join("cache_files")
+    .exists());
+}
+
+#[tokio::test]
+async fn restore_corrupted_backup_test() {
+  let test = EventIntegrationTest::new().await;
+  let user = test.init_anon_user().await;
+  let backup = create_backup(&test).await;
+
+  let archive_path = Path::new(&test.appflowy_core.config.storage_path)
+    .join("backups")
+    .join(user.id.to_string())
+    .join(format!("{}.zip", backup.backup_id));
+  std::fs::write(&archive_path, b"corrupted").unwrap();
+
+  let error = EventBuilder::new(test.clone())
+    .event(RestoreBackup)
+    .payload(RestoreBackupPB {
+      backup_id: backup.backup_id,
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::BackupCorrupted);
+}
@@ -1,4 +1,5 @@
 mod auth_test;
+mod backup_test;
 mod helper;
 mod import_af_data_local_test;
 mod user_awareness_test;
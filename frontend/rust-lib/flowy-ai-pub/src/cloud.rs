@@ -83,6 +83,13 @@ pub trait ChatCloudService: Send + Sync + 'static {
     complete_type: CompletionType,
   ) -> Result<StreamComplete, FlowyError>;
 
+  /// Complete with a user-defined prompt. The prompt already contains the text to complete.
+  async fn stream_complete_with_prompt(
+    &self,
+    workspace_id: &str,
+    prompt: &str,
+  ) -> Result<StreamComplete, FlowyError>;
+
   async fn index_file(
     &self,
     workspace_id: &str,
@@ -1,10 +1,17 @@
 use crate::chat::Chat;
+use crate::chat_history::{ChatHistory, ChatMessageSearchResult};
+use crate::chat_source::ChatSources;
 use crate::entities::{
-  ChatInfoPB, ChatMessageListPB, ChatMessagePB, FilePB, RepeatedRelatedQuestionPB,
+  ChatInfoPB, ChatMessageBranchesPB, ChatMessageListPB, ChatMessagePB, ChatThreadPB, FilePB,
+  RepeatedRelatedQuestionPB,
 };
 use crate::local_ai::local_llm_chat::LocalAIController;
 use crate::middleware::chat_service_mw::AICloudServiceMiddleware;
-use crate::persistence::{insert_chat, read_chat_metadata, ChatTable};
+use crate::openai_compatible::OpenAICompatibleProvider;
+use crate::persistence::{
+  insert_chat, read_chat_metadata, ChatMessageSearchQuery, ChatSourceTable, ChatTable,
+};
+use crate::prompt_library::PromptLibrary;
 
 use appflowy_plugin::manager::PluginManager;
 use dashmap::DashMap;
@@ -14,10 +21,11 @@ use flowy_sqlite::kv::KVStorePreferences;
 use flowy_sqlite::DBConnection;
 
 use flowy_storage_pub::storage::StorageService;
+use lib_infra::async_trait::async_trait;
 use lib_infra::util::timestamp;
 use std::path::PathBuf;
 use std::sync::{Arc, Weak};
-use tracing::{info, trace};
+use tracing::{error, info, trace};
 
 pub trait AIUserService: Send + Sync + 'static {
   fn user_id(&self) -> Result<i64, FlowyError>;
@@ -25,6 +33,52 @@ pub trait AIUserService: Send + Sync + 'static {
   fn workspace_id(&self) -> Result<String, FlowyError>;
   fn sqlite_connection(&self, uid: i64) -> Result<DBConnection, FlowyError>;
   fn application_root_dir(&self) -> Result<PathBuf, FlowyError>;
+  /// The secret of the current user that encrypts the credentials stored on the device
+  fn encrypt_secret(&self) -> Result<String, FlowyError>;
+}
+
+/// The documents are managed by the folder and the document module. The chat uses this service to
+/// export its messages into a new document, where the content of the document is the JSON of the
+/// `NestedBlock` defined in flowy-document.
+#[async_trait]
+pub trait AIDocumentService: Send + Sync {
+  /// Create a document view under the parent view and returns the id of the new view.
+  async fn create_document_view(
+    &self,
+    parent_view_id: &str,
+    name: &str,
+    json: &str,
+  ) -> FlowyResult<String>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AIViewSourceType {
+  Document = 0,
+  Database = 1,
+}
+
+impl From<i32> for AIViewSourceType {
+  fn from(value: i32) -> Self {
+    match value {
+      1 => AIViewSourceType::Database,
+      _ => AIViewSourceType::Document,
+    }
+  }
+}
+
+/// The text of a view that is used as the retrieval source of a chat.
+pub struct AIViewContent {
+  pub name: String,
+  pub source_type: AIViewSourceType,
+  /// The id of the block or the row, and its text. The passages are in the order of the view.
+  pub passages: Vec<(String, String)>,
+}
+
+/// The views are managed by the folder, the document and the database module. The chat uses this
+/// service to read the content of the views that are attached to it.
+#[async_trait]
+pub trait AIWorkspaceSourceService: Send + Sync {
+  async fn get_view_content(&self, view_id: &str) -> FlowyResult<AIViewContent>;
 }
 
 pub struct AIManager {
@@ -32,6 +86,10 @@ pub struct AIManager {
   pub user_service: Arc<dyn AIUserService>,
   chats: Arc<DashMap<String, Arc<Chat>>>,
   pub local_ai_controller: Arc<LocalAIController>,
+  pub openai_compatible: Arc<OpenAICompatibleProvider>,
+  pub prompt_library: Arc<PromptLibrary>,
+  chat_history: ChatHistory,
+  chat_sources: ChatSources,
 }
 
 impl AIManager {
@@ -49,12 +107,21 @@ impl AIManager {
       user_service.clone(),
       chat_cloud_service.clone(),
     ));
+    let openai_compatible = Arc::new(OpenAICompatibleProvider::new(
+      user_service.clone(),
+      store_preferences,
+    ));
+
+    let prompt_library = Arc::new(PromptLibrary::new(user_service.clone()));
+    let chat_history = ChatHistory::new(user_service.clone());
+    let chat_sources = ChatSources::new(user_service.clone(), local_ai_controller.clone());
 
     // setup local chat service
     let cloud_service_wm = Arc::new(AICloudServiceMiddleware::new(
       user_service.clone(),
       chat_cloud_service,
       local_ai_controller.clone(),
+      openai_compatible.clone(),
       storage_service,
     ));
 
@@ -63,9 +130,21 @@ impl AIManager {
       user_service,
       chats: Arc::new(DashMap::new()),
       local_ai_controller,
+      openai_compatible,
+      prompt_library,
+      chat_history,
+      chat_sources,
     }
   }
 
+  pub fn set_document_service(&self, service: Arc<dyn AIDocumentService>) {
+    self.chat_history.set_document_service(service);
+  }
+
+  pub fn set_workspace_source_service(&self, service: Arc<dyn AIWorkspaceSourceService>) {
+    self.chat_sources.set_source_service(service);
+  }
+
   pub async fn initialize(&self, _workspace_id: &str) -> Result<(), FlowyError> {
     // Ignore following error
     let _ = self.local_ai_controller.refresh().await;
@@ -105,6 +184,7 @@ impl AIManager {
         self.local_ai_controller.close_chat(chat_id);
       }
     }
+    self.chat_sources.remove_all(chat_id)?;
     Ok(())
   }
 
@@ -153,6 +233,7 @@ impl AIManager {
     question_stream_port: i64,
     metadata: Vec<ChatMessageMetadata>,
   ) -> Result<ChatMessagePB, FlowyError> {
+    self.refresh_chat_sources(chat_id).await;
     let chat = self.get_or_create_chat_instance(chat_id).await?;
     let question = chat
       .stream_chat_message(
@@ -166,6 +247,52 @@ impl AIManager {
     Ok(question)
   }
 
+  pub async fn edit_question(
+    &self,
+    chat_id: &str,
+    question_message_id: i64,
+    message: &str,
+    answer_stream_port: i64,
+    question_stream_port: i64,
+    metadata: Vec<ChatMessageMetadata>,
+  ) -> Result<ChatMessagePB, FlowyError> {
+    self.refresh_chat_sources(chat_id).await;
+    let chat = self.get_or_create_chat_instance(chat_id).await?;
+    let question = chat
+      .edit_question(
+        question_message_id,
+        message,
+        answer_stream_port,
+        question_stream_port,
+        metadata,
+      )
+      .await?;
+    Ok(question)
+  }
+
+  pub async fn load_active_chat_thread(&self, chat_id: &str) -> FlowyResult<ChatThreadPB> {
+    let chat = self.get_or_create_chat_instance(chat_id).await?;
+    chat.load_active_thread()
+  }
+
+  pub async fn get_chat_message_branches(
+    &self,
+    chat_id: &str,
+    message_id: i64,
+  ) -> FlowyResult<ChatMessageBranchesPB> {
+    let chat = self.get_or_create_chat_instance(chat_id).await?;
+    chat.get_message_branches(message_id)
+  }
+
+  pub async fn switch_chat_branch(
+    &self,
+    chat_id: &str,
+    message_id: i64,
+  ) -> FlowyResult<ChatThreadPB> {
+    let chat = self.get_or_create_chat_instance(chat_id).await?;
+    chat.switch_branch(message_id)
+  }
+
   pub async fn get_or_create_chat_instance(&self, chat_id: &str) -> Result<Arc<Chat>, FlowyError> {
     let chat = self.chats.get(chat_id).as_deref().cloned();
     match chat {
@@ -247,6 +374,7 @@ impl AIManager {
   pub async fn stop_stream(&self, chat_id: &str) -> Result<(), FlowyError> {
     let chat = self.get_or_create_chat_instance(chat_id).await?;
     chat.stop_stream_message().await;
+    self.cloud_service_wm.stop_stream(chat_id);
     Ok(())
   }
 
@@ -256,6 +384,52 @@ impl AIManager {
     Ok(())
   }
 
+  pub fn search_chat_messages(
+    &self,
+    query: ChatMessageSearchQuery,
+  ) -> FlowyResult<Vec<ChatMessageSearchResult>> {
+    self.chat_history.search(query)
+  }
+
+  pub async fn export_chat_to_document(
+    &self,
+    chat_id: &str,
+    parent_view_id: &str,
+    name: Option<String>,
+  ) -> FlowyResult<String> {
+    self
+      .chat_history
+      .export_to_document(chat_id, parent_view_id, name)
+      .await
+  }
+
+  pub async fn attach_chat_source(
+    &self,
+    chat_id: &str,
+    view_id: &str,
+  ) -> FlowyResult<ChatSourceTable> {
+    self.chat_sources.attach(chat_id, view_id).await
+  }
+
+  pub async fn detach_chat_source(&self, chat_id: &str, view_id: &str) -> FlowyResult<()> {
+    self.chat_sources.detach(chat_id, view_id).await
+  }
+
+  pub fn get_chat_sources(&self, chat_id: &str) -> FlowyResult<Vec<ChatSourceTable>> {
+    self.chat_sources.list(chat_id)
+  }
+
+  /// Re-index the sources that have changed since they were indexed. The question is still sent
+  /// if the sources can't be refreshed.
+  async fn refresh_chat_sources(&self, chat_id: &str) {
+    if let Err(err) = self.chat_sources.refresh(chat_id).await {
+      error!(
+        "[Chat] failed to refresh the sources of chat:{}: {}",
+        chat_id, err
+      );
+    }
+  }
+
   pub fn local_ai_purchased(&self) {}
 }
 
@@ -1,6 +1,10 @@
 use crate::ai_manager::AIUserService;
+use crate::chat_branch::{
+  load_chat_message_tree, save_message_parent, set_active_message, ChatMessageTree, ROOT_PARENT_ID,
+};
 use crate::entities::{
-  ChatMessageErrorPB, ChatMessageListPB, ChatMessagePB, RepeatedRelatedQuestionPB,
+  ChatMessageBranchPB, ChatMessageBranchesPB, ChatMessageErrorPB, ChatMessageListPB, ChatMessagePB,
+  ChatThreadPB, RepeatedRelatedQuestionPB,
 };
 use crate::middleware::chat_service_mw::AICloudServiceMiddleware;
 use crate::notification::{make_notification, ChatNotification};
@@ -85,6 +89,77 @@ impl Chat {
     answer_stream_port: i64,
     question_stream_port: i64,
     metadata: Vec<ChatMessageMetadata>,
+  ) -> Result<ChatMessagePB, FlowyError> {
+    // The question follows the last message of the active branch
+    let parent_message_id = self
+      .load_message_tree()?
+      .active_path()
+      .last()
+      .map(|message| message.message_id)
+      .unwrap_or(ROOT_PARENT_ID);
+    self
+      .stream_question(
+        message,
+        message_type,
+        answer_stream_port,
+        question_stream_port,
+        metadata,
+        parent_message_id,
+      )
+      .await
+  }
+
+  /// Send the edited content of a question as a new question, which becomes a branch of the
+  /// original question. The original question and its answers are kept.
+  ///
+  /// Only the model endpoint is answered with the messages of the branch, so editing is rejected
+  /// for the cloud and the local AI.
+  #[instrument(level = "info", skip_all, err)]
+  pub async fn edit_question(
+    &self,
+    question_message_id: i64,
+    message: &str,
+    answer_stream_port: i64,
+    question_stream_port: i64,
+    metadata: Vec<ChatMessageMetadata>,
+  ) -> Result<ChatMessagePB, FlowyError> {
+    if !self.chat_service.is_branching_supported() {
+      return Err(
+        FlowyError::not_support()
+          .with_context("Editing a question is only supported by the model endpoint"),
+      );
+    }
+    let tree = self.load_message_tree()?;
+    let question = tree.get(question_message_id).ok_or_else(|| {
+      FlowyError::record_not_found()
+        .with_context(format!("Message:{} not found", question_message_id))
+    })?;
+    if question.reply_message_id.is_some() {
+      return Err(FlowyError::invalid_data().with_context("Only the questions can be edited"));
+    }
+    let parent_message_id = tree
+      .parent_of(question_message_id)
+      .unwrap_or(ROOT_PARENT_ID);
+    self
+      .stream_question(
+        message,
+        ChatMessageType::User,
+        answer_stream_port,
+        question_stream_port,
+        metadata,
+        parent_message_id,
+      )
+      .await
+  }
+
+  async fn stream_question(
+    &self,
+    message: &str,
+    message_type: ChatMessageType,
+    answer_stream_port: i64,
+    question_stream_port: i64,
+    metadata: Vec<ChatMessageMetadata>,
+    parent_message_id: i64,
   ) -> Result<ChatMessagePB, FlowyError> {
     if message.len() > 2000 {
       return Err(FlowyError::text_too_long().with_context("Exceeds maximum message 2000 length"));
@@ -141,6 +216,13 @@ impl Chat {
 
     // Save message to disk
     save_and_notify_message(uid, &self.chat_id, &self.user_service, question.clone())?;
+    save_message_parent(
+      self.user_service.as_ref(),
+      uid,
+      &self.chat_id,
+      question.message_id,
+      parent_message_id,
+    )?;
 
     let stop_stream = self.stop_stream.clone();
     let chat_id = self.chat_id.clone();
@@ -430,10 +512,73 @@ impl Chat {
       .await?;
 
     save_and_notify_message(self.uid, &self.chat_id, &self.user_service, answer.clone())?;
+    // The regenerated answer becomes the active branch of the question
+    set_active_message(
+      self.user_service.as_ref(),
+      self.uid,
+      &self.chat_id,
+      question_message_id,
+      answer.message_id,
+    )?;
     let pb = ChatMessagePB::from(answer);
     Ok(pb)
   }
 
+  fn load_message_tree(&self) -> FlowyResult<ChatMessageTree> {
+    load_chat_message_tree(self.user_service.as_ref(), self.uid, &self.chat_id)
+  }
+
+  /// Load the messages along the active branches, where the first message of the chat comes first.
+  /// Only the messages that are stored locally are loaded.
+  pub fn load_active_thread(&self) -> FlowyResult<ChatThreadPB> {
+    let tree = self.load_message_tree()?;
+    Ok(thread_from_tree(&tree))
+  }
+
+  /// Returns the branches of the message, including itself. The oldest branch comes first.
+  pub fn get_message_branches(&self, message_id: i64) -> FlowyResult<ChatMessageBranchesPB> {
+    let tree = self.load_message_tree()?;
+    if tree.get(message_id).is_none() {
+      return Err(
+        FlowyError::record_not_found().with_context(format!("Message:{} not found", message_id)),
+      );
+    }
+    let active_path = tree.active_path();
+    let siblings = tree
+      .siblings_of(message_id)
+      .into_iter()
+      .filter_map(|id| tree.get(id).cloned())
+      .collect::<Vec<_>>();
+    let active_message_id = siblings
+      .iter()
+      .find(|sibling| {
+        active_path
+          .iter()
+          .any(|message| message.message_id == sibling.message_id)
+      })
+      .map(|sibling| sibling.message_id);
+    Ok(ChatMessageBranchesPB {
+      messages: siblings.into_iter().map(ChatMessagePB::from).collect(),
+      active_message_id,
+    })
+  }
+
+  /// Make the message the active branch among its siblings and returns the new active thread.
+  pub fn switch_branch(&self, message_id: i64) -> FlowyResult<ChatThreadPB> {
+    let tree = self.load_message_tree()?;
+    let parent_message_id = tree.parent_of(message_id).ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!("Message:{} not found", message_id))
+    })?;
+    set_active_message(
+      self.user_service.as_ref(),
+      self.uid,
+      &self.chat_id,
+      parent_message_id,
+      message_id,
+    )?;
+    self.load_active_thread()
+  }
+
   async fn load_local_chat_messages(
     &self,
     limit: i64,
@@ -450,15 +595,7 @@ impl Chat {
     )?;
     let messages = records
       .into_iter()
-      .map(|record| ChatMessagePB {
-        message_id: record.message_id,
-        content: record.content,
-        created_at: record.created_at,
-        author_type: record.author_type,
-        author_id: record.author_id,
-        reply_message_id: record.reply_message_id,
-        metadata: record.metadata,
-      })
+      .map(ChatMessagePB::from)
       .collect::<Vec<_>>();
 
     Ok(messages)
@@ -503,6 +640,24 @@ impl Chat {
   }
 }
 
+fn thread_from_tree(tree: &ChatMessageTree) -> ChatThreadPB {
+  let path = tree.active_path();
+  let branches = path
+    .iter()
+    .filter_map(|message| {
+      let sibling_ids = tree.siblings_of(message.message_id);
+      (sibling_ids.len() > 1).then(|| ChatMessageBranchPB {
+        message_id: message.message_id,
+        sibling_ids,
+      })
+    })
+    .collect();
+  ChatThreadPB {
+    messages: path.into_iter().cloned().map(ChatMessagePB::from).collect(),
+    branches,
+  }
+}
+
 fn save_chat_message_disk(
   conn: DBConnection,
   chat_id: &str,
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use flowy_error::FlowyResult;
+use lib_infra::util::timestamp;
+
+use crate::ai_manager::AIUserService;
+use crate::persistence::{
+  insert_chat_message_branch, select_all_chat_messages, select_chat_active_branches,
+  select_chat_message_branches, upsert_chat_active_branch, ChatActiveBranchTable,
+  ChatMessageBranchTable, ChatMessageTable,
+};
+
+/// The parent id of the first messages of a chat.
+pub const ROOT_PARENT_ID: i64 = 0;
+
+/// The messages of a chat form a tree when the questions are edited or the answers are
+/// regenerated. The parent of a message is:
+/// - the question that an answer replies to.
+/// - the recorded parent of a question that is sent after branching is supported.
+/// - otherwise, the previous message of the chat, which keeps the existing chats linear.
+///
+/// The messages that share the same parent are the branches of each other. Each parent has an
+/// active child, which is the selected one or the latest one.
+pub struct ChatMessageTree {
+  messages: HashMap<i64, ChatMessageTable>,
+  parents: HashMap<i64, i64>,
+  children: HashMap<i64, Vec<i64>>,
+  active_children: HashMap<i64, i64>,
+}
+
+impl ChatMessageTree {
+  pub fn new(
+    mut messages: Vec<ChatMessageTable>,
+    branches: Vec<ChatMessageBranchTable>,
+    active_children: HashMap<i64, i64>,
+  ) -> Self {
+    messages.sort_by_key(|message| message.message_id);
+    let recorded_parents = branches
+      .into_iter()
+      .map(|branch| {
+        (
+          branch.message_id,
+          branch.parent_message_id.unwrap_or(ROOT_PARENT_ID),
+        )
+      })
+      .collect::<HashMap<_, _>>();
+
+    let mut parents = HashMap::new();
+    let mut children: HashMap<i64, Vec<i64>> = HashMap::new();
+    let mut previous = ROOT_PARENT_ID;
+    for message in &messages {
+      let parent = message
+        .reply_message_id
+        .or_else(|| recorded_parents.get(&message.message_id).copied())
+        .unwrap_or(previous);
+      parents.insert(message.message_id, parent);
+      children.entry(parent).or_default().push(message.message_id);
+      previous = message.message_id;
+    }
+
+    Self {
+      messages: messages
+        .into_iter()
+        .map(|message| (message.message_id, message))
+        .collect(),
+      parents,
+      children,
+      active_children,
+    }
+  }
+
+  pub fn get(&self, message_id: i64) -> Option<&ChatMessageTable> {
+    self.messages.get(&message_id)
+  }
+
+  /// Returns [ROOT_PARENT_ID] for the first messages of the chat.
+  pub fn parent_of(&self, message_id: i64) -> Option<i64> {
+    self.parents.get(&message_id).copied()
+  }
+
+  /// Returns the messages that share the same parent with the message, including itself. The
+  /// oldest message comes first.
+  pub fn siblings_of(&self, message_id: i64) -> Vec<i64> {
+    self
+      .parent_of(message_id)
+      .and_then(|parent| self.children.get(&parent))
+      .cloned()
+      .unwrap_or_default()
+  }
+
+  fn active_child_of(&self, parent: i64) -> Option<i64> {
+    let children = self.children.get(&parent)?;
+    match self.active_children.get(&parent) {
+      Some(child) if children.contains(child) => Some(*child),
+      _ => children.last().copied(),
+    }
+  }
+
+  /// Returns the active answer of the question, which is the selected or the latest regenerated
+  /// answer.
+  pub fn answer_of(&self, question_id: i64) -> Option<&ChatMessageTable> {
+    let answers = self
+      .children
+      .get(&question_id)?
+      .iter()
+      .filter_map(|id| self.messages.get(id))
+      .filter(|message| message.reply_message_id == Some(question_id))
+      .collect::<Vec<_>>();
+    let active_child = self.active_children.get(&question_id);
+    answers
+      .iter()
+      .find(|answer| Some(&answer.message_id) == active_child)
+      .or_else(|| answers.last())
+      .copied()
+  }
+
+  /// Returns the messages from the first message of the chat along the active children.
+  pub fn active_path(&self) -> Vec<&ChatMessageTable> {
+    let mut path = vec![];
+    let mut parent = ROOT_PARENT_ID;
+    while let Some(child) = self.active_child_of(parent) {
+      if let Some(message) = self.messages.get(&child) {
+        path.push(message);
+      }
+      parent = child;
+    }
+    path
+  }
+
+  /// Returns the messages from the first message of the chat to the given message, excluding the
+  /// given message.
+  pub fn ancestors_of(&self, message_id: i64) -> Vec<&ChatMessageTable> {
+    let mut path = vec![];
+    let mut current = message_id;
+    while let Some(parent) = self.parent_of(current) {
+      if parent == ROOT_PARENT_ID {
+        break;
+      }
+      if let Some(message) = self.messages.get(&parent) {
+        path.push(message);
+      }
+      current = parent;
+    }
+    path.reverse();
+    path
+  }
+}
+
+pub fn load_chat_message_tree(
+  user_service: &dyn AIUserService,
+  uid: i64,
+  chat_id: &str,
+) -> FlowyResult<ChatMessageTree> {
+  let messages = select_all_chat_messages(user_service.sqlite_connection(uid)?, chat_id)?;
+  let branches = select_chat_message_branches(user_service.sqlite_connection(uid)?, chat_id)?;
+  let active_children = select_chat_active_branches(user_service.sqlite_connection(uid)?, chat_id)?;
+  Ok(ChatMessageTree::new(messages, branches, active_children))
+}
+
+/// Record the parent of a new message and make it the active child of the parent.
+pub fn save_message_parent(
+  user_service: &dyn AIUserService,
+  uid: i64,
+  chat_id: &str,
+  message_id: i64,
+  parent_message_id: i64,
+) -> FlowyResult<()> {
+  let branch = ChatMessageBranchTable {
+    message_id,
+    chat_id: chat_id.to_string(),
+    parent_message_id: (parent_message_id != ROOT_PARENT_ID).then_some(parent_message_id),
+    created_at: timestamp(),
+  };
+  insert_chat_message_branch(user_service.sqlite_connection(uid)?, &branch)?;
+  set_active_message(user_service, uid, chat_id, parent_message_id, message_id)
+}
+
+pub fn set_active_message(
+  user_service: &dyn AIUserService,
+  uid: i64,
+  chat_id: &str,
+  parent_message_id: i64,
+  message_id: i64,
+) -> FlowyResult<()> {
+  let active = ChatActiveBranchTable {
+    chat_id: chat_id.to_string(),
+    parent_message_id,
+    message_id,
+  };
+  upsert_chat_active_branch(user_service.sqlite_connection(uid)?, &active)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn message(message_id: i64, reply_message_id: Option<i64>) -> ChatMessageTable {
+    ChatMessageTable {
+      message_id,
+      chat_id: "chat".to_string(),
+      content: message_id.to_string(),
+      created_at: 0,
+      author_type: if reply_message_id.is_some() { 3 } else { 1 },
+      author_id: "".to_string(),
+      reply_message_id,
+      metadata: None,
+    }
+  }
+
+  fn branch(message_id: i64, parent_message_id: Option<i64>) -> ChatMessageBranchTable {
+    ChatMessageBranchTable {
+      message_id,
+      chat_id: "chat".to_string(),
+      parent_message_id,
+      created_at: 0,
+    }
+  }
+
+  fn ids(path: Vec<&ChatMessageTable>) -> Vec<i64> {
+    path.into_iter().map(|message| message.message_id).collect()
+  }
+
+  /// 1 -> 2 -> 3 -> 4, where the question 3 is edited as 5, and the answer 6 replies to 5.
+  fn edited_tree(active_children: HashMap<i64, i64>) -> ChatMessageTree {
+    let messages = vec![
+      message(1, None),
+      message(2, Some(1)),
+      message(3, None),
+      message(4, Some(3)),
+      message(5, None),
+      message(6, Some(5)),
+    ];
+    ChatMessageTree::new(messages, vec![branch(5, Some(2))], active_children)
+  }
+
+  #[test]
+  fn linear_chat_test() {
+    let messages = vec![message(1, None), message(2, Some(1)), message(3, None)];
+    let tree = ChatMessageTree::new(messages, vec![], HashMap::new());
+    assert_eq!(ids(tree.active_path()), vec![1, 2, 3]);
+    assert_eq!(tree.siblings_of(3), vec![3]);
+  }
+
+  #[test]
+  fn edited_question_test() {
+    let tree = edited_tree(HashMap::new());
+    // The latest branch is active by default
+    assert_eq!(ids(tree.active_path()), vec![1, 2, 5, 6]);
+    assert_eq!(tree.siblings_of(5), vec![3, 5]);
+    assert_eq!(ids(tree.ancestors_of(6)), vec![1, 2, 5]);
+    assert_eq!(tree.answer_of(5).map(|answer| answer.message_id), Some(6));
+    assert!(tree.answer_of(2).is_none());
+
+    let tree = edited_tree(HashMap::from([(2, 3)]));
+    assert_eq!(ids(tree.active_path()), vec![1, 2, 3, 4]);
+  }
+
+  #[test]
+  fn regenerated_answer_test() {
+    let messages = vec![message(1, None), message(2, Some(1)), message(3, Some(1))];
+    let tree = ChatMessageTree::new(messages, vec![], HashMap::from([(1, 2)]));
+    assert_eq!(tree.siblings_of(3), vec![2, 3]);
+    assert_eq!(ids(tree.active_path()), vec![1, 2]);
+    assert_eq!(tree.answer_of(1).map(|answer| answer.message_id), Some(2));
+  }
+}
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+use flowy_error::{FlowyError, FlowyResult};
+use serde_json::{json, Value};
+
+use crate::ai_manager::{AIDocumentService, AIUserService};
+use crate::chat_branch::{load_chat_message_tree, ChatMessageTree};
+use crate::persistence::{search_chat_messages, ChatMessageSearchQuery, ChatMessageTable};
+
+const DEFAULT_SEARCH_LIMIT: i64 = 50;
+const MAX_SEARCH_LIMIT: i64 = 200;
+/// The number of characters that are kept on each side of the keyword in the snippet.
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+const DEFAULT_EXPORT_NAME: &str = "AI chat";
+
+pub struct ChatMessageSearchResult {
+  /// The chat id is the id of the view of the chat.
+  pub chat_id: String,
+  pub message: ChatMessageTable,
+  /// The part of the content around the keyword.
+  pub snippet: String,
+  /// The question of an answer, or the active answer of a question.
+  pub related_message: Option<ChatMessageTable>,
+}
+
+/// Search and export the chat messages that are stored locally.
+pub struct ChatHistory {
+  user_service: Arc<dyn AIUserService>,
+  document_service: ArcSwapOption<Arc<dyn AIDocumentService>>,
+}
+
+impl ChatHistory {
+  pub fn new(user_service: Arc<dyn AIUserService>) -> Self {
+    Self {
+      user_service,
+      document_service: Default::default(),
+    }
+  }
+
+  pub fn set_document_service(&self, service: Arc<dyn AIDocumentService>) {
+    self.document_service.store(Some(Arc::new(service)));
+  }
+
+  /// Search the messages across all the chats. Only the messages that have been loaded from the
+  /// server are searchable.
+  pub fn search(
+    &self,
+    mut query: ChatMessageSearchQuery,
+  ) -> FlowyResult<Vec<ChatMessageSearchResult>> {
+    let keyword = query.keyword.trim().to_string();
+    if keyword.is_empty() {
+      return Err(FlowyError::invalid_data().with_context("The keyword is empty"));
+    }
+    query.keyword = keyword.clone();
+    query.limit = match query.limit {
+      limit if limit <= 0 => DEFAULT_SEARCH_LIMIT,
+      limit => limit.min(MAX_SEARCH_LIMIT),
+    };
+
+    let uid = self.user_service.user_id()?;
+    let messages = search_chat_messages(self.user_service.sqlite_connection(uid)?, &query)?;
+    let mut trees: HashMap<String, ChatMessageTree> = HashMap::new();
+    let mut results = Vec::with_capacity(messages.len());
+    for message in messages {
+      if !trees.contains_key(&message.chat_id) {
+        let tree = load_chat_message_tree(self.user_service.as_ref(), uid, &message.chat_id)?;
+        trees.insert(message.chat_id.clone(), tree);
+      }
+      let tree = &trees[&message.chat_id];
+      let related_message = match message.reply_message_id {
+        Some(question_id) => tree.get(question_id),
+        None => tree.answer_of(message.message_id),
+      }
+      .cloned();
+      results.push(ChatMessageSearchResult {
+        chat_id: message.chat_id.clone(),
+        snippet: make_snippet(&message.content, &keyword),
+        message,
+        related_message,
+      });
+    }
+    Ok(results)
+  }
+
+  /// Create a document under the parent view with the questions and answers along the active
+  /// branches of the chat. Returns the id of the new view.
+  pub async fn export_to_document(
+    &self,
+    chat_id: &str,
+    parent_view_id: &str,
+    name: Option<String>,
+  ) -> FlowyResult<String> {
+    let document_service = self
+      .document_service
+      .load_full()
+      .ok_or_else(|| FlowyError::internal().with_context("The document service is not ready"))?;
+
+    let uid = self.user_service.user_id()?;
+    let tree = load_chat_message_tree(self.user_service.as_ref(), uid, chat_id)?;
+    let messages = tree.active_path().into_iter().cloned().collect::<Vec<_>>();
+    if messages.is_empty() {
+      return Err(
+        FlowyError::record_not_found().with_context(format!("Chat:{} has no messages", chat_id)),
+      );
+    }
+
+    let name = name
+      .filter(|name| !name.trim().is_empty())
+      .unwrap_or_else(|| DEFAULT_EXPORT_NAME.to_string());
+    let json = serde_json::to_string(&chat_to_document_json(&messages))?;
+    document_service
+      .create_document_view(parent_view_id, &name, &json)
+      .await
+  }
+}
+
+/// Returns the content around the first occurrence of the keyword, ignoring the case.
+fn make_snippet(content: &str, keyword: &str) -> String {
+  let content = content.trim();
+  let chars = content.chars().collect::<Vec<_>>();
+  let lowercase_chars = content.to_lowercase().chars().collect::<Vec<_>>();
+  let keyword = keyword.to_lowercase().chars().collect::<Vec<_>>();
+
+  // Lowercasing might change the number of chars, fall back to the beginning of the content.
+  let start = if lowercase_chars.len() == chars.len() {
+    lowercase_chars
+      .windows(keyword.len().max(1))
+      .position(|window| window == keyword.as_slice())
+      .unwrap_or(0)
+  } else {
+    0
+  };
+  let snippet_start = start.saturating_sub(SNIPPET_CONTEXT_CHARS);
+  let snippet_end = (start + keyword.len() + SNIPPET_CONTEXT_CHARS).min(chars.len());
+
+  let mut snippet = chars[snippet_start..snippet_end]
+    .iter()
+    .collect::<String>()
+    .replace('\n', " ");
+  if snippet_start > 0 {
+    snippet = format!("…{}", snippet);
+  }
+  if snippet_end < chars.len() {
+    snippet.push('…');
+  }
+  snippet
+}
+
+/// Converts the messages into the JSON of the `NestedBlock` defined in flowy-document. Each
+/// question is a heading that is followed by its answer and the sources of the answer.
+fn chat_to_document_json(messages: &[ChatMessageTable]) -> Value {
+  let mut children = vec![];
+  for message in messages {
+    // The answers reply to a question, the questions don't reply to anything.
+    if message.reply_message_id.is_none() {
+      if !children.is_empty() {
+        children.push(json!({ "type": "divider" }));
+      }
+      children.push(text_block(
+        "heading",
+        message.content.trim(),
+        Some(json!({ "level": 2 })),
+      ));
+    } else {
+      children.extend(markdown_to_blocks(&message.content));
+    }
+
+    let sources = message
+      .metadata
+      .as_deref()
+      .map(sources_from_metadata)
+      .unwrap_or_default();
+    if !sources.is_empty() {
+      children.push(json!({
+        "type": "paragraph",
+        "data": { "delta": [{ "insert": "Sources", "attributes": { "bold": true } }] },
+      }));
+      for source in sources {
+        children.push(text_block("bulleted_list", &source, None));
+      }
+    }
+  }
+  json!({ "type": "page", "children": children })
+}
+
+fn text_block(ty: &str, text: &str, data: Option<Value>) -> Value {
+  let mut data = data.unwrap_or_else(|| json!({}));
+  data["delta"] = json!([{ "insert": text }]);
+  json!({ "type": ty, "data": data })
+}
+
+/// Converts the line-level markdown of an answer into blocks. The inline styles are kept as they
+/// are.
+fn markdown_to_blocks(markdown: &str) -> Vec<Value> {
+  let mut blocks = vec![];
+  let mut code: Option<(String, Vec<&str>)> = None;
+  for line in markdown.lines() {
+    if let Some(language) = line.trim_start().strip_prefix("```") {
+      match code.take() {
+        None => code = Some((language.trim().to_string(), vec![])),
+        Some((language, lines)) => blocks.push(text_block(
+          "code",
+          &lines.join("\n"),
+          Some(json!({ "language": language })),
+        )),
+      }
+      continue;
+    }
+    if let Some((_, lines)) = code.as_mut() {
+      lines.push(line);
+      continue;
+    }
+
+    let line = line.trim();
+    if line.is_empty() {
+      continue;
+    }
+    let heading_level = line.chars().take_while(|c| *c == '#').count();
+    let block = if (1..=6).contains(&heading_level) && line[heading_level..].starts_with(' ') {
+      text_block(
+        "heading",
+        line[heading_level..].trim(),
+        Some(json!({ "level": heading_level })),
+      )
+    } else if let Some(text) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+      text_block("bulleted_list", text, None)
+    } else if let Some(text) = line.strip_prefix("> ") {
+      text_block("quote", text, None)
+    } else if let Some(text) = numbered_list_item(line) {
+      text_block("numbered_list", text, None)
+    } else {
+      text_block("paragraph", line, None)
+    };
+    blocks.push(block);
+  }
+
+  // The code block is not closed
+  if let Some((language, lines)) = code {
+    blocks.push(text_block(
+      "code",
+      &lines.join("\n"),
+      Some(json!({ "language": language })),
+    ));
+  }
+  blocks
+}
+
+fn numbered_list_item(line: &str) -> Option<&str> {
+  let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+  if digits == 0 {
+    return None;
+  }
+  line[digits..].strip_prefix(". ")
+}
+
+/// The metadata of a message is a list of sources, or a single source, where each source has a
+/// `name`, `source` or `id`.
+fn sources_from_metadata(metadata: &str) -> Vec<String> {
+  let value = match serde_json::from_str::<Value>(metadata) {
+    Ok(value) => value,
+    Err(_) => return vec![],
+  };
+  let items = match value {
+    Value::Array(items) => items,
+    Value::Object(_) => vec![value],
+    _ => return vec![],
+  };
+  items
+    .iter()
+    .filter_map(|item| {
+      ["name", "source", "id"]
+        .iter()
+        .filter_map(|key| item.get(*key).and_then(Value::as_str))
+        .find(|value| !value.is_empty())
+        .map(|value| value.to_string())
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn message(message_id: i64, content: &str, reply_message_id: Option<i64>) -> ChatMessageTable {
+    ChatMessageTable {
+      message_id,
+      chat_id: "chat".to_string(),
+      content: content.to_string(),
+      created_at: 0,
+      author_type: if reply_message_id.is_some() { 3 } else { 1 },
+      author_id: "".to_string(),
+      reply_message_id,
+      metadata: None,
+    }
+  }
+
+  #[test]
+  fn make_snippet_test() {
+    let content = format!("{} AppFlowy {}", "a".repeat(100), "b".repeat(100));
+    let snippet = make_snippet(&content, "appflowy");
+    assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+    assert!(snippet.contains("AppFlowy"));
+    assert_eq!(make_snippet("Hello\nworld", "world"), "Hello world");
+  }
+
+  #[test]
+  fn chat_to_document_json_test() {
+    let mut answer = message(
+      2,
+      "## Steps\n1. Open the grid\n- Add a row\n```rust\nlet a = 1;\n```",
+      Some(1),
+    );
+    answer.metadata = Some(r#"[{"id":"1","name":"Roadmap","source":"appflowy"}]"#.to_string());
+    let json = chat_to_document_json(&[message(1, "How to add a row?", None), answer]);
+
+    let types = json["children"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|block| block["type"].as_str().unwrap())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      types,
+      vec![
+        "heading",
+        "heading",
+        "numbered_list",
+        "bulleted_list",
+        "code",
+        "paragraph",
+        "bulleted_list"
+      ]
+    );
+    assert_eq!(json["children"][4]["data"]["language"], "rust");
+    assert_eq!(json["children"][6]["data"]["delta"][0]["insert"], "Roadmap");
+  }
+}
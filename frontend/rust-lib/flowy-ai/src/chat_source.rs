@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+use flowy_error::{FlowyError, FlowyResult};
+use lib_infra::util::timestamp;
+use serde_json::{json, Value};
+use tracing::{error, trace};
+
+use crate::ai_manager::{AIUserService, AIViewContent, AIWorkspaceSourceService};
+use crate::local_ai::local_llm_chat::LocalAIController;
+use crate::persistence::{
+  delete_chat_source, delete_chat_sources, select_chat_sources, upsert_chat_source, ChatSourceTable,
+};
+
+/// The number of characters of each indexed chunk. The passages are not split unless a single
+/// passage is longer than the chunk.
+const CHUNK_SIZE: usize = 1000;
+/// The value of the `source` in the metadata of the chunks that are indexed from the workspace.
+pub const WORKSPACE_SOURCE: &str = "appflowy";
+
+/// The views of the workspace that are attached to a chat as its retrieval sources. The content of
+/// the views is indexed by the local AI plugin, so it never leaves the device.
+///
+/// The plugin can't remove the chunks that have been indexed. When a view is detached or changes,
+/// the index of the chat is reset and its remaining content is indexed again, see
+/// [LocalAIController::reset_chat_index]. The chunks carry the version of their source, so the
+/// citations of the outdated chunks are still dropped from the answers until the index is rebuilt.
+/// See [retain_current_citations].
+pub struct ChatSources {
+  user_service: Arc<dyn AIUserService>,
+  local_ai: Arc<LocalAIController>,
+  source_service: ArcSwapOption<Arc<dyn AIWorkspaceSourceService>>,
+}
+
+impl ChatSources {
+  pub fn new(user_service: Arc<dyn AIUserService>, local_ai: Arc<LocalAIController>) -> Self {
+    Self {
+      user_service,
+      local_ai,
+      source_service: Default::default(),
+    }
+  }
+
+  pub fn set_source_service(&self, service: Arc<dyn AIWorkspaceSourceService>) {
+    self.source_service.store(Some(Arc::new(service)));
+  }
+
+  pub async fn attach(&self, chat_id: &str, view_id: &str) -> FlowyResult<ChatSourceTable> {
+    if !self.local_ai.is_running() {
+      return Err(
+        FlowyError::local_ai_unavailable()
+          .with_context("The workspace sources require the local AI to be running"),
+      );
+    }
+    let uid = self.user_service.user_id()?;
+    let existing = select_chat_sources(self.user_service.sqlite_connection(uid)?, chat_id)?
+      .into_iter()
+      .find(|source| source.view_id == view_id);
+    let content = self.source_service()?.get_view_content(view_id).await?;
+    let version = existing.map(|source| source.version + 1).unwrap_or(1);
+    let source = self.index(chat_id, view_id, content, version).await?;
+    upsert_chat_source(self.user_service.sqlite_connection(uid)?, &source)?;
+    Ok(source)
+  }
+
+  /// Detach the view from the chat and drop its chunks by rebuilding the index of the chat. If the
+  /// local AI is not running, the index is rebuilt by the next [Self::refresh].
+  pub async fn detach(&self, chat_id: &str, view_id: &str) -> FlowyResult<()> {
+    let uid = self.user_service.user_id()?;
+    delete_chat_source(self.user_service.sqlite_connection(uid)?, chat_id, view_id)?;
+    self.local_ai.reset_chat_index(chat_id);
+    self.refresh(chat_id).await
+  }
+
+  pub fn remove_all(&self, chat_id: &str) -> FlowyResult<()> {
+    let uid = self.user_service.user_id()?;
+    delete_chat_sources(self.user_service.sqlite_connection(uid)?, chat_id)?;
+    self.local_ai.remove_chat_index(chat_id);
+    Ok(())
+  }
+
+  pub fn list(&self, chat_id: &str) -> FlowyResult<Vec<ChatSourceTable>> {
+    let uid = self.user_service.user_id()?;
+    select_chat_sources(self.user_service.sqlite_connection(uid)?, chat_id)
+  }
+
+  /// Rebuild the index of the chat if the content of its sources has changed or the index was
+  /// reset. The chunks of the changed sources can't be replaced in place, so all the content of the
+  /// chat is indexed again. The sources that can't be read keep their record but are left out of
+  /// the rebuilt index.
+  pub async fn refresh(&self, chat_id: &str) -> FlowyResult<()> {
+    if !self.local_ai.is_running() {
+      return Ok(());
+    }
+    let is_stale = self.local_ai.is_chat_index_stale(chat_id);
+    let sources = self.list(chat_id)?;
+    if sources.is_empty() && !is_stale {
+      return Ok(());
+    }
+
+    let source_service = self.source_service()?;
+    let mut contents = Vec::with_capacity(sources.len());
+    for source in sources {
+      match source_service.get_view_content(&source.view_id).await {
+        Ok(content) => contents.push((source, content)),
+        Err(err) => error!(
+          "[Chat] failed to read the source:{} of chat:{}: {}",
+          source.view_id, chat_id, err
+        ),
+      }
+    }
+    let has_changed = contents
+      .iter()
+      .any(|(source, content)| content_hash(content) != source.content_hash);
+    if !has_changed && !is_stale {
+      return Ok(());
+    }
+
+    trace!("[Chat] rebuild the index of chat:{}", chat_id);
+    if !is_stale {
+      self.local_ai.reset_chat_index(chat_id);
+    }
+    let uid = self.user_service.user_id()?;
+    for (source, content) in contents {
+      let version = if content_hash(&content) == source.content_hash {
+        source.version
+      } else {
+        source.version + 1
+      };
+      let source = self
+        .index(chat_id, &source.view_id, content, version)
+        .await?;
+      upsert_chat_source(self.user_service.sqlite_connection(uid)?, &source)?;
+    }
+    self.local_ai.rebuild_chat_index(chat_id).await;
+    Ok(())
+  }
+
+  async fn index(
+    &self,
+    chat_id: &str,
+    view_id: &str,
+    content: AIViewContent,
+    version: i64,
+  ) -> FlowyResult<ChatSourceTable> {
+    let content_hash = content_hash(&content);
+    let chunks = chunk_passages(&content.passages, CHUNK_SIZE);
+    for (index, chunk) in chunks.iter().enumerate() {
+      let mut metadata = HashMap::new();
+      metadata.insert("id".to_string(), json!(view_id));
+      metadata.insert("view_id".to_string(), json!(view_id));
+      metadata.insert("name".to_string(), json!(&content.name));
+      metadata.insert("at_name".to_string(), json!(format!("@{}", &content.name)));
+      metadata.insert("source".to_string(), json!(WORKSPACE_SOURCE));
+      metadata.insert("block_ids".to_string(), json!(&chunk.block_ids));
+      metadata.insert("version".to_string(), json!(version));
+      metadata.insert("chunk_index".to_string(), json!(index));
+      self
+        .local_ai
+        .index_chat_source(chat_id, chunk.text.clone(), metadata)
+        .await
+        .map_err(|err| FlowyError::local_ai().with_context(err))?;
+    }
+
+    Ok(ChatSourceTable {
+      chat_id: chat_id.to_string(),
+      view_id: view_id.to_string(),
+      name: content.name,
+      source_type: content.source_type as i32,
+      content_hash,
+      version,
+      chunk_count: chunks.len() as i32,
+      indexed_at: timestamp(),
+    })
+  }
+
+  fn source_service(&self) -> FlowyResult<Arc<dyn AIWorkspaceSourceService>> {
+    self
+      .source_service
+      .load_full()
+      .map(|service| service.as_ref().clone())
+      .ok_or_else(|| {
+        FlowyError::internal().with_context("The workspace source service is not ready")
+      })
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct SourceChunk {
+  text: String,
+  /// The ids of the blocks or the rows whose text is in the chunk
+  block_ids: Vec<String>,
+}
+
+fn content_hash(content: &AIViewContent) -> String {
+  let mut context = md5::Context::new();
+  context.consume(content.name.as_bytes());
+  for (id, text) in &content.passages {
+    context.consume(id.as_bytes());
+    context.consume(text.as_bytes());
+  }
+  format!("{:x}", context.compute())
+}
+
+/// Join the passages into chunks of about `chunk_size` characters.
+fn chunk_passages(passages: &[(String, String)], chunk_size: usize) -> Vec<SourceChunk> {
+  let mut chunks = vec![];
+  let mut current = SourceChunk {
+    text: String::new(),
+    block_ids: vec![],
+  };
+  let mut current_len = 0;
+  for (id, text) in passages {
+    let chars = text.chars().collect::<Vec<_>>();
+    for piece in chars.chunks(chunk_size.max(1)) {
+      if current_len > 0 && current_len + piece.len() > chunk_size {
+        chunks.push(std::mem::replace(
+          &mut current,
+          SourceChunk {
+            text: String::new(),
+            block_ids: vec![],
+          },
+        ));
+        current_len = 0;
+      }
+      if current_len > 0 {
+        current.text.push('\n');
+      }
+      current.text.extend(piece);
+      current_len += piece.len();
+      if current.block_ids.last() != Some(id) {
+        current.block_ids.push(id.clone());
+      }
+    }
+  }
+  if current_len > 0 {
+    chunks.push(current);
+  }
+  chunks
+}
+
+/// Drop the citations of the workspace sources that are detached from the chat or were indexed
+/// with an outdated version. `versions` maps the view id of each attached source to its version.
+/// The citations of the other sources are kept.
+pub fn retain_current_citations(value: Value, versions: &HashMap<String, i64>) -> Value {
+  let is_current = |item: &Value| {
+    if item.get("source").and_then(Value::as_str) != Some(WORKSPACE_SOURCE) {
+      return true;
+    }
+    let view_id = match item.get("view_id").and_then(Value::as_str) {
+      Some(view_id) => view_id,
+      None => return true,
+    };
+    let version = item.get("version").and_then(Value::as_i64);
+    versions
+      .get(view_id)
+      .is_some_and(|current| version == Some(*current))
+  };
+
+  match value {
+    Value::Array(items) => Value::Array(items.into_iter().filter(is_current).collect()),
+    value => value,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn passage(id: &str, len: usize) -> (String, String) {
+    (id.to_string(), "a".repeat(len))
+  }
+
+  #[test]
+  fn chunk_passages_test() {
+    let passages = vec![passage("1", 400), passage("2", 400), passage("3", 400)];
+    let chunks = chunk_passages(&passages, 1000);
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].block_ids, vec!["1", "2"]);
+    assert_eq!(chunks[1].block_ids, vec!["3"]);
+
+    // A long passage is split into multiple chunks
+    let chunks = chunk_passages(&[passage("1", 2500)], 1000);
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks.iter().all(|chunk| chunk.block_ids == vec!["1"]));
+    assert!(chunk_passages(&[], 1000).is_empty());
+  }
+
+  #[test]
+  fn content_hash_test() {
+    let content = |text: &str| AIViewContent {
+      name: "Notes".to_string(),
+      source_type: crate::ai_manager::AIViewSourceType::Document,
+      passages: vec![("1".to_string(), text.to_string())],
+    };
+    assert_eq!(content_hash(&content("a")), content_hash(&content("a")));
+    assert_ne!(content_hash(&content("a")), content_hash(&content("b")));
+  }
+
+  #[test]
+  fn retain_current_citations_test() {
+    let value = json!([
+      { "view_id": "v1", "version": 2, "source": "appflowy" },
+      { "view_id": "v1", "version": 1, "source": "appflowy" },
+      { "view_id": "v2", "version": 1, "source": "appflowy" },
+      { "id": "file", "source": "appflowy_file" },
+    ]);
+    let versions = HashMap::from([("v1".to_string(), 2)]);
+    let value = retain_current_citations(value, &versions);
+    assert_eq!(
+      value,
+      json!([
+        { "view_id": "v1", "version": 2, "source": "appflowy" },
+        { "id": "file", "source": "appflowy_file" },
+      ])
+    );
+  }
+}
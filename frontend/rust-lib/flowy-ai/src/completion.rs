@@ -1,5 +1,6 @@
 use crate::ai_manager::AIUserService;
 use crate::entities::{CompleteTextPB, CompleteTextTaskPB, CompletionTypePB};
+use crate::prompt_library::{PromptContext, PromptLibrary};
 use allo_isolate::Isolate;
 
 use dashmap::DashMap;
@@ -16,20 +17,40 @@ pub struct AICompletion {
   tasks: Arc<DashMap<String, tokio::sync::mpsc::Sender<()>>>,
   cloud_service: Weak<dyn ChatCloudService>,
   user_service: Weak<dyn AIUserService>,
+  prompt_library: Weak<PromptLibrary>,
 }
 
 impl AICompletion {
   pub fn new(
     cloud_service: Weak<dyn ChatCloudService>,
     user_service: Weak<dyn AIUserService>,
+    prompt_library: Weak<PromptLibrary>,
   ) -> Self {
     Self {
       tasks: Arc::new(DashMap::new()),
       cloud_service,
       user_service,
+      prompt_library,
     }
   }
 
+  /// Render the prompt of the prompt library with the text and its context.
+  fn render_custom_prompt(&self, complete: &CompleteTextPB) -> FlowyResult<String> {
+    let prompt_id = complete.prompt_id.as_ref().ok_or_else(|| {
+      FlowyError::invalid_data().with_context("The prompt id is required for the custom prompt")
+    })?;
+    let prompt = self
+      .prompt_library
+      .upgrade()
+      .ok_or_else(FlowyError::internal)?
+      .get_prompt(prompt_id)?;
+    Ok(prompt.render(&PromptContext {
+      selected_text: complete.text.clone(),
+      document_title: complete.document_title.clone().unwrap_or_default(),
+      context: complete.context.clone().unwrap_or_default(),
+    }))
+  }
+
   pub async fn create_complete_task(
     &self,
     complete: CompleteTextPB,
@@ -39,8 +60,18 @@ impl AICompletion {
       .upgrade()
       .ok_or_else(FlowyError::internal)?
       .workspace_id()?;
+    let prompt = match complete.completion_type {
+      CompletionTypePB::CustomPrompt => Some(self.render_custom_prompt(&complete)?),
+      _ => None,
+    };
     let (tx, rx) = tokio::sync::mpsc::channel(1);
-    let task = CompletionTask::new(workspace_id, complete, self.cloud_service.clone(), rx);
+    let task = CompletionTask::new(
+      workspace_id,
+      complete,
+      prompt,
+      self.cloud_service.clone(),
+      rx,
+    );
     let task_id = task.task_id.clone();
     self.tasks.insert(task_id.clone(), tx);
 
@@ -60,6 +91,8 @@ pub struct CompletionTask {
   task_id: String,
   stop_rx: tokio::sync::mpsc::Receiver<()>,
   context: CompleteTextPB,
+  /// The rendered prompt when completing with a prompt of the prompt library
+  prompt: Option<String>,
   cloud_service: Weak<dyn ChatCloudService>,
 }
 
@@ -67,6 +100,7 @@ impl CompletionTask {
   pub fn new(
     workspace_id: String,
     context: CompleteTextPB,
+    prompt: Option<String>,
     cloud_service: Weak<dyn ChatCloudService>,
     stop_rx: tokio::sync::mpsc::Receiver<()>,
   ) -> Self {
@@ -74,6 +108,7 @@ impl CompletionTask {
       workspace_id,
       task_id: uuid::Uuid::new_v4().to_string(),
       context,
+      prompt,
       cloud_service,
       stop_rx,
     }
@@ -84,21 +119,29 @@ impl CompletionTask {
       let mut sink = IsolateSink::new(Isolate::new(self.context.stream_port));
 
       if let Some(cloud_service) = self.cloud_service.upgrade() {
-        let complete_type = match self.context.completion_type {
-          CompletionTypePB::UnknownCompletionType | CompletionTypePB::ImproveWriting => {
-            CompletionType::ImproveWriting
+        let _ = sink.send("start:".to_string()).await;
+        let result = match self.prompt.take() {
+          Some(prompt) => {
+            cloud_service
+              .stream_complete_with_prompt(&self.workspace_id, &prompt)
+              .await
+          },
+          None => {
+            let complete_type = match self.context.completion_type {
+              CompletionTypePB::UnknownCompletionType
+              | CompletionTypePB::ImproveWriting
+              | CompletionTypePB::CustomPrompt => CompletionType::ImproveWriting,
+              CompletionTypePB::SpellingAndGrammar => CompletionType::SpellingAndGrammar,
+              CompletionTypePB::MakeShorter => CompletionType::MakeShorter,
+              CompletionTypePB::MakeLonger => CompletionType::MakeLonger,
+              CompletionTypePB::ContinueWriting => CompletionType::ContinueWriting,
+            };
+            cloud_service
+              .stream_complete(&self.workspace_id, &self.context.text, complete_type)
+              .await
           },
-          CompletionTypePB::SpellingAndGrammar => CompletionType::SpellingAndGrammar,
-          CompletionTypePB::MakeShorter => CompletionType::MakeShorter,
-          CompletionTypePB::MakeLonger => CompletionType::MakeLonger,
-          CompletionTypePB::ContinueWriting => CompletionType::ContinueWriting,
         };
-
-        let _ = sink.send("start:".to_string()).await;
-        match cloud_service
-          .stream_complete(&self.workspace_id, &self.context.text, complete_type)
-          .await
-        {
+        match result {
           Ok(mut stream) => loop {
             select! {
                 _ = self.stop_rx.recv() => {
@@ -2,7 +2,12 @@ use crate::local_ai::local_llm_chat::LLMModelInfo;
 use appflowy_plugin::core::plugin::RunningState;
 use std::collections::HashMap;
 
+use crate::ai_manager::AIViewSourceType;
+use crate::chat_history::ChatMessageSearchResult;
 use crate::local_ai::local_llm_resource::PendingResource;
+use crate::openai_compatible::OpenAICompatibleConfig;
+use crate::persistence::{ChatMessageTable, ChatSourceTable};
+use crate::prompt_library::{AIPrompt, PromptScope};
 use flowy_ai_pub::cloud::{
   ChatMessage, LLMModel, RelatedQuestion, RepeatedChatMessage, RepeatedRelatedQuestion,
 };
@@ -71,6 +76,30 @@ pub struct StreamChatPayloadPB {
   pub metadata: Vec<ChatMessageMetaPB>,
 }
 
+#[derive(Default, ProtoBuf, Validate, Clone, Debug)]
+pub struct EditChatQuestionPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub chat_id: String,
+
+  /// The id of the question that is edited
+  #[pb(index = 2)]
+  pub message_id: i64,
+
+  #[pb(index = 3)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub message: String,
+
+  #[pb(index = 4)]
+  pub answer_stream_port: i64,
+
+  #[pb(index = 5)]
+  pub question_stream_port: i64,
+
+  #[pb(index = 6)]
+  pub metadata: Vec<ChatMessageMetaPB>,
+}
+
 #[derive(Default, ProtoBuf, Validate, Clone, Debug)]
 pub struct ChatMessageMetaPB {
   #[pb(index = 1)]
@@ -190,6 +219,20 @@ pub struct ChatMessagePB {
   pub metadata: Option<String>,
 }
 
+impl From<ChatMessageTable> for ChatMessagePB {
+  fn from(record: ChatMessageTable) -> Self {
+    ChatMessagePB {
+      message_id: record.message_id,
+      content: record.content,
+      created_at: record.created_at,
+      author_type: record.author_type,
+      author_id: record.author_id,
+      reply_message_id: record.reply_message_id,
+      metadata: record.metadata,
+    }
+  }
+}
+
 #[derive(Debug, Clone, Default, ProtoBuf)]
 pub struct ChatMessageErrorPB {
   #[pb(index = 1)]
@@ -330,6 +373,17 @@ pub struct CompleteTextPB {
 
   #[pb(index = 3)]
   pub stream_port: i64,
+
+  /// Required when the completion type is [CompletionTypePB::CustomPrompt]
+  #[pb(index = 4, one_of)]
+  pub prompt_id: Option<String>,
+
+  #[pb(index = 5, one_of)]
+  pub document_title: Option<String>,
+
+  /// The content around the selected text
+  #[pb(index = 6, one_of)]
+  pub context: Option<String>,
 }
 
 #[derive(Default, ProtoBuf, Clone, Debug)]
@@ -347,6 +401,8 @@ pub enum CompletionTypePB {
   MakeShorter = 3,
   MakeLonger = 4,
   ContinueWriting = 5,
+  /// Complete with a prompt of the prompt library
+  CustomPrompt = 6,
 }
 
 #[derive(Default, ProtoBuf, Clone, Debug)]
@@ -525,3 +581,341 @@ pub struct CreateChatContextPB {
   #[validate(custom(function = "required_not_empty_str"))]
   pub chat_id: String,
 }
+
+/// The model endpoint that implements the chat completions API of OpenAI. It's configured per
+/// workspace.
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct OpenAICompatibleConfigPB {
+  #[pb(index = 1)]
+  pub enabled: bool,
+
+  #[pb(index = 2)]
+  pub base_url: String,
+
+  #[pb(index = 3)]
+  pub api_key: String,
+
+  #[pb(index = 4)]
+  pub model: String,
+}
+
+impl From<OpenAICompatibleConfig> for OpenAICompatibleConfigPB {
+  fn from(config: OpenAICompatibleConfig) -> Self {
+    Self {
+      enabled: config.enabled,
+      base_url: config.base_url,
+      api_key: config.api_key,
+      model: config.model,
+    }
+  }
+}
+
+impl From<OpenAICompatibleConfigPB> for OpenAICompatibleConfig {
+  fn from(pb: OpenAICompatibleConfigPB) -> Self {
+    Self {
+      enabled: pb.enabled,
+      base_url: pb.base_url.trim().to_string(),
+      api_key: pb.api_key.trim().to_string(),
+      model: pb.model.trim().to_string(),
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf_Enum, PartialEq, Eq, Copy)]
+pub enum AIPromptScopePB {
+  #[default]
+  User = 0,
+  Workspace = 1,
+}
+
+impl From<PromptScope> for AIPromptScopePB {
+  fn from(scope: PromptScope) -> Self {
+    match scope {
+      PromptScope::User => AIPromptScopePB::User,
+      PromptScope::Workspace => AIPromptScopePB::Workspace,
+    }
+  }
+}
+
+impl From<AIPromptScopePB> for PromptScope {
+  fn from(scope: AIPromptScopePB) -> Self {
+    match scope {
+      AIPromptScopePB::User => PromptScope::User,
+      AIPromptScopePB::Workspace => PromptScope::Workspace,
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct AIPromptPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  /// The template can reference the variables: {{selected_text}}, {{document_title}} and
+  /// {{context}}
+  #[pb(index = 3)]
+  pub template: String,
+
+  #[pb(index = 4)]
+  pub scope: AIPromptScopePB,
+
+  #[pb(index = 5)]
+  pub created_at: i64,
+
+  #[pb(index = 6)]
+  pub updated_at: i64,
+}
+
+impl From<AIPrompt> for AIPromptPB {
+  fn from(prompt: AIPrompt) -> Self {
+    Self {
+      id: prompt.id,
+      name: prompt.name,
+      template: prompt.template,
+      scope: prompt.scope.into(),
+      created_at: prompt.created_at,
+      updated_at: prompt.updated_at,
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct RepeatedAIPromptPB {
+  #[pb(index = 1)]
+  pub items: Vec<AIPromptPB>,
+}
+
+#[derive(Default, ProtoBuf, Validate, Clone, Debug)]
+pub struct CreateAIPromptPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub name: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub template: String,
+
+  #[pb(index = 3)]
+  pub scope: AIPromptScopePB,
+}
+
+#[derive(Default, ProtoBuf, Validate, Clone, Debug)]
+pub struct UpdateAIPromptPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub id: String,
+
+  #[pb(index = 2, one_of)]
+  pub name: Option<String>,
+
+  #[pb(index = 3, one_of)]
+  pub template: Option<String>,
+}
+
+#[derive(Default, ProtoBuf, Validate, Clone, Debug)]
+pub struct AIPromptIdPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub value: String,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ExportAIPromptsPB {
+  /// Export all the prompts if it's empty
+  #[pb(index = 1)]
+  pub prompt_ids: Vec<String>,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct AIPromptsDataPB {
+  #[pb(index = 1)]
+  pub json: String,
+}
+
+#[derive(Default, ProtoBuf, Validate, Clone, Debug)]
+pub struct ImportAIPromptsPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub json: String,
+
+  /// Override the scope of the imported prompts
+  #[pb(index = 2, one_of)]
+  pub scope: Option<AIPromptScopePB>,
+}
+
+#[derive(Default, ProtoBuf, Validate, Clone, Debug)]
+pub struct SearchChatMessagesPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub keyword: String,
+
+  /// Same as the author_type of [ChatMessagePB]
+  #[pb(index = 2, one_of)]
+  pub author_type: Option<i64>,
+
+  /// Timestamp in seconds
+  #[pb(index = 3, one_of)]
+  pub start_time: Option<i64>,
+
+  /// Timestamp in seconds
+  #[pb(index = 4, one_of)]
+  pub end_time: Option<i64>,
+
+  /// Use the default limit when it's 0
+  #[pb(index = 5)]
+  pub limit: i64,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ChatMessageSearchResultPB {
+  #[pb(index = 1)]
+  pub chat_id: String,
+
+  #[pb(index = 2)]
+  pub message: ChatMessagePB,
+
+  /// The content around the keyword
+  #[pb(index = 3)]
+  pub snippet: String,
+
+  /// The question of an answer, or the answer of a question
+  #[pb(index = 4, one_of)]
+  pub related_message: Option<ChatMessagePB>,
+}
+
+impl From<ChatMessageSearchResult> for ChatMessageSearchResultPB {
+  fn from(result: ChatMessageSearchResult) -> Self {
+    Self {
+      chat_id: result.chat_id,
+      message: result.message.into(),
+      snippet: result.snippet,
+      related_message: result.related_message.map(ChatMessagePB::from),
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct RepeatedChatMessageSearchResultPB {
+  #[pb(index = 1)]
+  pub items: Vec<ChatMessageSearchResultPB>,
+}
+
+#[derive(Default, ProtoBuf, Validate, Clone, Debug)]
+pub struct ExportChatToDocumentPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub chat_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub parent_view_id: String,
+
+  /// The name of the document. Use the default name if it's None
+  #[pb(index = 3, one_of)]
+  pub name: Option<String>,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ChatDocumentPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+}
+
+/// The messages along the active branches of a chat
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ChatThreadPB {
+  #[pb(index = 1)]
+  pub messages: Vec<ChatMessagePB>,
+
+  /// The messages in [ChatThreadPB::messages] that have more than one branch
+  #[pb(index = 2)]
+  pub branches: Vec<ChatMessageBranchPB>,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ChatMessageBranchPB {
+  #[pb(index = 1)]
+  pub message_id: i64,
+
+  /// The ids of the branches, including the message itself. The oldest branch comes first.
+  #[pb(index = 2)]
+  pub sibling_ids: Vec<i64>,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ChatMessageBranchesPB {
+  /// The oldest branch comes first
+  #[pb(index = 1)]
+  pub messages: Vec<ChatMessagePB>,
+
+  #[pb(index = 2, one_of)]
+  pub active_message_id: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf_Enum, PartialEq, Eq, Copy)]
+pub enum ChatSourceTypePB {
+  #[default]
+  Document = 0,
+  Database = 1,
+}
+
+impl From<AIViewSourceType> for ChatSourceTypePB {
+  fn from(value: AIViewSourceType) -> Self {
+    match value {
+      AIViewSourceType::Document => ChatSourceTypePB::Document,
+      AIViewSourceType::Database => ChatSourceTypePB::Database,
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Validate, Clone, Debug)]
+pub struct ChatSourcePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub chat_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+}
+
+/// A view of the workspace that is indexed by the local AI for the chat
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ChatSourcePB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub source_type: ChatSourceTypePB,
+
+  #[pb(index = 4)]
+  pub chunk_count: i32,
+
+  /// The timestamp of the last time the view was indexed
+  #[pb(index = 5)]
+  pub indexed_at: i64,
+}
+
+impl From<ChatSourceTable> for ChatSourcePB {
+  fn from(source: ChatSourceTable) -> Self {
+    Self {
+      view_id: source.view_id,
+      name: source.name,
+      source_type: AIViewSourceType::from(source.source_type).into(),
+      chunk_count: source.chunk_count,
+      indexed_at: source.indexed_at,
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct RepeatedChatSourcePB {
+  #[pb(index = 1)]
+  pub items: Vec<ChatSourcePB>,
+}
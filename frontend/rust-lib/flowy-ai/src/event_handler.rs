@@ -6,6 +6,7 @@ use crate::completion::AICompletion;
 use crate::entities::*;
 use crate::local_ai::local_llm_chat::LLMModelInfo;
 use crate::notification::{make_notification, ChatNotification, APPFLOWY_AI_NOTIFICATION_KEY};
+use crate::persistence::ChatMessageSearchQuery;
 use allo_isolate::Isolate;
 use flowy_ai_pub::cloud::{ChatMessageMetadata, ChatMessageType, ChatRAGData, ContextLoader};
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
@@ -22,20 +23,8 @@ fn upgrade_ai_manager(ai_manager: AFPluginState<Weak<AIManager>>) -> FlowyResult
   Ok(ai_manager)
 }
 
-#[tracing::instrument(level = "debug", skip_all, err)]
-pub(crate) async fn stream_chat_message_handler(
-  data: AFPluginData<StreamChatPayloadPB>,
-  ai_manager: AFPluginState<Weak<AIManager>>,
-) -> DataResult<ChatMessagePB, FlowyError> {
-  let data = data.into_inner();
-  data.validate()?;
-
-  let message_type = match data.message_type {
-    ChatMessageTypePB::System => ChatMessageType::System,
-    ChatMessageTypePB::User => ChatMessageType::User,
-  };
-  let metadata = data
-    .metadata
+fn to_chat_message_metadata(metadata: Vec<ChatMessageMetaPB>) -> Vec<ChatMessageMetadata> {
+  metadata
     .into_iter()
     .map(|metadata| {
       let (content_type, content_len) = match metadata.data_type {
@@ -57,7 +46,22 @@ pub(crate) async fn stream_chat_message_handler(
         extra: None,
       }
     })
-    .collect::<Vec<_>>();
+    .collect()
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn stream_chat_message_handler(
+  data: AFPluginData<StreamChatPayloadPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<ChatMessagePB, FlowyError> {
+  let data = data.into_inner();
+  data.validate()?;
+
+  let message_type = match data.message_type {
+    ChatMessageTypePB::System => ChatMessageType::System,
+    ChatMessageTypePB::User => ChatMessageType::User,
+  };
+  let metadata = to_chat_message_metadata(data.metadata);
 
   trace!("Stream chat message with metadata: {:?}", metadata);
   let ai_manager = upgrade_ai_manager(ai_manager)?;
@@ -417,3 +421,231 @@ pub(crate) async fn get_chat_info_handler(
   let pb = ai_manager.get_chat_info(&chat_id).await?;
   data_result_ok(pb)
 }
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_openai_compatible_config_handler(
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<OpenAICompatibleConfigPB, FlowyError> {
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let config = ai_manager.openai_compatible.get_config()?;
+  data_result_ok(config.into())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn update_openai_compatible_config_handler(
+  data: AFPluginData<OpenAICompatibleConfigPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> Result<(), FlowyError> {
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let config = data.into_inner();
+  ai_manager.openai_compatible.set_config(config.into())?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_ai_prompts_handler(
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<RepeatedAIPromptPB, FlowyError> {
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let prompts = ai_manager.prompt_library.get_prompts()?;
+  data_result_ok(RepeatedAIPromptPB {
+    items: prompts.into_iter().map(AIPromptPB::from).collect(),
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn create_ai_prompt_handler(
+  data: AFPluginData<CreateAIPromptPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<AIPromptPB, FlowyError> {
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let data = data.try_into_inner()?;
+  let prompt =
+    ai_manager
+      .prompt_library
+      .create_prompt(data.name, data.template, data.scope.into())?;
+  data_result_ok(prompt.into())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn update_ai_prompt_handler(
+  data: AFPluginData<UpdateAIPromptPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<AIPromptPB, FlowyError> {
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let data = data.try_into_inner()?;
+  let prompt = ai_manager
+    .prompt_library
+    .update_prompt(&data.id, data.name, data.template)?;
+  data_result_ok(prompt.into())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn delete_ai_prompt_handler(
+  data: AFPluginData<AIPromptIdPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> Result<(), FlowyError> {
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let data = data.try_into_inner()?;
+  ai_manager.prompt_library.delete_prompt(&data.value)?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_ai_prompts_handler(
+  data: AFPluginData<ExportAIPromptsPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<AIPromptsDataPB, FlowyError> {
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let data = data.into_inner();
+  let json = ai_manager.prompt_library.export_prompts(&data.prompt_ids)?;
+  data_result_ok(AIPromptsDataPB { json })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn import_ai_prompts_handler(
+  data: AFPluginData<ImportAIPromptsPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<RepeatedAIPromptPB, FlowyError> {
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let data = data.try_into_inner()?;
+  let prompts = ai_manager
+    .prompt_library
+    .import_prompts(&data.json, data.scope.map(Into::into))?;
+  data_result_ok(RepeatedAIPromptPB {
+    items: prompts.into_iter().map(AIPromptPB::from).collect(),
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn search_chat_messages_handler(
+  data: AFPluginData<SearchChatMessagesPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<RepeatedChatMessageSearchResultPB, FlowyError> {
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let data = data.try_into_inner()?;
+  let results = ai_manager.search_chat_messages(ChatMessageSearchQuery {
+    keyword: data.keyword,
+    author_type: data.author_type,
+    start_time: data.start_time,
+    end_time: data.end_time,
+    limit: data.limit,
+  })?;
+  data_result_ok(RepeatedChatMessageSearchResultPB {
+    items: results
+      .into_iter()
+      .map(ChatMessageSearchResultPB::from)
+      .collect(),
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_chat_to_document_handler(
+  data: AFPluginData<ExportChatToDocumentPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<ChatDocumentPB, FlowyError> {
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let data = data.try_into_inner()?;
+  let view_id = ai_manager
+    .export_chat_to_document(&data.chat_id, &data.parent_view_id, data.name)
+    .await?;
+  data_result_ok(ChatDocumentPB { view_id })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn edit_chat_question_handler(
+  data: AFPluginData<EditChatQuestionPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<ChatMessagePB, FlowyError> {
+  let data = data.try_into_inner()?;
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let question = ai_manager
+    .edit_question(
+      &data.chat_id,
+      data.message_id,
+      &data.message,
+      data.answer_stream_port,
+      data.question_stream_port,
+      to_chat_message_metadata(data.metadata),
+    )
+    .await?;
+  data_result_ok(question)
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn load_active_chat_thread_handler(
+  data: AFPluginData<ChatId>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<ChatThreadPB, FlowyError> {
+  let data = data.try_into_inner()?;
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let thread = ai_manager.load_active_chat_thread(&data.value).await?;
+  data_result_ok(thread)
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_chat_message_branches_handler(
+  data: AFPluginData<ChatMessageIdPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<ChatMessageBranchesPB, FlowyError> {
+  let data = data.into_inner();
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let branches = ai_manager
+    .get_chat_message_branches(&data.chat_id, data.message_id)
+    .await?;
+  data_result_ok(branches)
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn switch_chat_branch_handler(
+  data: AFPluginData<ChatMessageIdPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<ChatThreadPB, FlowyError> {
+  let data = data.into_inner();
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let thread = ai_manager
+    .switch_chat_branch(&data.chat_id, data.message_id)
+    .await?;
+  data_result_ok(thread)
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn attach_chat_source_handler(
+  data: AFPluginData<ChatSourcePayloadPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<ChatSourcePB, FlowyError> {
+  let data = data.try_into_inner()?;
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let source = ai_manager
+    .attach_chat_source(&data.chat_id, &data.view_id)
+    .await?;
+  data_result_ok(ChatSourcePB::from(source))
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn detach_chat_source_handler(
+  data: AFPluginData<ChatSourcePayloadPB>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> Result<(), FlowyError> {
+  let data = data.try_into_inner()?;
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  ai_manager
+    .detach_chat_source(&data.chat_id, &data.view_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_chat_sources_handler(
+  data: AFPluginData<ChatId>,
+  ai_manager: AFPluginState<Weak<AIManager>>,
+) -> DataResult<RepeatedChatSourcePB, FlowyError> {
+  let data = data.try_into_inner()?;
+  let ai_manager = upgrade_ai_manager(ai_manager)?;
+  let items = ai_manager
+    .get_chat_sources(&data.value)?
+    .into_iter()
+    .map(ChatSourcePB::from)
+    .collect();
+  data_result_ok(RepeatedChatSourcePB { items })
+}
@@ -12,7 +12,12 @@ use crate::event_handler::*;
 pub fn init(ai_manager: Weak<AIManager>) -> AFPlugin {
   let user_service = Arc::downgrade(&ai_manager.upgrade().unwrap().user_service);
   let cloud_service = Arc::downgrade(&ai_manager.upgrade().unwrap().cloud_service_wm);
-  let ai_tools = Arc::new(AICompletion::new(cloud_service, user_service));
+  let prompt_library = Arc::downgrade(&ai_manager.upgrade().unwrap().prompt_library);
+  let ai_tools = Arc::new(AICompletion::new(
+    cloud_service,
+    user_service,
+    prompt_library,
+  ));
   AFPlugin::new()
     .name("flowy-ai")
     .state(ai_manager)
@@ -57,6 +62,38 @@ pub fn init(ai_manager: Weak<AIManager>) -> AFPlugin {
     .event(AIEvent::GetOfflineAIAppLink, get_offline_app_handler)
     .event(AIEvent::CreateChatContext, create_chat_context_handler)
     .event(AIEvent::GetChatInfo, create_chat_context_handler)
+    .event(
+      AIEvent::GetOpenAICompatibleConfig,
+      get_openai_compatible_config_handler,
+    )
+    .event(
+      AIEvent::UpdateOpenAICompatibleConfig,
+      update_openai_compatible_config_handler,
+    )
+    .event(AIEvent::GetAIPrompts, get_ai_prompts_handler)
+    .event(AIEvent::CreateAIPrompt, create_ai_prompt_handler)
+    .event(AIEvent::UpdateAIPrompt, update_ai_prompt_handler)
+    .event(AIEvent::DeleteAIPrompt, delete_ai_prompt_handler)
+    .event(AIEvent::ExportAIPrompts, export_ai_prompts_handler)
+    .event(AIEvent::ImportAIPrompts, import_ai_prompts_handler)
+    .event(AIEvent::SearchChatMessages, search_chat_messages_handler)
+    .event(
+      AIEvent::ExportChatToDocument,
+      export_chat_to_document_handler,
+    )
+    .event(AIEvent::EditChatQuestion, edit_chat_question_handler)
+    .event(
+      AIEvent::LoadActiveChatThread,
+      load_active_chat_thread_handler,
+    )
+    .event(
+      AIEvent::GetChatMessageBranches,
+      get_chat_message_branches_handler,
+    )
+    .event(AIEvent::SwitchChatBranch, switch_chat_branch_handler)
+    .event(AIEvent::AttachChatSource, attach_chat_source_handler)
+    .event(AIEvent::DetachChatSource, detach_chat_source_handler)
+    .event(AIEvent::GetChatSources, get_chat_sources_handler)
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Display, Hash, ProtoBuf_Enum, Flowy_Event)]
@@ -142,4 +179,72 @@ pub enum AIEvent {
 
   #[event(input = "ChatId", output = "ChatInfoPB")]
   GetChatInfo = 24,
+
+  /// Return the model endpoint of the current workspace
+  #[event(output = "OpenAICompatibleConfigPB")]
+  GetOpenAICompatibleConfig = 25,
+
+  /// When the endpoint is enabled, the chat and the completion of the current workspace are
+  /// answered by the endpoint instead of AppFlowy Cloud or the local AI plugin.
+  #[event(input = "OpenAICompatibleConfigPB")]
+  UpdateOpenAICompatibleConfig = 26,
+
+  /// Return the prompts of the user and the prompts of the current workspace. The prompts can be
+  /// run with the [AIEvent::CompleteText] event.
+  #[event(output = "RepeatedAIPromptPB")]
+  GetAIPrompts = 27,
+
+  #[event(input = "CreateAIPromptPB", output = "AIPromptPB")]
+  CreateAIPrompt = 28,
+
+  #[event(input = "UpdateAIPromptPB", output = "AIPromptPB")]
+  UpdateAIPrompt = 29,
+
+  #[event(input = "AIPromptIdPB")]
+  DeleteAIPrompt = 30,
+
+  /// Export the prompts as JSON
+  #[event(input = "ExportAIPromptsPB", output = "AIPromptsDataPB")]
+  ExportAIPrompts = 31,
+
+  /// Import the prompts from the JSON that is exported by [AIEvent::ExportAIPrompts]
+  #[event(input = "ImportAIPromptsPB", output = "RepeatedAIPromptPB")]
+  ImportAIPrompts = 32,
+
+  /// Search the messages of all the chats that are stored locally
+  #[event(
+    input = "SearchChatMessagesPB",
+    output = "RepeatedChatMessageSearchResultPB"
+  )]
+  SearchChatMessages = 33,
+
+  /// Create a document with the questions and answers of the chat
+  #[event(input = "ExportChatToDocumentPB", output = "ChatDocumentPB")]
+  ExportChatToDocument = 34,
+
+  /// Send the edited question as a branch of the original question, the answer is streamed the
+  /// same as [AIEvent::StreamMessage]. Only supported when the chat is answered by the model
+  /// endpoint.
+  #[event(input = "EditChatQuestionPB", output = "ChatMessagePB")]
+  EditChatQuestion = 35,
+
+  #[event(input = "ChatId", output = "ChatThreadPB")]
+  LoadActiveChatThread = 36,
+
+  #[event(input = "ChatMessageIdPB", output = "ChatMessageBranchesPB")]
+  GetChatMessageBranches = 37,
+
+  #[event(input = "ChatMessageIdPB", output = "ChatThreadPB")]
+  SwitchChatBranch = 38,
+
+  /// Index a document or a database with the local AI, the answers of the chat cite its blocks
+  /// or rows
+  #[event(input = "ChatSourcePayloadPB", output = "ChatSourcePB")]
+  AttachChatSource = 39,
+
+  #[event(input = "ChatSourcePayloadPB")]
+  DetachChatSource = 40,
+
+  #[event(input = "ChatId", output = "RepeatedChatSourcePB")]
+  GetChatSources = 41,
 }
@@ -3,11 +3,16 @@ pub mod event_map;
 
 pub mod ai_manager;
 mod chat;
+mod chat_branch;
+mod chat_history;
+mod chat_source;
 mod completion;
 pub mod entities;
 mod local_ai;
 mod middleware;
 pub mod notification;
+pub mod openai_compatible;
+mod prompt_library;
 mod persistence;
 mod protobuf;
 mod stream_message;
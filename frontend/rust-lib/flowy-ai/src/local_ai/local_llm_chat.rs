@@ -4,6 +4,7 @@ use crate::local_ai::local_llm_resource::{LLMResourceService, LocalAIResourceCon
 use crate::notification::{make_notification, ChatNotification, APPFLOWY_AI_NOTIFICATION_KEY};
 use anyhow::Error;
 use appflowy_local_ai::chat_plugin::{AIPluginConfig, AppFlowyLocalAI};
+use appflowy_plugin::error::PluginError;
 use appflowy_plugin::manager::PluginManager;
 use appflowy_plugin::util::is_apple_silicon;
 use flowy_ai_pub::cloud::{
@@ -20,12 +21,13 @@ use crate::stream_message::StreamMessage;
 use arc_swap::ArcSwapOption;
 use futures_util::SinkExt;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::pin::Pin;
+use std::sync::{Arc, Weak};
 use tokio::select;
-use tokio_stream::StreamExt;
+use tokio_stream::{Stream, StreamExt};
 use tracing::{debug, error, info, instrument, trace, warn};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -43,6 +45,7 @@ const APPFLOWY_LOCAL_AI_ENABLED: &str = "appflowy_local_ai_enabled";
 const APPFLOWY_LOCAL_AI_CHAT_ENABLED: &str = "appflowy_local_ai_chat_enabled";
 const APPFLOWY_LOCAL_AI_CHAT_RAG_ENABLED: &str = "appflowy_local_ai_chat_rag_enabled";
 const LOCAL_AI_SETTING_KEY: &str = "appflowy_local_ai_setting:v0";
+const APPFLOWY_LOCAL_AI_CHAT_INDEX: &str = "appflowy_local_ai_chat_index";
 
 pub struct LocalAIController {
   local_ai: Arc<AppFlowyLocalAI>,
@@ -246,33 +249,197 @@ impl LocalAIController {
     self
       .current_chat_id
       .store(Some(Arc::new(chat_id.to_string())));
-    let chat_id = chat_id.to_string();
+    self.create_plugin_chat(self.plugin_chat_id(chat_id));
+  }
+
+  pub fn close_chat(&self, chat_id: &str) {
+    self.close_plugin_chat(self.plugin_chat_id(chat_id));
+  }
+
+  fn create_plugin_chat(&self, plugin_chat_id: String) {
     let weak_ctrl = Arc::downgrade(&self.local_ai);
     tokio::spawn(async move {
       if let Some(ctrl) = weak_ctrl.upgrade() {
-        if let Err(err) = ctrl.create_chat(&chat_id).await {
+        if let Err(err) = ctrl.create_chat(&plugin_chat_id).await {
           error!("[AI Plugin] failed to open chat: {:?}", err);
         }
       }
     });
   }
 
-  pub fn close_chat(&self, chat_id: &str) {
+  fn close_plugin_chat(&self, plugin_chat_id: String) {
     if !self.is_running() {
       return;
     }
-    info!("[AI Plugin] notify close chat: {}", chat_id);
+    info!("[AI Plugin] notify close chat: {}", plugin_chat_id);
     let weak_ctrl = Arc::downgrade(&self.local_ai);
-    let chat_id = chat_id.to_string();
     tokio::spawn(async move {
       if let Some(ctrl) = weak_ctrl.upgrade() {
-        if let Err(err) = ctrl.close_chat(&chat_id).await {
+        if let Err(err) = ctrl.close_chat(&plugin_chat_id).await {
           error!("[AI Plugin] failed to close chat: {:?}", err);
         }
       }
     });
   }
 
+  /// Returns the id of the chat in the plugin, which changes each time the index of the chat is
+  /// reset. See [ChatIndexState].
+  pub fn plugin_chat_id(&self, chat_id: &str) -> String {
+    self
+      .chat_index_state(chat_id)
+      .plugin_chat_id
+      .unwrap_or_else(|| chat_id.to_string())
+  }
+
+  /// Index a file or a text that is attached to a question of the chat. It's recorded so that it
+  /// can be indexed again when the index of the chat is rebuilt.
+  pub async fn index_chat_file(
+    &self,
+    chat_id: &str,
+    file_path: Option<PathBuf>,
+    content: Option<String>,
+    metadata: Option<HashMap<String, Value>>,
+  ) -> Result<(), PluginError> {
+    self
+      .local_ai
+      .index_file(
+        &self.plugin_chat_id(chat_id),
+        file_path.clone(),
+        content.clone(),
+        metadata.clone(),
+      )
+      .await?;
+    let mut state = self.chat_index_state(chat_id);
+    state.files.push(IndexedChatFile {
+      file_path,
+      content,
+      metadata,
+    });
+    self.set_chat_index_state(chat_id, &state);
+    Ok(())
+  }
+
+  /// Index a chunk of a workspace source of the chat. The sources are not recorded here since the
+  /// chat sources index them again with their current content.
+  pub async fn index_chat_source(
+    &self,
+    chat_id: &str,
+    content: String,
+    metadata: HashMap<String, Value>,
+  ) -> Result<(), PluginError> {
+    self
+      .local_ai
+      .index_file(
+        &self.plugin_chat_id(chat_id),
+        None,
+        Some(content),
+        Some(metadata),
+      )
+      .await
+  }
+
+  /// Returns true if the index of the chat was reset and its content is not indexed yet.
+  pub fn is_chat_index_stale(&self, chat_id: &str) -> bool {
+    self.chat_index_state(chat_id).is_stale
+  }
+
+  /// Switch the chat to a new empty index in the plugin. The index stays stale until
+  /// [Self::rebuild_chat_index] is called after the sources are indexed again.
+  pub fn reset_chat_index(&self, chat_id: &str) {
+    let mut state = self.chat_index_state(chat_id);
+    let old_plugin_chat_id = state
+      .plugin_chat_id
+      .take()
+      .unwrap_or_else(|| chat_id.to_string());
+    let plugin_chat_id = format!("{}_{}", chat_id, uuid::Uuid::new_v4());
+    state.plugin_chat_id = Some(plugin_chat_id.clone());
+    state.is_stale = true;
+    self.set_chat_index_state(chat_id, &state);
+
+    let is_current_chat = self
+      .current_chat_id
+      .load()
+      .as_ref()
+      .is_some_and(|current_chat_id| current_chat_id.as_str() == chat_id);
+    if is_current_chat && self.is_running() {
+      self.close_plugin_chat(old_plugin_chat_id);
+      self.create_plugin_chat(plugin_chat_id);
+    }
+  }
+
+  /// Index the recorded files of the chat again after its index is reset, and mark the index as
+  /// up to date. The files that fail to index, like a removed file, are skipped.
+  pub async fn rebuild_chat_index(&self, chat_id: &str) {
+    let mut state = self.chat_index_state(chat_id);
+    let plugin_chat_id = state
+      .plugin_chat_id
+      .clone()
+      .unwrap_or_else(|| chat_id.to_string());
+    for file in &state.files {
+      if let Err(err) = self
+        .local_ai
+        .index_file(
+          &plugin_chat_id,
+          file.file_path.clone(),
+          file.content.clone(),
+          file.metadata.clone(),
+        )
+        .await
+      {
+        error!("[AI Plugin] failed to index file again: {:?}", err);
+      }
+    }
+    state.is_stale = false;
+    self.set_chat_index_state(chat_id, &state);
+  }
+
+  pub fn remove_chat_index(&self, chat_id: &str) {
+    self.store_preferences.remove(&chat_index_key(chat_id));
+  }
+
+  fn chat_index_state(&self, chat_id: &str) -> ChatIndexState {
+    self
+      .store_preferences
+      .get_object::<ChatIndexState>(&chat_index_key(chat_id))
+      .unwrap_or_default()
+  }
+
+  fn set_chat_index_state(&self, chat_id: &str, state: &ChatIndexState) {
+    if let Err(err) = self
+      .store_preferences
+      .set_object(&chat_index_key(chat_id), state)
+    {
+      error!(
+        "[AI Plugin] failed to save the index of chat:{}: {}",
+        chat_id, err
+      );
+    }
+  }
+
+  /// Stream the completion of the prompt with a temporary chat, since the plugin only answers the
+  /// questions of a chat. The chat is closed once the stream is finished or dropped, which is how a
+  /// cancelled completion stops the plugin.
+  #[instrument(level = "debug", skip_all)]
+  pub async fn stream_prompt(
+    &self,
+    prompt: &str,
+  ) -> Result<Pin<Box<dyn Stream<Item = Result<Value, PluginError>> + Send>>, PluginError> {
+    let chat_id = format!("prompt_{}", uuid::Uuid::new_v4());
+    self.local_ai.create_chat(&chat_id).await?;
+    let guard = TemporaryChatGuard {
+      local_ai: Arc::downgrade(&self.local_ai),
+      chat_id,
+    };
+    let stream = self
+      .local_ai
+      .stream_question(&guard.chat_id, prompt, json!([]))
+      .await?;
+    Ok(Box::pin(stream.map(move |value| {
+      let _guard = &guard;
+      value
+    })))
+  }
+
   pub async fn select_local_llm(&self, llm_id: i64) -> FlowyResult<LocalModelResourcePB> {
     if !self.is_enabled() {
       return Err(FlowyError::local_ai_unavailable());
@@ -490,7 +657,7 @@ impl LocalAIController {
       .await;
 
     let result = self
-      .index_file(chat_id, file_path, content, Some(index_metadata.clone()))
+      .index_chat_file(chat_id, file_path, content, Some(index_metadata.clone()))
       .await;
     match result {
       Ok(_) => {
@@ -601,3 +768,47 @@ impl LLMResourceService for LLMResourceServiceImpl {
 fn local_ai_enabled_key(workspace_id: &str) -> String {
   format!("{}:{}", APPFLOWY_LOCAL_AI_ENABLED, workspace_id)
 }
+
+fn chat_index_key(chat_id: &str) -> String {
+  format!("{}:{}", APPFLOWY_LOCAL_AI_CHAT_INDEX, chat_id)
+}
+
+/// The local AI plugin can't delete the chunks it has indexed. When some content must be removed
+/// from a chat, like a detached workspace source, the chat is switched to a new id in the plugin
+/// and its remaining content is indexed again under that id.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ChatIndexState {
+  /// The id of the chat in the plugin. None for the id of the chat itself.
+  plugin_chat_id: Option<String>,
+  /// The files and the texts attached to the questions of the chat
+  files: Vec<IndexedChatFile>,
+  /// True if the index was reset and the content of the chat is not indexed again yet
+  is_stale: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedChatFile {
+  file_path: Option<PathBuf>,
+  content: Option<String>,
+  metadata: Option<HashMap<String, Value>>,
+}
+
+/// Closes the temporary chat of a prompt completion when dropped.
+struct TemporaryChatGuard {
+  local_ai: Weak<AppFlowyLocalAI>,
+  chat_id: String,
+}
+
+impl Drop for TemporaryChatGuard {
+  fn drop(&mut self) {
+    let weak_ctrl = self.local_ai.clone();
+    let chat_id = std::mem::take(&mut self.chat_id);
+    tokio::spawn(async move {
+      if let Some(ctrl) = weak_ctrl.upgrade() {
+        if let Err(err) = ctrl.close_chat(&chat_id).await {
+          error!("[AI Plugin] failed to close chat: {:?}", err);
+        }
+      }
+    });
+  }
+}
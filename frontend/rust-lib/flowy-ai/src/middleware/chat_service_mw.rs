@@ -1,15 +1,18 @@
 use crate::ai_manager::AIUserService;
+use crate::chat_source::retain_current_citations;
 use crate::entities::{ChatStatePB, ModelTypePB};
 use crate::local_ai::local_llm_chat::LocalAIController;
 use crate::notification::{make_notification, ChatNotification, APPFLOWY_AI_NOTIFICATION_KEY};
-use crate::persistence::{select_single_message, ChatMessageTable};
+use crate::openai_compatible::OpenAICompatibleProvider;
+use crate::persistence::{select_chat_sources, select_single_message, ChatMessageTable};
 use appflowy_plugin::error::PluginError;
+use bytes::Bytes;
 use std::collections::HashMap;
 
 use flowy_ai_pub::cloud::{
   ChatCloudService, ChatMessage, ChatMessageMetadata, ChatMessageType, CompletionType,
-  LocalAIConfig, MessageCursor, RelatedQuestion, RepeatedChatMessage, RepeatedRelatedQuestion,
-  StreamAnswer, StreamComplete, SubscriptionPlan,
+  LocalAIConfig, MessageCursor, QuestionStreamValue, RelatedQuestion, RepeatedChatMessage,
+  RepeatedRelatedQuestion, StreamAnswer, StreamComplete, SubscriptionPlan,
 };
 use flowy_error::{FlowyError, FlowyResult};
 use futures::{stream, Sink, StreamExt, TryStreamExt};
@@ -28,6 +31,7 @@ pub struct AICloudServiceMiddleware {
   cloud_service: Arc<dyn ChatCloudService>,
   user_service: Arc<dyn AIUserService>,
   local_llm_controller: Arc<LocalAIController>,
+  openai_compatible: Arc<OpenAICompatibleProvider>,
   storage_service: Weak<dyn StorageService>,
 }
 
@@ -36,16 +40,30 @@ impl AICloudServiceMiddleware {
     user_service: Arc<dyn AIUserService>,
     cloud_service: Arc<dyn ChatCloudService>,
     local_llm_controller: Arc<LocalAIController>,
+    openai_compatible: Arc<OpenAICompatibleProvider>,
     storage_service: Weak<dyn StorageService>,
   ) -> Self {
     Self {
       user_service,
       cloud_service,
       local_llm_controller,
+      openai_compatible,
       storage_service,
     }
   }
 
+  /// Stop the streaming answer of the chat that is generated by the model endpoint. The answers
+  /// of the other providers are stopped by the chat itself.
+  pub fn stop_stream(&self, chat_id: &str) {
+    self.openai_compatible.stop_stream(chat_id);
+  }
+
+  /// The model endpoint answers with the messages along the branch of the question. The cloud and
+  /// the local AI keep their own history of the chat, which doesn't follow the edited questions.
+  pub fn is_branching_supported(&self) -> bool {
+    self.openai_compatible.is_enabled()
+  }
+
   pub fn is_local_ai_enabled(&self) -> bool {
     self.local_llm_controller.is_enabled()
   }
@@ -87,6 +105,18 @@ impl AICloudServiceMiddleware {
     Ok(row)
   }
 
+  /// Returns the version of each workspace source that is attached to the chat
+  fn get_source_versions(&self, chat_id: &str) -> FlowyResult<HashMap<String, i64>> {
+    let uid = self.user_service.user_id()?;
+    let sources = select_chat_sources(self.user_service.sqlite_connection(uid)?, chat_id)?;
+    Ok(
+      sources
+        .into_iter()
+        .map(|source| (source.view_id, source.version))
+        .collect(),
+    )
+  }
+
   fn handle_plugin_error(&self, err: PluginError) {
     if matches!(
       err,
@@ -153,14 +183,33 @@ impl ChatCloudService for AICloudServiceMiddleware {
     chat_id: &str,
     question_id: i64,
   ) -> Result<StreamAnswer, FlowyError> {
-    if self.local_llm_controller.is_running() {
+    if self.openai_compatible.is_enabled() {
+      let row = self.get_message_record(question_id)?;
+      self.openai_compatible.stream_answer(chat_id, &row).await
+    } else if self.local_llm_controller.is_running() {
       let row = self.get_message_record(question_id)?;
       match self
         .local_llm_controller
-        .stream_question(chat_id, &row.content, json!([]))
+        .stream_question(
+          &self.local_llm_controller.plugin_chat_id(chat_id),
+          &row.content,
+          json!([]),
+        )
         .await
       {
-        Ok(stream) => Ok(QuestionStream::new(stream).boxed()),
+        Ok(stream) => {
+          let versions = self.get_source_versions(chat_id)?;
+          Ok(
+            QuestionStream::new(stream)
+              .map_ok(move |value| match value {
+                QuestionStreamValue::Metadata { value } => QuestionStreamValue::Metadata {
+                  value: retain_current_citations(value, &versions),
+                },
+                value => value,
+              })
+              .boxed(),
+          )
+        },
         Err(err) => {
           self.handle_plugin_error(err);
           Ok(stream::once(async { Err(FlowyError::local_ai_unavailable()) }).boxed())
@@ -180,11 +229,18 @@ impl ChatCloudService for AICloudServiceMiddleware {
     chat_id: &str,
     question_message_id: i64,
   ) -> Result<ChatMessage, FlowyError> {
-    if self.local_llm_controller.is_running() {
+    if self.openai_compatible.is_enabled() {
+      let row = self.get_message_record(question_message_id)?;
+      let answer = self.openai_compatible.get_answer(chat_id, &row).await?;
+      self
+        .cloud_service
+        .create_answer(workspace_id, chat_id, &answer, question_message_id, None)
+        .await
+    } else if self.local_llm_controller.is_running() {
       let content = self.get_message_record(question_message_id)?.content;
       match self
         .local_llm_controller
-        .ask_question(chat_id, &content)
+        .ask_question(&self.local_llm_controller.plugin_chat_id(chat_id), &content)
         .await
       {
         Ok(answer) => {
@@ -227,10 +283,15 @@ impl ChatCloudService for AICloudServiceMiddleware {
     chat_id: &str,
     message_id: i64,
   ) -> Result<RepeatedRelatedQuestion, FlowyError> {
-    if self.local_llm_controller.is_running() {
+    if self.openai_compatible.is_enabled() {
+      self
+        .openai_compatible
+        .get_related_questions(chat_id, message_id)
+        .await
+    } else if self.local_llm_controller.is_running() {
       let questions = self
         .local_llm_controller
-        .get_related_question(chat_id)
+        .get_related_question(&self.local_llm_controller.plugin_chat_id(chat_id))
         .await
         .map_err(|err| FlowyError::local_ai().with_context(err))?;
       trace!("LocalAI related questions: {:?}", questions);
@@ -258,7 +319,12 @@ impl ChatCloudService for AICloudServiceMiddleware {
     text: &str,
     complete_type: CompletionType,
   ) -> Result<StreamComplete, FlowyError> {
-    if self.local_llm_controller.is_running() {
+    if self.openai_compatible.is_enabled() {
+      self
+        .openai_compatible
+        .stream_complete(text, complete_type)
+        .await
+    } else if self.local_llm_controller.is_running() {
       match self
         .local_llm_controller
         .complete_text(text, complete_type as u8)
@@ -282,6 +348,38 @@ impl ChatCloudService for AICloudServiceMiddleware {
     }
   }
 
+  async fn stream_complete_with_prompt(
+    &self,
+    workspace_id: &str,
+    prompt: &str,
+  ) -> Result<StreamComplete, FlowyError> {
+    if self.openai_compatible.is_enabled() {
+      self.openai_compatible.stream_prompt(prompt).await
+    } else if self.local_llm_controller.is_running() {
+      match self.local_llm_controller.stream_prompt(prompt).await {
+        Ok(stream) => Ok(
+          QuestionStream::new(stream)
+            .try_filter_map(|value| async move {
+              match value {
+                QuestionStreamValue::Answer { value } => Ok(Some(Bytes::from(value))),
+                _ => Ok(None),
+              }
+            })
+            .boxed(),
+        ),
+        Err(err) => {
+          self.handle_plugin_error(err);
+          Ok(stream::once(async { Err(FlowyError::local_ai_unavailable()) }).boxed())
+        },
+      }
+    } else {
+      self
+        .cloud_service
+        .stream_complete_with_prompt(workspace_id, prompt)
+        .await
+    }
+  }
+
   async fn index_file(
     &self,
     workspace_id: &str,
@@ -292,7 +390,7 @@ impl ChatCloudService for AICloudServiceMiddleware {
     if self.local_llm_controller.is_running() {
       self
         .local_llm_controller
-        .index_file(chat_id, Some(file_path.to_path_buf()), None, metadata)
+        .index_chat_file(chat_id, Some(file_path.to_path_buf()), None, metadata)
         .await
         .map_err(|err| FlowyError::local_ai().with_context(err))?;
       Ok(())
@@ -0,0 +1,423 @@
+use std::collections::VecDeque;
+
+use flowy_error::{FlowyError, FlowyResult};
+use futures::stream::BoxStream;
+use futures::{stream, StreamExt};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::openai_compatible::sse::SSEDecoder;
+
+/// The endpoint that implements the chat completions API of OpenAI, such as Ollama, llama.cpp
+/// server, LM Studio or vLLM.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OpenAICompatibleConfig {
+  pub enabled: bool,
+  /// The base URL of the API, for example `http://localhost:11434/v1`. The requests are sent to
+  /// `{base_url}/chat/completions`.
+  pub base_url: String,
+  /// Optional. Most of the local servers don't require an API key.
+  pub api_key: String,
+  pub model: String,
+}
+
+impl OpenAICompatibleConfig {
+  pub fn is_valid(&self) -> bool {
+    !self.base_url.trim().is_empty() && !self.model.trim().is_empty()
+  }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct ChatCompletionMessage {
+  pub role: String,
+  pub content: String,
+}
+
+impl ChatCompletionMessage {
+  pub fn system<T: ToString>(content: T) -> Self {
+    Self {
+      role: "system".to_string(),
+      content: content.to_string(),
+    }
+  }
+
+  pub fn user<T: ToString>(content: T) -> Self {
+    Self {
+      role: "user".to_string(),
+      content: content.to_string(),
+    }
+  }
+
+  pub fn assistant<T: ToString>(content: T) -> Self {
+    Self {
+      role: "assistant".to_string(),
+      content: content.to_string(),
+    }
+  }
+}
+
+pub type ChatCompletionStream = BoxStream<'static, Result<String, FlowyError>>;
+
+pub struct OpenAICompatibleClient {
+  http: Client,
+  config: OpenAICompatibleConfig,
+}
+
+impl OpenAICompatibleClient {
+  pub fn new(config: OpenAICompatibleConfig) -> Self {
+    Self {
+      http: Client::new(),
+      config,
+    }
+  }
+
+  fn request(
+    &self,
+    stream: bool,
+    messages: &[ChatCompletionMessage],
+  ) -> FlowyResult<RequestBuilder> {
+    let url = format!(
+      "{}/chat/completions",
+      self.config.base_url.trim().trim_end_matches('/')
+    );
+    let body = json!({
+      "model": self.config.model,
+      "messages": messages,
+      "stream": stream,
+    });
+
+    let mut request = self
+      .http
+      .post(url)
+      .header(reqwest::header::CONTENT_TYPE, "application/json")
+      .body(serde_json::to_vec(&body)?);
+    if stream {
+      request = request.header(reqwest::header::ACCEPT, "text/event-stream");
+    }
+    if !self.config.api_key.is_empty() {
+      request = request.bearer_auth(&self.config.api_key);
+    }
+    Ok(request)
+  }
+
+  async fn send(&self, request: RequestBuilder) -> FlowyResult<Response> {
+    let response = request.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+      let text = response.text().await.unwrap_or_default();
+      return Err(FlowyError::http().with_context(format!(
+        "The model endpoint responded with {}: {}",
+        status,
+        error_message_from_body(&text)
+      )));
+    }
+    Ok(response)
+  }
+
+  /// Returns the whole answer of the model at once.
+  pub async fn chat(&self, messages: &[ChatCompletionMessage]) -> FlowyResult<String> {
+    let response = self.send(self.request(false, messages)?).await?;
+    let bytes = response.bytes().await?;
+    let response = serde_json::from_slice::<ChatCompletionResponse>(&bytes)?;
+    let content = response
+      .choices
+      .into_iter()
+      .next()
+      .and_then(|choice| choice.message.content)
+      .unwrap_or_default();
+    Ok(content)
+  }
+
+  /// Streams the answer of the model. The stream ends when the server sends `[DONE]` or closes the
+  /// connection. Dropping the stream closes the connection, which stops the generation on the
+  /// server.
+  pub async fn stream_chat(
+    &self,
+    messages: &[ChatCompletionMessage],
+  ) -> FlowyResult<ChatCompletionStream> {
+    let response = self.send(self.request(true, messages)?).await?;
+    let state = StreamState {
+      response,
+      decoder: SSEDecoder::default(),
+      pending: VecDeque::new(),
+      done: false,
+    };
+    let stream = stream::try_unfold(state, |mut state| async move {
+      loop {
+        if let Some(content) = state.pending.pop_front() {
+          return Ok::<_, FlowyError>(Some((content, state)));
+        }
+        if state.done {
+          return Ok(None);
+        }
+
+        match state.response.chunk().await? {
+          None => state.done = true,
+          Some(chunk) => {
+            for data in state.decoder.decode(&chunk) {
+              if data == "[DONE]" {
+                state.done = true;
+                break;
+              }
+              if let Some(content) = parse_chunk(&data)? {
+                state.pending.push_back(content);
+              }
+            }
+          },
+        }
+      }
+    });
+    Ok(stream.boxed())
+  }
+}
+
+struct StreamState {
+  response: Response,
+  decoder: SSEDecoder,
+  pending: VecDeque<String>,
+  done: bool,
+}
+
+fn parse_chunk(data: &str) -> FlowyResult<Option<String>> {
+  let chunk = serde_json::from_str::<ChatCompletionChunk>(data)?;
+  if let Some(error) = chunk.error {
+    return Err(FlowyError::http().with_context(error.message));
+  }
+  Ok(
+    chunk
+      .choices
+      .into_iter()
+      .next()
+      .and_then(|choice| choice.delta.content)
+      .filter(|content| !content.is_empty()),
+  )
+}
+
+fn error_message_from_body(body: &str) -> String {
+  serde_json::from_str::<ErrorResponse>(body)
+    .map(|resp| resp.error.message)
+    .unwrap_or_else(|_| body.to_string())
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionResponse {
+  #[serde(default)]
+  choices: Vec<ChatCompletionChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionChoice {
+  message: ChatCompletionChoiceMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionChoiceMessage {
+  /// Null when the model replies without a text, like a refusal or a tool call
+  #[serde(default)]
+  content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionChunk {
+  #[serde(default)]
+  choices: Vec<ChatCompletionChunkChoice>,
+  #[serde(default)]
+  error: Option<ErrorBody>,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionChunkChoice {
+  #[serde(default)]
+  delta: ChatCompletionDelta,
+}
+
+#[derive(Deserialize, Default)]
+struct ChatCompletionDelta {
+  #[serde(default)]
+  content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+  error: ErrorBody,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+  #[serde(default)]
+  message: String,
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use futures::StreamExt;
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+  use tokio::net::TcpListener;
+  use tokio_util::sync::CancellationToken;
+
+  use super::*;
+
+  /// Starts a server that responds to one request with the given chunks. When `hold_open` is true,
+  /// the connection is kept open after the chunks are sent.
+  async fn mock_server(
+    status: &'static str,
+    content_type: &'static str,
+    chunks: Vec<String>,
+    hold_open: bool,
+  ) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+      let (mut socket, _) = listener.accept().await.unwrap();
+      read_request(&mut socket).await;
+
+      let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nTransfer-Encoding: chunked\r\n\r\n",
+        status, content_type
+      );
+      socket.write_all(head.as_bytes()).await.unwrap();
+      for chunk in chunks {
+        let chunk = format!("{:x}\r\n{}\r\n", chunk.len(), chunk);
+        socket.write_all(chunk.as_bytes()).await.unwrap();
+        socket.flush().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+      }
+      if hold_open {
+        tokio::time::sleep(Duration::from_secs(30)).await;
+      }
+      let _ = socket.write_all(b"0\r\n\r\n").await;
+    });
+    format!("http://{}/v1/", addr)
+  }
+
+  async fn read_request(socket: &mut tokio::net::TcpStream) {
+    let mut request = vec![];
+    let mut buf = [0u8; 1024];
+    loop {
+      let n = socket.read(&mut buf).await.unwrap();
+      request.extend_from_slice(&buf[..n]);
+      let text = String::from_utf8_lossy(&request).to_string();
+      if let Some(pos) = text.find("\r\n\r\n") {
+        let content_length = text
+          .lines()
+          .find_map(|line| {
+            line
+              .to_lowercase()
+              .strip_prefix("content-length:")
+              .map(|value| value.trim().parse::<usize>().unwrap_or(0))
+          })
+          .unwrap_or(0);
+        if request.len() >= pos + 4 + content_length {
+          return;
+        }
+      }
+      if n == 0 {
+        return;
+      }
+    }
+  }
+
+  fn sse_chunk(content: &str) -> String {
+    format!(
+      "data: {}\n\n",
+      json!({"choices": [{"index": 0, "delta": {"content": content}}]})
+    )
+  }
+
+  fn client(base_url: String) -> OpenAICompatibleClient {
+    OpenAICompatibleClient::new(OpenAICompatibleConfig {
+      enabled: true,
+      base_url,
+      api_key: "test".to_string(),
+      model: "llama3".to_string(),
+    })
+  }
+
+  #[tokio::test]
+  async fn stream_chat_test() {
+    let base_url = mock_server(
+      "200 OK",
+      "text/event-stream",
+      vec![
+        // The first chunk of some servers only carries the role
+        format!(
+          "data: {}\n\n",
+          json!({"choices": [{"index": 0, "delta": {"role": "assistant", "content": null}}]})
+        ),
+        sse_chunk("Hello"),
+        sse_chunk(" world"),
+        "data: [DONE]\n\n".to_string(),
+      ],
+      false,
+    )
+    .await;
+    let stream = client(base_url)
+      .stream_chat(&[ChatCompletionMessage::user("hi")])
+      .await
+      .unwrap();
+    let answer = stream
+      .map(|value| value.unwrap())
+      .collect::<Vec<_>>()
+      .await
+      .join("");
+    assert_eq!(answer, "Hello world");
+  }
+
+  #[tokio::test]
+  async fn chat_test() {
+    let body =
+      json!({"choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}}]});
+    let base_url = mock_server("200 OK", "application/json", vec![body.to_string()], false).await;
+    let answer = client(base_url)
+      .chat(&[ChatCompletionMessage::user("hi")])
+      .await
+      .unwrap();
+    assert_eq!(answer, "Hi");
+  }
+
+  #[tokio::test]
+  async fn endpoint_error_test() {
+    let body = json!({"error": {"message": "invalid api key"}});
+    let base_url = mock_server(
+      "401 Unauthorized",
+      "application/json",
+      vec![body.to_string()],
+      false,
+    )
+    .await;
+    let err = client(base_url)
+      .stream_chat(&[ChatCompletionMessage::user("hi")])
+      .await
+      .err()
+      .unwrap();
+    assert!(err.msg.contains("invalid api key"), "{}", err);
+  }
+
+  #[tokio::test]
+  async fn cancel_stream_test() {
+    let base_url = mock_server(
+      "200 OK",
+      "text/event-stream",
+      vec![sse_chunk("Hello")],
+      true,
+    )
+    .await;
+    let token = CancellationToken::new();
+    let mut stream = client(base_url)
+      .stream_chat(&[ChatCompletionMessage::user("hi")])
+      .await
+      .unwrap()
+      .take_until(token.clone().cancelled_owned());
+    assert_eq!(stream.next().await.unwrap().unwrap(), "Hello");
+
+    // The server keeps the connection open, the stream ends right after it's cancelled.
+    token.cancel();
+    let next = tokio::time::timeout(Duration::from_secs(5), stream.next())
+      .await
+      .unwrap();
+    assert!(next.is_none());
+  }
+}
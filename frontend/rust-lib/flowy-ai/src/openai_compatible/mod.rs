@@ -0,0 +1,6 @@
+mod client;
+mod provider;
+mod sse;
+
+pub use client::*;
+pub use provider::*;
@@ -0,0 +1,356 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use bytes::Bytes;
+use dashmap::DashMap;
+use flowy_ai_pub::cloud::{
+  CompletionType, QuestionStreamValue, RelatedQuestion, RepeatedRelatedQuestion, StreamAnswer,
+  StreamComplete,
+};
+use flowy_encrypt::{decrypt_text, encrypt_text};
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::kv::KVStorePreferences;
+use futures::{StreamExt, TryStreamExt};
+use tokio_util::sync::CancellationToken;
+use tracing::trace;
+
+use crate::ai_manager::AIUserService;
+use crate::chat_branch::load_chat_message_tree;
+use crate::openai_compatible::client::{
+  ChatCompletionMessage, OpenAICompatibleClient, OpenAICompatibleConfig,
+};
+use crate::persistence::ChatMessageTable;
+
+/// The config whose API key is encrypted
+const OPENAI_COMPATIBLE_CONFIG_KEY: &str = "appflowy_openai_compatible_config:v1";
+const LEGACY_OPENAI_COMPATIBLE_CONFIG_KEY: &str = "appflowy_openai_compatible_config:v0";
+/// The number of previous messages that are sent to the model as the context of the question.
+const CHAT_HISTORY_LIMIT: usize = 10;
+const RELATED_QUESTION_LIMIT: usize = 3;
+
+const CHAT_SYSTEM_PROMPT: &str =
+  "You are a helpful assistant in AppFlowy, a note-taking and project management app. Answer \
+   the question of the user concisely and format the answer with Markdown.";
+const RELATED_QUESTION_PROMPT: &str =
+  "Based on the conversation above, suggest three short follow-up questions the user might ask \
+   next. Reply with one question per line, without numbering or any other text.";
+
+/// Talks to a user-provided model endpoint that implements the chat completions API of OpenAI.
+/// The endpoint is configured per workspace.
+pub struct OpenAICompatibleProvider {
+  user_service: Arc<dyn AIUserService>,
+  store_preferences: Arc<KVStorePreferences>,
+  /// The id and the cancellation token of the streaming answer of each chat.
+  streams: Arc<DashMap<String, (u64, CancellationToken)>>,
+  next_stream_id: AtomicU64,
+}
+
+impl OpenAICompatibleProvider {
+  pub fn new(
+    user_service: Arc<dyn AIUserService>,
+    store_preferences: Arc<KVStorePreferences>,
+  ) -> Self {
+    Self {
+      user_service,
+      store_preferences,
+      streams: Default::default(),
+      next_stream_id: AtomicU64::new(0),
+    }
+  }
+
+  /// Returns the config of the current workspace. The API key is stored encrypted with the secret
+  /// of the user, and the config that was stored with a plain API key is encrypted when it's read.
+  pub fn get_config(&self) -> FlowyResult<OpenAICompatibleConfig> {
+    let workspace_id = self.user_service.workspace_id()?;
+    let key = config_key(&workspace_id);
+    if let Some(mut config) = self
+      .store_preferences
+      .get_object::<OpenAICompatibleConfig>(&key)
+    {
+      if !config.api_key.is_empty() {
+        config.api_key = decrypt_text(&config.api_key, &self.user_service.encrypt_secret()?)
+          .map_err(|err| FlowyError::internal().with_context(err))?;
+      }
+      return Ok(config);
+    }
+
+    let legacy_key = format!("{}:{}", LEGACY_OPENAI_COMPATIBLE_CONFIG_KEY, workspace_id);
+    match self
+      .store_preferences
+      .get_object::<OpenAICompatibleConfig>(&legacy_key)
+    {
+      Some(config) => {
+        self.save_config(&config)?;
+        self.store_preferences.remove(&legacy_key);
+        Ok(config)
+      },
+      None => Ok(OpenAICompatibleConfig::default()),
+    }
+  }
+
+  pub fn set_config(&self, config: OpenAICompatibleConfig) -> FlowyResult<()> {
+    if config.enabled && !config.is_valid() {
+      return Err(
+        FlowyError::invalid_data().with_context("The base URL and the model name are required"),
+      );
+    }
+    self.save_config(&config)
+  }
+
+  fn save_config(&self, config: &OpenAICompatibleConfig) -> FlowyResult<()> {
+    let key = config_key(&self.user_service.workspace_id()?);
+    let mut config = config.clone();
+    if !config.api_key.is_empty() {
+      config.api_key = encrypt_text(&config.api_key, &self.user_service.encrypt_secret()?)
+        .map_err(|err| FlowyError::internal().with_context(err))?;
+    }
+    self.store_preferences.set_object(&key, &config)?;
+    Ok(())
+  }
+
+  /// Indicate whether the requests of the current workspace are sent to the model endpoint.
+  pub fn is_enabled(&self) -> bool {
+    self
+      .get_config()
+      .map(|config| config.enabled && config.is_valid())
+      .unwrap_or(false)
+  }
+
+  fn client(&self) -> FlowyResult<OpenAICompatibleClient> {
+    let config = self.get_config()?;
+    if !config.is_valid() {
+      return Err(FlowyError::invalid_data().with_context("The model endpoint is not configured"));
+    }
+    Ok(OpenAICompatibleClient::new(config))
+  }
+
+  /// Load the messages on the branch of the given message, oldest first. The given message is
+  /// included if `include_message` is true.
+  fn chat_history(
+    &self,
+    chat_id: &str,
+    message_id: i64,
+    include_message: bool,
+  ) -> FlowyResult<Vec<ChatCompletionMessage>> {
+    let uid = self.user_service.user_id()?;
+    let tree = load_chat_message_tree(self.user_service.as_ref(), uid, chat_id)?;
+    let mut messages = tree.ancestors_of(message_id);
+    if include_message {
+      messages.extend(tree.get(message_id));
+    }
+    let skip = messages.len().saturating_sub(CHAT_HISTORY_LIMIT);
+    Ok(
+      messages
+        .into_iter()
+        .skip(skip)
+        .map(|message| to_completion_message(message.clone()))
+        .collect(),
+    )
+  }
+
+  fn question_messages(
+    &self,
+    chat_id: &str,
+    question: &ChatMessageTable,
+  ) -> FlowyResult<Vec<ChatCompletionMessage>> {
+    let mut messages = vec![ChatCompletionMessage::system(CHAT_SYSTEM_PROMPT)];
+    messages.extend(self.chat_history(chat_id, question.message_id, false)?);
+    messages.push(ChatCompletionMessage::user(&question.content));
+    Ok(messages)
+  }
+
+  pub async fn get_answer(
+    &self,
+    chat_id: &str,
+    question: &ChatMessageTable,
+  ) -> FlowyResult<String> {
+    let messages = self.question_messages(chat_id, question)?;
+    self.client()?.chat(&messages).await
+  }
+
+  pub async fn stream_answer(
+    &self,
+    chat_id: &str,
+    question: &ChatMessageTable,
+  ) -> FlowyResult<StreamAnswer> {
+    let messages = self.question_messages(chat_id, question)?;
+    let stream = self.client()?.stream_chat(&messages).await?;
+    let token = CancellationToken::new();
+    let stream_id = self.next_stream_id.fetch_add(1, Ordering::Relaxed);
+    if let Some((_, old_token)) = self
+      .streams
+      .insert(chat_id.to_string(), (stream_id, token.clone()))
+    {
+      old_token.cancel();
+    }
+    let guard = StreamGuard {
+      streams: self.streams.clone(),
+      chat_id: chat_id.to_string(),
+      stream_id,
+    };
+    Ok(
+      stream
+        .take_until(token.cancelled_owned())
+        .map_ok(move |value| {
+          let _ = &guard;
+          QuestionStreamValue::Answer { value }
+        })
+        .boxed(),
+    )
+  }
+
+  /// Stop the streaming answer of the chat. The connection to the endpoint is closed, so the
+  /// endpoint stops generating as well.
+  pub fn stop_stream(&self, chat_id: &str) {
+    if let Some((_, (_, token))) = self.streams.remove(chat_id) {
+      trace!(
+        "[OpenAICompatible] stop streaming answer of chat: {}",
+        chat_id
+      );
+      token.cancel();
+    }
+  }
+
+  pub async fn get_related_questions(
+    &self,
+    chat_id: &str,
+    message_id: i64,
+  ) -> FlowyResult<RepeatedRelatedQuestion> {
+    let mut messages = self.chat_history(chat_id, message_id, true)?;
+    messages.push(ChatCompletionMessage::user(RELATED_QUESTION_PROMPT));
+    let answer = self.client()?.chat(&messages).await?;
+    let items = parse_related_questions(&answer)
+      .into_iter()
+      .map(|content| RelatedQuestion {
+        content,
+        metadata: None,
+      })
+      .collect();
+    Ok(RepeatedRelatedQuestion { message_id, items })
+  }
+
+  pub async fn stream_complete(
+    &self,
+    text: &str,
+    complete_type: CompletionType,
+  ) -> FlowyResult<StreamComplete> {
+    let messages = vec![
+      ChatCompletionMessage::system(completion_prompt(&complete_type)),
+      ChatCompletionMessage::user(text),
+    ];
+    let stream = self.client()?.stream_chat(&messages).await?;
+    Ok(stream.map_ok(Bytes::from).boxed())
+  }
+
+  pub async fn stream_prompt(&self, prompt: &str) -> FlowyResult<StreamComplete> {
+    let stream = self
+      .client()?
+      .stream_chat(&[ChatCompletionMessage::user(prompt)])
+      .await?;
+    Ok(stream.map_ok(Bytes::from).boxed())
+  }
+}
+
+/// Removes the streaming answer from the streams of the provider once the stream is finished or
+/// dropped, unless a newer answer of the chat already replaced it.
+struct StreamGuard {
+  streams: Arc<DashMap<String, (u64, CancellationToken)>>,
+  chat_id: String,
+  stream_id: u64,
+}
+
+impl Drop for StreamGuard {
+  fn drop(&mut self) {
+    self.streams.remove_if(&self.chat_id, |_, (stream_id, _)| {
+      *stream_id == self.stream_id
+    });
+  }
+}
+
+fn config_key(workspace_id: &str) -> String {
+  format!("{}:{}", OPENAI_COMPATIBLE_CONFIG_KEY, workspace_id)
+}
+
+/// The answers reply to a question, the questions don't reply to anything.
+fn to_completion_message(message: ChatMessageTable) -> ChatCompletionMessage {
+  if message.reply_message_id.is_some() {
+    ChatCompletionMessage::assistant(message.content)
+  } else {
+    ChatCompletionMessage::user(message.content)
+  }
+}
+
+fn completion_prompt(complete_type: &CompletionType) -> &'static str {
+  match complete_type {
+    CompletionType::SpellingAndGrammar => {
+      "Fix the spelling and grammar of the text. Reply with the corrected text only."
+    },
+    CompletionType::MakeShorter => {
+      "Make the text shorter while keeping its meaning. Reply with the rewritten text only."
+    },
+    CompletionType::MakeLonger => {
+      "Make the text longer by adding relevant details. Reply with the rewritten text only."
+    },
+    CompletionType::ContinueWriting => {
+      "Continue writing after the text in the same tone and style. Reply with the continuation \
+       only."
+    },
+    _ => "Improve the writing of the text. Reply with the improved text only.",
+  }
+}
+
+fn parse_related_questions(answer: &str) -> Vec<String> {
+  answer
+    .lines()
+    .map(|line| {
+      line
+        .trim()
+        .trim_start_matches(|c: char| c.is_ascii_digit() || c == '.' || c == '-' || c == '*')
+        .trim()
+        .to_string()
+    })
+    .filter(|line| !line.is_empty())
+    .take(RELATED_QUESTION_LIMIT)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_related_questions_test() {
+    let answer =
+      "1. What is AppFlowy?\n\n- How to create a database?\n* Can I use it offline?\nMore";
+    assert_eq!(
+      parse_related_questions(answer),
+      vec![
+        "What is AppFlowy?".to_string(),
+        "How to create a database?".to_string(),
+        "Can I use it offline?".to_string(),
+      ]
+    );
+  }
+
+  #[test]
+  fn finished_stream_is_removed_test() {
+    let streams: Arc<DashMap<String, (u64, CancellationToken)>> = Default::default();
+    streams.insert("chat".to_string(), (1, CancellationToken::new()));
+    let old_guard = StreamGuard {
+      streams: streams.clone(),
+      chat_id: "chat".to_string(),
+      stream_id: 0,
+    };
+    // The guard of a replaced stream doesn't remove the newer stream
+    drop(old_guard);
+    assert!(streams.contains_key("chat"));
+
+    let guard = StreamGuard {
+      streams: streams.clone(),
+      chat_id: "chat".to_string(),
+      stream_id: 1,
+    };
+    drop(guard);
+    assert!(streams.is_empty());
+  }
+}
@@ -0,0 +1,71 @@
+/// Decodes the server-sent events of a streaming response. The events might be split across
+/// multiple chunks, so the incomplete event is kept until the rest of it arrives.
+#[derive(Default)]
+pub struct SSEDecoder {
+  /// The raw bytes of the incomplete event. They're only decoded once the event is complete,
+  /// because a multi-byte character might be split across chunks as well.
+  buffer: Vec<u8>,
+}
+
+impl SSEDecoder {
+  /// Returns the data of the complete events in the chunk.
+  pub fn decode(&mut self, chunk: &[u8]) -> Vec<String> {
+    // A '\r' byte is never part of a multi-byte character, so it's safe to drop it here.
+    self
+      .buffer
+      .extend(chunk.iter().filter(|byte| **byte != b'\r'));
+
+    let mut events = vec![];
+    while let Some(pos) = self.buffer.windows(2).position(|window| window == b"\n\n") {
+      let event = String::from_utf8_lossy(&self.buffer[..pos]).into_owned();
+      self.buffer.drain(..pos + 2);
+
+      // An event might contain multiple data lines, which are joined with a newline. The other
+      // fields, such as `event` and `id`, and the comments are ignored.
+      let data = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect::<Vec<_>>();
+      if !data.is_empty() {
+        events.push(data.join("\n"));
+      }
+    }
+    events
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn decode_split_event_test() {
+    let mut decoder = SSEDecoder::default();
+    assert!(decoder.decode(b"data: {\"a\":").is_empty());
+    assert_eq!(
+      decoder.decode(b"1}\n\ndata: [DONE]\n\n"),
+      vec!["{\"a\":1}".to_string(), "[DONE]".to_string()]
+    );
+  }
+
+  #[test]
+  fn decode_split_multi_byte_character_test() {
+    let mut decoder = SSEDecoder::default();
+    let event = "data: caf\u{e9} \u{1f600}\n\n".as_bytes();
+    // split inside the 'é' and inside the emoji
+    assert!(decoder.decode(&event[..10]).is_empty());
+    assert!(decoder.decode(&event[10..14]).is_empty());
+    assert_eq!(
+      decoder.decode(&event[14..]),
+      vec!["caf\u{e9} \u{1f600}".to_string()]
+    );
+  }
+
+  #[test]
+  fn decode_crlf_and_comment_test() {
+    let mut decoder = SSEDecoder::default();
+    let events = decoder.decode(b": keep-alive\r\n\r\nevent: message\r\ndata: hello\r\n\r\n");
+    assert_eq!(events, vec!["hello".to_string()]);
+  }
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use flowy_error::FlowyResult;
+use flowy_sqlite::{
+  diesel, insert_into,
+  query_dsl::*,
+  schema::{chat_active_branch_table, chat_message_branch_table},
+  DBConnection, ExpressionMethods, Identifiable, Insertable, Queryable,
+};
+
+#[derive(Clone, Debug, Queryable, Insertable, Identifiable)]
+#[diesel(table_name = chat_message_branch_table)]
+#[diesel(primary_key(message_id))]
+pub struct ChatMessageBranchTable {
+  pub message_id: i64,
+  pub chat_id: String,
+  /// None if the message is the first message of the chat
+  pub parent_message_id: Option<i64>,
+  pub created_at: i64,
+}
+
+#[derive(Clone, Debug, Queryable, Insertable, Identifiable)]
+#[diesel(table_name = chat_active_branch_table)]
+#[diesel(primary_key(chat_id, parent_message_id))]
+pub struct ChatActiveBranchTable {
+  pub chat_id: String,
+  /// 0 for the first messages of the chat
+  pub parent_message_id: i64,
+  pub message_id: i64,
+}
+
+/// The parent of a message never changes, so the existing record is kept.
+pub fn insert_chat_message_branch(
+  mut conn: DBConnection,
+  branch: &ChatMessageBranchTable,
+) -> FlowyResult<()> {
+  insert_into(chat_message_branch_table::table)
+    .values(branch)
+    .on_conflict(chat_message_branch_table::message_id)
+    .do_nothing()
+    .execute(&mut *conn)?;
+  Ok(())
+}
+
+pub fn select_chat_message_branches(
+  mut conn: DBConnection,
+  chat_id: &str,
+) -> FlowyResult<Vec<ChatMessageBranchTable>> {
+  let branches = chat_message_branch_table::dsl::chat_message_branch_table
+    .filter(chat_message_branch_table::chat_id.eq(chat_id))
+    .load::<ChatMessageBranchTable>(&mut *conn)?;
+  Ok(branches)
+}
+
+pub fn upsert_chat_active_branch(
+  mut conn: DBConnection,
+  active: &ChatActiveBranchTable,
+) -> FlowyResult<()> {
+  insert_into(chat_active_branch_table::table)
+    .values(active)
+    .on_conflict((
+      chat_active_branch_table::chat_id,
+      chat_active_branch_table::parent_message_id,
+    ))
+    .do_update()
+    .set(chat_active_branch_table::message_id.eq(active.message_id))
+    .execute(&mut *conn)?;
+  Ok(())
+}
+
+/// Returns the selected child of each parent message
+pub fn select_chat_active_branches(
+  mut conn: DBConnection,
+  chat_id: &str,
+) -> FlowyResult<HashMap<i64, i64>> {
+  let rows = chat_active_branch_table::dsl::chat_active_branch_table
+    .filter(chat_active_branch_table::chat_id.eq(chat_id))
+    .load::<ChatActiveBranchTable>(&mut *conn)?;
+  Ok(
+    rows
+      .into_iter()
+      .map(|row| (row.parent_message_id, row.message_id))
+      .collect(),
+  )
+}
@@ -4,11 +4,11 @@ use flowy_sqlite::{
   diesel, insert_into,
   query_dsl::*,
   schema::{chat_message_table, chat_message_table::dsl},
-  DBConnection, ExpressionMethods, Identifiable, Insertable, OptionalExtension, QueryResult,
-  Queryable,
+  DBConnection, EscapeExpressionMethods, ExpressionMethods, Identifiable, Insertable,
+  OptionalExtension, QueryResult, Queryable, TextExpressionMethods,
 };
 
-#[derive(Queryable, Insertable, Identifiable)]
+#[derive(Clone, Debug, Queryable, Insertable, Identifiable)]
 #[diesel(table_name = chat_message_table)]
 #[diesel(primary_key(message_id))]
 pub struct ChatMessageTable {
@@ -82,3 +82,59 @@ pub fn select_single_message(
     .optional()?;
   Ok(message)
 }
+
+/// Returns all the messages of the chat that are stored locally, oldest first.
+pub fn select_all_chat_messages(
+  mut conn: DBConnection,
+  chat_id_val: &str,
+) -> QueryResult<Vec<ChatMessageTable>> {
+  dsl::chat_message_table
+    .filter(chat_message_table::chat_id.eq(chat_id_val))
+    .order(chat_message_table::message_id.asc())
+    .load::<ChatMessageTable>(&mut *conn)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ChatMessageSearchQuery {
+  /// Matches the messages whose content contains the keyword, case-insensitive for ASCII.
+  pub keyword: String,
+  pub author_type: Option<i64>,
+  /// The range of `created_at` in seconds. Both ends are inclusive.
+  pub start_time: Option<i64>,
+  pub end_time: Option<i64>,
+  pub limit: i64,
+}
+
+/// Search the messages of all the chats, newest first.
+pub fn search_chat_messages(
+  mut conn: DBConnection,
+  search: &ChatMessageSearchQuery,
+) -> QueryResult<Vec<ChatMessageTable>> {
+  let pattern = format!("%{}%", escape_like_pattern(&search.keyword));
+  let mut query = dsl::chat_message_table
+    .filter(chat_message_table::content.like(pattern).escape('\\'))
+    .into_boxed();
+  if let Some(author_type) = search.author_type {
+    query = query.filter(chat_message_table::author_type.eq(author_type));
+  }
+  if let Some(start_time) = search.start_time {
+    query = query.filter(chat_message_table::created_at.ge(start_time));
+  }
+  if let Some(end_time) = search.end_time {
+    query = query.filter(chat_message_table::created_at.le(end_time));
+  }
+  query
+    .order((
+      chat_message_table::created_at.desc(),
+      chat_message_table::message_id.desc(),
+    ))
+    .limit(search.limit)
+    .load::<ChatMessageTable>(&mut *conn)
+}
+
+fn escape_like_pattern(keyword: &str) -> String {
+  keyword
+    .replace('\\', "\\\\")
+    .replace('%', "\\%")
+    .replace('_', "\\_")
+}
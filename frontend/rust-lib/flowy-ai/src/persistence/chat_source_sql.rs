@@ -0,0 +1,59 @@
+use flowy_error::FlowyResult;
+use flowy_sqlite::{
+  diesel, insert_into,
+  query_dsl::*,
+  schema::{chat_source_table, chat_source_table::dsl},
+  AsChangeset, DBConnection, ExpressionMethods, Identifiable, Insertable, Queryable,
+};
+
+#[derive(Clone, Debug, Queryable, Insertable, AsChangeset, Identifiable)]
+#[diesel(table_name = chat_source_table)]
+#[diesel(primary_key(chat_id, view_id))]
+pub struct ChatSourceTable {
+  pub chat_id: String,
+  pub view_id: String,
+  pub name: String,
+  /// 0 for documents, 1 for databases
+  pub source_type: i32,
+  pub content_hash: String,
+  pub version: i64,
+  pub chunk_count: i32,
+  pub indexed_at: i64,
+}
+
+pub fn upsert_chat_source(mut conn: DBConnection, source: &ChatSourceTable) -> FlowyResult<()> {
+  insert_into(chat_source_table::table)
+    .values(source)
+    .on_conflict((chat_source_table::chat_id, chat_source_table::view_id))
+    .do_update()
+    .set(source)
+    .execute(&mut *conn)?;
+  Ok(())
+}
+
+pub fn select_chat_sources(
+  mut conn: DBConnection,
+  chat_id: &str,
+) -> FlowyResult<Vec<ChatSourceTable>> {
+  let sources = dsl::chat_source_table
+    .filter(chat_source_table::chat_id.eq(chat_id))
+    .order(chat_source_table::indexed_at.asc())
+    .load::<ChatSourceTable>(&mut *conn)?;
+  Ok(sources)
+}
+
+pub fn delete_chat_source(mut conn: DBConnection, chat_id: &str, view_id: &str) -> FlowyResult<()> {
+  diesel::delete(
+    dsl::chat_source_table
+      .filter(chat_source_table::chat_id.eq(chat_id))
+      .filter(chat_source_table::view_id.eq(view_id)),
+  )
+  .execute(&mut *conn)?;
+  Ok(())
+}
+
+pub fn delete_chat_sources(mut conn: DBConnection, chat_id: &str) -> FlowyResult<()> {
+  diesel::delete(dsl::chat_source_table.filter(chat_source_table::chat_id.eq(chat_id)))
+    .execute(&mut *conn)?;
+  Ok(())
+}
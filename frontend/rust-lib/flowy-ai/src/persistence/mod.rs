@@ -1,5 +1,11 @@
+mod chat_branch_sql;
 mod chat_message_sql;
+mod chat_source_sql;
 mod chat_sql;
+mod prompt_sql;
 
+pub use chat_branch_sql::*;
 pub use chat_message_sql::*;
+pub use chat_source_sql::*;
 pub use chat_sql::*;
+pub use prompt_sql::*;
@@ -0,0 +1,63 @@
+use flowy_error::FlowyResult;
+use flowy_sqlite::{
+  diesel, insert_into,
+  query_dsl::*,
+  schema::{ai_prompt_table, ai_prompt_table::dsl},
+  AsChangeset, BoolExpressionMethods, DBConnection, ExpressionMethods, Identifiable, Insertable,
+  OptionalExtension, Queryable,
+};
+
+#[derive(Clone, Debug, Default, Queryable, Insertable, AsChangeset, Identifiable)]
+#[diesel(table_name = ai_prompt_table)]
+#[diesel(primary_key(prompt_id))]
+pub struct PromptTable {
+  pub prompt_id: String,
+  /// Empty if the prompt belongs to the user instead of a workspace
+  pub workspace_id: String,
+  pub name: String,
+  pub template: String,
+  pub created_at: i64,
+  pub updated_at: i64,
+}
+
+pub fn upsert_prompts(mut conn: DBConnection, prompts: &[PromptTable]) -> FlowyResult<()> {
+  conn.immediate_transaction(|conn| {
+    for prompt in prompts {
+      insert_into(ai_prompt_table::table)
+        .values(prompt)
+        .on_conflict(ai_prompt_table::prompt_id)
+        .do_update()
+        .set(prompt)
+        .execute(conn)?;
+    }
+    Ok::<(), flowy_error::FlowyError>(())
+  })?;
+  Ok(())
+}
+
+/// Returns the prompts of the user and the prompts of the workspace, ordered by name.
+pub fn select_prompts(mut conn: DBConnection, workspace_id: &str) -> FlowyResult<Vec<PromptTable>> {
+  let prompts = dsl::ai_prompt_table
+    .filter(
+      ai_prompt_table::workspace_id
+        .eq("")
+        .or(ai_prompt_table::workspace_id.eq(workspace_id)),
+    )
+    .order(ai_prompt_table::name.asc())
+    .load::<PromptTable>(&mut *conn)?;
+  Ok(prompts)
+}
+
+pub fn select_prompt(mut conn: DBConnection, prompt_id: &str) -> FlowyResult<Option<PromptTable>> {
+  let prompt = dsl::ai_prompt_table
+    .filter(ai_prompt_table::prompt_id.eq(prompt_id))
+    .first::<PromptTable>(&mut *conn)
+    .optional()?;
+  Ok(prompt)
+}
+
+pub fn delete_prompt(mut conn: DBConnection, prompt_id: &str) -> FlowyResult<()> {
+  diesel::delete(dsl::ai_prompt_table.filter(ai_prompt_table::prompt_id.eq(prompt_id)))
+    .execute(&mut *conn)?;
+  Ok(())
+}
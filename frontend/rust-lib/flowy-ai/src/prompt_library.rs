@@ -0,0 +1,282 @@
+use std::sync::Arc;
+
+use flowy_error::{FlowyError, FlowyResult};
+use lib_infra::util::timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::ai_manager::AIUserService;
+use crate::persistence::{
+  delete_prompt, select_prompt, select_prompts, upsert_prompts, PromptTable,
+};
+
+/// The variables that can be referenced in the template of a prompt.
+pub const SELECTED_TEXT_VARIABLE: &str = "{{selected_text}}";
+pub const DOCUMENT_TITLE_VARIABLE: &str = "{{document_title}}";
+pub const CONTEXT_VARIABLE: &str = "{{context}}";
+
+const PROMPT_EXPORT_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptScope {
+  /// Available in all the workspaces of the user
+  #[default]
+  User,
+  /// Only available in the workspace that the prompt is created in
+  Workspace,
+}
+
+#[derive(Clone, Debug)]
+pub struct AIPrompt {
+  pub id: String,
+  pub name: String,
+  pub template: String,
+  pub scope: PromptScope,
+  pub created_at: i64,
+  pub updated_at: i64,
+}
+
+impl From<PromptTable> for AIPrompt {
+  fn from(table: PromptTable) -> Self {
+    let scope = if table.workspace_id.is_empty() {
+      PromptScope::User
+    } else {
+      PromptScope::Workspace
+    };
+    Self {
+      id: table.prompt_id,
+      name: table.name,
+      template: table.template,
+      scope,
+      created_at: table.created_at,
+      updated_at: table.updated_at,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PromptContext {
+  pub selected_text: String,
+  pub document_title: String,
+  /// The content around the selected text
+  pub context: String,
+}
+
+impl AIPrompt {
+  /// Replace the variables in the template with the context. When the template doesn't reference
+  /// the selected text, the selected text is appended to the end of the prompt.
+  pub fn render(&self, context: &PromptContext) -> String {
+    let mut prompt = self
+      .template
+      .replace(DOCUMENT_TITLE_VARIABLE, &context.document_title)
+      .replace(CONTEXT_VARIABLE, &context.context);
+    if prompt.contains(SELECTED_TEXT_VARIABLE) {
+      prompt = prompt.replace(SELECTED_TEXT_VARIABLE, &context.selected_text);
+    } else if !context.selected_text.is_empty() {
+      prompt = format!("{}\n\n{}", prompt.trim_end(), context.selected_text);
+    }
+    prompt
+  }
+}
+
+/// The JSON format of the exported prompts. The ids are not exported, so importing the same file
+/// twice creates duplicated prompts.
+#[derive(Debug, Serialize, Deserialize)]
+struct PromptExport {
+  version: u32,
+  prompts: Vec<ExportedPrompt>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportedPrompt {
+  name: String,
+  template: String,
+  #[serde(default)]
+  scope: PromptScope,
+}
+
+pub struct PromptLibrary {
+  user_service: Arc<dyn AIUserService>,
+}
+
+impl PromptLibrary {
+  pub fn new(user_service: Arc<dyn AIUserService>) -> Self {
+    Self { user_service }
+  }
+
+  /// Returns the prompts of the user and the prompts of the current workspace.
+  pub fn get_prompts(&self) -> FlowyResult<Vec<AIPrompt>> {
+    let uid = self.user_service.user_id()?;
+    let workspace_id = self.user_service.workspace_id()?;
+    let prompts = select_prompts(self.user_service.sqlite_connection(uid)?, &workspace_id)?;
+    Ok(prompts.into_iter().map(AIPrompt::from).collect())
+  }
+
+  pub fn get_prompt(&self, prompt_id: &str) -> FlowyResult<AIPrompt> {
+    let uid = self.user_service.user_id()?;
+    let prompt =
+      select_prompt(self.user_service.sqlite_connection(uid)?, prompt_id)?.ok_or_else(|| {
+        FlowyError::record_not_found().with_context(format!("Prompt:{} not found", prompt_id))
+      })?;
+    Ok(AIPrompt::from(prompt))
+  }
+
+  pub fn create_prompt(
+    &self,
+    name: String,
+    template: String,
+    scope: PromptScope,
+  ) -> FlowyResult<AIPrompt> {
+    let mut prompts = self.save_new_prompts(vec![ExportedPrompt {
+      name,
+      template,
+      scope,
+    }])?;
+    Ok(prompts.remove(0))
+  }
+
+  pub fn update_prompt(
+    &self,
+    prompt_id: &str,
+    name: Option<String>,
+    template: Option<String>,
+  ) -> FlowyResult<AIPrompt> {
+    let uid = self.user_service.user_id()?;
+    let mut prompt = select_prompt(self.user_service.sqlite_connection(uid)?, prompt_id)?
+      .ok_or_else(|| {
+        FlowyError::record_not_found().with_context(format!("Prompt:{} not found", prompt_id))
+      })?;
+    if let Some(name) = name {
+      prompt.name = name.trim().to_string();
+    }
+    if let Some(template) = template {
+      prompt.template = template;
+    }
+    validate_prompt(&prompt.name, &prompt.template)?;
+    prompt.updated_at = timestamp();
+    upsert_prompts(self.user_service.sqlite_connection(uid)?, &[prompt.clone()])?;
+    Ok(AIPrompt::from(prompt))
+  }
+
+  pub fn delete_prompt(&self, prompt_id: &str) -> FlowyResult<()> {
+    let uid = self.user_service.user_id()?;
+    delete_prompt(self.user_service.sqlite_connection(uid)?, prompt_id)
+  }
+
+  /// Export the prompts as JSON. All the prompts are exported if `prompt_ids` is empty.
+  pub fn export_prompts(&self, prompt_ids: &[String]) -> FlowyResult<String> {
+    let prompts = self
+      .get_prompts()?
+      .into_iter()
+      .filter(|prompt| prompt_ids.is_empty() || prompt_ids.contains(&prompt.id))
+      .map(|prompt| ExportedPrompt {
+        name: prompt.name,
+        template: prompt.template,
+        scope: prompt.scope,
+      })
+      .collect();
+    let export = PromptExport {
+      version: PROMPT_EXPORT_VERSION,
+      prompts,
+    };
+    Ok(serde_json::to_string_pretty(&export)?)
+  }
+
+  /// Import the prompts that are exported by [PromptLibrary::export_prompts]. Pass in the `scope`
+  /// to override the scope of the imported prompts.
+  pub fn import_prompts(
+    &self,
+    json: &str,
+    scope: Option<PromptScope>,
+  ) -> FlowyResult<Vec<AIPrompt>> {
+    let export = serde_json::from_str::<PromptExport>(json).map_err(|err| {
+      FlowyError::invalid_data().with_context(format!("Invalid prompts: {}", err))
+    })?;
+    if export.version > PROMPT_EXPORT_VERSION {
+      return Err(
+        FlowyError::invalid_data()
+          .with_context(format!("Unsupported prompts version: {}", export.version)),
+      );
+    }
+    let prompts = export
+      .prompts
+      .into_iter()
+      .map(|mut prompt| {
+        if let Some(scope) = scope {
+          prompt.scope = scope;
+        }
+        prompt
+      })
+      .collect();
+    self.save_new_prompts(prompts)
+  }
+
+  fn save_new_prompts(&self, prompts: Vec<ExportedPrompt>) -> FlowyResult<Vec<AIPrompt>> {
+    let uid = self.user_service.user_id()?;
+    let workspace_id = self.user_service.workspace_id()?;
+    let now = timestamp();
+    let rows = prompts
+      .into_iter()
+      .map(|prompt| {
+        validate_prompt(&prompt.name, &prompt.template)?;
+        Ok(PromptTable {
+          prompt_id: uuid::Uuid::new_v4().to_string(),
+          workspace_id: match prompt.scope {
+            PromptScope::User => "".to_string(),
+            PromptScope::Workspace => workspace_id.clone(),
+          },
+          name: prompt.name.trim().to_string(),
+          template: prompt.template,
+          created_at: now,
+          updated_at: now,
+        })
+      })
+      .collect::<FlowyResult<Vec<_>>>()?;
+    upsert_prompts(self.user_service.sqlite_connection(uid)?, &rows)?;
+    Ok(rows.into_iter().map(AIPrompt::from).collect())
+  }
+}
+
+fn validate_prompt(name: &str, template: &str) -> FlowyResult<()> {
+  if name.trim().is_empty() {
+    return Err(FlowyError::invalid_data().with_context("The name of the prompt is empty"));
+  }
+  if template.trim().is_empty() {
+    return Err(FlowyError::invalid_data().with_context("The template of the prompt is empty"));
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn prompt(template: &str) -> AIPrompt {
+    AIPrompt {
+      id: "1".to_string(),
+      name: "House style".to_string(),
+      template: template.to_string(),
+      scope: PromptScope::User,
+      created_at: 0,
+      updated_at: 0,
+    }
+  }
+
+  #[test]
+  fn render_prompt_test() {
+    let context = PromptContext {
+      selected_text: "hello".to_string(),
+      document_title: "Release notes".to_string(),
+      context: "".to_string(),
+    };
+    assert_eq!(
+      prompt("Rewrite \"{{selected_text}}\" for {{document_title}}").render(&context),
+      "Rewrite \"hello\" for Release notes"
+    );
+    // The selected text is appended when the template doesn't reference it
+    assert_eq!(
+      prompt("Use British spelling.\n").render(&context),
+      "Use British spelling.\n\nhello"
+    );
+  }
+}
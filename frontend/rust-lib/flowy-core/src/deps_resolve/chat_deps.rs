@@ -1,10 +1,18 @@
-use flowy_ai::ai_manager::{AIManager, AIUserService};
+use flowy_ai::ai_manager::{
+  AIManager, AIUserService, AIViewContent, AIViewSourceType, AIWorkspaceSourceService,
+};
 use flowy_ai_pub::cloud::ChatCloudService;
-use flowy_error::FlowyError;
+use flowy_database2::DatabaseManager;
+use flowy_document::manager::DocumentManager;
+use flowy_document::parser::document_data_parser::DocumentDataParser;
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_folder::entities::ViewLayoutPB;
+use flowy_folder::manager::FolderManager;
 use flowy_sqlite::kv::KVStorePreferences;
 use flowy_sqlite::DBConnection;
 use flowy_storage_pub::storage::StorageService;
 use flowy_user::services::authenticate_user::AuthenticateUser;
+use lib_infra::async_trait::async_trait;
 use std::path::PathBuf;
 use std::sync::{Arc, Weak};
 
@@ -25,6 +33,20 @@ impl ChatDepsResolver {
       storage_service,
     ))
   }
+
+  /// The views are read by the managers that are resolved after the [AIManager].
+  pub fn set_workspace_source_service(
+    ai_manager: &Arc<AIManager>,
+    folder_manager: &Arc<FolderManager>,
+    document_manager: &Arc<DocumentManager>,
+    database_manager: &Arc<DatabaseManager>,
+  ) {
+    ai_manager.set_workspace_source_service(Arc::new(AIWorkspaceSourceServiceImpl {
+      folder_manager: Arc::downgrade(folder_manager),
+      document_manager: Arc::downgrade(document_manager),
+      database_manager: Arc::downgrade(database_manager),
+    }));
+  }
 }
 
 struct ChatUserServiceImpl(Weak<AuthenticateUser>);
@@ -60,4 +82,54 @@ impl AIUserService for ChatUserServiceImpl {
       self.upgrade_user()?.get_application_root_dir(),
     ))
   }
+
+  fn encrypt_secret(&self) -> Result<String, FlowyError> {
+    self.upgrade_user()?.encrypt_secret()
+  }
+}
+
+struct AIWorkspaceSourceServiceImpl {
+  folder_manager: Weak<FolderManager>,
+  document_manager: Weak<DocumentManager>,
+  database_manager: Weak<DatabaseManager>,
+}
+
+#[async_trait]
+impl AIWorkspaceSourceService for AIWorkspaceSourceServiceImpl {
+  async fn get_view_content(&self, view_id: &str) -> FlowyResult<AIViewContent> {
+    let folder_manager = self
+      .folder_manager
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: FolderManager is None"))?;
+    let view = folder_manager.get_view_pb(view_id).await?;
+    match view.layout {
+      ViewLayoutPB::Document => {
+        let document_manager = self.document_manager.upgrade().ok_or(
+          FlowyError::internal().with_context("Unexpected error: DocumentManager is None"),
+        )?;
+        let data = document_manager.get_document_data(view_id).await?;
+        let passages = DocumentDataParser::new(Arc::new(data), None).to_block_texts();
+        Ok(AIViewContent {
+          name: view.name,
+          source_type: AIViewSourceType::Document,
+          passages,
+        })
+      },
+      ViewLayoutPB::Grid | ViewLayoutPB::Board | ViewLayoutPB::Calendar => {
+        let database_manager = self.database_manager.upgrade().ok_or(
+          FlowyError::internal().with_context("Unexpected error: DatabaseManager is None"),
+        )?;
+        let passages = database_manager.stringify_rows(view_id).await?;
+        Ok(AIViewContent {
+          name: view.name,
+          source_type: AIViewSourceType::Database,
+          passages,
+        })
+      },
+      layout => Err(
+        FlowyError::invalid_data()
+          .with_context(format!("{:?} can't be used as a chat source", layout)),
+      ),
+    }
+  }
 }
@@ -2,13 +2,17 @@ use appflowy_local_ai::ai_ops::{LocalAITranslateItem, LocalAITranslateRowData};
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
 use flowy_ai::ai_manager::AIManager;
-use flowy_database2::{DatabaseManager, DatabaseUser};
+use flowy_ai_pub::cloud::ChatCloudService;
+use flowy_database2::{DatabaseManager, DatabaseUser, DatabaseViewLockService};
 use flowy_database_pub::cloud::{
   DatabaseAIService, DatabaseCloudService, SummaryRowContent, TranslateRowContent,
   TranslateRowResponse,
 };
-use flowy_error::FlowyError;
+use flowy_error::{ErrorCode, FlowyError};
+use flowy_folder::manager::FolderManager;
+use flowy_sqlite::DBConnection;
 use flowy_user::services::authenticate_user::AuthenticateUser;
+use futures::StreamExt;
 use lib_infra::async_trait::async_trait;
 use lib_infra::priority_task::TaskDispatcher;
 use std::sync::{Arc, Weak};
@@ -37,12 +41,50 @@ impl DatabaseDepsResolver {
       }),
     ))
   }
+
+  /// The locks of the views are stored in the folder, which is resolved after the
+  /// [DatabaseManager].
+  pub fn set_view_lock_service(
+    database_manager: &Arc<DatabaseManager>,
+    folder_manager: &Arc<FolderManager>,
+  ) {
+    database_manager.set_view_lock_service(Arc::new(DatabaseViewLockServiceImpl(Arc::downgrade(
+      folder_manager,
+    ))));
+  }
 }
 
+struct DatabaseViewLockServiceImpl(Weak<FolderManager>);
+
+#[async_trait]
+impl DatabaseViewLockService for DatabaseViewLockServiceImpl {
+  async fn is_view_locked(&self, view_id: &str) -> bool {
+    match self.0.upgrade() {
+      Some(folder_manager) => folder_manager.get_view_lock(view_id).await.is_some(),
+      None => false,
+    }
+  }
+}
+
+/// Routes the database AI requests to the local LLM when the local AI is running, otherwise to
+/// the cloud service.
 struct DatabaseAIServiceMiddleware {
   ai_manager: Arc<AIManager>,
   ai_service: Arc<dyn DatabaseAIService>,
 }
+
+impl DatabaseAIServiceMiddleware {
+  /// The local server doesn't support the database AI. When the local AI is enabled but the plugin
+  /// is not running yet, tell the user the local AI is unavailable instead.
+  fn map_cloud_error(&self, err: FlowyError) -> FlowyError {
+    if err.code == ErrorCode::NotSupportYet && self.ai_manager.local_ai_controller.is_enabled() {
+      FlowyError::local_ai_unavailable()
+        .with_context("The local AI is enabled but not running, please check the local AI plugin")
+    } else {
+      err
+    }
+  }
+}
 #[async_trait]
 impl DatabaseAIService for DatabaseAIServiceMiddleware {
   async fn summary_database_row(
@@ -63,6 +105,7 @@ impl DatabaseAIService for DatabaseAIServiceMiddleware {
         .ai_service
         .summary_database_row(workspace_id, object_id, summary_row)
         .await
+        .map_err(|err| self.map_cloud_error(err))
     }
   }
 
@@ -97,7 +140,27 @@ impl DatabaseAIService for DatabaseAIServiceMiddleware {
         .ai_service
         .translate_database_row(workspace_id, translate_row, language)
         .await
+        .map_err(|err| self.map_cloud_error(err))
+    }
+  }
+
+  /// Use the same provider as the completions with a custom prompt.
+  async fn complete_database_prompt(
+    &self,
+    workspace_id: &str,
+    prompt: &str,
+  ) -> Result<String, FlowyError> {
+    let mut stream = self
+      .ai_manager
+      .cloud_service_wm
+      .stream_complete_with_prompt(workspace_id, prompt)
+      .await
+      .map_err(|err| self.map_cloud_error(err))?;
+    let mut response = vec![];
+    while let Some(bytes) = stream.next().await {
+      response.extend_from_slice(&bytes?);
     }
+    String::from_utf8(response).map_err(|err| FlowyError::invalid_data().with_context(err))
   }
 }
 
@@ -128,4 +191,8 @@ impl DatabaseUser for DatabaseUserImpl {
   fn workspace_database_object_id(&self) -> Result<String, FlowyError> {
     self.upgrade_user()?.workspace_database_object_id()
   }
+
+  fn sqlite_connection(&self, uid: i64) -> Result<DBConnection, FlowyError> {
+    self.upgrade_user()?.get_sqlite_connection(uid)
+  }
 }
@@ -1,21 +1,32 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Weak};
 
 use crate::deps_resolve::CollabSnapshotSql;
+use collab_folder::ViewLayout;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
-use flowy_database2::DatabaseManager;
+use flowy_database2::{DatabaseManager, DatabaseRowDocumentService};
 use flowy_document::entities::{DocumentSnapshotData, DocumentSnapshotMeta};
-use flowy_document::manager::{DocumentManager, DocumentSnapshotService, DocumentUserService};
+use flowy_document::manager::{
+  DocumentLockService, DocumentManager, DocumentSnapshotService, DocumentUserService,
+};
+use flowy_document::mention::{MentionView, MentionViewService};
+use flowy_document::parser::document_data_parser::DocumentDataParser;
+use flowy_document::parser::json::parser::JsonToDocumentParser;
 use flowy_document_pub::cloud::DocumentCloudService;
 use flowy_error::{FlowyError, FlowyResult};
+use flowy_folder::entities::ViewLayoutPB;
+use flowy_folder::manager::FolderManager;
+use flowy_sqlite::DBConnection;
 use flowy_storage_pub::storage::StorageService;
 use flowy_user::services::authenticate_user::AuthenticateUser;
+use lib_infra::async_trait::async_trait;
 
 pub struct DocumentDepsResolver();
 impl DocumentDepsResolver {
   pub fn resolve(
     authenticate_user: Weak<AuthenticateUser>,
-    _database_manager: &Arc<DatabaseManager>,
+    database_manager: &Arc<DatabaseManager>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
     cloud_service: Arc<dyn DocumentCloudService>,
     storage_service: Weak<dyn StorageService>,
@@ -23,13 +34,43 @@ impl DocumentDepsResolver {
     let user_service: Arc<dyn DocumentUserService> =
       Arc::new(DocumentUserImpl(authenticate_user.clone()));
     let snapshot_service = Arc::new(DocumentSnapshotImpl(authenticate_user));
-    Arc::new(DocumentManager::new(
+    let document_manager = Arc::new(DocumentManager::new(
       user_service.clone(),
       collab_builder,
       cloud_service,
       storage_service,
       snapshot_service,
-    ))
+    ));
+    database_manager.set_row_document_service(Arc::new(DatabaseRowDocumentServiceImpl {
+      user_service,
+      document_manager: Arc::downgrade(&document_manager),
+    }));
+    document_manager
+  }
+
+  /// The mentioned views are read by the folder, which is resolved after the [DocumentManager].
+  pub fn set_mention_view_service(
+    document_manager: &Arc<DocumentManager>,
+    folder_manager: &Arc<FolderManager>,
+    database_manager: &Arc<DatabaseManager>,
+  ) {
+    document_manager.set_mention_view_service(Arc::new(MentionViewServiceImpl {
+      folder_manager: Arc::downgrade(folder_manager),
+      database_manager: Arc::downgrade(database_manager),
+    }));
+  }
+
+  /// The locks of the views are stored in the folder, which is resolved after the
+  /// [DocumentManager].
+  pub fn set_lock_service(
+    document_manager: &Arc<DocumentManager>,
+    folder_manager: &Arc<FolderManager>,
+    database_manager: &Arc<DatabaseManager>,
+  ) {
+    document_manager.set_lock_service(Arc::new(DocumentLockServiceImpl {
+      folder_manager: Arc::downgrade(folder_manager),
+      database_manager: Arc::downgrade(database_manager),
+    }));
   }
 }
 
@@ -112,4 +153,200 @@ impl DocumentUserService for DocumentUserImpl {
       .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
       .get_collab_db(uid)
   }
+
+  fn sqlite_connection(&self, uid: i64) -> Result<DBConnection, FlowyError> {
+    self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
+      .get_sqlite_connection(uid)
+  }
+}
+
+struct DatabaseRowDocumentServiceImpl {
+  user_service: Arc<dyn DocumentUserService>,
+  document_manager: Weak<DocumentManager>,
+}
+
+impl DatabaseRowDocumentServiceImpl {
+  fn upgrade_document_manager(&self) -> FlowyResult<Arc<DocumentManager>> {
+    self
+      .document_manager
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: DocumentManager is None"))
+  }
+}
+
+#[async_trait]
+impl DatabaseRowDocumentService for DatabaseRowDocumentServiceImpl {
+  async fn create_document_with_json(&self, document_id: &str, json: &str) -> FlowyResult<()> {
+    let data = JsonToDocumentParser::json_str_to_document(json)?;
+    let uid = self.user_service.user_id()?;
+    self
+      .upgrade_document_manager()?
+      .create_document(uid, document_id, Some(data.into()))
+      .await?;
+    Ok(())
+  }
+
+  async fn get_document_json(&self, document_id: &str) -> FlowyResult<String> {
+    let data = self
+      .upgrade_document_manager()?
+      .get_document_data(document_id)
+      .await?;
+    let json = DocumentDataParser::new(Arc::new(data), None)
+      .to_json()
+      .ok_or_else(|| FlowyError::record_not_found().with_context("The document is empty"))?;
+    Ok(serde_json::to_string(&json)?)
+  }
+}
+
+struct MentionViewServiceImpl {
+  folder_manager: Weak<FolderManager>,
+  database_manager: Weak<DatabaseManager>,
+}
+
+impl MentionViewServiceImpl {
+  fn upgrade_folder_manager(&self) -> FlowyResult<Arc<FolderManager>> {
+    self
+      .folder_manager
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: FolderManager is None"))
+  }
+
+  fn upgrade_database_manager(&self) -> FlowyResult<Arc<DatabaseManager>> {
+    self
+      .database_manager
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: DatabaseManager is None"))
+  }
+}
+
+#[async_trait]
+impl MentionViewService for MentionViewServiceImpl {
+  async fn get_mention_views(&self, ids: Vec<String>) -> FlowyResult<HashMap<String, MentionView>> {
+    let mut mention_views = self
+      .upgrade_folder_manager()?
+      .get_views_with_trash_state(&ids)
+      .await?
+      .into_iter()
+      .map(|(view, is_trashed)| {
+        let mention_view = MentionView {
+          view_id: view.id.clone(),
+          row_id: None,
+          name: view.name,
+          is_trashed,
+        };
+        (view.id, mention_view)
+      })
+      .collect::<HashMap<_, _>>();
+
+    // The ids that are not views are the documents of the database rows. A row document is shown
+    // by the database that contains the row.
+    if ids.iter().any(|id| !mention_views.contains_key(id)) {
+      let row_documents = self
+        .upgrade_database_manager()?
+        .get_all_row_documents()
+        .await;
+      for row_document in row_documents {
+        if !ids.contains(&row_document.document_id) {
+          continue;
+        }
+        let is_trashed = mention_views
+          .get(&row_document.view_id)
+          .map(|view| view.is_trashed)
+          .unwrap_or(false);
+        mention_views.insert(
+          row_document.document_id,
+          MentionView {
+            view_id: row_document.view_id,
+            row_id: Some(row_document.row_id),
+            name: row_document.name,
+            is_trashed,
+          },
+        );
+      }
+    }
+    Ok(mention_views)
+  }
+
+  async fn get_document_ids(&self) -> FlowyResult<Vec<String>> {
+    let mut document_ids = self
+      .upgrade_folder_manager()?
+      .get_all_views_pb()
+      .await?
+      .into_iter()
+      .filter(|view| view.layout == ViewLayoutPB::Document)
+      .map(|view| view.id)
+      .collect::<Vec<_>>();
+    let row_documents = self
+      .upgrade_database_manager()?
+      .get_all_row_documents()
+      .await;
+    document_ids.extend(
+      row_documents
+        .into_iter()
+        .map(|row_document| row_document.document_id),
+    );
+    Ok(document_ids)
+  }
+
+  async fn get_document_ids_in_view(&self, view_id: &str) -> FlowyResult<Vec<String>> {
+    let folder_manager = self.upgrade_folder_manager()?;
+    let root = folder_manager.get_view_pb(view_id).await?;
+    let mut views = vec![(root.id, root.layout == ViewLayoutPB::Document)];
+    let mut index = 0;
+    while index < views.len() {
+      let children = folder_manager.get_views_belong_to(&views[index].0).await?;
+      views.extend(
+        children
+          .iter()
+          .map(|child| (child.id.clone(), child.layout == ViewLayout::Document)),
+      );
+      index += 1;
+    }
+
+    let view_ids = views
+      .iter()
+      .map(|(id, _)| id.clone())
+      .collect::<HashSet<_>>();
+    let mut document_ids = views
+      .into_iter()
+      .filter(|(_, is_document)| *is_document)
+      .map(|(id, _)| id)
+      .collect::<Vec<_>>();
+    let row_documents = self
+      .upgrade_database_manager()?
+      .get_all_row_documents()
+      .await;
+    document_ids.extend(
+      row_documents
+        .into_iter()
+        .filter(|row_document| view_ids.contains(&row_document.view_id))
+        .map(|row_document| row_document.document_id),
+    );
+    Ok(document_ids)
+  }
+}
+
+struct DocumentLockServiceImpl {
+  folder_manager: Weak<FolderManager>,
+  database_manager: Weak<DatabaseManager>,
+}
+
+#[async_trait]
+impl DocumentLockService for DocumentLockServiceImpl {
+  async fn is_document_locked(&self, document_id: &str) -> bool {
+    if let Some(folder_manager) = self.folder_manager.upgrade() {
+      if folder_manager.get_view_lock(document_id).await.is_some() {
+        return true;
+      }
+    }
+
+    // A row document isn't a view. It's locked with the database that contains the row.
+    match self.database_manager.upgrade() {
+      Some(database_manager) => database_manager.is_row_document_locked(document_id).await,
+      None => false,
+    }
+  }
 }
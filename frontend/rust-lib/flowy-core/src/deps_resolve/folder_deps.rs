@@ -1,33 +1,46 @@
 use bytes::Bytes;
 
+use collab_database::database::gen_database_id;
 use collab_entity::{CollabType, EncodedCollab};
 use collab_folder::hierarchy_builder::NestedViewBuilder;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
-use collab_integrate::CollabKVDB;
-use flowy_ai::ai_manager::AIManager;
-use flowy_database2::entities::DatabaseLayoutPB;
+use collab_integrate::{CollabKVAction, CollabKVDB};
+use flowy_ai::ai_manager::{AIDocumentService, AIManager};
+use flowy_database2::entities::{DatabaseLayoutPB, FileUploadTypePB};
+use flowy_database2::services::ai::{AI_DATABASE_PROMPT_KEY, AI_VIEW_QUERY_KEY};
+use flowy_database2::services::database::copy_database_collabs;
 use flowy_database2::services::share::csv::CSVFormat;
 use flowy_database2::template::{make_default_board, make_default_calendar, make_default_grid};
 use flowy_database2::DatabaseManager;
+use flowy_document::deps::DocumentData;
 use flowy_document::entities::DocumentDataPB;
+use flowy_document::export::{document_data_from_doc_state, export_document_data};
 use flowy_document::manager::DocumentManager;
 use flowy_document::parser::json::parser::JsonToDocumentParser;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_folder::entities::{CreateViewParams, ViewLayoutPB};
 use flowy_folder::manager::{FolderManager, FolderUser};
-use flowy_folder::share::ImportType;
+use flowy_folder::share::{
+  export_attachment_file_name, ExportFormat, ExportedFile, ExportedPage, ImportType,
+  StaticPageContent, EXPORT_DATABASE_DATA_SUFFIX,
+};
+use flowy_folder::template::{replace_template_ids, TemplateViewData};
 use flowy_folder::view_operation::{
   DatabaseEncodedCollab, DocumentEncodedCollab, EncodedCollabWrapper, FolderOperationHandler,
-  FolderOperationHandlers, ImportedData, View, ViewData,
+  FolderOperationHandlers, ImportedData, View, ViewData, ViewPurgeInfo, WorkspaceViewCollabs,
 };
 use flowy_folder::ViewLayout;
+use flowy_folder_pub::cloud::gen_view_id;
+use flowy_folder_pub::entities::PublishPayload;
 use flowy_search::folder::indexer::FolderIndexManagerImpl;
 use flowy_sqlite::kv::KVStorePreferences;
+use flowy_storage_pub::storage::StorageService;
 use flowy_user::services::authenticate_user::AuthenticateUser;
 use flowy_user::services::data_import::{load_collab_by_object_id, load_collab_by_object_ids};
 use lib_dispatch::prelude::ToBytes;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Weak};
 use tokio::sync::RwLock;
 
@@ -46,12 +59,13 @@ impl FolderDepsResolver {
     folder_indexer: Arc<FolderIndexManagerImpl>,
     store_preferences: Arc<KVStorePreferences>,
     operation_handlers: FolderOperationHandlers,
+    ai_manager: &Arc<AIManager>,
   ) -> Arc<FolderManager> {
     let user: Arc<dyn FolderUser> = Arc::new(FolderUserImpl {
       authenticate_user: authenticate_user.clone(),
     });
 
-    Arc::new(
+    let folder_manager = Arc::new(
       FolderManager::new(
         user.clone(),
         collab_builder,
@@ -61,7 +75,11 @@ impl FolderDepsResolver {
         store_preferences,
       )
       .unwrap(),
-    )
+    );
+    ai_manager.set_document_service(Arc::new(AIDocumentServiceImpl(Arc::downgrade(
+      &folder_manager,
+    ))));
+    folder_manager
   }
 }
 
@@ -69,13 +87,18 @@ pub fn folder_operation_handlers(
   document_manager: Arc<DocumentManager>,
   database_manager: Arc<DatabaseManager>,
   chat_manager: Arc<AIManager>,
+  storage_service: Weak<dyn StorageService>,
 ) -> FolderOperationHandlers {
   let mut map: HashMap<ViewLayout, Arc<dyn FolderOperationHandler + Send + Sync>> = HashMap::new();
 
-  let document_folder_operation = Arc::new(DocumentFolderOperation(document_manager));
+  let document_folder_operation = Arc::new(DocumentFolderOperation(
+    document_manager,
+    storage_service.clone(),
+  ));
   map.insert(ViewLayout::Document, document_folder_operation);
 
-  let database_folder_operation = Arc::new(DatabaseFolderOperation(database_manager));
+  let database_folder_operation =
+    Arc::new(DatabaseFolderOperation(database_manager, storage_service));
   let chat_folder_operation = Arc::new(ChatFolderOperation(chat_manager));
   map.insert(ViewLayout::Board, database_folder_operation.clone());
   map.insert(ViewLayout::Grid, database_folder_operation.clone());
@@ -107,16 +130,28 @@ impl FolderUser for FolderUserImpl {
     self.upgrade_user()?.workspace_id()
   }
 
+  fn user_name(&self) -> Result<String, FlowyError> {
+    self.upgrade_user()?.user_name()
+  }
+
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError> {
     self.upgrade_user()?.get_collab_db(uid)
   }
 
+  /// The folder is stored in its workspace, which isn't always the current one, for example when
+  /// a view is moved into another workspace.
   fn is_folder_exist_on_disk(&self, uid: i64, workspace_id: &str) -> FlowyResult<bool> {
-    self.upgrade_user()?.is_collab_on_disk(uid, workspace_id)
+    let collab_db = self
+      .upgrade_user()?
+      .get_collab_db(uid)?
+      .upgrade()
+      .ok_or_else(|| FlowyError::internal().with_context("The collab db is already dropped"))?;
+    let read_txn = collab_db.read_txn();
+    Ok(read_txn.is_exist(uid, workspace_id, workspace_id))
   }
 }
 
-struct DocumentFolderOperation(Arc<DocumentManager>);
+struct DocumentFolderOperation(Arc<DocumentManager>, Weak<dyn StorageService>);
 #[async_trait]
 impl FolderOperationHandler for DocumentFolderOperation {
   async fn create_workspace_view(
@@ -282,12 +317,168 @@ impl FolderOperationHandler for DocumentFolderOperation {
     Ok(())
   }
 
+  async fn import_document_file(
+    &self,
+    uid: i64,
+    view_id: &str,
+    path: &Path,
+    root: &Path,
+    pages: &HashMap<PathBuf, String>,
+  ) -> Result<Vec<ImportedData>, FlowyError> {
+    let encoded_collab = self
+      .0
+      .import_document_file(uid, view_id, path, root, pages)
+      .await?;
+    Ok(vec![(
+      view_id.to_string(),
+      CollabType::Document,
+      encoded_collab,
+    )])
+  }
+
+  async fn export_view(
+    &self,
+    view_id: &str,
+    file_stem: &str,
+    format: ExportFormat,
+    pages: &HashMap<String, ExportedPage>,
+  ) -> Result<Vec<ExportedFile>, FlowyError> {
+    let files = self
+      .0
+      .export_document(view_id, format, file_stem, pages)
+      .await?;
+    Ok(
+      files
+        .into_iter()
+        .map(|(path, data)| ExportedFile { path, data })
+        .collect(),
+    )
+  }
+
+  async fn render_static_page(
+    &self,
+    payload: PublishPayload,
+    file_stem: &str,
+    pages: &HashMap<String, ExportedPage>,
+  ) -> Result<StaticPageContent, FlowyError> {
+    let payload = match payload {
+      PublishPayload::Document(payload) => payload,
+      _ => return Err(FlowyError::invalid_data().with_context("Not a document payload")),
+    };
+    let data = document_data_from_doc_state(&payload.meta.view_id, payload.data)?;
+    let document = export_document_data(data, file_stem, pages, self.1.upgrade()).await;
+    Ok(StaticPageContent {
+      body: document.to_html_body(),
+      assets: document
+        .files
+        .into_iter()
+        .map(|(path, data)| ExportedFile { path, data })
+        .collect(),
+    })
+  }
+
+  async fn get_template_view_data(
+    &self,
+    view_id: &str,
+    _include_rows: bool,
+  ) -> Result<TemplateViewData, FlowyError> {
+    let data = self.0.get_document_data(view_id).await?;
+    Ok(TemplateViewData {
+      data: serde_json::to_string(&data)?,
+      object_ids: vec![],
+    })
+  }
+
+  async fn create_view_from_template(
+    &self,
+    uid: i64,
+    view_id: &str,
+    data: &str,
+  ) -> Result<Vec<ImportedData>, FlowyError> {
+    let data = serde_json::from_str::<DocumentData>(data)?;
+    let encoded_collab = self.0.create_document(uid, view_id, Some(data)).await?;
+    Ok(vec![(
+      view_id.to_string(),
+      CollabType::Document,
+      encoded_collab,
+    )])
+  }
+
+  async fn get_view_collabs_for_workspace(
+    &self,
+    _user: Arc<dyn FolderUser>,
+    view_id: &str,
+    workspace_id: &str,
+    ids: &mut HashMap<String, String>,
+    _is_copy: bool,
+  ) -> Result<WorkspaceViewCollabs, FlowyError> {
+    let new_view_id = ids
+      .get(view_id)
+      .cloned()
+      .unwrap_or_else(|| view_id.to_string());
+    // The mentions of the transferred views point to their new ids.
+    let data = self.0.get_document_data(view_id).await?;
+    let data = replace_template_ids(&serde_json::to_string(&data)?, ids);
+    let mut data = serde_json::from_str::<DocumentData>(&data)?;
+    let source_files = self.0.get_document_file_urls(view_id).await?;
+    let files = self
+      .0
+      .copy_document_files(&mut data, workspace_id, &new_view_id)
+      .await?;
+    let encoded_collab = match self.0.encode_document_data(&new_view_id, data).await {
+      Ok(encoded_collab) => encoded_collab,
+      Err(err) => {
+        delete_storage_files(&self.1, files).await;
+        return Err(err);
+      },
+    };
+    Ok(WorkspaceViewCollabs {
+      collabs: vec![(new_view_id, CollabType::Document, encoded_collab)],
+      databases: HashMap::new(),
+      files,
+      source_object_ids: vec![view_id.to_string()],
+      source_files,
+    })
+  }
+
+  async fn delete_view_files(&self, urls: Vec<String>) {
+    delete_storage_files(&self.1, urls).await
+  }
+
+  async fn get_view_purge_info(
+    &self,
+    user: Arc<dyn FolderUser>,
+    view_id: &str,
+  ) -> Result<ViewPurgeInfo, FlowyError> {
+    let mut info = ViewPurgeInfo::default();
+    if let EncodedCollabWrapper::Document(encoded) =
+      self.get_encoded_collab_v1_from_disk(user, view_id).await?
+    {
+      info.size += encoded_collab_size(&encoded.document_encoded_collab);
+    }
+    let urls = self.0.get_document_file_urls(view_id).await?;
+    add_storage_files_info(&self.1, &urls, &mut info).await;
+    Ok(info)
+  }
+
+  async fn purge_view(&self, view_id: &str) -> Result<(), FlowyError> {
+    match self.0.get_document_file_urls(view_id).await {
+      Ok(urls) => delete_storage_files(&self.1, urls).await,
+      Err(err) => tracing::error!(
+        "Failed to get the files of the document {}: {}",
+        view_id,
+        err
+      ),
+    }
+    self.delete_view(view_id).await
+  }
+
   fn name(&self) -> &str {
     "DocumentFolderOperationHandler"
   }
 }
 
-struct DatabaseFolderOperation(Arc<DatabaseManager>);
+struct DatabaseFolderOperation(Arc<DatabaseManager>, Weak<dyn StorageService>);
 
 #[async_trait]
 impl FolderOperationHandler for DatabaseFolderOperation {
@@ -427,6 +618,19 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     _user_id: i64,
     params: CreateViewParams,
   ) -> Result<Option<EncodedCollab>, FlowyError> {
+    if let Some(prompt) = params.meta.get(AI_DATABASE_PROMPT_KEY) {
+      let encoded_collab = self
+        .0
+        .create_database_with_prompt(
+          &params.view_id,
+          &params.name,
+          database_layout_from_view(&params.layout)?.into(),
+          prompt,
+        )
+        .await?;
+      return Ok(Some(encoded_collab));
+    }
+
     match CreateDatabaseExtParams::from_map(params.meta.clone()) {
       None => match params.initial_data {
         ViewData::DuplicateData(data) => {
@@ -448,14 +652,7 @@ impl FolderOperationHandler for DatabaseFolderOperation {
         ViewData::Empty => Ok(None),
       },
       Some(database_params) => {
-        let layout = match params.layout {
-          ViewLayoutPB::Board => DatabaseLayoutPB::Board,
-          ViewLayoutPB::Calendar => DatabaseLayoutPB::Calendar,
-          ViewLayoutPB::Grid => DatabaseLayoutPB::Grid,
-          ViewLayoutPB::Document | ViewLayoutPB::Chat => {
-            return Err(FlowyError::not_support());
-          },
-        };
+        let layout = database_layout_from_view(&params.layout)?;
         let name = params.name.to_string();
         let database_view_id = params.view_id.to_string();
         let database_parent_view_id = params.parent_view_id.to_string();
@@ -465,10 +662,21 @@ impl FolderOperationHandler for DatabaseFolderOperation {
             name,
             layout.into(),
             database_params.database_id,
-            database_view_id,
+            database_view_id.clone(),
             database_parent_view_id,
           )
           .await?;
+
+        if let Some(query) = params.meta.get(AI_VIEW_QUERY_KEY) {
+          // Remove the linked view if the query can't be applied, since the folder won't insert
+          // the view.
+          if let Err(err) = self.0.apply_view_query(&database_view_id, query).await {
+            if let Err(delete_err) = self.0.delete_database_view(&database_view_id).await {
+              tracing::error!("Failed to delete the linked view: {}", delete_err);
+            }
+            return Err(err);
+          }
+        }
         Ok(None)
       },
     }
@@ -520,6 +728,7 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     let format = match import_type {
       ImportType::CSV => CSVFormat::Original,
       ImportType::AFDatabase => CSVFormat::META,
+      ImportType::NotionCSV => CSVFormat::Notion,
       _ => CSVFormat::Original,
     };
     let content = tokio::task::spawn_blocking(move || {
@@ -576,11 +785,380 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     }
   }
 
+  async fn export_view(
+    &self,
+    view_id: &str,
+    file_stem: &str,
+    _format: ExportFormat,
+    _pages: &HashMap<String, ExportedPage>,
+  ) -> Result<Vec<ExportedFile>, FlowyError> {
+    let csv = self.0.export_csv(view_id, CSVFormat::Original).await?;
+    let schema = self.0.export_schema(view_id).await?;
+    let data = self.0.export_csv(view_id, CSVFormat::META).await?;
+    let mut files = vec![
+      ExportedFile {
+        path: format!("{}.csv", file_stem),
+        data: csv.into_bytes(),
+      },
+      ExportedFile {
+        path: format!("{}.schema.json", file_stem),
+        data: schema.into_bytes(),
+      },
+      ExportedFile {
+        path: format!("{}{}", file_stem, EXPORT_DATABASE_DATA_SUFFIX),
+        data: data.into_bytes(),
+      },
+    ];
+
+    // The files of the media cells are exported to the `{file_stem}_files` directory.
+    let storage_service = self.1.upgrade();
+    let mut file_names = HashSet::new();
+    for file in self.0.get_database_media_files(view_id).await? {
+      let upload_type = FileUploadTypePB::from(file.upload_type.clone());
+      let result = match (upload_type, &storage_service) {
+        (FileUploadTypePB::LocalFile, _) => tokio::fs::read(&file.url)
+          .await
+          .map(Some)
+          .map_err(FlowyError::from),
+        (FileUploadTypePB::CloudFile, Some(storage_service)) => storage_service
+          .get_object_data(&file.url)
+          .await
+          .map(|value| value.map(|(_, data)| data)),
+        _ => Ok(None),
+      };
+      match result {
+        Ok(Some(data)) => {
+          let file_name = export_attachment_file_name(&mut file_names, &file.name);
+          files.push(ExportedFile {
+            path: format!("{}_files/{}", file_stem, file_name),
+            data,
+          });
+        },
+        Ok(None) => {},
+        Err(err) => tracing::error!("Failed to export the file {}: {}", file.url, err),
+      }
+    }
+    Ok(files)
+  }
+
+  async fn render_static_page(
+    &self,
+    payload: PublishPayload,
+    _file_stem: &str,
+    _pages: &HashMap<String, ExportedPage>,
+  ) -> Result<StaticPageContent, FlowyError> {
+    let payload = match payload {
+      PublishPayload::Database(payload) => payload,
+      _ => return Err(FlowyError::invalid_data().with_context("Not a database payload")),
+    };
+    // The rows are rendered from the local database, which is the same as the published data.
+    let body = self.0.export_html_table(&payload.meta.view_id).await?;
+    Ok(StaticPageContent {
+      body,
+      assets: vec![],
+    })
+  }
+
+  async fn get_template_view_data(
+    &self,
+    view_id: &str,
+    include_rows: bool,
+  ) -> Result<TemplateViewData, FlowyError> {
+    let (data, object_ids) = self
+      .0
+      .get_database_template_data(view_id, include_rows)
+      .await?;
+    Ok(TemplateViewData { data, object_ids })
+  }
+
+  async fn create_view_from_template(
+    &self,
+    _uid: i64,
+    view_id: &str,
+    data: &str,
+  ) -> Result<Vec<ImportedData>, FlowyError> {
+    let result = self.0.create_database_from_template(view_id, data).await?;
+    Ok(
+      result
+        .encoded_collabs
+        .into_iter()
+        .map(|encoded| {
+          (
+            encoded.object_id,
+            encoded.collab_type,
+            encoded.encoded_collab,
+          )
+        })
+        .collect(),
+    )
+  }
+
+  async fn get_row_documents(&self, view_id: &str) -> Result<HashMap<String, String>, FlowyError> {
+    self.0.get_row_documents_json(view_id).await
+  }
+
+  async fn create_row_documents(
+    &self,
+    documents: HashMap<String, String>,
+  ) -> Result<(), FlowyError> {
+    self.0.create_row_documents_json(documents).await
+  }
+
+  async fn get_view_collabs_for_workspace(
+    &self,
+    user: Arc<dyn FolderUser>,
+    view_id: &str,
+    workspace_id: &str,
+    ids: &mut HashMap<String, String>,
+    is_copy: bool,
+  ) -> Result<WorkspaceViewCollabs, FlowyError> {
+    let database_id = self.0.get_database_id_with_view_id(view_id).await?;
+    let new_view_id = ids
+      .get(view_id)
+      .cloned()
+      .unwrap_or_else(|| view_id.to_string());
+
+    // The database is only transferred once for all its views.
+    let mut collabs = WorkspaceViewCollabs::default();
+    if !ids.contains_key(&database_id) {
+      if !is_copy {
+        let linked_views = self
+          .0
+          .get_all_databases_meta()
+          .await
+          .into_iter()
+          .find(|meta| meta.database_id == database_id)
+          .map(|meta| meta.linked_views)
+          .unwrap_or_default();
+        if linked_views
+          .iter()
+          .any(|view_id| !ids.contains_key(view_id))
+        {
+          return Err(
+            FlowyError::not_support()
+              .with_context("The database has views outside of the moved view, move them together"),
+          );
+        }
+      }
+
+      let encoded = match self.get_encoded_collab_v1_from_disk(user, view_id).await? {
+        EncodedCollabWrapper::Database(encoded) => encoded,
+        _ => return Err(FlowyError::internal().with_context("Expect the database collabs")),
+      };
+      collabs.source_object_ids = std::iter::once(database_id.clone())
+        .chain(encoded.database_row_encoded_collabs.keys().cloned())
+        .chain(
+          encoded
+            .database_row_document_encoded_collabs
+            .keys()
+            .cloned(),
+        )
+        .collect();
+
+      // The files of the media cells are copied under the new database id, and the cells point
+      // to the copies.
+      let new_database_id = gen_database_id();
+      ids.insert(database_id.clone(), new_database_id.clone());
+      let urls = self
+        .copy_database_files(view_id, workspace_id, &new_database_id)
+        .await?;
+      collabs.files = urls.values().cloned().collect();
+      collabs.source_files = urls.keys().cloned().collect();
+      let result = match self.0.replace_database_media_urls(view_id, &urls).await {
+        Ok(row_cells) => copy_database_collabs(
+          &database_id,
+          encoded.database_encoded_collab,
+          encoded.database_row_encoded_collabs,
+          encoded.database_row_document_encoded_collabs,
+          &row_cells,
+          ids,
+        ),
+        Err(err) => Err(err),
+      };
+      match result {
+        Ok(encoded_collabs) => {
+          collabs.collabs = encoded_collabs
+            .into_iter()
+            .map(|encoded| {
+              (
+                encoded.object_id,
+                encoded.collab_type,
+                encoded.encoded_collab,
+              )
+            })
+            .collect();
+        },
+        Err(err) => {
+          delete_storage_files(&self.1, collabs.files).await;
+          return Err(err);
+        },
+      }
+    }
+
+    let new_database_id = ids.get(&database_id).cloned().unwrap_or(database_id);
+    collabs.databases = HashMap::from([(new_database_id, vec![new_view_id])]);
+    Ok(collabs)
+  }
+
+  async fn delete_view_files(&self, urls: Vec<String>) {
+    delete_storage_files(&self.1, urls).await
+  }
+
+  /// The database is only purged with its last view, the other views only own their view data.
+  async fn get_view_purge_info(
+    &self,
+    user: Arc<dyn FolderUser>,
+    view_id: &str,
+  ) -> Result<ViewPurgeInfo, FlowyError> {
+    let mut info = ViewPurgeInfo::default();
+    if !self.is_last_database_view(view_id).await? {
+      return Ok(info);
+    }
+    if let EncodedCollabWrapper::Database(encoded) =
+      self.get_encoded_collab_v1_from_disk(user, view_id).await?
+    {
+      info.size += encoded_collab_size(&encoded.database_encoded_collab);
+      info.size += encoded
+        .database_row_encoded_collabs
+        .values()
+        .chain(encoded.database_row_document_encoded_collabs.values())
+        .map(encoded_collab_size)
+        .sum::<u64>();
+    }
+    let urls = self.0.get_database_file_urls(view_id).await?;
+    add_storage_files_info(&self.1, &urls, &mut info).await;
+    Ok(info)
+  }
+
+  async fn purge_view(&self, view_id: &str) -> Result<(), FlowyError> {
+    if !self.is_last_database_view(view_id).await? {
+      return self.delete_view(view_id).await;
+    }
+    match self.0.get_database_file_urls(view_id).await {
+      Ok(urls) => delete_storage_files(&self.1, urls).await,
+      Err(err) => tracing::error!(
+        "Failed to get the files of the database {}: {}",
+        view_id,
+        err
+      ),
+    }
+    self.0.delete_database_with_view_id(view_id).await
+  }
+
   fn name(&self) -> &str {
     "DatabaseFolderOperationHandler"
   }
 }
 
+impl DatabaseFolderOperation {
+  /// Copies the files of the media cells of the database into `parent_dir` of the workspace.
+  /// Returns the urls of the copies by the urls of the files, the copies are removed if any file
+  /// fails to be copied.
+  async fn copy_database_files(
+    &self,
+    view_id: &str,
+    workspace_id: &str,
+    parent_dir: &str,
+  ) -> FlowyResult<HashMap<String, String>> {
+    let mut urls = HashMap::new();
+    let files = self.0.get_database_media_files(view_id).await?;
+    if files.is_empty() {
+      return Ok(urls);
+    }
+    let storage_service = self.1.upgrade().ok_or_else(|| {
+      FlowyError::internal().with_context("The storage service is already dropped")
+    })?;
+    for file in files {
+      if urls.contains_key(&file.url) {
+        continue;
+      }
+      match storage_service
+        .copy_object(&file.url, workspace_id, parent_dir)
+        .await
+      {
+        Ok(Some(new_url)) => {
+          urls.insert(file.url, new_url);
+        },
+        Ok(None) => {},
+        Err(err) => {
+          delete_storage_files(&self.1, urls.into_values().collect()).await;
+          return Err(err);
+        },
+      }
+    }
+    Ok(urls)
+  }
+
+  async fn is_last_database_view(&self, view_id: &str) -> FlowyResult<bool> {
+    let database_id = self.0.get_database_id_with_view_id(view_id).await?;
+    let is_last_view = self
+      .0
+      .get_all_databases_meta()
+      .await
+      .into_iter()
+      .find(|meta| meta.database_id == database_id)
+      .map(|meta| {
+        meta
+          .linked_views
+          .iter()
+          .all(|linked_view| linked_view == view_id)
+      })
+      .unwrap_or(true);
+    Ok(is_last_view)
+  }
+}
+
+fn encoded_collab_size(encoded_collab: &EncodedCollab) -> u64 {
+  (encoded_collab.state_vector.len() + encoded_collab.doc_state.len()) as u64
+}
+
+/// Adds the files among `urls` that are managed by the storage to `info`.
+async fn add_storage_files_info(
+  storage_service: &Weak<dyn StorageService>,
+  urls: &[String],
+  info: &mut ViewPurgeInfo,
+) {
+  let storage_service = match storage_service.upgrade() {
+    None => return,
+    Some(storage_service) => storage_service,
+  };
+  for url in urls {
+    match storage_service.get_object_size(url).await {
+      Ok(Some(size)) => {
+        info.file_count += 1;
+        info.size += size;
+      },
+      Ok(None) => {},
+      Err(err) => tracing::error!("Failed to get the size of the file {}: {}", url, err),
+    }
+  }
+}
+
+/// Deletes the files among `urls` that are managed by the storage. The other urls, like links to
+/// external images, are left alone.
+async fn delete_storage_files(storage_service: &Weak<dyn StorageService>, urls: Vec<String>) {
+  let storage_service = match storage_service.upgrade() {
+    None => return,
+    Some(storage_service) => storage_service,
+  };
+  for url in urls {
+    if let Ok(Some(_)) = storage_service.get_object_size(&url).await {
+      if let Err(err) = storage_service.delete_object(url.clone()).await {
+        tracing::error!("Failed to delete the file {}: {}", url, err);
+      }
+    }
+  }
+}
+
+fn database_layout_from_view(layout: &ViewLayoutPB) -> FlowyResult<DatabaseLayoutPB> {
+  match layout {
+    ViewLayoutPB::Board => Ok(DatabaseLayoutPB::Board),
+    ViewLayoutPB::Calendar => Ok(DatabaseLayoutPB::Calendar),
+    ViewLayoutPB::Grid => Ok(DatabaseLayoutPB::Grid),
+    ViewLayoutPB::Document | ViewLayoutPB::Chat => Err(FlowyError::not_support()),
+  }
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct CreateDatabaseExtParams {
   database_id: String,
@@ -656,3 +1234,38 @@ impl FolderOperationHandler for ChatFolderOperation {
     Err(FlowyError::not_support())
   }
 }
+
+struct AIDocumentServiceImpl(Weak<FolderManager>);
+
+#[async_trait]
+impl AIDocumentService for AIDocumentServiceImpl {
+  async fn create_document_view(
+    &self,
+    parent_view_id: &str,
+    name: &str,
+    json: &str,
+  ) -> FlowyResult<String> {
+    let folder_manager = self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: FolderManager is None"))?;
+    let data = JsonToDocumentParser::json_str_to_document(json)?
+      .into_bytes()
+      .map_err(|_| FlowyError::invalid_data())?;
+    let params = CreateViewParams {
+      parent_view_id: parent_view_id.to_string(),
+      name: name.to_string(),
+      layout: ViewLayoutPB::Document,
+      view_id: gen_view_id().to_string(),
+      initial_data: ViewData::Data(data),
+      meta: Default::default(),
+      set_as_current: false,
+      index: None,
+      section: None,
+      icon: None,
+      extra: None,
+    };
+    let (view, _) = folder_manager.create_view_with_params(params, true).await?;
+    Ok(view.id)
+  }
+}
@@ -0,0 +1,733 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{error, info, instrument};
+use walkdir::WalkDir;
+
+use flowy_encrypt::{decrypt_stream, encrypt_stream};
+use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
+use flowy_sqlite::kv::KVStorePreferences;
+use flowy_sqlite::DB_NAME;
+use flowy_storage::sqlite_sql::select_upload_file_paths;
+use flowy_user::services::authenticate_user::AuthenticateUser;
+use flowy_user::services::backup::{BackupInfo, BackupSetting, UserBackupService};
+use flowy_user::services::cloud_config::get_encrypt_secret;
+use lib_infra::async_trait::async_trait;
+use lib_infra::file_util::{unzip_and_replace, zip_folder};
+use lib_infra::util::timestamp;
+
+const BACKUP_SETTING_KEY: &str = "appflowy_backup_setting";
+/// The backups of each user are stored in `{storage_path}/backups/{uid}`.
+const BACKUP_DIR: &str = "backups";
+const BACKUP_FILE_PREFIX: &str = "backup_";
+/// The version of the content of the archives. Bump it when the layout of the archive changes.
+/// - 2: the local files of the user are listed in [BackupContent::files].
+const BACKUP_CONTENT_VERSION: i32 = 2;
+const BACKUP_CONTENT_FILE_NAME: &str = "backup.json";
+const BACKUP_USER_DIR: &str = "user";
+const BACKUP_FILES_DIR: &str = "files";
+const PENDING_RESTORE_DIR: &str = "pending_restore";
+const PENDING_RESTORE_FILE_NAME: &str = "pending_restore.json";
+/// The data that was replaced by the last restore. It's kept until the next restore.
+const REPLACED_DATA_DIR: &str = "before_restore";
+/// The folders of the user data folder that are not backed up.
+const EXCLUDED_USER_DIRS: [&str; 1] = ["collab_db_history"];
+/// The collab database of the user data folder. It's copied by
+/// [AuthenticateUser::snapshot_databases] instead of being copied file by file.
+const COLLAB_DB_DIR: &str = "collab_db";
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Describes an archive. It's stored next to the archive, so the backups can be listed without
+/// reading, or decrypting, the archives.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct BackupManifest {
+  backup_id: String,
+  uid: i64,
+  workspace_id: String,
+  app_version: String,
+  created_at: i64,
+  size: u64,
+  encrypted: bool,
+  checksum: String,
+}
+
+impl From<BackupManifest> for BackupInfo {
+  fn from(manifest: BackupManifest) -> Self {
+    Self {
+      backup_id: manifest.backup_id,
+      created_at: manifest.created_at,
+      app_version: manifest.app_version,
+      size: manifest.size,
+      encrypted: manifest.encrypted,
+      checksum: manifest.checksum,
+    }
+  }
+}
+
+/// Stored inside the archive, so a restore can check that the archive belongs to the user.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct BackupContent {
+  version: i32,
+  uid: i64,
+  workspace_id: String,
+  #[serde(default)]
+  files: Vec<BackupFile>,
+}
+
+/// A local file of the user that is stored outside the user data folder, such as a file that is
+/// waiting to be uploaded.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct BackupFile {
+  /// The path of the file relative to the storage path.
+  path: String,
+  /// The name of the file in the files folder of the archive.
+  archive_name: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct PendingRestore {
+  backup_id: String,
+  requested_at: i64,
+}
+
+/// Backs up the collab database, the sqlite database and the local files of the current user
+/// into the archives of `{storage_path}/backups/{uid}`.
+pub struct BackupManager {
+  authenticate_user: Weak<AuthenticateUser>,
+  store_preferences: Arc<KVStorePreferences>,
+  storage_path: PathBuf,
+  app_version: String,
+  /// Only one backup or restore runs at a time.
+  lock: Mutex<()>,
+}
+
+impl BackupManager {
+  pub fn new(
+    authenticate_user: Weak<AuthenticateUser>,
+    store_preferences: Arc<KVStorePreferences>,
+    storage_path: &str,
+    app_version: String,
+  ) -> Self {
+    Self {
+      authenticate_user,
+      store_preferences,
+      storage_path: PathBuf::from(storage_path),
+      app_version,
+      lock: Mutex::new(()),
+    }
+  }
+
+  /// Backs up the data periodically, following the [BackupSetting] of the current user, until
+  /// the manager is dropped.
+  pub async fn run_schedule(manager: Weak<BackupManager>) {
+    loop {
+      tokio::time::sleep(BACKUP_CHECK_INTERVAL).await;
+      match manager.upgrade() {
+        None => break,
+        Some(manager) => {
+          if let Err(err) = manager.backup_if_needed().await {
+            error!("Scheduled backup failed: {}", err);
+          }
+        },
+      }
+    }
+  }
+
+  /// Replaces the data of the users with the backups that were restored since the last launch.
+  /// It must be called before any database of the users is opened.
+  pub fn apply_pending_restores(storage_path: &str) {
+    let storage_path = Path::new(storage_path);
+    let entries = match fs::read_dir(storage_path.join(BACKUP_DIR)) {
+      Ok(entries) => entries,
+      Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+      let backup_dir = entry.path();
+      let pending_restore_path = backup_dir.join(PENDING_RESTORE_FILE_NAME);
+      if !pending_restore_path.exists() {
+        continue;
+      }
+      let uid = match entry
+        .file_name()
+        .to_str()
+        .and_then(|s| s.parse::<i64>().ok())
+      {
+        Some(uid) => uid,
+        None => continue,
+      };
+
+      match apply_pending_restore(storage_path, uid, &backup_dir) {
+        Ok(_) => info!("Restored the data of user {} from backup", uid),
+        Err(err) => error!("Restore the data of user {} failed: {}", uid, err),
+      }
+      // The restore is only attempted once, whether it succeeded or not.
+      let _ = fs::remove_file(&pending_restore_path);
+      let _ = fs::remove_dir_all(backup_dir.join(PENDING_RESTORE_DIR));
+    }
+  }
+
+  fn upgrade_user(&self) -> FlowyResult<Arc<AuthenticateUser>> {
+    self
+      .authenticate_user
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))
+  }
+
+  fn backup_dir(&self, uid: i64) -> PathBuf {
+    self.storage_path.join(BACKUP_DIR).join(uid.to_string())
+  }
+
+  fn backup_setting(&self, uid: i64) -> BackupSetting {
+    self
+      .store_preferences
+      .get_object::<BackupSetting>(&backup_setting_key(uid))
+      .unwrap_or_default()
+  }
+
+  async fn backup_if_needed(&self) -> FlowyResult<()> {
+    let uid = match self.upgrade_user()?.user_id() {
+      Ok(uid) => uid,
+      // There is nothing to back up until the user signs in.
+      Err(_) => return Ok(()),
+    };
+    let setting = self.backup_setting(uid);
+    if !setting.enabled {
+      return Ok(());
+    }
+
+    let latest_backup_at = self
+      .list_backups()
+      .await?
+      .first()
+      .map(|backup| backup.created_at)
+      .unwrap_or(0);
+    if timestamp() - latest_backup_at >= setting.interval_hours as i64 * 3600 {
+      self.create_backup().await?;
+    }
+    Ok(())
+  }
+
+  fn encrypt_secret(&self, uid: i64) -> FlowyResult<String> {
+    get_encrypt_secret(uid, &self.store_preferences).ok_or(FlowyError::new(
+      ErrorCode::InvalidEncryptSecret,
+      "Encrypt secret is not set",
+    ))
+  }
+}
+
+#[async_trait]
+impl UserBackupService for BackupManager {
+  fn get_backup_setting(&self) -> FlowyResult<BackupSetting> {
+    let uid = self.upgrade_user()?.user_id()?;
+    Ok(self.backup_setting(uid))
+  }
+
+  fn set_backup_setting(&self, setting: BackupSetting) -> FlowyResult<()> {
+    let uid = self.upgrade_user()?.user_id()?;
+    if setting.encrypt {
+      // Fail early instead of failing every scheduled backup.
+      self.encrypt_secret(uid)?;
+    }
+    self
+      .store_preferences
+      .set_object(&backup_setting_key(uid), &setting)?;
+    Ok(())
+  }
+
+  #[instrument(level = "info", skip_all, err)]
+  async fn create_backup(&self) -> FlowyResult<BackupInfo> {
+    let _guard = self.lock.lock().await;
+    let user = self.upgrade_user()?;
+    let uid = user.user_id()?;
+    let setting = self.backup_setting(uid);
+    let encrypt_secret = if setting.encrypt {
+      Some(self.encrypt_secret(uid)?)
+    } else {
+      None
+    };
+
+    let params = CreateBackupParams {
+      uid,
+      workspace_id: user.workspace_id()?,
+      app_version: self.app_version.clone(),
+      storage_path: self.storage_path.clone(),
+      user_data_dir: user.get_user_data_dir()?,
+      backup_dir: self.backup_dir(uid),
+      encrypt_secret,
+      max_backups: setting.max_backups.max(1) as usize,
+    };
+    let manifest = tokio::task::spawn_blocking(move || write_backup(&user, params))
+      .await
+      .map_err(internal_error)??;
+    info!("Backed up the data of user {}: {}", uid, manifest.backup_id);
+    Ok(manifest.into())
+  }
+
+  async fn list_backups(&self) -> FlowyResult<Vec<BackupInfo>> {
+    let uid = self.upgrade_user()?.user_id()?;
+    let backup_dir = self.backup_dir(uid);
+    let manifests = tokio::task::spawn_blocking(move || read_manifests(&backup_dir))
+      .await
+      .map_err(internal_error)?;
+    Ok(manifests.into_iter().map(BackupInfo::from).collect())
+  }
+
+  #[instrument(level = "info", skip_all, err)]
+  async fn restore_backup(&self, backup_id: &str) -> FlowyResult<()> {
+    let _guard = self.lock.lock().await;
+    let uid = self.upgrade_user()?.user_id()?;
+    let backup_dir = self.backup_dir(uid);
+    let manifest = read_manifests(&backup_dir)
+      .into_iter()
+      .find(|manifest| manifest.backup_id == backup_id)
+      .ok_or(FlowyError::record_not_found().with_context("The backup is not found"))?;
+    let encrypt_secret = if manifest.encrypted {
+      Some(self.encrypt_secret(uid)?)
+    } else {
+      None
+    };
+
+    tokio::task::spawn_blocking(move || {
+      prepare_restore(uid, &backup_dir, &manifest, encrypt_secret.as_deref())
+    })
+    .await
+    .map_err(internal_error)??;
+    info!("The backup {} will be restored on next launch", backup_id);
+    Ok(())
+  }
+}
+
+struct CreateBackupParams {
+  uid: i64,
+  workspace_id: String,
+  app_version: String,
+  storage_path: PathBuf,
+  user_data_dir: PathBuf,
+  backup_dir: PathBuf,
+  encrypt_secret: Option<String>,
+  max_backups: usize,
+}
+
+fn write_backup(
+  user: &AuthenticateUser,
+  params: CreateBackupParams,
+) -> FlowyResult<BackupManifest> {
+  fs::create_dir_all(&params.backup_dir)?;
+  let created_at = timestamp();
+  let backup_id = format!("{}{}", BACKUP_FILE_PREFIX, created_at);
+  let encrypted = params.encrypt_secret.is_some();
+  let archive_path = params
+    .backup_dir
+    .join(archive_file_name(&backup_id, encrypted));
+  if archive_path.exists() {
+    return Err(FlowyError::new(
+      ErrorCode::Conflict,
+      "A backup was just created, try again later",
+    ));
+  }
+
+  // The temporary files are written next to the backups, so they are on the same disk.
+  let temp_dir = tempfile::tempdir_in(&params.backup_dir)?;
+  let content_dir = temp_dir.path().join("content");
+  let user_dir = content_dir.join(BACKUP_USER_DIR);
+  copy_user_data(&params.user_data_dir, &user_dir)?;
+  user.snapshot_databases(
+    params.uid,
+    &user_dir.join(DB_NAME),
+    &user_dir.join(COLLAB_DB_DIR),
+  )?;
+  // Only the files of the user are backed up. The folders of the storage path are shared with
+  // the other users.
+  let file_paths = select_upload_file_paths(user.get_sqlite_connection(params.uid)?)?;
+  let files = copy_user_files(
+    &params.storage_path,
+    &file_paths,
+    &content_dir.join(BACKUP_FILES_DIR),
+  )?;
+  let content = BackupContent {
+    version: BACKUP_CONTENT_VERSION,
+    uid: params.uid,
+    workspace_id: params.workspace_id.clone(),
+    files,
+  };
+  fs::write(
+    content_dir.join(BACKUP_CONTENT_FILE_NAME),
+    serde_json::to_vec_pretty(&content)?,
+  )?;
+
+  let zip_path = temp_dir.path().join("backup.zip");
+  zip_folder(&content_dir, &zip_path)?;
+  // The archive is encrypted and hashed while it's written, without reading it into memory.
+  let zip_file = io::BufReader::new(fs::File::open(&zip_path)?);
+  let mut writer = ChecksumWriter::new(io::BufWriter::new(fs::File::create(&archive_path)?));
+  let result = match &params.encrypt_secret {
+    Some(secret) => encrypt_stream(zip_file, &mut writer, secret).map_err(internal_error),
+    None => copy_and_flush(zip_file, &mut writer).map_err(FlowyError::from),
+  };
+  if let Err(err) = result {
+    drop(writer);
+    let _ = fs::remove_file(&archive_path);
+    return Err(err);
+  }
+  let (size, checksum) = writer.finish();
+
+  // Read the archive back to make sure it was written correctly.
+  if file_checksum(&archive_path)? != checksum {
+    let _ = fs::remove_file(&archive_path);
+    return Err(FlowyError::new(
+      ErrorCode::BackupCorrupted,
+      "The backup was not written correctly",
+    ));
+  }
+
+  let manifest = BackupManifest {
+    backup_id,
+    uid: params.uid,
+    workspace_id: params.workspace_id,
+    app_version: params.app_version,
+    created_at,
+    size,
+    encrypted,
+    checksum,
+  };
+  fs::write(
+    manifest_path(&params.backup_dir, &manifest.backup_id),
+    serde_json::to_vec_pretty(&manifest)?,
+  )?;
+
+  remove_old_backups(&params.backup_dir, params.max_backups);
+  Ok(manifest)
+}
+
+/// Copies the user data folder, except the folders that are not backed up and the databases,
+/// which are copied by [AuthenticateUser::snapshot_databases].
+fn copy_user_data(src: &Path, dst: &Path) -> io::Result<()> {
+  let entries = WalkDir::new(src)
+    .into_iter()
+    .filter_entry(|entry| {
+      entry.depth() != 1
+        || !entry
+          .file_name()
+          .to_str()
+          .map(|name| {
+            EXCLUDED_USER_DIRS.contains(&name)
+              || name == COLLAB_DB_DIR
+              // The sqlite database with its journal files.
+              || name.starts_with(DB_NAME)
+          })
+          .unwrap_or(false)
+    })
+    .filter_map(|entry| entry.ok());
+
+  for entry in entries {
+    let path = entry.path();
+    let target_path = dst.join(path.strip_prefix(src).unwrap());
+    if path.is_dir() {
+      fs::create_dir_all(&target_path)?;
+    } else {
+      fs::copy(path, target_path)?;
+    }
+  }
+  Ok(())
+}
+
+/// Copies the files into the folder. The files outside the storage path are not managed by the app,
+/// so they are skipped.
+fn copy_user_files(
+  storage_path: &Path,
+  file_paths: &[String],
+  dst: &Path,
+) -> io::Result<Vec<BackupFile>> {
+  let mut files = vec![];
+  for (index, file_path) in file_paths.iter().enumerate() {
+    let file_path = Path::new(file_path);
+    let relative_path = match file_path.strip_prefix(storage_path) {
+      Ok(relative_path) if file_path.is_file() => relative_path,
+      _ => continue,
+    };
+    let file_name = file_path
+      .file_name()
+      .map(|name| name.to_string_lossy().to_string())
+      .unwrap_or_default();
+    let archive_name = format!("{}_{}", index, file_name);
+    fs::create_dir_all(dst)?;
+    fs::copy(file_path, dst.join(&archive_name))?;
+    files.push(BackupFile {
+      path: relative_path.to_string_lossy().to_string(),
+      archive_name,
+    });
+  }
+  Ok(files)
+}
+
+/// Puts the files of the backup back to the storage path. The existing files are kept, since the
+/// folders of the storage path are shared with the other users.
+fn restore_user_files(storage_path: &Path, pending_restore_dir: &Path) {
+  let files = fs::read(pending_restore_dir.join(BACKUP_CONTENT_FILE_NAME))
+    .ok()
+    .and_then(|data| serde_json::from_slice::<BackupContent>(&data).ok())
+    .map(|content| content.files)
+    .unwrap_or_default();
+  for file in files {
+    let relative_path = Path::new(&file.path);
+    // The archive might be crafted to write the files outside the storage path.
+    if !relative_path
+      .components()
+      .all(|component| matches!(component, Component::Normal(_)))
+    {
+      continue;
+    }
+    let target = storage_path.join(relative_path);
+    if target.exists() {
+      continue;
+    }
+    let src = pending_restore_dir
+      .join(BACKUP_FILES_DIR)
+      .join(&file.archive_name);
+    let result = target
+      .parent()
+      .map(fs::create_dir_all)
+      .unwrap_or(Ok(()))
+      .and_then(|_| fs::rename(&src, &target));
+    if let Err(err) = result {
+      error!("Restore the file {} failed: {}", file.path, err);
+    }
+  }
+}
+
+/// Returns the manifests of the backups in the folder, the newest first.
+fn read_manifests(backup_dir: &Path) -> Vec<BackupManifest> {
+  let mut manifests = fs::read_dir(backup_dir)
+    .map(|entries| {
+      entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+          path.extension().and_then(|s| s.to_str()) == Some("json")
+            && path
+              .file_name()
+              .and_then(|s| s.to_str())
+              .map(|name| name.starts_with(BACKUP_FILE_PREFIX))
+              .unwrap_or(false)
+        })
+        .filter_map(|path| {
+          let data = fs::read(&path).ok()?;
+          serde_json::from_slice::<BackupManifest>(&data).ok()
+        })
+        // The backups whose archive was removed can't be restored.
+        .filter(|manifest| {
+          backup_dir
+            .join(archive_file_name(&manifest.backup_id, manifest.encrypted))
+            .exists()
+        })
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+  manifests.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+  manifests
+}
+
+fn remove_old_backups(backup_dir: &Path, max_backups: usize) {
+  for manifest in read_manifests(backup_dir).into_iter().skip(max_backups) {
+    info!("Remove old backup: {}", manifest.backup_id);
+    let archive_path = backup_dir.join(archive_file_name(&manifest.backup_id, manifest.encrypted));
+    if let Err(err) = fs::remove_file(archive_path) {
+      error!("Remove backup {} failed: {}", manifest.backup_id, err);
+      continue;
+    }
+    let _ = fs::remove_file(manifest_path(backup_dir, &manifest.backup_id));
+  }
+}
+
+/// Verifies the backup and unzips it into the pending restore folder. The data of the user is
+/// replaced on next launch by [BackupManager::apply_pending_restores].
+fn prepare_restore(
+  uid: i64,
+  backup_dir: &Path,
+  manifest: &BackupManifest,
+  encrypt_secret: Option<&str>,
+) -> FlowyResult<()> {
+  // A backup that was restored before, but not applied yet, is replaced by this one.
+  let _ = fs::remove_file(backup_dir.join(PENDING_RESTORE_FILE_NAME));
+
+  let archive_path = backup_dir.join(archive_file_name(&manifest.backup_id, manifest.encrypted));
+  if file_checksum(&archive_path)? != manifest.checksum {
+    return Err(FlowyError::new(
+      ErrorCode::BackupCorrupted,
+      "The checksum of the backup doesn't match",
+    ));
+  }
+
+  let temp_dir = tempfile::tempdir_in(backup_dir)?;
+  let zip_path = match encrypt_secret {
+    Some(secret) => {
+      let zip_path = temp_dir.path().join("backup.zip");
+      decrypt_stream(
+        io::BufReader::new(fs::File::open(&archive_path)?),
+        io::BufWriter::new(fs::File::create(&zip_path)?),
+        secret,
+      )
+      .map_err(|err| FlowyError::new(ErrorCode::InvalidEncryptSecret, err.to_string()))?;
+      zip_path
+    },
+    None => archive_path,
+  };
+  let pending_restore_dir = backup_dir.join(PENDING_RESTORE_DIR);
+  unzip_and_replace(&zip_path, &pending_restore_dir)
+    .map_err(|err| FlowyError::new(ErrorCode::BackupCorrupted, err.to_string()))?;
+
+  let content = fs::read(pending_restore_dir.join(BACKUP_CONTENT_FILE_NAME))
+    .ok()
+    .and_then(|data| serde_json::from_slice::<BackupContent>(&data).ok());
+  let is_valid = match content {
+    Some(content) => {
+      content.version <= BACKUP_CONTENT_VERSION
+        && content.uid == uid
+        && pending_restore_dir.join(BACKUP_USER_DIR).is_dir()
+    },
+    None => false,
+  };
+  if !is_valid {
+    let _ = fs::remove_dir_all(&pending_restore_dir);
+    return Err(FlowyError::new(
+      ErrorCode::BackupCorrupted,
+      "The backup doesn't contain the data of the user",
+    ));
+  }
+
+  let pending_restore = PendingRestore {
+    backup_id: manifest.backup_id.clone(),
+    requested_at: timestamp(),
+  };
+  fs::write(
+    backup_dir.join(PENDING_RESTORE_FILE_NAME),
+    serde_json::to_vec(&pending_restore)?,
+  )?;
+  Ok(())
+}
+
+fn apply_pending_restore(storage_path: &Path, uid: i64, backup_dir: &Path) -> io::Result<()> {
+  let pending_restore_dir = backup_dir.join(PENDING_RESTORE_DIR);
+  let user_data_dir = storage_path.join(uid.to_string());
+  let targets = [(
+    pending_restore_dir.join(BACKUP_USER_DIR),
+    user_data_dir.clone(),
+    BACKUP_USER_DIR.to_string(),
+  )];
+
+  let replaced_data_dir = backup_dir.join(REPLACED_DATA_DIR);
+  if replaced_data_dir.exists() {
+    fs::remove_dir_all(&replaced_data_dir)?;
+  }
+  fs::create_dir_all(&replaced_data_dir)?;
+
+  // The current data is moved aside instead of being removed, so it can be put back if any of
+  // the folders can't be replaced.
+  let mut replaced = vec![];
+  let mut result = Ok(());
+  for (src, target, name) in targets {
+    let aside = if target.exists() {
+      let aside = replaced_data_dir.join(&name);
+      if let Err(err) = fs::rename(&target, &aside) {
+        result = Err(err);
+        break;
+      }
+      Some(aside)
+    } else {
+      None
+    };
+    replaced.push((target.clone(), aside));
+    if let Err(err) = fs::rename(&src, &target) {
+      result = Err(err);
+      break;
+    }
+  }
+
+  if let Err(err) = result {
+    for (target, aside) in replaced.into_iter().rev() {
+      let _ = fs::remove_dir_all(&target);
+      if let Some(aside) = aside {
+        let _ = fs::rename(&aside, &target);
+      }
+    }
+    return Err(err);
+  }
+
+  // The history of the collab database is not part of the backups, so it's kept.
+  for dir in EXCLUDED_USER_DIRS {
+    let path = replaced_data_dir.join(BACKUP_USER_DIR).join(dir);
+    if path.exists() {
+      let _ = fs::rename(path, user_data_dir.join(dir));
+    }
+  }
+  restore_user_files(storage_path, &pending_restore_dir);
+  Ok(())
+}
+
+/// Computes the size and the MD5 checksum of the data that is written through it.
+struct ChecksumWriter<W> {
+  inner: W,
+  context: md5::Context,
+  size: u64,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+  fn new(inner: W) -> Self {
+    Self {
+      inner,
+      context: md5::Context::new(),
+      size: 0,
+    }
+  }
+
+  fn finish(self) -> (u64, String) {
+    (self.size, format!("{:x}", self.context.compute()))
+  }
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let len = self.inner.write(buf)?;
+    self.context.consume(&buf[..len]);
+    self.size += len as u64;
+    Ok(len)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.inner.flush()
+  }
+}
+
+fn copy_and_flush<R: io::Read, W: Write>(mut reader: R, writer: &mut W) -> io::Result<()> {
+  io::copy(&mut reader, writer)?;
+  writer.flush()
+}
+
+/// Returns the MD5 checksum of the file, reading it in chunks.
+fn file_checksum(path: &Path) -> io::Result<String> {
+  let mut writer = ChecksumWriter::new(io::sink());
+  io::copy(&mut io::BufReader::new(fs::File::open(path)?), &mut writer)?;
+  Ok(writer.finish().1)
+}
+
+fn backup_setting_key(uid: i64) -> String {
+  format!("{}:{}", BACKUP_SETTING_KEY, uid)
+}
+
+fn archive_file_name(backup_id: &str, encrypted: bool) -> String {
+  if encrypted {
+    format!("{}.zip.enc", backup_id)
+  } else {
+    format!("{}.zip", backup_id)
+  }
+}
+
+fn manifest_path(backup_dir: &Path, backup_id: &str) -> PathBuf {
+  backup_dir.join(format!("{}.json", backup_id))
+}
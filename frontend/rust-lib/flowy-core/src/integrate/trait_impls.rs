@@ -754,6 +754,18 @@ impl ChatCloudService for ServerProvider {
       .await
   }
 
+  async fn stream_complete_with_prompt(
+    &self,
+    workspace_id: &str,
+    prompt: &str,
+  ) -> Result<StreamComplete, FlowyError> {
+    self
+      .get_server()?
+      .chat_service()
+      .stream_complete_with_prompt(workspace_id, prompt)
+      .await
+  }
+
   async fn index_file(
     &self,
     workspace_id: &str,
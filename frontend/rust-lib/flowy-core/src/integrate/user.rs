@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use anyhow::Context;
 use client_api::entity::billing_dto::SubscriptionPlan;
-use tracing::{event, info};
+use tracing::{error, event, info};
 
 use collab_entity::CollabType;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
@@ -15,6 +15,7 @@ use flowy_storage::manager::StorageManager;
 use flowy_user::event_map::UserStatusCallback;
 use flowy_user_pub::cloud::{UserCloudConfig, UserCloudServiceProvider};
 use flowy_user_pub::entities::{Authenticator, UserProfile, UserWorkspace};
+use lib_dispatch::prelude::af_spawn;
 use lib_infra::async_trait::async_trait;
 
 use crate::integrate::server::{Server, ServerProvider};
@@ -29,6 +30,37 @@ pub(crate) struct UserStatusCallbackImpl {
   pub(crate) ai_manager: Arc<AIManager>,
 }
 
+impl UserStatusCallbackImpl {
+  /// Index the mentions and the tasks of the documents that were created before they were
+  /// indexed. It runs in the background because it reads every document of the workspace.
+  fn index_documents(&self) {
+    let document_manager = self.document_manager.clone();
+    af_spawn(async move {
+      let _ = document_manager.index_unindexed_documents().await;
+    });
+  }
+
+  /// Track the databases that were moved or copied into the workspace from another workspace.
+  async fn track_transferred_databases(&self, workspace_id: &str) {
+    match self
+      .folder_manager
+      .take_pending_database_views(workspace_id)
+    {
+      Ok(databases) if !databases.is_empty() => {
+        if let Err(err) = self
+          .database_manager
+          .update_database_indexing(databases)
+          .await
+        {
+          error!("Failed to track the transferred databases: {}", err);
+        }
+      },
+      Ok(_) => {},
+      Err(err) => error!("Failed to get the transferred databases: {}", err),
+    }
+  }
+}
+
 #[async_trait]
 impl UserStatusCallback for UserStatusCallbackImpl {
   async fn did_init(
@@ -69,7 +101,9 @@ impl UserStatusCallback for UserStatusCallbackImpl {
       .database_manager
       .initialize(user_id, authenticator == &Authenticator::Local)
       .await?;
+    self.track_transferred_databases(&user_workspace.id).await;
     self.document_manager.initialize(user_id).await?;
+    self.index_documents();
     self.ai_manager.initialize(&user_workspace.id).await?;
     Ok(())
   }
@@ -96,7 +130,9 @@ impl UserStatusCallback for UserStatusCallbackImpl {
       .database_manager
       .initialize(user_id, authenticator.is_local())
       .await?;
+    self.track_transferred_databases(&user_workspace.id).await;
     self.document_manager.initialize(user_id).await?;
+    self.index_documents();
     Ok(())
   }
 
@@ -197,7 +233,9 @@ impl UserStatusCallback for UserStatusCallbackImpl {
       .database_manager
       .initialize(user_id, authenticator.is_local())
       .await?;
+    self.track_transferred_databases(&user_workspace.id).await;
     self.document_manager.initialize(user_id).await?;
+    self.index_documents();
     self.ai_manager.initialize(&user_workspace.id).await?;
     self.storage_manager.initialize(&user_workspace.id).await;
     Ok(())
@@ -32,6 +32,7 @@ use module::make_plugins;
 use crate::config::AppFlowyCoreConfig;
 use crate::deps_resolve::file_storage_deps::FileStorageResolver;
 use crate::deps_resolve::*;
+use crate::integrate::backup::BackupManager;
 use crate::integrate::collab_interact::CollabInteractImpl;
 use crate::integrate::log::init_log;
 use crate::integrate::server::{current_server_type, Server, ServerProvider};
@@ -103,6 +104,9 @@ impl AppFlowyCore {
 
   #[instrument(skip(config, runtime))]
   async fn init(config: AppFlowyCoreConfig, runtime: Arc<AFPluginRuntime>) -> Self {
+    // Replace the user data with the restored backups before any database is opened
+    BackupManager::apply_pending_restores(&config.storage_path);
+
     // Init the key value database
     let store_preference = Arc::new(KVStorePreferences::new(&config.storage_path).unwrap());
     info!("🔥{:?}", &config);
@@ -194,6 +198,7 @@ impl AppFlowyCore {
         document_manager.clone(),
         database_manager.clone(),
         ai_manager.clone(),
+        Arc::downgrade(&storage_manager.storage_service),
       );
 
       let folder_manager = FolderDepsResolver::resolve(
@@ -203,8 +208,26 @@ impl AppFlowyCore {
         folder_indexer.clone(),
         store_preference.clone(),
         folder_operation_handlers,
+        &ai_manager,
       )
       .await;
+      ChatDepsResolver::set_workspace_source_service(
+        &ai_manager,
+        &folder_manager,
+        &document_manager,
+        &database_manager,
+      );
+      DocumentDepsResolver::set_mention_view_service(
+        &document_manager,
+        &folder_manager,
+        &database_manager,
+      );
+      DocumentDepsResolver::set_lock_service(
+        &document_manager,
+        &folder_manager,
+        &database_manager,
+      );
+      DatabaseDepsResolver::set_view_lock_service(&database_manager, &folder_manager);
 
       let user_manager = UserDepsResolver::resolve(
         authenticate_user.clone(),
@@ -237,6 +260,20 @@ impl AppFlowyCore {
     }
     .await;
 
+    let backup_manager = Arc::new(BackupManager::new(
+      Arc::downgrade(&authenticate_user),
+      store_preference.clone(),
+      &config.storage_path,
+      config.app_version.to_string(),
+    ));
+    user_manager
+      .set_backup_service(backup_manager.clone())
+      .await;
+    runtime.spawn(BackupManager::run_schedule(Arc::downgrade(&backup_manager)));
+    runtime.spawn(FolderManager::run_trash_purge_schedule(Arc::downgrade(
+      &folder_manager,
+    )));
+
     let user_status_callback = UserStatusCallbackImpl {
       collab_builder,
       folder_manager: folder_manager.clone(),
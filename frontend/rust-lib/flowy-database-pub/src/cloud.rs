@@ -28,6 +28,15 @@ pub trait DatabaseAIService: Send + Sync {
   ) -> Result<TranslateRowResponse, FlowyError> {
     Ok(TranslateRowResponse::default())
   }
+
+  /// Complete the prompt with the AI provider that is in use, and returns the whole response.
+  async fn complete_database_prompt(
+    &self,
+    _workspace_id: &str,
+    _prompt: &str,
+  ) -> Result<String, FlowyError> {
+    Err(FlowyError::not_support().with_context("Custom prompts are not supported"))
+  }
 }
 
 /// A trait for database cloud service.
@@ -0,0 +1,348 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::FlowyError;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+use crate::entities::FilterPB;
+use crate::services::automation::{
+  AutomationAction, AutomationExecutionStatus, AutomationLog, AutomationRule, AutomationTrigger,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum)]
+pub enum AutomationTriggerTypePB {
+  /// Triggered after a row is created in any view of the database.
+  #[default]
+  RowCreated = 0,
+  /// Triggered after the cell of the given field is changed.
+  CellChanged = 1,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct AutomationTriggerPB {
+  #[pb(index = 1)]
+  pub trigger_type: AutomationTriggerTypePB,
+
+  /// Required when the trigger type is [AutomationTriggerTypePB::CellChanged]
+  #[pb(index = 2, one_of)]
+  pub field_id: Option<String>,
+}
+
+impl From<AutomationTrigger> for AutomationTriggerPB {
+  fn from(trigger: AutomationTrigger) -> Self {
+    match trigger {
+      AutomationTrigger::RowCreated => Self {
+        trigger_type: AutomationTriggerTypePB::RowCreated,
+        field_id: None,
+      },
+      AutomationTrigger::CellChanged { field_id } => Self {
+        trigger_type: AutomationTriggerTypePB::CellChanged,
+        field_id: Some(field_id),
+      },
+    }
+  }
+}
+
+impl TryFrom<AutomationTriggerPB> for AutomationTrigger {
+  type Error = FlowyError;
+
+  fn try_from(pb: AutomationTriggerPB) -> Result<Self, Self::Error> {
+    match pb.trigger_type {
+      AutomationTriggerTypePB::RowCreated => Ok(AutomationTrigger::RowCreated),
+      AutomationTriggerTypePB::CellChanged => match pb.field_id {
+        Some(field_id) if !field_id.is_empty() => Ok(AutomationTrigger::CellChanged { field_id }),
+        _ => Err(
+          FlowyError::invalid_data().with_context("The cell changed trigger requires a field id"),
+        ),
+      },
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum)]
+pub enum AutomationActionTypePB {
+  /// Set the cell with the given string content. It works for the fields whose cell changeset is
+  /// a string, such as text, number, url and checkbox.
+  #[default]
+  SetCellContent = 0,
+  /// Set the date cell to the current time.
+  SetDateToNow = 1,
+  /// Select the given options of a single/multi select cell.
+  SetSelectOptions = 2,
+  /// Check or uncheck the checkbox cell.
+  SetCheckbox = 3,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct AutomationActionPB {
+  #[pb(index = 1)]
+  pub action_type: AutomationActionTypePB,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub content: String,
+
+  #[pb(index = 4)]
+  pub option_ids: Vec<String>,
+
+  #[pb(index = 5)]
+  pub is_checked: bool,
+}
+
+impl From<AutomationAction> for AutomationActionPB {
+  fn from(action: AutomationAction) -> Self {
+    match action {
+      AutomationAction::SetCellContent { field_id, content } => Self {
+        action_type: AutomationActionTypePB::SetCellContent,
+        field_id,
+        content,
+        ..Default::default()
+      },
+      AutomationAction::SetDateToNow { field_id } => Self {
+        action_type: AutomationActionTypePB::SetDateToNow,
+        field_id,
+        ..Default::default()
+      },
+      AutomationAction::SetSelectOptions {
+        field_id,
+        option_ids,
+      } => Self {
+        action_type: AutomationActionTypePB::SetSelectOptions,
+        field_id,
+        option_ids,
+        ..Default::default()
+      },
+      AutomationAction::SetCheckbox {
+        field_id,
+        is_checked,
+      } => Self {
+        action_type: AutomationActionTypePB::SetCheckbox,
+        field_id,
+        is_checked,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+impl From<AutomationActionPB> for AutomationAction {
+  fn from(pb: AutomationActionPB) -> Self {
+    match pb.action_type {
+      AutomationActionTypePB::SetCellContent => AutomationAction::SetCellContent {
+        field_id: pb.field_id,
+        content: pb.content,
+      },
+      AutomationActionTypePB::SetDateToNow => AutomationAction::SetDateToNow {
+        field_id: pb.field_id,
+      },
+      AutomationActionTypePB::SetSelectOptions => AutomationAction::SetSelectOptions {
+        field_id: pb.field_id,
+        option_ids: pb.option_ids,
+      },
+      AutomationActionTypePB::SetCheckbox => AutomationAction::SetCheckbox {
+        field_id: pb.field_id,
+        is_checked: pb.is_checked,
+      },
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct RepeatedAutomationActionPB {
+  #[pb(index = 1)]
+  pub items: Vec<AutomationActionPB>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct AutomationRulePB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub database_id: String,
+
+  #[pb(index = 3)]
+  pub name: String,
+
+  #[pb(index = 4)]
+  pub enabled: bool,
+
+  #[pb(index = 5)]
+  pub trigger: AutomationTriggerPB,
+
+  /// The condition reuses the filter model. The rule only runs when the row matches the filter.
+  #[pb(index = 6, one_of)]
+  pub condition: Option<FilterPB>,
+
+  #[pb(index = 7)]
+  pub actions: Vec<AutomationActionPB>,
+
+  #[pb(index = 8)]
+  pub created_at: i64,
+
+  #[pb(index = 9)]
+  pub updated_at: i64,
+}
+
+impl From<AutomationRule> for AutomationRulePB {
+  fn from(rule: AutomationRule) -> Self {
+    Self {
+      id: rule.id,
+      database_id: rule.database_id,
+      name: rule.name,
+      enabled: rule.enabled,
+      trigger: rule.trigger.into(),
+      condition: rule.condition.as_ref().map(FilterPB::from),
+      actions: rule.actions.into_iter().map(Into::into).collect(),
+      created_at: rule.created_at,
+      updated_at: rule.updated_at,
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct RepeatedAutomationRulePB {
+  #[pb(index = 1)]
+  pub items: Vec<AutomationRulePB>,
+}
+
+impl From<Vec<AutomationRule>> for RepeatedAutomationRulePB {
+  fn from(rules: Vec<AutomationRule>) -> Self {
+    Self {
+      items: rules.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct CreateAutomationRulePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub trigger: AutomationTriggerPB,
+
+  #[pb(index = 4, one_of)]
+  pub condition: Option<FilterPB>,
+
+  #[pb(index = 5)]
+  pub actions: Vec<AutomationActionPB>,
+}
+
+/// Pass in None if you don't want to modify a property
+/// Pass in Some(Value) if you want to modify a property
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct UpdateAutomationRulePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub rule_id: String,
+
+  #[pb(index = 3, one_of)]
+  pub name: Option<String>,
+
+  #[pb(index = 4, one_of)]
+  pub enabled: Option<bool>,
+
+  #[pb(index = 5, one_of)]
+  pub trigger: Option<AutomationTriggerPB>,
+
+  #[pb(index = 6, one_of)]
+  pub condition: Option<FilterPB>,
+
+  /// Remove the condition of the rule. It takes precedence over the `condition`.
+  #[pb(index = 7)]
+  pub remove_condition: bool,
+
+  #[pb(index = 8, one_of)]
+  pub actions: Option<RepeatedAutomationActionPB>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct DeleteAutomationRulePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub rule_id: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum)]
+pub enum AutomationExecutionStatusPB {
+  #[default]
+  Succeeded = 0,
+  Failed = 1,
+  /// The rule was skipped because it had already run in the current chain of automations.
+  SkippedByLoopProtection = 2,
+}
+
+impl From<AutomationExecutionStatus> for AutomationExecutionStatusPB {
+  fn from(status: AutomationExecutionStatus) -> Self {
+    match status {
+      AutomationExecutionStatus::Succeeded => Self::Succeeded,
+      AutomationExecutionStatus::Failed => Self::Failed,
+      AutomationExecutionStatus::SkippedByLoopProtection => Self::SkippedByLoopProtection,
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct AutomationLogPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub rule_id: String,
+
+  #[pb(index = 3)]
+  pub row_id: String,
+
+  #[pb(index = 4)]
+  pub status: AutomationExecutionStatusPB,
+
+  #[pb(index = 5)]
+  pub message: String,
+
+  #[pb(index = 6)]
+  pub executed_at: i64,
+}
+
+impl From<AutomationLog> for AutomationLogPB {
+  fn from(log: AutomationLog) -> Self {
+    Self {
+      id: log.id,
+      rule_id: log.rule_id,
+      row_id: log.row_id,
+      status: log.status.into(),
+      message: log.message,
+      executed_at: log.executed_at,
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct RepeatedAutomationLogPB {
+  #[pb(index = 1)]
+  pub items: Vec<AutomationLogPB>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct GetAutomationLogsPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  /// The maximum number of logs to return. Returns the latest 100 logs if it's not set.
+  #[pb(index = 2, one_of)]
+  pub limit: Option<i64>,
+}
@@ -1,3 +1,4 @@
+mod automation_entities;
 mod board_entities;
 pub mod calculation;
 mod calendar_entities;
@@ -11,6 +12,7 @@ mod group_entities;
 pub mod parser;
 mod position_entities;
 mod row_entities;
+mod row_template_entities;
 pub mod setting_entities;
 mod share_entities;
 mod sort_entities;
@@ -20,6 +22,7 @@ mod view_entities;
 #[macro_use]
 mod macros;
 
+pub use automation_entities::*;
 pub use board_entities::*;
 pub use calculation::*;
 pub use calendar_entities::*;
@@ -32,6 +35,7 @@ pub use filter_entities::*;
 pub use group_entities::*;
 pub use position_entities::*;
 pub use row_entities::*;
+pub use row_template_entities::*;
 pub use setting_entities::*;
 pub use share_entities::*;
 pub use sort_entities::*;
@@ -506,3 +506,52 @@ pub struct TranslateRowPB {
   #[validate(custom(function = "required_not_empty_str"))]
   pub field_id: String,
 }
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct FillAIColumnPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  /// The id of the Summary or Translate field
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub field_id: String,
+
+  /// Only generate the content of the empty cells
+  #[pb(index = 3)]
+  pub only_empty: bool,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct CancelAIColumnFillPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub field_id: String,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct AIColumnFillProgressPB {
+  #[pb(index = 1)]
+  pub field_id: String,
+
+  /// The number of rows that need to be filled
+  #[pb(index = 2)]
+  pub total: i64,
+
+  #[pb(index = 3)]
+  pub completed: i64,
+
+  #[pb(index = 4)]
+  pub failed: i64,
+
+  #[pb(index = 5)]
+  pub is_finished: bool,
+
+  #[pb(index = 6)]
+  pub is_cancelled: bool,
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use flowy_derive::ProtoBuf;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+use crate::entities::{CreateRowPayloadPB, OrderObjectPositionPB};
+use crate::services::row_template::RowTemplate;
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct RowTemplatePB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  /// The preset cell values keyed by field id.
+  #[pb(index = 3)]
+  pub cells: HashMap<String, String>,
+
+  /// The JSON of the `NestedBlock` that is used to pre-fill the row document.
+  #[pb(index = 4, one_of)]
+  pub document: Option<String>,
+
+  #[pb(index = 5)]
+  pub created_at: i64,
+
+  #[pb(index = 6)]
+  pub updated_at: i64,
+}
+
+impl From<RowTemplate> for RowTemplatePB {
+  fn from(template: RowTemplate) -> Self {
+    Self {
+      id: template.id,
+      name: template.name,
+      cells: template.cells,
+      document: template.document,
+      created_at: template.created_at,
+      updated_at: template.updated_at,
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct RepeatedRowTemplatePB {
+  #[pb(index = 1)]
+  pub items: Vec<RowTemplatePB>,
+
+  /// The default template of the view
+  #[pb(index = 2, one_of)]
+  pub default_template_id: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct RowTemplateCellsPB {
+  #[pb(index = 1)]
+  pub cells: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct CreateRowTemplatePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub cells: HashMap<String, String>,
+
+  /// Pre-fill the row document with the JSON of a `NestedBlock`.
+  #[pb(index = 4, one_of)]
+  pub document_json: Option<String>,
+
+  /// Pre-fill the row document with the content of an existing document. It's ignored if the
+  /// `document_json` is set.
+  #[pb(index = 5, one_of)]
+  pub document_id: Option<String>,
+}
+
+/// Pass in None if you don't want to modify a property
+/// Pass in Some(Value) if you want to modify a property
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct UpdateRowTemplatePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub template_id: String,
+
+  #[pb(index = 3, one_of)]
+  pub name: Option<String>,
+
+  /// Replace the preset cell values of the template
+  #[pb(index = 4, one_of)]
+  pub cells: Option<RowTemplateCellsPB>,
+
+  #[pb(index = 5, one_of)]
+  pub document_json: Option<String>,
+
+  #[pb(index = 6, one_of)]
+  pub document_id: Option<String>,
+
+  /// Remove the document of the template. It takes precedence over the `document_json` and the
+  /// `document_id`.
+  #[pb(index = 7)]
+  pub remove_document: bool,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct RowTemplateIdPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub template_id: String,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct SetDefaultRowTemplatePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  /// Pass None to remove the default template of the view.
+  #[pb(index = 2, one_of)]
+  pub template_id: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct CreateRowFromTemplatePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub template_id: String,
+
+  #[pb(index = 3)]
+  pub row_position: OrderObjectPositionPB,
+
+  #[pb(index = 4, one_of)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub group_id: Option<String>,
+
+  /// The cell values that override the preset cell values of the template.
+  #[pb(index = 5)]
+  pub data: HashMap<String, String>,
+}
+
+impl From<CreateRowFromTemplatePayloadPB> for CreateRowPayloadPB {
+  fn from(payload: CreateRowFromTemplatePayloadPB) -> Self {
+    Self {
+      view_id: payload.view_id,
+      row_position: payload.row_position,
+      group_id: payload.group_id,
+      data: payload.data,
+    }
+  }
+}
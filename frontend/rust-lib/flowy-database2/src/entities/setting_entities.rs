@@ -212,3 +212,15 @@ impl TryInto<LayoutSettingChangeset> for LayoutSettingChangesetPB {
     })
   }
 }
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct DatabaseViewQueryPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "lib_infra::validator_fn::required_not_empty_str"))]
+  pub view_id: String,
+
+  /// The natural-language query, for example "open tasks due this week sorted by priority"
+  #[pb(index = 2)]
+  #[validate(custom(function = "lib_infra::validator_fn::required_not_empty_str"))]
+  pub query: String,
+}
@@ -120,6 +120,7 @@ pub(crate) async fn update_database_setting_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -203,6 +204,7 @@ pub(crate) async fn delete_all_sorts_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let view_id: DatabaseViewIdPB = data.into_inner();
+  manager.check_view_editable(view_id.as_ref()).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(view_id.as_ref())
     .await?;
@@ -267,6 +269,7 @@ pub(crate) async fn update_field_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -281,6 +284,7 @@ pub(crate) async fn update_field_type_option_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: TypeOptionChangesetParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -301,6 +305,7 @@ pub(crate) async fn delete_field_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: FieldIdParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -315,6 +320,7 @@ pub(crate) async fn clear_field_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: FieldIdParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -331,6 +337,7 @@ pub(crate) async fn switch_to_field_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: EditFieldParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -353,6 +360,7 @@ pub(crate) async fn duplicate_field_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: DuplicateFieldPayloadPB = data.into_inner();
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -370,6 +378,7 @@ pub(crate) async fn create_field_handler(
 ) -> DataResult<FieldPB, FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: CreateFieldParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -387,6 +396,7 @@ pub(crate) async fn move_field_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: MoveFieldParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -448,6 +458,7 @@ pub(crate) async fn update_row_meta_handler(
 ) -> FlowyResult<()> {
   let manager = upgrade_manager(manager)?;
   let params: UpdateRowMetaParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -465,6 +476,7 @@ pub(crate) async fn delete_rows_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: RepeatedRowIdPB = data.into_inner();
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -484,6 +496,7 @@ pub(crate) async fn duplicate_row_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: RowIdParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -500,6 +513,7 @@ pub(crate) async fn move_row_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: MoveRowParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -516,6 +530,7 @@ pub(crate) async fn remove_cover_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: RemoveCoverParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -541,6 +556,7 @@ pub(crate) async fn create_row_handler(
 ) -> DataResult<RowMetaPB, FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -575,6 +591,7 @@ pub(crate) async fn update_cell_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: CellChangesetPB = data.into_inner();
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -618,6 +635,7 @@ pub(crate) async fn insert_or_update_select_option_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params = data.into_inner();
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -639,6 +657,7 @@ pub(crate) async fn delete_select_option_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params = data.into_inner();
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -660,6 +679,9 @@ pub(crate) async fn update_select_option_cell_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: SelectOptionCellChangesetParams = data.into_inner().try_into()?;
+  manager
+    .check_view_editable(&params.cell_identifier.view_id)
+    .await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.cell_identifier.view_id)
     .await?;
@@ -685,6 +707,7 @@ pub(crate) async fn update_checklist_cell_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.cell_id.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.cell_id.view_id)
     .await?;
@@ -719,6 +742,7 @@ pub(crate) async fn update_date_cell_handler(
     reminder_id: data.reminder_id,
   };
 
+  manager.check_view_editable(&cell_id.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&cell_id.view_id)
     .await?;
@@ -770,6 +794,7 @@ pub(crate) async fn set_group_by_field_handler(
 ) -> FlowyResult<()> {
   let manager = upgrade_manager(manager)?;
   let params: GroupByFieldParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -787,6 +812,7 @@ pub(crate) async fn update_group_handler(
   let manager = upgrade_manager(manager)?;
   let params: UpdateGroupParams = data.into_inner().try_into()?;
   let view_id = params.view_id.clone();
+  manager.check_view_editable(&view_id).await?;
   let database_editor = manager.get_database_editor_with_view_id(&view_id).await?;
   let group_changeset = GroupChangeset::from(params);
   database_editor
@@ -802,6 +828,7 @@ pub(crate) async fn move_group_handler(
 ) -> FlowyResult<()> {
   let manager = upgrade_manager(manager)?;
   let params: MoveGroupParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -818,6 +845,7 @@ pub(crate) async fn move_group_row_handler(
 ) -> FlowyResult<()> {
   let manager = upgrade_manager(manager)?;
   let params: MoveGroupRowParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -840,6 +868,7 @@ pub(crate) async fn create_group_handler(
 ) -> FlowyResult<()> {
   let manager = upgrade_manager(manager)?;
   let params: CreateGroupParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -856,6 +885,7 @@ pub(crate) async fn delete_group_handler(
 ) -> FlowyResult<()> {
   let manager = upgrade_manager(manager)?;
   let params: DeleteGroupParams = data.into_inner().try_into()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -909,6 +939,7 @@ pub(crate) async fn set_layout_setting_handler(
   let changeset = data.into_inner();
   let view_id = changeset.view_id.clone();
   let params: LayoutSettingChangeset = changeset.try_into()?;
+  manager.check_view_editable(&view_id).await?;
   let database_editor = manager.get_database_editor_with_view_id(&view_id).await?;
   database_editor.set_layout_setting(&view_id, params).await?;
   Ok(())
@@ -993,6 +1024,7 @@ pub(crate) async fn move_calendar_event_handler(
     timestamp: Some(data.timestamp),
     ..Default::default()
   };
+  manager.check_view_editable(&cell_id.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&cell_id.view_id)
     .await?;
@@ -1107,6 +1139,7 @@ pub(crate) async fn update_field_settings_handler(
 ) -> FlowyResult<()> {
   let manager = upgrade_manager(manager)?;
   let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -1139,6 +1172,7 @@ pub(crate) async fn update_calculation_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: UpdateCalculationChangesetPB = data.into_inner();
+  manager.check_view_editable(&params.view_id).await?;
   let editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -1155,6 +1189,7 @@ pub(crate) async fn remove_calculation_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: RemoveCalculationChangesetPB = data.into_inner();
+  manager.check_view_editable(&params.view_id).await?;
   let editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
@@ -1191,6 +1226,7 @@ pub(crate) async fn update_relation_cell_handler(
     removed_row_ids: params.removed_row_ids.into_iter().map(Into::into).collect(),
   };
 
+  manager.check_view_editable(&view_id).await?;
   let database_editor = manager.get_database_editor_with_view_id(&view_id).await?;
 
   // // get the related database
@@ -1257,6 +1293,7 @@ pub(crate) async fn summarize_row_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let data = data.into_inner();
+  manager.check_view_editable(&data.view_id).await?;
   let row_id = RowId::from(data.row_id);
   let (tx, rx) = oneshot::channel();
   af_spawn(async move {
@@ -1276,6 +1313,7 @@ pub(crate) async fn translate_row_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let data = data.try_into_inner()?;
+  manager.check_view_editable(&data.view_id).await?;
   let row_id = RowId::from(data.row_id);
   let (tx, rx) = oneshot::channel();
   af_spawn(async move {
@@ -1289,6 +1327,53 @@ pub(crate) async fn translate_row_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn fill_ai_column_handler(
+  data: AFPluginData<FillAIColumnPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
+  let task = manager
+    .start_ai_column_fill(&params.view_id, &params.field_id)
+    .await?;
+  af_spawn(async move {
+    if let Err(err) = manager
+      .fill_ai_column(params.view_id, params.only_empty, task)
+      .await
+    {
+      tracing::error!("[AI]: fill column failed: {}", err);
+    }
+  });
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn cancel_ai_column_fill_handler(
+  data: AFPluginData<CancelAIColumnFillPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.cancel_ai_column_fill(&params.field_id);
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn apply_database_view_query_handler(
+  data: AFPluginData<DatabaseViewQueryPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseViewSettingPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
+  let setting = manager
+    .apply_view_query(&params.view_id, &params.query)
+    .await?;
+  data_result_ok(setting)
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn update_media_cell_handler(
   data: AFPluginData<MediaCellChangesetPB>,
@@ -1307,6 +1392,7 @@ pub(crate) async fn update_media_cell_handler(
     removed_ids: params.removed_ids,
   };
 
+  manager.check_view_editable(&cell_id.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&cell_id.view_id)
     .await?;
@@ -1338,6 +1424,7 @@ pub(crate) async fn rename_media_cell_file_handler(
   let params: RenameMediaChangesetPB = data.into_inner();
   let cell_id: CellIdParams = params.cell_id.try_into()?;
 
+  manager.check_view_editable(&cell_id.view_id).await?;
   let database_editor = manager
     .get_database_editor_with_view_id(&cell_id.view_id)
     .await?;
@@ -1405,3 +1492,178 @@ pub(crate) async fn rename_media_cell_file_handler(
 
   Ok(())
 }
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn create_automation_rule_handler(
+  data: AFPluginData<CreateAutomationRulePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<AutomationRulePB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let rule = database_editor.create_automation_rule(params).await?;
+  data_result_ok(AutomationRulePB::from(rule))
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_automation_rules_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedAutomationRulePB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner();
+  let database_editor = manager
+    .get_database_editor_with_view_id(view_id.as_ref())
+    .await?;
+  let rules = database_editor.get_automation_rules().await?;
+  data_result_ok(RepeatedAutomationRulePB::from(rules))
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn update_automation_rule_handler(
+  data: AFPluginData<UpdateAutomationRulePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<AutomationRulePB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let rule = database_editor.update_automation_rule(params).await?;
+  data_result_ok(AutomationRulePB::from(rule))
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn delete_automation_rule_handler(
+  data: AFPluginData<DeleteAutomationRulePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  database_editor
+    .delete_automation_rule(&params.rule_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_automation_logs_handler(
+  data: AFPluginData<GetAutomationLogsPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedAutomationLogPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let logs = database_editor
+    .get_automation_logs(params.limit.unwrap_or(100))
+    .await?;
+  data_result_ok(RepeatedAutomationLogPB {
+    items: logs.into_iter().map(AutomationLogPB::from).collect(),
+  })
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn create_row_template_handler(
+  data: AFPluginData<CreateRowTemplatePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RowTemplatePB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let template = database_editor.create_row_template(params).await?;
+  data_result_ok(RowTemplatePB::from(template))
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_row_templates_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedRowTemplatePB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner();
+  let database_editor = manager
+    .get_database_editor_with_view_id(view_id.as_ref())
+    .await?;
+  let templates = database_editor.get_row_templates(view_id.as_ref()).await?;
+  data_result_ok(templates)
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn update_row_template_handler(
+  data: AFPluginData<UpdateRowTemplatePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RowTemplatePB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let template = database_editor.update_row_template(params).await?;
+  data_result_ok(RowTemplatePB::from(template))
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn delete_row_template_handler(
+  data: AFPluginData<RowTemplateIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  database_editor
+    .delete_row_template(&params.template_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn set_default_row_template_handler(
+  data: AFPluginData<SetDefaultRowTemplatePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  database_editor
+    .set_default_row_template(&params.view_id, params.template_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn create_row_from_template_handler(
+  data: AFPluginData<CreateRowFromTemplatePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RowMetaPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager.check_view_editable(&params.view_id).await?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+
+  match database_editor.create_row_from_template(params).await? {
+    Some(row) => data_result_ok(RowMetaPB::from(row)),
+    None => Err(FlowyError::internal().with_context("Error creating row")),
+  }
+}
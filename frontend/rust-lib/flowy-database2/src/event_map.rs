@@ -95,9 +95,25 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
          // AI
          .event(DatabaseEvent::SummarizeRow, summarize_row_handler)
          .event(DatabaseEvent::TranslateRow, translate_row_handler)
+         .event(DatabaseEvent::FillAIColumn, fill_ai_column_handler)
+         .event(DatabaseEvent::CancelAIColumnFill, cancel_ai_column_fill_handler)
+         .event(DatabaseEvent::ApplyDatabaseViewQuery, apply_database_view_query_handler)
          // Media
          .event(DatabaseEvent::UpdateMediaCell, update_media_cell_handler)
          .event(DatabaseEvent::RenameMediaFile, rename_media_cell_file_handler)
+         // Automation
+         .event(DatabaseEvent::CreateAutomationRule, create_automation_rule_handler)
+         .event(DatabaseEvent::GetAutomationRules, get_automation_rules_handler)
+         .event(DatabaseEvent::UpdateAutomationRule, update_automation_rule_handler)
+         .event(DatabaseEvent::DeleteAutomationRule, delete_automation_rule_handler)
+         .event(DatabaseEvent::GetAutomationLogs, get_automation_logs_handler)
+         // Row template
+         .event(DatabaseEvent::CreateRowTemplate, create_row_template_handler)
+         .event(DatabaseEvent::GetRowTemplates, get_row_templates_handler)
+         .event(DatabaseEvent::UpdateRowTemplate, update_row_template_handler)
+         .event(DatabaseEvent::DeleteRowTemplate, delete_row_template_handler)
+         .event(DatabaseEvent::SetDefaultRowTemplate, set_default_row_template_handler)
+         .event(DatabaseEvent::CreateRowFromTemplate, create_row_from_template_handler)
 }
 
 /// [DatabaseEvent] defines events that are used to interact with the Grid. You could check [this](https://appflowy.gitbook.io/docs/essential-documentation/contribute-to-appflowy/architecture/backend/protobuf)
@@ -401,4 +417,63 @@ pub enum DatabaseEvent {
 
   #[event(input = "RenameMediaChangesetPB")]
   RenameMediaFile = 201,
+
+  /// Create an automation rule for the database of the view. The rule runs the actions when it's
+  /// triggered and the row matches the condition.
+  #[event(input = "CreateAutomationRulePayloadPB", output = "AutomationRulePB")]
+  CreateAutomationRule = 210,
+
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedAutomationRulePB")]
+  GetAutomationRules = 211,
+
+  #[event(input = "UpdateAutomationRulePayloadPB", output = "AutomationRulePB")]
+  UpdateAutomationRule = 212,
+
+  #[event(input = "DeleteAutomationRulePayloadPB")]
+  DeleteAutomationRule = 213,
+
+  /// Returns the latest execution logs of the automation rules, newest first.
+  #[event(
+    input = "GetAutomationLogsPayloadPB",
+    output = "RepeatedAutomationLogPB"
+  )]
+  GetAutomationLogs = 214,
+
+  /// Create a row template for the database of the view. The template contains the preset cell
+  /// values and an optional row document.
+  #[event(input = "CreateRowTemplatePayloadPB", output = "RowTemplatePB")]
+  CreateRowTemplate = 220,
+
+  /// Returns all the row templates of the database and the default template of the view.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedRowTemplatePB")]
+  GetRowTemplates = 221,
+
+  #[event(input = "UpdateRowTemplatePayloadPB", output = "RowTemplatePB")]
+  UpdateRowTemplate = 222,
+
+  #[event(input = "RowTemplateIdPB")]
+  DeleteRowTemplate = 223,
+
+  /// The new rows of the view are pre-filled with the default template.
+  #[event(input = "SetDefaultRowTemplatePayloadPB")]
+  SetDefaultRowTemplate = 224,
+
+  #[event(input = "CreateRowFromTemplatePayloadPB", output = "RowMetaPB")]
+  CreateRowFromTemplate = 225,
+
+  /// Generate the content of a Summary or Translate field for all the rows of the view. The event
+  /// returns immediately, the progress is sent with the
+  /// DatabaseNotification::DidUpdateAIColumnFillProgress notification.
+  #[event(input = "FillAIColumnPayloadPB")]
+  FillAIColumn = 230,
+
+  #[event(input = "CancelAIColumnFillPayloadPB")]
+  CancelAIColumnFill = 231,
+
+  /// Configure the filters, sorts and group of the view with a natural-language query. The
+  /// existing filters and sorts of the view are replaced. To apply the query to a new view, create
+  /// the linked view with the query in the meta of the view, under the
+  /// [crate::services::ai::AI_VIEW_QUERY_KEY] key.
+  #[event(input = "DatabaseViewQueryPayloadPB", output = "DatabaseViewSettingPB")]
+  ApplyDatabaseViewQuery = 240,
 }
@@ -1,14 +1,16 @@
 use anyhow::anyhow;
-use arc_swap::ArcSwapOption;
+use arc_swap::{ArcSwap, ArcSwapOption};
 use async_trait::async_trait;
+use chrono::Local;
 use collab::core::collab::DataSource;
 use collab::core::origin::CollabOrigin;
 use collab::lock::RwLock;
 use collab::preclude::Collab;
-use collab_database::database::{Database, DatabaseData};
+use collab_database::database::{timestamp, Database, DatabaseData};
 use collab_database::entity::{CreateDatabaseParams, CreateViewParams};
 use collab_database::error::DatabaseError;
-use collab_database::rows::RowId;
+use collab_database::fields::media_type_option::{MediaCellData, MediaFile};
+use collab_database::rows::{database_row_document_id_from_row_id, Cell, CreateRowParams, RowId};
 use collab_database::template::csv::CSVTemplate;
 use collab_database::views::DatabaseLayout;
 use collab_database::workspace_database::{
@@ -30,16 +32,27 @@ use flowy_database_pub::cloud::{
   DatabaseAIService, DatabaseCloudService, SummaryRowContent, TranslateItem, TranslateRowContent,
 };
 use flowy_error::{internal_error, FlowyError, FlowyResult};
+use flowy_sqlite::DBConnection;
 use lib_dispatch::prelude::af_spawn;
 use lib_infra::box_any::BoxAny;
 use lib_infra::priority_task::TaskDispatcher;
 
-use crate::entities::{DatabaseLayoutPB, DatabaseSnapshotPB, FieldType, RowMetaPB};
+use crate::entities::{
+  AIColumnFillProgressPB, DatabaseLayoutPB, DatabaseSnapshotPB, DatabaseViewSettingPB, FieldType,
+  RowMetaPB, UpdateSortPayloadPB,
+};
+use crate::notification::{send_notification, DatabaseNotification};
+use crate::services::ai::{
+  build_view_query_prompt, generate_database_params, parse_view_query, AIColumnFillTask,
+  AIColumnFillTasks, AIResponseCache,
+};
 use crate::services::cell::stringify_cell;
 use crate::services::database::DatabaseEditor;
 use crate::services::database_view::DatabaseLayoutDepsResolver;
 use crate::services::field::translate_type_option::translate::TranslateTypeOption;
 use crate::services::field_settings::default_field_settings_by_layout_map;
+use crate::services::filter::FilterChangeset;
+use crate::services::row_template::RowDocumentServiceHolder;
 use crate::services::share::csv::{CSVFormat, CSVImporter, ImportResult};
 use tokio::sync::RwLock as TokioRwLock;
 
@@ -48,9 +61,38 @@ pub trait DatabaseUser: Send + Sync {
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError>;
   fn workspace_id(&self) -> Result<String, FlowyError>;
   fn workspace_database_object_id(&self) -> Result<String, FlowyError>;
+  fn sqlite_connection(&self, uid: i64) -> Result<DBConnection, FlowyError>;
+}
+
+/// The row documents are managed by the document module. The database uses this service to create
+/// and read them, where the content of a document is the JSON of the `NestedBlock` defined in
+/// flowy-document.
+#[async_trait]
+pub trait DatabaseRowDocumentService: Send + Sync {
+  async fn create_document_with_json(&self, document_id: &str, json: &str) -> FlowyResult<()>;
+
+  async fn get_document_json(&self, document_id: &str) -> FlowyResult<String>;
+}
+
+/// The views are locked by the folder. A database can't be changed through any of its views while
+/// one of them is locked.
+#[async_trait]
+pub trait DatabaseViewLockService: Send + Sync {
+  async fn is_view_locked(&self, view_id: &str) -> bool;
 }
 
 pub(crate) type DatabaseEditorMap = HashMap<String, Arc<DatabaseEditor>>;
+
+/// The document of a database row.
+#[derive(Debug, Clone)]
+pub struct RowDocumentMeta {
+  pub document_id: String,
+  /// The inline view of the database that contains the row.
+  pub view_id: String,
+  pub row_id: String,
+  pub name: String,
+}
+
 pub struct DatabaseManager {
   user: Arc<dyn DatabaseUser>,
   workspace_database_manager: ArcSwapOption<RwLock<WorkspaceDatabaseManager>>,
@@ -59,7 +101,11 @@ pub struct DatabaseManager {
   removing_editor: Arc<Mutex<HashMap<String, Arc<DatabaseEditor>>>>,
   collab_builder: Arc<AppFlowyCollabBuilder>,
   cloud_service: Arc<dyn DatabaseCloudService>,
-  ai_service: Arc<dyn DatabaseAIService>,
+  ai_service: ArcSwap<Arc<dyn DatabaseAIService>>,
+  row_document_service: RowDocumentServiceHolder,
+  view_lock_service: ArcSwapOption<Arc<dyn DatabaseViewLockService>>,
+  ai_response_cache: AIResponseCache,
+  ai_column_fills: AIColumnFillTasks,
 }
 
 impl DatabaseManager {
@@ -78,8 +124,108 @@ impl DatabaseManager {
       removing_editor: Default::default(),
       collab_builder,
       cloud_service,
-      ai_service,
+      ai_service: ArcSwap::from_pointee(ai_service),
+      row_document_service: Default::default(),
+      view_lock_service: Default::default(),
+      ai_response_cache: AIResponseCache::new(),
+      ai_column_fills: Default::default(),
+    }
+  }
+
+  pub fn set_row_document_service(&self, service: Arc<dyn DatabaseRowDocumentService>) {
+    self.row_document_service.store(Some(Arc::new(service)));
+  }
+
+  fn row_document_service(&self) -> FlowyResult<Arc<dyn DatabaseRowDocumentService>> {
+    self
+      .row_document_service
+      .load_full()
+      .map(|service| service.as_ref().clone())
+      .ok_or_else(|| FlowyError::internal().with_context("The row document service is not set"))
+  }
+
+  /// Replace the service that answers the AI requests of the databases, e.g. the summaries and
+  /// the translations of the rows.
+  pub fn set_ai_service(&self, ai_service: Arc<dyn DatabaseAIService>) {
+    self.ai_service.store(Arc::new(ai_service));
+  }
+
+  fn ai_service(&self) -> Arc<dyn DatabaseAIService> {
+    self.ai_service.load_full().as_ref().clone()
+  }
+
+  pub fn set_view_lock_service(&self, service: Arc<dyn DatabaseViewLockService>) {
+    self.view_lock_service.store(Some(Arc::new(service)));
+  }
+
+  /// Returns a `ViewLocked` error if the database of the view is locked.
+  pub async fn check_view_editable(&self, view_id: &str) -> FlowyResult<()> {
+    if self.is_database_locked(view_id).await {
+      return Err(FlowyError::view_locked());
+    }
+    Ok(())
+  }
+
+  /// Returns true if any view of the database that contains the view is locked. All the views of
+  /// a database share its rows and fields, so locking one of them locks the whole database.
+  pub async fn is_database_locked(&self, view_id: &str) -> bool {
+    let lock_service = match self.view_lock_service.load_full() {
+      Some(lock_service) => lock_service,
+      None => return false,
+    };
+    if lock_service.is_view_locked(view_id).await {
+      return true;
+    }
+    let database_id = match self.get_database_id_with_view_id(view_id).await {
+      Ok(database_id) => database_id,
+      Err(_) => return false,
+    };
+    let linked_views = self
+      .get_all_databases_meta()
+      .await
+      .into_iter()
+      .find(|meta| meta.database_id == database_id)
+      .map(|meta| meta.linked_views)
+      .unwrap_or_default();
+    for linked_view_id in linked_views {
+      if linked_view_id != view_id && lock_service.is_view_locked(&linked_view_id).await {
+        return true;
+      }
+    }
+    false
+  }
+
+  /// Returns true if the document belongs to a row of a locked database.
+  pub async fn is_row_document_locked(&self, document_id: &str) -> bool {
+    let lock_service = match self.view_lock_service.load_full() {
+      Some(lock_service) => lock_service,
+      None => return false,
+    };
+    for meta in self.get_all_databases_meta().await {
+      let mut locked_view_id = None;
+      for view_id in &meta.linked_views {
+        if lock_service.is_view_locked(view_id).await {
+          locked_view_id = Some(view_id.clone());
+          break;
+        }
+      }
+      // Only the rows of the locked databases are read.
+      let view_id = match locked_view_id {
+        Some(view_id) => view_id,
+        None => continue,
+      };
+      let row_ids = self
+        .get_database_row_ids_with_view_id(&view_id)
+        .await
+        .unwrap_or_default();
+      if row_ids
+        .iter()
+        .any(|row_id| database_row_document_id_from_row_id(row_id) == document_id)
+      {
+        return true;
+      }
     }
+    false
   }
 
   /// When initialize with new workspace, all the resources will be cleared.
@@ -251,6 +397,7 @@ impl DatabaseManager {
       database,
       self.task_scheduler.clone(),
       self.collab_builder.clone(),
+      self.row_document_service.clone(),
     )
     .await?;
 
@@ -340,6 +487,119 @@ impl DatabaseManager {
     Ok(())
   }
 
+  /// Returns the urls of the files attached to the rows of the database of the view.
+  pub async fn get_database_file_urls(&self, view_id: &str) -> FlowyResult<Vec<String>> {
+    let urls = self
+      .get_database_media_files(view_id)
+      .await?
+      .into_iter()
+      .map(|file| file.url)
+      .collect();
+    Ok(urls)
+  }
+
+  /// Returns the files attached to the media cells of the rows of the database of the view.
+  pub async fn get_database_media_files(&self, view_id: &str) -> FlowyResult<Vec<MediaFile>> {
+    let files = self
+      .get_database_media_cells(view_id)
+      .await?
+      .into_iter()
+      .flat_map(|(_, _, cell_data)| cell_data.files)
+      .collect();
+    Ok(files)
+  }
+
+  /// Returns the media cells of the database of the view, by the row ids and the field ids, with
+  /// the urls of their files replaced according to `urls`. Only the cells with a replaced url are
+  /// returned.
+  pub async fn replace_database_media_urls(
+    &self,
+    view_id: &str,
+    urls: &HashMap<String, String>,
+  ) -> FlowyResult<HashMap<String, HashMap<String, Cell>>> {
+    let mut row_cells: HashMap<String, HashMap<String, Cell>> = HashMap::new();
+    for (row_id, field_id, mut cell_data) in self.get_database_media_cells(view_id).await? {
+      let mut is_replaced = false;
+      for file in cell_data.files.iter_mut() {
+        if let Some(new_url) = urls.get(&file.url) {
+          file.url = new_url.clone();
+          is_replaced = true;
+        }
+      }
+      if is_replaced {
+        row_cells
+          .entry(row_id.into_inner())
+          .or_default()
+          .insert(field_id, Cell::from(cell_data));
+      }
+    }
+    Ok(row_cells)
+  }
+
+  /// Returns the media cells of the rows of the database of the view with the ids of their rows
+  /// and fields.
+  async fn get_database_media_cells(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<Vec<(RowId, String, MediaCellData)>> {
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    let media_field_ids = database
+      .get_fields(view_id, None)
+      .await
+      .into_iter()
+      .filter(|field| FieldType::from(field.field_type) == FieldType::Media)
+      .map(|field| field.id)
+      .collect::<Vec<_>>();
+    if media_field_ids.is_empty() {
+      return Ok(vec![]);
+    }
+
+    let cells = database
+      .get_all_rows(view_id)
+      .await?
+      .iter()
+      .flat_map(|row| {
+        media_field_ids
+          .iter()
+          .filter_map(|field_id| {
+            row
+              .cells
+              .get(field_id)
+              .map(|cell| (row.id.clone(), field_id.clone(), MediaCellData::from(cell)))
+          })
+          .collect::<Vec<_>>()
+      })
+      .collect();
+    Ok(cells)
+  }
+
+  /// Deletes the view with its database, rows and row documents from the disk. It's called when
+  /// the last view of the database is deleted permanently.
+  #[instrument(level = "debug", skip(self), err)]
+  pub async fn delete_database_with_view_id(&self, view_id: &str) -> FlowyResult<()> {
+    let database_id = self.get_database_id_with_view_id(view_id).await?;
+    let row_ids = self.get_database_row_ids_with_view_id(view_id).await?;
+    self.delete_database_view(view_id).await?;
+
+    let uid = self.user.user_id()?;
+    let workspace_id = self.user.workspace_id()?;
+    if let Some(collab_db) = self.user.collab_db(uid)?.upgrade() {
+      let object_ids = row_ids
+        .into_iter()
+        .flat_map(|row_id| {
+          let row_id = row_id.into_inner();
+          [database_row_document_id_from_row_id(&row_id), row_id]
+        })
+        .chain(std::iter::once(database_id));
+      for object_id in object_ids {
+        if let Err(err) = collab_db.delete_doc(uid, &workspace_id, &object_id).await {
+          error!("[Database]: delete collab {} failed: {}", object_id, err);
+        }
+      }
+    }
+    Ok(())
+  }
+
   pub async fn get_database_data(&self, view_id: &str) -> FlowyResult<DatabaseData> {
     let lock = self.workspace_database()?;
     let wdb = lock.read().await;
@@ -388,6 +648,151 @@ impl DatabaseManager {
     Ok(encoded_collab)
   }
 
+  /// Returns the JSON of the [DatabaseData] that only contains the view, to save the view in a
+  /// template, and the ids of the database and its rows. The rows are only kept if `include_rows`
+  /// is true.
+  pub async fn get_database_template_data(
+    &self,
+    view_id: &str,
+    include_rows: bool,
+  ) -> FlowyResult<(String, Vec<String>)> {
+    let mut data = self.get_database_data(view_id).await?;
+    data.views.retain(|view| view.id == view_id);
+    if !include_rows {
+      data.rows.clear();
+    }
+    let object_ids = std::iter::once(data.database_id.clone())
+      .chain(data.rows.iter().map(|row| row.id.to_string()))
+      .collect();
+    Ok((serde_json::to_string(&data)?, object_ids))
+  }
+
+  /// Returns the JSON of the documents of the rows in the view keyed by the row ids. The rows
+  /// whose document is empty are left out.
+  pub async fn get_row_documents_json(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<HashMap<String, String>> {
+    let document_service = self.row_document_service()?;
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    let mut documents = HashMap::new();
+    for row in database.get_all_rows(view_id).await? {
+      let document_id = match database.get_row_meta(view_id, &row.id).await {
+        Some(RowMetaPB {
+          document_id: Some(document_id),
+          is_document_empty: Some(false),
+          ..
+        }) => document_id,
+        _ => continue,
+      };
+      match document_service.get_document_json(&document_id).await {
+        Ok(json) => {
+          documents.insert(row.id.to_string(), json);
+        },
+        Err(err) => warn!("Failed to read the document of the row {}: {}", row.id, err),
+      }
+    }
+    Ok(documents)
+  }
+
+  /// Create the documents of the rows from their JSON keyed by the row ids, like the ones
+  /// returned by [Self::get_row_documents_json] whose row ids were replaced with new ids.
+  pub async fn create_row_documents_json(
+    &self,
+    documents: HashMap<String, String>,
+  ) -> FlowyResult<()> {
+    let document_service = self.row_document_service()?;
+    for (row_id, json) in documents {
+      let document_id = database_row_document_id_from_row_id(&RowId::from(row_id));
+      document_service
+        .create_document_with_json(&document_id, &json)
+        .await?;
+    }
+    Ok(())
+  }
+
+  /// Create the database of a template, where the ids of the database and the rows are already
+  /// replaced with new ids. Unlike [Self::create_database_with_data], the ids are kept, so the
+  /// relations between the databases of the template remain. If the database was already created
+  /// by another view of the template, the view is linked to it.
+  #[tracing::instrument(level = "trace", skip(self, data), err)]
+  pub async fn create_database_from_template(
+    &self,
+    view_id: &str,
+    data: &str,
+  ) -> FlowyResult<ImportResult> {
+    let data: DatabaseData = serde_json::from_str(data)?;
+    let view = data
+      .views
+      .into_iter()
+      .next()
+      .ok_or_else(|| FlowyError::invalid_data().with_context("The database data is empty"))?;
+    let now = timestamp();
+    let view_params = CreateViewParams {
+      database_id: data.database_id.clone(),
+      view_id: view_id.to_string(),
+      name: view.name,
+      layout: view.layout,
+      layout_settings: view.layout_settings,
+      filters: view.filters,
+      group_settings: view.group_settings,
+      sorts: view.sorts,
+      field_settings: view.field_settings,
+      created_at: now,
+      modified_at: now,
+      ..Default::default()
+    };
+
+    let is_created = self
+      .get_all_databases_meta()
+      .await
+      .iter()
+      .any(|meta| meta.database_id == data.database_id);
+    if is_created {
+      let lock = self.workspace_database()?;
+      lock
+        .write()
+        .await
+        .create_database_linked_view(view_params)
+        .await?;
+      return Ok(ImportResult {
+        database_id: data.database_id,
+        view_id: view_id.to_string(),
+        encoded_collabs: vec![],
+      });
+    }
+
+    let rows = data
+      .rows
+      .into_iter()
+      .map(|row| {
+        let mut params = CreateRowParams::new(row.id, data.database_id.clone());
+        params.cells = row.cells;
+        params.height = row.height;
+        params.visibility = row.visibility;
+        params.created_at = now;
+        params.modified_at = now;
+        params
+      })
+      .collect();
+    let params = CreateDatabaseParams {
+      database_id: data.database_id.clone(),
+      views: vec![view_params],
+      rows,
+      fields: data.fields,
+    };
+    let database = self.import_database(params).await?;
+    let encoded_database = database.read().await.encode_database_collabs().await?;
+    let encoded_collabs = std::iter::once(encoded_database.encoded_database_collab)
+      .chain(encoded_database.encoded_row_collabs)
+      .collect();
+    Ok(ImportResult {
+      database_id: data.database_id,
+      view_id: view_id.to_string(),
+      encoded_collabs,
+    })
+  }
+
   /// When duplicating a database view, it will duplicate all the database views and replace the duplicated
   /// database_view_id with the new_database_view_id. The new database id is the ID created by Folder.
   #[tracing::instrument(level = "trace", skip_all, err)]
@@ -423,6 +828,34 @@ impl DatabaseManager {
     Ok(database)
   }
 
+  /// Create a new database whose fields and example rows are designed by the AI for the prompt.
+  /// Nothing is created if the response of the AI is invalid.
+  #[instrument(level = "debug", skip(self), err)]
+  pub async fn create_database_with_prompt(
+    &self,
+    view_id: &str,
+    name: &str,
+    layout: DatabaseLayout,
+    prompt: &str,
+  ) -> FlowyResult<EncodedCollab> {
+    let params = generate_database_params(
+      self.ai_service().as_ref(),
+      &self.user.workspace_id()?,
+      view_id,
+      name,
+      layout,
+      prompt,
+    )
+    .await?;
+    let database = self.import_database(params).await?;
+    let encoded_collab = database
+      .read()
+      .await
+      .encode_collab_v1(|collab| CollabType::Database.validate_require_data(collab))
+      .map_err(|err| FlowyError::internal().with_context(err))?;
+    Ok(encoded_collab)
+  }
+
   /// A linked view is a view that is linked to existing database.
   #[tracing::instrument(level = "trace", skip(self), err)]
   pub async fn create_linked_view(
@@ -470,7 +903,7 @@ impl DatabaseManager {
         database_template.into_params()
       },
 
-      CSVFormat::META => {
+      CSVFormat::META | CSVFormat::Notion => {
         let cloned_view_id = view_id.clone();
         tokio::task::spawn_blocking(move || {
           CSVImporter.import_csv_from_string(cloned_view_id, content, format)
@@ -510,6 +943,20 @@ impl DatabaseManager {
     database.export_csv(style).await
   }
 
+  /// Returns the rows of the view as a read-only HTML table.
+  pub async fn export_html_table(&self, view_id: &str) -> FlowyResult<String> {
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    database.export_html_table(view_id).await
+  }
+
+  /// Returns the fields of the view, with their type options, as JSON. It's exported next to the
+  /// CSV of the view, so the CSV can be imported with the original field types.
+  pub async fn export_schema(&self, view_id: &str) -> FlowyResult<String> {
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    let fields = database.get_fields(view_id, None).await;
+    Ok(serde_json::to_string_pretty(&fields)?)
+  }
+
   pub async fn update_database_layout(
     &self,
     view_id: &str,
@@ -548,6 +995,80 @@ impl DatabaseManager {
       .ok_or_else(|| FlowyError::internal().with_context("Workspace database not initialized"))
   }
 
+  /// Returns the id and the text of each row in the view, where the text is the name and the
+  /// content of each non-empty cell, one cell per line.
+  pub async fn stringify_rows(&self, view_id: &str) -> FlowyResult<Vec<(String, String)>> {
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    let fields = database.get_fields(view_id, None).await;
+    let rows = database.get_all_rows(view_id).await?;
+    let texts = rows
+      .iter()
+      .filter_map(|row| {
+        let text = fields
+          .iter()
+          .filter_map(|field| {
+            let cell = row.cells.get(&field.id)?;
+            let content = stringify_cell(cell, field);
+            (!content.trim().is_empty()).then(|| format!("{}: {}", field.name, content))
+          })
+          .collect::<Vec<_>>()
+          .join("\n");
+        (!text.is_empty()).then(|| (row.id.to_string(), text))
+      })
+      .collect();
+    Ok(texts)
+  }
+
+  /// Returns the documents of the rows in all the databases. The name of a row document is the
+  /// content of the primary cell of the row.
+  pub async fn get_all_row_documents(&self) -> Vec<RowDocumentMeta> {
+    let mut documents = vec![];
+    for meta in self.get_all_databases_meta().await {
+      let view_id = match meta.linked_views.first() {
+        Some(view_id) => view_id.clone(),
+        None => continue,
+      };
+      let database = match self.get_database_editor_with_view_id(&view_id).await {
+        Ok(database) => database,
+        Err(err) => {
+          warn!("Failed to open the database {}: {}", meta.database_id, err);
+          continue;
+        },
+      };
+      let primary_field = database
+        .get_fields(&view_id, None)
+        .await
+        .into_iter()
+        .find(|field| field.is_primary);
+      let rows = database.get_all_rows(&view_id).await.unwrap_or_default();
+      for row in rows {
+        let document_id = match database.get_row_meta(&view_id, &row.id).await {
+          Some(RowMetaPB {
+            document_id: Some(document_id),
+            ..
+          }) => document_id,
+          _ => continue,
+        };
+        let name = primary_field
+          .as_ref()
+          .and_then(|field| {
+            row
+              .cells
+              .get(&field.id)
+              .map(|cell| stringify_cell(cell, field))
+          })
+          .unwrap_or_default();
+        documents.push(RowDocumentMeta {
+          document_id,
+          view_id: view_id.clone(),
+          row_id: row.id.to_string(),
+          name,
+        });
+      }
+    }
+    documents
+  }
+
   #[instrument(level = "debug", skip_all)]
   pub async fn summarize_row(
     &self,
@@ -579,10 +1100,21 @@ impl DatabaseManager {
       row_id,
       summary_row_content
     );
-    let response = self
-      .ai_service
-      .summary_database_row(&self.user.workspace_id()?, &row_id, summary_row_content)
-      .await?;
+    let cache_key = AIResponseCache::summary_key(&summary_row_content);
+    let response = match self.ai_response_cache.get(cache_key).await {
+      Some(response) => response,
+      None => {
+        let response = self
+          .ai_service()
+          .summary_database_row(&self.user.workspace_id()?, &row_id, summary_row_content)
+          .await?;
+        self
+          .ai_response_cache
+          .insert(cache_key, response.clone())
+          .await;
+        response
+      },
+    };
     trace!("[AI]:summarize row response: {}", response);
 
     // Update the cell with the response from the cloud service.
@@ -640,24 +1172,35 @@ impl DatabaseManager {
       language,
       translate_row_content
     );
-    let response = self
-      .ai_service
-      .translate_database_row(&self.user.workspace_id()?, translate_row_content, &language)
-      .await?;
-
-    // Format the response items into a single string
-    let content = response
-      .items
-      .into_iter()
-      .map(|value| {
-        value
-          .into_values()
-          .map(|v| v.to_string())
+    let cache_key = AIResponseCache::translate_key(&translate_row_content, &language);
+    let content = match self.ai_response_cache.get(cache_key).await {
+      Some(content) => content,
+      None => {
+        let response = self
+          .ai_service()
+          .translate_database_row(&self.user.workspace_id()?, translate_row_content, &language)
+          .await?;
+
+        // Format the response items into a single string
+        let content = response
+          .items
+          .into_iter()
+          .map(|value| {
+            value
+              .into_values()
+              .map(|v| v.to_string())
+              .collect::<Vec<String>>()
+              .join(", ")
+          })
           .collect::<Vec<String>>()
-          .join(", ")
-      })
-      .collect::<Vec<String>>()
-      .join(",");
+          .join(",");
+        self
+          .ai_response_cache
+          .insert(cache_key, content.clone())
+          .await;
+        content
+      },
+    };
 
     trace!("[AI]:translate row response: {}", content);
     // Update the cell with the response from the cloud service.
@@ -667,6 +1210,187 @@ impl DatabaseManager {
     Ok(())
   }
 
+  /// Validate the field and register a new fill task of the field. The running fill of the same
+  /// field is cancelled.
+  pub async fn start_ai_column_fill(
+    &self,
+    view_id: &str,
+    field_id: &str,
+  ) -> FlowyResult<AIColumnFillTask> {
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    let field = database.get_field(field_id).await.ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!("Field:{} not found", field_id))
+    })?;
+    if !FieldType::from(field.field_type).is_ai_field() {
+      return Err(
+        FlowyError::invalid_data()
+          .with_context("Only the Summary and Translate fields can be filled by AI"),
+      );
+    }
+    Ok(self.ai_column_fills.start(field_id))
+  }
+
+  /// Generate the content of the AI field for the rows of the view one by one. The progress is
+  /// sent after each row, and the fill stops as soon as the task is cancelled.
+  #[instrument(level = "debug", skip_all, err)]
+  pub async fn fill_ai_column(
+    &self,
+    view_id: String,
+    only_empty: bool,
+    task: AIColumnFillTask,
+  ) -> FlowyResult<()> {
+    let result = self.run_ai_column_fill(&view_id, only_empty, &task).await;
+    self.ai_column_fills.finish(&task);
+    result
+  }
+
+  async fn run_ai_column_fill(
+    &self,
+    view_id: &str,
+    only_empty: bool,
+    task: &AIColumnFillTask,
+  ) -> FlowyResult<()> {
+    let field_id = task.field_id.clone();
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    let field = database.get_field(&field_id).await.ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!("Field:{} not found", field_id))
+    })?;
+    let field_type = FieldType::from(field.field_type);
+    let row_ids = database
+      .get_all_rows(view_id)
+      .await?
+      .into_iter()
+      .filter(|row| {
+        !only_empty
+          || row
+            .cells
+            .get(&field_id)
+            .map(|cell| stringify_cell(cell, &field).is_empty())
+            .unwrap_or(true)
+      })
+      .map(|row| row.id.clone())
+      .collect::<Vec<_>>();
+
+    let mut progress = AIColumnFillProgressPB {
+      field_id: field_id.clone(),
+      total: row_ids.len() as i64,
+      ..Default::default()
+    };
+    for row_id in row_ids {
+      let fill_row = async {
+        if field_type == FieldType::Summary {
+          self
+            .summarize_row(view_id.to_string(), row_id.clone(), field_id.clone())
+            .await
+        } else {
+          self
+            .translate_row(view_id.to_string(), row_id.clone(), field_id.clone())
+            .await
+        }
+      };
+      let result = tokio::select! {
+        _ = task.token.cancelled() => None,
+        result = fill_row => Some(result),
+      };
+      match result {
+        None => {
+          progress.is_cancelled = true;
+          break;
+        },
+        Some(Ok(())) => progress.completed += 1,
+        Some(Err(err)) => {
+          warn!(
+            "[AI]: fill row:{} of field:{} failed: {}",
+            row_id, field_id, err
+          );
+          progress.failed += 1;
+        },
+      }
+      if progress.completed + progress.failed < progress.total {
+        send_notification(view_id, DatabaseNotification::DidUpdateAIColumnFillProgress)
+          .payload(progress.clone())
+          .send();
+      }
+    }
+
+    progress.is_finished = true;
+    send_notification(view_id, DatabaseNotification::DidUpdateAIColumnFillProgress)
+      .payload(progress)
+      .send();
+    Ok(())
+  }
+
+  /// Returns false if there is no running fill of the field.
+  pub fn cancel_ai_column_fill(&self, field_id: &str) -> bool {
+    self.ai_column_fills.cancel(field_id)
+  }
+
+  pub fn is_ai_column_filling(&self, field_id: &str) -> bool {
+    self.ai_column_fills.is_running(field_id)
+  }
+
+  /// Translate the natural-language query into filters, sorts and a group with the AI, then
+  /// replace the filters and sorts of the view with them. Nothing is applied if the response
+  /// refers to a field, a condition or an option that doesn't exist.
+  #[instrument(level = "debug", skip_all, err)]
+  pub async fn apply_view_query(
+    &self,
+    view_id: &str,
+    query: &str,
+  ) -> FlowyResult<DatabaseViewSettingPB> {
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    let fields = database.get_fields(view_id, None).await;
+    let prompt = build_view_query_prompt(&fields, query, Local::now().date_naive());
+    trace!("[AI]: view query prompt: {}", prompt);
+    let response = self
+      .ai_service()
+      .complete_database_prompt(&self.user.workspace_id()?, &prompt)
+      .await?;
+    trace!("[AI]: view query response: {}", response);
+    let view_query = parse_view_query(&response, &fields)?;
+
+    for filter in database.get_all_filters(view_id).await.items {
+      database
+        .modify_view_filters(
+          view_id,
+          FilterChangeset::Delete {
+            filter_id: filter.id,
+          },
+        )
+        .await?;
+    }
+    for data in view_query.filters {
+      database
+        .modify_view_filters(
+          view_id,
+          FilterChangeset::Insert {
+            parent_filter_id: None,
+            data,
+          },
+        )
+        .await?;
+    }
+
+    database.delete_all_sorts(view_id).await;
+    for (field_id, condition) in view_query.sorts {
+      database
+        .create_or_update_sort(UpdateSortPayloadPB {
+          view_id: view_id.to_string(),
+          field_id,
+          sort_id: None,
+          condition: condition.into(),
+        })
+        .await?;
+    }
+
+    if let Some(field_id) = view_query.group_field_id {
+      database
+        .set_group_by_field(view_id, &field_id, vec![])
+        .await?;
+    }
+    database.get_database_view_setting(view_id).await
+  }
+
   /// Only expose this method for testing
   #[cfg(debug_assertions)]
   pub fn get_cloud_service(&self) -> &Arc<dyn DatabaseCloudService> {
@@ -52,6 +52,10 @@ pub enum DatabaseNotification {
   DidUpdateFieldSettings = 86,
   // Trigger when Calculation changed
   DidUpdateCalculation = 87,
+  // Trigger when an automation rule is executed
+  DidUpdateAutomationLog = 88,
+  // Trigger after each row of the AI column fill is processed
+  DidUpdateAIColumnFillProgress = 89,
 }
 
 impl std::convert::From<DatabaseNotification> for i32 {
@@ -84,6 +88,8 @@ impl std::convert::From<i32> for DatabaseNotification {
       84 => DatabaseNotification::DidMoveDatabaseViewToTrash,
       86 => DatabaseNotification::DidUpdateFieldSettings,
       87 => DatabaseNotification::DidUpdateCalculation,
+      88 => DatabaseNotification::DidUpdateAutomationLog,
+      89 => DatabaseNotification::DidUpdateAIColumnFillProgress,
       _ => DatabaseNotification::Unknown,
     }
   }
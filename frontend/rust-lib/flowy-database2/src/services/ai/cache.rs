@@ -0,0 +1,60 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+use flowy_database_pub::cloud::{SummaryRowContent, TranslateRowContent};
+
+/// Caches the AI responses by the hash of the row content, so the same content is only generated
+/// once. Generating with the local LLM is slow, and a column fill usually runs over many rows that
+/// haven't changed since the last run.
+pub struct AIResponseCache {
+  responses: moka::future::Cache<u64, String>,
+}
+
+impl Default for AIResponseCache {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl AIResponseCache {
+  pub fn new() -> Self {
+    let responses = moka::future::Cache::builder()
+      .max_capacity(1000)
+      .time_to_idle(Duration::from_secs(60 * 60))
+      .build();
+    Self { responses }
+  }
+
+  pub async fn get(&self, key: u64) -> Option<String> {
+    self.responses.get(&key).await
+  }
+
+  /// Empty responses are not cached, the service might return an empty string when it's not
+  /// available.
+  pub async fn insert(&self, key: u64, response: String) {
+    if !response.is_empty() {
+      self.responses.insert(key, response).await;
+    }
+  }
+
+  pub fn summary_key(content: &SummaryRowContent) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    "summary".hash(&mut hasher);
+    // The iteration order of the HashMap is random, sort the content by the field name.
+    content.iter().collect::<BTreeMap<_, _>>().hash(&mut hasher);
+    hasher.finish()
+  }
+
+  pub fn translate_key(content: &TranslateRowContent, language: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    "translate".hash(&mut hasher);
+    language.hash(&mut hasher);
+    for item in content {
+      item.title.hash(&mut hasher);
+      item.content.hash(&mut hasher);
+    }
+    hasher.finish()
+  }
+}
@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use dashmap::DashMap;
+use tokio_util::sync::CancellationToken;
+
+pub struct AIColumnFillTask {
+  pub field_id: String,
+  id: u64,
+  pub token: CancellationToken,
+}
+
+/// Keeps track of the running AI column fills. Each field has at most one running fill.
+#[derive(Default)]
+pub struct AIColumnFillTasks {
+  next_id: AtomicU64,
+  tasks: DashMap<String, (u64, CancellationToken)>,
+}
+
+impl AIColumnFillTasks {
+  /// Start a new fill of the field. The running fill of the same field is cancelled.
+  pub fn start(&self, field_id: &str) -> AIColumnFillTask {
+    let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+    let token = CancellationToken::new();
+    if let Some((_, (_, old_token))) = self.tasks.remove(field_id) {
+      old_token.cancel();
+    }
+    self.tasks.insert(field_id.to_string(), (id, token.clone()));
+    AIColumnFillTask {
+      field_id: field_id.to_string(),
+      id,
+      token,
+    }
+  }
+
+  /// Returns false if there is no running fill of the field.
+  pub fn cancel(&self, field_id: &str) -> bool {
+    match self.tasks.remove(field_id) {
+      None => false,
+      Some((_, (_, token))) => {
+        token.cancel();
+        true
+      },
+    }
+  }
+
+  pub fn finish(&self, task: &AIColumnFillTask) {
+    // The task might be replaced by a newer fill of the same field.
+    self
+      .tasks
+      .remove_if(&task.field_id, |_, (id, _)| *id == task.id);
+  }
+
+  pub fn is_running(&self, field_id: &str) -> bool {
+    self.tasks.contains_key(field_id)
+  }
+}
@@ -0,0 +1,599 @@
+use chrono::{Local, NaiveDateTime, TimeZone};
+use collab_database::database::{gen_database_id, gen_row_id, timestamp};
+use collab_database::entity::{CreateDatabaseParams, CreateViewParams};
+use collab_database::fields::date_type_option::{DateFormat, DateTypeOption, TimeFormat};
+use collab_database::fields::number_type_option::{NumberFormat, NumberTypeOption};
+use collab_database::fields::select_type_option::{SelectOption, SelectTypeOption};
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, CreateRowParams};
+use collab_database::views::{DatabaseLayout, LayoutSettings};
+use flowy_database_pub::cloud::DatabaseAIService;
+use flowy_error::{FlowyError, FlowyResult};
+use lib_infra::box_any::BoxAny;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tracing::trace;
+
+use crate::entities::FieldType;
+use crate::services::ai::view_query::{extract_json_object, parse_date_timestamp, value_to_string};
+use crate::services::cell::{
+  apply_cell_changeset, insert_checkbox_cell, insert_checklist_cell, insert_date_cell,
+  insert_select_option_cell, insert_text_cell, insert_url_cell,
+};
+use crate::services::field::checklist_type_option::ChecklistCellInsertChangeset;
+use crate::services::field::{
+  new_select_option_color, select_type_option_from_field, FieldBuilder,
+  SelectTypeOptionSharedAction,
+};
+use crate::services::field_settings::default_field_settings_for_fields;
+use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
+
+/// The key of the meta of the view that is created from a prompt. When the meta of a new
+/// database view contains the key, the database is generated by the AI instead of being created
+/// from the default template.
+pub const AI_DATABASE_PROMPT_KEY: &str = "ai_prompt";
+
+const MAX_GENERATED_FIELDS: usize = 20;
+const MAX_GENERATED_ROWS: usize = 20;
+
+const FIELD_TYPES: [(&str, FieldType); 8] = [
+  ("text", FieldType::RichText),
+  ("number", FieldType::Number),
+  ("date", FieldType::DateTime),
+  ("single_select", FieldType::SingleSelect),
+  ("multi_select", FieldType::MultiSelect),
+  ("checkbox", FieldType::Checkbox),
+  ("url", FieldType::URL),
+  ("checklist", FieldType::Checklist),
+];
+
+const NUMBER_FORMATS: [(&str, NumberFormat); 16] = [
+  ("number", NumberFormat::Num),
+  ("percent", NumberFormat::Percent),
+  ("usd", NumberFormat::USD),
+  ("cad", NumberFormat::CanadianDollar),
+  ("eur", NumberFormat::EUR),
+  ("gbp", NumberFormat::Pound),
+  ("jpy", NumberFormat::Yen),
+  ("rub", NumberFormat::Ruble),
+  ("inr", NumberFormat::Rupee),
+  ("krw", NumberFormat::Won),
+  ("cny", NumberFormat::Yuan),
+  ("brl", NumberFormat::Real),
+  ("try", NumberFormat::Lira),
+  ("idr", NumberFormat::Rupiah),
+  ("chf", NumberFormat::Franc),
+  ("hkd", NumberFormat::HongKongDollar),
+];
+
+const DATE_FORMATS: [(&str, DateFormat); 6] = [
+  ("local", DateFormat::Local),
+  ("us", DateFormat::US),
+  ("iso", DateFormat::ISO),
+  ("friendly", DateFormat::Friendly),
+  ("day_month_year", DateFormat::DayMonthYear),
+  ("friendly_full", DateFormat::FriendlyFull),
+];
+
+#[derive(Debug, Deserialize)]
+struct GeneratedDatabase {
+  fields: Vec<GeneratedField>,
+  #[serde(default)]
+  rows: Vec<Map<String, Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeneratedField {
+  name: String,
+  #[serde(rename = "type")]
+  field_type: String,
+  /// The options of the select fields
+  #[serde(default)]
+  options: Vec<String>,
+  /// The format of the number fields
+  #[serde(default)]
+  format: Option<String>,
+  /// The format of the date fields
+  #[serde(default)]
+  date_format: Option<String>,
+  /// Whether the date fields include the time, in the `YYYY-MM-DD HH:MM` format
+  #[serde(default)]
+  include_time: bool,
+}
+
+/// Ask the AI to design a database for the prompt and returns the params that create it. The
+/// response is validated before anything is created.
+pub async fn generate_database_params(
+  ai_service: &dyn DatabaseAIService,
+  workspace_id: &str,
+  view_id: &str,
+  name: &str,
+  layout: DatabaseLayout,
+  prompt: &str,
+) -> FlowyResult<CreateDatabaseParams> {
+  if prompt.trim().is_empty() {
+    return Err(FlowyError::invalid_data().with_context("The prompt is empty"));
+  }
+  let prompt = build_database_generation_prompt(prompt);
+  trace!("[AI]: database generation prompt: {}", prompt);
+  let response = ai_service
+    .complete_database_prompt(workspace_id, &prompt)
+    .await?;
+  trace!("[AI]: database generation response: {}", response);
+  parse_generated_database(&response, view_id, name, layout)
+}
+
+/// Returns the prompt that asks the AI for the JSON that is parsed by [parse_generated_database].
+pub fn build_database_generation_prompt(prompt: &str) -> String {
+  let names = |items: &[&str]| {
+    items
+      .iter()
+      .map(|item| format!("\"{}\"", item))
+      .collect::<Vec<_>>()
+      .join(", ")
+  };
+  format!(
+    r#"Design a database for: {prompt}
+
+Reply with a single JSON object and nothing else, in the form:
+{{"fields": [{{"name": "...", "type": "...", "options": ["..."], "format": "...", "date_format": "...", "include_time": false}}],
+"rows": [{{"<field name>": <value>}}]}}
+
+Rules:
+- The first field is a "text" field with the title of each row.
+- "type" is one of {types}.
+- "options" lists the options of the "single_select" and "multi_select" fields.
+- "format" of the "number" fields is one of {number_formats}.
+- "date_format" of the "date" fields is one of {date_formats}.
+- Add 3 to 5 example rows. The values of the rows are strings for the "text" and "url" fields, numbers for the "number" fields, "YYYY-MM-DD" dates (or "YYYY-MM-DD HH:MM" if "include_time" is true) for the "date" fields, an option name for the "single_select" fields, a list of option names for the "multi_select" fields, booleans for the "checkbox" fields and a list of task names for the "checklist" fields.
+- Use at most {max_fields} fields."#,
+    prompt = prompt.trim(),
+    types = names(&FIELD_TYPES.map(|(name, _)| name)),
+    number_formats = names(&NUMBER_FORMATS.map(|(name, _)| name)),
+    date_formats = names(&DATE_FORMATS.map(|(name, _)| name)),
+    max_fields = MAX_GENERATED_FIELDS,
+  )
+}
+
+/// Parse the response of the AI into the params of a new database. The field types, the formats
+/// and the values of the rows are validated, and any invalid reference is an error.
+///
+/// The first text field becomes the primary field, a `Name` field is added if there is none. A
+/// `Date` field is added to the calendars that don't have a date field.
+pub fn parse_generated_database(
+  response: &str,
+  view_id: &str,
+  name: &str,
+  layout: DatabaseLayout,
+) -> FlowyResult<CreateDatabaseParams> {
+  let json = extract_json_object(response).ok_or_else(|| {
+    FlowyError::invalid_data().with_context("The AI response doesn't contain a JSON object")
+  })?;
+  let generated = serde_json::from_str::<GeneratedDatabase>(json).map_err(|err| {
+    FlowyError::invalid_data().with_context(format!("Invalid AI response: {}", err))
+  })?;
+  if generated.fields.is_empty() {
+    return Err(FlowyError::invalid_data().with_context("The AI response has no fields"));
+  }
+  if generated.fields.len() > MAX_GENERATED_FIELDS {
+    return Err(FlowyError::invalid_data().with_context(format!(
+      "The AI response has more than {} fields",
+      MAX_GENERATED_FIELDS
+    )));
+  }
+
+  let mut fields = vec![];
+  let mut include_times = vec![];
+  for generated_field in &generated.fields {
+    let field_name = generated_field.name.trim();
+    if field_name.is_empty() {
+      return Err(FlowyError::invalid_data().with_context("The name of a field is empty"));
+    }
+    if fields
+      .iter()
+      .any(|field: &Field| field.name.eq_ignore_ascii_case(field_name))
+    {
+      return Err(
+        FlowyError::invalid_data().with_context(format!("Duplicate field \"{}\"", field_name)),
+      );
+    }
+    fields.push(build_field(generated_field)?);
+    include_times.push(generated_field.include_time);
+  }
+
+  match fields
+    .iter()
+    .position(|field| FieldType::from(field.field_type) == FieldType::RichText)
+  {
+    Some(index) => {
+      let field = fields.remove(index);
+      let include_time = include_times.remove(index);
+      fields.insert(0, field);
+      include_times.insert(0, include_time);
+    },
+    None => {
+      let field = FieldBuilder::from_field_type(FieldType::RichText)
+        .name(&unique_field_name(&fields, "Name"))
+        .build();
+      fields.insert(0, field);
+      include_times.insert(0, false);
+    },
+  }
+  fields[0].is_primary = true;
+
+  let mut layout_settings = LayoutSettings::default();
+  match layout {
+    DatabaseLayout::Grid => {},
+    DatabaseLayout::Board => {
+      layout_settings.insert(DatabaseLayout::Board, BoardLayoutSetting::new().into());
+    },
+    DatabaseLayout::Calendar => {
+      let date_field_id = match fields
+        .iter()
+        .find(|field| FieldType::from(field.field_type) == FieldType::DateTime)
+      {
+        Some(field) => field.id.clone(),
+        None => {
+          let field = FieldBuilder::from_field_type(FieldType::DateTime)
+            .name(&unique_field_name(&fields, "Date"))
+            .build();
+          let field_id = field.id.clone();
+          fields.push(field);
+          include_times.push(false);
+          field_id
+        },
+      };
+      layout_settings.insert(
+        DatabaseLayout::Calendar,
+        CalendarLayoutSetting::new(date_field_id).into(),
+      );
+    },
+  }
+
+  let database_id = gen_database_id();
+  let rows = generated
+    .rows
+    .iter()
+    .take(MAX_GENERATED_ROWS)
+    .map(|values| {
+      let mut row = CreateRowParams::new(gen_row_id(), database_id.clone());
+      for (key, value) in values {
+        if value.is_null() {
+          continue;
+        }
+        let index = fields
+          .iter()
+          .position(|field| field.name.eq_ignore_ascii_case(key.trim()))
+          .ok_or_else(|| {
+            FlowyError::record_not_found()
+              .with_context(format!("The field \"{}\" of the row doesn't exist", key))
+          })?;
+        let field = &fields[index];
+        let cell = build_cell(field, value, include_times[index])?;
+        row.cells.insert(field.id.clone(), cell);
+      }
+      Ok(row)
+    })
+    .collect::<FlowyResult<Vec<_>>>()?;
+
+  let field_settings = default_field_settings_for_fields(&fields, layout);
+  let timestamp = timestamp();
+  Ok(CreateDatabaseParams {
+    database_id: database_id.clone(),
+    views: vec![CreateViewParams {
+      database_id,
+      view_id: view_id.to_string(),
+      name: name.to_string(),
+      layout,
+      layout_settings,
+      filters: vec![],
+      group_settings: vec![],
+      sorts: vec![],
+      field_settings,
+      created_at: timestamp,
+      modified_at: timestamp,
+      ..Default::default()
+    }],
+    rows,
+    fields,
+  })
+}
+
+fn build_field(generated: &GeneratedField) -> FlowyResult<Field> {
+  let field_type = find_by_name(&FIELD_TYPES, &generated.field_type).ok_or_else(|| {
+    FlowyError::invalid_data().with_context(format!(
+      "The type \"{}\" of the field \"{}\" is not supported",
+      generated.field_type, generated.name
+    ))
+  })?;
+  let builder = match field_type {
+    FieldType::Number => {
+      let format = match generated.format.as_deref() {
+        None => NumberFormat::Num,
+        Some(format) => find_by_name(&NUMBER_FORMATS, format).ok_or_else(|| {
+          FlowyError::invalid_data()
+            .with_context(format!("The number format \"{}\" is not supported", format))
+        })?,
+      };
+      let type_option = NumberTypeOption {
+        format,
+        ..Default::default()
+      };
+      FieldBuilder::new(field_type, type_option)
+    },
+    FieldType::DateTime => {
+      let date_format = match generated.date_format.as_deref() {
+        None => DateFormat::Friendly,
+        Some(format) => find_by_name(&DATE_FORMATS, format).ok_or_else(|| {
+          FlowyError::invalid_data()
+            .with_context(format!("The date format \"{}\" is not supported", format))
+        })?,
+      };
+      let type_option = DateTypeOption {
+        date_format,
+        time_format: TimeFormat::TwentyFourHour,
+        ..Default::default()
+      };
+      FieldBuilder::new(field_type, type_option)
+    },
+    FieldType::SingleSelect | FieldType::MultiSelect => {
+      let mut options: Vec<SelectOption> = vec![];
+      for name in &generated.options {
+        let name = name.trim();
+        if name.is_empty()
+          || options
+            .iter()
+            .any(|option| option.name.eq_ignore_ascii_case(name))
+        {
+          continue;
+        }
+        let color = new_select_option_color(&options);
+        options.push(SelectOption::with_color(name, color));
+      }
+      let type_option = SelectTypeOption {
+        options,
+        disable_color: false,
+      };
+      FieldBuilder::new(field_type, type_option)
+    },
+    field_type => FieldBuilder::from_field_type(field_type),
+  };
+  Ok(builder.name(generated.name.trim()).build())
+}
+
+fn build_cell(field: &Field, value: &Value, include_time: bool) -> FlowyResult<Cell> {
+  let field_type = FieldType::from(field.field_type);
+  let invalid_value = || {
+    FlowyError::invalid_data().with_context(format!(
+      "Invalid value {} of the field \"{}\"",
+      value, field.name
+    ))
+  };
+  let cell = match field_type {
+    FieldType::RichText => insert_text_cell(value_to_string(value), field),
+    FieldType::URL => insert_url_cell(value_to_string(value), field),
+    FieldType::Number => {
+      let number = match value {
+        Value::Number(number) => number.to_string(),
+        Value::String(s) if s.trim().parse::<f64>().is_ok() => s.trim().to_string(),
+        _ => return Err(invalid_value()),
+      };
+      apply_cell_changeset(BoxAny::new(number), None, field, None)?
+    },
+    FieldType::Checkbox => {
+      let is_checked = match value {
+        Value::Bool(is_checked) => *is_checked,
+        Value::String(s) => matches!(s.trim().to_lowercase().as_str(), "true" | "yes"),
+        _ => return Err(invalid_value()),
+      };
+      insert_checkbox_cell(is_checked, field)
+    },
+    FieldType::DateTime => {
+      let date = value.as_str().ok_or_else(invalid_value)?;
+      let timestamp = if include_time {
+        parse_date_time_timestamp(date)?
+      } else {
+        parse_date_timestamp(date)?
+      };
+      insert_date_cell(timestamp, None, Some(include_time), field)
+    },
+    FieldType::SingleSelect | FieldType::MultiSelect => {
+      let names = match value {
+        Value::String(name) => vec![name.as_str()],
+        Value::Array(names) => names
+          .iter()
+          .map(|name| name.as_str().ok_or_else(invalid_value))
+          .collect::<FlowyResult<Vec<_>>>()?,
+        _ => return Err(invalid_value()),
+      };
+      if field_type == FieldType::SingleSelect && names.len() > 1 {
+        return Err(invalid_value());
+      }
+      let options = select_type_option_from_field(field)
+        .map(|type_option| type_option.options().clone())
+        .unwrap_or_default();
+      let option_ids = names
+        .into_iter()
+        .map(|name| {
+          options
+            .iter()
+            .find(|option| option.name.eq_ignore_ascii_case(name.trim()))
+            .map(|option| option.id.clone())
+            .ok_or_else(|| {
+              FlowyError::record_not_found().with_context(format!(
+                "The option \"{}\" of the field \"{}\" doesn't exist",
+                name, field.name
+              ))
+            })
+        })
+        .collect::<FlowyResult<Vec<_>>>()?;
+      insert_select_option_cell(option_ids, field)
+    },
+    FieldType::Checklist => {
+      let tasks = value
+        .as_array()
+        .ok_or_else(invalid_value)?
+        .iter()
+        .map(|task| match task {
+          Value::String(name) => Ok(ChecklistCellInsertChangeset {
+            name: name.clone(),
+            is_complete: false,
+            index: None,
+          }),
+          _ => Err(invalid_value()),
+        })
+        .collect::<FlowyResult<Vec<_>>>()?;
+      insert_checklist_cell(tasks, field)
+    },
+    _ => return Err(invalid_value()),
+  };
+  Ok(cell)
+}
+
+fn find_by_name<T: Clone>(items: &[(&str, T)], name: &str) -> Option<T> {
+  let name = name.trim();
+  items
+    .iter()
+    .find(|(item, _)| item.eq_ignore_ascii_case(name))
+    .map(|(_, value)| value.clone())
+}
+
+fn unique_field_name(fields: &[Field], name: &str) -> String {
+  let mut unique_name = name.to_string();
+  let mut index = 1;
+  while fields
+    .iter()
+    .any(|field| field.name.eq_ignore_ascii_case(&unique_name))
+  {
+    index += 1;
+    unique_name = format!("{} {}", name, index);
+  }
+  unique_name
+}
+
+/// Returns the timestamp in seconds of the `YYYY-MM-DD HH:MM` date time in the local time zone.
+fn parse_date_time_timestamp(date_time: &str) -> FlowyResult<i64> {
+  let date_time =
+    NaiveDateTime::parse_from_str(date_time.trim(), "%Y-%m-%d %H:%M").map_err(|_| {
+      FlowyError::invalid_data().with_context(format!(
+        "\"{}\" is not a YYYY-MM-DD HH:MM date time",
+        date_time
+      ))
+    })?;
+  Local
+    .from_local_datetime(&date_time)
+    .earliest()
+    .map(|date_time| date_time.timestamp())
+    .ok_or_else(|| FlowyError::invalid_data().with_context("Invalid date time"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RESPONSE: &str = r#"```json
+{"fields": [
+  {"name": "Status", "type": "single_select", "options": ["Idea", "Recorded", "Published"]},
+  {"name": "Episode", "type": "text"},
+  {"name": "Budget", "type": "number", "format": "usd"},
+  {"name": "Release", "type": "date", "date_format": "iso"},
+  {"name": "Topics", "type": "multi_select", "options": ["Tech", "Design", "tech"]},
+  {"name": "Guest confirmed", "type": "checkbox"},
+  {"name": "Prep", "type": "checklist"}
+],
+"rows": [
+  {"Episode": "Pilot", "Status": "idea", "Budget": 120.5, "Release": "2024-05-01",
+   "Topics": ["Tech", "Design"], "Guest confirmed": true, "Prep": ["Book studio"]},
+  {"Episode": "Interview", "Status": "Recorded", "Budget": "80", "Topics": []}
+]}
+```"#;
+
+  #[test]
+  fn parse_generated_database_test() {
+    let params = parse_generated_database(RESPONSE, "v1", "Podcast", DatabaseLayout::Grid).unwrap();
+    assert_eq!(params.fields.len(), 7);
+    assert_eq!(params.fields[0].name, "Episode");
+    assert!(params.fields[0].is_primary);
+    assert!(params.fields.iter().skip(1).all(|field| !field.is_primary));
+
+    let budget = params.fields.iter().find(|f| f.name == "Budget").unwrap();
+    let type_option = budget
+      .get_type_option::<NumberTypeOption>(FieldType::Number)
+      .unwrap();
+    assert!(matches!(type_option.format, NumberFormat::USD));
+
+    let topics = params.fields.iter().find(|f| f.name == "Topics").unwrap();
+    let type_option = select_type_option_from_field(topics).unwrap();
+    assert_eq!(type_option.options().len(), 2);
+
+    assert_eq!(params.rows.len(), 2);
+    assert_eq!(params.rows[0].cells.len(), 7);
+    assert_eq!(params.rows[1].cells.len(), 4);
+    assert_eq!(params.views[0].view_id, "v1");
+    assert_eq!(params.views[0].layout, DatabaseLayout::Grid);
+  }
+
+  #[test]
+  fn add_missing_fields_test() {
+    let response = r#"{"fields": [{"name": "Done", "type": "checkbox"}], "rows": []}"#;
+    let params =
+      parse_generated_database(response, "v1", "Tasks", DatabaseLayout::Calendar).unwrap();
+    let names = params
+      .fields
+      .iter()
+      .map(|field| field.name.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(names, vec!["Name", "Done", "Date"]);
+    assert!(params.fields[0].is_primary);
+    assert!(params.views[0]
+      .layout_settings
+      .get(&DatabaseLayout::Calendar)
+      .is_some());
+  }
+
+  #[test]
+  fn invalid_response_test() {
+    let parse = |response: &str| {
+      parse_generated_database(response, "v1", "Podcast", DatabaseLayout::Grid).unwrap_err()
+    };
+    assert!(parse("no json").msg.contains("JSON"));
+    assert!(parse(r#"{"fields": []}"#).msg.contains("no fields"));
+    assert!(parse(r#"{"fields": [{"name": "A", "type": "formula"}]}"#)
+      .msg
+      .contains("formula"));
+    assert!(
+      parse(r#"{"fields": [{"name": "A", "type": "number", "format": "bitcoin"}]}"#)
+        .msg
+        .contains("bitcoin")
+    );
+    assert!(
+      parse(r#"{"fields": [{"name": "A", "type": "text"}, {"name": "a", "type": "text"}]}"#)
+        .msg
+        .contains("Duplicate")
+    );
+    assert!(parse(
+      r#"{"fields": [{"name": "A", "type": "single_select", "options": ["X"]}], "rows": [{"A": "Y"}]}"#
+    )
+    .msg
+    .contains("\"Y\""));
+    assert!(
+      parse(r#"{"fields": [{"name": "A", "type": "text"}], "rows": [{"B": "x"}]}"#)
+        .msg
+        .contains("\"B\"")
+    );
+    assert!(
+      parse(r#"{"fields": [{"name": "A", "type": "date"}], "rows": [{"A": "May 1st"}]}"#)
+        .msg
+        .contains("May 1st")
+    );
+  }
+
+  #[test]
+  fn build_database_generation_prompt_test() {
+    let prompt = build_database_generation_prompt(" content calendar for a podcast ");
+    assert!(prompt.contains("Design a database for: content calendar for a podcast\n"));
+    assert!(prompt.contains("\"single_select\""));
+    assert!(prompt.contains("\"usd\""));
+    assert!(prompt.contains("\"friendly_full\""));
+  }
+}
@@ -0,0 +1,9 @@
+mod cache;
+mod column_fill;
+mod database_generation;
+mod view_query;
+
+pub use cache::*;
+pub use column_fill::*;
+pub use database_generation::*;
+pub use view_query::*;
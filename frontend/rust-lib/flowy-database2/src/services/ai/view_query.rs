@@ -0,0 +1,483 @@
+use chrono::{Local, NaiveDate, TimeZone};
+use collab_database::fields::Field;
+use flowy_error::{FlowyError, FlowyResult};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::entities::{
+  CheckboxFilterConditionPB, ChecklistFilterConditionPB, DateFilterConditionPB, DateFilterContent,
+  FieldType, NumberFilterConditionPB, SelectOptionFilterConditionPB, TextFilterConditionPB,
+};
+use crate::services::field::{select_type_option_from_field, SelectTypeOptionSharedAction};
+use crate::services::filter::FilterInner;
+use crate::services::sort::SortCondition;
+
+/// The key of the meta of a new linked view. When the meta of the view contains the key, the
+/// filters, sorts and group of the view are generated from the query once the view is created.
+pub const AI_VIEW_QUERY_KEY: &str = "ai_view_query";
+
+/// The filters, sorts and group that are generated from a natural-language query of a view.
+#[derive(Debug)]
+pub struct DatabaseViewQuery {
+  /// The filters are combined with AND
+  pub filters: Vec<FilterInner>,
+  /// The field id and the condition of each sort, the first sort has the highest priority
+  pub sorts: Vec<(String, SortCondition)>,
+  pub group_field_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewQueryResponse {
+  #[serde(default)]
+  filters: Vec<FilterResponse>,
+  #[serde(default)]
+  sorts: Vec<SortResponse>,
+  #[serde(default)]
+  group_by: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FilterResponse {
+  field: String,
+  condition: String,
+  #[serde(default)]
+  value: Option<Value>,
+  /// The end date of the `between` condition
+  #[serde(default)]
+  end: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SortResponse {
+  field: String,
+  #[serde(default)]
+  direction: Option<String>,
+}
+
+/// Returns the prompt that asks the AI to translate the query into the JSON that is parsed by
+/// [parse_view_query]. The prompt describes the fields of the view and the select options.
+pub fn build_view_query_prompt(fields: &[Field], query: &str, today: NaiveDate) -> String {
+  let schema = fields
+    .iter()
+    .filter_map(|field| {
+      let field_type = FieldType::from(field.field_type);
+      let conditions = conditions_of(&field_type)?;
+      let mut line = format!(
+        "- \"{}\" ({:?}), conditions: {}",
+        field.name,
+        field_type,
+        conditions
+          .iter()
+          .map(|(name, _)| *name)
+          .collect::<Vec<_>>()
+          .join(", ")
+      );
+      if field_type.is_select_option() {
+        let options = select_type_option_from_field(field)
+          .map(|type_option| {
+            type_option
+              .options()
+              .iter()
+              .map(|option| format!("\"{}\"", option.name))
+              .collect::<Vec<_>>()
+          })
+          .unwrap_or_default();
+        line.push_str(&format!(", options: [{}]", options.join(", ")));
+      }
+      if field_type.can_be_group() {
+        line.push_str(", groupable");
+      }
+      Some(line)
+    })
+    .collect::<Vec<_>>()
+    .join("\n");
+
+  format!(
+    r#"You configure the view of a database. The fields of the database are:
+{schema}
+
+Today is {today} ({weekday}). Translate the request into a JSON object without any explanation:
+{{"filters": [{{"field": "<field name>", "condition": "<condition>", "value": <value>, "end": "<YYYY-MM-DD>"}}], "sorts": [{{"field": "<field name>", "direction": "asc" | "desc"}}], "group_by": "<field name>" | null}}
+The value is a string for text fields, a number for number fields, a list of option names for select fields, and a date in the YYYY-MM-DD format for date fields. The end is only used by the between condition of date fields. Omit the value for the conditions that don't need one.
+
+Request: {query}"#,
+    schema = schema,
+    today = today.format("%Y-%m-%d"),
+    weekday = today.format("%A"),
+    query = query.trim(),
+  )
+}
+
+/// Parse the response of the AI into a [DatabaseViewQuery]. Any reference to a field, a condition
+/// or a select option that doesn't exist is an error, instead of being dropped.
+pub fn parse_view_query(response: &str, fields: &[Field]) -> FlowyResult<DatabaseViewQuery> {
+  let json = extract_json_object(response).ok_or_else(|| {
+    FlowyError::invalid_data().with_context("The AI response doesn't contain a JSON object")
+  })?;
+  let response = serde_json::from_str::<ViewQueryResponse>(json).map_err(|err| {
+    FlowyError::invalid_data().with_context(format!("Invalid AI response: {}", err))
+  })?;
+
+  let filters = response
+    .filters
+    .into_iter()
+    .map(|filter| parse_filter(filter, fields))
+    .collect::<FlowyResult<Vec<_>>>()?;
+
+  let sorts = response
+    .sorts
+    .into_iter()
+    .map(|sort| {
+      let field = find_field(fields, &sort.field)?;
+      let condition = match sort.direction.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("asc") | Some("ascending") => SortCondition::Ascending,
+        Some("desc") | Some("descending") => SortCondition::Descending,
+        Some(direction) => {
+          return Err(
+            FlowyError::invalid_data()
+              .with_context(format!("Invalid sort direction: {}", direction)),
+          )
+        },
+      };
+      Ok((field.id.clone(), condition))
+    })
+    .collect::<FlowyResult<Vec<_>>>()?;
+
+  let group_field_id = match response.group_by.filter(|name| !name.trim().is_empty()) {
+    None => None,
+    Some(name) => {
+      let field = find_field(fields, &name)?;
+      if !FieldType::from(field.field_type).can_be_group() {
+        return Err(
+          FlowyError::invalid_data()
+            .with_context(format!("The field \"{}\" can't be grouped", field.name)),
+        );
+      }
+      Some(field.id.clone())
+    },
+  };
+
+  Ok(DatabaseViewQuery {
+    filters,
+    sorts,
+    group_field_id,
+  })
+}
+
+/// The models might wrap the JSON in a code block or add some text around it.
+pub(super) fn extract_json_object(response: &str) -> Option<&str> {
+  let start = response.find('{')?;
+  let end = response.rfind('}')?;
+  (start < end).then(|| &response[start..=end])
+}
+
+/// Find the field by its name, ignoring the case, or by its id.
+fn find_field<'a>(fields: &'a [Field], name: &str) -> FlowyResult<&'a Field> {
+  let name = name.trim();
+  fields
+    .iter()
+    .find(|field| field.name.trim().eq_ignore_ascii_case(name))
+    .or_else(|| fields.iter().find(|field| field.id == name))
+    .ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!("The field \"{}\" doesn't exist", name))
+    })
+}
+
+/// Returns the conditions that can be used in the query for each field type, or None if the
+/// field type can't be filtered by the query.
+fn conditions_of(field_type: &FieldType) -> Option<Vec<(&'static str, u8)>> {
+  let conditions = match field_type {
+    FieldType::RichText | FieldType::URL | FieldType::Summary | FieldType::Translate => vec![
+      ("is", TextFilterConditionPB::TextIs as u8),
+      ("is_not", TextFilterConditionPB::TextIsNot as u8),
+      ("contains", TextFilterConditionPB::TextContains as u8),
+      (
+        "does_not_contain",
+        TextFilterConditionPB::TextDoesNotContain as u8,
+      ),
+      ("starts_with", TextFilterConditionPB::TextStartsWith as u8),
+      ("ends_with", TextFilterConditionPB::TextEndsWith as u8),
+      ("is_empty", TextFilterConditionPB::TextIsEmpty as u8),
+      ("is_not_empty", TextFilterConditionPB::TextIsNotEmpty as u8),
+    ],
+    FieldType::Number => vec![
+      ("equal", NumberFilterConditionPB::Equal as u8),
+      ("not_equal", NumberFilterConditionPB::NotEqual as u8),
+      ("greater_than", NumberFilterConditionPB::GreaterThan as u8),
+      ("less_than", NumberFilterConditionPB::LessThan as u8),
+      (
+        "greater_than_or_equal",
+        NumberFilterConditionPB::GreaterThanOrEqualTo as u8,
+      ),
+      (
+        "less_than_or_equal",
+        NumberFilterConditionPB::LessThanOrEqualTo as u8,
+      ),
+      ("is_empty", NumberFilterConditionPB::NumberIsEmpty as u8),
+      (
+        "is_not_empty",
+        NumberFilterConditionPB::NumberIsNotEmpty as u8,
+      ),
+    ],
+    FieldType::DateTime | FieldType::CreatedTime | FieldType::LastEditedTime => vec![
+      ("on", DateFilterConditionPB::DateStartsOn as u8),
+      ("before", DateFilterConditionPB::DateStartsBefore as u8),
+      ("after", DateFilterConditionPB::DateStartsAfter as u8),
+      (
+        "on_or_before",
+        DateFilterConditionPB::DateStartsOnOrBefore as u8,
+      ),
+      (
+        "on_or_after",
+        DateFilterConditionPB::DateStartsOnOrAfter as u8,
+      ),
+      ("between", DateFilterConditionPB::DateStartsBetween as u8),
+      ("is_empty", DateFilterConditionPB::DateStartIsEmpty as u8),
+      (
+        "is_not_empty",
+        DateFilterConditionPB::DateStartIsNotEmpty as u8,
+      ),
+    ],
+    FieldType::SingleSelect | FieldType::MultiSelect => vec![
+      ("is", SelectOptionFilterConditionPB::OptionIs as u8),
+      ("is_not", SelectOptionFilterConditionPB::OptionIsNot as u8),
+      (
+        "contains",
+        SelectOptionFilterConditionPB::OptionContains as u8,
+      ),
+      (
+        "does_not_contain",
+        SelectOptionFilterConditionPB::OptionDoesNotContain as u8,
+      ),
+      (
+        "is_empty",
+        SelectOptionFilterConditionPB::OptionIsEmpty as u8,
+      ),
+      (
+        "is_not_empty",
+        SelectOptionFilterConditionPB::OptionIsNotEmpty as u8,
+      ),
+    ],
+    FieldType::Checkbox => vec![
+      ("is_checked", CheckboxFilterConditionPB::IsChecked as u8),
+      ("is_unchecked", CheckboxFilterConditionPB::IsUnChecked as u8),
+    ],
+    FieldType::Checklist => vec![
+      ("is_complete", ChecklistFilterConditionPB::IsComplete as u8),
+      (
+        "is_incomplete",
+        ChecklistFilterConditionPB::IsIncomplete as u8,
+      ),
+    ],
+    FieldType::Relation | FieldType::Time | FieldType::Media => return None,
+  };
+  Some(conditions)
+}
+
+fn parse_filter(filter: FilterResponse, fields: &[Field]) -> FlowyResult<FilterInner> {
+  let field = find_field(fields, &filter.field)?;
+  let field_type = FieldType::from(field.field_type);
+  let conditions = conditions_of(&field_type).ok_or_else(|| {
+    FlowyError::invalid_data().with_context(format!(
+      "The field \"{}\" can't be filtered by the query",
+      field.name
+    ))
+  })?;
+  let condition_name = filter.condition.trim().to_lowercase();
+  let condition = conditions
+    .iter()
+    .find(|(name, _)| *name == condition_name)
+    .map(|(_, condition)| *condition)
+    .ok_or_else(|| {
+      FlowyError::invalid_data().with_context(format!(
+        "The condition \"{}\" is not supported by the field \"{}\"",
+        filter.condition, field.name
+      ))
+    })?;
+  let needs_value = !matches!(
+    condition_name.as_str(),
+    "is_empty" | "is_not_empty" | "is_checked" | "is_unchecked" | "is_complete" | "is_incomplete"
+  );
+
+  let content = if !needs_value {
+    "".to_string()
+  } else {
+    let value = filter.value.as_ref().ok_or_else(|| {
+      FlowyError::invalid_data().with_context(format!(
+        "The condition \"{}\" of the field \"{}\" requires a value",
+        filter.condition, field.name
+      ))
+    })?;
+    match field_type {
+      FieldType::DateTime | FieldType::CreatedTime | FieldType::LastEditedTime => {
+        let date = parse_date_timestamp(value_to_string(value).as_str())?;
+        let content = if condition_name == "between" {
+          let end = filter.end.as_deref().ok_or_else(|| {
+            FlowyError::invalid_data()
+              .with_context("The between condition requires an end date".to_string())
+          })?;
+          DateFilterContent {
+            start: Some(date),
+            end: Some(parse_date_timestamp(end)?),
+            timestamp: None,
+          }
+        } else {
+          DateFilterContent {
+            start: None,
+            end: None,
+            timestamp: Some(date),
+          }
+        };
+        content.to_string()
+      },
+      FieldType::SingleSelect | FieldType::MultiSelect => {
+        let options = select_type_option_from_field(field)?.options().clone();
+        let names = match value {
+          Value::Array(values) => values.iter().map(value_to_string).collect::<Vec<_>>(),
+          value => vec![value_to_string(value)],
+        };
+        names
+          .iter()
+          .map(|name| {
+            options
+              .iter()
+              .find(|option| option.name.trim().eq_ignore_ascii_case(name.trim()))
+              .map(|option| option.id.clone())
+              .ok_or_else(|| {
+                FlowyError::record_not_found().with_context(format!(
+                  "The option \"{}\" doesn't exist in the field \"{}\"",
+                  name, field.name
+                ))
+              })
+          })
+          .collect::<FlowyResult<Vec<_>>>()?
+          .join(",")
+      },
+      FieldType::Number => {
+        let number = value_to_string(value);
+        if number.parse::<f64>().is_err() {
+          return Err(
+            FlowyError::invalid_data().with_context(format!("\"{}\" is not a number", number)),
+          );
+        }
+        number
+      },
+      _ => value_to_string(value),
+    }
+  };
+
+  Ok(FilterInner::new_data(
+    field.id.clone(),
+    field_type,
+    condition as i64,
+    content,
+  ))
+}
+
+pub(super) fn value_to_string(value: &Value) -> String {
+  match value {
+    Value::String(s) => s.clone(),
+    value => value.to_string(),
+  }
+}
+
+/// Returns the timestamp in seconds of the noon of the date in the local time zone. The date
+/// filters compare the dates in the local time zone.
+pub(super) fn parse_date_timestamp(date: &str) -> FlowyResult<i64> {
+  let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| {
+    FlowyError::invalid_data().with_context(format!("\"{}\" is not a YYYY-MM-DD date", date))
+  })?;
+  date
+    .and_hms_opt(12, 0, 0)
+    .and_then(|date_time| Local.from_local_datetime(&date_time).earliest())
+    .map(|date_time| date_time.timestamp())
+    .ok_or_else(|| FlowyError::invalid_data().with_context("Invalid date"))
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::select_type_option::{SelectOption, SelectTypeOption};
+
+  use super::*;
+  use crate::services::field::FieldBuilder;
+
+  fn fields() -> Vec<Field> {
+    let options = vec![SelectOption::new("Todo"), SelectOption::new("Done")];
+    vec![
+      FieldBuilder::from_field_type(FieldType::RichText)
+        .name("Name")
+        .build(),
+      FieldBuilder::new(
+        FieldType::SingleSelect,
+        SelectTypeOption {
+          options,
+          disable_color: false,
+        },
+      )
+      .name("Status")
+      .build(),
+      FieldBuilder::from_field_type(FieldType::DateTime)
+        .name("Due")
+        .build(),
+      FieldBuilder::from_field_type(FieldType::Number)
+        .name("Priority")
+        .build(),
+    ]
+  }
+
+  #[test]
+  fn parse_view_query_test() {
+    let fields = fields();
+    let response = r#"Here is the query:
+```json
+{"filters": [
+  {"field": "status", "condition": "is_not", "value": ["Done"]},
+  {"field": "Due", "condition": "between", "value": "2024-11-11", "end": "2024-11-17"}
+],
+"sorts": [{"field": "Priority", "direction": "desc"}],
+"group_by": "Status"}
+```"#;
+    let query = parse_view_query(response, &fields).unwrap();
+    assert_eq!(query.filters.len(), 2);
+    match &query.filters[0] {
+      FilterInner::Data { field_id, .. } => assert_eq!(field_id, &fields[1].id),
+      _ => panic!("Expected a data filter"),
+    }
+    assert_eq!(query.sorts.len(), 1);
+    assert_eq!(query.sorts[0].0, fields[3].id);
+    assert!(matches!(query.sorts[0].1, SortCondition::Descending));
+    assert_eq!(query.group_field_id, Some(fields[1].id.clone()));
+  }
+
+  #[test]
+  fn invalid_references_test() {
+    let fields = fields();
+    let unknown_field = r#"{"filters": [{"field": "Assignee", "condition": "is", "value": "me"}]}"#;
+    let err = parse_view_query(unknown_field, &fields).unwrap_err();
+    assert!(err.msg.contains("Assignee"));
+
+    let unknown_option =
+      r#"{"filters": [{"field": "Status", "condition": "is", "value": ["Doing"]}]}"#;
+    let err = parse_view_query(unknown_option, &fields).unwrap_err();
+    assert!(err.msg.contains("Doing"));
+
+    let unknown_condition =
+      r#"{"filters": [{"field": "Priority", "condition": "contains", "value": 1}]}"#;
+    assert!(parse_view_query(unknown_condition, &fields).is_err());
+
+    let not_groupable = r#"{"group_by": "Priority"}"#;
+    assert!(parse_view_query(not_groupable, &fields).is_err());
+
+    assert!(parse_view_query("I don't know", &fields).is_err());
+  }
+
+  #[test]
+  fn build_view_query_prompt_test() {
+    let today = NaiveDate::from_ymd_opt(2024, 11, 13).unwrap();
+    let prompt = build_view_query_prompt(&fields(), "open tasks due this week", today);
+    assert!(prompt.contains(r#"- "Status" (SingleSelect)"#));
+    assert!(prompt.contains(r#"options: ["Todo", "Done"], groupable"#));
+    assert!(prompt.contains("Today is 2024-11-13 (Wednesday)"));
+    assert!(prompt.ends_with("Request: open tasks due this week"));
+  }
+}
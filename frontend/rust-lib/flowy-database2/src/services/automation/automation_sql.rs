@@ -0,0 +1,214 @@
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::schema::{database_automation_log_table, database_automation_rule_table};
+use flowy_sqlite::{
+  diesel, insert_into, query_dsl::*, AsChangeset, DBConnection, ExpressionMethods, Identifiable,
+  Insertable, Queryable,
+};
+use tracing::error;
+
+use crate::services::automation::{
+  gen_automation_log_id, AutomationAction, AutomationCondition, AutomationLog, AutomationRule,
+  AutomationTrigger,
+};
+
+/// The maximum number of execution logs that are kept for each database.
+const MAX_LOGS_PER_DATABASE: i64 = 500;
+
+#[derive(Queryable, Insertable, AsChangeset, Identifiable, Debug, Clone)]
+#[diesel(table_name = database_automation_rule_table)]
+#[diesel(primary_key(rule_id))]
+pub struct AutomationRuleTable {
+  pub rule_id: String,
+  pub database_id: String,
+  pub name: String,
+  pub enabled: bool,
+  pub trigger_data: String,
+  pub condition_data: Option<String>,
+  pub actions_data: String,
+  pub created_at: i64,
+  pub updated_at: i64,
+}
+
+impl TryFrom<&AutomationRule> for AutomationRuleTable {
+  type Error = FlowyError;
+
+  fn try_from(rule: &AutomationRule) -> Result<Self, Self::Error> {
+    let condition_data = match &rule.condition {
+      None => None,
+      Some(condition) => Some(serde_json::to_string(condition)?),
+    };
+    Ok(Self {
+      rule_id: rule.id.clone(),
+      database_id: rule.database_id.clone(),
+      name: rule.name.clone(),
+      enabled: rule.enabled,
+      trigger_data: serde_json::to_string(&rule.trigger)?,
+      condition_data,
+      actions_data: serde_json::to_string(&rule.actions)?,
+      created_at: rule.created_at,
+      updated_at: rule.updated_at,
+    })
+  }
+}
+
+impl TryFrom<AutomationRuleTable> for AutomationRule {
+  type Error = FlowyError;
+
+  fn try_from(table: AutomationRuleTable) -> Result<Self, Self::Error> {
+    let trigger = serde_json::from_str::<AutomationTrigger>(&table.trigger_data)?;
+    let condition = match table.condition_data {
+      None => None,
+      Some(data) => Some(serde_json::from_str::<AutomationCondition>(&data)?),
+    };
+    let actions = serde_json::from_str::<Vec<AutomationAction>>(&table.actions_data)?;
+    Ok(Self {
+      id: table.rule_id,
+      database_id: table.database_id,
+      name: table.name,
+      enabled: table.enabled,
+      trigger,
+      condition,
+      actions,
+      created_at: table.created_at,
+      updated_at: table.updated_at,
+    })
+  }
+}
+
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone)]
+#[diesel(table_name = database_automation_log_table)]
+#[diesel(primary_key(log_id))]
+pub struct AutomationLogTable {
+  pub log_id: String,
+  pub rule_id: String,
+  pub database_id: String,
+  pub row_id: String,
+  pub status: i32,
+  pub message: String,
+  pub executed_at: i64,
+}
+
+impl From<AutomationLogTable> for AutomationLog {
+  fn from(table: AutomationLogTable) -> Self {
+    Self {
+      id: table.log_id,
+      rule_id: table.rule_id,
+      database_id: table.database_id,
+      row_id: table.row_id,
+      status: table.status.into(),
+      message: table.message,
+      executed_at: table.executed_at,
+    }
+  }
+}
+
+pub fn upsert_automation_rule(mut conn: DBConnection, rule: &AutomationRule) -> FlowyResult<()> {
+  let table = AutomationRuleTable::try_from(rule)?;
+  insert_into(database_automation_rule_table::table)
+    .values(&table)
+    .on_conflict(database_automation_rule_table::rule_id)
+    .do_update()
+    .set(&table)
+    .execute(&mut *conn)?;
+  Ok(())
+}
+
+pub fn select_automation_rules(
+  mut conn: DBConnection,
+  database_id: &str,
+) -> FlowyResult<Vec<AutomationRule>> {
+  let rows = database_automation_rule_table::dsl::database_automation_rule_table
+    .filter(database_automation_rule_table::database_id.eq(database_id))
+    .order(database_automation_rule_table::created_at.asc())
+    .load::<AutomationRuleTable>(&mut *conn)?;
+
+  let rules = rows
+    .into_iter()
+    .flat_map(|row| {
+      let rule_id = row.rule_id.clone();
+      match AutomationRule::try_from(row) {
+        Ok(rule) => Some(rule),
+        Err(err) => {
+          error!(
+            "[Automation]: failed to parse rule:{}, error:{}",
+            rule_id, err
+          );
+          None
+        },
+      }
+    })
+    .collect();
+  Ok(rules)
+}
+
+pub fn delete_automation_rule(mut conn: DBConnection, rule_id: &str) -> FlowyResult<()> {
+  conn.immediate_transaction(|conn| {
+    diesel::delete(
+      database_automation_rule_table::dsl::database_automation_rule_table
+        .filter(database_automation_rule_table::rule_id.eq(rule_id)),
+    )
+    .execute(conn)?;
+    diesel::delete(
+      database_automation_log_table::dsl::database_automation_log_table
+        .filter(database_automation_log_table::rule_id.eq(rule_id)),
+    )
+    .execute(conn)?;
+    Ok::<(), FlowyError>(())
+  })?;
+  Ok(())
+}
+
+pub fn insert_automation_log(mut conn: DBConnection, log: AutomationLog) -> FlowyResult<()> {
+  let table = AutomationLogTable {
+    log_id: if log.id.is_empty() {
+      gen_automation_log_id()
+    } else {
+      log.id
+    },
+    rule_id: log.rule_id,
+    database_id: log.database_id,
+    row_id: log.row_id,
+    status: log.status as i32,
+    message: log.message,
+    executed_at: log.executed_at,
+  };
+
+  conn.immediate_transaction(|conn| {
+    insert_into(database_automation_log_table::table)
+      .values(&table)
+      .execute(conn)?;
+
+    // Only keep the latest logs of the database.
+    let expired_log_ids = database_automation_log_table::dsl::database_automation_log_table
+      .select(database_automation_log_table::log_id)
+      .filter(database_automation_log_table::database_id.eq(&table.database_id))
+      .order(database_automation_log_table::executed_at.desc())
+      .offset(MAX_LOGS_PER_DATABASE)
+      .load::<String>(conn)?;
+    if !expired_log_ids.is_empty() {
+      diesel::delete(
+        database_automation_log_table::dsl::database_automation_log_table
+          .filter(database_automation_log_table::log_id.eq_any(expired_log_ids)),
+      )
+      .execute(conn)?;
+    }
+    Ok::<(), FlowyError>(())
+  })?;
+  Ok(())
+}
+
+pub fn select_automation_logs(
+  mut conn: DBConnection,
+  database_id: &str,
+  limit: i64,
+) -> FlowyResult<Vec<AutomationLog>> {
+  let logs = database_automation_log_table::dsl::database_automation_log_table
+    .filter(database_automation_log_table::database_id.eq(database_id))
+    .order(database_automation_log_table::executed_at.desc())
+    .limit(limit)
+    .load::<AutomationLogTable>(&mut *conn)?
+    .into_iter()
+    .map(AutomationLog::from)
+    .collect();
+  Ok(logs)
+}
@@ -0,0 +1,371 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use collab_database::rows::{Row, RowId};
+use dashmap::DashMap;
+use flowy_error::{FlowyError, FlowyResult};
+use lib_infra::box_any::BoxAny;
+use lib_infra::util::timestamp;
+use tokio::sync::RwLock as TokioRwLock;
+use tracing::{error, trace};
+
+use crate::entities::{AutomationLogPB, FieldType};
+use crate::notification::{send_notification, DatabaseNotification};
+use crate::services::automation::{
+  delete_automation_rule, gen_automation_log_id, insert_automation_log, select_automation_logs,
+  select_automation_rules, upsert_automation_rule, AutomationAction, AutomationEvent,
+  AutomationExecutionStatus, AutomationLog, AutomationRule,
+};
+use crate::services::database::DatabaseEditor;
+use crate::services::field::{DateCellChangeset, SelectOptionCellChangeset, CHECK, UNCHECK};
+use crate::services::filter::apply_filter;
+use crate::DatabaseUser;
+
+/// The maximum number of rules that can run in a single chain of automations. A chain starts
+/// with a change made by the user and contains the changes made by its actions afterwards.
+const MAX_CHAIN_STEPS: usize = 20;
+
+#[derive(Default)]
+struct AutomationChain {
+  pending: VecDeque<AutomationEvent>,
+  fired_rule_ids: HashSet<String>,
+  steps: usize,
+}
+
+/// A cell change made on this device, which is recorded before the cell is updated. The row
+/// observer takes it when the change is observed, so the changes made by the other devices don't
+/// trigger the automations again.
+struct PendingCellChange {
+  view_id: String,
+  old_row: Option<Row>,
+  /// The change is made by the actions of a running chain, which handles it by itself.
+  is_automation: bool,
+}
+
+pub struct AutomationController {
+  database_id: String,
+  user: Arc<dyn DatabaseUser>,
+  rules: TokioRwLock<Option<Vec<AutomationRule>>>,
+  /// The local cell changes that haven't been observed yet, keyed by [cell_key].
+  pending_cells: DashMap<String, VecDeque<PendingCellChange>>,
+  /// The rows created locally that haven't been observed yet, mapped to the view they're created
+  /// in.
+  pending_rows: DashMap<String, String>,
+  /// The cells that are being updated by the actions, keyed by [cell_key]. The value is the
+  /// number of the running actions that update the cell.
+  automation_cells: DashMap<String, usize>,
+}
+
+impl AutomationController {
+  pub fn new(database_id: String, user: Arc<dyn DatabaseUser>) -> Self {
+    Self {
+      database_id,
+      user,
+      rules: TokioRwLock::new(None),
+      pending_cells: DashMap::new(),
+      pending_rows: DashMap::new(),
+      automation_cells: DashMap::new(),
+    }
+  }
+
+  pub async fn get_rules(&self) -> FlowyResult<Vec<AutomationRule>> {
+    if let Some(rules) = self.rules.read().await.as_ref() {
+      return Ok(rules.clone());
+    }
+
+    let uid = self.user.user_id()?;
+    let conn = self.user.sqlite_connection(uid)?;
+    let rules = select_automation_rules(conn, &self.database_id)?;
+    *self.rules.write().await = Some(rules.clone());
+    Ok(rules)
+  }
+
+  pub async fn get_rule(&self, rule_id: &str) -> FlowyResult<AutomationRule> {
+    self
+      .get_rules()
+      .await?
+      .into_iter()
+      .find(|rule| rule.id == rule_id)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("Automation rule:{} not found", rule_id))
+      })
+  }
+
+  pub async fn save_rule(&self, rule: &AutomationRule) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    let conn = self.user.sqlite_connection(uid)?;
+    upsert_automation_rule(conn, rule)?;
+    *self.rules.write().await = None;
+    Ok(())
+  }
+
+  pub async fn delete_rule(&self, rule_id: &str) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    let conn = self.user.sqlite_connection(uid)?;
+    delete_automation_rule(conn, rule_id)?;
+    *self.rules.write().await = None;
+    Ok(())
+  }
+
+  pub fn get_logs(&self, limit: i64) -> FlowyResult<Vec<AutomationLog>> {
+    let uid = self.user.user_id()?;
+    let conn = self.user.sqlite_connection(uid)?;
+    select_automation_logs(conn, &self.database_id, limit)
+  }
+
+  /// Record the row that is going to be created in the view. Must be called before the row is
+  /// created.
+  pub fn will_create_row(&self, view_id: &str, row_id: &RowId) {
+    self
+      .pending_rows
+      .insert(row_id.to_string(), view_id.to_string());
+  }
+
+  /// Record the old row of the cell that is going to be updated. Must be called before the cell
+  /// is updated.
+  pub fn will_update_cell(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    field_id: &str,
+    old_row: Option<Row>,
+  ) {
+    let key = cell_key(row_id, field_id);
+    let is_automation = self.automation_cells.contains_key(&key);
+    self
+      .pending_cells
+      .entry(key)
+      .or_default()
+      .push_back(PendingCellChange {
+        view_id: view_id.to_string(),
+        old_row,
+        is_automation,
+      });
+  }
+
+  /// Discard the recorded change when the cell fails to update.
+  pub fn cancel_cell_update(&self, row_id: &RowId, field_id: &str) {
+    self
+      .pending_cells
+      .remove_if_mut(&cell_key(row_id, field_id), |_, changes| {
+        changes.pop_back();
+        changes.is_empty()
+      });
+  }
+
+  /// Called by the view observer when a row is inserted. Only the rows created on this device
+  /// trigger the automations, and only once for all the views of the database.
+  pub async fn did_create_row(&self, editor: &DatabaseEditor, row: Row) {
+    if let Some((_, view_id)) = self.pending_rows.remove(row.id.as_str()) {
+      let event = AutomationEvent::RowCreated { view_id, row };
+      self.run_chain(editor, event).await;
+    }
+  }
+
+  /// Called by the row observer when a cell is updated.
+  pub async fn did_update_cell(&self, editor: &DatabaseEditor, row_id: &RowId, field_id: &str) {
+    let key = cell_key(row_id, field_id);
+    let change = self
+      .pending_cells
+      .get_mut(&key)
+      .and_then(|mut changes| changes.pop_front());
+    self
+      .pending_cells
+      .remove_if(&key, |_, changes| changes.is_empty());
+    let change = match change {
+      Some(change) if !change.is_automation => change,
+      // The change is made by another device, or by the actions of a running chain
+      _ => return,
+    };
+
+    if let Some(row) = editor.get_row(&change.view_id, row_id).await {
+      let event = AutomationEvent::CellChanged {
+        view_id: change.view_id,
+        field_id: field_id.to_string(),
+        old_row: change.old_row,
+        row,
+      };
+      self.run_chain(editor, event).await;
+    }
+  }
+
+  /// Run the rules that are triggered by a change of the user, and then the rules that are
+  /// triggered by the changes of the actions. Each chain has its own loop protection, so the
+  /// changes that the user makes while the chain is running start their own chains.
+  async fn run_chain(&self, editor: &DatabaseEditor, event: AutomationEvent) {
+    let rules = match self.get_rules().await {
+      Ok(rules) => rules,
+      Err(err) => {
+        error!("[Automation]: failed to load rules: {}", err);
+        return;
+      },
+    };
+    if rules.iter().all(|rule| !rule.enabled) {
+      return;
+    }
+
+    let mut chain = AutomationChain::default();
+    chain.pending.push_back(event);
+    while let Some(event) = chain.pending.pop_front() {
+      let row_id = event.row_id().to_string();
+      for rule in rules.iter().filter(|rule| rule.enabled) {
+        if !event.is_triggered(&rule.trigger) || !self.should_run(editor, rule, &event).await {
+          continue;
+        }
+
+        let can_run = chain.steps < MAX_CHAIN_STEPS && chain.fired_rule_ids.insert(rule.id.clone());
+        let (status, message) = if can_run {
+          chain.steps += 1;
+          trace!("[Automation]: run rule:{} for row:{}", rule.id, row_id);
+          match self.run_actions(editor, rule, &event).await {
+            Ok(events) => {
+              chain.pending.extend(events);
+              (AutomationExecutionStatus::Succeeded, "".to_string())
+            },
+            Err(err) => (AutomationExecutionStatus::Failed, err.msg),
+          }
+        } else {
+          (
+            AutomationExecutionStatus::SkippedByLoopProtection,
+            "The rule has already run in the current chain of automations".to_string(),
+          )
+        };
+        self.save_log(rule, &row_id, status, message);
+      }
+    }
+  }
+
+  /// Returns true if the row matches the condition of the rule. For the cell changed trigger, the
+  /// rule only runs when the row becomes matched, which means the row didn't match the condition
+  /// before the change.
+  async fn should_run(
+    &self,
+    editor: &DatabaseEditor,
+    rule: &AutomationRule,
+    event: &AutomationEvent,
+  ) -> bool {
+    let filter = match rule
+      .condition
+      .as_ref()
+      .and_then(|condition| condition.to_filter())
+    {
+      None => return true,
+      Some(filter) => filter,
+    };
+
+    let field_by_field_id = editor
+      .get_fields(event.view_id(), None)
+      .await
+      .into_iter()
+      .map(|field| (field.id.clone(), field))
+      .collect::<HashMap<_, _>>();
+    let is_matched = |row: &Row| {
+      apply_filter(row, &field_by_field_id, &editor.cell_cache, &filter).unwrap_or(true)
+    };
+
+    match event {
+      AutomationEvent::RowCreated { row, .. } => is_matched(row),
+      AutomationEvent::CellChanged { old_row, row, .. } => {
+        is_matched(row) && !old_row.as_ref().map(is_matched).unwrap_or(false)
+      },
+    }
+  }
+
+  /// Apply the actions of the rule to the row. Returns the cell changes made by the actions, which
+  /// are handled by the same chain.
+  async fn run_actions(
+    &self,
+    editor: &DatabaseEditor,
+    rule: &AutomationRule,
+    event: &AutomationEvent,
+  ) -> FlowyResult<Vec<AutomationEvent>> {
+    let mut events = vec![];
+    for action in &rule.actions {
+      let field_id = action.field_id();
+      let field = editor.get_field(field_id).await.ok_or_else(|| {
+        FlowyError::field_record_not_found().with_context(format!("Field:{} not found", field_id))
+      })?;
+      if !action.is_allowed(FieldType::from(field.field_type)) {
+        return Err(FlowyError::invalid_data().with_context(format!(
+          "The action can't be applied to the field:{}",
+          field.name
+        )));
+      }
+
+      let changeset = match action {
+        AutomationAction::SetCellContent { content, .. } => BoxAny::new(content.clone()),
+        AutomationAction::SetDateToNow { .. } => BoxAny::new(DateCellChangeset {
+          timestamp: Some(timestamp()),
+          ..Default::default()
+        }),
+        AutomationAction::SetSelectOptions { option_ids, .. } => BoxAny::new(
+          SelectOptionCellChangeset::from_insert_options(option_ids.clone()),
+        ),
+        AutomationAction::SetCheckbox { is_checked, .. } => {
+          let content = if *is_checked { CHECK } else { UNCHECK };
+          BoxAny::new(content.to_string())
+        },
+      };
+      let old_row = editor.get_row(event.view_id(), event.row_id()).await;
+      let key = cell_key(event.row_id(), field_id);
+      *self.automation_cells.entry(key.clone()).or_default() += 1;
+      let result = editor
+        .update_cell_with_changeset(event.view_id(), event.row_id(), field_id, changeset)
+        .await;
+      self.automation_cells.remove_if_mut(&key, |_, count| {
+        *count -= 1;
+        *count == 0
+      });
+      result?;
+
+      if let Some(row) = editor.get_row(event.view_id(), event.row_id()).await {
+        events.push(AutomationEvent::CellChanged {
+          view_id: event.view_id().to_string(),
+          field_id: field_id.to_string(),
+          old_row,
+          row,
+        });
+      }
+    }
+    Ok(events)
+  }
+
+  fn save_log(
+    &self,
+    rule: &AutomationRule,
+    row_id: &str,
+    status: AutomationExecutionStatus,
+    message: String,
+  ) {
+    let log = AutomationLog {
+      id: gen_automation_log_id(),
+      rule_id: rule.id.clone(),
+      database_id: self.database_id.clone(),
+      row_id: row_id.to_string(),
+      status,
+      message,
+      executed_at: timestamp(),
+    };
+
+    let result = self
+      .user
+      .user_id()
+      .and_then(|uid| self.user.sqlite_connection(uid))
+      .and_then(|conn| insert_automation_log(conn, log.clone()));
+    if let Err(err) = result {
+      error!("[Automation]: failed to save log: {}", err);
+    }
+
+    send_notification(
+      &self.database_id,
+      DatabaseNotification::DidUpdateAutomationLog,
+    )
+    .payload(AutomationLogPB::from(log))
+    .send();
+  }
+}
+
+fn cell_key(row_id: &RowId, field_id: &str) -> String {
+  format!("{}:{}", row_id, field_id)
+}
@@ -0,0 +1,286 @@
+use collab_database::database::gen_database_filter_id;
+use collab_database::rows::{Row, RowId};
+use nanoid::nanoid;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{FieldType, FilterDataPB, FilterPB, FilterType};
+use crate::services::filter::{Filter, FilterInner};
+
+pub fn gen_automation_rule_id() -> String {
+  format!("a:{}", nanoid!(6))
+}
+
+pub fn gen_automation_log_id() -> String {
+  nanoid!(10)
+}
+
+#[derive(Debug, Clone)]
+pub struct AutomationRule {
+  pub id: String,
+  pub database_id: String,
+  pub name: String,
+  pub enabled: bool,
+  pub trigger: AutomationTrigger,
+  /// The rule runs only when the row matches the condition. A rule without condition runs
+  /// whenever it's triggered.
+  pub condition: Option<AutomationCondition>,
+  pub actions: Vec<AutomationAction>,
+  pub created_at: i64,
+  pub updated_at: i64,
+}
+
+impl AutomationRule {
+  /// Returns all the field ids that are referenced by the trigger, the condition and the actions.
+  pub fn referenced_field_ids(&self) -> Vec<String> {
+    let mut field_ids = vec![];
+    if let AutomationTrigger::CellChanged { field_id } = &self.trigger {
+      field_ids.push(field_id.clone());
+    }
+    if let Some(condition) = &self.condition {
+      condition.collect_field_ids(&mut field_ids);
+    }
+    for action in &self.actions {
+      field_ids.push(action.field_id().to_string());
+    }
+    field_ids.sort();
+    field_ids.dedup();
+    field_ids
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationTrigger {
+  RowCreated,
+  CellChanged { field_id: String },
+}
+
+/// A serializable form of the [Filter] tree. The data of each leaf is the protobuf encoded filter
+/// data, which is the same as [FilterDataPB::data], so the filter model can be reused to decide
+/// whether a row matches the condition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationCondition {
+  And {
+    children: Vec<AutomationCondition>,
+  },
+  Or {
+    children: Vec<AutomationCondition>,
+  },
+  Data {
+    field_id: String,
+    field_type: i64,
+    data: Vec<u8>,
+  },
+}
+
+impl AutomationCondition {
+  pub fn collect_field_ids(&self, field_ids: &mut Vec<String>) {
+    match self {
+      AutomationCondition::And { children } | AutomationCondition::Or { children } => {
+        for child in children {
+          child.collect_field_ids(field_ids);
+        }
+      },
+      AutomationCondition::Data { field_id, .. } => field_ids.push(field_id.clone()),
+    }
+  }
+
+  pub fn to_filter(&self) -> Option<Filter> {
+    let inner = match self {
+      AutomationCondition::And { children } => FilterInner::And {
+        children: children
+          .iter()
+          .flat_map(|child| child.to_filter())
+          .collect(),
+      },
+      AutomationCondition::Or { children } => FilterInner::Or {
+        children: children
+          .iter()
+          .flat_map(|child| child.to_filter())
+          .collect(),
+      },
+      AutomationCondition::Data {
+        field_id,
+        field_type,
+        data,
+      } => FilterInner::try_from(FilterDataPB {
+        field_id: field_id.clone(),
+        field_type: FieldType::from(*field_type),
+        data: data.clone(),
+      })
+      .ok()?,
+    };
+    Some(Filter {
+      id: gen_database_filter_id(),
+      inner,
+    })
+  }
+}
+
+impl From<FilterPB> for AutomationCondition {
+  fn from(filter: FilterPB) -> Self {
+    match (filter.filter_type, filter.data) {
+      (FilterType::Or, _) => AutomationCondition::Or {
+        children: filter.children.into_iter().map(Into::into).collect(),
+      },
+      (FilterType::Data, Some(data)) => AutomationCondition::Data {
+        field_id: data.field_id,
+        field_type: data.field_type.value(),
+        data: data.data,
+      },
+      _ => AutomationCondition::And {
+        children: filter.children.into_iter().map(Into::into).collect(),
+      },
+    }
+  }
+}
+
+impl From<&AutomationCondition> for FilterPB {
+  fn from(condition: &AutomationCondition) -> Self {
+    match condition {
+      AutomationCondition::And { children } => FilterPB {
+        id: "".to_string(),
+        filter_type: FilterType::And,
+        children: children.iter().map(FilterPB::from).collect(),
+        data: None,
+      },
+      AutomationCondition::Or { children } => FilterPB {
+        id: "".to_string(),
+        filter_type: FilterType::Or,
+        children: children.iter().map(FilterPB::from).collect(),
+        data: None,
+      },
+      AutomationCondition::Data {
+        field_id,
+        field_type,
+        data,
+      } => FilterPB {
+        id: "".to_string(),
+        filter_type: FilterType::Data,
+        children: vec![],
+        data: Some(FilterDataPB {
+          field_id: field_id.clone(),
+          field_type: FieldType::from(*field_type),
+          data: data.clone(),
+        }),
+      },
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationAction {
+  SetCellContent {
+    field_id: String,
+    content: String,
+  },
+  SetDateToNow {
+    field_id: String,
+  },
+  SetSelectOptions {
+    field_id: String,
+    option_ids: Vec<String>,
+  },
+  SetCheckbox {
+    field_id: String,
+    is_checked: bool,
+  },
+}
+
+impl AutomationAction {
+  pub fn field_id(&self) -> &str {
+    match self {
+      AutomationAction::SetCellContent { field_id, .. }
+      | AutomationAction::SetDateToNow { field_id }
+      | AutomationAction::SetSelectOptions { field_id, .. }
+      | AutomationAction::SetCheckbox { field_id, .. } => field_id,
+    }
+  }
+
+  /// Returns true if the action can be applied to the field with the given type.
+  pub fn is_allowed(&self, field_type: FieldType) -> bool {
+    match self {
+      AutomationAction::SetCellContent { .. } => matches!(
+        field_type,
+        FieldType::RichText | FieldType::Number | FieldType::URL | FieldType::Checkbox
+      ),
+      AutomationAction::SetDateToNow { .. } => field_type.is_date(),
+      AutomationAction::SetSelectOptions { .. } => field_type.is_select_option(),
+      AutomationAction::SetCheckbox { .. } => field_type.is_checkbox(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum AutomationExecutionStatus {
+  Succeeded = 0,
+  Failed = 1,
+  SkippedByLoopProtection = 2,
+}
+
+impl From<i32> for AutomationExecutionStatus {
+  fn from(value: i32) -> Self {
+    match value {
+      1 => AutomationExecutionStatus::Failed,
+      2 => AutomationExecutionStatus::SkippedByLoopProtection,
+      _ => AutomationExecutionStatus::Succeeded,
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct AutomationLog {
+  pub id: String,
+  pub rule_id: String,
+  pub database_id: String,
+  pub row_id: String,
+  pub status: AutomationExecutionStatus,
+  pub message: String,
+  pub executed_at: i64,
+}
+
+/// The events that may trigger the automation rules.
+#[derive(Debug, Clone)]
+pub enum AutomationEvent {
+  RowCreated {
+    view_id: String,
+    row: Row,
+  },
+  CellChanged {
+    view_id: String,
+    field_id: String,
+    old_row: Option<Row>,
+    row: Row,
+  },
+}
+
+impl AutomationEvent {
+  pub fn row_id(&self) -> &RowId {
+    match self {
+      AutomationEvent::RowCreated { row, .. } | AutomationEvent::CellChanged { row, .. } => &row.id,
+    }
+  }
+
+  pub fn view_id(&self) -> &str {
+    match self {
+      AutomationEvent::RowCreated { view_id, .. }
+      | AutomationEvent::CellChanged { view_id, .. } => view_id,
+    }
+  }
+
+  pub fn is_triggered(&self, trigger: &AutomationTrigger) -> bool {
+    match (self, trigger) {
+      (AutomationEvent::RowCreated { .. }, AutomationTrigger::RowCreated) => true,
+      (
+        AutomationEvent::CellChanged { field_id, .. },
+        AutomationTrigger::CellChanged {
+          field_id: trigger_field_id,
+        },
+      ) => field_id == trigger_field_id,
+      _ => false,
+    }
+  }
+}
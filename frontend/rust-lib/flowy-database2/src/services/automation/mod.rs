@@ -0,0 +1,7 @@
+mod automation_sql;
+mod controller;
+mod entities;
+
+pub(crate) use automation_sql::*;
+pub use controller::*;
+pub use entities::*;
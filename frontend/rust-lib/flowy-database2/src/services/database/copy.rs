@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use collab::core::collab::DataSource;
+use collab::core::origin::CollabOrigin;
+use collab::preclude::Collab;
+use collab_database::database::{
+  gen_database_id, gen_database_view_id, gen_row_id, mut_database_views_with_collab,
+  reset_inline_view_id,
+};
+use collab_database::entity::EncodedCollabInfo;
+use collab_database::rows::{
+  database_row_document_id_from_row_id, mut_row_with_collab, Cell, RowId,
+};
+use collab_entity::{CollabType, EncodedCollab};
+use flowy_error::{FlowyError, FlowyResult};
+
+/// Copies the collabs of a database, giving the database, its views, rows and row documents new
+/// ids. The ids already in `ids` are reused and the generated ones are added to it, so the caller
+/// decides the ids of the views it knows about.
+///
+/// The relations of the database are kept, they still point to the related databases. The cells in
+/// `row_cells`, by the old row ids and the field ids, replace the cells of the copied rows.
+pub fn copy_database_collabs(
+  database_id: &str,
+  database_collab: EncodedCollab,
+  row_collabs: HashMap<String, EncodedCollab>,
+  row_document_collabs: HashMap<String, EncodedCollab>,
+  row_cells: &HashMap<String, HashMap<String, Cell>>,
+  ids: &mut HashMap<String, String>,
+) -> FlowyResult<Vec<EncodedCollabInfo>> {
+  let new_database_id = exchange_id(ids, database_id, gen_database_id);
+  let mut collab = collab_from_encoded(database_id, database_collab)?;
+  reset_inline_view_id(&mut collab, |old_inline_view_id| {
+    exchange_id(ids, &old_inline_view_id, gen_database_view_id)
+  })
+  .map_err(|err| FlowyError::internal().with_context(err))?;
+
+  let mut row_ids = vec![];
+  mut_database_views_with_collab(&mut collab, |database_view| {
+    database_view.id = exchange_id(ids, &database_view.id, gen_database_view_id);
+    database_view.database_id = new_database_id.clone();
+    database_view
+      .row_orders
+      .retain(|row_order| row_collabs.contains_key(row_order.id.as_str()));
+    database_view.row_orders.iter_mut().for_each(|row_order| {
+      let old_row_id = String::from(row_order.id.clone());
+      let new_row_id = exchange_id(ids, &old_row_id, || gen_row_id().to_string());
+      row_order.id = RowId::from(new_row_id.clone());
+      if !row_ids.iter().any(|(old, _)| old == &old_row_id) {
+        row_ids.push((old_row_id, new_row_id));
+      }
+    });
+  });
+
+  let mut collabs = vec![EncodedCollabInfo {
+    object_id: new_database_id.clone(),
+    collab_type: CollabType::Database,
+    encoded_collab: encode_collab(&collab, CollabType::Database)?,
+  }];
+
+  for (old_row_id, new_row_id) in row_ids {
+    if let Some(row_collab) = row_collabs.get(&old_row_id) {
+      let mut collab = collab_from_encoded(&old_row_id, row_collab.clone())?;
+      mut_row_with_collab(&mut collab, |row_update| {
+        row_update
+          .set_row_id(RowId::from(new_row_id.clone()))
+          .set_database_id(new_database_id.clone());
+        if let Some(cells) = row_cells.get(&old_row_id) {
+          row_update.update_cells(|cells_update| {
+            for (field_id, cell) in cells {
+              cells_update.insert(field_id, cell.clone());
+            }
+          });
+        }
+      });
+      collabs.push(EncodedCollabInfo {
+        object_id: new_row_id.clone(),
+        collab_type: CollabType::DatabaseRow,
+        encoded_collab: encode_collab(&collab, CollabType::DatabaseRow)?,
+      });
+    }
+
+    let old_row_document_id = database_row_document_id_from_row_id(&old_row_id);
+    if let Some(row_document_collab) = row_document_collabs.get(&old_row_document_id) {
+      let new_row_document_id = database_row_document_id_from_row_id(&new_row_id);
+      ids.insert(old_row_document_id, new_row_document_id.clone());
+      collabs.push(EncodedCollabInfo {
+        object_id: new_row_document_id,
+        collab_type: CollabType::Document,
+        encoded_collab: row_document_collab.clone(),
+      });
+    }
+  }
+
+  Ok(collabs)
+}
+
+fn exchange_id(
+  ids: &mut HashMap<String, String>,
+  old_id: &str,
+  gen_id: impl FnOnce() -> String,
+) -> String {
+  ids.entry(old_id.to_string()).or_insert_with(gen_id).clone()
+}
+
+fn collab_from_encoded(object_id: &str, encoded_collab: EncodedCollab) -> FlowyResult<Collab> {
+  Collab::new_with_source(
+    CollabOrigin::Empty,
+    object_id,
+    DataSource::from(encoded_collab),
+    vec![],
+    false,
+  )
+  .map_err(|err| FlowyError::internal().with_context(err))
+}
+
+fn encode_collab(collab: &Collab, collab_type: CollabType) -> FlowyResult<EncodedCollab> {
+  collab
+    .encode_collab_v1(|collab| collab_type.validate_require_data(collab))
+    .map_err(|err| FlowyError::internal().with_context(err))
+}
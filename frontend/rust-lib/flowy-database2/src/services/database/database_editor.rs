@@ -1,5 +1,9 @@
 use crate::entities::*;
 use crate::notification::{send_notification, DatabaseNotification};
+use crate::services::automation::{
+  gen_automation_rule_id, AutomationAction, AutomationCondition, AutomationController,
+  AutomationLog, AutomationRule, AutomationTrigger,
+};
 use crate::services::calculations::Calculation;
 use crate::services::cell::{apply_cell_changeset, get_cell_protobuf, CellCache};
 use crate::services::database::database_observe::*;
@@ -16,7 +20,11 @@ use crate::services::field::{
 use crate::services::field_settings::{default_field_settings_by_layout_map, FieldSettings};
 use crate::services::filter::{Filter, FilterChangeset};
 use crate::services::group::{default_group_setting, GroupChangeset, GroupSetting};
+use crate::services::row_template::{
+  gen_row_template_id, RowDocumentServiceHolder, RowTemplate, RowTemplateController,
+};
 use crate::services::share::csv::{CSVExport, CSVFormat};
+use crate::services::share::html::HtmlTableExport;
 use crate::services::sort::Sort;
 use crate::utils::cache::AnyTypeCache;
 use crate::DatabaseUser;
@@ -67,6 +75,8 @@ pub struct DatabaseEditor {
   database_cancellation: Arc<RwLock<Option<CancellationToken>>>,
   un_finalized_rows_cancellation: Arc<ArcSwapOption<CancellationToken>>,
   finalized_rows: Arc<moka::future::Cache<String, Weak<RwLock<DatabaseRow>>>>,
+  pub(crate) automations: Arc<AutomationController>,
+  pub(crate) row_templates: Arc<RowTemplateController>,
 }
 
 impl DatabaseEditor {
@@ -75,6 +85,7 @@ impl DatabaseEditor {
     database: Arc<RwLock<Database>>,
     task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
+    row_document_service: RowDocumentServiceHolder,
   ) -> FlowyResult<Arc<Self>> {
     let finalized_rows: moka::future::Cache<String, Weak<RwLock<DatabaseRow>>> =
       moka::future::Cache::builder()
@@ -126,6 +137,12 @@ impl DatabaseEditor {
       CollabBuilderConfig::default(),
       database.clone(),
     )?;
+    let automations = Arc::new(AutomationController::new(database_id.clone(), user.clone()));
+    let row_templates = Arc::new(RowTemplateController::new(
+      database_id.clone(),
+      user.clone(),
+      row_document_service,
+    ));
     let this = Arc::new(Self {
       database_id: database_id.clone(),
       user,
@@ -138,9 +155,12 @@ impl DatabaseEditor {
       database_cancellation,
       un_finalized_rows_cancellation: Arc::new(Default::default()),
       finalized_rows: Arc::new(finalized_rows),
+      automations,
+      row_templates,
     });
     observe_block_event(&database_id, &this).await;
     observe_view_change(&database_id, &this).await;
+    observe_cell_change_for_automations(&database_id, &this).await;
     Ok(this)
   }
 
@@ -339,6 +359,107 @@ impl DatabaseEditor {
     Ok(())
   }
 
+  pub async fn get_automation_rules(&self) -> FlowyResult<Vec<AutomationRule>> {
+    self.automations.get_rules().await
+  }
+
+  pub async fn create_automation_rule(
+    &self,
+    params: CreateAutomationRulePayloadPB,
+  ) -> FlowyResult<AutomationRule> {
+    let now = timestamp();
+    let rule = AutomationRule {
+      id: gen_automation_rule_id(),
+      database_id: self.database_id.clone(),
+      name: params.name,
+      enabled: true,
+      trigger: AutomationTrigger::try_from(params.trigger)?,
+      condition: params.condition.map(AutomationCondition::from),
+      actions: params
+        .actions
+        .into_iter()
+        .map(AutomationAction::from)
+        .collect(),
+      created_at: now,
+      updated_at: now,
+    };
+    self.validate_automation_rule(&rule).await?;
+    self.automations.save_rule(&rule).await?;
+    Ok(rule)
+  }
+
+  pub async fn update_automation_rule(
+    &self,
+    params: UpdateAutomationRulePayloadPB,
+  ) -> FlowyResult<AutomationRule> {
+    let mut rule = self.automations.get_rule(&params.rule_id).await?;
+    if let Some(name) = params.name {
+      rule.name = name;
+    }
+    if let Some(enabled) = params.enabled {
+      rule.enabled = enabled;
+    }
+    if let Some(trigger) = params.trigger {
+      rule.trigger = AutomationTrigger::try_from(trigger)?;
+    }
+    if params.remove_condition {
+      rule.condition = None;
+    } else if let Some(condition) = params.condition {
+      rule.condition = Some(AutomationCondition::from(condition));
+    }
+    if let Some(actions) = params.actions {
+      rule.actions = actions
+        .items
+        .into_iter()
+        .map(AutomationAction::from)
+        .collect();
+    }
+    rule.updated_at = timestamp();
+    self.validate_automation_rule(&rule).await?;
+    self.automations.save_rule(&rule).await?;
+    Ok(rule)
+  }
+
+  pub async fn delete_automation_rule(&self, rule_id: &str) -> FlowyResult<()> {
+    self.automations.delete_rule(rule_id).await
+  }
+
+  pub async fn get_automation_logs(&self, limit: i64) -> FlowyResult<Vec<AutomationLog>> {
+    self.automations.get_logs(limit)
+  }
+
+  /// Make sure all the fields referenced by the rule exist and the actions can be applied to them.
+  async fn validate_automation_rule(&self, rule: &AutomationRule) -> FlowyResult<()> {
+    if rule.actions.is_empty() {
+      return Err(
+        FlowyError::invalid_data().with_context("The rule should have at least one action"),
+      );
+    }
+
+    let database = self.database.read().await;
+    for field_id in rule.referenced_field_ids() {
+      if database.get_field(&field_id).is_none() {
+        return Err(
+          FlowyError::field_record_not_found()
+            .with_context(format!("Field:{} used by the rule is not found", field_id)),
+        );
+      }
+    }
+
+    for action in &rule.actions {
+      if let Some(field) = database.get_field(action.field_id()) {
+        let field_type = FieldType::from(field.field_type);
+        if !action.is_allowed(field_type) {
+          return Err(FlowyError::invalid_data().with_context(format!(
+            "The action can't be applied to the {:?} field:{}",
+            field_type, field.name
+          )));
+        }
+      }
+    }
+    Ok(())
+  }
+
   pub async fn get_all_filters(&self, view_id: &str) -> RepeatedFilterPB {
     if let Ok(view_editor) = self.database_views.get_or_init_view_editor(view_id).await {
       let filters = view_editor.v_get_all_filters().await;
@@ -670,13 +791,48 @@ impl DatabaseEditor {
     Ok(())
   }
 
+  /// Create a row in the view. The row is pre-filled with the default template of the view if
+  /// there is one.
   pub async fn create_row(&self, params: CreateRowPayloadPB) -> FlowyResult<Option<RowDetail>> {
+    let template = self
+      .row_templates
+      .get_default_template(&params.view_id)
+      .await
+      .unwrap_or_else(|err| {
+        error!("[RowTemplate]: failed to get the default template: {}", err);
+        None
+      });
+    self.create_row_with_template(params, template).await
+  }
+
+  pub async fn create_row_from_template(
+    &self,
+    params: CreateRowFromTemplatePayloadPB,
+  ) -> FlowyResult<Option<RowDetail>> {
+    let template = self.row_templates.get_template(&params.template_id).await?;
+    self
+      .create_row_with_template(params.into(), Some(template))
+      .await
+  }
+
+  async fn create_row_with_template(
+    &self,
+    mut params: CreateRowPayloadPB,
+    template: Option<RowTemplate>,
+  ) -> FlowyResult<Option<RowDetail>> {
+    if let Some(template) = template.as_ref() {
+      template.fill_cells(&mut params.data);
+    }
+
     let view_editor = self
       .database_views
       .get_or_init_view_editor(&params.view_id)
       .await?;
 
     let params = view_editor.v_will_create_row(params).await?;
+    self
+      .automations
+      .will_create_row(&view_editor.view_id, &params.id);
 
     let mut database = self.database.write().await;
     let (index, row_order) = database
@@ -688,12 +844,164 @@ impl DatabaseEditor {
     trace!("[Database]: did create row: {} at {}", row_order.id, index);
     if let Some(row_detail) = row_detail {
       trace!("created row: {:?} at {}", row_detail, index);
+      if let Some(template) = template.as_ref() {
+        self
+          .did_create_row_with_template(&view_editor.view_id, &row_detail, template)
+          .await;
+      }
       return Ok(Some(row_detail));
     }
 
     Ok(None)
   }
 
+  async fn did_create_row_with_template(
+    &self,
+    view_id: &str,
+    row_detail: &RowDetail,
+    template: &RowTemplate,
+  ) {
+    if template.document.is_none() {
+      return;
+    }
+
+    match self
+      .row_templates
+      .create_row_document(&row_detail.document_id, template)
+      .await
+    {
+      Ok(_) => {
+        self
+          .update_row_meta(
+            &row_detail.row.id,
+            UpdateRowMetaParams {
+              id: row_detail.row.id.to_string(),
+              view_id: view_id.to_string(),
+              is_document_empty: Some(false),
+              ..Default::default()
+            },
+          )
+          .await;
+      },
+      Err(err) => error!(
+        "[RowTemplate]: failed to create the document of row:{}, error:{}",
+        row_detail.row.id, err
+      ),
+    }
+  }
+
+  pub async fn get_row_templates(&self, view_id: &str) -> FlowyResult<RepeatedRowTemplatePB> {
+    let items = self
+      .row_templates
+      .get_templates()
+      .await?
+      .into_iter()
+      .map(RowTemplatePB::from)
+      .collect();
+    let default_template_id = self.row_templates.get_default_template_id(view_id).await?;
+    Ok(RepeatedRowTemplatePB {
+      items,
+      default_template_id,
+    })
+  }
+
+  pub async fn create_row_template(
+    &self,
+    params: CreateRowTemplatePayloadPB,
+  ) -> FlowyResult<RowTemplate> {
+    let document = self
+      .resolve_template_document(params.document_json, params.document_id)
+      .await?;
+    let now = timestamp();
+    let template = RowTemplate {
+      id: gen_row_template_id(),
+      database_id: self.database_id.clone(),
+      name: params.name,
+      cells: params.cells,
+      document,
+      created_at: now,
+      updated_at: now,
+    };
+    self.validate_row_template(&template).await?;
+    self.row_templates.save_template(&template).await?;
+    Ok(template)
+  }
+
+  pub async fn update_row_template(
+    &self,
+    params: UpdateRowTemplatePayloadPB,
+  ) -> FlowyResult<RowTemplate> {
+    let mut template = self.row_templates.get_template(&params.template_id).await?;
+    if let Some(name) = params.name {
+      template.name = name;
+    }
+    if let Some(cells) = params.cells {
+      template.cells = cells.cells;
+    }
+    if params.remove_document {
+      template.document = None;
+    } else if let Some(document) = self
+      .resolve_template_document(params.document_json, params.document_id)
+      .await?
+    {
+      template.document = Some(document);
+    }
+    template.updated_at = timestamp();
+    self.validate_row_template(&template).await?;
+    self.row_templates.save_template(&template).await?;
+    Ok(template)
+  }
+
+  pub async fn delete_row_template(&self, template_id: &str) -> FlowyResult<()> {
+    self.row_templates.delete_template(template_id).await
+  }
+
+  pub async fn set_default_row_template(
+    &self,
+    view_id: &str,
+    template_id: Option<String>,
+  ) -> FlowyResult<()> {
+    self
+      .row_templates
+      .set_default_template(view_id, template_id)
+      .await
+  }
+
+  /// Returns the document JSON of the template. The content of the existing document is copied
+  /// if the document id is given.
+  async fn resolve_template_document(
+    &self,
+    document_json: Option<String>,
+    document_id: Option<String>,
+  ) -> FlowyResult<Option<String>> {
+    match (document_json, document_id) {
+      (Some(json), _) => {
+        serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&json).map_err(
+          |err| FlowyError::invalid_data().with_context(format!("Invalid document json: {}", err)),
+        )?;
+        Ok(Some(json))
+      },
+      (None, Some(document_id)) => {
+        let json = self.row_templates.get_document_json(&document_id).await?;
+        Ok(Some(json))
+      },
+      (None, None) => Ok(None),
+    }
+  }
+
+  async fn validate_row_template(&self, template: &RowTemplate) -> FlowyResult<()> {
+    let database = self.database.read().await;
+    for field_id in template.cells.keys() {
+      if database.get_field(field_id).is_none() {
+        return Err(FlowyError::field_record_not_found().with_context(format!(
+          "Field:{} used by the template is not found",
+          field_id
+        )));
+      }
+    }
+    Ok(())
+  }
+
   pub async fn create_field_with_type_option(
     &self,
     params: CreateFieldParams,
@@ -992,6 +1300,9 @@ impl DatabaseEditor {
     let old_row = self.get_row(view_id, row_id).await;
     trace!("[Database Row]: update cell: {:?}", new_cell);
     self
+      .automations
+      .will_update_cell(view_id, row_id, field_id, old_row.clone());
+    let result = self
       .update_row(row_id.clone(), |row_update| {
         row_update
           .set_last_modified(timestamp())
@@ -999,7 +1310,11 @@ impl DatabaseEditor {
             cell_update.insert(field_id, new_cell);
           });
       })
-      .await?;
+      .await;
+    if let Err(err) = result {
+      self.automations.cancel_cell_update(row_id, field_id);
+      return Err(err);
+    }
 
     self
       .did_update_row(view_id, row_id, field_id, old_row)
@@ -1027,12 +1342,19 @@ impl DatabaseEditor {
     // Get the old row before updating the cell. It would be better to get the old cell
     let old_row = self.get_row(view_id, &row_id).await;
     self
+      .automations
+      .will_update_cell(view_id, &row_id, field_id, old_row.clone());
+    let result = self
       .update_row(row_id.clone(), |row_update| {
         row_update.update_cells(|cell_update| {
           cell_update.clear(field_id);
         });
       })
-      .await?;
+      .await;
+    if let Err(err) = result {
+      self.automations.cancel_cell_update(&row_id, field_id);
+      return Err(err);
+    }
 
     self
       .did_update_row(view_id, &row_id, field_id, old_row)
@@ -1066,7 +1388,7 @@ impl DatabaseEditor {
       if let Some(field_type) = field_type {
         if FieldType::from(field_type) == FieldType::Media {
           self
-            .did_update_attachments(view_id, row_id, field_id, old_row.clone())
+            .did_update_attachments(view_id, row_id, field_id, old_row)
             .await;
         }
       }
@@ -1691,6 +2013,11 @@ impl DatabaseEditor {
     Ok(csv)
   }
 
+  pub async fn export_html_table(&self, view_id: &str) -> FlowyResult<String> {
+    let database = self.database.read().await;
+    HtmlTableExport.export_view(&database, view_id).await
+  }
+
   pub async fn get_field_settings(
     &self,
     view_id: &str,
@@ -82,6 +82,44 @@ pub(crate) async fn observe_rows_change(
     });
   }
 }
+/// Run the automations that are triggered by the cell changes. The automations of a change run in
+/// their own task, so they don't block the other changes.
+pub(crate) async fn observe_cell_change_for_automations(
+  database_id: &str,
+  database_editor: &Arc<DatabaseEditor>,
+) {
+  let database_id = database_id.to_string();
+  let weak_database_editor = Arc::downgrade(database_editor);
+  let sub = database_editor.database.read().await.subscribe_row_change();
+  if let Some(mut row_change) = sub {
+    af_spawn(async move {
+      while let Ok(row_change) = row_change.recv().await {
+        let database_editor = match weak_database_editor.upgrade() {
+          None => {
+            trace!(
+              "[Database Observe]: {} automations: database dropped",
+              database_id
+            );
+            break;
+          },
+          Some(database_editor) => database_editor,
+        };
+        if let RowChange::DidUpdateCell {
+          field_id, row_id, ..
+        } = row_change
+        {
+          af_spawn(async move {
+            database_editor
+              .automations
+              .did_update_cell(&database_editor, &row_id, &field_id)
+              .await;
+          });
+        }
+      }
+    });
+  }
+}
+
 #[allow(dead_code)]
 pub(crate) async fn observe_field_change(database_id: &str, database: &Arc<RwLock<Database>>) {
   let database_id = database_id.to_string();
@@ -222,6 +260,18 @@ async fn handle_did_update_row_orders(
       view_editor.insert_row(row.clone(), index, &row_order).await;
 
       let is_move_row = is_move_row(&view_editor, &row_order, &delete_row_indexes).await;
+      if is_local_change && !is_move_row {
+        if let Some(row) = row.as_ref() {
+          let row = row.as_ref().clone();
+          let database_editor = database_editor.clone();
+          af_spawn(async move {
+            database_editor
+              .automations
+              .did_create_row(&database_editor, row)
+              .await;
+          });
+        }
+      }
       if let Some((index, row_detail)) = view_editor.v_get_row(&row_order.id).await {
         view_editor
           .v_did_create_row(
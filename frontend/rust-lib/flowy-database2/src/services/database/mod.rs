@@ -1,8 +1,10 @@
+mod copy;
 mod database_editor;
 mod database_observe;
 mod entities;
 mod util;
 
+pub use copy::*;
 pub use database_editor::*;
 pub use entities::*;
 pub(crate) use util::database_view_setting_pb_from_view;
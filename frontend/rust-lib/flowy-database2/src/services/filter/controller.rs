@@ -471,7 +471,7 @@ fn filter_row(
 }
 
 /// Recursively applies a `Filter` to a `Row`'s cells.
-fn apply_filter(
+pub(crate) fn apply_filter(
   row: &Row,
   field_by_field_id: &HashMap<String, Field>,
   cell_data_cache: &CellCache,
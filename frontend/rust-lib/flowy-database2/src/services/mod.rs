@@ -1,3 +1,5 @@
+pub mod ai;
+pub mod automation;
 pub mod calculations;
 pub mod cell;
 pub mod database;
@@ -6,6 +8,7 @@ pub mod field;
 pub mod field_settings;
 pub mod filter;
 pub mod group;
+pub mod row_template;
 pub mod setting;
 pub mod share;
 pub mod snapshot;
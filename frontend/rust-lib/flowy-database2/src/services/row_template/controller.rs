@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+use flowy_error::{FlowyError, FlowyResult};
+use tokio::sync::RwLock as TokioRwLock;
+
+use crate::manager::DatabaseRowDocumentService;
+use crate::services::row_template::{
+  delete_default_row_template, delete_row_template, select_default_row_templates,
+  select_row_templates, upsert_default_row_template, upsert_row_template, DefaultRowTemplateTable,
+  RowTemplate,
+};
+use crate::DatabaseUser;
+
+pub type RowDocumentServiceHolder = Arc<ArcSwapOption<Arc<dyn DatabaseRowDocumentService>>>;
+
+#[derive(Clone, Default)]
+struct RowTemplateCache {
+  templates: Vec<RowTemplate>,
+  /// The default template id of each view.
+  default_template_ids: HashMap<String, String>,
+}
+
+pub struct RowTemplateController {
+  database_id: String,
+  user: Arc<dyn DatabaseUser>,
+  document_service: RowDocumentServiceHolder,
+  cache: TokioRwLock<Option<RowTemplateCache>>,
+}
+
+impl RowTemplateController {
+  pub fn new(
+    database_id: String,
+    user: Arc<dyn DatabaseUser>,
+    document_service: RowDocumentServiceHolder,
+  ) -> Self {
+    Self {
+      database_id,
+      user,
+      document_service,
+      cache: TokioRwLock::new(None),
+    }
+  }
+
+  async fn get_cache(&self) -> FlowyResult<RowTemplateCache> {
+    if let Some(cache) = self.cache.read().await.as_ref() {
+      return Ok(cache.clone());
+    }
+
+    let uid = self.user.user_id()?;
+    let templates = select_row_templates(self.user.sqlite_connection(uid)?, &self.database_id)?;
+    let default_template_ids =
+      select_default_row_templates(self.user.sqlite_connection(uid)?, &self.database_id)?;
+    let cache = RowTemplateCache {
+      templates,
+      default_template_ids,
+    };
+    *self.cache.write().await = Some(cache.clone());
+    Ok(cache)
+  }
+
+  pub async fn get_templates(&self) -> FlowyResult<Vec<RowTemplate>> {
+    Ok(self.get_cache().await?.templates)
+  }
+
+  pub async fn get_template(&self, template_id: &str) -> FlowyResult<RowTemplate> {
+    self
+      .get_templates()
+      .await?
+      .into_iter()
+      .find(|template| template.id == template_id)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("Row template:{} not found", template_id))
+      })
+  }
+
+  pub async fn get_default_template_id(&self, view_id: &str) -> FlowyResult<Option<String>> {
+    Ok(self.get_cache().await?.default_template_ids.remove(view_id))
+  }
+
+  pub async fn get_default_template(&self, view_id: &str) -> FlowyResult<Option<RowTemplate>> {
+    let cache = self.get_cache().await?;
+    let template = cache
+      .default_template_ids
+      .get(view_id)
+      .and_then(|template_id| {
+        cache
+          .templates
+          .into_iter()
+          .find(|template| &template.id == template_id)
+      });
+    Ok(template)
+  }
+
+  pub async fn save_template(&self, template: &RowTemplate) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    upsert_row_template(self.user.sqlite_connection(uid)?, template)?;
+    *self.cache.write().await = None;
+    Ok(())
+  }
+
+  pub async fn delete_template(&self, template_id: &str) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    delete_row_template(self.user.sqlite_connection(uid)?, template_id)?;
+    *self.cache.write().await = None;
+    Ok(())
+  }
+
+  /// Set the default template of the view. Pass None to remove the default template.
+  pub async fn set_default_template(
+    &self,
+    view_id: &str,
+    template_id: Option<String>,
+  ) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    let conn = self.user.sqlite_connection(uid)?;
+    match template_id {
+      None => delete_default_row_template(conn, view_id)?,
+      Some(template_id) => {
+        // Make sure the template exists
+        let template = self.get_template(&template_id).await?;
+        upsert_default_row_template(
+          conn,
+          DefaultRowTemplateTable {
+            view_id: view_id.to_string(),
+            database_id: self.database_id.clone(),
+            template_id: template.id,
+          },
+        )?;
+      },
+    }
+    *self.cache.write().await = None;
+    Ok(())
+  }
+
+  fn document_service(&self) -> FlowyResult<Arc<dyn DatabaseRowDocumentService>> {
+    self
+      .document_service
+      .load_full()
+      .map(|service| service.as_ref().clone())
+      .ok_or_else(|| FlowyError::internal().with_context("The row document service is not set"))
+  }
+
+  /// Returns the JSON of the document, which can be used as the document of a template.
+  pub async fn get_document_json(&self, document_id: &str) -> FlowyResult<String> {
+    self
+      .document_service()?
+      .get_document_json(document_id)
+      .await
+  }
+
+  pub async fn create_row_document(
+    &self,
+    document_id: &str,
+    template: &RowTemplate,
+  ) -> FlowyResult<()> {
+    if let Some(json) = template.document.as_ref() {
+      self
+        .document_service()?
+        .create_document_with_json(document_id, json)
+        .await?;
+    }
+    Ok(())
+  }
+}
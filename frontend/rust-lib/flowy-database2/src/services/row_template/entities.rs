@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use nanoid::nanoid;
+
+pub fn gen_row_template_id() -> String {
+  format!("rt:{}", nanoid!(6))
+}
+
+#[derive(Debug, Clone)]
+pub struct RowTemplate {
+  pub id: String,
+  pub database_id: String,
+  pub name: String,
+  /// The preset cell values keyed by field id. The value is the same as the cell data of
+  /// [CreateRowPayloadPB::data](crate::entities::CreateRowPayloadPB).
+  pub cells: HashMap<String, String>,
+  /// The JSON of the [NestedBlock] that is used to pre-fill the row document.
+  pub document: Option<String>,
+  pub created_at: i64,
+  pub updated_at: i64,
+}
+
+impl RowTemplate {
+  /// Fill the cells that are not set by the caller with the preset cell values of the template.
+  pub fn fill_cells(&self, cells: &mut HashMap<String, String>) {
+    for (field_id, data) in &self.cells {
+      cells
+        .entry(field_id.clone())
+        .or_insert_with(|| data.clone());
+    }
+  }
+}
@@ -0,0 +1,7 @@
+mod controller;
+mod entities;
+mod row_template_sql;
+
+pub use controller::*;
+pub use entities::*;
+pub(crate) use row_template_sql::*;
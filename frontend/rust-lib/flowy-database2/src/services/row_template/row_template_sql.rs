@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::schema::{database_row_template_table, database_view_row_template_table};
+use flowy_sqlite::{
+  diesel, insert_into, query_dsl::*, AsChangeset, DBConnection, ExpressionMethods, Identifiable,
+  Insertable, Queryable,
+};
+use tracing::error;
+
+use crate::services::row_template::RowTemplate;
+
+#[derive(Queryable, Insertable, AsChangeset, Identifiable, Debug, Clone)]
+#[diesel(table_name = database_row_template_table)]
+#[diesel(primary_key(template_id))]
+pub struct RowTemplateTable {
+  pub template_id: String,
+  pub database_id: String,
+  pub name: String,
+  pub cells_data: String,
+  pub document_data: Option<String>,
+  pub created_at: i64,
+  pub updated_at: i64,
+}
+
+impl TryFrom<&RowTemplate> for RowTemplateTable {
+  type Error = FlowyError;
+
+  fn try_from(template: &RowTemplate) -> Result<Self, Self::Error> {
+    Ok(Self {
+      template_id: template.id.clone(),
+      database_id: template.database_id.clone(),
+      name: template.name.clone(),
+      cells_data: serde_json::to_string(&template.cells)?,
+      document_data: template.document.clone(),
+      created_at: template.created_at,
+      updated_at: template.updated_at,
+    })
+  }
+}
+
+impl TryFrom<RowTemplateTable> for RowTemplate {
+  type Error = FlowyError;
+
+  fn try_from(table: RowTemplateTable) -> Result<Self, Self::Error> {
+    let cells = serde_json::from_str::<HashMap<String, String>>(&table.cells_data)?;
+    Ok(Self {
+      id: table.template_id,
+      database_id: table.database_id,
+      name: table.name,
+      cells,
+      document: table.document_data,
+      created_at: table.created_at,
+      updated_at: table.updated_at,
+    })
+  }
+}
+
+#[derive(Queryable, Insertable, AsChangeset, Identifiable, Debug, Clone)]
+#[diesel(table_name = database_view_row_template_table)]
+#[diesel(primary_key(view_id))]
+pub struct DefaultRowTemplateTable {
+  pub view_id: String,
+  pub database_id: String,
+  pub template_id: String,
+}
+
+pub fn upsert_row_template(mut conn: DBConnection, template: &RowTemplate) -> FlowyResult<()> {
+  let table = RowTemplateTable::try_from(template)?;
+  insert_into(database_row_template_table::table)
+    .values(&table)
+    .on_conflict(database_row_template_table::template_id)
+    .do_update()
+    .set(&table)
+    .execute(&mut *conn)?;
+  Ok(())
+}
+
+pub fn select_row_templates(
+  mut conn: DBConnection,
+  database_id: &str,
+) -> FlowyResult<Vec<RowTemplate>> {
+  let rows = database_row_template_table::dsl::database_row_template_table
+    .filter(database_row_template_table::database_id.eq(database_id))
+    .order(database_row_template_table::created_at.asc())
+    .load::<RowTemplateTable>(&mut *conn)?;
+
+  let templates = rows
+    .into_iter()
+    .flat_map(|row| {
+      let template_id = row.template_id.clone();
+      match RowTemplate::try_from(row) {
+        Ok(template) => Some(template),
+        Err(err) => {
+          error!(
+            "[RowTemplate]: failed to parse template:{}, error:{}",
+            template_id, err
+          );
+          None
+        },
+      }
+    })
+    .collect();
+  Ok(templates)
+}
+
+/// Delete the template and unset it from all the views that use it as the default template.
+pub fn delete_row_template(mut conn: DBConnection, template_id: &str) -> FlowyResult<()> {
+  conn.immediate_transaction(|conn| {
+    diesel::delete(
+      database_row_template_table::dsl::database_row_template_table
+        .filter(database_row_template_table::template_id.eq(template_id)),
+    )
+    .execute(conn)?;
+    diesel::delete(
+      database_view_row_template_table::dsl::database_view_row_template_table
+        .filter(database_view_row_template_table::template_id.eq(template_id)),
+    )
+    .execute(conn)?;
+    Ok::<(), FlowyError>(())
+  })?;
+  Ok(())
+}
+
+pub fn select_default_row_templates(
+  mut conn: DBConnection,
+  database_id: &str,
+) -> FlowyResult<HashMap<String, String>> {
+  let rows = database_view_row_template_table::dsl::database_view_row_template_table
+    .filter(database_view_row_template_table::database_id.eq(database_id))
+    .load::<DefaultRowTemplateTable>(&mut *conn)?;
+  Ok(
+    rows
+      .into_iter()
+      .map(|row| (row.view_id, row.template_id))
+      .collect(),
+  )
+}
+
+pub fn upsert_default_row_template(
+  mut conn: DBConnection,
+  table: DefaultRowTemplateTable,
+) -> FlowyResult<()> {
+  insert_into(database_view_row_template_table::table)
+    .values(&table)
+    .on_conflict(database_view_row_template_table::view_id)
+    .do_update()
+    .set(&table)
+    .execute(&mut *conn)?;
+  Ok(())
+}
+
+pub fn delete_default_row_template(mut conn: DBConnection, view_id: &str) -> FlowyResult<()> {
+  diesel::delete(
+    database_view_row_template_table::dsl::database_view_row_template_table
+      .filter(database_view_row_template_table::view_id.eq(view_id)),
+  )
+  .execute(&mut *conn)?;
+  Ok(())
+}
@@ -1,6 +1,6 @@
 use collab_database::database::Database;
 use collab_database::fields::Field;
-use collab_database::rows::Cell;
+use collab_database::rows::{Cell, Row};
 use futures::StreamExt;
 use indexmap::IndexMap;
 
@@ -18,6 +18,9 @@ pub enum CSVFormat {
   /// The export data contains meta data, such as field type.
   /// It can be used to fully restore the database.
   META,
+  /// The pure data exported by Notion. The field types are inferred from the values when
+  /// importing it, and it's exported like [CSVFormat::Original].
+  Notion,
 }
 
 pub struct CSVExport;
@@ -35,7 +38,7 @@ impl CSVExport {
     let field_records = fields
       .iter()
       .map(|field| match &style {
-        CSVFormat::Original => field.name.clone(),
+        CSVFormat::Original | CSVFormat::Notion => field.name.clone(),
         CSVFormat::META => serde_json::to_string(&field).unwrap(),
       })
       .collect::<Vec<String>>();
@@ -56,31 +59,14 @@ impl CSVExport {
       .await;
 
     let stringify = |cell: &Cell, field: &Field, style: CSVFormat| match style {
-      CSVFormat::Original => stringify_cell(cell, field),
+      CSVFormat::Original | CSVFormat::Notion => stringify_cell(cell, field),
       CSVFormat::META => serde_json::to_string(cell).unwrap_or_else(|_| "".to_string()),
     };
 
     for row in rows {
       let cells = field_by_field_id
-        .iter()
-        .map(|(field_id, field)| {
-          let field_type = FieldType::from(field.field_type);
-          match field_type {
-            FieldType::LastEditedTime | FieldType::CreatedTime => {
-              let cell_data = if field_type.is_created_time() {
-                TimestampCellData::new(row.created_at)
-              } else {
-                TimestampCellData::new(row.modified_at)
-              };
-              let cell = Cell::from(TimestampCellDataWrapper::from((field_type, cell_data)));
-              stringify(&cell, field, style)
-            },
-            _ => match row.cells.get(field_id) {
-              None => "".to_string(),
-              Some(cell) => stringify(cell, field, style),
-            },
-          }
-        })
+        .values()
+        .map(|field| stringify_row_cell(&row, field, |cell, field| stringify(cell, field, style)))
         .collect::<Vec<_>>();
 
       if let Err(e) = wtr.write_record(&cells) {
@@ -95,3 +81,27 @@ impl CSVExport {
     Ok(csv)
   }
 }
+
+/// Stringify the cell of the field in the row. The created time and the last edited time are not
+/// stored in the cells, so they are read from the row.
+pub(crate) fn stringify_row_cell<F>(row: &Row, field: &Field, stringify: F) -> String
+where
+  F: Fn(&Cell, &Field) -> String,
+{
+  let field_type = FieldType::from(field.field_type);
+  match field_type {
+    FieldType::LastEditedTime | FieldType::CreatedTime => {
+      let cell_data = if field_type.is_created_time() {
+        TimestampCellData::new(row.created_at)
+      } else {
+        TimestampCellData::new(row.modified_at)
+      };
+      let cell = Cell::from(TimestampCellDataWrapper::from((field_type, cell_data)));
+      stringify(&cell, field)
+    },
+    _ => match row.cells.get(&field.id) {
+      None => "".to_string(),
+      Some(cell) => stringify(cell, field),
+    },
+  }
+}
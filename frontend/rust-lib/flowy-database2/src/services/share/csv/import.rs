@@ -10,6 +10,7 @@ use std::{fs::File, io::prelude::*};
 use crate::entities::FieldType;
 use crate::services::field::{default_type_option_data_from_type, CELL_DATA};
 use crate::services::field_settings::default_field_settings_for_fields;
+use crate::services::share::csv::infer::{infer_cell, infer_field};
 use crate::services::share::csv::CSVFormat;
 
 #[derive(Default)]
@@ -84,6 +85,18 @@ fn database_from_fields_and_rows(
     .enumerate()
     .map(|(index, field_meta)| match format {
       CSVFormat::Original => default_field(field_meta, index == 0),
+      CSVFormat::Notion => {
+        let values = rows
+          .iter()
+          .map(|cells| {
+            cells
+              .get(index)
+              .map(|cell| cell.as_str())
+              .unwrap_or_default()
+          })
+          .collect::<Vec<_>>();
+        infer_field(&field_meta, &values, index == 0)
+      },
       CSVFormat::META => {
         //
         match serde_json::from_str(&field_meta) {
@@ -113,6 +126,7 @@ fn database_from_fields_and_rows(
             CSVFormat::Original => {
               cell.insert(CELL_DATA.into(), cell_content.as_str().into());
             },
+            CSVFormat::Notion => cell = infer_cell(field, cell_content),
             CSVFormat::META => match serde_json::from_str::<Cell>(cell_content) {
               Ok(cell_json) => cell = cell_json,
               Err(_) => {
@@ -189,7 +203,12 @@ impl Display for ImportResult {
 #[cfg(test)]
 mod tests {
   use collab_database::database::gen_database_view_id;
+  use collab_database::fields::date_type_option::DateCellData;
+  use collab_database::fields::select_type_option::SelectOptionIds;
 
+  use crate::entities::{CheckboxCellDataPB, FieldType};
+  use crate::services::cell::stringify_cell;
+  use crate::services::field::select_type_option_from_field;
   use crate::services::share::csv::{CSVFormat, CSVImporter};
 
   #[test]
@@ -219,6 +238,106 @@ mod tests {
     println!("{:?}", result);
   }
 
+  #[test]
+  fn import_notion_csv_infers_field_types_test() {
+    let s = r#"Name,Status,Tags,Estimate,Due,Done,Notes
+Task 1,Todo,"Work, Home",1,"May 26, 2023",Yes,first
+Task 2,Done,Work,"1,200","May 22, 2023 10:30 AM",No,second
+Task 3,Todo,,2.5,"May 20, 2023 → May 21, 2023",No,third"#;
+    let result = CSVImporter
+      .import_csv_from_string(gen_database_view_id(), s.to_string(), CSVFormat::Notion)
+      .unwrap();
+    let field_types = result
+      .fields
+      .iter()
+      .map(|field| FieldType::from(field.field_type))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      field_types,
+      vec![
+        FieldType::RichText,
+        FieldType::SingleSelect,
+        FieldType::MultiSelect,
+        FieldType::Number,
+        FieldType::DateTime,
+        FieldType::Checkbox,
+        FieldType::RichText,
+      ]
+    );
+    assert!(result.fields[0].is_primary);
+
+    let status = &result.fields[1];
+    let options = select_type_option_from_field(status)
+      .unwrap()
+      .options()
+      .clone();
+    assert_eq!(
+      options
+        .iter()
+        .map(|option| option.name.as_str())
+        .collect::<Vec<_>>(),
+      vec!["Todo", "Done"]
+    );
+    let tags = &result.fields[2];
+    let tag_options = select_type_option_from_field(tags)
+      .unwrap()
+      .options()
+      .clone();
+    assert_eq!(tag_options.len(), 2);
+    let tag_cell = result.rows[0].cells.get(&tags.id).unwrap();
+    assert_eq!(
+      SelectOptionIds::from(tag_cell).into_inner(),
+      tag_options
+        .iter()
+        .map(|option| option.id.clone())
+        .collect::<Vec<_>>()
+    );
+
+    let estimate = &result.fields[3];
+    let estimates = result
+      .rows
+      .iter()
+      .map(|row| stringify_cell(row.cells.get(&estimate.id).unwrap(), estimate))
+      .collect::<Vec<_>>();
+    assert_eq!(estimates, vec!["1", "1200", "2.5"]);
+
+    let due = &result.fields[4];
+    let first_due = DateCellData::from(result.rows[0].cells.get(&due.id).unwrap());
+    assert!(first_due.timestamp.is_some());
+    assert!(!first_due.include_time);
+    let second_due = DateCellData::from(result.rows[1].cells.get(&due.id).unwrap());
+    assert!(second_due.include_time);
+    let third_due = DateCellData::from(result.rows[2].cells.get(&due.id).unwrap());
+    assert!(third_due.is_range);
+    assert_eq!(
+      third_due.end_timestamp.unwrap() - third_due.timestamp.unwrap(),
+      24 * 60 * 60
+    );
+
+    let done = &result.fields[5];
+    let checked = result
+      .rows
+      .iter()
+      .map(|row| CheckboxCellDataPB::from(row.cells.get(&done.id).unwrap()).is_checked)
+      .collect::<Vec<_>>();
+    assert_eq!(checked, vec![true, false, false]);
+  }
+
+  #[test]
+  fn import_notion_csv_keeps_unique_values_as_text_test() {
+    let s = r#"Name,Description
+1,first
+2,second
+3,third"#;
+    let result = CSVImporter
+      .import_csv_from_string(gen_database_view_id(), s.to_string(), CSVFormat::Notion)
+      .unwrap();
+    assert_eq!(
+      FieldType::from(result.fields[1].field_type),
+      FieldType::RichText
+    );
+  }
+
   #[test]
   fn import_empty_csv_data_test() {
     let s = r#""#;
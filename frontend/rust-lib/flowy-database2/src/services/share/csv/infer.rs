@@ -0,0 +1,208 @@
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+use collab_database::fields::date_type_option::{DateFormat, DateTypeOption, TimeFormat};
+use collab_database::fields::select_type_option::{SelectOption, SelectTypeOption};
+use collab_database::fields::Field;
+use collab_database::rows::{new_cell_builder, Cell};
+use lib_infra::box_any::BoxAny;
+
+use crate::entities::FieldType;
+use crate::services::cell::{
+  apply_cell_changeset, insert_checkbox_cell, insert_select_option_cell,
+};
+use crate::services::field::{
+  new_select_option_color, select_type_option_from_field, DateCellChangeset, FieldBuilder,
+  CELL_DATA,
+};
+
+/// A column whose values are repeated and have fewer distinct values than this becomes a select
+/// field.
+const MAX_SELECT_OPTIONS: usize = 20;
+const MAX_SELECT_OPTION_LEN: usize = 50;
+/// Notion separates the start and the end of a date range with this arrow.
+const DATE_RANGE_SEPARATOR: &str = "→";
+
+const DATE_FORMATS: [&str; 5] = ["%B %d, %Y", "%Y-%m-%d", "%Y/%m/%d", "%m/%d/%Y", "%d/%m/%Y"];
+const DATE_TIME_FORMATS: [&str; 4] = [
+  "%B %d, %Y %I:%M %p",
+  "%B %d, %Y %H:%M",
+  "%Y-%m-%d %H:%M",
+  "%Y/%m/%d %H:%M",
+];
+
+/// Returns the field of the column, whose type is inferred from the values of the column. The
+/// primary field is always a text field.
+pub(crate) fn infer_field(name: &str, values: &[&str], is_primary: bool) -> Field {
+  let values = values
+    .iter()
+    .map(|value| value.trim())
+    .filter(|value| !value.is_empty())
+    .collect::<Vec<_>>();
+
+  let builder = if is_primary || values.is_empty() {
+    FieldBuilder::from_field_type(FieldType::RichText)
+  } else if values.iter().all(|value| parse_checkbox(value).is_some()) {
+    FieldBuilder::from_field_type(FieldType::Checkbox)
+  } else if values.iter().all(|value| parse_number(value).is_some()) {
+    FieldBuilder::from_field_type(FieldType::Number)
+  } else if values.iter().all(|value| parse_date(value).is_some()) {
+    let type_option = DateTypeOption {
+      date_format: DateFormat::Friendly,
+      time_format: TimeFormat::TwelveHour,
+      ..Default::default()
+    };
+    FieldBuilder::new(FieldType::DateTime, type_option)
+  } else if let Some((field_type, options)) = infer_select_options(&values) {
+    let type_option = SelectTypeOption {
+      options,
+      disable_color: false,
+    };
+    FieldBuilder::new(field_type, type_option)
+  } else {
+    FieldBuilder::from_field_type(FieldType::RichText)
+  };
+  builder.name(name).primary(is_primary).build()
+}
+
+/// Returns the cell of the value in the field that is returned by [infer_field].
+pub(crate) fn infer_cell(field: &Field, value: &str) -> Cell {
+  let value = value.trim();
+  let field_type = FieldType::from(field.field_type);
+  if value.is_empty() {
+    return new_cell_builder(field_type);
+  }
+  let cell = match field_type {
+    FieldType::Checkbox => {
+      parse_checkbox(value).map(|is_checked| insert_checkbox_cell(is_checked, field))
+    },
+    FieldType::Number => parse_number(value)
+      .and_then(|number| apply_cell_changeset(BoxAny::new(number), None, field, None).ok()),
+    FieldType::DateTime => parse_date(value).and_then(|date| {
+      let changeset = DateCellChangeset {
+        timestamp: Some(date.timestamp),
+        end_timestamp: date.end_timestamp,
+        include_time: Some(date.include_time),
+        is_range: Some(date.end_timestamp.is_some()),
+        ..Default::default()
+      };
+      apply_cell_changeset(BoxAny::new(changeset), None, field, None).ok()
+    }),
+    FieldType::SingleSelect | FieldType::MultiSelect => {
+      let options = select_type_option_from_field(field)
+        .map(|type_option| type_option.options().clone())
+        .unwrap_or_default();
+      let option_ids = split_select_options(value)
+        .filter_map(|name| options.iter().find(|option| option.name == name))
+        .map(|option| option.id.clone())
+        .collect::<Vec<_>>();
+      Some(insert_select_option_cell(option_ids, field))
+    },
+    _ => None,
+  };
+  cell.unwrap_or_else(|| {
+    let mut cell = new_cell_builder(field_type);
+    cell.insert(CELL_DATA.into(), value.into());
+    cell
+  })
+}
+
+fn parse_checkbox(value: &str) -> Option<bool> {
+  match value.to_lowercase().as_str() {
+    "yes" | "true" => Some(true),
+    "no" | "false" => Some(false),
+    _ => None,
+  }
+}
+
+/// Returns the number without the thousands separators.
+fn parse_number(value: &str) -> Option<String> {
+  let number = value.replace(',', "");
+  number.parse::<f64>().ok().map(|_| number)
+}
+
+struct ParsedDate {
+  timestamp: i64,
+  end_timestamp: Option<i64>,
+  include_time: bool,
+}
+
+fn parse_date(value: &str) -> Option<ParsedDate> {
+  let (start, end) = match value.split_once(DATE_RANGE_SEPARATOR) {
+    Some((start, end)) => (start.trim(), Some(end.trim())),
+    None => (value, None),
+  };
+  let (timestamp, include_time) = parse_date_timestamp(start)?;
+  let end_timestamp = match end {
+    Some(end) => Some(parse_date_timestamp(end)?.0),
+    None => None,
+  };
+  Some(ParsedDate {
+    timestamp,
+    end_timestamp,
+    include_time,
+  })
+}
+
+/// Returns the timestamp in seconds of the date in the local time zone, and whether the date
+/// includes the time. A date without the time is at its noon, like the dates that are picked in
+/// the app.
+fn parse_date_timestamp(value: &str) -> Option<(i64, bool)> {
+  let (date_time, include_time) = DATE_TIME_FORMATS
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .map(|date_time| (date_time, true))
+    .or_else(|| {
+      DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .and_then(|date| date.and_hms_opt(12, 0, 0))
+        .map(|date_time| (date_time, false))
+    })?;
+  let timestamp = Local
+    .from_local_datetime(&date_time)
+    .earliest()?
+    .timestamp();
+  Some((timestamp, include_time))
+}
+
+/// Notion exports the options of a multi-select cell separated by commas.
+fn split_select_options(value: &str) -> impl Iterator<Item = &str> {
+  value
+    .split(',')
+    .map(|name| name.trim())
+    .filter(|name| !name.is_empty())
+}
+
+/// Returns the select options of the column if its values are a few short names that are used by
+/// several rows.
+fn infer_select_options(values: &[&str]) -> Option<(FieldType, Vec<SelectOption>)> {
+  let mut options: Vec<SelectOption> = vec![];
+  let mut is_multi_select = false;
+  let mut count = 0;
+  for value in values {
+    let names = split_select_options(value).collect::<Vec<_>>();
+    is_multi_select |= names.len() > 1;
+    for name in names {
+      if name.chars().count() > MAX_SELECT_OPTION_LEN {
+        return None;
+      }
+      count += 1;
+      if !options.iter().any(|option| option.name == name) {
+        if options.len() == MAX_SELECT_OPTIONS {
+          return None;
+        }
+        let color = new_select_option_color(&options);
+        options.push(SelectOption::with_color(name, color));
+      }
+    }
+  }
+
+  if count <= options.len() {
+    return None;
+  }
+  let field_type = if is_multi_select {
+    FieldType::MultiSelect
+  } else {
+    FieldType::SingleSelect
+  };
+  Some((field_type, options))
+}
@@ -1,5 +1,6 @@
 mod export;
 mod import;
+mod infer;
 
 pub use export::*;
 pub use import::*;
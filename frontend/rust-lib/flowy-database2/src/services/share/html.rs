@@ -0,0 +1,40 @@
+use collab_database::database::Database;
+use futures::StreamExt;
+
+use flowy_error::FlowyResult;
+use lib_infra::util::escape_html;
+
+use crate::services::cell::stringify_cell;
+use crate::services::share::csv::stringify_row_cell;
+
+pub struct HtmlTableExport;
+impl HtmlTableExport {
+  /// Export the fields and the rows of the view to a read-only HTML table, in the order of the
+  /// view. The cells are exported as text.
+  pub async fn export_view(&self, database: &Database, view_id: &str) -> FlowyResult<String> {
+    let fields = database.get_fields_in_view(view_id, None);
+    let rows = database
+      .get_rows_for_view(view_id, None)
+      .await
+      .filter_map(|result| async { result.ok() })
+      .collect::<Vec<_>>()
+      .await;
+
+    let mut html = String::from("<table class=\"database\"><thead><tr>");
+    for field in &fields {
+      html.push_str(&format!("<th>{}</th>", escape_html(&field.name)));
+    }
+    html.push_str("</tr></thead><tbody>");
+    for row in rows {
+      html.push_str("<tr>");
+      for field in &fields {
+        let text = stringify_row_cell(&row, field, stringify_cell);
+        let text = escape_html(&text).replace('\n', "<br>");
+        html.push_str(&format!("<td>{}</td>", text));
+      }
+      html.push_str("</tr>");
+    }
+    html.push_str("</tbody></table>");
+    Ok(html)
+  }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::cell::stringify_cell;
+use flowy_database_pub::cloud::{DatabaseAIService, SummaryRowContent};
+use flowy_error::FlowyError;
+use lib_infra::async_trait::async_trait;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+struct MockDatabaseAIService;
+
+const MOCK_SUMMARY: &str = "A summary written by the AI";
+
+#[async_trait]
+impl DatabaseAIService for MockDatabaseAIService {
+  async fn summary_database_row(
+    &self,
+    _workspace_id: &str,
+    _object_id: &str,
+    _summary_row: SummaryRowContent,
+  ) -> Result<String, FlowyError> {
+    Ok(MOCK_SUMMARY.to_string())
+  }
+}
+
+#[tokio::test]
+async fn fill_non_ai_column_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let result = test
+    .sdk
+    .database_manager
+    .start_ai_column_fill(&test.view_id, &text_field.id)
+    .await;
+  assert!(result.is_err());
+  assert!(!test
+    .sdk
+    .database_manager
+    .is_ai_column_filling(&text_field.id));
+}
+
+#[tokio::test]
+async fn fill_ai_column_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let summary_field = test.get_first_field(FieldType::Summary).await;
+  let manager = &test.sdk.database_manager;
+  manager.set_ai_service(Arc::new(MockDatabaseAIService));
+  let task = manager
+    .start_ai_column_fill(&test.view_id, &summary_field.id)
+    .await
+    .unwrap();
+  assert!(manager.is_ai_column_filling(&summary_field.id));
+
+  manager
+    .fill_ai_column(test.view_id.clone(), false, task)
+    .await
+    .unwrap();
+  assert!(!manager.is_ai_column_filling(&summary_field.id));
+
+  let rows = test.get_rows().await;
+  assert!(!rows.is_empty());
+  for row in rows {
+    let summary = row
+      .cells
+      .get(&summary_field.id)
+      .map(|cell| stringify_cell(cell, &summary_field))
+      .unwrap_or_default();
+    assert_eq!(summary, MOCK_SUMMARY);
+  }
+}
+
+#[tokio::test]
+async fn cancel_ai_column_fill_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let summary_field = test.get_first_field(FieldType::Summary).await;
+  let manager = &test.sdk.database_manager;
+  let task = manager
+    .start_ai_column_fill(&test.view_id, &summary_field.id)
+    .await
+    .unwrap();
+  assert!(manager.cancel_ai_column_fill(&summary_field.id));
+  assert!(task.token.is_cancelled());
+  assert!(!manager.cancel_ai_column_fill(&summary_field.id));
+}
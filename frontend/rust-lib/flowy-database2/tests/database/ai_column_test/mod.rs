@@ -0,0 +1 @@
+mod ai_column_test;
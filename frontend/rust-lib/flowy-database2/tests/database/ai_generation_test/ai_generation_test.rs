@@ -0,0 +1,101 @@
+use collab_database::database::gen_database_view_id;
+use collab_database::views::DatabaseLayout;
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::ai::generate_database_params;
+use flowy_database2::services::cell::stringify_cell;
+use flowy_database_pub::cloud::DatabaseAIService;
+use flowy_error::FlowyError;
+use lib_infra::async_trait::async_trait;
+
+struct MockDatabaseAIService(&'static str);
+
+#[async_trait]
+impl DatabaseAIService for MockDatabaseAIService {
+  async fn complete_database_prompt(
+    &self,
+    _workspace_id: &str,
+    _prompt: &str,
+  ) -> Result<String, FlowyError> {
+    Ok(self.0.to_string())
+  }
+}
+
+const PODCAST_RESPONSE: &str = r#"{"fields": [
+  {"name": "Episode", "type": "text"},
+  {"name": "Status", "type": "single_select", "options": ["Idea", "Recorded", "Published"]},
+  {"name": "Release", "type": "date", "date_format": "friendly"},
+  {"name": "Downloads", "type": "number", "format": "number"}
+],
+"rows": [
+  {"Episode": "Pilot", "Status": "Published", "Release": "2024-05-01", "Downloads": 1200},
+  {"Episode": "Interview", "Status": "Recorded", "Release": "2024-05-08"},
+  {"Episode": "Q&A", "Status": "Idea"}
+]}"#;
+
+#[tokio::test]
+async fn create_database_from_generated_params_test() {
+  let sdk = EventIntegrationTest::new().await;
+  let _ = sdk.init_anon_user().await;
+  let view_id = gen_database_view_id();
+  let params = generate_database_params(
+    &MockDatabaseAIService(PODCAST_RESPONSE),
+    "workspace",
+    &view_id,
+    "Podcast",
+    DatabaseLayout::Board,
+    "content calendar for a podcast",
+  )
+  .await
+  .unwrap();
+  sdk.database_manager.import_database(params).await.unwrap();
+
+  let editor = sdk
+    .database_manager
+    .get_database_editor_with_view_id(&view_id)
+    .await
+    .unwrap();
+  let fields = editor.get_fields(&view_id, None).await;
+  let field_types = fields
+    .iter()
+    .map(|field| FieldType::from(field.field_type))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    field_types,
+    vec![
+      FieldType::RichText,
+      FieldType::SingleSelect,
+      FieldType::DateTime,
+      FieldType::Number
+    ]
+  );
+  assert!(fields[0].is_primary);
+  assert_eq!(
+    editor.get_layout_type(&view_id).await,
+    DatabaseLayout::Board
+  );
+
+  let rows = editor.get_all_rows(&view_id).await.unwrap();
+  assert_eq!(rows.len(), 3);
+  let cell = rows[0].cells.get(&fields[0].id).unwrap();
+  assert_eq!(stringify_cell(cell, &fields[0]), "Pilot");
+  let cell = rows[0].cells.get(&fields[1].id).unwrap();
+  assert_eq!(stringify_cell(cell, &fields[1]), "Published");
+  assert!(rows[2].cells.get(&fields[2].id).is_none());
+}
+
+#[tokio::test]
+async fn invalid_generated_database_test() {
+  let response = r#"{"fields": [{"name": "Status", "type": "single_select", "options": ["Idea"]}],
+  "rows": [{"Status": "Done"}]}"#;
+  let result = generate_database_params(
+    &MockDatabaseAIService(response),
+    "workspace",
+    &gen_database_view_id(),
+    "Podcast",
+    DatabaseLayout::Grid,
+    "content calendar for a podcast",
+  )
+  .await;
+  assert!(result.unwrap_err().msg.contains("\"Done\""));
+}
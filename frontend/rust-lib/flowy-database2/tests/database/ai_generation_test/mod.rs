@@ -0,0 +1 @@
+mod ai_generation_test;
@@ -0,0 +1,225 @@
+use collab_database::database::gen_database_filter_id;
+use flowy_database2::entities::{
+  AutomationActionPB, AutomationActionTypePB, AutomationExecutionStatusPB, AutomationLogPB,
+  AutomationTriggerPB, AutomationTriggerTypePB, CheckboxFilterConditionPB, CheckboxFilterPB,
+  CreateAutomationRulePayloadPB, FieldType, FilterPB,
+};
+use flowy_database2::services::automation::AutomationLog;
+use flowy_database2::services::field::StringCellData;
+use flowy_database2::services::filter::{Filter, FilterInner};
+use lib_infra::box_any::BoxAny;
+use std::time::Duration;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+/// The automations run after the changes are observed. Wait until the logs of the rules are saved.
+async fn wait_for_automation_logs(test: &DatabaseEditorTest, count: usize) -> Vec<AutomationLog> {
+  for _ in 0..30 {
+    let logs = test.editor.get_automation_logs(10).await.unwrap();
+    if logs.len() >= count {
+      return logs;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+  }
+  test.editor.get_automation_logs(10).await.unwrap()
+}
+
+#[tokio::test]
+async fn automation_runs_when_row_becomes_matched_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let checkbox_field = test.get_first_field(FieldType::Checkbox).await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let row_id = test.rows[0].id.clone();
+
+  let condition = FilterPB::from(&Filter {
+    id: gen_database_filter_id(),
+    inner: FilterInner::Data {
+      field_id: checkbox_field.id.clone(),
+      field_type: FieldType::Checkbox,
+      condition_and_content: BoxAny::new(CheckboxFilterPB {
+        condition: CheckboxFilterConditionPB::IsChecked,
+      }),
+    },
+  });
+  test
+    .editor
+    .create_automation_rule(CreateAutomationRulePayloadPB {
+      view_id: test.view_id.clone(),
+      name: "Mark as done".to_string(),
+      trigger: AutomationTriggerPB {
+        trigger_type: AutomationTriggerTypePB::CellChanged,
+        field_id: Some(checkbox_field.id.clone()),
+      },
+      condition: Some(condition),
+      actions: vec![AutomationActionPB {
+        action_type: AutomationActionTypePB::SetCellContent,
+        field_id: text_field.id.clone(),
+        content: "Done".to_string(),
+        ..Default::default()
+      }],
+    })
+    .await
+    .unwrap();
+
+  // Unchecking the checkbox doesn't match the condition
+  test
+    .update_cell(
+      &checkbox_field.id,
+      row_id.clone(),
+      BoxAny::new("No".to_string()),
+    )
+    .await
+    .unwrap();
+  assert!(wait_for_automation_logs(&test, 1).await.is_empty());
+  let cell = test.editor.get_cell(&text_field.id, &row_id).await.unwrap();
+  assert_ne!(StringCellData::from(&cell).as_str(), "Done");
+
+  test
+    .update_cell(
+      &checkbox_field.id,
+      row_id.clone(),
+      BoxAny::new("Yes".to_string()),
+    )
+    .await
+    .unwrap();
+  let logs = wait_for_automation_logs(&test, 1).await;
+  let cell = test.editor.get_cell(&text_field.id, &row_id).await.unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "Done");
+  assert_eq!(logs.len(), 1);
+  assert_eq!(
+    AutomationLogPB::from(logs[0].clone()).status,
+    AutomationExecutionStatusPB::Succeeded
+  );
+}
+
+#[tokio::test]
+async fn automation_loop_protection_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let row_id = test.rows[0].id.clone();
+
+  // The rule updates the cell that triggers itself
+  test
+    .editor
+    .create_automation_rule(CreateAutomationRulePayloadPB {
+      view_id: test.view_id.clone(),
+      name: "Loop".to_string(),
+      trigger: AutomationTriggerPB {
+        trigger_type: AutomationTriggerTypePB::CellChanged,
+        field_id: Some(text_field.id.clone()),
+      },
+      condition: None,
+      actions: vec![AutomationActionPB {
+        action_type: AutomationActionTypePB::SetCellContent,
+        field_id: text_field.id.clone(),
+        content: "loop".to_string(),
+        ..Default::default()
+      }],
+    })
+    .await
+    .unwrap();
+
+  test
+    .update_cell(
+      &text_field.id,
+      row_id.clone(),
+      BoxAny::new("hello".to_string()),
+    )
+    .await
+    .unwrap();
+  let logs = wait_for_automation_logs(&test, 2).await;
+  let cell = test.editor.get_cell(&text_field.id, &row_id).await.unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "loop");
+
+  let statuses = logs
+    .into_iter()
+    .map(|log: AutomationLog| AutomationLogPB::from(log).status)
+    .collect::<Vec<_>>();
+  assert_eq!(statuses.len(), 2);
+  assert!(statuses.contains(&AutomationExecutionStatusPB::Succeeded));
+  assert!(statuses.contains(&AutomationExecutionStatusPB::SkippedByLoopProtection));
+}
+
+#[tokio::test]
+async fn automation_concurrent_user_changes_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let checkbox_field = test.get_first_field(FieldType::Checkbox).await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let row_id = test.rows[0].id.clone();
+
+  test
+    .editor
+    .create_automation_rule(CreateAutomationRulePayloadPB {
+      view_id: test.view_id.clone(),
+      name: "Check".to_string(),
+      trigger: AutomationTriggerPB {
+        trigger_type: AutomationTriggerTypePB::CellChanged,
+        field_id: Some(text_field.id.clone()),
+      },
+      condition: None,
+      actions: vec![AutomationActionPB {
+        action_type: AutomationActionTypePB::SetCheckbox,
+        field_id: checkbox_field.id.clone(),
+        is_checked: true,
+        ..Default::default()
+      }],
+    })
+    .await
+    .unwrap();
+
+  // Each change of the user starts its own chain, even if the chain of the other change is still
+  // running on the same row.
+  let (first, second) = tokio::join!(
+    test.update_cell(
+      &text_field.id,
+      row_id.clone(),
+      BoxAny::new("first".to_string()),
+    ),
+    test.update_cell(
+      &text_field.id,
+      row_id.clone(),
+      BoxAny::new("second".to_string()),
+    )
+  );
+  first.unwrap();
+  second.unwrap();
+
+  let statuses = wait_for_automation_logs(&test, 2)
+    .await
+    .into_iter()
+    .map(|log: AutomationLog| AutomationLogPB::from(log).status)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    statuses,
+    vec![
+      AutomationExecutionStatusPB::Succeeded,
+      AutomationExecutionStatusPB::Succeeded
+    ]
+  );
+}
+
+#[tokio::test]
+async fn automation_action_with_invalid_field_type_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+
+  let result = test
+    .editor
+    .create_automation_rule(CreateAutomationRulePayloadPB {
+      view_id: test.view_id.clone(),
+      name: "Invalid".to_string(),
+      trigger: AutomationTriggerPB {
+        trigger_type: AutomationTriggerTypePB::RowCreated,
+        field_id: None,
+      },
+      condition: None,
+      actions: vec![AutomationActionPB {
+        action_type: AutomationActionTypePB::SetDateToNow,
+        field_id: text_field.id.clone(),
+        ..Default::default()
+      }],
+    })
+    .await;
+  assert!(result.is_err());
+  assert!(test.editor.get_automation_rules().await.unwrap().is_empty());
+}
@@ -0,0 +1 @@
+mod automation_test;
@@ -1,3 +1,6 @@
+mod ai_column_test;
+mod ai_generation_test;
+mod automation_test;
 mod block_test;
 mod calculations_test;
 mod cell_test;
@@ -9,5 +12,6 @@ mod group_test;
 mod layout_test;
 mod mock_data;
 mod pre_fill_cell_test;
+mod row_template_test;
 mod share_test;
 mod sort_test;
@@ -0,0 +1 @@
+mod row_template_test;
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use flowy_database2::entities::{
+  CreateRowFromTemplatePayloadPB, CreateRowPayloadPB, CreateRowTemplatePayloadPB, FieldType,
+};
+use flowy_database2::services::field::{NumberCellData, StringCellData};
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn create_row_from_template_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let number_field = test.get_first_field(FieldType::Number).await;
+
+  let template = test
+    .editor
+    .create_row_template(CreateRowTemplatePayloadPB {
+      view_id: test.view_id.clone(),
+      name: "Bug report".to_string(),
+      cells: HashMap::from([
+        (text_field.id.clone(), "New bug".to_string()),
+        (number_field.id.clone(), "1".to_string()),
+      ]),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+  // The cells passed by the caller override the preset cells of the template
+  let row_detail = test
+    .editor
+    .create_row_from_template(CreateRowFromTemplatePayloadPB {
+      view_id: test.view_id.clone(),
+      template_id: template.id.clone(),
+      data: HashMap::from([(number_field.id.clone(), "2".to_string())]),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+  let text_cell = test
+    .editor
+    .get_cell(&text_field.id, &row_detail.row.id)
+    .await
+    .unwrap();
+  assert_eq!(StringCellData::from(&text_cell).as_str(), "New bug");
+  let number_cell = test
+    .editor
+    .get_cell(&number_field.id, &row_detail.row.id)
+    .await
+    .unwrap();
+  assert_eq!(NumberCellData::from(&number_cell).0, "2");
+}
+
+#[tokio::test]
+async fn create_row_with_default_template_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+
+  let template = test
+    .editor
+    .create_row_template(CreateRowTemplatePayloadPB {
+      view_id: test.view_id.clone(),
+      name: "Task".to_string(),
+      cells: HashMap::from([(text_field.id.clone(), "Untitled task".to_string())]),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  test
+    .editor
+    .set_default_row_template(&test.view_id, Some(template.id.clone()))
+    .await
+    .unwrap();
+
+  let templates = test.editor.get_row_templates(&test.view_id).await.unwrap();
+  assert_eq!(templates.items.len(), 1);
+  assert_eq!(templates.default_template_id, Some(template.id.clone()));
+
+  let row_detail = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+  let cell = test
+    .editor
+    .get_cell(&text_field.id, &row_detail.row.id)
+    .await
+    .unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "Untitled task");
+
+  // Deleting the template also removes it from the view
+  test.editor.delete_row_template(&template.id).await.unwrap();
+  let templates = test.editor.get_row_templates(&test.view_id).await.unwrap();
+  assert!(templates.items.is_empty());
+  assert!(templates.default_template_id.is_none());
+}
+
+#[tokio::test]
+async fn create_row_from_template_with_document_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let json = r#"{"type":"page","children":[{"type":"paragraph","data":{"delta":[{"insert":"Steps to reproduce"}]}}]}"#;
+  let template = test
+    .editor
+    .create_row_template(CreateRowTemplatePayloadPB {
+      view_id: test.view_id.clone(),
+      name: "Bug report".to_string(),
+      document_json: Some(json.to_string()),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+  let row_detail = test
+    .editor
+    .create_row_from_template(CreateRowFromTemplatePayloadPB {
+      view_id: test.view_id.clone(),
+      template_id: template.id,
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+  assert!(!row_detail.meta.is_document_empty);
+
+  let document = test.sdk.get_document_data(&row_detail.document_id).await;
+  assert!(document.blocks.len() > 1);
+}
+
+#[tokio::test]
+async fn create_row_template_with_invalid_field_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let result = test
+    .editor
+    .create_row_template(CreateRowTemplatePayloadPB {
+      view_id: test.view_id.clone(),
+      name: "Invalid".to_string(),
+      cells: HashMap::from([("not exist".to_string(), "hello".to_string())]),
+      ..Default::default()
+    })
+    .await;
+  assert!(result.is_err());
+}
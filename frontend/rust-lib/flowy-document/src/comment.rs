@@ -0,0 +1,259 @@
+use collab::preclude::encoding::serde::{from_any, to_any};
+use collab::preclude::updates::decoder::Decode;
+use collab::preclude::updates::encoder::Encode;
+use collab::preclude::{
+  Any, Assoc, Collab, IndexedSequence, Map, MapRef, Out, ReadTxn, StickyIndex, TextRef,
+  TransactionMut, WriteTxn,
+};
+use collab_document::blocks::DocumentData;
+use flowy_error::{internal_error, FlowyError, FlowyResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::parser::utils::get_delta_for_block;
+
+/// The threads and the comments are stored in the document collab, so they sync with the document.
+/// A comment is stored under its own key, so the replies written by different users are merged.
+///
+/// The maps are the root-level shared types of the collab. The root-level types with the same name
+/// are the same type on every device, while the nested maps that are created on two devices at the
+/// same time replace each other. The earlier versions stored the maps in `collab.data`, which are
+/// still read.
+const COMMENT_THREADS: &str = "comment_threads";
+const COMMENTS: &str = "comments";
+
+/// The path of the texts of the blocks in the document collab.
+const DOCUMENT_ROOT: &str = "document";
+const DOCUMENT_META: &str = "meta";
+const TEXT_MAP: &str = "text_map";
+
+/// A discussion anchored to a range of the text of a block.
+///
+/// The range is measured in UTF-16 code units, the same as the text deltas sent by the client. The
+/// range is stored as the sticky indexes of the text, so it follows the local and the remote edits
+/// of the text. `start` and `length` are resolved from them when the threads are read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentThread {
+  pub id: String,
+  pub block_id: String,
+  pub start: u32,
+  /// The length is zero when the anchored text has been deleted.
+  pub length: u32,
+  /// The encoded sticky index of the start of the range. The text inserted at the start stays out
+  /// of the range.
+  #[serde(default)]
+  pub start_anchor: Option<Vec<u8>>,
+  /// The encoded sticky index of the end of the range. The text inserted at the end stays out of
+  /// the range.
+  #[serde(default)]
+  pub end_anchor: Option<Vec<u8>>,
+  /// The anchored text when the thread was created.
+  pub anchor_text: String,
+  pub created_by: i64,
+  pub created_at: i64,
+  #[serde(default)]
+  pub resolved_by: Option<i64>,
+  #[serde(default)]
+  pub resolved_at: Option<i64>,
+}
+
+impl CommentThread {
+  pub fn is_resolved(&self) -> bool {
+    self.resolved_at.is_some()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+  pub id: String,
+  pub thread_id: String,
+  pub author: i64,
+  pub content: String,
+  pub created_at: i64,
+}
+
+/// Returns the threads of the document with their comments, ordered by the creation time.
+pub fn get_comment_threads(collab: &Collab) -> Vec<(CommentThread, Vec<Comment>)> {
+  let txn = collab.transact();
+  let mut threads = read_values::<CommentThread, _>(&collab.data, &txn, COMMENT_THREADS);
+  let comments = read_values::<Comment, _>(&collab.data, &txn, COMMENTS);
+
+  threads
+    .iter_mut()
+    .for_each(|thread| resolve_anchor(thread, &txn));
+  threads.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+  threads
+    .into_iter()
+    .map(|thread| {
+      let mut thread_comments = comments
+        .iter()
+        .filter(|comment| comment.thread_id == thread.id)
+        .cloned()
+        .collect::<Vec<_>>();
+      thread_comments.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+      (thread, thread_comments)
+    })
+    .collect()
+}
+
+pub fn get_comment_thread(collab: &Collab, thread_id: &str) -> Option<CommentThread> {
+  let txn = collab.transact();
+  let mut thread = read_value::<CommentThread, _>(&collab.data, &txn, COMMENT_THREADS, thread_id)?;
+  resolve_anchor(&mut thread, &txn);
+  Some(thread)
+}
+
+/// Insert the thread with its first comment. The range of the thread is anchored to the text of
+/// the block.
+pub fn insert_comment_thread(
+  collab: &mut Collab,
+  text_id: &str,
+  thread: &CommentThread,
+  comment: &Comment,
+) -> FlowyResult<()> {
+  let data = collab.data.clone();
+  let mut txn = collab.transact_mut();
+  let text = get_text(&data, &txn, text_id).ok_or_else(|| {
+    FlowyError::record_not_found().with_context(format!("Text {} not found", text_id))
+  })?;
+  let mut thread = thread.clone();
+  thread.start_anchor = text
+    .sticky_index(&mut txn, thread.start, Assoc::After)
+    .map(|index| index.encode_v1());
+  thread.end_anchor = text
+    .sticky_index(&mut txn, thread.start + thread.length, Assoc::Before)
+    .map(|index| index.encode_v1());
+  write_value(&mut txn, COMMENT_THREADS, &thread.id, &thread)?;
+  write_value(&mut txn, COMMENTS, &comment.id, comment)?;
+  Ok(())
+}
+
+pub fn insert_comment(collab: &mut Collab, comment: &Comment) -> FlowyResult<()> {
+  if get_comment_thread(collab, &comment.thread_id).is_none() {
+    return Err(
+      FlowyError::record_not_found()
+        .with_context(format!("Comment thread {} not found", comment.thread_id)),
+    );
+  }
+  let mut txn = collab.transact_mut();
+  write_value(&mut txn, COMMENTS, &comment.id, comment)
+}
+
+pub fn update_comment_thread<F>(
+  collab: &mut Collab,
+  thread_id: &str,
+  f: F,
+) -> FlowyResult<CommentThread>
+where
+  F: FnOnce(&mut CommentThread),
+{
+  let mut thread = get_comment_thread(collab, thread_id).ok_or_else(|| {
+    FlowyError::record_not_found().with_context(format!("Comment thread {} not found", thread_id))
+  })?;
+  f(&mut thread);
+  let mut txn = collab.transact_mut();
+  write_value(&mut txn, COMMENT_THREADS, thread_id, &thread)?;
+  Ok(thread)
+}
+
+/// Returns the text of the range in the block, or None if the range is out of the text.
+pub fn get_anchor_text(
+  data: &DocumentData,
+  block_id: &str,
+  start: u32,
+  length: u32,
+) -> Option<String> {
+  let text = get_delta_for_block(block_id, data)?
+    .into_iter()
+    .map(|delta| delta.insert)
+    .collect::<String>()
+    .encode_utf16()
+    .collect::<Vec<_>>();
+  let end = start.checked_add(length)? as usize;
+  if length == 0 || end > text.len() {
+    return None;
+  }
+  Some(String::from_utf16_lossy(&text[start as usize..end]))
+}
+
+/// Resolve the range of the thread from its sticky indexes. The range of a thread without the
+/// indexes is kept.
+fn resolve_anchor<T: ReadTxn>(thread: &mut CommentThread, txn: &T) {
+  let offset = |anchor: &Option<Vec<u8>>| {
+    let index = StickyIndex::decode_v1(anchor.as_ref()?).ok()?;
+    Some(index.get_offset(txn)?.index)
+  };
+  if let (Some(start), Some(end)) = (offset(&thread.start_anchor), offset(&thread.end_anchor)) {
+    thread.start = start;
+    thread.length = end.saturating_sub(start);
+  }
+}
+
+fn get_text<T: ReadTxn>(data: &MapRef, txn: &T, text_id: &str) -> Option<TextRef> {
+  let meta = get_map(&get_map(data, txn, DOCUMENT_ROOT)?, txn, DOCUMENT_META)?;
+  match get_map(&meta, txn, TEXT_MAP)?.get(txn, text_id) {
+    Some(Out::YText(text)) => Some(text),
+    _ => None,
+  }
+}
+
+fn get_map<T: ReadTxn>(data: &MapRef, txn: &T, key: &str) -> Option<MapRef> {
+  match data.get(txn, key) {
+    Some(Out::YMap(map)) => Some(map),
+    _ => None,
+  }
+}
+
+/// Returns the values of the root-level map, and the values of the map of `collab.data` written by
+/// the earlier versions. The values of the root-level map win.
+fn read_entries<T: ReadTxn>(data: &MapRef, txn: &T, map_key: &str) -> HashMap<String, Any> {
+  let mut entries = HashMap::new();
+  for map in [get_map(data, txn, map_key), txn.get_map(map_key)]
+    .into_iter()
+    .flatten()
+  {
+    for (key, value) in map.iter(txn) {
+      if let Out::Any(any) = value {
+        entries.insert(key.to_string(), any);
+      }
+    }
+  }
+  entries
+}
+
+fn read_value<V, T>(data: &MapRef, txn: &T, map_key: &str, key: &str) -> Option<V>
+where
+  V: for<'de> Deserialize<'de>,
+  T: ReadTxn,
+{
+  [txn.get_map(map_key), get_map(data, txn, map_key)]
+    .into_iter()
+    .flatten()
+    .find_map(|map| match map.get(txn, key) {
+      Some(Out::Any(any)) => from_any(&any).ok(),
+      _ => None,
+    })
+}
+
+fn read_values<V, T>(data: &MapRef, txn: &T, map_key: &str) -> Vec<V>
+where
+  V: for<'de> Deserialize<'de>,
+  T: ReadTxn,
+{
+  read_entries(data, txn, map_key)
+    .into_values()
+    .filter_map(|any| from_any(&any).ok())
+    .collect()
+}
+
+fn write_value<V: Serialize>(
+  txn: &mut TransactionMut,
+  map_key: &str,
+  key: &str,
+  value: &V,
+) -> FlowyResult<()> {
+  let any = to_any(value).map_err(internal_error)?;
+  let map = txn.get_or_insert_map(map_key);
+  map.insert(txn, key, any);
+  Ok(())
+}
@@ -1,12 +1,24 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
 use crate::entities::{
-  DocEventPB, DocumentAwarenessStatesPB, DocumentSnapshotStatePB, DocumentSyncStatePB,
+  DocEventPB, DocumentAwarenessStatesPB, DocumentOutlinePB, DocumentSnapshotStatePB,
+  DocumentStatisticsPB, DocumentSyncStatePB,
 };
 use crate::notification::{send_notification, DocumentNotification};
+use crate::parser::document_data_parser::DocumentDataParser;
+use collab::lock::RwLock;
 use collab::preclude::Collab;
+use collab_document::blocks::DocumentData;
 use collab_document::document::Document;
+use dashmap::DashSet;
 use futures::StreamExt;
 use lib_dispatch::prelude::af_spawn;
 
+/// The outline and the statistics of a subscribed document are sent after the changes settle.
+const STATISTICS_NOTIFY_DELAY: Duration = Duration::from_millis(500);
+
 pub fn subscribe_document_changed(doc_id: &str, document: &mut Document) {
   let doc_id_clone_for_block_changed = doc_id.to_owned();
   document.subscribe_block_changed("key", move |events, is_remote| {
@@ -67,3 +79,48 @@ pub fn subscribe_document_sync_state(collab: &Collab) {
     }
   });
 }
+
+/// Send the outline and the statistics of the document when its blocks change, if the document is
+/// in the subscriptions.
+pub fn subscribe_document_statistics(
+  doc_id: &str,
+  document: &mut Document,
+  weak_document: Weak<RwLock<Document>>,
+  subscriptions: Weak<DashSet<String>>,
+) {
+  let doc_id = doc_id.to_owned();
+  let is_pending = Arc::new(AtomicBool::new(false));
+  document.subscribe_block_changed("statistics", move |_events, _is_remote| {
+    let is_subscribed = subscriptions
+      .upgrade()
+      .map_or(false, |subscriptions| subscriptions.contains(&doc_id));
+    if !is_subscribed || is_pending.swap(true, Ordering::SeqCst) {
+      return;
+    }
+
+    let doc_id = doc_id.clone();
+    let weak_document = weak_document.clone();
+    let is_pending = is_pending.clone();
+    af_spawn(async move {
+      tokio::time::sleep(STATISTICS_NOTIFY_DELAY).await;
+      is_pending.store(false, Ordering::SeqCst);
+      if let Some(document) = weak_document.upgrade() {
+        let result = document.read().await.get_document_data();
+        match result {
+          Ok(data) => send_document_statistics(&doc_id, data),
+          Err(err) => tracing::error!("Failed to read the document {}: {}", doc_id, err),
+        }
+      }
+    });
+  });
+}
+
+pub fn send_document_statistics(doc_id: &str, data: DocumentData) {
+  let parser = DocumentDataParser::new(Arc::new(data), None);
+  send_notification(doc_id, DocumentNotification::DidUpdateDocumentOutline)
+    .payload(DocumentOutlinePB::from(parser.to_outline()))
+    .send();
+  send_notification(doc_id, DocumentNotification::DidUpdateDocumentStatistics)
+    .payload(DocumentStatisticsPB::from(parser.to_statistics()))
+    .send();
+}
@@ -14,7 +14,12 @@ use flowy_error::ErrorCode;
 use lib_infra::validator_fn::{required_not_empty_str, required_valid_path};
 use validator::Validate;
 
+use crate::comment::{Comment, CommentThread};
+use crate::find_replace::{FindOptions, FindScope, ReplaceMatch, TextMatch};
 use crate::parse::{NotEmptyStr, NotEmptyVec};
+use crate::parser::document_outline::{DocumentStatistics, OutlineHeading};
+use crate::parser::parser_entities::RangePB;
+use crate::task::TaskQuery;
 
 #[derive(Default, ProtoBuf)]
 pub struct EncodedCollabPB {
@@ -669,3 +674,628 @@ impl From<DocumentAwarenessPosition> for DocumentAwarenessPositionPB {
     }
   }
 }
+
+#[derive(Default, ProtoBuf, Validate)]
+pub struct ViewLinksPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum)]
+pub enum LinkNodeStatePB {
+  #[default]
+  Normal = 0,
+  /// The view is in the trash
+  Trashed = 1,
+  /// The view has been deleted. The links to a deleted view are broken.
+  Deleted = 2,
+}
+
+/// A document or a view in the link graph.
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct LinkNodePB {
+  /// The id of the document or the view
+  #[pb(index = 1)]
+  pub id: String,
+
+  /// The id of the view that shows the node. For the row documents, it's the id of the database
+  /// view that contains the row.
+  #[pb(index = 2)]
+  pub view_id: String,
+
+  #[pb(index = 3, one_of)]
+  pub row_id: Option<String>,
+
+  #[pb(index = 4)]
+  pub name: String,
+
+  #[pb(index = 5)]
+  pub state: LinkNodeStatePB,
+}
+
+/// A page mention in a block of the source document.
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct DocumentLinkPB {
+  #[pb(index = 1)]
+  pub source: LinkNodePB,
+
+  #[pb(index = 2)]
+  pub block_id: String,
+
+  #[pb(index = 3)]
+  pub target: LinkNodePB,
+}
+
+impl DocumentLinkPB {
+  pub fn is_broken(&self) -> bool {
+    self.target.state == LinkNodeStatePB::Deleted
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct RepeatedDocumentLinkPB {
+  #[pb(index = 1)]
+  pub items: Vec<DocumentLinkPB>,
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct LinkEdgePB {
+  #[pb(index = 1)]
+  pub source_id: String,
+
+  #[pb(index = 2)]
+  pub target_id: String,
+
+  /// The number of blocks of the source that mention the target
+  #[pb(index = 3)]
+  pub mention_count: i32,
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct DocumentLinkGraphPB {
+  #[pb(index = 1)]
+  pub nodes: Vec<LinkNodePB>,
+
+  #[pb(index = 2)]
+  pub edges: Vec<LinkEdgePB>,
+}
+
+#[derive(Default, ProtoBuf, Validate)]
+pub struct CreateCommentThreadPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub document_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub block_id: String,
+
+  // The range of the anchored text in the block, in UTF-16 code units
+  #[pb(index = 3)]
+  pub start: u32,
+
+  #[pb(index = 4)]
+  pub length: u32,
+
+  #[pb(index = 5)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub content: String,
+}
+
+#[derive(Default, ProtoBuf, Validate)]
+pub struct ReplyCommentPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub document_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub thread_id: String,
+
+  #[pb(index = 3)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub content: String,
+}
+
+#[derive(Default, ProtoBuf, Validate)]
+pub struct ResolveCommentThreadPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub document_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub thread_id: String,
+
+  // false to reopen the thread
+  #[pb(index = 3)]
+  pub resolved: bool,
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct CommentPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub thread_id: String,
+
+  #[pb(index = 3)]
+  pub author: i64,
+
+  #[pb(index = 4)]
+  pub content: String,
+
+  #[pb(index = 5)]
+  pub created_at: i64,
+}
+
+impl From<Comment> for CommentPB {
+  fn from(comment: Comment) -> Self {
+    Self {
+      id: comment.id,
+      thread_id: comment.thread_id,
+      author: comment.author,
+      content: comment.content,
+      created_at: comment.created_at,
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct CommentThreadPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub block_id: String,
+
+  #[pb(index = 3)]
+  pub start: u32,
+
+  // zero when the anchored text has been deleted
+  #[pb(index = 4)]
+  pub length: u32,
+
+  #[pb(index = 5)]
+  pub anchor_text: String,
+
+  #[pb(index = 6)]
+  pub created_by: i64,
+
+  #[pb(index = 7)]
+  pub created_at: i64,
+
+  #[pb(index = 8)]
+  pub is_resolved: bool,
+
+  #[pb(index = 9, one_of)]
+  pub resolved_by: Option<i64>,
+
+  #[pb(index = 10, one_of)]
+  pub resolved_at: Option<i64>,
+
+  #[pb(index = 11)]
+  pub comments: Vec<CommentPB>,
+}
+
+impl From<(CommentThread, Vec<Comment>)> for CommentThreadPB {
+  fn from((thread, comments): (CommentThread, Vec<Comment>)) -> Self {
+    Self {
+      is_resolved: thread.is_resolved(),
+      id: thread.id,
+      block_id: thread.block_id,
+      start: thread.start,
+      length: thread.length,
+      anchor_text: thread.anchor_text,
+      created_by: thread.created_by,
+      created_at: thread.created_at,
+      resolved_by: thread.resolved_by,
+      resolved_at: thread.resolved_at,
+      comments: comments.into_iter().map(CommentPB::from).collect(),
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct RepeatedCommentThreadPB {
+  #[pb(index = 1)]
+  pub items: Vec<CommentThreadPB>,
+}
+
+#[derive(Default, ProtoBuf, Validate)]
+pub struct DocumentRangePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub document_id: String,
+
+  // if range is None, the whole document is used
+  #[pb(index = 2, one_of)]
+  pub range: Option<RangePB>,
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct DocumentHeadingPB {
+  #[pb(index = 1)]
+  pub block_id: String,
+
+  #[pb(index = 2)]
+  pub level: u32,
+
+  #[pb(index = 3)]
+  pub text: String,
+
+  #[pb(index = 4)]
+  pub children: Vec<DocumentHeadingPB>,
+}
+
+impl From<OutlineHeading> for DocumentHeadingPB {
+  fn from(heading: OutlineHeading) -> Self {
+    Self {
+      block_id: heading.block_id,
+      level: heading.level,
+      text: heading.text,
+      children: heading.children.into_iter().map(Self::from).collect(),
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct DocumentOutlinePB {
+  #[pb(index = 1)]
+  pub items: Vec<DocumentHeadingPB>,
+}
+
+impl From<Vec<OutlineHeading>> for DocumentOutlinePB {
+  fn from(headings: Vec<OutlineHeading>) -> Self {
+    Self {
+      items: headings.into_iter().map(DocumentHeadingPB::from).collect(),
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct BlockTypeCountPB {
+  #[pb(index = 1)]
+  pub ty: String,
+
+  #[pb(index = 2)]
+  pub count: u64,
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct DocumentStatisticsPB {
+  // a CJK character counts as one word
+  #[pb(index = 1)]
+  pub words: u64,
+
+  // excluding the whitespaces
+  #[pb(index = 2)]
+  pub characters: u64,
+
+  #[pb(index = 3)]
+  pub characters_with_spaces: u64,
+
+  #[pb(index = 4)]
+  pub cjk_characters: u64,
+
+  #[pb(index = 5)]
+  pub reading_time_minutes: u64,
+
+  #[pb(index = 6)]
+  pub block_counts: Vec<BlockTypeCountPB>,
+
+  #[pb(index = 7)]
+  pub open_todos: u64,
+
+  #[pb(index = 8)]
+  pub checked_todos: u64,
+}
+
+impl From<DocumentStatistics> for DocumentStatisticsPB {
+  fn from(statistics: DocumentStatistics) -> Self {
+    Self {
+      words: statistics.words,
+      characters: statistics.characters,
+      characters_with_spaces: statistics.characters_with_spaces,
+      cjk_characters: statistics.cjk_characters,
+      reading_time_minutes: statistics.reading_time_minutes,
+      block_counts: statistics
+        .block_counts
+        .into_iter()
+        .map(|(ty, count)| BlockTypeCountPB { ty, count })
+        .collect(),
+      open_todos: statistics.open_todos,
+      checked_todos: statistics.checked_todos,
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Validate)]
+pub struct DocumentStatisticsSubscriptionPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub document_id: String,
+
+  // false to stop receiving the updates
+  #[pb(index = 2)]
+  pub subscribe: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum)]
+pub enum FindScopePB {
+  #[default]
+  Document = 0,
+  /// The view and all its descendant views
+  View = 1,
+  Workspace = 2,
+}
+
+#[derive(Default, ProtoBuf, Validate)]
+pub struct FindReplacePayloadPB {
+  #[pb(index = 1)]
+  pub scope: FindScopePB,
+
+  // the id of the document or the view. Ignored for the workspace scope
+  #[pb(index = 2)]
+  pub scope_id: String,
+
+  #[pb(index = 3)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub query: String,
+
+  #[pb(index = 4)]
+  pub is_regex: bool,
+
+  #[pb(index = 5)]
+  pub case_sensitive: bool,
+
+  #[pb(index = 6)]
+  pub whole_word: bool,
+
+  // the capture groups, like $1, are expanded when the query is a regex
+  #[pb(index = 7, one_of)]
+  pub replacement: Option<String>,
+}
+
+pub struct FindReplaceParams {
+  pub scope: FindScope,
+  pub options: FindOptions,
+  pub replacement: Option<String>,
+}
+
+impl TryInto<FindReplaceParams> for FindReplacePayloadPB {
+  type Error = ErrorCode;
+  fn try_into(self) -> Result<FindReplaceParams, Self::Error> {
+    let scope = match self.scope {
+      FindScopePB::Workspace => FindScope::Workspace,
+      FindScopePB::Document => FindScope::Document(
+        NotEmptyStr::parse(self.scope_id)
+          .map_err(|_| ErrorCode::DocumentIdIsEmpty)?
+          .0,
+      ),
+      FindScopePB::View => FindScope::View(
+        NotEmptyStr::parse(self.scope_id)
+          .map_err(|_| ErrorCode::ViewIdIsInvalid)?
+          .0,
+      ),
+    };
+    Ok(FindReplaceParams {
+      scope,
+      options: FindOptions {
+        query: self.query,
+        is_regex: self.is_regex,
+        case_sensitive: self.case_sensitive,
+        whole_word: self.whole_word,
+      },
+      replacement: self.replacement,
+    })
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct TextMatchPB {
+  #[pb(index = 1)]
+  pub block_id: String,
+
+  // the range of the match, in UTF-16 code units
+  #[pb(index = 2)]
+  pub start: u32,
+
+  #[pb(index = 3)]
+  pub length: u32,
+
+  #[pb(index = 4)]
+  pub text: String,
+
+  // the text around the match
+  #[pb(index = 5)]
+  pub context: String,
+
+  // the text that replaces the match, if the replacement is provided
+  #[pb(index = 6, one_of)]
+  pub replacement: Option<String>,
+}
+
+impl From<TextMatch> for TextMatchPB {
+  fn from(text_match: TextMatch) -> Self {
+    Self {
+      block_id: text_match.block_id,
+      start: text_match.start,
+      length: text_match.length,
+      text: text_match.text,
+      context: text_match.context,
+      replacement: text_match.replacement,
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct DocumentTextMatchesPB {
+  #[pb(index = 1)]
+  pub document_id: String,
+
+  #[pb(index = 2)]
+  pub matches: Vec<TextMatchPB>,
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct RepeatedDocumentTextMatchesPB {
+  #[pb(index = 1)]
+  pub items: Vec<DocumentTextMatchesPB>,
+}
+
+/// The matches of the preview that are confirmed to be replaced. The replacement of each match is
+/// required.
+#[derive(Default, ProtoBuf)]
+pub struct ReplaceTextPayloadPB {
+  #[pb(index = 1)]
+  pub items: Vec<DocumentTextMatchesPB>,
+}
+
+impl TryInto<Vec<(String, Vec<ReplaceMatch>)>> for ReplaceTextPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<Vec<(String, Vec<ReplaceMatch>)>, Self::Error> {
+    self
+      .items
+      .into_iter()
+      .map(|item| {
+        let document_id =
+          NotEmptyStr::parse(item.document_id).map_err(|_| ErrorCode::DocumentIdIsEmpty)?;
+        let matches = item
+          .matches
+          .into_iter()
+          .map(|text_match| {
+            Ok(ReplaceMatch {
+              block_id: text_match.block_id,
+              start: text_match.start,
+              length: text_match.length,
+              text: text_match.text,
+              replacement: text_match.replacement.ok_or(ErrorCode::InvalidParams)?,
+            })
+          })
+          .collect::<Result<Vec<_>, ErrorCode>>()?;
+        Ok((document_id.0, matches))
+      })
+      .collect()
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct ReplaceTextResultPB {
+  #[pb(index = 1)]
+  pub document_id: String,
+
+  // the number of the replaced matches
+  #[pb(index = 2)]
+  pub count: u64,
+
+  // the error of the document, the other documents are replaced anyway
+  #[pb(index = 3, one_of)]
+  pub error: Option<String>,
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct RepeatedReplaceTextResultPB {
+  #[pb(index = 1)]
+  pub items: Vec<ReplaceTextResultPB>,
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct QueryTasksPayloadPB {
+  // returns the open tasks if it's false, and the done tasks if it's true
+  #[pb(index = 1, one_of)]
+  pub is_checked: Option<bool>,
+
+  // the tasks that are due at or after the timestamp, in seconds
+  #[pb(index = 2, one_of)]
+  pub due_after: Option<i64>,
+
+  // the tasks that are due before the timestamp, in seconds
+  #[pb(index = 3, one_of)]
+  pub due_before: Option<i64>,
+}
+
+impl From<QueryTasksPayloadPB> for TaskQuery {
+  fn from(payload: QueryTasksPayloadPB) -> Self {
+    Self {
+      is_checked: payload.is_checked,
+      due_after: payload.due_after,
+      due_before: payload.due_before,
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct TaskPersonPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+}
+
+/// A todo block of a document.
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct DocumentTaskPB {
+  #[pb(index = 1)]
+  pub document_id: String,
+
+  #[pb(index = 2)]
+  pub block_id: String,
+
+  // the view that shows the document. For a row document, it's the database view of the row
+  #[pb(index = 3)]
+  pub view_id: String,
+
+  #[pb(index = 4, one_of)]
+  pub row_id: Option<String>,
+
+  #[pb(index = 5)]
+  pub view_name: String,
+
+  // the text of the todo without the mentions
+  #[pb(index = 6)]
+  pub content: String,
+
+  #[pb(index = 7)]
+  pub is_checked: bool,
+
+  // the earliest date mentioned in the todo, in seconds
+  #[pb(index = 8, one_of)]
+  pub due_at: Option<i64>,
+
+  #[pb(index = 9, one_of)]
+  pub reminder_id: Option<String>,
+
+  #[pb(index = 10)]
+  pub people: Vec<TaskPersonPB>,
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct RepeatedDocumentTaskPB {
+  #[pb(index = 1)]
+  pub items: Vec<DocumentTaskPB>,
+}
+
+#[derive(Default, ProtoBuf, Validate)]
+pub struct UpdateTaskPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub document_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub block_id: String,
+
+  #[pb(index = 3)]
+  pub is_checked: bool,
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct DocumentTasksChangedPB {
+  #[pb(index = 1)]
+  pub document_id: String,
+}
@@ -16,12 +16,14 @@ use lib_dispatch::prelude::{data_result_ok, AFPluginData, AFPluginState, DataRes
 use tracing::instrument;
 
 use crate::entities::*;
+use crate::find_replace::ReplaceMatch;
 use crate::parser::document_data_parser::DocumentDataParser;
 use crate::parser::external::parser::ExternalDataToNestedJSONParser;
 use crate::parser::parser_entities::{
   ConvertDataToJsonParams, ConvertDataToJsonPayloadPB, ConvertDataToJsonResponsePB,
-  ConvertDocumentParams, ConvertDocumentPayloadPB, ConvertDocumentResponsePB,
+  ConvertDocumentParams, ConvertDocumentPayloadPB, ConvertDocumentResponsePB, Range,
 };
+use crate::task::TaskQuery;
 use crate::{manager::DocumentManager, parser::json::parser::JsonToDocumentParser};
 
 fn upgrade_document(
@@ -122,6 +124,7 @@ pub(crate) async fn apply_action_handler(
   let manager = upgrade_document(manager)?;
   let params: ApplyActionParams = data.into_inner().try_into()?;
   let doc_id = params.document_id;
+  manager.check_document_editable(&doc_id).await?;
   let document = manager.editable_document(&doc_id).await?;
   let actions = params.actions;
   if cfg!(feature = "verbose_log") {
@@ -139,6 +142,7 @@ pub(crate) async fn create_text_handler(
   let manager = upgrade_document(manager)?;
   let params: TextDeltaParams = data.into_inner().try_into()?;
   let doc_id = params.document_id;
+  manager.check_document_editable(&doc_id).await?;
   let document = manager.editable_document(&doc_id).await?;
   let mut document = document.write().await;
   document.apply_text_delta(&params.text_id, params.delta);
@@ -153,6 +157,7 @@ pub(crate) async fn apply_text_delta_handler(
   let manager = upgrade_document(manager)?;
   let params: TextDeltaParams = data.into_inner().try_into()?;
   let doc_id = params.document_id;
+  manager.check_document_editable(&doc_id).await?;
   let document = manager.editable_document(&doc_id).await?;
   let text_id = params.text_id;
   let delta = params.delta;
@@ -194,6 +199,7 @@ pub(crate) async fn redo_handler(
   let manager = upgrade_document(manager)?;
   let params: DocumentRedoUndoParams = data.into_inner().try_into()?;
   let doc_id = params.document_id;
+  manager.check_document_editable(&doc_id).await?;
   let document = manager.editable_document(&doc_id).await?;
   let mut document = document.write().await;
   let redo = document.redo();
@@ -213,6 +219,7 @@ pub(crate) async fn undo_handler(
   let manager = upgrade_document(manager)?;
   let params: DocumentRedoUndoParams = data.into_inner().try_into()?;
   let doc_id = params.document_id;
+  manager.check_document_editable(&doc_id).await?;
   let document = manager.editable_document(&doc_id).await?;
   let mut document = document.write().await;
   let undo = document.undo();
@@ -508,3 +515,188 @@ pub(crate) async fn set_awareness_local_state_handler(
     .await?;
   Ok(())
 }
+
+#[instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_backlinks_handler(
+  data: AFPluginData<ViewLinksPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<RepeatedDocumentLinkPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params = data.try_into_inner()?;
+  let items = manager.get_backlinks(&params.view_id).await?;
+  data_result_ok(RepeatedDocumentLinkPB { items })
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_outgoing_links_handler(
+  data: AFPluginData<ViewLinksPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<RepeatedDocumentLinkPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params = data.try_into_inner()?;
+  let items = manager.get_outgoing_links(&params.view_id).await?;
+  data_result_ok(RepeatedDocumentLinkPB { items })
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_link_graph_handler(
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<DocumentLinkGraphPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let graph = manager.get_link_graph().await?;
+  data_result_ok(graph)
+}
+
+pub(crate) async fn create_comment_thread_handler(
+  data: AFPluginData<CreateCommentThreadPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<CommentThreadPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params = data.try_into_inner()?;
+  let thread = manager
+    .create_comment_thread(
+      &params.document_id,
+      &params.block_id,
+      params.start,
+      params.length,
+      params.content,
+    )
+    .await?;
+  data_result_ok(thread)
+}
+
+pub(crate) async fn reply_comment_handler(
+  data: AFPluginData<ReplyCommentPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<CommentPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params = data.try_into_inner()?;
+  let comment = manager
+    .reply_comment(&params.document_id, &params.thread_id, params.content)
+    .await?;
+  data_result_ok(comment)
+}
+
+pub(crate) async fn resolve_comment_thread_handler(
+  data: AFPluginData<ResolveCommentThreadPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<CommentThreadPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params = data.try_into_inner()?;
+  let thread = manager
+    .resolve_comment_thread(&params.document_id, &params.thread_id, params.resolved)
+    .await?;
+  data_result_ok(thread)
+}
+
+pub(crate) async fn get_comment_threads_handler(
+  data: AFPluginData<OpenDocumentPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<RepeatedCommentThreadPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params: OpenDocumentParams = data.into_inner().try_into()?;
+  let items = manager.get_comment_threads(&params.document_id).await?;
+  data_result_ok(RepeatedCommentThreadPB { items })
+}
+
+pub(crate) async fn get_document_outline_handler(
+  data: AFPluginData<DocumentRangePayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<DocumentOutlinePB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params = data.try_into_inner()?;
+  let range = params.range.map(Range::from);
+  let outline = manager
+    .get_document_outline(&params.document_id, range)
+    .await?;
+  data_result_ok(outline)
+}
+
+pub(crate) async fn get_document_statistics_handler(
+  data: AFPluginData<DocumentRangePayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<DocumentStatisticsPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params = data.try_into_inner()?;
+  let range = params.range.map(Range::from);
+  let statistics = manager
+    .get_document_statistics(&params.document_id, range)
+    .await?;
+  data_result_ok(statistics)
+}
+
+pub(crate) async fn subscribe_document_statistics_handler(
+  data: AFPluginData<DocumentStatisticsSubscriptionPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_document(manager)?;
+  let params = data.try_into_inner()?;
+  manager
+    .subscribe_document_statistics(&params.document_id, params.subscribe)
+    .await
+}
+
+pub(crate) async fn find_text_handler(
+  data: AFPluginData<FindReplacePayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<RepeatedDocumentTextMatchesPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params: FindReplaceParams = data.try_into_inner()?.try_into()?;
+  let items = manager
+    .find_text(params.scope, &params.options, params.replacement.as_deref())
+    .await?
+    .into_iter()
+    .map(|(document_id, matches)| DocumentTextMatchesPB {
+      document_id,
+      matches: matches.into_iter().map(TextMatchPB::from).collect(),
+    })
+    .collect();
+  data_result_ok(RepeatedDocumentTextMatchesPB { items })
+}
+
+pub(crate) async fn replace_text_handler(
+  data: AFPluginData<ReplaceTextPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<RepeatedReplaceTextResultPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let matches: Vec<(String, Vec<ReplaceMatch>)> = data.into_inner().try_into()?;
+  let items = manager
+    .replace_text(matches)
+    .await
+    .into_iter()
+    .map(|(document_id, result)| match result {
+      Ok(count) => ReplaceTextResultPB {
+        document_id,
+        count: count as u64,
+        error: None,
+      },
+      Err(err) => ReplaceTextResultPB {
+        document_id,
+        count: 0,
+        error: Some(err.to_string()),
+      },
+    })
+    .collect();
+  data_result_ok(RepeatedReplaceTextResultPB { items })
+}
+
+pub(crate) async fn get_tasks_handler(
+  data: AFPluginData<QueryTasksPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<RepeatedDocumentTaskPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let query = TaskQuery::from(data.into_inner());
+  let items = manager.get_tasks(&query).await?;
+  data_result_ok(RepeatedDocumentTaskPB { items })
+}
+
+pub(crate) async fn update_task_handler(
+  data: AFPluginData<UpdateTaskPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_document(manager)?;
+  let params = data.try_into_inner()?;
+  manager
+    .set_task_checked(&params.document_id, &params.block_id, params.is_checked)
+    .await
+}
@@ -52,6 +52,38 @@ pub fn init(document_manager: Weak<DocumentManager>) -> AFPlugin {
       DocumentEvent::SetAwarenessState,
       set_awareness_local_state_handler,
     )
+    .event(DocumentEvent::GetBacklinks, get_backlinks_handler)
+    .event(DocumentEvent::GetOutgoingLinks, get_outgoing_links_handler)
+    .event(DocumentEvent::GetLinkGraph, get_link_graph_handler)
+    .event(
+      DocumentEvent::CreateCommentThread,
+      create_comment_thread_handler,
+    )
+    .event(DocumentEvent::ReplyComment, reply_comment_handler)
+    .event(
+      DocumentEvent::ResolveCommentThread,
+      resolve_comment_thread_handler,
+    )
+    .event(
+      DocumentEvent::GetCommentThreads,
+      get_comment_threads_handler,
+    )
+    .event(
+      DocumentEvent::GetDocumentOutline,
+      get_document_outline_handler,
+    )
+    .event(
+      DocumentEvent::GetDocumentStatistics,
+      get_document_statistics_handler,
+    )
+    .event(
+      DocumentEvent::SubscribeDocumentStatistics,
+      subscribe_document_statistics_handler,
+    )
+    .event(DocumentEvent::FindText, find_text_handler)
+    .event(DocumentEvent::ReplaceText, replace_text_handler)
+    .event(DocumentEvent::GetTasks, get_tasks_handler)
+    .event(DocumentEvent::UpdateTask, update_task_handler)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Display, ProtoBuf_Enum, Flowy_Event)]
@@ -137,4 +169,60 @@ pub enum DocumentEvent {
 
   #[event(input = "OpenDocumentPayloadPB", output = "DocumentTextPB")]
   GetDocumentText = 20,
+
+  // Returns the links to the view from the other documents
+  #[event(input = "ViewLinksPayloadPB", output = "RepeatedDocumentLinkPB")]
+  GetBacklinks = 21,
+
+  // Returns the links from the document of the view, including the broken links
+  #[event(input = "ViewLinksPayloadPB", output = "RepeatedDocumentLinkPB")]
+  GetOutgoingLinks = 22,
+
+  #[event(output = "DocumentLinkGraphPB")]
+  GetLinkGraph = 23,
+
+  #[event(input = "CreateCommentThreadPayloadPB", output = "CommentThreadPB")]
+  CreateCommentThread = 24,
+
+  #[event(input = "ReplyCommentPayloadPB", output = "CommentPB")]
+  ReplyComment = 25,
+
+  // Resolve or reopen the thread
+  #[event(input = "ResolveCommentThreadPayloadPB", output = "CommentThreadPB")]
+  ResolveCommentThread = 26,
+
+  #[event(input = "OpenDocumentPayloadPB", output = "RepeatedCommentThreadPB")]
+  GetCommentThreads = 27,
+
+  #[event(input = "DocumentRangePayloadPB", output = "DocumentOutlinePB")]
+  GetDocumentOutline = 28,
+
+  #[event(input = "DocumentRangePayloadPB", output = "DocumentStatisticsPB")]
+  GetDocumentStatistics = 29,
+
+  // The outline and the statistics are sent by the DidUpdateDocumentOutline and the
+  // DidUpdateDocumentStatistics notifications
+  #[event(input = "DocumentStatisticsSubscriptionPB")]
+  SubscribeDocumentStatistics = 30,
+
+  // Returns the matches in the scope, with the replacement of each match if it's provided
+  #[event(
+    input = "FindReplacePayloadPB",
+    output = "RepeatedDocumentTextMatchesPB"
+  )]
+  FindText = 31,
+
+  // Replace the matches of the preview that are returned by FindText. The matches whose text has
+  // changed since the preview are skipped
+  #[event(input = "ReplaceTextPayloadPB", output = "RepeatedReplaceTextResultPB")]
+  ReplaceText = 32,
+
+  // Returns the todo blocks of the workspace. The changes are sent by the DidUpdateTasks
+  // notification
+  #[event(input = "QueryTasksPayloadPB", output = "RepeatedDocumentTaskPB")]
+  GetTasks = 33,
+
+  // Check or uncheck the todo block in its document
+  #[event(input = "UpdateTaskPayloadPB")]
+  UpdateTask = 34,
 }
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use collab::core::collab::DataSource;
+use collab::core::origin::CollabOrigin;
+use collab::preclude::Collab;
+use collab_document::blocks::DocumentData;
+use collab_document::document::Document;
+use flowy_error::{internal_error, FlowyResult};
+use flowy_folder_pub::export::{export_attachment_file_name, ExportedPage};
+use flowy_storage_pub::storage::StorageService;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use tracing::error;
+
+use crate::parser::document_data_parser::DocumentDataParser;
+use crate::parser::markdown::{
+  collect_links, mentions_to_links, nested_block_to_markdown, rewrite_links, LinkTarget, PageLink,
+};
+use crate::parser::parser_entities::{ConvertBlockToHtmlParams, NestedBlock};
+
+/// The characters that are encoded in the links of the exported files.
+const LINK_ENCODE_SET: &AsciiSet = &CONTROLS
+  .add(b' ')
+  .add(b'"')
+  .add(b'#')
+  .add(b'%')
+  .add(b'(')
+  .add(b')')
+  .add(b'<')
+  .add(b'>')
+  .add(b'?');
+
+/// The document whose attachments and mentions are rewritten to the links of the export.
+pub struct ExportedDocument {
+  pub block: NestedBlock,
+  /// The paths and the data of the attachments, relative to the root of the export.
+  pub files: Vec<(String, Vec<u8>)>,
+}
+
+impl ExportedDocument {
+  pub fn to_markdown(&self) -> String {
+    nested_block_to_markdown(&self.block)
+  }
+
+  /// Returns the HTML of the blocks, without the head of the HTML document.
+  pub fn to_html_body(&self) -> String {
+    self.block.convert_to_html(ConvertBlockToHtmlParams {
+      prev_block_ty: None,
+      next_block_ty: None,
+    })
+  }
+}
+
+/// Prepare the document to be exported to the files starting with `file_stem`. The files that the
+/// document attaches from the disk or from the storage are exported to the `{file_stem}_files`
+/// directory, and the mentions of the `pages` become links to their files. The files of the
+/// storage are skipped if `storage_service` is None.
+pub async fn export_document_data(
+  data: DocumentData,
+  file_stem: &str,
+  pages: &HashMap<String, ExportedPage>,
+  storage_service: Option<Arc<dyn StorageService>>,
+) -> ExportedDocument {
+  let mut block = DocumentDataParser::new(Arc::new(data), None)
+    .to_json()
+    .unwrap_or_default();
+  let (dir, name) = file_stem.rsplit_once('/').unwrap_or(("", file_stem));
+
+  let mut files = vec![];
+  let mut file_names = HashSet::new();
+  let mut targets = HashMap::new();
+  for link in collect_links(&block) {
+    if targets.contains_key(&link) {
+      continue;
+    }
+    let (file_name, bytes) = match read_attachment(&link, storage_service.as_deref()).await {
+      Ok(Some(attachment)) => attachment,
+      Ok(None) => continue,
+      Err(err) => {
+        error!("Failed to export {}: {}", link, err);
+        continue;
+      },
+    };
+    let file_name = export_attachment_file_name(&mut file_names, &file_name);
+    files.push((format!("{}_files/{}", file_stem, file_name), bytes));
+    let link_path = format!("{}_files/{}", name, file_name);
+    targets.insert(link, LinkTarget::Url(relative_link("", &link_path)));
+  }
+  rewrite_links(&mut block, &targets);
+
+  let page_links = pages
+    .iter()
+    .map(|(view_id, page)| {
+      let link = PageLink {
+        name: page.name.clone(),
+        href: relative_link(dir, &page.path),
+      };
+      (view_id.clone(), link)
+    })
+    .collect();
+  mentions_to_links(&mut block, &page_links);
+  ExportedDocument { block, files }
+}
+
+/// Returns the name and the data of the attached file if it's on the disk or in the storage.
+async fn read_attachment(
+  link: &str,
+  storage_service: Option<&dyn StorageService>,
+) -> FlowyResult<Option<(String, Vec<u8>)>> {
+  let path = PathBuf::from(link.strip_prefix("file://").unwrap_or(link));
+  if path.is_absolute() {
+    let file_name = match path.file_name().and_then(|name| name.to_str()) {
+      Some(file_name) => file_name.to_string(),
+      None => return Ok(None),
+    };
+    let bytes = tokio::fs::read(&path).await?;
+    return Ok(Some((file_name, bytes)));
+  }
+  match storage_service {
+    Some(storage_service) => storage_service.get_object_data(link).await,
+    None => Ok(None),
+  }
+}
+
+/// Decode the doc state of the document, such as the data of a published document.
+pub fn document_data_from_doc_state(doc_id: &str, doc_state: Vec<u8>) -> FlowyResult<DocumentData> {
+  let collab = Collab::new_with_source(
+    CollabOrigin::Empty,
+    doc_id,
+    DataSource::DocStateV1(doc_state),
+    vec![],
+    false,
+  )
+  .map_err(internal_error)?;
+  let document = Document::open(collab).map_err(internal_error)?;
+  document.get_document_data().map_err(internal_error)
+}
+
+/// Returns the relative link from the directory to the path. Both are relative to the root of the
+/// export and separated by `/`.
+pub fn relative_link(from_dir: &str, to_path: &str) -> String {
+  let from = from_dir
+    .split('/')
+    .filter(|s| !s.is_empty())
+    .collect::<Vec<_>>();
+  let to = to_path
+    .split('/')
+    .filter(|s| !s.is_empty())
+    .collect::<Vec<_>>();
+  // The last component of the path is the file, which is never shared with the directory.
+  let common = from
+    .iter()
+    .zip(to.iter().take(to.len().saturating_sub(1)))
+    .take_while(|(a, b)| a == b)
+    .count();
+  let mut components = vec![".."; from.len() - common];
+  components.extend_from_slice(&to[common..]);
+  let link = components.join("/");
+  utf8_percent_encode(&link, LINK_ENCODE_SET).to_string()
+}
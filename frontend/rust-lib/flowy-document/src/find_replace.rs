@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use collab_document::blocks::DocumentData;
+use flowy_error::{FlowyError, FlowyResult};
+use regex::{Regex, RegexBuilder};
+use serde_json::{json, Value};
+
+use crate::parser::constant::MENTION;
+use crate::parser::document_data_parser::DocumentDataParser;
+use crate::parser::utils::get_delta_for_block;
+
+/// Stands for a mention in the text of a block, so a match never covers a mention.
+const MENTION_PLACEHOLDER: char = '\u{FFFC}';
+/// The number of characters around a match that are shown in the preview.
+const CONTEXT_LENGTH: usize = 30;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FindScope {
+  Document(String),
+  /// The view and all its descendant views.
+  View(String),
+  Workspace,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FindOptions {
+  pub query: String,
+  pub is_regex: bool,
+  pub case_sensitive: bool,
+  /// Only matches that don't continue a word on either edge. The edges are checked on the matches
+  /// instead of with `\b`, which never matches next to a query that starts or ends with a
+  /// punctuation, like `C++` or `#tag`.
+  pub whole_word: bool,
+}
+
+impl FindOptions {
+  pub fn to_regex(&self) -> FlowyResult<Regex> {
+    let pattern = if self.is_regex {
+      self.query.clone()
+    } else {
+      regex::escape(&self.query)
+    };
+    RegexBuilder::new(&pattern)
+      .case_insensitive(!self.case_sensitive)
+      .build()
+      .map_err(|err| FlowyError::invalid_data().with_context(format!("Invalid pattern: {}", err)))
+  }
+}
+
+/// A match in the text of a block. The range is measured in UTF-16 code units, the same as the
+/// text deltas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMatch {
+  pub block_id: String,
+  pub text_id: String,
+  pub start: u32,
+  pub length: u32,
+  pub text: String,
+  /// The text around the match.
+  pub context: String,
+  /// The text that replaces the match, with the capture groups of a regex expanded.
+  pub replacement: Option<String>,
+  /// The attributes of the text at the start of the match, which are kept by the replacement.
+  pub attributes: Option<HashMap<String, Value>>,
+}
+
+/// A match of the preview that is confirmed to be replaced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplaceMatch {
+  pub block_id: String,
+  pub start: u32,
+  pub length: u32,
+  /// The matched text of the preview. The match is skipped if the text of the range has changed.
+  pub text: String,
+  pub replacement: String,
+}
+
+/// The text of a block, where a mention stands for one [MENTION_PLACEHOLDER].
+struct BlockText {
+  text_id: String,
+  text: String,
+  /// The byte index where each insert of the delta starts, with its attributes.
+  segments: Vec<(usize, Option<HashMap<String, Value>>)>,
+}
+
+impl BlockText {
+  fn new(data: &DocumentData, block_id: &str) -> Option<Self> {
+    let text_id = data.blocks.get(block_id)?.external_id.clone()?;
+    let delta = get_delta_for_block(block_id, data)?;
+    let mut text = String::new();
+    let mut segments = vec![];
+    for insert in delta {
+      let is_mention = insert
+        .attributes
+        .as_ref()
+        .map_or(false, |attributes| attributes.contains_key(MENTION));
+      segments.push((text.len(), insert.attributes));
+      if is_mention {
+        text.push(MENTION_PLACEHOLDER);
+      } else {
+        text.push_str(&insert.insert);
+      }
+    }
+    Some(Self {
+      text_id,
+      text,
+      segments,
+    })
+  }
+
+  fn attributes_at(&self, index: usize) -> Option<HashMap<String, Value>> {
+    self
+      .segments
+      .iter()
+      .rev()
+      .find(|(start, _)| *start <= index)
+      .and_then(|(_, attributes)| attributes.clone())
+  }
+
+  /// Returns the byte index of the UTF-16 index, or None if it's out of the text or inside a
+  /// character.
+  fn byte_index(&self, utf16_index: u32) -> Option<usize> {
+    let mut len = 0;
+    for (index, c) in self.text.char_indices() {
+      if len == utf16_index {
+        return Some(index);
+      }
+      len += c.len_utf16() as u32;
+    }
+    (len == utf16_index).then_some(self.text.len())
+  }
+}
+
+/// Returns the matches in the text blocks of the document, in the order of the document.
+pub fn find_in_document(
+  data: DocumentData,
+  regex: &Regex,
+  options: &FindOptions,
+  replacement: Option<&str>,
+) -> Vec<TextMatch> {
+  let parser = DocumentDataParser::new(Arc::new(data), None);
+  let mut matches = vec![];
+  for block_id in parser.block_ids_in_range() {
+    let block = match BlockText::new(&parser.document_data, &block_id) {
+      Some(block) => block,
+      None => continue,
+    };
+    let text = &block.text;
+    let mut index = 0;
+    while let Some(captures) = regex.captures_at(text, index) {
+      let found = captures.get(0).unwrap();
+      if found.is_empty()
+        || (options.whole_word && !is_whole_word(text, found.start(), found.end()))
+      {
+        // Search again from the next character, since a match that starts there may be valid
+        match text[found.start()..].chars().next() {
+          Some(c) => index = found.start() + c.len_utf8(),
+          None => break,
+        }
+        continue;
+      }
+      index = found.end();
+      if found.as_str().contains(MENTION_PLACEHOLDER) {
+        continue;
+      }
+      let replacement = replacement.map(|replacement| {
+        if options.is_regex {
+          let mut expanded = String::new();
+          captures.expand(replacement, &mut expanded);
+          expanded
+        } else {
+          replacement.to_string()
+        }
+      });
+      matches.push(TextMatch {
+        block_id: block_id.clone(),
+        text_id: block.text_id.clone(),
+        start: utf16_len(&text[..found.start()]),
+        length: utf16_len(found.as_str()),
+        text: found.as_str().to_string(),
+        context: context_of(text, found.start(), found.end()),
+        replacement,
+        attributes: block.attributes_at(found.start()),
+      });
+    }
+  }
+  matches
+}
+
+/// Returns the matches of the preview whose text is unchanged, ordered by their texts and
+/// ranges as [replace_deltas] requires. The matches that overlap a previous match are skipped.
+pub fn resolve_replace_matches(data: &DocumentData, matches: Vec<ReplaceMatch>) -> Vec<TextMatch> {
+  let mut matches = matches;
+  matches.sort_by(|a, b| (&a.block_id, a.start).cmp(&(&b.block_id, b.start)));
+
+  let mut blocks: HashMap<String, Option<BlockText>> = HashMap::new();
+  let mut resolved: Vec<TextMatch> = vec![];
+  for replace_match in matches {
+    let block = blocks
+      .entry(replace_match.block_id.clone())
+      .or_insert_with(|| BlockText::new(data, &replace_match.block_id));
+    let block = match block {
+      Some(block) => block,
+      None => continue,
+    };
+    let is_overlapped = resolved.last().map_or(false, |last| {
+      last.block_id == replace_match.block_id && last.start + last.length > replace_match.start
+    });
+    if is_overlapped {
+      continue;
+    }
+
+    let end = replace_match.start + replace_match.length;
+    let (start, end) = match (block.byte_index(replace_match.start), block.byte_index(end)) {
+      (Some(start), Some(end)) if start < end => (start, end),
+      _ => continue,
+    };
+    if block.text[start..end] != replace_match.text {
+      continue;
+    }
+    resolved.push(TextMatch {
+      text_id: block.text_id.clone(),
+      context: context_of(&block.text, start, end),
+      attributes: block.attributes_at(start),
+      block_id: replace_match.block_id,
+      start: replace_match.start,
+      length: replace_match.length,
+      text: replace_match.text,
+      replacement: Some(replace_match.replacement),
+    });
+  }
+  resolved
+}
+
+/// Returns the text id and the delta that replaces the matches of each block. The matches must be
+/// in the order of the text.
+pub fn replace_deltas(matches: &[TextMatch]) -> Vec<(String, String)> {
+  let mut ops_by_text: Vec<(String, Vec<Value>, u32)> = vec![];
+  for text_match in matches {
+    let replacement = match &text_match.replacement {
+      Some(replacement) => replacement,
+      None => continue,
+    };
+    if ops_by_text
+      .last()
+      .map_or(true, |(text_id, _, _)| text_id != &text_match.text_id)
+    {
+      ops_by_text.push((text_match.text_id.clone(), vec![], 0));
+    }
+    let (_, ops, index) = ops_by_text.last_mut().unwrap();
+    if text_match.start > *index {
+      ops.push(json!({ "retain": text_match.start - *index }));
+    }
+    ops.push(json!({ "delete": text_match.length }));
+    if !replacement.is_empty() {
+      match &text_match.attributes {
+        Some(attributes) => ops.push(json!({ "insert": replacement, "attributes": attributes })),
+        None => ops.push(json!({ "insert": replacement })),
+      }
+    }
+    *index = text_match.start + text_match.length;
+  }
+
+  ops_by_text
+    .into_iter()
+    .map(|(text_id, ops, _)| (text_id, Value::Array(ops).to_string()))
+    .collect()
+}
+
+/// Returns true if the match doesn't continue a word on either edge. An edge is a boundary unless
+/// both the character of the match and the character next to it are word characters.
+fn is_whole_word(text: &str, start: usize, end: usize) -> bool {
+  let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+  let found = &text[start..end];
+  let is_boundary = |outer: Option<char>, inner: Option<char>| match (outer, inner) {
+    (Some(outer), Some(inner)) => !(is_word_char(outer) && is_word_char(inner)),
+    _ => true,
+  };
+  is_boundary(text[..start].chars().next_back(), found.chars().next())
+    && is_boundary(text[end..].chars().next(), found.chars().next_back())
+}
+
+fn utf16_len(text: &str) -> u32 {
+  text.encode_utf16().count() as u32
+}
+
+fn context_of(text: &str, start: usize, end: usize) -> String {
+  let before = text[..start]
+    .chars()
+    .rev()
+    .take(CONTEXT_LENGTH)
+    .collect::<Vec<_>>()
+    .into_iter()
+    .rev()
+    .collect::<String>();
+  let after = text[end..].chars().take(CONTEXT_LENGTH).collect::<String>();
+  format!("{}{}{}", before, &text[start..end], after).replace(MENTION_PLACEHOLDER, "@")
+}
@@ -0,0 +1,139 @@
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use collab::core::collab_state::SyncState;
+use collab::lock::RwLock;
+use collab_document::blocks::DocumentData;
+use collab_document::document::Document;
+use dashmap::DashSet;
+use flowy_error::FlowyResult;
+use futures::StreamExt;
+use lib_dispatch::prelude::af_spawn;
+use tracing::error;
+
+/// The changes of an opened document are indexed together after the delay.
+const DOCUMENT_INDEX_DELAY: Duration = Duration::from_secs(2);
+
+/// An index that is built from the data of the documents, like the mentions or the tasks.
+pub trait DocumentIndex: Send + Sync {
+  /// The name that is used in the logs.
+  fn name(&self) -> &str;
+
+  /// Replace the index of the document with the data.
+  fn index(&self, document_id: &str, data: Arc<DocumentData>) -> FlowyResult<()>;
+
+  fn remove(&self, document_id: &str) -> FlowyResult<()>;
+
+  /// Returns the given documents that have never been indexed.
+  fn filter_unindexed_document_ids(&self, document_ids: Vec<String>) -> FlowyResult<Vec<String>>;
+}
+
+/// Keeps the indexes of the documents up to date. The opened documents are indexed whenever their
+/// blocks change, and once the updates of other devices are merged by the initial sync.
+pub struct DocumentIndexer {
+  indexes: Vec<Arc<dyn DocumentIndex>>,
+  pending_documents: DashSet<String>,
+}
+
+impl DocumentIndexer {
+  pub fn new(indexes: Vec<Arc<dyn DocumentIndex>>) -> Self {
+    Self {
+      indexes,
+      pending_documents: Default::default(),
+    }
+  }
+
+  /// Replace the indexes of the document with the data. The failure of an index doesn't stop the
+  /// others.
+  pub fn index(&self, document_id: &str, data: DocumentData) {
+    let data = Arc::new(data);
+    for index in &self.indexes {
+      if let Err(err) = index.index(document_id, data.clone()) {
+        error!(
+          "Failed to index the {} of {}: {}",
+          index.name(),
+          document_id,
+          err
+        );
+      }
+    }
+  }
+
+  pub fn remove(&self, document_id: &str) -> FlowyResult<()> {
+    for index in &self.indexes {
+      index.remove(document_id)?;
+    }
+    Ok(())
+  }
+
+  /// Returns the given documents that have never been indexed by any of the indexes.
+  pub fn filter_unindexed_document_ids(
+    &self,
+    document_ids: Vec<String>,
+  ) -> FlowyResult<Vec<String>> {
+    let mut unindexed = vec![];
+    for index in &self.indexes {
+      for document_id in index.filter_unindexed_document_ids(document_ids.clone())? {
+        if !unindexed.contains(&document_id) {
+          unindexed.push(document_id);
+        }
+      }
+    }
+    Ok(unindexed)
+  }
+
+  pub fn subscribe(
+    self: &Arc<Self>,
+    document_id: &str,
+    document: &mut Document,
+    weak_document: Weak<RwLock<Document>>,
+  ) {
+    let weak_indexer = Arc::downgrade(self);
+    let cloned_document_id = document_id.to_string();
+    let cloned_weak_document = weak_document.clone();
+    document.subscribe_block_changed("index", move |_events, _is_remote| {
+      if let Some(indexer) = weak_indexer.upgrade() {
+        indexer.schedule_index(&cloned_document_id, cloned_weak_document.clone());
+      }
+    });
+
+    let weak_indexer = Arc::downgrade(self);
+    let document_id = document_id.to_string();
+    let mut sync_state_stream = document.subscribe_sync_state();
+    af_spawn(async move {
+      while let Some(sync_state) = sync_state_stream.next().await {
+        if !matches!(sync_state, SyncState::InitSyncEnd) {
+          continue;
+        }
+        match weak_indexer.upgrade() {
+          Some(indexer) => indexer.schedule_index(&document_id, weak_document.clone()),
+          None => break,
+        }
+      }
+    });
+  }
+
+  fn schedule_index(self: &Arc<Self>, document_id: &str, document: Weak<RwLock<Document>>) {
+    if !self.pending_documents.insert(document_id.to_string()) {
+      return;
+    }
+
+    let weak_indexer = Arc::downgrade(self);
+    let document_id = document_id.to_string();
+    af_spawn(async move {
+      tokio::time::sleep(DOCUMENT_INDEX_DELAY).await;
+      let indexer = match weak_indexer.upgrade() {
+        Some(indexer) => indexer,
+        None => return,
+      };
+      indexer.pending_documents.remove(&document_id);
+      if let Some(document) = document.upgrade() {
+        let result = document.read().await.get_document_data();
+        match result {
+          Ok(data) => indexer.index(&document_id, data),
+          Err(err) => error!("Failed to read the document {}: {}", document_id, err),
+        }
+      }
+    });
+  }
+}
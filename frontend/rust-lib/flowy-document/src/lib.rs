@@ -1,9 +1,14 @@
+pub mod comment;
 pub mod document;
 pub mod document_data;
 pub mod entities;
 pub mod event_handler;
 pub mod event_map;
+pub mod export;
+pub mod find_replace;
+pub mod indexer;
 pub mod manager;
+pub mod mention;
 pub mod parser;
 pub mod protobuf;
 
@@ -11,4 +16,5 @@ pub mod deps;
 pub mod notification;
 mod parse;
 pub mod reminder;
+pub mod task;
 pub use collab_document::document::DocumentIndexContent;
@@ -1,6 +1,9 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::Weak;
 
+use arc_swap::ArcSwapOption;
 use collab::core::collab::DataSource;
 use collab::core::collab_plugin::CollabPersistence;
 use collab::core::origin::CollabOrigin;
@@ -15,33 +18,62 @@ use collab_document::document_data::default_document_data;
 use collab_entity::CollabType;
 
 use collab_plugins::CollabKVDB;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use lib_infra::util::timestamp;
-use tracing::{event, instrument};
-use tracing::{info, trace};
+use serde_json::json;
+use tracing::{error, event, info, instrument, trace};
 
 use crate::document::{
-  subscribe_document_changed, subscribe_document_snapshot_state, subscribe_document_sync_state,
+  send_document_statistics, subscribe_document_changed, subscribe_document_snapshot_state,
+  subscribe_document_statistics, subscribe_document_sync_state,
 };
 use collab_integrate::collab_builder::{
   AppFlowyCollabBuilder, CollabBuilderConfig, CollabPersistenceImpl,
 };
 use flowy_document_pub::cloud::DocumentCloudService;
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
+use flowy_folder_pub::export::{ExportFormat, ExportedPage};
+use flowy_sqlite::DBConnection;
 use flowy_storage_pub::storage::{CreatedUpload, StorageService};
 use lib_dispatch::prelude::af_spawn;
+use lib_infra::async_trait::async_trait;
 
-use crate::entities::UpdateDocumentAwarenessStatePB;
+use crate::comment::{
+  get_anchor_text, get_comment_threads, insert_comment, insert_comment_thread,
+  update_comment_thread, Comment, CommentThread,
+};
+use crate::entities::{
+  CommentPB, CommentThreadPB, DocumentLinkGraphPB, DocumentLinkPB, DocumentOutlinePB,
+  DocumentStatisticsPB, DocumentTaskPB, TaskPersonPB, UpdateDocumentAwarenessStatePB,
+};
 use crate::entities::{
   DocumentSnapshotData, DocumentSnapshotMeta, DocumentSnapshotMetaPB, DocumentSnapshotPB,
 };
+use crate::export::export_document_data;
+use crate::find_replace::{
+  find_in_document, replace_deltas, resolve_replace_matches, FindOptions, FindScope, ReplaceMatch,
+  TextMatch,
+};
+use crate::indexer::{DocumentIndex, DocumentIndexer};
+use crate::mention::{DocumentMentions, MentionViewService};
+use crate::notification::{send_notification, DocumentNotification};
+use crate::parser::constant::{CHECKED, TODO_LIST, URL};
+use crate::parser::document_data_parser::DocumentDataParser;
+use crate::parser::json::parser::JsonToDocumentParser;
+use crate::parser::markdown::{
+  collect_links, html_to_nested_block, markdown_to_nested_block, resolve_export_file,
+  resolve_relative_link, rewrite_links, LinkTarget,
+};
+use crate::parser::parser_entities::Range;
 use crate::reminder::DocumentReminderAction;
+use crate::task::{DocumentTasks, TaskQuery};
 
 pub trait DocumentUserService: Send + Sync {
   fn user_id(&self) -> Result<i64, FlowyError>;
   fn device_id(&self) -> Result<String, FlowyError>;
   fn workspace_id(&self) -> Result<String, FlowyError>;
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError>;
+  fn sqlite_connection(&self, uid: i64) -> Result<DBConnection, FlowyError>;
 }
 
 pub trait DocumentSnapshotService: Send + Sync {
@@ -52,6 +84,12 @@ pub trait DocumentSnapshotService: Send + Sync {
   fn get_document_snapshot(&self, snapshot_id: &str) -> FlowyResult<DocumentSnapshotData>;
 }
 
+#[async_trait]
+pub trait DocumentLockService: Send + Sync {
+  /// Returns true if the view of the document is locked. The locked documents are read-only.
+  async fn is_document_locked(&self, document_id: &str) -> bool;
+}
+
 pub struct DocumentManager {
   pub user_service: Arc<dyn DocumentUserService>,
   collab_builder: Arc<AppFlowyCollabBuilder>,
@@ -60,6 +98,11 @@ pub struct DocumentManager {
   cloud_service: Arc<dyn DocumentCloudService>,
   storage_service: Weak<dyn StorageService>,
   snapshot_service: Arc<dyn DocumentSnapshotService>,
+  mentions: Arc<DocumentMentions>,
+  tasks: Arc<DocumentTasks>,
+  indexer: Arc<DocumentIndexer>,
+  statistics_subscriptions: Arc<DashSet<String>>,
+  lock_service: ArcSwapOption<Arc<dyn DocumentLockService>>,
 }
 
 impl DocumentManager {
@@ -70,6 +113,12 @@ impl DocumentManager {
     storage_service: Weak<dyn StorageService>,
     snapshot_service: Arc<dyn DocumentSnapshotService>,
   ) -> Self {
+    let mentions = Arc::new(DocumentMentions::new(user_service.clone()));
+    let tasks = Arc::new(DocumentTasks::new(user_service.clone()));
+    let indexer = Arc::new(DocumentIndexer::new(vec![
+      mentions.clone() as Arc<dyn DocumentIndex>,
+      tasks.clone(),
+    ]));
     Self {
       user_service,
       collab_builder,
@@ -78,7 +127,30 @@ impl DocumentManager {
       cloud_service,
       storage_service,
       snapshot_service,
+      mentions,
+      tasks,
+      indexer,
+      statistics_subscriptions: Default::default(),
+      lock_service: Default::default(),
+    }
+  }
+
+  pub fn set_mention_view_service(&self, service: Arc<dyn MentionViewService>) {
+    self.mentions.set_view_service(service);
+  }
+
+  pub fn set_lock_service(&self, service: Arc<dyn DocumentLockService>) {
+    self.lock_service.store(Some(Arc::new(service)));
+  }
+
+  /// Returns a [ErrorCode::ViewLocked] error if the document can't be edited.
+  pub async fn check_document_editable(&self, doc_id: &str) -> FlowyResult<()> {
+    if let Some(lock_service) = self.lock_service.load_full() {
+      if lock_service.is_document_locked(doc_id).await {
+        return Err(FlowyError::view_locked());
+      }
     }
+    Ok(())
   }
 
   /// Get the encoded collab of the document.
@@ -149,11 +221,15 @@ impl DocumentManager {
         format!("document {} already exists", doc_id),
       ))
     } else {
+      let index_data = data.clone();
       let encoded_collab = doc_state_from_document_data(doc_id, data).await?;
       self
         .persistence()?
         .save_collab_to_disk(doc_id, encoded_collab.clone())
         .map_err(internal_error)?;
+      if let Some(data) = index_data {
+        self.indexer.index(doc_id, data);
+      }
 
       // Send the collab data to server with a background task.
       let cloud_service = self.cloud_service.clone();
@@ -221,7 +297,8 @@ impl DocumentManager {
     let mut doc_state = self.persistence()?.into_data_source();
     // If the document does not exist in local disk, try get the doc state from the cloud. This happens
     // When user_device_a create a document and user_device_b open the document.
-    if !self.is_doc_exist(doc_id).await? {
+    let is_remote_doc_state = !self.is_doc_exist(doc_id).await?;
+    if is_remote_doc_state {
       info!(
         "document {} not found in local disk, try to get the doc state from the cloud",
         doc_id
@@ -253,11 +330,29 @@ impl DocumentManager {
       .await;
     match result {
       Ok(document) => {
+        // The document is created on another device, so it has never been indexed on this one.
+        if is_remote_doc_state {
+          let result = document.read().await.get_document_data();
+          match result {
+            Ok(data) => self.indexer.index(doc_id, data),
+            Err(err) => error!("Failed to read the document {}: {}", doc_id, err),
+          }
+        }
+
         // Only push the document to the cache if the sync is enabled.
         if enable_sync {
           {
             let mut lock = document.write().await;
             subscribe_document_changed(doc_id, &mut lock);
+            self
+              .indexer
+              .subscribe(doc_id, &mut lock, Arc::downgrade(&document));
+            subscribe_document_statistics(
+              doc_id,
+              &mut lock,
+              Arc::downgrade(&document),
+              Arc::downgrade(&self.statistics_subscriptions),
+            );
             subscribe_document_snapshot_state(&lock);
             subscribe_document_sync_state(&lock);
           }
@@ -316,6 +411,7 @@ impl DocumentManager {
   }
 
   pub async fn close_document(&self, doc_id: &str) -> FlowyResult<()> {
+    self.statistics_subscriptions.remove(doc_id);
     if let Some((doc_id, document)) = self.documents.remove(doc_id) {
       {
         // clear the awareness state when close the document
@@ -349,9 +445,454 @@ impl DocumentManager {
       // When deleting a document, we need to remove it from the cache.
       self.documents.remove(doc_id);
     }
+    self.indexer.remove(doc_id)?;
     Ok(())
   }
 
+  pub async fn get_document_outline(
+    &self,
+    doc_id: &str,
+    range: Option<Range>,
+  ) -> FlowyResult<DocumentOutlinePB> {
+    let data = self.get_document_data(doc_id).await?;
+    let outline = DocumentDataParser::new(Arc::new(data), range).to_outline();
+    Ok(outline.into())
+  }
+
+  pub async fn get_document_statistics(
+    &self,
+    doc_id: &str,
+    range: Option<Range>,
+  ) -> FlowyResult<DocumentStatisticsPB> {
+    let data = self.get_document_data(doc_id).await?;
+    let statistics = DocumentDataParser::new(Arc::new(data), range).to_statistics();
+    Ok(statistics.into())
+  }
+
+  /// Send the outline and the statistics of the opened document whenever it changes. The current
+  /// outline and statistics are sent right after subscribing.
+  pub async fn subscribe_document_statistics(
+    &self,
+    doc_id: &str,
+    subscribe: bool,
+  ) -> FlowyResult<()> {
+    if !subscribe {
+      self.statistics_subscriptions.remove(doc_id);
+      return Ok(());
+    }
+
+    let document = self.editable_document(doc_id).await?;
+    self.statistics_subscriptions.insert(doc_id.to_string());
+    let data = document.read().await.get_document_data()?;
+    send_document_statistics(doc_id, data);
+    Ok(())
+  }
+
+  /// Create the document from the Markdown or the HTML file. The relative links to the imported
+  /// pages become mentions, and the other files that the file links to are uploaded.
+  ///
+  /// The `pages` maps the paths of the imported pages to their view ids. Only the files inside the
+  /// `root` directory of the import are uploaded.
+  pub async fn import_document_file(
+    &self,
+    uid: i64,
+    doc_id: &str,
+    path: &Path,
+    root: &Path,
+    pages: &HashMap<PathBuf, String>,
+  ) -> FlowyResult<EncodedCollab> {
+    let content = tokio::fs::read_to_string(path).await?;
+    let is_html = path
+      .extension()
+      .and_then(|extension| extension.to_str())
+      .map_or(false, |extension| extension.eq_ignore_ascii_case("html"));
+    let mut block = if is_html {
+      html_to_nested_block(content)
+    } else {
+      markdown_to_nested_block(&content)?
+    };
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let workspace_id = self.user_service.workspace_id()?;
+    let mut targets = HashMap::new();
+    for link in collect_links(&block) {
+      if targets.contains_key(&link) {
+        continue;
+      }
+      let file_path = match resolve_relative_link(dir, &link) {
+        Some(file_path) => file_path,
+        None => continue,
+      };
+      if let Some(view_id) = pages.get(&file_path) {
+        targets.insert(link, LinkTarget::Page(view_id.clone()));
+      } else if let Some(file_path) =
+        resolve_export_file(root, dir, &link).filter(|file_path| file_path.is_file())
+      {
+        let result = self
+          .upload_file(workspace_id.clone(), doc_id, &file_path.to_string_lossy())
+          .await;
+        match result {
+          Ok(upload) => {
+            targets.insert(link, LinkTarget::Url(upload.url));
+          },
+          Err(err) => error!("Failed to upload {}: {}", file_path.display(), err),
+        }
+      }
+    }
+    rewrite_links(&mut block, &targets);
+
+    let json = serde_json::to_string(&block)?;
+    let data = JsonToDocumentParser::json_str_to_document(&json)?;
+    self.create_document(uid, doc_id, Some(data.into())).await
+  }
+
+  /// Export the document to Markdown or HTML as the `{file_stem}.md` or `{file_stem}.html` file.
+  /// The files that the document attaches from the disk or from the storage are exported to the
+  /// `{file_stem}_files` directory, and the other attachments are kept as links. The mentions of
+  /// the `pages` become links to their files.
+  ///
+  /// Returns the paths and the data of the exported files, starting with the document. The paths
+  /// are relative to the root of the export.
+  pub async fn export_document(
+    &self,
+    doc_id: &str,
+    format: ExportFormat,
+    file_stem: &str,
+    pages: &HashMap<String, ExportedPage>,
+  ) -> FlowyResult<Vec<(String, Vec<u8>)>> {
+    let data = self.get_document_data(doc_id).await?;
+    let document =
+      export_document_data(data, file_stem, pages, self.storage_service.upgrade()).await;
+    let content = match format {
+      ExportFormat::Markdown => document.to_markdown(),
+      ExportFormat::Html => format!("<meta charset=\"UTF-8\">{}", document.to_html_body()),
+    };
+    let mut files = document.files;
+    let path = format!("{}.{}", file_stem, format.extension());
+    files.insert(0, (path, content.into_bytes()));
+    Ok(files)
+  }
+
+  /// Returns the matches of each document in the scope. The documents without matches are omitted.
+  pub async fn find_text(
+    &self,
+    scope: FindScope,
+    options: &FindOptions,
+    replacement: Option<&str>,
+  ) -> FlowyResult<Vec<(String, Vec<TextMatch>)>> {
+    let regex = options.to_regex()?;
+    let mut results = vec![];
+    for doc_id in self.document_ids_in_scope(scope).await? {
+      match self.get_document_data(&doc_id).await {
+        Ok(data) => {
+          let matches = find_in_document(data, &regex, options, replacement);
+          if !matches.is_empty() {
+            results.push((doc_id, matches));
+          }
+        },
+        Err(err) => error!("Failed to search the document {}: {}", doc_id, err),
+      }
+    }
+    Ok(results)
+  }
+
+  /// Replace the previewed matches of each document, and return the number of the replaced
+  /// matches or the error of each document. The matches whose text has changed since the preview
+  /// are skipped. The replacements are applied as text deltas, so they sync with the other clients
+  /// and can be undone like the other edits.
+  pub async fn replace_text(
+    &self,
+    matches: Vec<(String, Vec<ReplaceMatch>)>,
+  ) -> Vec<(String, FlowyResult<usize>)> {
+    let mut results = vec![];
+    for (doc_id, matches) in matches {
+      let result = self.replace_matches_in_document(&doc_id, matches).await;
+      if let Err(err) = &result {
+        error!("Failed to replace the text of {}: {}", doc_id, err);
+      }
+      results.push((doc_id, result));
+    }
+    results
+  }
+
+  async fn replace_matches_in_document(
+    &self,
+    doc_id: &str,
+    matches: Vec<ReplaceMatch>,
+  ) -> FlowyResult<usize> {
+    // the locked documents are read-only
+    self.check_document_editable(doc_id).await?;
+    let is_opened = self.documents.contains_key(doc_id);
+    self.open_document(doc_id).await?;
+
+    let result = self.apply_replace_matches(doc_id, matches).await;
+    if !is_opened {
+      if let Err(err) = self.close_document(doc_id).await {
+        error!("Failed to close the document {}: {}", doc_id, err);
+      }
+    }
+    result
+  }
+
+  async fn apply_replace_matches(
+    &self,
+    doc_id: &str,
+    matches: Vec<ReplaceMatch>,
+  ) -> FlowyResult<usize> {
+    let document = self.editable_document(doc_id).await?;
+    let mut document = document.write().await;
+    let data = document.get_document_data()?;
+    let matches = resolve_replace_matches(&data, matches);
+    for (text_id, delta) in replace_deltas(&matches) {
+      document.apply_text_delta(&text_id, delta);
+    }
+    Ok(matches.len())
+  }
+
+  async fn document_ids_in_scope(&self, scope: FindScope) -> FlowyResult<Vec<String>> {
+    match scope {
+      FindScope::Document(doc_id) => Ok(vec![doc_id]),
+      FindScope::View(view_id) => {
+        self
+          .mentions
+          .view_service()?
+          .get_document_ids_in_view(&view_id)
+          .await
+      },
+      FindScope::Workspace => self.mentions.view_service()?.get_document_ids().await,
+    }
+  }
+
+  /// Create a thread anchored to the text range of the block with its first comment.
+  pub async fn create_comment_thread(
+    &self,
+    document_id: &str,
+    block_id: &str,
+    start: u32,
+    length: u32,
+    content: String,
+  ) -> FlowyResult<CommentThreadPB> {
+    let uid = self.user_service.user_id()?;
+    let document = self.editable_document(document_id).await?;
+    let mut document = document.write().await;
+    let data = document.get_document_data()?;
+    let anchor_text = get_anchor_text(&data, block_id, start, length).ok_or_else(|| {
+      FlowyError::invalid_data().with_context("The comment must be anchored to the text of a block")
+    })?;
+    let text_id = data
+      .blocks
+      .get(block_id)
+      .and_then(|block| block.external_id.clone())
+      .ok_or_else(|| FlowyError::invalid_data().with_context("The block has no text"))?;
+    let created_at = timestamp();
+    let thread = CommentThread {
+      id: uuid::Uuid::new_v4().to_string(),
+      block_id: block_id.to_string(),
+      start,
+      length,
+      start_anchor: None,
+      end_anchor: None,
+      anchor_text,
+      created_by: uid,
+      created_at,
+      resolved_by: None,
+      resolved_at: None,
+    };
+    let comment = Comment {
+      id: uuid::Uuid::new_v4().to_string(),
+      thread_id: thread.id.clone(),
+      author: uid,
+      content,
+      created_at,
+    };
+    insert_comment_thread(&mut document, &text_id, &thread, &comment)?;
+    drop(document);
+
+    send_notification(document_id, DocumentNotification::DidCreateComment)
+      .payload(CommentPB::from(comment.clone()))
+      .send();
+    Ok(CommentThreadPB::from((thread, vec![comment])))
+  }
+
+  pub async fn reply_comment(
+    &self,
+    document_id: &str,
+    thread_id: &str,
+    content: String,
+  ) -> FlowyResult<CommentPB> {
+    let uid = self.user_service.user_id()?;
+    let document = self.editable_document(document_id).await?;
+    let comment = Comment {
+      id: uuid::Uuid::new_v4().to_string(),
+      thread_id: thread_id.to_string(),
+      author: uid,
+      content,
+      created_at: timestamp(),
+    };
+    insert_comment(&mut *document.write().await, &comment)?;
+
+    let comment = CommentPB::from(comment);
+    send_notification(document_id, DocumentNotification::DidCreateComment)
+      .payload(comment.clone())
+      .send();
+    Ok(comment)
+  }
+
+  /// Resolve the thread, or reopen it when `resolved` is false.
+  pub async fn resolve_comment_thread(
+    &self,
+    document_id: &str,
+    thread_id: &str,
+    resolved: bool,
+  ) -> FlowyResult<CommentThreadPB> {
+    let uid = self.user_service.user_id()?;
+    let document = self.editable_document(document_id).await?;
+    let mut document = document.write().await;
+    update_comment_thread(&mut document, thread_id, |thread| {
+      if resolved {
+        thread.resolved_by = Some(uid);
+        thread.resolved_at = Some(timestamp());
+      } else {
+        thread.resolved_by = None;
+        thread.resolved_at = None;
+      }
+    })?;
+    get_comment_threads(&document)
+      .into_iter()
+      .find(|(thread, _)| thread.id == thread_id)
+      .map(CommentThreadPB::from)
+      .ok_or_else(FlowyError::record_not_found)
+  }
+
+  pub async fn get_comment_threads(&self, document_id: &str) -> FlowyResult<Vec<CommentThreadPB>> {
+    let document = self.get_document(document_id).await?;
+    let document = document.read().await;
+    Ok(
+      get_comment_threads(&document)
+        .into_iter()
+        .map(CommentThreadPB::from)
+        .collect(),
+    )
+  }
+
+  /// Index the documents that have never been indexed, such as the documents that were created
+  /// before an index was added.
+  #[instrument(level = "debug", skip_all, err)]
+  pub async fn index_unindexed_documents(&self) -> FlowyResult<()> {
+    let document_ids = self.mentions.view_service()?.get_document_ids().await?;
+    let document_ids = self.indexer.filter_unindexed_document_ids(document_ids)?;
+    trace!("index {} documents", document_ids.len());
+    for document_id in document_ids {
+      match self.get_document_data(&document_id).await {
+        Ok(data) => self.indexer.index(&document_id, data),
+        Err(err) => error!("Failed to read the document {}: {}", document_id, err),
+      }
+    }
+    Ok(())
+  }
+
+  /// Returns the tasks of the workspace that match the query. The tasks of the documents that are
+  /// in the trash or not in the workspace are omitted.
+  pub async fn get_tasks(&self, query: &TaskQuery) -> FlowyResult<Vec<DocumentTaskPB>> {
+    let tasks = self.tasks.query(query)?;
+    if tasks.is_empty() {
+      return Ok(vec![]);
+    }
+
+    let document_ids = tasks
+      .iter()
+      .map(|task| task.document_id.clone())
+      .collect::<HashSet<_>>()
+      .into_iter()
+      .collect::<Vec<_>>();
+    let views = self
+      .mentions
+      .view_service()?
+      .get_mention_views(document_ids)
+      .await?;
+    Ok(
+      tasks
+        .into_iter()
+        .filter_map(|task| {
+          let view = views
+            .get(&task.document_id)
+            .filter(|view| !view.is_trashed)?;
+          Some(DocumentTaskPB {
+            people: task
+              .people()
+              .into_iter()
+              .map(|person| TaskPersonPB {
+                id: person.id,
+                name: person.name,
+              })
+              .collect(),
+            document_id: task.document_id,
+            block_id: task.block_id,
+            view_id: view.view_id.clone(),
+            row_id: view.row_id.clone(),
+            view_name: view.name.clone(),
+            content: task.content,
+            is_checked: task.is_checked,
+            due_at: task.due_at,
+            reminder_id: task.reminder_id,
+          })
+        })
+        .collect(),
+    )
+  }
+
+  /// Check or uncheck the todo block in its document. The document is opened to apply the change,
+  /// so the change syncs like an edit, and the index is updated right away.
+  pub async fn set_task_checked(
+    &self,
+    doc_id: &str,
+    block_id: &str,
+    is_checked: bool,
+  ) -> FlowyResult<()> {
+    self.check_document_editable(doc_id).await?;
+    let is_opened = self.documents.contains_key(doc_id);
+    self.open_document(doc_id).await?;
+    let result = self.update_task_block(doc_id, block_id, is_checked).await;
+    if !is_opened {
+      self.close_document(doc_id).await?;
+    }
+    let data = result?;
+    self.tasks.index(doc_id, Arc::new(data))
+  }
+
+  async fn update_task_block(
+    &self,
+    doc_id: &str,
+    block_id: &str,
+    is_checked: bool,
+  ) -> FlowyResult<DocumentData> {
+    let document = self.editable_document(doc_id).await?;
+    let mut document = document.write().await;
+    let block = document
+      .get_block(block_id)
+      .filter(|block| block.ty == TODO_LIST)
+      .ok_or_else(|| {
+        FlowyError::record_not_found().with_context(format!("Todo block {} not found", block_id))
+      })?;
+    let mut data = block.data;
+    data.insert(CHECKED.to_string(), json!(is_checked));
+    document
+      .update_block(block_id, data)
+      .map_err(internal_error)?;
+    Ok(document.get_document_data()?)
+  }
+
+  pub async fn get_backlinks(&self, view_id: &str) -> FlowyResult<Vec<DocumentLinkPB>> {
+    self.mentions.get_backlinks(view_id).await
+  }
+
+  pub async fn get_outgoing_links(&self, document_id: &str) -> FlowyResult<Vec<DocumentLinkPB>> {
+    self.mentions.get_outgoing_links(document_id).await
+  }
+
+  pub async fn get_link_graph(&self) -> FlowyResult<DocumentLinkGraphPB> {
+    self.mentions.get_link_graph().await
+  }
+
   #[instrument(level = "debug", skip_all, err)]
   pub async fn set_document_awareness_local_state(
     &self,
@@ -423,6 +964,68 @@ impl DocumentManager {
     Ok(upload)
   }
 
+  /// Returns the urls that the blocks of the document refer to, including the urls of the
+  /// uploaded files.
+  pub async fn get_document_file_urls(&self, doc_id: &str) -> FlowyResult<Vec<String>> {
+    let data = self.get_document_data(doc_id).await?;
+    let urls = data
+      .blocks
+      .values()
+      .filter_map(|block| block.data.get(URL).and_then(|value| value.as_str()))
+      .filter(|url| !url.is_empty())
+      .map(|url| url.to_string())
+      .collect();
+    Ok(urls)
+  }
+
+  /// Copies the files referenced by the blocks of `data` into the given workspace and points
+  /// the blocks at the copies. Returns the urls of the copies, which are removed if any file
+  /// fails to be copied.
+  #[instrument(level = "debug", skip(self, data), err)]
+  pub async fn copy_document_files(
+    &self,
+    data: &mut DocumentData,
+    workspace_id: &str,
+    document_id: &str,
+  ) -> FlowyResult<Vec<String>> {
+    let storage_service = self.storage_service_upgrade()?;
+    let mut copied_urls = vec![];
+    for block in data.blocks.values_mut() {
+      let url = match block.data.get(URL).and_then(|value| value.as_str()) {
+        Some(url) if !url.is_empty() => url.to_string(),
+        _ => continue,
+      };
+      match storage_service
+        .copy_object(&url, workspace_id, document_id)
+        .await
+      {
+        Ok(Some(new_url)) => {
+          block.data.insert(URL.to_string(), json!(new_url));
+          copied_urls.push(new_url);
+        },
+        Ok(None) => {},
+        Err(err) => {
+          for copied_url in copied_urls {
+            if let Err(err) = storage_service.delete_object(copied_url.clone()).await {
+              error!("Failed to delete the copied file {}: {}", copied_url, err);
+            }
+          }
+          return Err(err);
+        },
+      }
+    }
+    Ok(copied_urls)
+  }
+
+  /// Encodes the document data without writing it to disk.
+  pub async fn encode_document_data(
+    &self,
+    doc_id: &str,
+    data: DocumentData,
+  ) -> FlowyResult<EncodedCollab> {
+    doc_state_from_document_data(doc_id, Some(data)).await
+  }
+
   pub async fn download_file(&self, local_file_path: String, url: String) -> FlowyResult<()> {
     let storage_service = self.storage_service_upgrade()?;
     storage_service.download_object(url, local_file_path)?;
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::schema::{document_mention_table, indexed_document_table};
+use flowy_sqlite::{
+  diesel, insert_into, query_dsl::*, DBConnection, ExpressionMethods, Insertable, Queryable,
+};
+
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq)]
+#[diesel(table_name = document_mention_table)]
+pub struct DocumentMentionTable {
+  pub document_id: String,
+  pub block_id: String,
+  /// The id of the mentioned view
+  pub view_id: String,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = indexed_document_table)]
+struct IndexedDocumentTable {
+  document_id: String,
+  indexed_at: i64,
+}
+
+/// Replace the mentions of the document and mark the document as indexed.
+pub fn replace_document_mentions(
+  mut conn: DBConnection,
+  document_id: &str,
+  mentions: &[DocumentMentionTable],
+  indexed_at: i64,
+) -> FlowyResult<()> {
+  conn.immediate_transaction(|conn| {
+    diesel::delete(
+      document_mention_table::dsl::document_mention_table
+        .filter(document_mention_table::document_id.eq(document_id)),
+    )
+    .execute(conn)?;
+    if !mentions.is_empty() {
+      insert_into(document_mention_table::table)
+        .values(mentions)
+        .execute(conn)?;
+    }
+    let indexed = IndexedDocumentTable {
+      document_id: document_id.to_string(),
+      indexed_at,
+    };
+    insert_into(indexed_document_table::table)
+      .values(&indexed)
+      .on_conflict(indexed_document_table::document_id)
+      .do_update()
+      .set(indexed_document_table::indexed_at.eq(indexed_at))
+      .execute(conn)?;
+    Ok::<(), FlowyError>(())
+  })?;
+  Ok(())
+}
+
+pub fn delete_document_mentions(mut conn: DBConnection, document_id: &str) -> FlowyResult<()> {
+  conn.immediate_transaction(|conn| {
+    diesel::delete(
+      document_mention_table::dsl::document_mention_table
+        .filter(document_mention_table::document_id.eq(document_id)),
+    )
+    .execute(conn)?;
+    diesel::delete(
+      indexed_document_table::dsl::indexed_document_table
+        .filter(indexed_document_table::document_id.eq(document_id)),
+    )
+    .execute(conn)?;
+    Ok::<(), FlowyError>(())
+  })?;
+  Ok(())
+}
+
+/// Returns the mentions of the view in all the documents.
+pub fn select_view_mentions(
+  mut conn: DBConnection,
+  view_id: &str,
+) -> FlowyResult<Vec<DocumentMentionTable>> {
+  let mentions = document_mention_table::dsl::document_mention_table
+    .filter(document_mention_table::view_id.eq(view_id))
+    .load::<DocumentMentionTable>(&mut *conn)?;
+  Ok(mentions)
+}
+
+/// Returns the mentions in the document.
+pub fn select_document_mentions(
+  mut conn: DBConnection,
+  document_id: &str,
+) -> FlowyResult<Vec<DocumentMentionTable>> {
+  let mentions = document_mention_table::dsl::document_mention_table
+    .filter(document_mention_table::document_id.eq(document_id))
+    .load::<DocumentMentionTable>(&mut *conn)?;
+  Ok(mentions)
+}
+
+pub fn select_all_mentions(mut conn: DBConnection) -> FlowyResult<Vec<DocumentMentionTable>> {
+  let mentions =
+    document_mention_table::dsl::document_mention_table.load::<DocumentMentionTable>(&mut *conn)?;
+  Ok(mentions)
+}
+
+pub fn select_indexed_document_ids(mut conn: DBConnection) -> FlowyResult<HashSet<String>> {
+  let document_ids = indexed_document_table::dsl::indexed_document_table
+    .select(indexed_document_table::document_id)
+    .load::<String>(&mut *conn)?;
+  Ok(document_ids.into_iter().collect())
+}
@@ -0,0 +1,226 @@
+mod mention_sql;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+use collab_document::blocks::DocumentData;
+use flowy_error::{FlowyError, FlowyResult};
+use lib_infra::async_trait::async_trait;
+use lib_infra::util::timestamp;
+
+use crate::entities::{
+  DocumentLinkGraphPB, DocumentLinkPB, LinkEdgePB, LinkNodePB, LinkNodeStatePB,
+};
+use crate::indexer::DocumentIndex;
+use crate::manager::DocumentUserService;
+use crate::parser::document_data_parser::DocumentDataParser;
+use mention_sql::*;
+
+/// The view that shows a document or a mentioned view.
+#[derive(Debug, Clone)]
+pub struct MentionView {
+  /// The id of the view. The view of a row document is the database view that contains the row.
+  pub view_id: String,
+  pub row_id: Option<String>,
+  pub name: String,
+  pub is_trashed: bool,
+}
+
+#[async_trait]
+pub trait MentionViewService: Send + Sync {
+  /// Returns the views of the given documents or views. The ids whose views have been deleted are
+  /// not in the result.
+  async fn get_mention_views(&self, ids: Vec<String>) -> FlowyResult<HashMap<String, MentionView>>;
+
+  /// Returns the ids of the documents of the current workspace.
+  async fn get_document_ids(&self) -> FlowyResult<Vec<String>>;
+
+  /// Returns the ids of the documents of the view and its descendant views.
+  async fn get_document_ids_in_view(&self, view_id: &str) -> FlowyResult<Vec<String>>;
+}
+
+/// The index of the page mentions of the documents, including the row documents. It's updated by
+/// the [crate::indexer::DocumentIndexer] when a document is created, edited or deleted, and
+/// answers the backlinks of the views.
+pub struct DocumentMentions {
+  user_service: Arc<dyn DocumentUserService>,
+  view_service: ArcSwapOption<Arc<dyn MentionViewService>>,
+}
+
+impl DocumentMentions {
+  pub fn new(user_service: Arc<dyn DocumentUserService>) -> Self {
+    Self {
+      user_service,
+      view_service: Default::default(),
+    }
+  }
+
+  pub fn set_view_service(&self, service: Arc<dyn MentionViewService>) {
+    self.view_service.store(Some(Arc::new(service)));
+  }
+
+  /// Returns the links to the view. The links of the documents that are in the trash or have been
+  /// deleted are omitted.
+  pub async fn get_backlinks(&self, view_id: &str) -> FlowyResult<Vec<DocumentLinkPB>> {
+    let uid = self.user_service.user_id()?;
+    let mentions = select_view_mentions(self.user_service.sqlite_connection(uid)?, view_id)?;
+    let links = self.resolve_links(mentions).await?;
+    Ok(
+      links
+        .into_iter()
+        .filter(|link| link.source.state == LinkNodeStatePB::Normal)
+        .collect(),
+    )
+  }
+
+  /// Returns the links of the document, including the broken links to the deleted views.
+  pub async fn get_outgoing_links(&self, document_id: &str) -> FlowyResult<Vec<DocumentLinkPB>> {
+    let uid = self.user_service.user_id()?;
+    let mentions =
+      select_document_mentions(self.user_service.sqlite_connection(uid)?, document_id)?;
+    self.resolve_links(mentions).await
+  }
+
+  /// Returns the graph of the links of the workspace. The documents that are in the trash or have
+  /// been deleted are not in the graph, but the views they link to are kept with their state.
+  pub async fn get_link_graph(&self) -> FlowyResult<DocumentLinkGraphPB> {
+    let uid = self.user_service.user_id()?;
+    let mentions = select_all_mentions(self.user_service.sqlite_connection(uid)?)?;
+    let links = self.resolve_links(mentions).await?;
+    Ok(link_graph(links))
+  }
+
+  async fn resolve_links(
+    &self,
+    mentions: Vec<DocumentMentionTable>,
+  ) -> FlowyResult<Vec<DocumentLinkPB>> {
+    if mentions.is_empty() {
+      return Ok(vec![]);
+    }
+
+    let ids = mentions
+      .iter()
+      .flat_map(|mention| [mention.document_id.clone(), mention.view_id.clone()])
+      .collect::<HashSet<_>>()
+      .into_iter()
+      .collect::<Vec<_>>();
+    let views = self.view_service()?.get_mention_views(ids).await?;
+    Ok(
+      mentions
+        .into_iter()
+        .map(|mention| DocumentLinkPB {
+          source: link_node(&mention.document_id, &views),
+          block_id: mention.block_id,
+          target: link_node(&mention.view_id, &views),
+        })
+        .collect(),
+    )
+  }
+
+  pub(crate) fn view_service(&self) -> FlowyResult<Arc<dyn MentionViewService>> {
+    self
+      .view_service
+      .load_full()
+      .map(|service| service.as_ref().clone())
+      .ok_or_else(|| FlowyError::internal().with_context("The mention view service is not ready"))
+  }
+}
+
+impl DocumentIndex for DocumentMentions {
+  fn name(&self) -> &str {
+    "mentions"
+  }
+
+  /// Replace the mentions of the document with the mentions in the data.
+  fn index(&self, document_id: &str, data: Arc<DocumentData>) -> FlowyResult<()> {
+    let mentions = DocumentDataParser::new(data, None)
+      .to_page_mentions()
+      .into_iter()
+      .filter(|(_, view_id)| view_id != document_id)
+      .map(|(block_id, view_id)| DocumentMentionTable {
+        document_id: document_id.to_string(),
+        block_id,
+        view_id,
+      })
+      .collect::<Vec<_>>();
+    let uid = self.user_service.user_id()?;
+    replace_document_mentions(
+      self.user_service.sqlite_connection(uid)?,
+      document_id,
+      &mentions,
+      timestamp(),
+    )
+  }
+
+  fn remove(&self, document_id: &str) -> FlowyResult<()> {
+    let uid = self.user_service.user_id()?;
+    delete_document_mentions(self.user_service.sqlite_connection(uid)?, document_id)
+  }
+
+  fn filter_unindexed_document_ids(&self, document_ids: Vec<String>) -> FlowyResult<Vec<String>> {
+    let uid = self.user_service.user_id()?;
+    let indexed = select_indexed_document_ids(self.user_service.sqlite_connection(uid)?)?;
+    Ok(
+      document_ids
+        .into_iter()
+        .filter(|document_id| !indexed.contains(document_id))
+        .collect(),
+    )
+  }
+}
+
+fn link_node(id: &str, views: &HashMap<String, MentionView>) -> LinkNodePB {
+  match views.get(id) {
+    Some(view) => LinkNodePB {
+      id: id.to_string(),
+      view_id: view.view_id.clone(),
+      row_id: view.row_id.clone(),
+      name: view.name.clone(),
+      state: if view.is_trashed {
+        LinkNodeStatePB::Trashed
+      } else {
+        LinkNodeStatePB::Normal
+      },
+    },
+    None => LinkNodePB {
+      id: id.to_string(),
+      view_id: id.to_string(),
+      row_id: None,
+      name: "".to_string(),
+      state: LinkNodeStatePB::Deleted,
+    },
+  }
+}
+
+fn link_graph(links: Vec<DocumentLinkPB>) -> DocumentLinkGraphPB {
+  let mut nodes: Vec<LinkNodePB> = vec![];
+  let mut edges: Vec<LinkEdgePB> = vec![];
+  // The index of each node and edge, so the graph keeps the order of the links
+  let mut node_indexes: HashMap<String, usize> = HashMap::new();
+  let mut edge_indexes: HashMap<(String, String), usize> = HashMap::new();
+  for link in links {
+    if link.source.state != LinkNodeStatePB::Normal {
+      continue;
+    }
+    let edge_key = (link.source.id.clone(), link.target.id.clone());
+    match edge_indexes.get(&edge_key) {
+      Some(index) => edges[*index].mention_count += 1,
+      None => {
+        edge_indexes.insert(edge_key, edges.len());
+        edges.push(LinkEdgePB {
+          source_id: link.source.id.clone(),
+          target_id: link.target.id.clone(),
+          mention_count: 1,
+        });
+      },
+    }
+    for node in [link.source, link.target] {
+      if !node_indexes.contains_key(&node.id) {
+        node_indexes.insert(node.id.clone(), nodes.len());
+        nodes.push(node);
+      }
+    }
+  }
+  DocumentLinkGraphPB { nodes, edges }
+}
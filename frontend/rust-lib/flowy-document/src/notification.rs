@@ -12,6 +12,11 @@ pub enum DocumentNotification {
   DidUpdateDocumentSnapshotState = 2,
   DidUpdateDocumentSyncState = 3,
   DidUpdateDocumentAwarenessState = 4,
+  DidCreateComment = 5,
+  DidUpdateDocumentOutline = 6,
+  DidUpdateDocumentStatistics = 7,
+  // Sent to the workspace when the tasks of a document change
+  DidUpdateTasks = 8,
 }
 
 impl std::convert::From<DocumentNotification> for i32 {
@@ -26,6 +31,10 @@ impl std::convert::From<i32> for DocumentNotification {
       2 => DocumentNotification::DidUpdateDocumentSnapshotState,
       3 => DocumentNotification::DidUpdateDocumentSyncState,
       4 => DocumentNotification::DidUpdateDocumentAwarenessState,
+      5 => DocumentNotification::DidCreateComment,
+      6 => DocumentNotification::DidUpdateDocumentOutline,
+      7 => DocumentNotification::DidUpdateDocumentStatistics,
+      8 => DocumentNotification::DidUpdateTasks,
       _ => DocumentNotification::Unknown,
     }
   }
@@ -12,6 +12,7 @@ pub const HEIGHT: &str = "height";
 pub const URL: &str = "url";
 pub const CAPTION: &str = "caption";
 pub const ALIGN: &str = "align";
+pub const NAME: &str = "name";
 
 pub const PAGE: &str = "page";
 pub const HEADING: &str = "heading";
@@ -25,6 +26,7 @@ pub const CALLOUT: &str = "callout";
 pub const IMAGE: &str = "image";
 pub const DIVIDER: &str = "divider";
 pub const MATH_EQUATION: &str = "math_equation";
+pub const FILE: &str = "file";
 pub const BOLD: &str = "bold";
 pub const ITALIC: &str = "italic";
 pub const STRIKETHROUGH: &str = "strikethrough";
@@ -35,6 +37,15 @@ pub const BG_COLOR: &str = "bg_color";
 
 pub const FORMULA: &str = "formula";
 pub const MENTION: &str = "mention";
+pub const MENTION_TYPE: &str = "type";
+pub const MENTION_PAGE_ID: &str = "page_id";
+pub const MENTION_PAGE: &str = "page";
+pub const MENTION_CHILD_PAGE: &str = "childPage";
+pub const MENTION_DATE: &str = "date";
+pub const MENTION_REMINDER_ID: &str = "reminder_id";
+pub const MENTION_PERSON: &str = "person";
+pub const MENTION_PERSON_ID: &str = "person_id";
+pub const MENTION_PERSON_NAME: &str = "person_name";
 
 pub const TEXT_DIRECTION: &str = "text_direction";
 
@@ -55,6 +55,60 @@ impl DocumentDataParser {
     self.to_text_with_json(&json)
   }
 
+  /// Returns the id and the plain text of each block that has text, in the order of the document.
+  /// The range is ignored.
+  pub fn to_block_texts(&self) -> Vec<(String, String)> {
+    let mut texts = vec![];
+    self.collect_block_texts(&self.document_data.page_id, &mut texts);
+    texts
+  }
+
+  fn collect_block_texts(&self, block_id: &str, texts: &mut Vec<(String, String)>) {
+    let block = match self.document_data.blocks.get(block_id) {
+      Some(block) => block,
+      None => return,
+    };
+    if let Some(delta) = get_delta_for_block(block_id, &self.document_data) {
+      let text = delta.iter().map(|d| d.to_text()).collect::<String>();
+      if !text.trim().is_empty() {
+        texts.push((block_id.to_string(), text));
+      }
+    }
+    if let Some(children_ids) = self.document_data.meta.children_map.get(&block.children) {
+      for child_id in children_ids {
+        self.collect_block_texts(child_id, texts);
+      }
+    }
+  }
+
+  /// Returns the id of the block and the id of the mentioned view of each page mention, in the
+  /// order of the document. The range is ignored.
+  pub fn to_page_mentions(&self) -> Vec<(String, String)> {
+    let mut mentions = vec![];
+    self.collect_page_mentions(&self.document_data.page_id, &mut mentions);
+    mentions
+  }
+
+  fn collect_page_mentions(&self, block_id: &str, mentions: &mut Vec<(String, String)>) {
+    let block = match self.document_data.blocks.get(block_id) {
+      Some(block) => block,
+      None => return,
+    };
+    if let Some(delta) = get_delta_for_block(block_id, &self.document_data) {
+      for view_id in delta.iter().filter_map(|d| d.page_mention()) {
+        let mention = (block_id.to_string(), view_id.to_string());
+        if !mentions.contains(&mention) {
+          mentions.push(mention);
+        }
+      }
+    }
+    if let Some(children_ids) = self.document_data.meta.children_map.get(&block.children) {
+      for child_id in children_ids {
+        self.collect_page_mentions(child_id, mentions);
+      }
+    }
+  }
+
   /// Converts the document data to a nested JSON structure, considering the optional range.
   pub fn to_json(&self) -> Option<NestedBlock> {
     let root_id = &self.document_data.page_id;
@@ -128,7 +182,7 @@ impl DocumentDataParser {
     })
   }
 
-  fn get_delta(&self, block_id: &str) -> Option<Vec<InsertDelta>> {
+  pub(crate) fn get_delta(&self, block_id: &str) -> Option<Vec<InsertDelta>> {
     match &self.range {
       Some(range) if block_id == range.start.block_id => {
         get_delta_for_selection(&range.start, &self.document_data)
@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+
+use crate::parser::constant::{CHECKED, HEADING, LEVEL, MENTION, TODO_LIST};
+use crate::parser::document_data_parser::DocumentDataParser;
+
+/// Average reading speed in words per minute for the non-CJK text.
+const WORDS_PER_MINUTE: f64 = 200.0;
+/// Average reading speed in characters per minute for the CJK text.
+const CJK_CHARACTERS_PER_MINUTE: f64 = 500.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineHeading {
+  pub block_id: String,
+  pub level: u32,
+  pub text: String,
+  /// The headings that follow this heading with a deeper level.
+  pub children: Vec<OutlineHeading>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocumentStatistics {
+  /// A CJK character counts as one word.
+  pub words: u64,
+  /// The characters excluding the whitespaces.
+  pub characters: u64,
+  pub characters_with_spaces: u64,
+  pub cjk_characters: u64,
+  pub reading_time_minutes: u64,
+  /// The number of blocks of each type, excluding the page block.
+  pub block_counts: BTreeMap<String, u64>,
+  pub open_todos: u64,
+  pub checked_todos: u64,
+}
+
+impl DocumentDataParser {
+  /// Returns the headings of the document, nested by their levels. The headings out of the range
+  /// are omitted.
+  pub fn to_outline(&self) -> Vec<OutlineHeading> {
+    let headings = self
+      .block_ids_in_range()
+      .into_iter()
+      .filter_map(|block_id| {
+        let block = self.document_data.blocks.get(&block_id)?;
+        if block.ty != HEADING {
+          return None;
+        }
+        let level = block
+          .data
+          .get(LEVEL)
+          .and_then(|level| level.as_u64())
+          .unwrap_or(1) as u32;
+        Some(OutlineHeading {
+          text: self.block_text(&block_id),
+          block_id,
+          level,
+          children: vec![],
+        })
+      })
+      .collect::<Vec<_>>();
+    nest_headings(headings)
+  }
+
+  /// Returns the statistics of the text and the blocks in the range.
+  pub fn to_statistics(&self) -> DocumentStatistics {
+    let mut statistics = DocumentStatistics::default();
+    for block_id in self.block_ids_in_range() {
+      let block = match self.document_data.blocks.get(&block_id) {
+        Some(block) => block,
+        None => continue,
+      };
+      *statistics.block_counts.entry(block.ty.clone()).or_default() += 1;
+      if block.ty == TODO_LIST {
+        let checked = block
+          .data
+          .get(CHECKED)
+          .and_then(|checked| checked.as_bool())
+          .unwrap_or(false);
+        if checked {
+          statistics.checked_todos += 1;
+        } else {
+          statistics.open_todos += 1;
+        }
+      }
+      count_text(&self.block_text(&block_id), &mut statistics);
+    }
+
+    let minutes = (statistics.words - statistics.cjk_characters) as f64 / WORDS_PER_MINUTE
+      + statistics.cjk_characters as f64 / CJK_CHARACTERS_PER_MINUTE;
+    statistics.reading_time_minutes = minutes.ceil() as u64;
+    statistics
+  }
+
+  /// Returns the ids of the blocks in the order of the document, excluding the page block. If the
+  /// range is set, only the blocks from the start block to the end block are returned.
+  pub(crate) fn block_ids_in_range(&self) -> Vec<String> {
+    let mut block_ids = vec![];
+    self.collect_block_ids(&self.document_data.page_id, &mut block_ids);
+    block_ids.retain(|block_id| block_id != &self.document_data.page_id);
+
+    if let Some(range) = &self.range {
+      let start = block_ids
+        .iter()
+        .position(|block_id| block_id == &range.start.block_id);
+      let end = block_ids
+        .iter()
+        .position(|block_id| block_id == &range.end.block_id);
+      return match (start, end) {
+        (Some(start), Some(end)) if start <= end => block_ids[start..=end].to_vec(),
+        _ => vec![],
+      };
+    }
+    block_ids
+  }
+
+  fn collect_block_ids(&self, block_id: &str, block_ids: &mut Vec<String>) {
+    let block = match self.document_data.blocks.get(block_id) {
+      Some(block) => block,
+      None => return,
+    };
+    block_ids.push(block_id.to_string());
+    if let Some(children_ids) = self.document_data.meta.children_map.get(&block.children) {
+      for child_id in children_ids {
+        self.collect_block_ids(child_id, block_ids);
+      }
+    }
+  }
+
+  /// Returns the text of the block within the range. The mentions are not counted as text.
+  fn block_text(&self, block_id: &str) -> String {
+    self
+      .get_delta(block_id)
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|delta| {
+        delta
+          .attributes
+          .as_ref()
+          .map_or(true, |attributes| !attributes.contains_key(MENTION))
+      })
+      .map(|delta| delta.insert)
+      .collect()
+  }
+}
+
+fn nest_headings(headings: Vec<OutlineHeading>) -> Vec<OutlineHeading> {
+  let mut roots: Vec<OutlineHeading> = vec![];
+  for heading in headings {
+    insert_heading(&mut roots, heading);
+  }
+  roots
+}
+
+/// Insert the heading as the last child of the last heading that has a lower level.
+fn insert_heading(siblings: &mut Vec<OutlineHeading>, heading: OutlineHeading) {
+  match siblings.last_mut() {
+    Some(last) if last.level < heading.level => insert_heading(&mut last.children, heading),
+    _ => siblings.push(heading),
+  }
+}
+
+fn count_text(text: &str, statistics: &mut DocumentStatistics) {
+  let mut in_word = false;
+  for c in text.chars() {
+    statistics.characters_with_spaces += 1;
+    if c.is_whitespace() {
+      in_word = false;
+      continue;
+    }
+
+    statistics.characters += 1;
+    if is_cjk(c) {
+      statistics.cjk_characters += 1;
+      statistics.words += 1;
+      in_word = false;
+    } else if c.is_alphanumeric() {
+      if !in_word {
+        statistics.words += 1;
+      }
+      in_word = true;
+    } else {
+      // Punctuation separates the words, except the apostrophes and the hyphens inside a word.
+      in_word = in_word && matches!(c, '\'' | '’' | '-');
+    }
+  }
+}
+
+fn is_cjk(c: char) -> bool {
+  matches!(
+    c as u32,
+    0x3040..=0x30FF // Hiragana and Katakana
+      | 0x3400..=0x4DBF // CJK Unified Ideographs Extension A
+      | 0x4E00..=0x9FFF // CJK Unified Ideographs
+      | 0xAC00..=0xD7AF // Hangul Syllables
+      | 0xF900..=0xFAFF // CJK Compatibility Ideographs
+      | 0x20000..=0x2FA1F // CJK Unified Ideographs Extension B to F and the supplement
+  )
+}
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::parser::constant::{CHECKED, MENTION, TODO_LIST};
+use crate::parser::document_data_parser::DocumentDataParser;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskPerson {
+  pub id: String,
+  pub name: String,
+}
+
+/// A todo block of a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentTask {
+  pub block_id: String,
+  /// The text of the block without the mentions.
+  pub text: String,
+  pub checked: bool,
+  /// The earliest date mentioned in the block, as a timestamp in seconds.
+  pub due_at: Option<i64>,
+  /// The reminder of the earliest date.
+  pub reminder_id: Option<String>,
+  pub people: Vec<TaskPerson>,
+}
+
+impl DocumentDataParser {
+  /// Returns the todo blocks of the document in the order of the document.
+  pub fn to_tasks(&self) -> Vec<DocumentTask> {
+    self
+      .block_ids_in_range()
+      .into_iter()
+      .filter_map(|block_id| {
+        let block = self.document_data.blocks.get(&block_id)?;
+        if block.ty != TODO_LIST {
+          return None;
+        }
+        let checked = block
+          .data
+          .get(CHECKED)
+          .and_then(|checked| checked.as_bool())
+          .unwrap_or(false);
+
+        let mut task = DocumentTask {
+          block_id: block_id.clone(),
+          text: String::new(),
+          checked,
+          due_at: None,
+          reminder_id: None,
+          people: vec![],
+        };
+        for delta in self.get_delta(&block_id).unwrap_or_default() {
+          if let Some((date, reminder_id)) = delta.date_mention() {
+            if let Some(due_at) = parse_mention_date(date) {
+              if task.due_at.map_or(true, |earliest| due_at < earliest) {
+                task.due_at = Some(due_at);
+                task.reminder_id = reminder_id.map(|id| id.to_string());
+              }
+            }
+          } else if let Some((id, name)) = delta.person_mention() {
+            if !task.people.iter().any(|person| person.id == id) {
+              task.people.push(TaskPerson {
+                id: id.to_string(),
+                name: name.to_string(),
+              });
+            }
+          } else if !delta
+            .attributes
+            .as_ref()
+            .map_or(false, |attributes| attributes.contains_key(MENTION))
+          {
+            task.text.push_str(&delta.insert);
+          }
+        }
+        task.text = task.text.trim().to_string();
+        Some(task)
+      })
+      .collect()
+  }
+}
+
+/// Parse the date of a date mention to a timestamp in seconds. The dates without a timezone are in
+/// the local timezone.
+pub fn parse_mention_date(date: &str) -> Option<i64> {
+  if let Ok(date_time) = DateTime::parse_from_rfc3339(date) {
+    return Some(date_time.timestamp());
+  }
+  let date_time = NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S%.f")
+    .ok()
+    .or_else(|| {
+      NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })?;
+  Local
+    .from_local_datetime(&date_time)
+    .earliest()
+    .map(|date_time| date_time.timestamp())
+}
@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use flowy_error::{FlowyError, FlowyResult};
+use lib_infra::file_util::canonicalize_in_dir;
+use markdown::{CompileOptions, Constructs, Options, ParseOptions};
+use percent_encoding::percent_decode_str;
+use serde_json::{json, Value};
+
+use crate::parser::constant::*;
+use crate::parser::external::parser::ExternalDataToNestedJSONParser;
+use crate::parser::parser_entities::{InputType, InsertDelta, NestedBlock};
+use crate::parser::utils::convert_insert_delta_from_json;
+
+const UNCHECKED_PREFIX: &str = "[ ] ";
+const CHECKED_PREFIXES: [&str; 2] = ["[x] ", "[X] "];
+
+/// The page that a mention links to when the document is exported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageLink {
+  pub name: String,
+  pub href: String,
+}
+
+/// What a link or an image of a Markdown file points to after the import.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkTarget {
+  /// The link becomes a mention of the view.
+  Page(String),
+  /// The link or the image points to the url, such as an uploaded file.
+  Url(String),
+}
+
+/// Convert the Markdown to a page block. The Markdown is rendered to HTML first, so it's parsed the
+/// same way as the pasted HTML.
+pub fn markdown_to_nested_block(markdown: &str) -> FlowyResult<NestedBlock> {
+  let options = Options {
+    parse: ParseOptions {
+      // The task list items are kept as text, and turned into todo blocks afterwards.
+      constructs: Constructs {
+        gfm_task_list_item: false,
+        ..Constructs::gfm()
+      },
+      ..ParseOptions::gfm()
+    },
+    compile: CompileOptions::gfm(),
+  };
+  let html = markdown::to_html_with_options(markdown, &options)
+    .map_err(|err| FlowyError::invalid_data().with_context(err.to_string()))?;
+  Ok(html_to_nested_block(html))
+}
+
+/// Convert the HTML, such as an exported document, to a page block.
+pub fn html_to_nested_block(html: String) -> NestedBlock {
+  let mut block = ExternalDataToNestedJSONParser::new(html, InputType::Html)
+    .to_nested_block()
+    .unwrap_or_else(|| NestedBlock {
+      ty: PAGE.to_string(),
+      ..Default::default()
+    });
+  block.ty = PAGE.to_string();
+  normalize_block(&mut block);
+  block
+}
+
+/// Convert the page block to Markdown. The blocks that Markdown can't represent, such as the
+/// toggle lists and the callouts, are converted to the closest Markdown blocks.
+pub fn nested_block_to_markdown(block: &NestedBlock) -> String {
+  let mut markdown = block_to_markdown(block, 0);
+  if !markdown.is_empty() {
+    markdown.push('\n');
+  }
+  markdown
+}
+
+/// Replace the mentions of the pages in `links` with the links to the pages.
+pub fn mentions_to_links(block: &mut NestedBlock, links: &HashMap<String, PageLink>) {
+  if let Some(delta) = block
+    .data
+    .get(DELTA)
+    .and_then(convert_insert_delta_from_json)
+  {
+    let delta = delta
+      .into_iter()
+      .map(
+        |insert| match insert.page_mention().and_then(|id| links.get(id)) {
+          Some(link) => InsertDelta {
+            insert: link.name.clone(),
+            attributes: Some(HashMap::from([(HREF.to_string(), json!(link.href))])),
+          },
+          None => insert,
+        },
+      )
+      .collect::<Vec<_>>();
+    block.data.insert(
+      DELTA.to_string(),
+      serde_json::to_value(delta).unwrap_or_default(),
+    );
+  }
+
+  for child in block.children.iter_mut() {
+    mentions_to_links(child, links);
+  }
+}
+
+/// Returns the links and the image urls of the block and its children.
+pub fn collect_links(block: &NestedBlock) -> Vec<String> {
+  let mut links = vec![];
+  collect_block_links(block, &mut links);
+  links
+}
+
+/// Rewrite the links and the image urls that have a target. The links to pages become mentions.
+pub fn rewrite_links(block: &mut NestedBlock, targets: &HashMap<String, LinkTarget>) {
+  if block.ty == IMAGE || block.ty == FILE {
+    let target = block
+      .data
+      .get(URL)
+      .and_then(|url| url.as_str())
+      .and_then(|url| targets.get(url));
+    if let Some(LinkTarget::Url(url)) = target {
+      block.data.insert(URL.to_string(), json!(url));
+    }
+  }
+
+  if let Some(delta) = block
+    .data
+    .get(DELTA)
+    .and_then(convert_insert_delta_from_json)
+  {
+    let delta = delta
+      .into_iter()
+      .map(
+        |insert| match href_of(&insert).and_then(|href| targets.get(href)) {
+          Some(LinkTarget::Page(view_id)) => InsertDelta {
+            insert: "$".to_string(),
+            attributes: Some(HashMap::from([(
+              MENTION.to_string(),
+              json!({ MENTION_TYPE: MENTION_PAGE, MENTION_PAGE_ID: view_id }),
+            )])),
+          },
+          Some(LinkTarget::Url(url)) => {
+            let mut insert = insert;
+            if let Some(attributes) = insert.attributes.as_mut() {
+              attributes.insert(HREF.to_string(), json!(url));
+            }
+            insert
+          },
+          None => insert,
+        },
+      )
+      .collect::<Vec<_>>();
+    block.data.insert(
+      DELTA.to_string(),
+      serde_json::to_value(delta).unwrap_or_default(),
+    );
+  }
+
+  for child in block.children.iter_mut() {
+    rewrite_links(child, targets);
+  }
+}
+
+/// Returns the path of the file that the relative link points to. The links with a scheme, the
+/// absolute links and the anchors are not relative.
+pub fn resolve_relative_link(dir: &Path, link: &str) -> Option<PathBuf> {
+  if link.is_empty() || link.starts_with(['#', '/']) || link.contains(':') {
+    return None;
+  }
+  let link = link.split(['#', '?']).next()?;
+  let link = percent_decode_str(link).decode_utf8().ok()?;
+  let mut path = PathBuf::new();
+  for component in dir.join(link.as_ref()).components() {
+    match component {
+      Component::CurDir => {},
+      Component::ParentDir => {
+        path.pop();
+      },
+      component => path.push(component),
+    }
+  }
+  Some(path)
+}
+
+/// Returns the path of the file that the relative link points to, if the file exists inside the
+/// `root` of the export. The links that escape the export, like `../../.ssh/id_rsa`, are ignored so
+/// an imported file can't attach the other files on the disk.
+pub fn resolve_export_file(root: &Path, dir: &Path, link: &str) -> Option<PathBuf> {
+  let path = resolve_relative_link(dir, link)?;
+  canonicalize_in_dir(root, &path)
+}
+
+fn collect_block_links(block: &NestedBlock, links: &mut Vec<String>) {
+  if block.ty == IMAGE || block.ty == FILE {
+    if let Some(url) = block.data.get(URL).and_then(|url| url.as_str()) {
+      links.push(url.to_string());
+    }
+  }
+  if let Some(delta) = block
+    .data
+    .get(DELTA)
+    .and_then(convert_insert_delta_from_json)
+  {
+    links.extend(
+      delta
+        .iter()
+        .filter_map(href_of)
+        .map(|href| href.to_string()),
+    );
+  }
+  for child in &block.children {
+    collect_block_links(child, links);
+  }
+}
+
+fn href_of(insert: &InsertDelta) -> Option<&str> {
+  insert.attributes.as_ref()?.get(HREF)?.as_str()
+}
+
+/// Unwrap the images of the paragraphs, and turn the list items that start with a checkbox into
+/// todo blocks.
+fn normalize_block(block: &mut NestedBlock) {
+  for child in block.children.iter_mut() {
+    normalize_block(child);
+  }
+
+  let children = std::mem::take(&mut block.children);
+  for child in children {
+    let is_image_paragraph = child.ty == PARAGRAPH
+      && !child.data.contains_key(DELTA)
+      && !child.children.is_empty()
+      && child.children.iter().all(|block| block.ty == IMAGE);
+    if is_image_paragraph {
+      block.children.extend(child.children);
+    } else {
+      block.children.push(child);
+    }
+  }
+
+  if block.ty == BULLETED_LIST {
+    if let Some(mut delta) = block
+      .data
+      .get(DELTA)
+      .and_then(convert_insert_delta_from_json)
+    {
+      if let Some(first) = delta.first_mut() {
+        let checked = if first.insert.starts_with(UNCHECKED_PREFIX) {
+          Some(false)
+        } else if CHECKED_PREFIXES
+          .iter()
+          .any(|prefix| first.insert.starts_with(prefix))
+        {
+          Some(true)
+        } else {
+          None
+        };
+        if let Some(checked) = checked {
+          first.insert = first.insert[UNCHECKED_PREFIX.len()..].to_string();
+          if first.insert.is_empty() {
+            delta.remove(0);
+          }
+          block.ty = TODO_LIST.to_string();
+          block.data.insert(CHECKED.to_string(), Value::Bool(checked));
+          block.data.insert(
+            DELTA.to_string(),
+            serde_json::to_value(delta).unwrap_or_default(),
+          );
+        }
+      }
+    }
+  }
+}
+
+fn block_to_markdown(block: &NestedBlock, number: usize) -> String {
+  let text = block
+    .data
+    .get(DELTA)
+    .and_then(convert_insert_delta_from_json)
+    .map(|delta| delta_to_markdown(&delta))
+    .unwrap_or_default();
+  let children = children_to_markdown(&block.children);
+  let data_str = |key: &str| {
+    block
+      .data
+      .get(key)
+      .and_then(|value| value.as_str())
+      .unwrap_or_default()
+      .to_string()
+  };
+
+  match block.ty.as_str() {
+    HEADING => {
+      let level = block
+        .data
+        .get(LEVEL)
+        .and_then(|level| level.as_u64())
+        .unwrap_or(1)
+        .clamp(1, 6) as usize;
+      join_blocks(format!("{} {}", "#".repeat(level), text), children)
+    },
+    BULLETED_LIST | TOGGLE_LIST => list_item_to_markdown("- ", text, children),
+    NUMBERED_LIST => list_item_to_markdown(&format!("{}. ", number), text, children),
+    TODO_LIST => {
+      let checked = block
+        .data
+        .get(CHECKED)
+        .and_then(|checked| checked.as_bool())
+        .unwrap_or(false);
+      let marker = if checked { "- [x] " } else { "- [ ] " };
+      list_item_to_markdown(marker, text, children)
+    },
+    QUOTE => prefix_lines(&join_blocks(text, children), "> "),
+    CALLOUT => {
+      let text = format!("{} {}", data_str(ICON), text);
+      prefix_lines(&join_blocks(text.trim().to_string(), children), "> ")
+    },
+    CODE => {
+      let code = block
+        .data
+        .get(DELTA)
+        .and_then(convert_insert_delta_from_json)
+        .map(|delta| {
+          delta
+            .iter()
+            .map(|insert| insert.to_text())
+            .collect::<String>()
+        })
+        .unwrap_or_default();
+      format!("```{}\n{}\n```", data_str(LANGUAGE), code)
+    },
+    DIVIDER => "---".to_string(),
+    IMAGE => format!("![]({})", data_str(URL)),
+    FILE => {
+      let url = data_str(URL);
+      let name = data_str(NAME);
+      let name = if name.is_empty() { url.clone() } else { name };
+      format!("[{}]({})", escape_markdown(&name), url)
+    },
+    MATH_EQUATION => format!("$$\n{}\n$$", data_str(FORMULA)),
+    _ => join_blocks(text, children),
+  }
+}
+
+fn children_to_markdown(children: &[NestedBlock]) -> String {
+  let mut markdown = String::new();
+  let mut number = 0;
+  let mut prev_ty: Option<&str> = None;
+  for child in children {
+    if child.ty == NUMBERED_LIST {
+      number = match prev_ty {
+        Some(NUMBERED_LIST) => number + 1,
+        _ => child
+          .data
+          .get(NUMBER)
+          .and_then(|number| number.as_u64())
+          .unwrap_or(1) as usize,
+      };
+    }
+    let child_markdown = block_to_markdown(child, number);
+    if child_markdown.is_empty() {
+      continue;
+    }
+    if let Some(prev_ty) = prev_ty {
+      // The items of a list are not separated by blank lines.
+      let is_same_list = prev_ty == child.ty
+        && matches!(
+          prev_ty,
+          BULLETED_LIST | NUMBERED_LIST | TODO_LIST | TOGGLE_LIST
+        );
+      markdown.push_str(if is_same_list { "\n" } else { "\n\n" });
+    }
+    markdown.push_str(&child_markdown);
+    prev_ty = Some(&child.ty);
+  }
+  markdown
+}
+
+fn list_item_to_markdown(marker: &str, text: String, children: String) -> String {
+  let mut markdown = format!("{}{}", marker, text);
+  if !children.is_empty() {
+    // The children of a list item are indented to the start of its text.
+    let indent = " ".repeat(marker.len().min(4));
+    markdown.push('\n');
+    markdown.push_str(&prefix_lines(&children, &indent));
+  }
+  markdown
+}
+
+fn join_blocks(text: String, children: String) -> String {
+  match (text.is_empty(), children.is_empty()) {
+    (_, true) => text,
+    (true, false) => children,
+    (false, false) => format!("{}\n\n{}", text, children),
+  }
+}
+
+/// Prefix the lines of the text. The blank lines are only prefixed if the prefix is not blank.
+fn prefix_lines(text: &str, prefix: &str) -> String {
+  text
+    .split('\n')
+    .map(|line| {
+      if line.is_empty() {
+        prefix.trim_end().to_string()
+      } else {
+        format!("{}{}", prefix, line)
+      }
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn delta_to_markdown(delta: &[InsertDelta]) -> String {
+  delta.iter().map(insert_to_markdown).collect()
+}
+
+fn insert_to_markdown(insert: &InsertDelta) -> String {
+  let attributes = match &insert.attributes {
+    Some(attributes) => attributes,
+    None => return escape_markdown(&insert.insert).replace('\n', "  \n"),
+  };
+  if attributes.contains_key(MENTION) {
+    return if let Some((date, _)) = insert.date_mention() {
+      date.to_string()
+    } else if let Some((_, name)) = insert.person_mention() {
+      format!("@{}", escape_markdown(name))
+    } else {
+      String::new()
+    };
+  }
+  if let Some(formula) = attributes.get(FORMULA).and_then(|formula| formula.as_str()) {
+    return format!("${}$", formula);
+  }
+
+  let is_enabled = |key: &str| {
+    attributes
+      .get(key)
+      .and_then(|value| value.as_bool())
+      .unwrap_or(false)
+  };
+  let text = insert.insert.as_str();
+  // The leading and the trailing whitespaces can't be inside the emphasis.
+  let content = text.trim();
+  if content.is_empty() {
+    return escape_markdown(text);
+  }
+  let mut markdown = if is_enabled(CODE) {
+    if content.contains('`') {
+      format!("`` {} ``", content)
+    } else {
+      format!("`{}`", content)
+    }
+  } else {
+    escape_markdown(content)
+  };
+  if is_enabled(BOLD) {
+    markdown = format!("**{}**", markdown);
+  }
+  if is_enabled(ITALIC) {
+    markdown = format!("_{}_", markdown);
+  }
+  if is_enabled(STRIKETHROUGH) {
+    markdown = format!("~~{}~~", markdown);
+  }
+  if let Some(href) = attributes.get(HREF).and_then(|href| href.as_str()) {
+    markdown = format!("[{}]({})", markdown, href);
+  }
+
+  let leading = &text[..text.len() - text.trim_start().len()];
+  let trailing = &text[text.trim_end().len()..];
+  format!("{}{}{}", leading, markdown, trailing).replace('\n', "  \n")
+}
+
+fn escape_markdown(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '~') {
+      escaped.push('\\');
+    }
+    escaped.push(c);
+  }
+  escaped
+}
@@ -1,6 +1,9 @@
 pub mod constant;
 pub mod document_data_parser;
+pub mod document_outline;
+pub mod document_task;
 pub mod external;
 pub mod json;
+pub mod markdown;
 pub mod parser_entities;
 pub mod utils;
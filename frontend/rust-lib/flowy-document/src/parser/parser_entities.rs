@@ -2,10 +2,11 @@ use crate::parse::NotEmptyStr;
 use crate::parser::constant::*;
 use crate::parser::utils::{
   convert_insert_delta_from_json, convert_nested_block_children_to_html, delta_to_html,
-  delta_to_text, required_not_empty_str, serialize_color_attribute,
+  delta_to_text, is_safe_link, required_not_empty_str, serialize_color_attribute,
 };
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
+use lib_infra::util::escape_html;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -163,10 +164,59 @@ impl InsertDelta {
     self.insert.clone()
   }
 
+  /// Returns the id of the mentioned view if the delta is a page mention.
+  pub fn page_mention(&self) -> Option<&str> {
+    let mention = self.attributes.as_ref()?.get(MENTION)?;
+    match mention.get(MENTION_TYPE)?.as_str()? {
+      MENTION_PAGE | MENTION_CHILD_PAGE => mention
+        .get(MENTION_PAGE_ID)?
+        .as_str()
+        .filter(|view_id| !view_id.is_empty()),
+      _ => None,
+    }
+  }
+
+  /// Returns the date and the id of its reminder if the delta is a date mention. The date is an
+  /// ISO 8601 string.
+  pub fn date_mention(&self) -> Option<(&str, Option<&str>)> {
+    let mention = self.attributes.as_ref()?.get(MENTION)?;
+    if mention.get(MENTION_TYPE)?.as_str()? != MENTION_DATE {
+      return None;
+    }
+    let date = mention.get(MENTION_DATE)?.as_str()?;
+    let reminder_id = mention
+      .get(MENTION_REMINDER_ID)
+      .and_then(|id| id.as_str())
+      .filter(|id| !id.is_empty());
+    Some((date, reminder_id))
+  }
+
+  /// Returns the id and the name of the mentioned person if the delta is a person mention.
+  pub fn person_mention(&self) -> Option<(&str, &str)> {
+    let mention = self.attributes.as_ref()?.get(MENTION)?;
+    if mention.get(MENTION_TYPE)?.as_str()? != MENTION_PERSON {
+      return None;
+    }
+    let id = mention.get(MENTION_PERSON_ID)?.as_str()?;
+    let name = mention
+      .get(MENTION_PERSON_NAME)
+      .and_then(|name| name.as_str())
+      .unwrap_or_default();
+    Some((id, name))
+  }
+
+  /// The text and the attribute values are escaped, and the links that aren't safe, like the
+  /// `javascript:` links, are rendered as plain text.
   pub fn to_html(&self) -> String {
     let mut html = String::new();
     let mut style = String::new();
     let mut html_attributes = String::new();
+    let href = self
+      .attributes
+      .as_ref()
+      .and_then(|attrs| attrs.get(HREF))
+      .and_then(|href| href.as_str())
+      .filter(|href| is_safe_link(href));
     // If there are attributes, serialize them as a HashMap.
     if let Some(attrs) = &self.attributes {
       // Serialize the color attributes.
@@ -178,8 +228,13 @@ impl InsertDelta {
         BACKGROUND_COLOR,
       ));
       // Serialize the href attributes.
-      if let Some(href) = attrs.get(HREF) {
-        html.push_str(&format!("<{} {}={}>", A_TAG_NAME, HREF, href));
+      if let Some(href) = href {
+        html.push_str(&format!(
+          "<{} {}=\"{}\">",
+          A_TAG_NAME,
+          HREF,
+          escape_html(href)
+        ));
       }
       // Serialize the code attributes.
       if let Some(code) = attrs.get(CODE) {
@@ -214,19 +269,23 @@ impl InsertDelta {
           style.push_str(FONT_FAMILY_FANTASY);
         }
       }
-      if let Some(direction) = attrs.get(TEXT_DIRECTION) {
-        html_attributes.push_str(&format!(" {}=\"{}\"", DIR_ATTR_NAME, direction));
+      if let Some(direction) = attrs.get(TEXT_DIRECTION).and_then(|value| value.as_str()) {
+        html_attributes.push_str(&format!(
+          " {}=\"{}\"",
+          DIR_ATTR_NAME,
+          escape_html(direction)
+        ));
       }
     }
     if !style.is_empty() {
-      html_attributes.push_str(&format!(" {}=\"{}\"", STYLE, style));
+      html_attributes.push_str(&format!(" {}=\"{}\"", STYLE, escape_html(&style)));
     }
 
     if !html_attributes.is_empty() {
       html.push_str(&format!("<{}{}>", SPAN_TAG_NAME, html_attributes));
     }
     // Serialize the insert field.
-    html.push_str(&self.insert);
+    html.push_str(&escape_html(&self.insert));
 
     // Close the style tag.
     if !html_attributes.is_empty() {
@@ -237,7 +296,7 @@ impl InsertDelta {
       if attrs.contains_key(CODE) {
         html.push_str(&format!("</{}>", CODE_TAG_NAME));
       }
-      if attrs.contains_key(HREF) {
+      if href.is_some() {
         html.push_str(&format!("</{}>", A_TAG_NAME));
       }
     }
@@ -299,10 +358,15 @@ impl NestedBlock {
     match self.ty.as_str() {
       // <h1>Hello</h1>
       HEADING => {
-        let level = self.data.get(LEVEL).unwrap_or(&Value::Null);
-        if level.as_u64().unwrap_or(0) > 6 {
+        let level = self
+          .data
+          .get(LEVEL)
+          .and_then(|level| level.as_u64())
+          .unwrap_or(1);
+        if level > 6 {
           html.push_str(&format!("<{}>{}</{}>", H6_TAG_NAME, text_html, H6_TAG_NAME));
         } else {
+          let level = level.max(1);
           html.push_str(&format!("<h{}>{}</h{}>", level, text_html, level));
         }
       },
@@ -318,24 +382,33 @@ impl NestedBlock {
         html.push_str(&format!(
           "<{}>{}{}</{}>",
           ASIDE_TAG_NAME,
-          self
-            .data
-            .get(ICON)
-            .unwrap_or(&Value::Null)
-            .to_string()
-            .trim_matches('\"'),
+          escape_html(
+            self
+              .data
+              .get(ICON)
+              .unwrap_or(&Value::Null)
+              .to_string()
+              .trim_matches('\"')
+          ),
           text_html,
           ASIDE_TAG_NAME
         ));
       },
       // <img src="https://www.google.com/images/branding/googlelogo/2x/googlelogo_color_272x92dp.png" alt="Google Logo" />
       IMAGE => {
-        html.push_str(&format!(
-          "<{} src={} alt={} />",
-          IMG_TAG_NAME,
-          self.data.get(URL).unwrap(),
-          "AppFlowy-Image"
-        ));
+        if let Some(url) = self
+          .data
+          .get(URL)
+          .and_then(|url| url.as_str())
+          .filter(|url| is_safe_link(url))
+        {
+          html.push_str(&format!(
+            "<{} src=\"{}\" alt={} />",
+            IMG_TAG_NAME,
+            escape_html(url),
+            "AppFlowy-Image"
+          ));
+        }
       },
       // <hr />
       DIVIDER => {
@@ -347,7 +420,7 @@ impl NestedBlock {
         html.push_str(&format!(
           "<{}>{}</{}>",
           P_TAG_NAME,
-          formula.to_string().trim_matches('\"'),
+          escape_html(formula.to_string().trim_matches('\"')),
           P_TAG_NAME
         ));
       },
@@ -360,7 +433,7 @@ impl NestedBlock {
           CODE_TAG_NAME,
           CLASS,
           LANGUAGE,
-          language.to_string().trim_matches('\"'),
+          escape_html(language.to_string().trim_matches('\"')),
           text_html,
           CODE_TAG_NAME,
           PRE_TAG_NAME
@@ -124,3 +124,20 @@ pub fn serialize_color_attribute(
   }
   "".to_string()
 }
+
+/// Returns true if the link can be rendered in a `href`. Only the http, https and mailto links and
+/// the relative links are allowed, so a link like `javascript:alert(1)` is never clickable.
+pub fn is_safe_link(href: &str) -> bool {
+  // The browsers ignore the whitespaces and the control characters of a scheme
+  let href = href
+    .chars()
+    .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+    .collect::<String>()
+    .to_ascii_lowercase();
+  match href.find([':', '/', '?', '#']) {
+    Some(index) if href[index..].starts_with(':') => {
+      matches!(&href[..index], "http" | "https" | "mailto")
+    },
+    _ => true,
+  }
+}
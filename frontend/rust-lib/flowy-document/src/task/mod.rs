@@ -0,0 +1,127 @@
+mod task_sql;
+
+use std::sync::Arc;
+
+use collab_document::blocks::DocumentData;
+use flowy_error::{internal_error, FlowyResult};
+use lib_infra::util::timestamp;
+
+use crate::entities::DocumentTasksChangedPB;
+use crate::indexer::DocumentIndex;
+use crate::manager::DocumentUserService;
+use crate::notification::{send_notification, DocumentNotification};
+use crate::parser::document_data_parser::DocumentDataParser;
+use crate::parser::document_task::TaskPerson;
+pub use task_sql::DocumentTaskTable;
+use task_sql::*;
+
+#[derive(Debug, Clone, Default)]
+pub struct TaskQuery {
+  pub is_checked: Option<bool>,
+  /// The tasks that are due at or after the timestamp, in seconds.
+  pub due_after: Option<i64>,
+  /// The tasks that are due before the timestamp, in seconds.
+  pub due_before: Option<i64>,
+}
+
+/// The index of the todo blocks of the documents, including the row documents. It's updated by the
+/// [crate::indexer::DocumentIndexer] when a document is created, edited or deleted, and the
+/// DidUpdateTasks notification is sent to the workspace when the tasks of a document change.
+pub struct DocumentTasks {
+  user_service: Arc<dyn DocumentUserService>,
+}
+
+impl DocumentTasks {
+  pub fn new(user_service: Arc<dyn DocumentUserService>) -> Self {
+    Self { user_service }
+  }
+
+  pub fn query(&self, query: &TaskQuery) -> FlowyResult<Vec<DocumentTaskTable>> {
+    let uid = self.user_service.user_id()?;
+    select_tasks(
+      self.user_service.sqlite_connection(uid)?,
+      query.is_checked,
+      query.due_after,
+      query.due_before,
+    )
+  }
+
+  pub fn get_document_tasks(&self, document_id: &str) -> FlowyResult<Vec<DocumentTaskTable>> {
+    let uid = self.user_service.user_id()?;
+    select_document_tasks(self.user_service.sqlite_connection(uid)?, document_id)
+  }
+
+  fn notify_tasks_changed(&self, document_id: &str) {
+    if let Ok(workspace_id) = self.user_service.workspace_id() {
+      send_notification(&workspace_id, DocumentNotification::DidUpdateTasks)
+        .payload(DocumentTasksChangedPB {
+          document_id: document_id.to_string(),
+        })
+        .send();
+    }
+  }
+}
+
+impl DocumentIndex for DocumentTasks {
+  fn name(&self) -> &str {
+    "tasks"
+  }
+
+  /// Replace the tasks of the document with the todo blocks in the data.
+  fn index(&self, document_id: &str, data: Arc<DocumentData>) -> FlowyResult<()> {
+    let tasks = DocumentDataParser::new(data, None)
+      .to_tasks()
+      .into_iter()
+      .enumerate()
+      .map(|(position, task)| {
+        Ok(DocumentTaskTable {
+          document_id: document_id.to_string(),
+          block_id: task.block_id,
+          position: position as i32,
+          content: task.text,
+          is_checked: task.checked,
+          due_at: task.due_at,
+          reminder_id: task.reminder_id,
+          people: serde_json::to_string(&task.people).map_err(internal_error)?,
+        })
+      })
+      .collect::<FlowyResult<Vec<_>>>()?;
+
+    let uid = self.user_service.user_id()?;
+    let old_tasks = select_document_tasks(self.user_service.sqlite_connection(uid)?, document_id)?;
+    replace_document_tasks(
+      self.user_service.sqlite_connection(uid)?,
+      document_id,
+      &tasks,
+      timestamp(),
+    )?;
+    if old_tasks != tasks {
+      self.notify_tasks_changed(document_id);
+    }
+    Ok(())
+  }
+
+  fn remove(&self, document_id: &str) -> FlowyResult<()> {
+    let uid = self.user_service.user_id()?;
+    delete_document_tasks(self.user_service.sqlite_connection(uid)?, document_id)?;
+    self.notify_tasks_changed(document_id);
+    Ok(())
+  }
+
+  fn filter_unindexed_document_ids(&self, document_ids: Vec<String>) -> FlowyResult<Vec<String>> {
+    let uid = self.user_service.user_id()?;
+    let indexed = select_task_indexed_document_ids(self.user_service.sqlite_connection(uid)?)?;
+    Ok(
+      document_ids
+        .into_iter()
+        .filter(|document_id| !indexed.contains(document_id))
+        .collect(),
+    )
+  }
+}
+
+impl DocumentTaskTable {
+  pub fn people(&self) -> Vec<TaskPerson> {
+    serde_json::from_str(&self.people).unwrap_or_default()
+  }
+}
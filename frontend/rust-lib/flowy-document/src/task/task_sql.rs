@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::schema::{document_task_table, task_indexed_document_table};
+use flowy_sqlite::{
+  diesel, insert_into, query_dsl::*, DBConnection, ExpressionMethods, Insertable, Queryable,
+};
+
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq)]
+#[diesel(table_name = document_task_table)]
+pub struct DocumentTaskTable {
+  pub document_id: String,
+  pub block_id: String,
+  pub position: i32,
+  pub content: String,
+  pub is_checked: bool,
+  pub due_at: Option<i64>,
+  pub reminder_id: Option<String>,
+  /// The JSON array of the mentioned people
+  pub people: String,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = task_indexed_document_table)]
+struct TaskIndexedDocumentTable {
+  document_id: String,
+  indexed_at: i64,
+}
+
+/// Replace the tasks of the document and mark the document as indexed.
+pub fn replace_document_tasks(
+  mut conn: DBConnection,
+  document_id: &str,
+  tasks: &[DocumentTaskTable],
+  indexed_at: i64,
+) -> FlowyResult<()> {
+  conn.immediate_transaction(|conn| {
+    diesel::delete(
+      document_task_table::dsl::document_task_table
+        .filter(document_task_table::document_id.eq(document_id)),
+    )
+    .execute(conn)?;
+    if !tasks.is_empty() {
+      insert_into(document_task_table::table)
+        .values(tasks)
+        .execute(conn)?;
+    }
+    let indexed = TaskIndexedDocumentTable {
+      document_id: document_id.to_string(),
+      indexed_at,
+    };
+    insert_into(task_indexed_document_table::table)
+      .values(&indexed)
+      .on_conflict(task_indexed_document_table::document_id)
+      .do_update()
+      .set(task_indexed_document_table::indexed_at.eq(indexed_at))
+      .execute(conn)?;
+    Ok::<(), FlowyError>(())
+  })?;
+  Ok(())
+}
+
+pub fn delete_document_tasks(mut conn: DBConnection, document_id: &str) -> FlowyResult<()> {
+  conn.immediate_transaction(|conn| {
+    diesel::delete(
+      document_task_table::dsl::document_task_table
+        .filter(document_task_table::document_id.eq(document_id)),
+    )
+    .execute(conn)?;
+    diesel::delete(
+      task_indexed_document_table::dsl::task_indexed_document_table
+        .filter(task_indexed_document_table::document_id.eq(document_id)),
+    )
+    .execute(conn)?;
+    Ok::<(), FlowyError>(())
+  })?;
+  Ok(())
+}
+
+pub fn select_document_tasks(
+  mut conn: DBConnection,
+  document_id: &str,
+) -> FlowyResult<Vec<DocumentTaskTable>> {
+  let tasks = document_task_table::dsl::document_task_table
+    .filter(document_task_table::document_id.eq(document_id))
+    .order(document_task_table::position.asc())
+    .load::<DocumentTaskTable>(&mut *conn)?;
+  Ok(tasks)
+}
+
+/// Returns the tasks that match the filters. The tasks with a due date come first, ordered by the
+/// due date.
+pub fn select_tasks(
+  mut conn: DBConnection,
+  is_checked: Option<bool>,
+  due_after: Option<i64>,
+  due_before: Option<i64>,
+) -> FlowyResult<Vec<DocumentTaskTable>> {
+  let mut query = document_task_table::dsl::document_task_table.into_boxed();
+  if let Some(is_checked) = is_checked {
+    query = query.filter(document_task_table::is_checked.eq(is_checked));
+  }
+  if let Some(due_after) = due_after {
+    query = query.filter(document_task_table::due_at.ge(due_after));
+  }
+  if let Some(due_before) = due_before {
+    query = query.filter(document_task_table::due_at.lt(due_before));
+  }
+  let tasks = query
+    .order((
+      document_task_table::due_at.is_null(),
+      document_task_table::due_at.asc(),
+      document_task_table::document_id.asc(),
+      document_task_table::position.asc(),
+    ))
+    .load::<DocumentTaskTable>(&mut *conn)?;
+  Ok(tasks)
+}
+
+pub fn select_task_indexed_document_ids(mut conn: DBConnection) -> FlowyResult<HashSet<String>> {
+  let document_ids = task_indexed_document_table::dsl::task_indexed_document_table
+    .select(task_indexed_document_table::document_id)
+    .load::<String>(&mut *conn)?;
+  Ok(document_ids.into_iter().collect())
+}
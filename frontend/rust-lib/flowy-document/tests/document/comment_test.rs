@@ -0,0 +1,259 @@
+use collab::core::collab::DataSource;
+use collab::core::origin::CollabOrigin;
+use collab::preclude::updates::decoder::Decode;
+use collab::preclude::{Collab, ReadTxn, StateVector, Update};
+use collab_document::blocks::DocumentData;
+use collab_document::document::Document;
+use flowy_document::comment::{get_comment_threads, insert_comment_thread, Comment, CommentThread};
+use flowy_document::parser::json::parser::JsonToDocumentParser;
+use serde_json::json;
+
+use crate::document::util::{gen_document_id, DocumentTest};
+
+#[tokio::test]
+async fn create_and_reply_comment_thread_test() {
+  let (test, document_id, block_id, _) = create_and_open_text_document("Hello world").await;
+  let thread = test
+    .create_comment_thread(&document_id, &block_id, 6, 5, "Which world?".to_string())
+    .await
+    .unwrap();
+  assert_eq!(thread.anchor_text, "world");
+  assert_eq!(thread.comments.len(), 1);
+  assert!(!thread.is_resolved);
+
+  let reply = test
+    .reply_comment(&document_id, &thread.id, "This one".to_string())
+    .await
+    .unwrap();
+  assert_eq!(reply.thread_id, thread.id);
+
+  let threads = test.get_comment_threads(&document_id).await.unwrap();
+  assert_eq!(threads.len(), 1);
+  let contents = threads[0]
+    .comments
+    .iter()
+    .map(|comment| comment.content.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(contents, vec!["Which world?", "This one"]);
+}
+
+#[tokio::test]
+async fn resolve_and_reopen_comment_thread_test() {
+  let (test, document_id, block_id, _) = create_and_open_text_document("Hello world").await;
+  let thread = test
+    .create_comment_thread(&document_id, &block_id, 0, 5, "Hi".to_string())
+    .await
+    .unwrap();
+
+  let resolved = test
+    .resolve_comment_thread(&document_id, &thread.id, true)
+    .await
+    .unwrap();
+  assert!(resolved.is_resolved);
+  assert_eq!(resolved.resolved_by, Some(1));
+  assert_eq!(resolved.comments.len(), 1);
+
+  let reopened = test
+    .resolve_comment_thread(&document_id, &thread.id, false)
+    .await
+    .unwrap();
+  assert!(!reopened.is_resolved);
+  assert_eq!(reopened.resolved_at, None);
+}
+
+#[tokio::test]
+async fn comment_thread_out_of_text_test() {
+  let (test, document_id, block_id, _) = create_and_open_text_document("Hello").await;
+  let result = test
+    .create_comment_thread(&document_id, &block_id, 3, 5, "Hi".to_string())
+    .await;
+  assert!(result.is_err());
+
+  let result = test
+    .reply_comment(&document_id, "unknown_thread", "Hi".to_string())
+    .await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn remap_comment_anchor_after_text_changed_test() {
+  let (test, document_id, block_id, text_id) = create_and_open_text_document("Hello world").await;
+  let thread = test
+    .create_comment_thread(&document_id, &block_id, 6, 5, "Hi".to_string())
+    .await
+    .unwrap();
+
+  apply_text_delta(&test, &document_id, &text_id, json!([{"insert": "Oh, "}])).await;
+  let threads = test.get_comment_threads(&document_id).await.unwrap();
+  assert_eq!(threads[0].id, thread.id);
+  assert_eq!(threads[0].start, 10);
+  assert_eq!(threads[0].length, 5);
+
+  // The text inserted at the end of the range stays out of the range
+  apply_text_delta(
+    &test,
+    &document_id,
+    &text_id,
+    json!([{"retain": 15}, {"insert": "!"}]),
+  )
+  .await;
+  let threads = test.get_comment_threads(&document_id).await.unwrap();
+  assert_eq!((threads[0].start, threads[0].length), (10, 5));
+
+  // The text inserted inside the range extends the range
+  apply_text_delta(
+    &test,
+    &document_id,
+    &text_id,
+    json!([{"retain": 12}, {"insert": "ab"}]),
+  )
+  .await;
+  let threads = test.get_comment_threads(&document_id).await.unwrap();
+  assert_eq!((threads[0].start, threads[0].length), (10, 7));
+}
+
+#[tokio::test]
+async fn delete_comment_anchor_text_test() {
+  let (test, document_id, block_id, text_id) = create_and_open_text_document("Hello world").await;
+  test
+    .create_comment_thread(&document_id, &block_id, 6, 5, "Hi".to_string())
+    .await
+    .unwrap();
+
+  // Delete "o world", across the start of the range
+  apply_text_delta(
+    &test,
+    &document_id,
+    &text_id,
+    json!([{"retain": 4}, {"delete": 7}]),
+  )
+  .await;
+  let threads = test.get_comment_threads(&document_id).await.unwrap();
+  assert_eq!((threads[0].start, threads[0].length), (4, 0));
+}
+
+#[test]
+fn merge_comment_threads_created_on_two_devices_test() {
+  let document_id = gen_document_id();
+  let data = text_document_data("Hello world");
+  let text_id = data
+    .blocks
+    .values()
+    .find(|block| block.ty == "paragraph")
+    .and_then(|block| block.external_id.clone())
+    .unwrap();
+  let collab = Collab::new_with_origin(CollabOrigin::Empty, &document_id, vec![], false);
+  let doc_state = Document::create_with_data(collab, data)
+    .unwrap()
+    .encode_collab()
+    .unwrap()
+    .doc_state
+    .to_vec();
+  let open_collab = || {
+    Collab::new_with_source(
+      CollabOrigin::Empty,
+      &document_id,
+      DataSource::DocStateV1(doc_state.clone()),
+      vec![],
+      false,
+    )
+    .unwrap()
+  };
+
+  // Both devices create the first thread of the document at the same time.
+  let mut collab_1 = open_collab();
+  let mut collab_2 = open_collab();
+  for (collab, thread_id) in [(&mut collab_1, "thread_1"), (&mut collab_2, "thread_2")] {
+    let thread = CommentThread {
+      id: thread_id.to_string(),
+      block_id: "block".to_string(),
+      start: 0,
+      length: 5,
+      start_anchor: None,
+      end_anchor: None,
+      anchor_text: "Hello".to_string(),
+      created_by: 1,
+      created_at: 1,
+      resolved_by: None,
+      resolved_at: None,
+    };
+    let comment = Comment {
+      id: format!("{}_comment", thread_id),
+      thread_id: thread_id.to_string(),
+      author: 1,
+      content: thread_id.to_string(),
+      created_at: 1,
+    };
+    insert_comment_thread(collab, &text_id, &thread, &comment).unwrap();
+  }
+
+  let update_1 = collab_1
+    .transact()
+    .encode_state_as_update_v1(&StateVector::default());
+  let update_2 = collab_2
+    .transact()
+    .encode_state_as_update_v1(&StateVector::default());
+  collab_1
+    .transact_mut()
+    .apply_update(Update::decode_v1(&update_2).unwrap())
+    .unwrap();
+  collab_2
+    .transact_mut()
+    .apply_update(Update::decode_v1(&update_1).unwrap())
+    .unwrap();
+
+  for collab in [&collab_1, &collab_2] {
+    let threads = get_comment_threads(collab);
+    let thread_ids = threads
+      .iter()
+      .map(|(thread, _)| thread.id.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(thread_ids, vec!["thread_1", "thread_2"]);
+    assert!(threads.iter().all(|(_, comments)| comments.len() == 1));
+  }
+}
+
+async fn apply_text_delta(
+  test: &DocumentTest,
+  document_id: &str,
+  text_id: &str,
+  delta: serde_json::Value,
+) {
+  let document = test.editable_document(document_id).await.unwrap();
+  let mut document = document.write().await;
+  document.apply_text_delta(text_id, delta.to_string());
+}
+
+/// Returns the test, the document id, the id of the text block and the id of its text.
+async fn create_and_open_text_document(text: &str) -> (DocumentTest, String, String, String) {
+  let test = DocumentTest::new();
+  let document_id = gen_document_id();
+  let data = text_document_data(text);
+  let uid = test.user_service.user_id().unwrap();
+  test
+    .create_document(uid, &document_id, Some(data))
+    .await
+    .unwrap();
+  test.open_document(&document_id).await.unwrap();
+
+  let data = test.get_document_data(&document_id).await.unwrap();
+  let block = data
+    .blocks
+    .values()
+    .find(|block| block.ty == "paragraph")
+    .unwrap();
+  let text_id = block.external_id.clone().unwrap();
+  (test, document_id, block.id.clone(), text_id)
+}
+
+fn text_document_data(text: &str) -> DocumentData {
+  let json = json!({
+    "type": "page",
+    "data": {},
+    "children": [{"type": "paragraph", "data": {"delta": [{"insert": text}]}}]
+  })
+  .to_string();
+  JsonToDocumentParser::json_str_to_document(&json)
+    .unwrap()
+    .into()
+}
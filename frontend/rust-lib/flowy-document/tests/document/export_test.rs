@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use collab_document::blocks::DocumentData;
+use flowy_document::export::{document_data_from_doc_state, export_document_data};
+use flowy_document::parser::json::parser::JsonToDocumentParser;
+use flowy_folder_pub::export::{ExportFormat, ExportedPage};
+use serde_json::json;
+
+use crate::document::util::{gen_document_id, DocumentTest, TEST_STORAGE_URL_PREFIX};
+
+#[tokio::test]
+async fn export_document_to_markdown_test() {
+  let test = DocumentTest::new();
+  let temp_dir = tempfile::tempdir().unwrap();
+  let image_path = temp_dir.path().join("a b.png");
+  std::fs::write(&image_path, b"image").unwrap();
+
+  // The image in the storage is named like the one on the disk.
+  let cloud_image_url = format!("{}A B.png", TEST_STORAGE_URL_PREFIX);
+
+  let document_id = gen_document_id();
+  let target_id = gen_document_id();
+  let json = json!({
+    "type": "page",
+    "data": {},
+    "children": [
+      {"type": "heading", "data": {"level": 1, "delta": [{"insert": "Notes"}]}},
+      {"type": "todo_list", "data": {"checked": true, "delta": [{"insert": "Ship"}]}},
+      {"type": "paragraph", "data": {"delta": [
+        {"insert": "See "},
+        {"insert": "$", "attributes": {"mention": {"type": "page", "page_id": &target_id}}}
+      ]}},
+      {"type": "image", "data": {"url": image_path.to_str().unwrap()}},
+      {"type": "image", "data": {"url": &cloud_image_url}}
+    ]
+  })
+  .to_string();
+  let data: DocumentData = JsonToDocumentParser::json_str_to_document(&json)
+    .unwrap()
+    .into();
+  let uid = test.user_service.user_id().unwrap();
+  test
+    .create_document(uid, &document_id, Some(data))
+    .await
+    .unwrap();
+
+  let pages = HashMap::from([(
+    target_id,
+    ExportedPage {
+      name: "Roadmap".to_string(),
+      path: "Projects/Roadmap.md".to_string(),
+    },
+  )]);
+  let files = test
+    .export_document(
+      &document_id,
+      ExportFormat::Markdown,
+      "Projects/Notes",
+      &pages,
+    )
+    .await
+    .unwrap();
+  assert_eq!(files.len(), 3);
+  assert_eq!(files[0].0, "Projects/Notes.md");
+  assert_eq!(
+    String::from_utf8(files[0].1.clone()).unwrap(),
+    "# Notes\n\n- [x] Ship\n\nSee [Roadmap](Roadmap.md)\n\n![](Notes_files/a%20b.png)\n\n![](Notes_files/A%20B%20%282%29.png)\n"
+  );
+  assert_eq!(files[1].0, "Projects/Notes_files/a b.png");
+  assert_eq!(files[1].1, b"image");
+  assert_eq!(files[2].0, "Projects/Notes_files/A B (2).png");
+  assert_eq!(files[2].1, cloud_image_url.as_bytes());
+}
+
+#[tokio::test]
+async fn export_document_to_html_test() {
+  let test = DocumentTest::new();
+  let document_id = gen_document_id();
+  let uid = test.user_service.user_id().unwrap();
+  test.create_document(uid, &document_id, None).await.unwrap();
+
+  let files = test
+    .export_document(&document_id, ExportFormat::Html, "Notes", &HashMap::new())
+    .await
+    .unwrap();
+  assert_eq!(files.len(), 1);
+  assert_eq!(files[0].0, "Notes.html");
+  assert!(String::from_utf8(files[0].1.clone())
+    .unwrap()
+    .starts_with("<meta charset=\"UTF-8\">"));
+}
+
+#[tokio::test]
+async fn render_published_document_test() {
+  let test = DocumentTest::new();
+  let document_id = gen_document_id();
+  let target_id = gen_document_id();
+  let json = json!({
+    "type": "page",
+    "data": {},
+    "children": [
+      {"type": "paragraph", "data": {"delta": [
+        {"insert": "Read "},
+        {"insert": "$", "attributes": {"mention": {"type": "page", "page_id": &target_id}}}
+      ]}}
+    ]
+  })
+  .to_string();
+  let data: DocumentData = JsonToDocumentParser::json_str_to_document(&json)
+    .unwrap()
+    .into();
+  let uid = test.user_service.user_id().unwrap();
+  test
+    .create_document(uid, &document_id, Some(data))
+    .await
+    .unwrap();
+
+  // The published data of the document is its doc state.
+  let doc_state = test
+    .get_encoded_collab_with_view_id(&document_id)
+    .await
+    .unwrap()
+    .doc_state
+    .to_vec();
+  let data = document_data_from_doc_state(&document_id, doc_state).unwrap();
+  let pages = HashMap::from([(
+    target_id,
+    ExportedPage {
+      name: "Guide".to_string(),
+      path: "guide.html".to_string(),
+    },
+  )]);
+  let document = export_document_data(data, "home", &pages, None).await;
+  assert!(document.files.is_empty());
+  let html = document.to_html_body();
+  assert!(html.contains("Read "));
+  assert!(html.contains("<a href=\"guide.html\">Guide</a>"));
+}
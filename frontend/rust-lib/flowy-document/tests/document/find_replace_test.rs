@@ -0,0 +1,333 @@
+use collab_document::blocks::DocumentData;
+use flowy_document::find_replace::{
+  find_in_document, FindOptions, FindScope, ReplaceMatch, TextMatch,
+};
+use flowy_document::parser::json::parser::JsonToDocumentParser;
+use flowy_document::parser::parser_entities::InsertDelta;
+use flowy_document::parser::utils::get_delta_for_block;
+use serde_json::{json, Value};
+
+use crate::document::util::{gen_document_id, DocumentTest};
+
+#[tokio::test]
+async fn find_text_in_document_test() {
+  let (test, document_id) =
+    create_document(vec![json!([{"insert": "Hello world, hello World"}])]).await;
+  let options = FindOptions {
+    query: "hello".to_string(),
+    ..Default::default()
+  };
+  let results = test
+    .find_text(FindScope::Document(document_id.clone()), &options, None)
+    .await
+    .unwrap();
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].0, document_id);
+  let ranges = results[0]
+    .1
+    .iter()
+    .map(|text_match| (text_match.start, text_match.length))
+    .collect::<Vec<_>>();
+  assert_eq!(ranges, vec![(0, 5), (13, 5)]);
+
+  let options = FindOptions {
+    query: "World".to_string(),
+    case_sensitive: true,
+    ..Default::default()
+  };
+  let results = test
+    .find_text(FindScope::Document(document_id), &options, None)
+    .await
+    .unwrap();
+  assert_eq!(results[0].1.len(), 1);
+  assert_eq!(results[0].1[0].start, 19);
+}
+
+#[tokio::test]
+async fn find_whole_word_and_regex_test() {
+  let (test, document_id) =
+    create_document(vec![json!([{"insert": "cat catalog 2024-10-19"}])]).await;
+  let options = FindOptions {
+    query: "cat".to_string(),
+    whole_word: true,
+    ..Default::default()
+  };
+  let results = test
+    .find_text(FindScope::Document(document_id.clone()), &options, None)
+    .await
+    .unwrap();
+  assert_eq!(results[0].1.len(), 1);
+
+  let options = FindOptions {
+    query: r"(\d{4})-(\d{2})-(\d{2})".to_string(),
+    is_regex: true,
+    ..Default::default()
+  };
+  let results = test
+    .find_text(
+      FindScope::Document(document_id.clone()),
+      &options,
+      Some("$3/$2/$1"),
+    )
+    .await
+    .unwrap();
+  let text_match = &results[0].1[0];
+  assert_eq!(text_match.text, "2024-10-19");
+  assert_eq!(text_match.replacement.as_deref(), Some("19/10/2024"));
+
+  let options = FindOptions {
+    query: "(".to_string(),
+    is_regex: true,
+    ..Default::default()
+  };
+  assert!(test
+    .find_text(FindScope::Document(document_id), &options, None)
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn replace_text_keeps_formatting_test() {
+  let (test, document_id) = create_document(vec![
+    json!([{"insert": "Hello "}, {"insert": "world", "attributes": {"bold": true}}]),
+    json!([{"insert": "world of worlds"}]),
+  ])
+  .await;
+  let options = FindOptions {
+    query: "world".to_string(),
+    whole_word: true,
+    ..Default::default()
+  };
+  let results = replace_found_text(&test, &document_id, &options, "earth").await;
+  assert_eq!(results, vec![(document_id.clone(), 2)]);
+
+  let data = test.get_document_data(&document_id).await.unwrap();
+  let deltas = paragraph_deltas(&data);
+  assert_eq!(deltas[0].len(), 2);
+  assert_eq!(deltas[0][1].insert, "earth");
+  assert_eq!(
+    deltas[0][1].attributes.as_ref().unwrap().get("bold"),
+    Some(&json!(true))
+  );
+  assert_eq!(deltas[1][0].insert, "earth of worlds");
+}
+
+#[tokio::test]
+async fn replace_only_previewed_matches_test() {
+  let (test, document_id) = create_document(vec![json!([{"insert": "one two one"}])]).await;
+  let options = FindOptions {
+    query: "one".to_string(),
+    ..Default::default()
+  };
+  let mut preview = test
+    .find_text(
+      FindScope::Document(document_id.clone()),
+      &options,
+      Some("1"),
+    )
+    .await
+    .unwrap();
+  let (_, mut matches) = preview.remove(0);
+  // Only the second match is confirmed
+  matches.remove(0);
+
+  // The text after the preview changes, which moves the confirmed match
+  let text_id = matches[0].text_id.clone();
+  test.open_document(&document_id).await.unwrap();
+  {
+    let document = test.editable_document(&document_id).await.unwrap();
+    let mut document = document.write().await;
+    document.apply_text_delta(&text_id, json!([{"insert": "zero "}]).to_string());
+  }
+
+  let results = test
+    .replace_text(vec![(document_id.clone(), to_replace_matches(matches))])
+    .await;
+  // The moved match is skipped instead of replacing the text at its old range
+  assert_eq!(results.len(), 1);
+  assert_eq!(*results[0].1.as_ref().unwrap(), 0);
+  let data = test.get_document_data(&document_id).await.unwrap();
+  assert_eq!(paragraph_deltas(&data)[0][0].insert, "zero one two one");
+
+  let preview = test
+    .find_text(
+      FindScope::Document(document_id.clone()),
+      &options,
+      Some("1"),
+    )
+    .await
+    .unwrap();
+  let matches = preview[0].1[1..].to_vec();
+  let results = test
+    .replace_text(vec![(document_id.clone(), to_replace_matches(matches))])
+    .await;
+  assert_eq!(*results[0].1.as_ref().unwrap(), 1);
+  let data = test.get_document_data(&document_id).await.unwrap();
+  assert_eq!(paragraph_deltas(&data)[0][0].insert, "zero one two 1");
+}
+
+#[tokio::test]
+async fn replace_text_collects_errors_of_documents_test() {
+  let (test, document_id) = create_document(vec![json!([{"insert": "hello"}])]).await;
+  let options = FindOptions {
+    query: "hello".to_string(),
+    ..Default::default()
+  };
+  let preview = test
+    .find_text(
+      FindScope::Document(document_id.clone()),
+      &options,
+      Some("bye"),
+    )
+    .await
+    .unwrap();
+  let matches = to_replace_matches(preview[0].1.clone());
+
+  let results = test
+    .replace_text(vec![
+      (gen_document_id(), matches.clone()),
+      (document_id.clone(), matches),
+    ])
+    .await;
+  assert_eq!(results.len(), 2);
+  assert!(results[0].1.is_err());
+  assert_eq!(*results[1].1.as_ref().unwrap(), 1);
+  let data = test.get_document_data(&document_id).await.unwrap();
+  assert_eq!(paragraph_deltas(&data)[0][0].insert, "bye");
+}
+
+#[test]
+fn find_text_skips_mentions_test() {
+  let json = json!({
+    "type": "page",
+    "data": {},
+    "children": [{"type": "paragraph", "data": {"delta": [
+      {"insert": "see "},
+      {"insert": "$", "attributes": {"mention": {"type": "page", "page_id": "page"}}},
+      {"insert": " page"}
+    ]}}]
+  })
+  .to_string();
+  let data: DocumentData = JsonToDocumentParser::json_str_to_document(&json)
+    .unwrap()
+    .into();
+  let options = FindOptions {
+    query: r"\$".to_string(),
+    is_regex: true,
+    ..Default::default()
+  };
+  let matches = find_in_document(data.clone(), &options.to_regex().unwrap(), &options, None);
+  assert!(matches.is_empty());
+
+  let options = FindOptions {
+    query: "page".to_string(),
+    ..Default::default()
+  };
+  let matches = find_in_document(data, &options.to_regex().unwrap(), &options, None);
+  assert_eq!(matches.len(), 1);
+  assert_eq!(matches[0].start, 6);
+  assert_eq!(matches[0].context, "see @ page");
+}
+
+#[test]
+fn find_whole_word_with_punctuation_test() {
+  let json = json!({
+    "type": "page",
+    "data": {},
+    "children": [{"type": "paragraph", "data": {"delta": [
+      {"insert": "C++ and C, #tag #tags, v1. v10 cat_v1. aab ab"}
+    ]}}]
+  })
+  .to_string();
+  let data: DocumentData = JsonToDocumentParser::json_str_to_document(&json)
+    .unwrap()
+    .into();
+  let find = |query: &str| {
+    let options = FindOptions {
+      query: query.to_string(),
+      whole_word: true,
+      ..Default::default()
+    };
+    find_in_document(data.clone(), &options.to_regex().unwrap(), &options, None)
+      .into_iter()
+      .map(|text_match| text_match.start)
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(find("C++"), vec![0]);
+  assert_eq!(find("#tag"), vec![11]);
+  // The `v1.` of `cat_v1.` continues a word
+  assert_eq!(find("v1."), vec![23]);
+  // The match inside `aab` is skipped, and the next one is still found
+  assert_eq!(find("ab"), vec![43]);
+}
+
+async fn create_document(deltas: Vec<Value>) -> (DocumentTest, String) {
+  let test = DocumentTest::new();
+  let document_id = gen_document_id();
+  let children = deltas
+    .into_iter()
+    .map(|delta| json!({"type": "paragraph", "data": {"delta": delta}}))
+    .collect::<Vec<_>>();
+  let json = json!({"type": "page", "data": {}, "children": children}).to_string();
+  let data: DocumentData = JsonToDocumentParser::json_str_to_document(&json)
+    .unwrap()
+    .into();
+  let uid = test.user_service.user_id().unwrap();
+  test
+    .create_document(uid, &document_id, Some(data))
+    .await
+    .unwrap();
+  (test, document_id)
+}
+
+fn paragraph_deltas(data: &DocumentData) -> Vec<Vec<InsertDelta>> {
+  let children = data
+    .meta
+    .children_map
+    .get(&data.blocks[&data.page_id].children)
+    .unwrap();
+  children
+    .iter()
+    .map(|block_id| get_delta_for_block(block_id, data).unwrap())
+    .collect()
+}
+
+/// Replace all the matches of the preview.
+async fn replace_found_text(
+  test: &DocumentTest,
+  document_id: &str,
+  options: &FindOptions,
+  replacement: &str,
+) -> Vec<(String, usize)> {
+  let preview = test
+    .find_text(
+      FindScope::Document(document_id.to_string()),
+      options,
+      Some(replacement),
+    )
+    .await
+    .unwrap();
+  let matches = preview
+    .into_iter()
+    .map(|(document_id, matches)| (document_id, to_replace_matches(matches)))
+    .collect();
+  test
+    .replace_text(matches)
+    .await
+    .into_iter()
+    .map(|(document_id, result)| (document_id, result.unwrap()))
+    .collect()
+}
+
+fn to_replace_matches(matches: Vec<TextMatch>) -> Vec<ReplaceMatch> {
+  matches
+    .into_iter()
+    .map(|text_match| ReplaceMatch {
+      block_id: text_match.block_id,
+      start: text_match.start,
+      length: text_match.length,
+      text: text_match.text,
+      replacement: text_match.replacement.unwrap(),
+    })
+    .collect()
+}
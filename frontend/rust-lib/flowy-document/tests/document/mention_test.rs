@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use collab_document::blocks::DocumentData;
+use flowy_document::entities::LinkNodeStatePB;
+use flowy_document::mention::{MentionView, MentionViewService};
+use flowy_document::parser::json::parser::JsonToDocumentParser;
+use flowy_error::FlowyResult;
+use lib_infra::async_trait::async_trait;
+
+use crate::document::util::{gen_document_id, DocumentTest};
+
+#[tokio::test]
+async fn backlinks_of_mentioned_view_test() {
+  let test = DocumentTest::new();
+  let target_id = gen_document_id();
+  let source_id = gen_document_id();
+  create_document_with_mentions(&test, &target_id, &[]).await;
+  create_document_with_mentions(&test, &source_id, &[&target_id, &target_id]).await;
+  test.set_mention_view_service(Arc::new(MockMentionViewService::new(vec![
+    mention_view(&source_id, "Source", false),
+    mention_view(&target_id, "Target", false),
+  ])));
+
+  let backlinks = test.get_backlinks(&target_id).await.unwrap();
+  assert_eq!(backlinks.len(), 2);
+  for link in backlinks {
+    assert_eq!(link.source.id, source_id);
+    assert_eq!(link.source.name, "Source");
+    assert_eq!(link.target.id, target_id);
+    assert!(!link.is_broken());
+  }
+  assert!(test.get_backlinks(&source_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn backlinks_from_trashed_and_deleted_documents_test() {
+  let test = DocumentTest::new();
+  let target_id = gen_document_id();
+  let trashed_id = gen_document_id();
+  let deleted_id = gen_document_id();
+  create_document_with_mentions(&test, &target_id, &[]).await;
+  create_document_with_mentions(&test, &trashed_id, &[&target_id]).await;
+  create_document_with_mentions(&test, &deleted_id, &[&target_id]).await;
+  test.set_mention_view_service(Arc::new(MockMentionViewService::new(vec![
+    mention_view(&target_id, "Target", false),
+    mention_view(&trashed_id, "Trashed", true),
+    mention_view(&deleted_id, "Deleted", false),
+  ])));
+
+  test.delete_document(&deleted_id).await.unwrap();
+  assert!(test.get_backlinks(&target_id).await.unwrap().is_empty());
+
+  let graph = test.get_link_graph().await.unwrap();
+  assert!(graph.nodes.is_empty());
+  assert!(graph.edges.is_empty());
+}
+
+#[tokio::test]
+async fn outgoing_links_to_deleted_view_test() {
+  let test = DocumentTest::new();
+  let source_id = gen_document_id();
+  let target_id = gen_document_id();
+  let deleted_id = gen_document_id();
+  create_document_with_mentions(&test, &source_id, &[&target_id, &deleted_id]).await;
+  test.set_mention_view_service(Arc::new(MockMentionViewService::new(vec![
+    mention_view(&source_id, "Source", false),
+    mention_view(&target_id, "Target", true),
+  ])));
+
+  let links = test.get_outgoing_links(&source_id).await.unwrap();
+  assert_eq!(links.len(), 2);
+  let target_link = links
+    .iter()
+    .find(|link| link.target.id == target_id)
+    .unwrap();
+  assert_eq!(target_link.target.state, LinkNodeStatePB::Trashed);
+  assert!(!target_link.is_broken());
+  let deleted_link = links
+    .iter()
+    .find(|link| link.target.id == deleted_id)
+    .unwrap();
+  assert!(deleted_link.is_broken());
+}
+
+#[tokio::test]
+async fn link_graph_test() {
+  let test = DocumentTest::new();
+  let a = gen_document_id();
+  let b = gen_document_id();
+  let c = gen_document_id();
+  create_document_with_mentions(&test, &a, &[&b, &b, &c]).await;
+  create_document_with_mentions(&test, &b, &[&c, &a]).await;
+  create_document_with_mentions(&test, &c, &[]).await;
+  test.set_mention_view_service(Arc::new(MockMentionViewService::new(vec![
+    mention_view(&a, "A", false),
+    mention_view(&b, "B", false),
+    mention_view(&c, "C", false),
+  ])));
+
+  let graph = test.get_link_graph().await.unwrap();
+  assert_eq!(graph.nodes.len(), 3);
+  assert_eq!(graph.edges.len(), 4);
+  let edge = graph
+    .edges
+    .iter()
+    .find(|edge| edge.source_id == a && edge.target_id == b)
+    .unwrap();
+  assert_eq!(edge.mention_count, 2);
+}
+
+async fn create_document_with_mentions(test: &DocumentTest, document_id: &str, view_ids: &[&str]) {
+  let mentions = view_ids
+    .iter()
+    .map(|view_id| {
+      format!(
+        r#"{{"type": "paragraph", "data": {{"delta": [{{"insert": "$", "attributes": {{"mention": {{"type": "page", "page_id": "{}"}}}}}}]}}}}"#,
+        view_id
+      )
+    })
+    .collect::<Vec<_>>()
+    .join(",");
+  let json = format!(
+    r#"{{"type": "page", "data": {{}}, "children": [{}]}}"#,
+    mentions
+  );
+  let data: DocumentData = JsonToDocumentParser::json_str_to_document(&json)
+    .unwrap()
+    .into();
+  let uid = test.user_service.user_id().unwrap();
+  test
+    .create_document(uid, document_id, Some(data))
+    .await
+    .unwrap();
+}
+
+pub(crate) fn mention_view(view_id: &str, name: &str, is_trashed: bool) -> MentionView {
+  MentionView {
+    view_id: view_id.to_string(),
+    row_id: None,
+    name: name.to_string(),
+    is_trashed,
+  }
+}
+
+pub(crate) struct MockMentionViewService {
+  views: HashMap<String, MentionView>,
+}
+
+impl MockMentionViewService {
+  pub(crate) fn new(views: Vec<MentionView>) -> Self {
+    Self {
+      views: views
+        .into_iter()
+        .map(|view| (view.view_id.clone(), view))
+        .collect(),
+    }
+  }
+}
+
+#[async_trait]
+impl MentionViewService for MockMentionViewService {
+  async fn get_mention_views(&self, ids: Vec<String>) -> FlowyResult<HashMap<String, MentionView>> {
+    Ok(
+      ids
+        .into_iter()
+        .filter_map(|id| self.views.get(&id).map(|view| (id, view.clone())))
+        .collect(),
+    )
+  }
+
+  async fn get_document_ids(&self) -> FlowyResult<Vec<String>> {
+    Ok(self.views.keys().cloned().collect())
+  }
+
+  async fn get_document_ids_in_view(&self, view_id: &str) -> FlowyResult<Vec<String>> {
+    Ok(vec![view_id.to_string()])
+  }
+}
@@ -1,5 +1,10 @@
+mod comment_test;
 mod document_insert_test;
 mod document_redo_undo_test;
 mod document_test;
 mod event_handler_test;
+mod export_test;
+mod find_replace_test;
+mod mention_test;
+mod task_test;
 pub mod util;
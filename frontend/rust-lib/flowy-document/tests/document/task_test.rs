@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use collab_document::blocks::DocumentData;
+use flowy_document::parser::document_data_parser::DocumentDataParser;
+use flowy_document::parser::document_task::parse_mention_date;
+use flowy_document::parser::json::parser::JsonToDocumentParser;
+use flowy_document::task::TaskQuery;
+use serde_json::{json, Value};
+
+use crate::document::mention_test::{mention_view, MockMentionViewService};
+use crate::document::util::{gen_document_id, DocumentTest};
+
+#[tokio::test]
+async fn query_tasks_across_documents_test() {
+  let test = DocumentTest::new();
+  let first_id = gen_document_id();
+  let second_id = gen_document_id();
+  let trashed_id = gen_document_id();
+  create_document(
+    &test,
+    &first_id,
+    vec![
+      todo(
+        json!([{"insert": "Write the report "}, date_mention("2024-11-20T00:00:00Z")]),
+        false,
+      ),
+      todo(json!([{"insert": "Buy milk"}]), true),
+    ],
+  )
+  .await;
+  create_document(
+    &test,
+    &second_id,
+    vec![
+      todo(
+        json!([{"insert": "Call "}, person_mention("1", "Lucas")]),
+        false,
+      ),
+      json!({"type": "paragraph", "data": {"delta": [{"insert": "Not a task"}]}}),
+    ],
+  )
+  .await;
+  create_document(
+    &test,
+    &trashed_id,
+    vec![todo(json!([{"insert": "Old"}]), false)],
+  )
+  .await;
+  test.set_mention_view_service(Arc::new(MockMentionViewService::new(vec![
+    mention_view(&first_id, "First", false),
+    mention_view(&second_id, "Second", false),
+    mention_view(&trashed_id, "Trashed", true),
+  ])));
+
+  let tasks = test.get_tasks(&TaskQuery::default()).await.unwrap();
+  let contents = tasks
+    .iter()
+    .map(|task| task.content.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(contents.len(), 3);
+  // The tasks with a due date come first.
+  assert_eq!(contents[0], "Write the report");
+  assert_eq!(tasks[0].view_name, "First");
+  assert_eq!(tasks[0].due_at, Some(1732060800));
+
+  let open_tasks = test
+    .get_tasks(&TaskQuery {
+      is_checked: Some(false),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  assert_eq!(open_tasks.len(), 2);
+  let call = open_tasks
+    .iter()
+    .find(|task| task.document_id == second_id)
+    .unwrap();
+  assert_eq!(call.content, "Call");
+  assert_eq!(call.people[0].name, "Lucas");
+
+  let due_tasks = test
+    .get_tasks(&TaskQuery {
+      due_after: Some(1731888000),
+      due_before: Some(1732492800),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  assert_eq!(due_tasks.len(), 1);
+  assert_eq!(due_tasks[0].document_id, first_id);
+}
+
+#[tokio::test]
+async fn check_task_from_index_test() {
+  let test = DocumentTest::new();
+  let document_id = gen_document_id();
+  create_document(
+    &test,
+    &document_id,
+    vec![todo(json!([{"insert": "Ship it"}]), false)],
+  )
+  .await;
+  test.set_mention_view_service(Arc::new(MockMentionViewService::new(vec![mention_view(
+    &document_id,
+    "Doc",
+    false,
+  )])));
+
+  let task = test
+    .get_tasks(&TaskQuery::default())
+    .await
+    .unwrap()
+    .remove(0);
+  assert!(!task.is_checked);
+  test
+    .set_task_checked(&document_id, &task.block_id, true)
+    .await
+    .unwrap();
+
+  let task = test
+    .get_tasks(&TaskQuery::default())
+    .await
+    .unwrap()
+    .remove(0);
+  assert!(task.is_checked);
+  let data = test.get_document_data(&document_id).await.unwrap();
+  assert_eq!(
+    data.blocks[&task.block_id].data.get("checked"),
+    Some(&json!(true))
+  );
+
+  // Only the todo blocks can be checked.
+  assert!(test
+    .set_task_checked(&document_id, &data.page_id, true)
+    .await
+    .is_err());
+}
+
+#[test]
+fn parse_tasks_test() {
+  let data = document_data(vec![todo(
+    json!([
+      {"insert": "Review "},
+      date_mention("2024-11-22T00:00:00Z"),
+      {"insert": " or "},
+      date_mention("2024-11-21T00:00:00Z"),
+    ]),
+    false,
+  )]);
+  let tasks = DocumentDataParser::new(Arc::new(data), None).to_tasks();
+  assert_eq!(tasks.len(), 1);
+  assert_eq!(tasks[0].text, "Review  or");
+  assert_eq!(tasks[0].due_at, parse_mention_date("2024-11-21T00:00:00Z"));
+  assert!(parse_mention_date("2024-11-21").is_some());
+  assert!(parse_mention_date("next week").is_none());
+}
+
+fn todo(delta: Value, checked: bool) -> Value {
+  json!({"type": "todo_list", "data": {"delta": delta, "checked": checked}})
+}
+
+fn date_mention(date: &str) -> Value {
+  json!({"insert": "$", "attributes": {"mention": {"type": "date", "date": date}}})
+}
+
+fn person_mention(id: &str, name: &str) -> Value {
+  json!({
+    "insert": "$",
+    "attributes": {"mention": {"type": "person", "person_id": id, "person_name": name}}
+  })
+}
+
+fn document_data(children: Vec<Value>) -> DocumentData {
+  let json = json!({"type": "page", "data": {}, "children": children}).to_string();
+  JsonToDocumentParser::json_str_to_document(&json)
+    .unwrap()
+    .into()
+}
+
+async fn create_document(test: &DocumentTest, document_id: &str, children: Vec<Value>) {
+  let uid = test.user_service.user_id().unwrap();
+  test
+    .create_document(uid, document_id, Some(document_data(children)))
+    .await
+    .unwrap();
+}
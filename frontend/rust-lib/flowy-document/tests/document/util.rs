@@ -20,13 +20,16 @@ use collab_integrate::CollabKVDB;
 use flowy_document::entities::{DocumentSnapshotData, DocumentSnapshotMeta};
 use flowy_document::manager::{DocumentManager, DocumentSnapshotService, DocumentUserService};
 use flowy_document_pub::cloud::*;
-use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
+use flowy_sqlite::{DBConnection, Database};
 use flowy_storage_pub::storage::{CreatedUpload, FileProgressReceiver, StorageService};
 use lib_infra::async_trait::async_trait;
 use lib_infra::box_any::BoxAny;
 
 pub struct DocumentTest {
   inner: DocumentManager,
+  // the manager only keeps a weak reference to the storage
+  _file_storage: Arc<dyn StorageService>,
 }
 
 impl DocumentTest {
@@ -50,7 +53,10 @@ impl DocumentTest {
       Arc::downgrade(&file_storage),
       document_snapshot,
     );
-    Self { inner: manager }
+    Self {
+      inner: manager,
+      _file_storage: file_storage,
+    }
   }
 }
 
@@ -65,6 +71,7 @@ impl Deref for DocumentTest {
 pub struct FakeUser {
   workspace_id: String,
   collab_db: Arc<CollabKVDB>,
+  sqlite_db: Database,
 }
 
 impl FakeUser {
@@ -73,11 +80,13 @@ impl FakeUser {
 
     let tempdir = TempDir::new().unwrap();
     let path = tempdir.into_path();
-    let collab_db = Arc::new(CollabKVDB::open(path).unwrap());
+    let collab_db = Arc::new(CollabKVDB::open(path.clone()).unwrap());
+    let sqlite_db = flowy_sqlite::init(path.join("sqlite")).unwrap();
     let workspace_id = uuid::Uuid::new_v4().to_string();
 
     Self {
       collab_db,
+      sqlite_db,
       workspace_id,
     }
   }
@@ -99,6 +108,10 @@ impl DocumentUserService for FakeUser {
   fn device_id(&self) -> Result<String, FlowyError> {
     Ok("".to_string())
   }
+
+  fn sqlite_connection(&self, _uid: i64) -> Result<DBConnection, FlowyError> {
+    self.sqlite_db.get_connection().map_err(internal_error)
+  }
 }
 
 pub fn setup_log() {
@@ -182,6 +195,9 @@ impl DocumentCloudService for LocalTestDocumentCloudServiceImpl {
   }
 }
 
+/// The urls of the files in [DocumentTestFileStorageService] start with this prefix.
+pub const TEST_STORAGE_URL_PREFIX: &str = "https://storage.appflowy.test/";
+
 pub struct DocumentTestFileStorageService;
 
 #[async_trait]
@@ -203,6 +219,27 @@ impl StorageService for DocumentTestFileStorageService {
     todo!()
   }
 
+  async fn copy_object(
+    &self,
+    _url: &str,
+    _workspace_id: &str,
+    _parent_dir: &str,
+  ) -> FlowyResult<Option<String>> {
+    todo!()
+  }
+
+  async fn get_object_size(&self, _url: &str) -> FlowyResult<Option<u64>> {
+    todo!()
+  }
+
+  async fn get_object_data(&self, url: &str) -> FlowyResult<Option<(String, Vec<u8>)>> {
+    Ok(
+      url
+        .strip_prefix(TEST_STORAGE_URL_PREFIX)
+        .map(|file_name| (file_name.to_string(), url.as_bytes().to_vec())),
+    )
+  }
+
   async fn start_upload(&self, _record: &BoxAny) -> Result<(), FlowyError> {
     todo!()
   }
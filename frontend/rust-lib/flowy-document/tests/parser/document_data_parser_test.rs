@@ -16,6 +16,58 @@ async fn document_data_parse_json_test() {
   assert_eq!(read_me_json, json);
 }
 
+#[tokio::test]
+async fn document_data_to_block_texts_test() {
+  let initial_json_str = include_str!("../assets/json/initial_document.json");
+  let document_data: DocumentData = JsonToDocumentParser::json_str_to_document(initial_json_str)
+    .unwrap()
+    .into();
+  let parser = DocumentDataParser::new(Arc::new(document_data.clone()), None);
+  let texts = parser.to_block_texts();
+  assert!(!texts.is_empty());
+  for (block_id, text) in &texts {
+    assert!(document_data.blocks.contains_key(block_id));
+    assert!(!text.trim().is_empty());
+  }
+  // The text of the blocks is in the order of the document
+  assert_eq!(texts[0].1, "Welcome to AppFlowy!");
+  assert_eq!(texts[1].1, "Here are the basics");
+}
+
+#[tokio::test]
+async fn document_data_to_page_mentions_test() {
+  let json_str = r#"{
+    "type": "page",
+    "data": {},
+    "children": [
+      {"type": "paragraph", "data": {"delta": [
+        {"insert": "See "},
+        {"insert": "$", "attributes": {"mention": {"type": "page", "page_id": "view_1"}}},
+        {"insert": "$", "attributes": {"mention": {"type": "date", "date": "2024-01-01"}}},
+        {"insert": "$", "attributes": {"mention": {"type": "page", "page_id": "view_1"}}}
+      ]}},
+      {"type": "toggle_list", "data": {"delta": [{"insert": "Toggle"}]}, "children": [
+        {"type": "paragraph", "data": {"delta": [
+          {"insert": "$", "attributes": {"mention": {"type": "childPage", "page_id": "view_2"}}}
+        ]}}
+      ]}
+    ]
+  }"#;
+  let document_data: DocumentData = JsonToDocumentParser::json_str_to_document(json_str)
+    .unwrap()
+    .into();
+  let parser = DocumentDataParser::new(Arc::new(document_data.clone()), None);
+  let mentions = parser.to_page_mentions();
+  let view_ids = mentions
+    .iter()
+    .map(|(_, view_id)| view_id.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(view_ids, vec!["view_1", "view_2"]);
+  for (block_id, _) in &mentions {
+    assert!(document_data.blocks.contains_key(block_id));
+  }
+}
+
 // range_1 is a range from the 2nd block to the 8th block
 #[tokio::test]
 async fn document_data_to_json_with_range_1_test() {
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use collab_document::blocks::DocumentData;
+use flowy_document::parser::document_data_parser::DocumentDataParser;
+use flowy_document::parser::json::parser::JsonToDocumentParser;
+use flowy_document::parser::parser_entities::{Range, Selection};
+use serde_json::json;
+
+fn document_data() -> DocumentData {
+  let json = json!({
+    "type": "page",
+    "data": {},
+    "children": [
+      {"type": "heading", "data": {"level": 1, "delta": [{"insert": "Introduction"}]}},
+      {"type": "paragraph", "data": {"delta": [{"insert": "Hello, well-known world!"}]}},
+      {"type": "heading", "data": {"level": 2, "delta": [{"insert": "Background"}]}},
+      {"type": "heading", "data": {"level": 3, "delta": [{"insert": "History"}]}},
+      {"type": "todo_list", "data": {"checked": true, "delta": [{"insert": "你好世界"}]}},
+      {"type": "todo_list", "data": {"checked": false, "delta": [
+        {"insert": "Read "},
+        {"insert": "$", "attributes": {"mention": {"type": "page", "page_id": "view_1"}}}
+      ]}},
+      {"type": "heading", "data": {"level": 1, "delta": [{"insert": "Summary"}]}}
+    ]
+  })
+  .to_string();
+  JsonToDocumentParser::json_str_to_document(&json)
+    .unwrap()
+    .into()
+}
+
+fn block_id_with_text(data: &DocumentData, text: &str) -> String {
+  let text_map = data.meta.text_map.as_ref().unwrap();
+  data
+    .blocks
+    .values()
+    .find(|block| {
+      block
+        .external_id
+        .as_ref()
+        .and_then(|text_id| text_map.get(text_id))
+        .map_or(false, |delta| delta.contains(text))
+    })
+    .unwrap()
+    .id
+    .clone()
+}
+
+#[test]
+fn document_outline_test() {
+  let parser = DocumentDataParser::new(Arc::new(document_data()), None);
+  let outline = parser.to_outline();
+  assert_eq!(outline.len(), 2);
+  assert_eq!(outline[0].text, "Introduction");
+  assert_eq!(outline[0].children.len(), 1);
+  assert_eq!(outline[0].children[0].text, "Background");
+  assert_eq!(outline[0].children[0].level, 2);
+  assert_eq!(outline[0].children[0].children[0].text, "History");
+  assert_eq!(outline[1].text, "Summary");
+  assert!(outline[1].children.is_empty());
+}
+
+#[test]
+fn document_statistics_test() {
+  let parser = DocumentDataParser::new(Arc::new(document_data()), None);
+  let statistics = parser.to_statistics();
+  // Introduction, Hello, well-known, world, Background, History, 4 CJK characters, Read, Summary
+  assert_eq!(statistics.words, 12);
+  assert_eq!(statistics.cjk_characters, 4);
+  assert_eq!(statistics.reading_time_minutes, 1);
+  assert_eq!(statistics.open_todos, 1);
+  assert_eq!(statistics.checked_todos, 1);
+  assert_eq!(statistics.block_counts.get("heading"), Some(&4));
+  assert_eq!(statistics.block_counts.get("todo_list"), Some(&2));
+  assert_eq!(statistics.block_counts.get("page"), None);
+  assert_eq!(statistics.characters_with_spaces - statistics.characters, 3);
+}
+
+#[test]
+fn document_statistics_in_range_test() {
+  let data = document_data();
+  let range = Range {
+    start: Selection {
+      block_id: block_id_with_text(&data, "Hello"),
+      index: 7,
+      length: 17,
+    },
+    end: Selection {
+      block_id: block_id_with_text(&data, "History"),
+      index: 0,
+      length: 7,
+    },
+  };
+  let parser = DocumentDataParser::new(Arc::new(data), Some(range));
+  let statistics = parser.to_statistics();
+  // well-known, world, Background, History
+  assert_eq!(statistics.words, 4);
+  assert_eq!(statistics.open_todos, 0);
+
+  let outline = parser.to_outline();
+  assert_eq!(outline.len(), 1);
+  assert_eq!(outline[0].text, "Background");
+  assert_eq!(outline[0].children[0].text, "History");
+}
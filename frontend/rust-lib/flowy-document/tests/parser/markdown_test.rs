@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use flowy_document::export::relative_link;
+use flowy_document::parser::constant::{CHECKED, DELTA, HEADING, IMAGE, TODO_LIST, URL};
+use flowy_document::parser::markdown::{
+  collect_links, markdown_to_nested_block, nested_block_to_markdown, resolve_export_file,
+  resolve_relative_link, rewrite_links, LinkTarget,
+};
+use flowy_document::parser::parser_entities::NestedBlock;
+use flowy_document::parser::utils::convert_insert_delta_from_json;
+use serde_json::json;
+
+fn find_block<'a>(block: &'a NestedBlock, ty: &str) -> Option<&'a NestedBlock> {
+  if block.ty == ty {
+    return Some(block);
+  }
+  block
+    .children
+    .iter()
+    .find_map(|child| find_block(child, ty))
+}
+
+fn text_of(block: &NestedBlock) -> String {
+  block
+    .data
+    .get(DELTA)
+    .and_then(convert_insert_delta_from_json)
+    .unwrap_or_default()
+    .into_iter()
+    .map(|insert| insert.insert)
+    .collect()
+}
+
+#[test]
+fn markdown_to_nested_block_test() {
+  let markdown = "# Roadmap\n\n- [x] Design\n- [ ] Build\n\n![diagram](diagram.png)\n";
+  let block = markdown_to_nested_block(markdown).unwrap();
+
+  let heading = find_block(&block, HEADING).unwrap();
+  assert_eq!(text_of(heading), "Roadmap");
+
+  let todos = block
+    .children
+    .iter()
+    .filter(|block| block.ty == TODO_LIST)
+    .collect::<Vec<_>>();
+  assert_eq!(todos.len(), 2);
+  assert_eq!(text_of(todos[0]), "Design");
+  assert_eq!(todos[0].data.get(CHECKED).unwrap().as_bool(), Some(true));
+  assert_eq!(text_of(todos[1]), "Build");
+  assert_eq!(todos[1].data.get(CHECKED).unwrap().as_bool(), Some(false));
+
+  let image = find_block(&block, IMAGE).unwrap();
+  assert_eq!(image.data.get(URL).unwrap().as_str(), Some("diagram.png"));
+}
+
+#[test]
+fn rewrite_page_links_test() {
+  let markdown = "See [Notes](Notes%20abc.md) and [AppFlowy](https://appflowy.io).\n";
+  let mut block = markdown_to_nested_block(markdown).unwrap();
+  assert_eq!(
+    collect_links(&block),
+    vec!["Notes%20abc.md", "https://appflowy.io"]
+  );
+
+  let targets = HashMap::from([(
+    "Notes%20abc.md".to_string(),
+    LinkTarget::Page("view_1".to_string()),
+  )]);
+  rewrite_links(&mut block, &targets);
+  assert_eq!(collect_links(&block), vec!["https://appflowy.io"]);
+  let mention = block.children[0]
+    .data
+    .get(DELTA)
+    .and_then(convert_insert_delta_from_json)
+    .unwrap()
+    .into_iter()
+    .find(|insert| insert.insert == "$")
+    .unwrap();
+  let mention = mention.attributes.unwrap();
+  assert_eq!(mention["mention"]["page_id"], "view_1");
+}
+
+#[test]
+fn resolve_relative_link_test() {
+  let dir = Path::new("export/Projects");
+  assert_eq!(
+    resolve_relative_link(dir, "Roadmap%20abc/Design.md"),
+    Some(PathBuf::from("export/Projects/Roadmap abc/Design.md"))
+  );
+  assert_eq!(
+    resolve_relative_link(dir, "../Home.md#intro"),
+    Some(PathBuf::from("export/Home.md"))
+  );
+  assert_eq!(resolve_relative_link(dir, "https://appflowy.io"), None);
+  assert_eq!(resolve_relative_link(dir, "#intro"), None);
+}
+
+#[test]
+fn resolve_export_file_outside_root_test() {
+  let temp_dir = tempfile::tempdir().unwrap();
+  let root = temp_dir.path().join("export");
+  let dir = root.join("Projects");
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(dir.join("image.png"), b"image").unwrap();
+  std::fs::write(root.join("Home.md"), b"home").unwrap();
+  std::fs::write(temp_dir.path().join("secret.txt"), b"secret").unwrap();
+
+  assert_eq!(
+    resolve_export_file(&root, &dir, "image.png"),
+    Some(dir.join("image.png").canonicalize().unwrap())
+  );
+  assert_eq!(
+    resolve_export_file(&root, &dir, "../Home.md"),
+    Some(root.join("Home.md").canonicalize().unwrap())
+  );
+  assert_eq!(resolve_export_file(&root, &dir, "../../secret.txt"), None);
+  assert_eq!(
+    resolve_export_file(&root, &dir, "../../../../../../etc/passwd"),
+    None
+  );
+  assert_eq!(resolve_export_file(&root, &dir, "missing.png"), None);
+}
+
+#[test]
+fn nested_block_to_markdown_test() {
+  let block: NestedBlock = serde_json::from_value(json!({
+    "type": "page",
+    "children": [
+      {"type": "bulleted_list", "data": {"delta": [{"insert": "Parent"}]}, "children": [
+        {"type": "bulleted_list", "data": {"delta": [{"insert": "Child"}]}}
+      ]},
+      {"type": "numbered_list", "data": {"delta": [{"insert": "One"}]}},
+      {"type": "numbered_list", "data": {"delta": [{"insert": "Two"}]}},
+      {"type": "code", "data": {"language": "rust", "delta": [{"insert": "fn main() {}"}]}},
+      {"type": "paragraph", "data": {"delta": [
+        {"insert": "Bold", "attributes": {"bold": true}},
+        {"insert": " text_with *stars*"}
+      ]}}
+    ]
+  }))
+  .unwrap();
+  assert_eq!(
+    nested_block_to_markdown(&block),
+    "- Parent\n  - Child\n\n1. One\n2. Two\n\n```rust\nfn main() {}\n```\n\n**Bold** text\\_with \\*stars\\*\n"
+  );
+}
+
+#[test]
+fn export_link_test() {
+  assert_eq!(relative_link("a/b", "a/c/d e.md"), "../c/d%20e.md");
+  assert_eq!(relative_link("", "a/b.md"), "a/b.md");
+  assert_eq!(relative_link("a", "a.md"), "../a.md");
+}
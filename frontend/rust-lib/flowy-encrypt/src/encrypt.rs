@@ -1,4 +1,7 @@
+use std::io::{Read, Write};
+
 use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::aead::stream::{DecryptorBE32, EncryptorBE32};
 use aes_gcm::aead::Aead;
 use aes_gcm::{Aes256Gcm, KeyInit};
 use anyhow::Result;
@@ -22,6 +25,16 @@ const ITERATIONS: u32 = 1000;
 /// The length of the nonce for AES-GCM encryption.
 const NONCE_LENGTH: usize = 12;
 
+/// The length of the nonce for the stream encryption. The stream uses 5 bytes of the AES-GCM nonce
+/// for the counter of the chunks and the last chunk flag.
+const STREAM_NONCE_LENGTH: usize = 7;
+
+/// The length of the plaintext chunks of the stream encryption.
+const STREAM_CHUNK_LENGTH: usize = 64 * 1024;
+
+/// The length of the authentication tag that is appended to each encrypted chunk.
+const TAG_LENGTH: usize = 16;
+
 /// Delimiter used to concatenate the passphrase and salt.
 const CONCATENATED_DELIMITER: &str = "$";
 
@@ -67,6 +80,98 @@ pub fn decrypt_data<T: AsRef<[u8]>>(data: T, combined_passphrase_salt: &str) ->
     .map_err(|e| anyhow::anyhow!("Decryption error: {:?}", e))
 }
 
+/// Encrypt the data of the reader into the writer in chunks using the STREAM construction of
+/// AES-GCM, so the data doesn't need to fit in memory.
+///
+/// # Arguments
+/// * `reader`: The data to encrypt.
+/// * `writer`: The destination of the encrypted data.
+/// * `combined_passphrase_salt`: The concatenated passphrase and salt.
+pub fn encrypt_stream<R: Read, W: Write>(
+  mut reader: R,
+  mut writer: W,
+  combined_passphrase_salt: &str,
+) -> Result<()> {
+  let (passphrase, salt) = split_passphrase_and_salt(combined_passphrase_salt)?;
+  let key = derive_key(passphrase, &salt)?;
+  let cipher = Aes256Gcm::new(GenericArray::from_slice(&key));
+  let nonce: [u8; STREAM_NONCE_LENGTH] = rand::thread_rng().gen();
+  writer.write_all(&nonce)?;
+
+  let mut encryptor = EncryptorBE32::from_aead(cipher, GenericArray::from_slice(&nonce));
+  let mut chunk = vec![0u8; STREAM_CHUNK_LENGTH];
+  let mut next_chunk = vec![0u8; STREAM_CHUNK_LENGTH];
+  let mut len = read_chunk(&mut reader, &mut chunk)?;
+  loop {
+    // The last chunk is encrypted differently, so the next chunk is read before encrypting this one.
+    let next_len = if len == chunk.len() {
+      read_chunk(&mut reader, &mut next_chunk)?
+    } else {
+      0
+    };
+    if next_len == 0 {
+      let ciphertext = encryptor
+        .encrypt_last(&chunk[..len])
+        .map_err(|e| anyhow::anyhow!("Encryption error: {:?}", e))?;
+      writer.write_all(&ciphertext)?;
+      writer.flush()?;
+      return Ok(());
+    }
+    let ciphertext = encryptor
+      .encrypt_next(&chunk[..len])
+      .map_err(|e| anyhow::anyhow!("Encryption error: {:?}", e))?;
+    writer.write_all(&ciphertext)?;
+    std::mem::swap(&mut chunk, &mut next_chunk);
+    len = next_len;
+  }
+}
+
+/// Decrypt the data of the reader, which was encrypted by [encrypt_stream], into the writer.
+///
+/// # Arguments
+/// * `reader`: The data to decrypt.
+/// * `writer`: The destination of the decrypted data.
+/// * `combined_passphrase_salt`: The concatenated passphrase and salt.
+pub fn decrypt_stream<R: Read, W: Write>(
+  mut reader: R,
+  mut writer: W,
+  combined_passphrase_salt: &str,
+) -> Result<()> {
+  let mut nonce = [0u8; STREAM_NONCE_LENGTH];
+  if read_chunk(&mut reader, &mut nonce)? != STREAM_NONCE_LENGTH {
+    return Err(anyhow::anyhow!("Ciphertext too short to include nonce."));
+  }
+  let (passphrase, salt) = split_passphrase_and_salt(combined_passphrase_salt)?;
+  let key = derive_key(passphrase, &salt)?;
+  let cipher = Aes256Gcm::new(GenericArray::from_slice(&key));
+
+  let mut decryptor = DecryptorBE32::from_aead(cipher, GenericArray::from_slice(&nonce));
+  let mut chunk = vec![0u8; STREAM_CHUNK_LENGTH + TAG_LENGTH];
+  let mut next_chunk = vec![0u8; STREAM_CHUNK_LENGTH + TAG_LENGTH];
+  let mut len = read_chunk(&mut reader, &mut chunk)?;
+  loop {
+    let next_len = if len == chunk.len() {
+      read_chunk(&mut reader, &mut next_chunk)?
+    } else {
+      0
+    };
+    if next_len == 0 {
+      let plaintext = decryptor
+        .decrypt_last(&chunk[..len])
+        .map_err(|e| anyhow::anyhow!("Decryption error: {:?}", e))?;
+      writer.write_all(&plaintext)?;
+      writer.flush()?;
+      return Ok(());
+    }
+    let plaintext = decryptor
+      .decrypt_next(&chunk[..len])
+      .map_err(|e| anyhow::anyhow!("Decryption error: {:?}", e))?;
+    writer.write_all(&plaintext)?;
+    std::mem::swap(&mut chunk, &mut next_chunk);
+    len = next_len;
+  }
+}
+
 /// Encrypt a string using AES-GCM and return the result as a base64 encoded string.
 ///
 /// # Arguments
@@ -137,6 +242,20 @@ fn split_passphrase_and_salt(combined: &str) -> Result<(&str, [u8; SALT_LENGTH])
   Ok((passphrase, salt_array))
 }
 
+/// Fills the buffer from the reader, unless the reader ends first. Returns the number of bytes read.
+fn read_chunk<R: Read>(reader: &mut R, buf: &mut [u8]) -> std::io::Result<usize> {
+  let mut len = 0;
+  while len < buf.len() {
+    match reader.read(&mut buf[len..]) {
+      Ok(0) => break,
+      Ok(n) => len += n,
+      Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {},
+      Err(err) => return Err(err),
+    }
+  }
+  Ok(len)
+}
+
 fn derive_key(passphrase: &str, salt: &[u8; SALT_LENGTH]) -> Result<[u8; KEY_LENGTH]> {
   let mut key = [0u8; KEY_LENGTH];
   pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), salt, ITERATIONS, &mut key)?;
@@ -169,4 +288,21 @@ mod tests {
     let decrypted = decrypt_data(encrypted, "invalid secret");
     assert!(decrypted.is_err())
   }
+
+  #[test]
+  fn encrypt_decrypt_stream_test() {
+    let secret = generate_encryption_secret();
+    for len in [0, 11, STREAM_CHUNK_LENGTH, STREAM_CHUNK_LENGTH * 2 + 5] {
+      let data = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+      let mut encrypted = vec![];
+      encrypt_stream(data.as_slice(), &mut encrypted, &secret).unwrap();
+      let mut decrypted = vec![];
+      decrypt_stream(encrypted.as_slice(), &mut decrypted, &secret).unwrap();
+      assert_eq!(data, decrypted);
+
+      let other_secret = generate_encryption_secret();
+      let mut decrypted = vec![];
+      assert!(decrypt_stream(encrypted.as_slice(), &mut decrypted, &other_secret).is_err());
+    }
+  }
 }
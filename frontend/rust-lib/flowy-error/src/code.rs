@@ -356,6 +356,12 @@ pub enum ErrorCode {
 
   #[error("Requested namespace has one or more invalid characters")]
   CustomNamespaceInvalidCharacter = 122,
+
+  #[error("The backup is corrupted")]
+  BackupCorrupted = 123,
+
+  #[error("The view is locked")]
+  ViewLocked = 124,
 }
 
 impl ErrorCode {
@@ -116,3 +116,13 @@ pub enum PublishPayload {
   Database(PublishDatabasePayload),
   Unknown,
 }
+
+impl PublishPayload {
+  pub fn meta(&self) -> Option<&PublishViewMeta> {
+    match self {
+      PublishPayload::Document(payload) => Some(&payload.meta),
+      PublishPayload::Database(payload) => Some(&payload.meta),
+      PublishPayload::Unknown => None,
+    }
+  }
+}
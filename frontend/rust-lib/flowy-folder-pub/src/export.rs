@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+/// The longest name of an exported file, in characters, without the extension.
+const MAX_FILE_NAME_LEN: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+  Markdown,
+  Html,
+}
+
+impl ExportFormat {
+  /// The extension of the exported documents.
+  pub fn extension(&self) -> &'static str {
+    match self {
+      ExportFormat::Markdown => "md",
+      ExportFormat::Html => "html",
+    }
+  }
+}
+
+/// A view of the export that the other views can link to.
+#[derive(Clone, Debug)]
+pub struct ExportedPage {
+  pub name: String,
+  /// The path of the main file of the view, relative to the root of the export.
+  pub path: String,
+}
+
+/// Returns a file name for the view name that is valid on all the platforms and not in `used`.
+pub fn export_file_name(used: &mut HashSet<String>, name: &str) -> String {
+  unique_file_name(used, name, None)
+}
+
+/// Returns a file name for the attached file that is valid on all the platforms and not in `used`.
+/// The extension of the file is kept.
+pub fn export_attachment_file_name(used: &mut HashSet<String>, file_name: &str) -> String {
+  match file_name.rsplit_once('.') {
+    Some((stem, extension)) if !stem.is_empty() && !extension.is_empty() => {
+      unique_file_name(used, stem, Some(extension))
+    },
+    _ => unique_file_name(used, file_name, None),
+  }
+}
+
+fn unique_file_name(used: &mut HashSet<String>, stem: &str, extension: Option<&str>) -> String {
+  let stem = sanitize_file_name(stem);
+  let stem = if stem.is_empty() { "Untitled" } else { &stem };
+  let with_extension = |stem: &str| match extension {
+    Some(extension) => format!("{}.{}", stem, sanitize_file_name(extension)),
+    None => stem.to_string(),
+  };
+
+  let mut file_name = with_extension(stem);
+  let mut n = 1;
+  // The file names are compared case-insensitively, as on Windows and macOS.
+  while used.contains(&file_name.to_lowercase()) {
+    n += 1;
+    file_name = with_extension(&format!("{} ({})", stem, n));
+  }
+  used.insert(file_name.to_lowercase());
+  file_name
+}
+
+fn sanitize_file_name(name: &str) -> String {
+  let name = name
+    .chars()
+    .map(|c| match c {
+      '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+      c if c.is_control() => '_',
+      c => c,
+    })
+    .take(MAX_FILE_NAME_LEN)
+    .collect::<String>();
+  name.trim().trim_end_matches('.').trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn export_file_name_test() {
+    let mut used = HashSet::new();
+    assert_eq!(export_file_name(&mut used, "a/b: c"), "a_b_ c");
+    assert_eq!(export_file_name(&mut used, "A_B_ C"), "A_B_ C (2)");
+    assert_eq!(export_file_name(&mut used, " . "), "Untitled");
+    assert_eq!(export_file_name(&mut used, "v1.2"), "v1.2");
+  }
+
+  #[test]
+  fn export_attachment_file_name_test() {
+    let mut used = HashSet::new();
+    assert_eq!(export_attachment_file_name(&mut used, "a.png"), "a.png");
+    assert_eq!(export_attachment_file_name(&mut used, "A.png"), "A (2).png");
+    assert_eq!(export_attachment_file_name(&mut used, "a.png"), "a (3).png");
+    assert_eq!(export_attachment_file_name(&mut used, ".env"), ".env");
+  }
+}
@@ -1,2 +1,3 @@
 pub mod cloud;
 pub mod entities;
+pub mod export;
@@ -0,0 +1,70 @@
+use crate::entities::{ViewIconPB, ViewPB, ViewSectionPB};
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+#[derive(ProtoBuf_Enum, Clone, Debug, PartialEq, Eq, Default)]
+pub enum BatchViewOperationPB {
+  #[default]
+  MoveToTrash = 0,
+  RestoreFromTrash = 1,
+  Move = 2,
+  Favorite = 3,
+  Visibility = 4,
+  Icon = 5,
+}
+
+/// Moves the views under the new parent, in the order of the ids. If `prev_view_id` is `None`, the
+/// views become the first children of the parent.
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct BatchMoveViewsPB {
+  #[pb(index = 1)]
+  pub view_ids: Vec<String>,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub new_parent_id: String,
+
+  #[pb(index = 3, one_of)]
+  pub prev_view_id: Option<String>,
+
+  #[pb(index = 4, one_of)]
+  pub from_section: Option<ViewSectionPB>,
+
+  #[pb(index = 5, one_of)]
+  pub to_section: Option<ViewSectionPB>,
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct BatchSetFavoritePB {
+  #[pb(index = 1)]
+  pub view_ids: Vec<String>,
+
+  #[pb(index = 2)]
+  pub is_favorite: bool,
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct BatchUpdateViewIconPB {
+  #[pb(index = 1)]
+  pub view_ids: Vec<String>,
+
+  // removes the icons of the views if it's None
+  #[pb(index = 2, one_of)]
+  pub icon: Option<ViewIconPB>,
+}
+
+/// The payload of the notification sent once a batch is applied.
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct BatchViewChangesPB {
+  #[pb(index = 1)]
+  pub operation: BatchViewOperationPB,
+
+  // the views after the batch, without their child views
+  #[pb(index = 2)]
+  pub views: Vec<ViewPB>,
+
+  // the parents whose child views changed
+  #[pb(index = 3)]
+  pub parent_view_ids: Vec<String>,
+}
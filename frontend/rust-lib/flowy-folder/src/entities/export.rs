@@ -0,0 +1,46 @@
+use crate::share::ExportFormat;
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+#[derive(Clone, Copy, Debug, Default, ProtoBuf_Enum)]
+pub enum ExportFormatPB {
+  #[default]
+  Markdown = 0,
+  HTML = 1,
+}
+
+impl From<ExportFormatPB> for ExportFormat {
+  fn from(pb: ExportFormatPB) -> Self {
+    match pb {
+      ExportFormatPB::Markdown => ExportFormat::Markdown,
+      ExportFormatPB::HTML => ExportFormat::Html,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct ExportZipPB {
+  // the view to export with its descendants. The whole workspace is exported if it's empty
+  #[pb(index = 1, one_of)]
+  pub view_id: Option<String>,
+
+  // the path of the zip file to create
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub file_path: String,
+
+  // the format of the documents. The databases are exported to CSV
+  #[pb(index = 3)]
+  pub document_format: ExportFormatPB,
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ExportZipResultPB {
+  #[pb(index = 1)]
+  pub file_path: String,
+
+  // the number of the exported views
+  #[pb(index = 2)]
+  pub view_count: i64,
+}
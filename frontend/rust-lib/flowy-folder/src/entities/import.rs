@@ -118,3 +118,25 @@ pub struct ImportZipPB {
   #[validate(custom(function = "required_not_empty_str"))]
   pub file_path: String,
 }
+
+/// The progress of importing a zip file into the workspace.
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportProgressPB {
+  #[pb(index = 1)]
+  pub file_path: String,
+
+  // the number of the pages in the file
+  #[pb(index = 2)]
+  pub total: i64,
+
+  // the number of the pages that have been processed, including the pages failed to import
+  #[pb(index = 3)]
+  pub imported: i64,
+
+  // the name of the page that is being imported
+  #[pb(index = 4)]
+  pub current_name: String,
+
+  #[pb(index = 5)]
+  pub is_finished: bool,
+}
@@ -1,14 +1,22 @@
+mod batch;
+mod export;
 pub mod icon;
 mod import;
 mod parser;
 pub mod publish;
+mod tag;
+mod template;
 pub mod trash;
 pub mod view;
 pub mod workspace;
 
+pub use batch::*;
+pub use export::*;
 pub use icon::*;
 pub use import::*;
 pub use publish::*;
+pub use tag::*;
+pub use template::*;
 pub use trash::*;
 pub use view::*;
 pub use workspace::*;
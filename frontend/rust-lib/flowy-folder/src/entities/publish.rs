@@ -1,6 +1,8 @@
 use client_api::entity::workspace_dto::{FolderViewMinimal, PublishInfoView};
 use client_api::entity::PublishInfo;
 use flowy_derive::ProtoBuf;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
 
 use super::{RepeatedViewIdPB, ViewIconPB, ViewLayoutPB};
 
@@ -16,6 +18,27 @@ pub struct PublishViewParamsPB {
   pub selected_view_ids: Option<RepeatedViewIdPB>,
 }
 
+#[derive(Default, ProtoBuf, Validate)]
+pub struct PublishStaticSitePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  // the directory to render the site to
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub dir_path: String,
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct PublishStaticSiteResultPB {
+  #[pb(index = 1)]
+  pub dir_path: String,
+
+  #[pb(index = 2)]
+  pub page_count: i64,
+}
+
 #[derive(Default, ProtoBuf)]
 pub struct UnpublishViewsPayloadPB {
   #[pb(index = 1)]
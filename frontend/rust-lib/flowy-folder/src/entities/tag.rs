@@ -0,0 +1,101 @@
+use crate::tag::ViewTag;
+use flowy_derive::ProtoBuf;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct TagPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub color: String,
+}
+
+impl From<ViewTag> for TagPB {
+  fn from(tag: ViewTag) -> Self {
+    Self {
+      id: tag.id,
+      name: tag.name,
+      color: tag.color,
+    }
+  }
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct RepeatedTagPB {
+  #[pb(index = 1)]
+  pub items: Vec<TagPB>,
+}
+
+impl From<Vec<ViewTag>> for RepeatedTagPB {
+  fn from(tags: Vec<ViewTag>) -> Self {
+    Self {
+      items: tags.into_iter().map(TagPB::from).collect(),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct CreateTagPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub name: String,
+
+  #[pb(index = 2)]
+  pub color: String,
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct UpdateTagPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub tag_id: String,
+
+  #[pb(index = 2, one_of)]
+  pub name: Option<String>,
+
+  #[pb(index = 3, one_of)]
+  pub color: Option<String>,
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct TagIdPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub tag_id: String,
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct MergeTagsPB {
+  // the tags to merge into the target tag. They're deleted after the merge
+  #[pb(index = 1)]
+  pub source_tag_ids: Vec<String>,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub target_tag_id: String,
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct SetViewTagsPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  // replaces the tags of the view
+  #[pb(index = 2)]
+  pub tag_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ViewTagIdsPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub tag_ids: Vec<String>,
+}
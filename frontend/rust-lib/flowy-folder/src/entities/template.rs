@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use crate::entities::ViewLayoutPB;
+use crate::template::ViewTemplate;
+use flowy_derive::ProtoBuf;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct SaveViewTemplatePB {
+  // the view to save with its descendants
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  // the name of the template. The name of the view is used if it's empty
+  #[pb(index = 2, one_of)]
+  pub name: Option<String>,
+
+  // save the rows of the databases
+  #[pb(index = 3)]
+  pub include_rows: bool,
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ViewTemplatePB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub created_at: i64,
+
+  // the number of the views in the template
+  #[pb(index = 4)]
+  pub view_count: i64,
+
+  // the layout of the root view
+  #[pb(index = 5)]
+  pub layout: ViewLayoutPB,
+
+  #[pb(index = 6)]
+  pub include_rows: bool,
+}
+
+impl From<ViewTemplate> for ViewTemplatePB {
+  fn from(template: ViewTemplate) -> Self {
+    let layout = template
+      .root_view()
+      .map(|view| view.layout.clone().into())
+      .unwrap_or_default();
+    Self {
+      id: template.id,
+      name: template.name,
+      created_at: template.created_at,
+      view_count: template.views.len() as i64,
+      layout,
+      include_rows: template.include_rows,
+    }
+  }
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct RepeatedViewTemplatePB {
+  #[pb(index = 1)]
+  pub items: Vec<ViewTemplatePB>,
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct ViewTemplateIdPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub template_id: String,
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct RenameViewTemplatePB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub template_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub name: String,
+}
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct CreateViewFromTemplatePB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub template_id: String,
+
+  // the view to insert the new views into
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub parent_view_id: String,
+
+  // the values of the {{name}} placeholders. The `date` and `time` default to the current time,
+  // and the `user` to the name of the current user
+  #[pb(index = 3)]
+  pub variables: HashMap<String, String>,
+}
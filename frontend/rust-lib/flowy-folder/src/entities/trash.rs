@@ -71,3 +71,49 @@ pub struct RepeatedTrashIdPB {
   #[pb(index = 1)]
   pub items: Vec<TrashIdPB>,
 }
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct TrashRetentionPB {
+  /// The days the views stay in the trash before they're purged. 0 keeps them forever.
+  #[pb(index = 1)]
+  pub days: i64,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct PreviewTrashPurgePB {
+  /// Uses the retention of the workspace if it's not set.
+  #[pb(index = 1, one_of)]
+  pub days: Option<i64>,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct TrashPurgeItemPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub deleted_at: i64,
+
+  /// The trashed view and its descendants.
+  #[pb(index = 4)]
+  pub view_count: i64,
+
+  #[pb(index = 5)]
+  pub file_count: i64,
+
+  /// The bytes purging the item reclaims on this device.
+  #[pb(index = 6)]
+  pub size: i64,
+}
+
+#[derive(PartialEq, Eq, ProtoBuf, Default, Debug, Clone)]
+pub struct TrashPurgePreviewPB {
+  #[pb(index = 1)]
+  pub items: Vec<TrashPurgeItemPB>,
+
+  #[pb(index = 2)]
+  pub total_size: i64,
+}
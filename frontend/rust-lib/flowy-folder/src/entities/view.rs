@@ -7,9 +7,12 @@ use std::sync::Arc;
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
 use flowy_folder_pub::cloud::gen_view_id;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
 
 use crate::entities::icon::ViewIconPB;
 use crate::entities::parser::view::{ViewIdentify, ViewName, ViewThumbnail};
+use crate::lock::{view_lock, ViewLock};
 use crate::view_operation::ViewData;
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
@@ -72,6 +75,10 @@ pub struct ViewPB {
   // user_id
   #[pb(index = 12, one_of)]
   pub last_edited_by: Option<i64>,
+
+  // the view can't be edited while it's locked
+  #[pb(index = 13, one_of)]
+  pub lock: Option<ViewLockPB>,
 }
 
 pub fn view_pb_without_child_views(view: View) -> ViewPB {
@@ -84,6 +91,7 @@ pub fn view_pb_without_child_views(view: View) -> ViewPB {
     layout: view.layout.into(),
     icon: view.icon.clone().map(|icon| icon.into()),
     is_favorite: view.is_favorite,
+    lock: view_lock(view.extra.as_deref()).map(ViewLockPB::from),
     extra: view.extra,
     created_by: view.created_by,
     last_edited: view.last_edited_time,
@@ -105,9 +113,38 @@ pub fn view_pb_without_child_views_from_arc(view: Arc<View>) -> ViewPB {
     created_by: view.created_by,
     last_edited: view.last_edited_time,
     last_edited_by: view.last_edited_by,
+    lock: view_lock(view.extra.as_deref()).map(ViewLockPB::from),
+  }
+}
+
+#[derive(Eq, PartialEq, Debug, Default, ProtoBuf, Clone)]
+pub struct ViewLockPB {
+  #[pb(index = 1)]
+  pub locked_by: i64,
+
+  #[pb(index = 2)]
+  pub locked_at: i64,
+}
+
+impl From<ViewLock> for ViewLockPB {
+  fn from(lock: ViewLock) -> Self {
+    Self {
+      locked_by: lock.locked_by,
+      locked_at: lock.locked_at,
+    }
   }
 }
 
+#[derive(Eq, PartialEq, Debug, Default, ProtoBuf, Clone)]
+pub struct ViewLockStatePB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  // None if the view is unlocked
+  #[pb(index = 2, one_of)]
+  pub lock: Option<ViewLockPB>,
+}
+
 /// Returns a ViewPB with child views. Only the first level of child views are included.
 pub fn view_pb_with_child_views(view: Arc<View>, child_views: Vec<Arc<View>>) -> ViewPB {
   ViewPB {
@@ -126,6 +163,7 @@ pub fn view_pb_with_child_views(view: Arc<View>, child_views: Vec<Arc<View>>) ->
     created_by: view.created_by,
     last_edited: view.last_edited_time,
     last_edited_by: view.last_edited_by,
+    lock: view_lock(view.extra.as_deref()).map(ViewLockPB::from),
   }
 }
 
@@ -535,6 +573,22 @@ pub struct MoveNestedViewPayloadPB {
   pub to_section: Option<ViewSectionPB>,
 }
 
+/// Moves or copies the view with its descendants into another workspace of the user.
+/// If `parent_view_id` is `None`, the view is added to the root of the workspace.
+#[derive(Clone, Debug, Default, Validate, ProtoBuf)]
+pub struct TransferViewToWorkspacePB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub workspace_id: String,
+
+  #[pb(index = 3, one_of)]
+  pub parent_view_id: Option<String>,
+}
+
 pub struct MoveViewParams {
   pub view_id: String,
   pub from: usize,
@@ -1,3 +1,4 @@
+use collab_folder::ViewIcon;
 use std::sync::{Arc, Weak};
 use tracing::instrument;
 
@@ -372,6 +373,43 @@ pub(crate) async fn delete_my_trash_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn get_trash_retention_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<TrashRetentionPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let days = folder.get_trash_retention_days()?.unwrap_or(0);
+  data_result_ok(TrashRetentionPB { days })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_trash_retention_handler(
+  data: AFPluginData<TrashRetentionPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let days = data.into_inner().days;
+  if days < 0 {
+    return Err(FlowyError::invalid_data().with_context("The trash retention can't be negative"));
+  }
+  folder.set_trash_retention_days(Some(days))?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn preview_trash_purge_handler(
+  data: AFPluginData<PreviewTrashPurgePB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<TrashPurgePreviewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let days = data.into_inner().days;
+  if days.is_some_and(|days| days < 0) {
+    return Err(FlowyError::invalid_data().with_context("The trash retention can't be negative"));
+  }
+  let preview = folder.preview_trash_purge(days).await?;
+  data_result_ok(preview)
+}
+
 #[tracing::instrument(level = "debug", skip(data, folder), err)]
 pub(crate) async fn import_data_handler(
   data: AFPluginData<ImportPayloadPB>,
@@ -394,6 +432,130 @@ pub(crate) async fn import_zip_file_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn export_zip_file_handler(
+  data: AFPluginData<ExportZipPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ExportZipResultPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  let view_count = folder
+    .export_zip_file(data.view_id, data.document_format.into(), &data.file_path)
+    .await?;
+  data_result_ok(ExportZipResultPB {
+    file_path: data.file_path,
+    view_count: view_count as i64,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn publish_static_site_handler(
+  data: AFPluginData<PublishStaticSitePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<PublishStaticSiteResultPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  let page_count = folder
+    .publish_static_site(&data.view_id, &data.dir_path)
+    .await?;
+  data_result_ok(PublishStaticSiteResultPB {
+    dir_path: data.dir_path,
+    page_count: page_count as i64,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn save_view_as_template_handler(
+  data: AFPluginData<SaveViewTemplatePB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewTemplatePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  let template = folder
+    .save_view_as_template(&data.view_id, data.name, data.include_rows)
+    .await?;
+  data_result_ok(template.into())
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn get_view_templates_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewTemplatePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let items = folder
+    .get_view_templates()?
+    .into_iter()
+    .map(ViewTemplatePB::from)
+    .collect();
+  data_result_ok(RepeatedViewTemplatePB { items })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn rename_view_template_handler(
+  data: AFPluginData<RenameViewTemplatePB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  folder.rename_view_template(&data.template_id, data.name)?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn delete_view_template_handler(
+  data: AFPluginData<ViewTemplateIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  folder.delete_view_template(&data.template_id)?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn create_view_from_template_handler(
+  data: AFPluginData<CreateViewFromTemplatePB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  let view = folder
+    .create_view_from_template(&data.template_id, &data.parent_view_id, data.variables)
+    .await?;
+  data_result_ok(view)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn copy_view_to_workspace_handler(
+  data: AFPluginData<TransferViewToWorkspacePB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  let view = folder
+    .transfer_view_to_workspace(&data.view_id, &data.workspace_id, data.parent_view_id, true)
+    .await?;
+  data_result_ok(view)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn move_view_to_workspace_handler(
+  data: AFPluginData<TransferViewToWorkspacePB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  let view = folder
+    .transfer_view_to_workspace(
+      &data.view_id,
+      &data.workspace_id,
+      data.parent_view_id,
+      false,
+    )
+    .await?;
+  data_result_ok(view)
+}
+
 #[tracing::instrument(level = "debug", skip(folder), err)]
 pub(crate) async fn get_folder_snapshots_handler(
   data: AFPluginData<WorkspaceIdPB>,
@@ -533,3 +695,194 @@ pub(crate) async fn remove_default_publish_view_handler(
   folder.remove_default_published_view().await?;
   Ok(())
 }
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn create_tag_handler(
+  data: AFPluginData<CreateTagPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<TagPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  let tag = folder.create_tag(data.name, data.color).await?;
+  data_result_ok(tag.into())
+}
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn get_tags_handler(
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedTagPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let tags = folder.get_workspace_tags().await?;
+  data_result_ok(tags.into())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn update_tag_handler(
+  data: AFPluginData<UpdateTagPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  folder
+    .update_tag(&data.tag_id, data.name, data.color)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn delete_tag_handler(
+  data: AFPluginData<TagIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  folder.delete_tag(&data.tag_id).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn merge_tags_handler(
+  data: AFPluginData<MergeTagsPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  folder
+    .merge_tags(data.source_tag_ids, &data.target_tag_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn set_view_tags_handler(
+  data: AFPluginData<SetViewTagsPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  folder.set_view_tags(&data.view_id, data.tag_ids).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_views_with_tag_handler(
+  data: AFPluginData<TagIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  let views = folder.get_views_with_tag(&data.tag_id).await?;
+  data_result_ok(views.into())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_view_tags_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ViewTagIdsPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id = data.into_inner().value;
+  let tag_ids = folder.get_view_tag_ids(&view_id).await?;
+  data_result_ok(ViewTagIdsPB { view_id, tag_ids })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn lock_view_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id = data.into_inner().value;
+  folder.lock_view(&view_id).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn unlock_view_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id = data.into_inner().value;
+  folder.unlock_view(&view_id).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn batch_move_views_to_trash_handler(
+  data: AFPluginData<RepeatedViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_ids = data.into_inner().items;
+  folder.batch_move_views_to_trash(view_ids).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn batch_restore_trash_handler(
+  data: AFPluginData<RepeatedViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_ids = data.into_inner().items;
+  folder.batch_restore_trash(view_ids).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn batch_move_views_handler(
+  data: AFPluginData<BatchMoveViewsPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  folder
+    .batch_move_views(
+      data.view_ids,
+      &data.new_parent_id,
+      data.prev_view_id,
+      data.from_section,
+      data.to_section,
+    )
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn batch_set_favorite_handler(
+  data: AFPluginData<BatchSetFavoritePB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  folder
+    .batch_set_favorite(data.view_ids, data.is_favorite)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn batch_update_view_visibility_handler(
+  data: AFPluginData<UpdateViewVisibilityStatusPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  folder
+    .batch_set_views_visibility(data.view_ids, data.is_public)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn batch_update_view_icon_handler(
+  data: AFPluginData<BatchUpdateViewIconPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  let icon = data.icon.map(ViewIcon::from);
+  folder.batch_update_view_icon(data.view_ids, icon).await?;
+  Ok(())
+}
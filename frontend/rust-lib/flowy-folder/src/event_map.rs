@@ -33,6 +33,34 @@ pub fn init(folder: Weak<FolderManager>) -> AFPlugin {
     .event(FolderEvent::PermanentlyDeleteAllTrashItem, delete_my_trash_handler)
     .event(FolderEvent::ImportData, import_data_handler)
     .event(FolderEvent::ImportZipFile, import_zip_file_handler)
+    .event(FolderEvent::ExportZipFile, export_zip_file_handler)
+    .event(FolderEvent::PublishStaticSite, publish_static_site_handler)
+    .event(FolderEvent::SaveViewAsTemplate, save_view_as_template_handler)
+    .event(FolderEvent::GetViewTemplates, get_view_templates_handler)
+    .event(FolderEvent::RenameViewTemplate, rename_view_template_handler)
+    .event(FolderEvent::DeleteViewTemplate, delete_view_template_handler)
+    .event(FolderEvent::CreateViewFromTemplate, create_view_from_template_handler)
+    .event(FolderEvent::CopyViewToWorkspace, copy_view_to_workspace_handler)
+    .event(FolderEvent::MoveViewToWorkspace, move_view_to_workspace_handler)
+    .event(FolderEvent::GetTrashRetention, get_trash_retention_handler)
+    .event(FolderEvent::SetTrashRetention, set_trash_retention_handler)
+    .event(FolderEvent::PreviewTrashPurge, preview_trash_purge_handler)
+    .event(FolderEvent::CreateTag, create_tag_handler)
+    .event(FolderEvent::GetTags, get_tags_handler)
+    .event(FolderEvent::UpdateTag, update_tag_handler)
+    .event(FolderEvent::DeleteTag, delete_tag_handler)
+    .event(FolderEvent::MergeTags, merge_tags_handler)
+    .event(FolderEvent::SetViewTags, set_view_tags_handler)
+    .event(FolderEvent::GetViewsWithTag, get_views_with_tag_handler)
+    .event(FolderEvent::GetViewTags, get_view_tags_handler)
+    .event(FolderEvent::LockView, lock_view_handler)
+    .event(FolderEvent::UnlockView, unlock_view_handler)
+    .event(FolderEvent::BatchMoveViewsToTrash, batch_move_views_to_trash_handler)
+    .event(FolderEvent::BatchRestoreTrash, batch_restore_trash_handler)
+    .event(FolderEvent::BatchMoveViews, batch_move_views_handler)
+    .event(FolderEvent::BatchSetFavorite, batch_set_favorite_handler)
+    .event(FolderEvent::BatchUpdateViewVisibility, batch_update_view_visibility_handler)
+    .event(FolderEvent::BatchUpdateViewIcon, batch_update_view_icon_handler)
     .event(FolderEvent::GetFolderSnapshots, get_folder_snapshots_handler)
     .event(FolderEvent::UpdateViewIcon, update_view_icon_handler)
     .event(FolderEvent::ReadFavorites, read_favorites_handler)
@@ -220,4 +248,97 @@ pub enum FolderEvent {
 
   #[event()]
   RemoveDefaultPublishView = 53,
+
+  #[event(input = "ExportZipPB", output = "ExportZipResultPB")]
+  ExportZipFile = 54,
+
+  #[event(
+    input = "PublishStaticSitePayloadPB",
+    output = "PublishStaticSiteResultPB"
+  )]
+  PublishStaticSite = 55,
+
+  #[event(input = "SaveViewTemplatePB", output = "ViewTemplatePB")]
+  SaveViewAsTemplate = 56,
+
+  #[event(output = "RepeatedViewTemplatePB")]
+  GetViewTemplates = 57,
+
+  #[event(input = "RenameViewTemplatePB")]
+  RenameViewTemplate = 58,
+
+  #[event(input = "ViewTemplateIdPB")]
+  DeleteViewTemplate = 59,
+
+  #[event(input = "CreateViewFromTemplatePB", output = "ViewPB")]
+  CreateViewFromTemplate = 60,
+
+  #[event(input = "TransferViewToWorkspacePB", output = "ViewPB")]
+  CopyViewToWorkspace = 61,
+
+  #[event(input = "TransferViewToWorkspacePB", output = "ViewPB")]
+  MoveViewToWorkspace = 62,
+
+  #[event(output = "TrashRetentionPB")]
+  GetTrashRetention = 63,
+
+  /// 0 keeps the views in the trash until they're deleted.
+  #[event(input = "TrashRetentionPB")]
+  SetTrashRetention = 64,
+
+  #[event(input = "PreviewTrashPurgePB", output = "TrashPurgePreviewPB")]
+  PreviewTrashPurge = 65,
+
+  #[event(input = "CreateTagPB", output = "TagPB")]
+  CreateTag = 66,
+
+  /// Return the tags of the current workspace
+  #[event(output = "RepeatedTagPB")]
+  GetTags = 67,
+
+  #[event(input = "UpdateTagPB")]
+  UpdateTag = 68,
+
+  /// Delete the tag and remove it from the views
+  #[event(input = "TagIdPB")]
+  DeleteTag = 69,
+
+  #[event(input = "MergeTagsPB")]
+  MergeTags = 70,
+
+  #[event(input = "SetViewTagsPB")]
+  SetViewTags = 71,
+
+  #[event(input = "TagIdPB", output = "RepeatedViewPB")]
+  GetViewsWithTag = 72,
+
+  /// Lock the view so the document or the database of the view can't be edited
+  #[event(input = "ViewIdPB")]
+  LockView = 73,
+
+  #[event(input = "ViewIdPB")]
+  UnlockView = 74,
+
+  /// The batch events apply to all the views in one folder transaction, and send one
+  /// DidBatchUpdateViews notification. Nothing is changed if any of the views is invalid.
+  #[event(input = "RepeatedViewIdPB")]
+  BatchMoveViewsToTrash = 75,
+
+  #[event(input = "RepeatedViewIdPB")]
+  BatchRestoreTrash = 76,
+
+  #[event(input = "BatchMoveViewsPB")]
+  BatchMoveViews = 77,
+
+  #[event(input = "BatchSetFavoritePB")]
+  BatchSetFavorite = 78,
+
+  #[event(input = "UpdateViewVisibilityStatusPayloadPB")]
+  BatchUpdateViewVisibility = 79,
+
+  #[event(input = "BatchUpdateViewIconPB")]
+  BatchUpdateViewIcon = 80,
+
+  #[event(input = "ViewIdPB", output = "ViewTagIdsPB")]
+  GetViewTags = 81,
 }
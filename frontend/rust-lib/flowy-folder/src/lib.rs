@@ -15,5 +15,8 @@ mod manager_observer;
 pub mod manager_test_util;
 
 pub mod publish_util;
+pub mod lock;
 pub mod share;
+pub mod tag;
+pub mod template;
 mod util;
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::get_extra_value;
+
+/// The lock of a view is stored in the extra of the view, so it's synced with the folder and sent
+/// with the view. The extra is read and written under the folder write lock when the lock is set.
+pub const VIEW_LOCK_KEY: &str = "lock";
+
+/// Who locked the view and when. A locked view can't be edited until it's unlocked.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewLock {
+  pub locked_by: i64,
+  pub locked_at: i64,
+}
+
+/// Returns the lock stored in the extra of the view, or `None` if the view isn't locked.
+pub fn view_lock(extra: Option<&str>) -> Option<ViewLock> {
+  get_extra_value(extra, VIEW_LOCK_KEY)
+}
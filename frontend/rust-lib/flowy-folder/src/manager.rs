@@ -1,38 +1,57 @@
 use crate::entities::icon::UpdateViewIconParams;
 use crate::entities::{
   view_pb_with_child_views, view_pb_without_child_views, view_pb_without_child_views_from_arc,
-  CreateViewParams, CreateWorkspaceParams, DeletedViewPB, DuplicateViewParams, FolderSnapshotPB,
-  MoveNestedViewParams, RepeatedTrashPB, RepeatedViewIdPB, RepeatedViewPB, UpdateViewParams,
-  ViewLayoutPB, ViewPB, ViewSectionPB, WorkspacePB, WorkspaceSettingPB,
+  BatchViewChangesPB, BatchViewOperationPB, CreateViewParams, CreateWorkspaceParams, DeletedViewPB,
+  DuplicateViewParams, FolderSnapshotPB, ImportProgressPB, MoveNestedViewParams, RepeatedTagPB,
+  RepeatedTrashPB, RepeatedViewIdPB, RepeatedViewPB, TrashPurgeItemPB, TrashPurgePreviewPB,
+  UpdateViewParams, ViewLayoutPB, ViewPB, ViewSectionPB, WorkspacePB, WorkspaceSettingPB,
 };
+use crate::lock::{view_lock, ViewLock, VIEW_LOCK_KEY};
 use crate::manager_observer::{
   notify_child_views_changed, notify_did_update_workspace, notify_parent_view_did_change,
-  ChildViewChangeReason,
+  notify_view_lock_changed, BatchUpdatedViews, ChildViewChangeReason,
 };
 use crate::notification::{
   send_current_workspace_notification, send_notification, FolderNotification,
 };
 use crate::publish_util::{generate_publish_name, view_pb_to_publish_view};
-use crate::share::{ImportParams, ImportValue};
-use crate::util::{folder_not_init_error, workspace_data_not_sync_error};
+use crate::share::{
+  collect_notion_pages, export_file_name, notion_page_links, static_page_html,
+  static_site_index_html, ExportFormat, ExportManifest, ExportedFile, ExportedPage, ExportedView,
+  ExportedViewData, ImportParams, ImportType, ImportValue, NotionPage, EXPORT_DATABASE_DATA_SUFFIX,
+  EXPORT_MANIFEST_FILE_NAME, EXPORT_MANIFEST_VERSION, EXPORT_VIEW_DATA_SUFFIX,
+  STATIC_SITE_INDEX_FILE_NAME, STATIC_SITE_STYLE, STATIC_SITE_STYLE_FILE_NAME,
+};
+use crate::tag::{
+  view_tag_ids, views_tag_ids, workspace_tags, write_view_tag_ids, write_workspace_tags, ViewTag,
+};
+use crate::template::{
+  replace_template_ids, substitute_template_variables, substitute_template_variables_in_json,
+  TemplateView, ViewTemplate, TEMPLATE_VARIABLE_DATE, TEMPLATE_VARIABLE_TIME,
+  TEMPLATE_VARIABLE_USER,
+};
+use crate::util::{folder_not_init_error, set_extra_value, workspace_data_not_sync_error};
 use crate::view_operation::{
-  create_view, EncodedCollabWrapper, FolderOperationHandler, FolderOperationHandlers, ViewData,
+  create_view, EncodedCollabWrapper, FolderOperationHandler, FolderOperationHandlers, ImportedData,
+  ViewData, WorkspaceViewCollabs,
 };
 use arc_swap::ArcSwapOption;
 use client_api::entity::workspace_dto::PublishInfoView;
 use client_api::entity::PublishInfo;
 use collab::core::collab::DataSource;
 use collab::lock::RwLock;
+use collab::preclude::Subscription;
 use collab_entity::{CollabType, EncodedCollab};
 use collab_folder::hierarchy_builder::{ParentChildViews, SpacePermission, ViewExtraBuilder};
 use collab_folder::{
-  Folder, FolderData, FolderNotify, Section, SectionItem, TrashInfo, View, ViewLayout, ViewUpdate,
-  Workspace,
+  Folder, FolderData, FolderNotify, Section, SectionItem, TrashInfo, View, ViewIcon, ViewLayout,
+  ViewUpdate, Workspace,
 };
 use collab_integrate::collab_builder::{
   AppFlowyCollabBuilder, CollabBuilderConfig, CollabPersistenceImpl,
 };
-use collab_integrate::CollabKVDB;
+use collab_integrate::{CollabKVAction, CollabKVDB};
+use collab_plugins::local_storage::kv::KVTransactionDB;
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
 use flowy_folder_pub::cloud::{gen_view_id, FolderCloudService, FolderCollabParams};
 use flowy_folder_pub::entities::{
@@ -42,15 +61,24 @@ use flowy_folder_pub::entities::{
 use flowy_search_pub::entities::FolderIndexManager;
 use flowy_sqlite::kv::KVStorePreferences;
 use futures::future;
-use std::collections::HashMap;
+use lib_infra::file_util::{canonicalize_in_dir, unzip_and_replace, zip_folder};
+use lib_infra::util::timestamp;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
-use std::sync::{Arc, Weak};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
 use tokio::sync::RwLockWriteGuard;
 use tracing::{error, info, instrument};
 
+const TRASH_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// How long to wait for the folder to be opened before purging the trash.
+const TRASH_PURGE_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
 pub trait FolderUser: Send + Sync {
   fn user_id(&self) -> Result<i64, FlowyError>;
   fn workspace_id(&self) -> Result<String, FlowyError>;
+  fn user_name(&self) -> Result<String, FlowyError>;
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError>;
 
   fn is_folder_exist_on_disk(&self, uid: i64, workspace_id: &str) -> FlowyResult<bool>;
@@ -69,6 +97,8 @@ pub struct FolderManager {
   pub cloud_service: Arc<dyn FolderCloudService>,
   pub(crate) folder_indexer: Arc<dyn FolderIndexManager>,
   pub(crate) store_preferences: Arc<KVStorePreferences>,
+  pub(crate) batch_updated_views: BatchUpdatedViews,
+  pub(crate) view_tags_subscription: Mutex<Option<Subscription>>,
 }
 
 impl FolderManager {
@@ -88,6 +118,8 @@ impl FolderManager {
       cloud_service,
       folder_indexer,
       store_preferences,
+      batch_updated_views: Default::default(),
+      view_tags_subscription: Default::default(),
     };
 
     Ok(manager)
@@ -687,6 +719,33 @@ impl FolderManager {
     Ok(views)
   }
 
+  /// Retrieves the views of the specified view IDs together with whether they are in the trash.
+  ///
+  /// The views that belong to the other private sections are omitted.
+  pub async fn get_views_with_trash_state(
+    &self,
+    view_ids: &[String],
+  ) -> FlowyResult<Vec<(ViewPB, bool)>> {
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let folder = lock.read().await;
+    let trash_ids = Self::get_all_trash_ids(&folder);
+    let other_private_view_ids = Self::get_other_private_view_ids(&folder);
+
+    let views = view_ids
+      .iter()
+      .filter(|view_id| !other_private_view_ids.contains(view_id))
+      .filter_map(|view_id| folder.get_view(view_id))
+      .map(|view| {
+        let is_trashed = trash_ids.contains(&view.id);
+        (view_pb_without_child_views_from_arc(view), is_trashed)
+      })
+      .collect::<Vec<_>>();
+    Ok(views)
+  }
+
   /// Retrieves the ancestors of the view corresponding to the specified view ID, including the view itself.
   ///
   /// For example, if the view hierarchy is as follows:
@@ -910,6 +969,389 @@ impl FolderManager {
       .await
   }
 
+  /// Locks the view with the current user and time. The documents and databases reject the edits
+  /// of a locked view.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn lock_view(&self, view_id: &str) -> FlowyResult<()> {
+    let new_lock = ViewLock {
+      locked_by: self.user.user_id()?,
+      locked_at: timestamp(),
+    };
+    self.set_view_lock(view_id, Some(new_lock)).await
+  }
+
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn unlock_view(&self, view_id: &str) -> FlowyResult<()> {
+    self.set_view_lock(view_id, None).await
+  }
+
+  /// Returns the lock of the view, or `None` if the view isn't locked.
+  pub async fn get_view_lock(&self, view_id: &str) -> Option<ViewLock> {
+    let lock = self.mutex_folder.load_full()?;
+    let view = lock.read().await.get_view(view_id)?;
+    view_lock(view.extra.as_deref())
+  }
+
+  async fn set_view_lock(&self, view_id: &str, new_lock: Option<ViewLock>) -> FlowyResult<()> {
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    if lock.read().await.get_view(view_id).is_none() {
+      return Err(FlowyError::record_not_found().with_context("Can't find the view"));
+    }
+    // The extra is read and written under the folder write lock, so the other values of the
+    // extra set at the same time are kept
+    self
+      .update_view_with(view_id, |view, update| {
+        let extra = set_extra_value(view.extra.as_deref(), VIEW_LOCK_KEY, &new_lock);
+        update.set_extra_if_not_none(Some(extra)).done()
+      })
+      .await?;
+    notify_view_lock_changed(view_id, new_lock);
+    Ok(())
+  }
+
+  /// Move the views to trash in one transaction. Like [Self::move_view_to_trash], the views and
+  /// their descendants are unfavorited first.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn batch_move_views_to_trash(&self, view_ids: Vec<String>) -> FlowyResult<()> {
+    let view_ids = unique_view_ids(view_ids);
+    if view_ids.is_empty() {
+      return Ok(());
+    }
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let parent_view_ids = {
+      let mut folder_guard = lock.write().await;
+      let folder = &mut *folder_guard;
+      let views = check_batch_views(folder, &view_ids)?;
+      let trash_ids = Self::get_all_trash_ids(folder);
+      if let Some(view_id) = view_ids.iter().find(|id| trash_ids.contains(id)) {
+        return Err(FlowyError::new(
+          ErrorCode::Internal,
+          format!(
+            "Can't move the view({}) to trash, it is already in trash",
+            view_id
+          ),
+        ));
+      }
+      let favorite_view_ids = view_ids
+        .iter()
+        .flat_map(|view_id| {
+          let mut ids = get_all_child_view_ids(folder, view_id);
+          ids.push(view_id.clone());
+          ids
+        })
+        .filter(|view_id| {
+          folder
+            .get_view(view_id)
+            .map(|view| view.is_favorite)
+            .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+
+      let mut txn = folder.collab.transact_mut();
+      if let Some(op) = folder.body.section.section_op(&txn, Section::Favorite) {
+        op.delete_section_items_with_txn(&mut txn, favorite_view_ids);
+      }
+      if let Some(op) = folder.body.section.section_op(&txn, Section::Trash) {
+        op.add_sections_item(
+          &mut txn,
+          view_ids.iter().cloned().map(SectionItem::new).collect(),
+        );
+      }
+      parent_view_ids_of(&views)
+    };
+    self
+      .notify_batch_views_changed(
+        BatchViewOperationPB::MoveToTrash,
+        &view_ids,
+        parent_view_ids,
+      )
+      .await
+  }
+
+  /// Restore the views from trash in one transaction.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn batch_restore_trash(&self, view_ids: Vec<String>) -> FlowyResult<()> {
+    let view_ids = unique_view_ids(view_ids);
+    if view_ids.is_empty() {
+      return Ok(());
+    }
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let parent_view_ids = {
+      let mut folder_guard = lock.write().await;
+      let folder = &mut *folder_guard;
+      let views = check_batch_views(folder, &view_ids)?;
+      let trash_ids = folder
+        .get_my_trash_info()
+        .into_iter()
+        .map(|trash| trash.id)
+        .collect::<HashSet<_>>();
+      if let Some(view_id) = view_ids.iter().find(|id| !trash_ids.contains(*id)) {
+        return Err(
+          FlowyError::record_not_found()
+            .with_context(format!("The view({}) is not in trash", view_id)),
+        );
+      }
+
+      let mut txn = folder.collab.transact_mut();
+      if let Some(op) = folder.body.section.section_op(&txn, Section::Trash) {
+        op.delete_section_items_with_txn(&mut txn, view_ids.clone());
+      }
+      parent_view_ids_of(&views)
+    };
+    self
+      .notify_batch_views_changed(
+        BatchViewOperationPB::RestoreFromTrash,
+        &view_ids,
+        parent_view_ids,
+      )
+      .await
+  }
+
+  /// Move the views under the new parent in one transaction. The views keep the order of the ids,
+  /// and the first one is placed right after `prev_view_id`. Like [Self::move_nested_view], the
+  /// views become private or public when they're moved from one section to the other.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn batch_move_views(
+    &self,
+    view_ids: Vec<String>,
+    new_parent_id: &str,
+    prev_view_id: Option<String>,
+    from_section: Option<ViewSectionPB>,
+    to_section: Option<ViewSectionPB>,
+  ) -> FlowyResult<()> {
+    let view_ids = unique_view_ids(view_ids);
+    if view_ids.is_empty() {
+      return Ok(());
+    }
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let parent_view_ids = {
+      let mut folder_guard = lock.write().await;
+      let folder = &mut *folder_guard;
+      let views = check_batch_views(folder, &view_ids)?;
+      // A view can't be moved under itself or its descendants
+      let mut ancestor = folder.get_view(new_parent_id).ok_or_else(|| {
+        FlowyError::record_not_found().with_context("Can't find the new parent view")
+      })?;
+      loop {
+        if view_ids.contains(&ancestor.id) {
+          return Err(
+            FlowyError::invalid_data()
+              .with_context(format!("Can't move the view({}) under itself", ancestor.id)),
+          );
+        }
+        match folder.get_view(&ancestor.parent_view_id) {
+          Some(parent) if parent.id != ancestor.id => ancestor = parent,
+          _ => break,
+        }
+      }
+      if let Some(prev_view_id) = prev_view_id.as_ref() {
+        if view_ids.contains(prev_view_id) {
+          return Err(
+            FlowyError::invalid_data().with_context("The previous view can't be a moved view"),
+          );
+        }
+      }
+
+      // Only the views that get a new parent are updated, the others are just reordered
+      let reparented_view_ids = views
+        .iter()
+        .filter(|view| view.parent_view_id != new_parent_id)
+        .map(|view| view.id.clone())
+        .collect::<Vec<_>>();
+      self.batch_updated_views.add(&reparented_view_ids);
+
+      let mut txn = folder.collab.transact_mut();
+      let mut prev_view_id = prev_view_id;
+      for view_id in &view_ids {
+        folder
+          .body
+          .move_nested_view(&mut txn, view_id, new_parent_id, prev_view_id);
+        prev_view_id = Some(view_id.clone());
+      }
+      if from_section != to_section {
+        if let Some(op) = folder.body.section.section_op(&txn, Section::Private) {
+          if to_section == Some(ViewSectionPB::Private) {
+            op.add_sections_item(
+              &mut txn,
+              view_ids.iter().cloned().map(SectionItem::new).collect(),
+            );
+          } else {
+            op.delete_section_items_with_txn(&mut txn, view_ids.clone());
+          }
+        }
+      }
+      let mut parent_view_ids = parent_view_ids_of(&views);
+      parent_view_ids.push(new_parent_id.to_string());
+      parent_view_ids
+    };
+    self
+      .notify_batch_views_changed(BatchViewOperationPB::Move, &view_ids, parent_view_ids)
+      .await
+  }
+
+  /// Add the views to or remove them from the favorites in one transaction.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn batch_set_favorite(
+    &self,
+    view_ids: Vec<String>,
+    is_favorite: bool,
+  ) -> FlowyResult<()> {
+    let view_ids = unique_view_ids(view_ids);
+    if view_ids.is_empty() {
+      return Ok(());
+    }
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    {
+      let mut folder_guard = lock.write().await;
+      let folder = &mut *folder_guard;
+      // Only the views whose favorite status changes, so no view is added to the favorites twice
+      let changed_view_ids = check_batch_views(folder, &view_ids)?
+        .into_iter()
+        .filter(|view| view.is_favorite != is_favorite)
+        .map(|view| view.id.clone())
+        .collect::<Vec<_>>();
+
+      let mut txn = folder.collab.transact_mut();
+      if let Some(op) = folder.body.section.section_op(&txn, Section::Favorite) {
+        if is_favorite {
+          op.add_sections_item(
+            &mut txn,
+            changed_view_ids.into_iter().map(SectionItem::new).collect(),
+          );
+        } else {
+          op.delete_section_items_with_txn(&mut txn, changed_view_ids);
+        }
+      }
+    }
+    self
+      .notify_batch_views_changed(BatchViewOperationPB::Favorite, &view_ids, vec![])
+      .await
+  }
+
+  /// Make the views public or private in one transaction.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn batch_set_views_visibility(
+    &self,
+    view_ids: Vec<String>,
+    is_public: bool,
+  ) -> FlowyResult<()> {
+    let view_ids = unique_view_ids(view_ids);
+    if view_ids.is_empty() {
+      return Ok(());
+    }
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let parent_view_ids = {
+      let mut folder_guard = lock.write().await;
+      let folder = &mut *folder_guard;
+      let views = check_batch_views(folder, &view_ids)?;
+
+      let mut txn = folder.collab.transact_mut();
+      if let Some(op) = folder.body.section.section_op(&txn, Section::Private) {
+        if is_public {
+          op.delete_section_items_with_txn(&mut txn, view_ids.clone());
+        } else {
+          op.add_sections_item(
+            &mut txn,
+            view_ids.iter().cloned().map(SectionItem::new).collect(),
+          );
+        }
+      }
+      parent_view_ids_of(&views)
+    };
+    self
+      .notify_batch_views_changed(BatchViewOperationPB::Visibility, &view_ids, parent_view_ids)
+      .await
+  }
+
+  /// Set the icon of the views in one transaction. The icons are removed if `icon` is `None`.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn batch_update_view_icon(
+    &self,
+    view_ids: Vec<String>,
+    icon: Option<ViewIcon>,
+  ) -> FlowyResult<()> {
+    let view_ids = unique_view_ids(view_ids);
+    if view_ids.is_empty() {
+      return Ok(());
+    }
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let parent_view_ids = {
+      let mut folder_guard = lock.write().await;
+      let folder = &mut *folder_guard;
+      let views = check_batch_views(folder, &view_ids)?;
+      // Only the views whose icon changes are updated
+      let changed_view_ids = views
+        .iter()
+        .filter(|view| view.icon != icon)
+        .map(|view| view.id.clone())
+        .collect::<Vec<_>>();
+      self.batch_updated_views.add(&changed_view_ids);
+
+      let mut txn = folder.collab.transact_mut();
+      for view_id in &changed_view_ids {
+        folder.body.views.update_view(&mut txn, view_id, |update| {
+          update.set_icon(icon.clone()).done()
+        });
+      }
+      parent_view_ids_of(&views)
+    };
+    self
+      .notify_batch_views_changed(BatchViewOperationPB::Icon, &view_ids, parent_view_ids)
+      .await
+  }
+
+  /// Send one notification for the views changed by a batch, instead of one per view.
+  async fn notify_batch_views_changed(
+    &self,
+    operation: BatchViewOperationPB,
+    view_ids: &[String],
+    mut parent_view_ids: Vec<String>,
+  ) -> FlowyResult<()> {
+    let workspace_id = self.user.workspace_id()?;
+    let views = match self.mutex_folder.load_full() {
+      Some(lock) => {
+        let folder = lock.read().await;
+        view_ids
+          .iter()
+          .filter_map(|view_id| folder.get_view(view_id))
+          .map(view_pb_without_child_views_from_arc)
+          .collect()
+      },
+      None => vec![],
+    };
+    parent_view_ids.sort();
+    parent_view_ids.dedup();
+    send_notification(&workspace_id, FolderNotification::DidBatchUpdateViews)
+      .payload(BatchViewChangesPB {
+        operation,
+        views,
+        parent_view_ids,
+      })
+      .send();
+    Ok(())
+  }
+
   /// Duplicate the view with the given view id.
   ///
   /// Including the view data (icon, cover, extra) and the child views.
@@ -1410,6 +1852,93 @@ impl FolderManager {
     Ok(payloads)
   }
 
+  /// Render the view and its descendants to a static site in the directory, from the same payloads
+  /// that [Self::publish_view] publishes. Each view has a page named after its publish name, with
+  /// the navigation of the published views, and the index of the site redirects to the page of the
+  /// view. Returns the number of the pages.
+  #[instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn publish_static_site(
+    &self,
+    view_id: &str,
+    dir_path: &str,
+  ) -> FlowyResult<usize> {
+    let payloads = self.get_batch_publish_payload(view_id, None, true).await?;
+    let root_meta = payloads
+      .iter()
+      .filter_map(|payload| payload.meta())
+      .find(|meta| meta.view_id == view_id)
+      .cloned()
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("Can't publish the view with ID: {}", view_id))
+      })?;
+
+    let mut file_names = HashSet::new();
+    let pages = payloads
+      .iter()
+      .filter_map(|payload| payload.meta())
+      .map(|meta| {
+        let file_name = export_file_name(&mut file_names, &meta.publish_name);
+        let page = ExportedPage {
+          name: meta.metadata.view.name.clone(),
+          path: format!("{}.html", file_name),
+        };
+        (meta.view_id.clone(), page)
+      })
+      .collect::<HashMap<_, _>>();
+    let navigation = vec![PublishViewInfo {
+      child_views: Some(root_meta.metadata.child_views.clone()),
+      ..root_meta.metadata.view.clone()
+    }];
+
+    let dir = PathBuf::from(dir_path);
+    tokio::fs::create_dir_all(&dir).await?;
+    let mut page_count = 0;
+    for payload in payloads {
+      let (view, page) = match payload
+        .meta()
+        .and_then(|meta| Some((meta.metadata.view.clone(), pages.get(&meta.view_id)?)))
+      {
+        Some(value) => value,
+        None => continue,
+      };
+      let file_stem = page.path.trim_end_matches(".html").to_string();
+      let handler = self.get_handler(&view.layout)?;
+      let content = match handler
+        .render_static_page(payload, &file_stem, &pages)
+        .await
+      {
+        Ok(content) => content,
+        Err(err) if err.code == ErrorCode::NotSupportYet => continue,
+        Err(err) => {
+          error!("Failed to render the view {}: {}", view.view_id, err);
+          return Err(err);
+        },
+      };
+
+      for asset in content.assets {
+        let path = dir.join(&asset.path);
+        if let Some(parent) = path.parent() {
+          tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, &asset.data).await?;
+      }
+      let html = static_page_html(&view, &navigation, &pages, &content.body);
+      tokio::fs::write(dir.join(&page.path), html).await?;
+      page_count += 1;
+    }
+
+    if let Some(page) = pages.get(view_id) {
+      tokio::fs::write(
+        dir.join(STATIC_SITE_INDEX_FILE_NAME),
+        static_site_index_html(&page.path),
+      )
+      .await?;
+    }
+    tokio::fs::write(dir.join(STATIC_SITE_STYLE_FILE_NAME), STATIC_SITE_STYLE).await?;
+    Ok(page_count)
+  }
+
   async fn build_publish_views(&self, view_id: &str) -> Option<PublishViewInfo> {
     let view_pb = self.get_view_pb(view_id).await.ok()?;
 
@@ -1621,116 +2150,1553 @@ impl FolderManager {
     Ok(())
   }
 
-  /// Imports a single file to the folder and returns the encoded collab for immediate cloud sync.
-  pub(crate) async fn import_single_file(
-    &self,
-    parent_view_id: String,
-    import_data: ImportValue,
-  ) -> FlowyResult<(View, Vec<(String, CollabType, EncodedCollab)>)> {
-    // Ensure either data or file_path is provided
-    if import_data.data.is_none() && import_data.file_path.is_none() {
-      return Err(FlowyError::new(
-        ErrorCode::InvalidParams,
-        "Either data or file_path is required",
-      ));
+  /// Returns the days the views stay in the trash of the current workspace before they're purged.
+  /// `None` means they stay until the user deletes them.
+  pub(crate) fn get_trash_retention_days(&self) -> FlowyResult<Option<i64>> {
+    let key = trash_retention_key(self.user.user_id()?, &self.user.workspace_id()?);
+    Ok(
+      self
+        .store_preferences
+        .get_i64(&key)
+        .filter(|days| *days > 0),
+    )
+  }
+
+  pub(crate) fn set_trash_retention_days(&self, days: Option<i64>) -> FlowyResult<()> {
+    let key = trash_retention_key(self.user.user_id()?, &self.user.workspace_id()?);
+    match days.filter(|days| *days > 0) {
+      None => self.store_preferences.remove(&key),
+      Some(days) => self.store_preferences.set_i64(&key, days)?,
     }
+    Ok(())
+  }
 
-    let handler = self.get_handler(&import_data.view_layout)?;
-    let view_id = gen_view_id().to_string();
-    let uid = self.user.user_id()?;
-    let mut encoded_collab = vec![];
+  /// Returns the trash items that have been in the trash for at least `days` days.
+  async fn get_expired_trash(&self, days: i64) -> Vec<ExpiredTrash> {
+    let lock = match self.mutex_folder.load_full() {
+      None => return vec![],
+      Some(lock) => lock,
+    };
+    let folder = lock.read().await;
+    let expired_at = timestamp() - days * 24 * 60 * 60;
+    folder
+      .get_my_trash_info()
+      .into_iter()
+      .filter(|trash| trash.created_at <= expired_at)
+      .map(|trash| {
+        let views = std::iter::once(trash.id.clone())
+          .chain(get_all_child_view_ids(&folder, &trash.id))
+          .filter_map(|view_id| folder.get_view(&view_id))
+          .collect();
+        ExpiredTrash { trash, views }
+      })
+      .collect()
+  }
 
-    // Import data from bytes if available
-    if let Some(data) = import_data.data {
-      encoded_collab = handler
-        .import_from_bytes(
-          uid,
-          &view_id,
-          &import_data.name,
-          import_data.import_type,
-          data,
-        )
-        .await?;
-    }
+  /// Returns the trash items that would be purged with the retention of `days` days, or the
+  /// retention of the workspace if it's `None`, and the bytes purging them would reclaim on this
+  /// device.
+  pub(crate) async fn preview_trash_purge(
+    &self,
+    days: Option<i64>,
+  ) -> FlowyResult<TrashPurgePreviewPB> {
+    let days = match days.or(self.get_trash_retention_days()?) {
+      None => return Ok(TrashPurgePreviewPB::default()),
+      Some(days) => days,
+    };
 
-    // Import data from file path if available
-    if let Some(file_path) = import_data.file_path {
-      handler
+    let mut items = vec![];
+    for expired in self.get_expired_trash(days).await {
+      let mut item = TrashPurgeItemPB {
+        id: expired.trash.id,
+        name: expired.trash.name,
+        deleted_at: expired.trash.created_at,
+        view_count: expired.views.len() as i64,
+        ..Default::default()
+      };
+      for view in expired.views {
+        let result = match self.get_handler(&view.layout) {
+          Ok(handler) => {
+            handler
+              .get_view_purge_info(self.user.clone(), &view.id)
+              .await
+          },
+          Err(err) => Err(err),
+        };
+        match result {
+          Ok(info) => {
+            item.size += info.size as i64;
+            item.file_count += info.file_count as i64;
+          },
+          Err(err) => error!(
+            "Failed to get the purge info of the view {}: {}",
+            view.id, err
+          ),
+        }
+      }
+      items.push(item);
+    }
+    let total_size = items.iter().map(|item| item.size).sum();
+    Ok(TrashPurgePreviewPB { items, total_size })
+  }
+
+  /// Permanently deletes the trash items of the current workspace that are older than its trash
+  /// retention, with their descendants, collabs and files. Returns the number of purged items.
+  #[instrument(level = "debug", skip(self), err)]
+  pub async fn purge_expired_trash(&self) -> FlowyResult<usize> {
+    match self.get_trash_retention_days()? {
+      None => Ok(0),
+      Some(days) => self.purge_trash(days).await,
+    }
+  }
+
+  /// Permanently deletes the trash items of the current workspace that have been in the trash for
+  /// at least `days` days, with their descendants, collabs and files. Returns the number of purged
+  /// items.
+  pub async fn purge_trash(&self, days: i64) -> FlowyResult<usize> {
+    let expired_trash = self.get_expired_trash(days).await;
+    if expired_trash.is_empty() {
+      return Ok(0);
+    }
+
+    for expired in &expired_trash {
+      if let Some(lock) = self.mutex_folder.load_full() {
+        let view_ids = std::iter::once(expired.trash.id.clone())
+          .chain(expired.views.iter().map(|view| view.id.clone()))
+          .collect::<Vec<_>>();
+        let mut folder = lock.write().await;
+        folder.delete_trash_view_ids(view_ids.clone());
+        folder.delete_views(view_ids.iter().map(|id| id.as_str()).collect());
+      }
+
+      for view in &expired.views {
+        if let Ok(handler) = self.get_handler(&view.layout) {
+          if let Err(err) = handler.purge_view(&view.id).await {
+            error!("Failed to purge the view {}: {}", view.id, err);
+          }
+        }
+      }
+    }
+
+    send_notification("trash", FolderNotification::DidUpdateTrash)
+      .payload(RepeatedTrashPB::from(self.get_my_trash_info().await))
+      .send();
+    Ok(expired_trash.len())
+  }
+
+  /// Purges the expired trash of the current workspace once its folder is opened at startup, and
+  /// then periodically, until the manager is dropped.
+  pub async fn run_trash_purge_schedule(manager: Weak<FolderManager>) {
+    loop {
+      let interval = match manager.upgrade() {
+        None => break,
+        Some(manager) => {
+          if manager.mutex_folder.load_full().is_none() {
+            TRASH_PURGE_RETRY_INTERVAL
+          } else {
+            match manager.purge_expired_trash().await {
+              Ok(0) => {},
+              Ok(count) => info!("Purged {} expired trash items", count),
+              Err(err) => error!("Scheduled trash purge failed: {}", err),
+            }
+            TRASH_PURGE_INTERVAL
+          }
+        },
+      };
+      tokio::time::sleep(interval).await;
+    }
+  }
+
+  /// Returns the tags of the current workspace.
+  pub async fn get_workspace_tags(&self) -> FlowyResult<Vec<ViewTag>> {
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let folder = lock.read().await;
+    Ok(workspace_tags(&folder.collab))
+  }
+
+  pub(crate) async fn create_tag(&self, name: String, color: String) -> FlowyResult<ViewTag> {
+    let tag = ViewTag {
+      id: gen_view_id().to_string(),
+      name: name.trim().to_string(),
+      color,
+    };
+    let tag_clone = tag.clone();
+    self
+      .update_workspace_tags(move |tags| {
+        check_tag_name(tags, &tag_clone.id, &tag_clone.name)?;
+        tags.push(tag_clone);
+        Ok(())
+      })
+      .await?;
+    Ok(tag)
+  }
+
+  pub(crate) async fn update_tag(
+    &self,
+    tag_id: &str,
+    name: Option<String>,
+    color: Option<String>,
+  ) -> FlowyResult<()> {
+    self
+      .update_workspace_tags(|tags| {
+        if let Some(name) = &name {
+          check_tag_name(tags, tag_id, name)?;
+        }
+        let tag = tags
+          .iter_mut()
+          .find(|tag| tag.id == tag_id)
+          .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the tag"))?;
+        if let Some(name) = name {
+          tag.name = name.trim().to_string();
+        }
+        if let Some(color) = color {
+          tag.color = color;
+        }
+        Ok(())
+      })
+      .await
+  }
+
+  /// Deletes the tag from the workspace and from the views that have it.
+  pub(crate) async fn delete_tag(&self, tag_id: &str) -> FlowyResult<()> {
+    self
+      .update_workspace_tags(|tags| {
+        let len = tags.len();
+        tags.retain(|tag| tag.id != tag_id);
+        if tags.len() == len {
+          return Err(FlowyError::record_not_found().with_context("Can't find the tag"));
+        }
+        Ok(())
+      })
+      .await?;
+    self
+      .retag_views(|id| (id != tag_id).then(|| id.to_string()))
+      .await
+  }
+
+  /// Replaces the source tags with the target tag on every view, then deletes the source tags.
+  pub(crate) async fn merge_tags(
+    &self,
+    source_tag_ids: Vec<String>,
+    target_tag_id: &str,
+  ) -> FlowyResult<()> {
+    let source_tag_ids = source_tag_ids
+      .into_iter()
+      .filter(|id| id != target_tag_id)
+      .collect::<HashSet<_>>();
+    self
+      .update_workspace_tags(|tags| {
+        if !tags.iter().any(|tag| tag.id == target_tag_id) {
+          return Err(FlowyError::record_not_found().with_context("Can't find the target tag"));
+        }
+        tags.retain(|tag| !source_tag_ids.contains(&tag.id));
+        Ok(())
+      })
+      .await?;
+    self
+      .retag_views(|id| {
+        if source_tag_ids.contains(id) {
+          Some(target_tag_id.to_string())
+        } else {
+          Some(id.to_string())
+        }
+      })
+      .await
+  }
+
+  /// Replaces the tags of the view. The tags must belong to the current workspace.
+  pub(crate) async fn set_view_tags(&self, view_id: &str, tag_ids: Vec<String>) -> FlowyResult<()> {
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let mut folder = lock.write().await;
+    if folder.get_view(view_id).is_none() {
+      return Err(FlowyError::record_not_found().with_context("Can't find the view"));
+    }
+    let tags = workspace_tags(&folder.collab);
+    let mut new_tag_ids: Vec<String> = vec![];
+    for tag_id in tag_ids {
+      if !tags.iter().any(|tag| tag.id == tag_id) {
+        return Err(
+          FlowyError::record_not_found().with_context(format!("Can't find the tag {}", tag_id)),
+        );
+      }
+      if !new_tag_ids.contains(&tag_id) {
+        new_tag_ids.push(tag_id);
+      }
+    }
+    write_view_tag_ids(&mut folder.collab, view_id, &new_tag_ids)
+  }
+
+  /// Returns the ids of the tags of the view.
+  pub async fn get_view_tag_ids(&self, view_id: &str) -> FlowyResult<Vec<String>> {
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let folder = lock.read().await;
+    let view = folder
+      .get_view(view_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the view"))?;
+    Ok(view_tag_ids(&folder.collab, &view))
+  }
+
+  /// Returns the views that have the tag, excluding the views in the trash and the private views
+  /// of the other members.
+  pub(crate) async fn get_views_with_tag(&self, tag_id: &str) -> FlowyResult<Vec<ViewPB>> {
+    let view_tags = {
+      let lock = self
+        .mutex_folder
+        .load_full()
+        .ok_or_else(folder_not_init_error)?;
+      let folder = lock.read().await;
+      views_tag_ids(&folder.collab, &folder.get_all_views())
+    };
+    let views = self
+      .get_all_views_pb()
+      .await?
+      .into_iter()
+      .filter(|view| {
+        view_tags
+          .get(&view.id)
+          .map(|tag_ids| tag_ids.iter().any(|id| id == tag_id))
+          .unwrap_or(false)
+      })
+      .collect();
+    Ok(views)
+  }
+
+  /// Updates the tags of the current workspace with `f` and notifies the new tags.
+  async fn update_workspace_tags<F>(&self, f: F) -> FlowyResult<()>
+  where
+    F: FnOnce(&mut Vec<ViewTag>) -> FlowyResult<()>,
+  {
+    let workspace_id = self.user.workspace_id()?;
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let tags = {
+      let mut folder = lock.write().await;
+      let old_tags = workspace_tags(&folder.collab);
+      let mut tags = old_tags.clone();
+      f(&mut tags)?;
+      write_workspace_tags(&mut folder.collab, &old_tags, &tags)?;
+      workspace_tags(&folder.collab)
+    };
+
+    send_notification(&workspace_id, FolderNotification::DidUpdateWorkspaceTags)
+      .payload(RepeatedTagPB::from(tags))
+      .send();
+    Ok(())
+  }
+
+  /// Maps the tag ids of every view with `f`, removing the ids for which it returns `None`. The
+  /// views whose tags changed are notified and re-indexed by the observer of the tags.
+  async fn retag_views<F>(&self, f: F) -> FlowyResult<()>
+  where
+    F: Fn(&str) -> Option<String>,
+  {
+    let lock = self
+      .mutex_folder
+      .load_full()
+      .ok_or_else(folder_not_init_error)?;
+    let mut folder = lock.write().await;
+    let view_tags = views_tag_ids(&folder.collab, &folder.get_all_views());
+    for (view_id, tag_ids) in view_tags {
+      let mut new_tag_ids: Vec<String> = vec![];
+      for new_tag_id in tag_ids.iter().filter_map(|id| f(id)) {
+        if !new_tag_ids.contains(&new_tag_id) {
+          new_tag_ids.push(new_tag_id);
+        }
+      }
+      if new_tag_ids != tag_ids {
+        write_view_tag_ids(&mut folder.collab, &view_id, &new_tag_ids)?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Imports a single file to the folder and returns the encoded collab for immediate cloud sync.
+  pub(crate) async fn import_single_file(
+    &self,
+    parent_view_id: String,
+    import_data: ImportValue,
+  ) -> FlowyResult<(View, Vec<(String, CollabType, EncodedCollab)>)> {
+    // Ensure either data or file_path is provided
+    if import_data.data.is_none() && import_data.file_path.is_none() {
+      return Err(FlowyError::new(
+        ErrorCode::InvalidParams,
+        "Either data or file_path is required",
+      ));
+    }
+
+    let handler = self.get_handler(&import_data.view_layout)?;
+    let view_id = gen_view_id().to_string();
+    let uid = self.user.user_id()?;
+    let mut encoded_collab = vec![];
+
+    // Import data from bytes if available
+    if let Some(data) = import_data.data {
+      encoded_collab = handler
+        .import_from_bytes(
+          uid,
+          &view_id,
+          &import_data.name,
+          import_data.import_type,
+          data,
+        )
+        .await?;
+    }
+
+    // Import data from file path if available
+    if let Some(file_path) = import_data.file_path {
+      handler
         .import_from_file_path(&view_id, &import_data.name, file_path)
         .await?;
     }
 
-    let params = CreateViewParams {
-      parent_view_id,
-      name: import_data.name,
-      layout: import_data.view_layout.clone().into(),
-      initial_data: ViewData::Empty,
-      view_id,
-      meta: Default::default(),
-      set_as_current: false,
-      index: None,
-      section: None,
-      extra: None,
-      icon: None,
+    let params = CreateViewParams {
+      parent_view_id,
+      name: import_data.name,
+      layout: import_data.view_layout.clone().into(),
+      initial_data: ViewData::Empty,
+      view_id,
+      meta: Default::default(),
+      set_as_current: false,
+      index: None,
+      section: None,
+      extra: None,
+      icon: None,
+    };
+
+    let view = create_view(self.user.user_id()?, params, import_data.view_layout);
+
+    // Insert the new view into the folder
+    if let Some(lock) = self.mutex_folder.load_full() {
+      let mut folder = lock.write().await;
+      folder.insert_view(view.clone(), None);
+    }
+
+    Ok((view, encoded_collab))
+  }
+
+  /// Import the zip file. The export of [Self::export_zip_file] is imported with the hierarchy of
+  /// its manifest. The other zip files are imported on the server, and the local version imports
+  /// the Markdown & CSV export of Notion by itself, since the local server can't import it.
+  pub(crate) async fn import_zip_file(&self, zip_file_path: &str) -> FlowyResult<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let export_dir = temp_dir.path().join("export");
+    let zip_path = PathBuf::from(zip_file_path);
+    let cloned_export_dir = export_dir.clone();
+    tokio::task::spawn_blocking(move || unzip_and_replace(&zip_path, &cloned_export_dir))
+      .await
+      .map_err(internal_error)?
+      .map_err(internal_error)?;
+
+    if export_dir.join(EXPORT_MANIFEST_FILE_NAME).is_file() {
+      self.import_exported_views(&export_dir).await?;
+      return Ok(());
+    }
+    match self.cloud_service.import_zip(zip_file_path).await {
+      Err(err) if err.is_local_version_not_support() => {
+        self
+          .import_notion_export(zip_file_path, &export_dir)
+          .await?;
+        Ok(())
+      },
+      result => result,
+    }
+  }
+
+  /// Import the views of the extracted export to the top level of the workspace, with the
+  /// hierarchy, the icons and the extras of the manifest. The views get new ids, and the links
+  /// between the exported documents become mentions of the imported views. Nothing is added to the
+  /// folder if a view fails to import.
+  #[instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn import_exported_views(
+    &self,
+    export_dir: &Path,
+  ) -> FlowyResult<RepeatedViewPB> {
+    let manifest = tokio::fs::read(export_dir.join(EXPORT_MANIFEST_FILE_NAME)).await?;
+    let manifest = serde_json::from_slice::<ExportManifest>(&manifest)?;
+    if manifest.version > EXPORT_MANIFEST_VERSION {
+      return Err(FlowyError::not_support().with_context(format!(
+        "The version {} of the export is not supported",
+        manifest.version
+      )));
+    }
+
+    let uid = self.user.user_id()?;
+    let workspace_id = self.user.workspace_id()?;
+    let view_ids = manifest
+      .views
+      .iter()
+      .map(|view| (view.view_id.clone(), gen_view_id().to_string()))
+      .collect::<HashMap<_, _>>();
+    // The main files of the views, which the exported documents link to.
+    let links = manifest
+      .views
+      .iter()
+      .filter_map(|view| {
+        let path = view.files.first()?;
+        Some((export_dir.join(path), view_ids[&view.view_id].clone()))
+      })
+      .collect::<HashMap<_, _>>();
+
+    // The data files keep the whole content of the views. The objects of the views, like the
+    // databases and their rows, get new ids, which are shared by the views of the same database,
+    // and the mentions of the exported views point to the imported views.
+    let mut view_data = HashMap::new();
+    for exported_view in &manifest.views {
+      if let Some(path) = exported_view
+        .files
+        .iter()
+        .find(|path| path.ends_with(EXPORT_VIEW_DATA_SUFFIX))
+      {
+        let bytes = tokio::fs::read(exported_file_path(export_dir, path)?).await?;
+        let data = serde_json::from_slice::<ExportedViewData>(&bytes)?;
+        view_data.insert(exported_view.view_id.clone(), data);
+      }
+    }
+    let mut ids = view_ids.clone();
+    for object_id in view_data.values().flat_map(|data| &data.data.object_ids) {
+      ids
+        .entry(object_id.clone())
+        .or_insert_with(|| gen_view_id().to_string());
+    }
+
+    let mut imported_row_ids = HashSet::new();
+    let mut imported_views = vec![];
+    let mut objects = vec![];
+    for exported_view in &manifest.views {
+      // The views at the top level of the export are imported to the top level of the workspace.
+      let parent_view_id = exported_view
+        .parent_view_id
+        .as_ref()
+        .and_then(|parent_view_id| view_ids.get(parent_view_id))
+        .cloned()
+        .unwrap_or_else(|| workspace_id.clone());
+      let params = CreateViewParams {
+        parent_view_id,
+        name: exported_view.name.clone(),
+        layout: exported_view.layout.clone().into(),
+        initial_data: ViewData::Empty,
+        view_id: view_ids[&exported_view.view_id].clone(),
+        meta: Default::default(),
+        set_as_current: false,
+        index: None,
+        section: None,
+        extra: exported_view.extra.clone(),
+        icon: exported_view.icon.clone(),
+      };
+      let mut view = create_view(uid, params, exported_view.layout.clone());
+      view.created_at = exported_view.created_at;
+
+      let encoded_collabs = match view_data.remove(&exported_view.view_id) {
+        Some(data) => {
+          self
+            .import_exported_view_data(uid, &view, data, &ids, &mut imported_row_ids)
+            .await?
+        },
+        None => {
+          self
+            .import_exported_view(uid, export_dir, &view, &exported_view.files, &links)
+            .await?
+        },
+      };
+      for (object_id, collab_type, encoded_collab) in encoded_collabs {
+        objects.push(self.get_folder_collab_params(object_id, collab_type, encoded_collab)?);
+      }
+      imported_views.push(view);
+    }
+
+    let mut views = vec![];
+    let mut parent_view_ids = vec![];
+    if let Some(lock) = self.mutex_folder.load_full() {
+      let mut folder = lock.write().await;
+      for view in imported_views {
+        if !parent_view_ids.contains(&view.parent_view_id) {
+          parent_view_ids.push(view.parent_view_id.clone());
+        }
+        views.push(view_pb_without_child_views(view.clone()));
+        folder.insert_view(view, None);
+      }
+    }
+
+    info!("Syncing the imported {} collab to the cloud", objects.len());
+    self
+      .cloud_service
+      .batch_create_folder_collab_objects(&workspace_id, objects)
+      .await?;
+
+    if let Some(lock) = self.mutex_folder.load_full() {
+      let folder = lock.read().await;
+      notify_parent_view_did_change(&workspace_id, &folder, parent_view_ids);
+    }
+    Ok(RepeatedViewPB { items: views })
+  }
+
+  /// Create the view from its exported data, whose ids are replaced with the `ids`. The documents
+  /// of the rows are only created once for all the views of a database, the ids of their rows are
+  /// added to `imported_row_ids`.
+  async fn import_exported_view_data(
+    &self,
+    uid: i64,
+    view: &View,
+    data: ExportedViewData,
+    ids: &HashMap<String, String>,
+    imported_row_ids: &mut HashSet<String>,
+  ) -> FlowyResult<Vec<ImportedData>> {
+    let handler = self.get_handler(&view.layout)?;
+    let encoded_collabs = handler
+      .create_view_from_template(uid, &view.id, &replace_template_ids(&data.data.data, ids))
+      .await?;
+    let row_documents = data
+      .row_documents
+      .into_iter()
+      .filter(|(row_id, _)| imported_row_ids.insert(row_id.clone()))
+      .filter_map(|(row_id, json)| {
+        Some((ids.get(&row_id)?.clone(), replace_template_ids(&json, ids)))
+      })
+      .collect::<HashMap<_, _>>();
+    if !row_documents.is_empty() {
+      handler.create_row_documents(row_documents).await?;
+    }
+    Ok(encoded_collabs)
+  }
+
+  /// Create the view from its exported files, for the exports without the data file of the view.
+  async fn import_exported_view(
+    &self,
+    uid: i64,
+    export_dir: &Path,
+    view: &View,
+    files: &[String],
+    links: &HashMap<PathBuf, String>,
+  ) -> FlowyResult<Vec<ImportedData>> {
+    let handler = self.get_handler(&view.layout)?;
+    match files.first() {
+      Some(path) if view.layout == ViewLayout::Document => {
+        let path = exported_file_path(export_dir, path)?;
+        handler
+          .import_document_file(uid, &view.id, &path, export_dir, links)
+          .await
+      },
+      Some(path) if view.layout.is_database() => {
+        // The data file keeps the field types and the cells. The exports without it are imported
+        // from the CSV file, whose field types are inferred.
+        let (path, import_type) = match files
+          .iter()
+          .find(|path| path.ends_with(EXPORT_DATABASE_DATA_SUFFIX))
+        {
+          Some(data_path) => (data_path, ImportType::AFDatabase),
+          None => (path, ImportType::NotionCSV),
+        };
+        let bytes = tokio::fs::read(exported_file_path(export_dir, path)?).await?;
+        let encoded_collabs = handler
+          .import_from_bytes(uid, &view.id, &view.name, import_type, bytes)
+          .await?;
+
+        // The database is imported as a grid, so its layout is updated like a changed view.
+        if view.layout != ViewLayout::Grid {
+          let mut grid_view = view.clone();
+          grid_view.layout = ViewLayout::Grid;
+          handler.did_update_view(&grid_view, view).await?;
+        }
+        Ok(encoded_collabs)
+      },
+      // The views without the exported files, such as the chats, are created with the default
+      // data.
+      _ => {
+        handler
+          .create_view_with_default_data(uid, &view.id, &view.name, view.layout.clone())
+          .await?;
+        Ok(vec![])
+      },
+    }
+  }
+
+  /// Import the extracted Markdown & CSV export of Notion to the top level of the workspace. The
+  /// progress is sent by the [FolderNotification::DidUpdateImportProgress] notification. A page
+  /// that fails to import is skipped, and its child pages are imported into its parent.
+  #[instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn import_notion_export(
+    &self,
+    zip_file_path: &str,
+    export_dir: &Path,
+  ) -> FlowyResult<RepeatedViewPB> {
+    let uid = self.user.user_id()?;
+    let workspace_id = self.user.workspace_id()?;
+    let cloned_export_dir = export_dir.to_path_buf();
+    let pages = tokio::task::spawn_blocking(move || collect_notion_pages(&cloned_export_dir))
+      .await
+      .map_err(internal_error)??;
+    let links = notion_page_links(&pages);
+
+    let mut progress = ImportProgressPB {
+      file_path: zip_file_path.to_string(),
+      total: pages.len() as i64,
+      ..Default::default()
+    };
+    let mut imported_view_ids: Vec<Option<String>> = Vec::with_capacity(pages.len());
+    let mut parent_view_ids = vec![];
+    let mut views = vec![];
+    let mut objects = vec![];
+    for page in &pages {
+      progress.current_name = page.name.clone();
+      send_notification(&workspace_id, FolderNotification::DidUpdateImportProgress)
+        .payload(progress.clone())
+        .send();
+
+      // The nearest parent that has been imported.
+      let mut parent = page.parent;
+      let parent_view_id = loop {
+        match parent {
+          Some(index) => match &imported_view_ids[index] {
+            Some(view_id) => break view_id.clone(),
+            None => parent = pages[index].parent,
+          },
+          None => break workspace_id.clone(),
+        }
+      };
+
+      match self.import_notion_page(uid, page, export_dir, &links).await {
+        Ok(encoded_collabs) => {
+          let params = CreateViewParams {
+            parent_view_id: parent_view_id.clone(),
+            name: page.name.clone(),
+            layout: page.layout.clone().into(),
+            initial_data: ViewData::Empty,
+            view_id: page.view_id.clone(),
+            meta: Default::default(),
+            set_as_current: false,
+            index: None,
+            section: None,
+            extra: None,
+            icon: None,
+          };
+          let view = create_view(uid, params, page.layout.clone());
+          if let Some(lock) = self.mutex_folder.load_full() {
+            lock.write().await.insert_view(view.clone(), None);
+          }
+          views.push(view_pb_without_child_views(view));
+          imported_view_ids.push(Some(page.view_id.clone()));
+          if !parent_view_ids.contains(&parent_view_id) {
+            parent_view_ids.push(parent_view_id);
+          }
+
+          for (object_id, collab_type, encoded_collab) in encoded_collabs {
+            match self.get_folder_collab_params(object_id, collab_type, encoded_collab) {
+              Ok(params) => objects.push(params),
+              Err(err) => error!("import error {}", err),
+            }
+          }
+        },
+        Err(err) => {
+          error!("Failed to import the page {}: {}", page.name, err);
+          imported_view_ids.push(None);
+        },
+      }
+      progress.imported += 1;
+    }
+
+    progress.current_name = String::new();
+    progress.is_finished = true;
+    send_notification(&workspace_id, FolderNotification::DidUpdateImportProgress)
+      .payload(progress)
+      .send();
+
+    info!("Syncing the imported {} collab to the cloud", objects.len());
+    self
+      .cloud_service
+      .batch_create_folder_collab_objects(&workspace_id, objects)
+      .await?;
+
+    if let Some(lock) = self.mutex_folder.load_full() {
+      let folder = lock.read().await;
+      notify_parent_view_did_change(&workspace_id, &folder, parent_view_ids);
+    }
+    Ok(RepeatedViewPB { items: views })
+  }
+
+  async fn import_notion_page(
+    &self,
+    uid: i64,
+    page: &NotionPage,
+    export_dir: &Path,
+    links: &HashMap<PathBuf, String>,
+  ) -> FlowyResult<Vec<ImportedData>> {
+    let handler = self.get_handler(&page.layout)?;
+    match page.files.first() {
+      None => {
+        handler
+          .create_view_with_default_data(uid, &page.view_id, &page.name, page.layout.clone())
+          .await?;
+        Ok(vec![])
+      },
+      Some(path) if page.layout == ViewLayout::Document => {
+        handler
+          .import_document_file(uid, &page.view_id, path, export_dir, links)
+          .await
+      },
+      Some(path) => {
+        let bytes = tokio::fs::read(path).await?;
+        handler
+          .import_from_bytes(uid, &page.view_id, &page.name, ImportType::NotionCSV, bytes)
+          .await
+      },
+    }
+  }
+
+  /// Import function to handle the import of data.
+  pub(crate) async fn import(&self, import_data: ImportParams) -> FlowyResult<RepeatedViewPB> {
+    let workspace_id = self.user.workspace_id()?;
+    let mut objects = vec![];
+    let mut views = vec![];
+    for data in import_data.values {
+      // Import a single file and get the view and encoded collab data
+      let (view, encoded_collabs) = self
+        .import_single_file(import_data.parent_view_id.clone(), data)
+        .await?;
+      views.push(view_pb_without_child_views(view));
+
+      for (object_id, collab_type, encode_collab) in encoded_collabs {
+        match self.get_folder_collab_params(object_id, collab_type, encode_collab) {
+          Ok(params) => objects.push(params),
+          Err(e) => {
+            error!("import error {}", e);
+          },
+        }
+      }
+    }
+
+    info!("Syncing the imported {} collab to the cloud", objects.len());
+    self
+      .cloud_service
+      .batch_create_folder_collab_objects(&workspace_id, objects)
+      .await?;
+
+    // Notify that the parent view has changed
+    if let Some(lock) = self.mutex_folder.load_full() {
+      let folder = lock.read().await;
+      notify_parent_view_did_change(&workspace_id, &folder, vec![import_data.parent_view_id]);
+    }
+
+    Ok(RepeatedViewPB { items: views })
+  }
+
+  /// Export the view with its descendants, or the whole workspace if `view_id` is None, to the zip
+  /// file. The child views are exported to the directory named after their parent, and the
+  /// manifest records the hierarchy, so the export can be imported again. The views in the trash
+  /// are skipped. Returns the number of the exported views.
+  #[instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn export_zip_file(
+    &self,
+    view_id: Option<String>,
+    format: ExportFormat,
+    zip_file_path: &str,
+  ) -> FlowyResult<usize> {
+    let workspace_id = self.user.workspace_id()?;
+    let items = {
+      let lock = self
+        .mutex_folder
+        .load_full()
+        .ok_or_else(folder_not_init_error)?;
+      let folder = lock.read().await;
+      let filtered_view_ids = Self::get_view_ids_should_be_filtered(&folder);
+      let views = match &view_id {
+        Some(view_id) => vec![folder
+          .get_view(view_id)
+          .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the view"))?],
+        None => folder
+          .get_views_belong_to(&workspace_id)
+          .into_iter()
+          .filter(|view| !filtered_view_ids.contains(&view.id))
+          .collect(),
+      };
+      let mut items = vec![];
+      collect_export_views(&folder, views, None, "", &filtered_view_ids, &mut items);
+      items
+    };
+
+    // The paths of the main files, which the exported documents link to.
+    let pages = items
+      .iter()
+      .filter_map(|item| {
+        let extension = match item.view.layout {
+          ViewLayout::Document => format.extension(),
+          ViewLayout::Grid | ViewLayout::Board | ViewLayout::Calendar => "csv",
+          _ => return None,
+        };
+        let page = ExportedPage {
+          name: item.view.name.clone(),
+          path: format!("{}.{}", item.file_stem, extension),
+        };
+        Some((item.view.id.clone(), page))
+      })
+      .collect::<HashMap<_, _>>();
+
+    let temp_dir = tempfile::tempdir()?;
+    let mut views = Vec::with_capacity(items.len());
+    for item in items {
+      let files = match self.get_handler(&item.view.layout) {
+        Ok(handler) => {
+          let mut files = match handler
+            .export_view(&item.view.id, &item.file_stem, format, &pages)
+            .await
+          {
+            Ok(files) => files,
+            Err(err) if err.code == ErrorCode::NotSupportYet => vec![],
+            Err(err) => {
+              error!("Failed to export the view {}: {}", item.view.id, err);
+              return Err(err);
+            },
+          };
+          if !files.is_empty() {
+            if let Some(file) =
+              export_view_data_file(handler, &item.view.id, &item.file_stem).await?
+            {
+              files.push(file);
+            }
+          }
+          files
+        },
+        Err(_) => vec![],
+      };
+      for file in &files {
+        let path = temp_dir.path().join(&file.path);
+        if let Some(parent) = path.parent() {
+          tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, &file.data).await?;
+      }
+      views.push(ExportedView {
+        view_id: item.view.id.clone(),
+        parent_view_id: item.parent_view_id,
+        name: item.view.name.clone(),
+        layout: item.view.layout.clone(),
+        icon: item.view.icon.clone(),
+        extra: item.view.extra.clone(),
+        created_at: item.view.created_at,
+        files: files.into_iter().map(|file| file.path).collect(),
+      });
+    }
+
+    let view_count = views.len();
+    let manifest = ExportManifest {
+      version: EXPORT_MANIFEST_VERSION,
+      workspace_id,
+      root_view_id: view_id,
+      document_format: format,
+      exported_at: timestamp(),
+      views,
     };
+    tokio::fs::write(
+      temp_dir.path().join(EXPORT_MANIFEST_FILE_NAME),
+      serde_json::to_vec_pretty(&manifest)?,
+    )
+    .await?;
+
+    let export_dir = temp_dir.path().to_path_buf();
+    let zip_path = PathBuf::from(zip_file_path);
+    tokio::task::spawn_blocking(move || zip_folder(export_dir, &zip_path))
+      .await
+      .map_err(internal_error)??;
+    Ok(view_count)
+  }
 
-    let view = create_view(self.user.user_id()?, params, import_data.view_layout);
+  /// Save the view with its descendants as a template of the current user. The views in the
+  /// trash, and the child views whose content can't be saved, are skipped with their descendants.
+  #[instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn save_view_as_template(
+    &self,
+    view_id: &str,
+    name: Option<String>,
+    include_rows: bool,
+  ) -> FlowyResult<ViewTemplate> {
+    let items = {
+      let lock = self
+        .mutex_folder
+        .load_full()
+        .ok_or_else(folder_not_init_error)?;
+      let folder = lock.read().await;
+      let filtered_view_ids = Self::get_view_ids_should_be_filtered(&folder);
+      let view = folder
+        .get_view(view_id)
+        .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the view"))?;
+      let mut items = vec![];
+      collect_export_views(
+        &folder,
+        vec![view],
+        None,
+        "",
+        &filtered_view_ids,
+        &mut items,
+      );
+      items
+    };
 
-    // Insert the new view into the folder
-    if let Some(lock) = self.mutex_folder.load_full() {
-      let mut folder = lock.write().await;
-      folder.insert_view(view.clone(), None);
+    let mut views: Vec<TemplateView> = Vec::with_capacity(items.len());
+    for item in items {
+      let is_root = item.parent_view_id.is_none();
+      if !is_root
+        && !views
+          .iter()
+          .any(|view| item.parent_view_id.as_ref() == Some(&view.view_id))
+      {
+        continue;
+      }
+
+      let result = match self.get_handler(&item.view.layout) {
+        Ok(handler) => {
+          handler
+            .get_template_view_data(&item.view.id, include_rows)
+            .await
+        },
+        Err(err) => Err(err),
+      };
+      let data = match result {
+        Ok(data) => data,
+        Err(err) if !is_root => {
+          error!("Skip the view {} of the template: {}", item.view.id, err);
+          continue;
+        },
+        Err(err) => return Err(err),
+      };
+      views.push(TemplateView {
+        view_id: item.view.id.clone(),
+        parent_view_id: item.parent_view_id,
+        name: item.view.name.clone(),
+        layout: item.view.layout.clone(),
+        icon: item.view.icon.clone(),
+        extra: item.view.extra.clone(),
+        data,
+      });
     }
 
-    Ok((view, encoded_collab))
+    let name = name
+      .filter(|name| !name.trim().is_empty())
+      .or_else(|| views.first().map(|view| view.name.clone()))
+      .filter(|name| !name.trim().is_empty())
+      .unwrap_or_else(|| "Untitled".to_string());
+    let template = ViewTemplate {
+      id: gen_view_id().to_string(),
+      name,
+      created_at: timestamp(),
+      include_rows,
+      views,
+    };
+    let mut templates = self.get_view_templates()?;
+    templates.push(template.clone());
+    self.save_view_templates(templates)?;
+    Ok(template)
   }
 
-  pub(crate) async fn import_zip_file(&self, zip_file_path: &str) -> FlowyResult<()> {
-    self.cloud_service.import_zip(zip_file_path).await?;
+  /// Returns the templates of the current user, the oldest first.
+  pub(crate) fn get_view_templates(&self) -> FlowyResult<Vec<ViewTemplate>> {
+    let key = view_templates_key(self.user.user_id()?);
+    Ok(self.store_preferences.get_object(&key).unwrap_or_default())
+  }
+
+  fn save_view_templates(&self, templates: Vec<ViewTemplate>) -> FlowyResult<()> {
+    let key = view_templates_key(self.user.user_id()?);
+    self.store_preferences.set_object(&key, &templates)?;
     Ok(())
   }
 
-  /// Import function to handle the import of data.
-  pub(crate) async fn import(&self, import_data: ImportParams) -> FlowyResult<RepeatedViewPB> {
+  pub(crate) fn rename_view_template(&self, template_id: &str, name: String) -> FlowyResult<()> {
+    let mut templates = self.get_view_templates()?;
+    let template = templates
+      .iter_mut()
+      .find(|template| template.id == template_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the template"))?;
+    template.name = name;
+    self.save_view_templates(templates)
+  }
+
+  pub(crate) fn delete_view_template(&self, template_id: &str) -> FlowyResult<()> {
+    let mut templates = self.get_view_templates()?;
+    let len = templates.len();
+    templates.retain(|template| template.id != template_id);
+    if templates.len() == len {
+      return Err(FlowyError::record_not_found().with_context("Can't find the template"));
+    }
+    self.save_view_templates(templates)
+  }
+
+  /// Create the views of the template under the parent view. Every view and every object of the
+  /// template gets a new id, so the mentions of the pages and the relations of the databases point
+  /// to the new views. The `{{date}}` and `{{time}}` variables default to the current time, and
+  /// the `{{user}}` variable to the name of the current user.
+  /// The views are only added to the folder when the content of all of them is created, and the
+  /// content that was created is removed if any view fails. Returns the root view of the new views.
+  #[instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn create_view_from_template(
+    &self,
+    template_id: &str,
+    parent_view_id: &str,
+    mut variables: HashMap<String, String>,
+  ) -> FlowyResult<ViewPB> {
+    let uid = self.user.user_id()?;
     let workspace_id = self.user.workspace_id()?;
-    let mut objects = vec![];
+    let template = self
+      .get_view_templates()?
+      .into_iter()
+      .find(|template| template.id == template_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the template"))?;
+    let root_view_id = template
+      .root_view()
+      .map(|view| view.view_id.clone())
+      .ok_or_else(|| FlowyError::invalid_data().with_context("The template is empty"))?;
+
+    let now = chrono::Local::now();
+    variables
+      .entry(TEMPLATE_VARIABLE_DATE.to_string())
+      .or_insert_with(|| now.format("%Y-%m-%d").to_string());
+    variables
+      .entry(TEMPLATE_VARIABLE_TIME.to_string())
+      .or_insert_with(|| now.format("%H:%M").to_string());
+    if !variables.contains_key(TEMPLATE_VARIABLE_USER) {
+      let name = self.user.user_name().unwrap_or_else(|err| {
+        error!("Failed to read the name of the user: {}", err);
+        String::new()
+      });
+      variables.insert(TEMPLATE_VARIABLE_USER.to_string(), name);
+    }
+
+    let ids = template
+      .views
+      .iter()
+      .flat_map(|view| std::iter::once(&view.view_id).chain(view.data.object_ids.iter()))
+      .map(|id| (id.clone(), gen_view_id().to_string()))
+      .collect::<HashMap<String, String>>();
+
     let mut views = vec![];
-    for data in import_data.values {
-      // Import a single file and get the view and encoded collab data
-      let (view, encoded_collabs) = self
-        .import_single_file(import_data.parent_view_id.clone(), data)
-        .await?;
-      views.push(view_pb_without_child_views(view));
+    let mut objects = vec![];
+    let mut result = Ok(());
+    for template_view in &template.views {
+      let parent_view_id = match &template_view.parent_view_id {
+        None => parent_view_id.to_string(),
+        Some(id) => ids.get(id).cloned().unwrap_or_default(),
+      };
+      let view_id = ids[&template_view.view_id].clone();
+      let data = substitute_template_variables_in_json(
+        &replace_template_ids(&template_view.data.data, &ids),
+        &variables,
+      );
+      let encoded_collabs = match self.get_handler(&template_view.layout) {
+        Ok(handler) => {
+          handler
+            .create_view_from_template(uid, &view_id, &data)
+            .await
+        },
+        Err(err) => Err(err),
+      };
+      let encoded_collabs = match encoded_collabs {
+        Ok(encoded_collabs) => encoded_collabs,
+        Err(err) => {
+          error!(
+            "Failed to create the view {} of the template: {}",
+            view_id, err
+          );
+          result = Err(err);
+          break;
+        },
+      };
 
-      for (object_id, collab_type, encode_collab) in encoded_collabs {
-        match self.get_folder_collab_params(object_id, collab_type, encode_collab) {
+      let params = CreateViewParams {
+        parent_view_id,
+        name: substitute_template_variables(&template_view.name, &variables),
+        layout: template_view.layout.clone().into(),
+        initial_data: ViewData::Empty,
+        view_id: view_id.clone(),
+        meta: Default::default(),
+        set_as_current: false,
+        index: None,
+        section: None,
+        extra: template_view
+          .extra
+          .as_ref()
+          .map(|extra| replace_template_ids(extra, &ids)),
+        icon: template_view.icon.clone(),
+      };
+      views.push(create_view(uid, params, template_view.layout.clone()));
+      for (object_id, collab_type, encoded_collab) in encoded_collabs {
+        match self.get_folder_collab_params(object_id, collab_type, encoded_collab) {
           Ok(params) => objects.push(params),
-          Err(e) => {
-            error!("import error {}", e);
-          },
+          Err(err) => result = Err(err),
         }
       }
+      if result.is_err() {
+        break;
+      }
     }
 
-    info!("Syncing the imported {} collab to the cloud", objects.len());
-    self
-      .cloud_service
-      .batch_create_folder_collab_objects(&workspace_id, objects)
-      .await?;
+    if result.is_ok() {
+      result = self
+        .cloud_service
+        .batch_create_folder_collab_objects(&workspace_id, objects)
+        .await;
+    }
+    if let Err(err) = result {
+      // Remove the content of the views that were created, since the views are not added.
+      for view in &views {
+        if let Ok(handler) = self.get_handler(&view.layout) {
+          if let Err(err) = handler.purge_view(&view.id).await {
+            error!(
+              "Failed to remove the view {} of the template: {}",
+              view.id, err
+            );
+          }
+        }
+      }
+      return Err(err);
+    }
 
-    // Notify that the parent view has changed
+    if let Some(lock) = self.mutex_folder.load_full() {
+      let mut folder = lock.write().await;
+      for view in views {
+        folder.insert_view(view, None);
+      }
+    }
     if let Some(lock) = self.mutex_folder.load_full() {
       let folder = lock.read().await;
-      notify_parent_view_did_change(&workspace_id, &folder, vec![import_data.parent_view_id]);
+      notify_parent_view_did_change(&workspace_id, &folder, vec![parent_view_id.to_string()]);
     }
+    self.get_view_pb(&ids[&root_view_id]).await
+  }
 
-    Ok(RepeatedViewPB { items: views })
+  /// Move or copy the view with its descendants into another workspace of the user, under the
+  /// parent view or at the root of the workspace. All the collabs of the views are collected and
+  /// written into the other workspace before its folder is updated, so a failure leaves no views
+  /// or copied files behind. The views and their objects get new ids in the other workspace, and
+  /// a move removes the views with their collabs and files from the current workspace. Returns
+  /// the transferred view.
+  #[instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn transfer_view_to_workspace(
+    &self,
+    view_id: &str,
+    target_workspace_id: &str,
+    target_parent_view_id: Option<String>,
+    is_copy: bool,
+  ) -> FlowyResult<ViewPB> {
+    let uid = self.user.user_id()?;
+    let workspace_id = self.user.workspace_id()?;
+    if target_workspace_id == workspace_id {
+      return Err(FlowyError::invalid_data().with_context("The view is already in the workspace"));
+    }
+
+    let items = {
+      let lock = self
+        .mutex_folder
+        .load_full()
+        .ok_or_else(folder_not_init_error)?;
+      let folder = lock.read().await;
+      let filtered_view_ids = Self::get_view_ids_should_be_filtered(&folder);
+      let view = folder
+        .get_view(view_id)
+        .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the view"))?;
+      let mut items = vec![];
+      collect_export_views(
+        &folder,
+        vec![view],
+        None,
+        "",
+        &filtered_view_ids,
+        &mut items,
+      );
+      items
+    };
+
+    // 1. Open the folder of the target workspace and check the parent view before anything is
+    // copied.
+    let collab_db = self.user.collab_db(uid)?;
+    let data_source = if self
+      .user
+      .is_folder_exist_on_disk(uid, target_workspace_id)?
+    {
+      None
+    } else {
+      let doc_state = self
+        .cloud_service
+        .get_folder_doc_state(
+          target_workspace_id,
+          uid,
+          CollabType::Folder,
+          target_workspace_id,
+        )
+        .await?;
+      Some(DataSource::DocStateV1(doc_state))
+    };
+    let target_folder = self
+      .make_folder(
+        uid,
+        target_workspace_id,
+        collab_db.clone(),
+        data_source,
+        None::<FolderNotify>,
+      )
+      .await?;
+    let target_parent_view_id =
+      target_parent_view_id.unwrap_or_else(|| target_workspace_id.to_string());
+    if target_parent_view_id != target_workspace_id
+      && target_folder
+        .read()
+        .await
+        .get_view(&target_parent_view_id)
+        .is_none()
+    {
+      return Err(
+        FlowyError::record_not_found().with_context("Can't find the parent view in the workspace"),
+      );
+    }
+    let db = collab_db
+      .upgrade()
+      .ok_or_else(|| FlowyError::internal().with_context("The collab db is already dropped"))?;
+
+    let mut ids = items
+      .iter()
+      .map(|item| (item.view.id.clone(), gen_view_id().to_string()))
+      .collect::<HashMap<String, String>>();
+
+    // 2. Collect the collabs of all the views, with their files copied into the target workspace.
+    // The copied files are removed if any step before the views are inserted fails.
+    let mut transferred = vec![];
+    let result = self
+      .write_views_to_workspace(
+        uid,
+        &db,
+        target_workspace_id,
+        &items,
+        &mut ids,
+        is_copy,
+        &mut transferred,
+      )
+      .await;
+    if let Err(err) = result {
+      for (layout, view_collabs) in transferred {
+        if let Ok(handler) = self.get_handler(&layout) {
+          handler.delete_view_files(view_collabs.files).await;
+        }
+      }
+      return Err(err);
+    }
+
+    // 4. Insert the views into the target folder.
+    let root_view_id = ids[view_id].clone();
+    let root_view = {
+      let mut folder = target_folder.write().await;
+      for item in &items {
+        let parent_view_id = match &item.parent_view_id {
+          None => target_parent_view_id.clone(),
+          Some(id) => ids.get(id).cloned().unwrap_or_default(),
+        };
+        let params = CreateViewParams {
+          parent_view_id,
+          name: item.view.name.clone(),
+          layout: item.view.layout.clone().into(),
+          initial_data: ViewData::Empty,
+          view_id: ids[&item.view.id].clone(),
+          meta: Default::default(),
+          set_as_current: false,
+          index: None,
+          section: None,
+          extra: item
+            .view
+            .extra
+            .as_ref()
+            .map(|extra| replace_template_ids(extra, &ids)),
+          icon: item.view.icon.clone(),
+        };
+        folder.insert_view(create_view(uid, params, item.view.layout.clone()), None);
+      }
+      let view = folder
+        .get_view(&root_view_id)
+        .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the view"))?;
+      view_pb_with_child_views(view, folder.get_views_belong_to(&root_view_id))
+    };
+
+    // 5. Remove the moved views, including their descendants in the trash, with their collabs
+    // and files from the current workspace.
+    if !is_copy {
+      if let Some(lock) = self.mutex_folder.load_full() {
+        let (parent_view_id, views) = {
+          let mut folder = lock.write().await;
+          let mut view_ids = vec![view_id.to_string()];
+          view_ids.extend(get_all_child_view_ids(&folder, view_id));
+          let views = view_ids
+            .iter()
+            .filter_map(|id| folder.get_view(id))
+            .collect::<Vec<_>>();
+          let parent_view_id = views
+            .first()
+            .map(|view| view.parent_view_id.clone())
+            .unwrap_or_default();
+          if let Some(view) = views.first() {
+            Self::unfavorite_view_and_decendants(view.clone(), &mut folder);
+          }
+          folder.delete_trash_view_ids(view_ids.clone());
+          folder.delete_views(view_ids.iter().map(|id| id.as_str()).collect());
+          (parent_view_id, views)
+        };
+
+        for view in views {
+          if let Ok(handler) = self.get_handler(&view.layout) {
+            if let Err(err) = handler.delete_view(&view.id).await {
+              error!("Failed to delete the moved view {}: {}", view.id, err);
+            }
+          }
+        }
+        for (layout, view_collabs) in transferred {
+          for object_id in &view_collabs.source_object_ids {
+            if let Ok(true) = db.is_exist(uid, &workspace_id, object_id).await {
+              if let Err(err) = db.delete_doc(uid, &workspace_id, object_id).await {
+                error!("Failed to remove the moved collab {}: {}", object_id, err);
+              }
+            }
+          }
+          if let Ok(handler) = self.get_handler(&layout) {
+            handler.delete_view_files(view_collabs.source_files).await;
+          }
+        }
+        let folder = lock.read().await;
+        notify_parent_view_did_change(&workspace_id, &folder, vec![parent_view_id]);
+      }
+    }
+    Ok(root_view)
+  }
+
+  /// Collects the collabs of the views into `transferred`, writes them into the target workspace
+  /// in one transaction and creates them on the server. The written collabs are removed if the
+  /// server fails to create them.
+  #[allow(clippy::too_many_arguments)]
+  async fn write_views_to_workspace(
+    &self,
+    uid: i64,
+    db: &CollabKVDB,
+    target_workspace_id: &str,
+    items: &[ExportViewItem],
+    ids: &mut HashMap<String, String>,
+    is_copy: bool,
+    transferred: &mut Vec<(ViewLayout, WorkspaceViewCollabs)>,
+  ) -> FlowyResult<()> {
+    let mut databases: HashMap<String, Vec<String>> = HashMap::new();
+    for item in items {
+      let view_collabs = self
+        .get_handler(&item.view.layout)?
+        .get_view_collabs_for_workspace(
+          self.user.clone(),
+          &item.view.id,
+          target_workspace_id,
+          ids,
+          is_copy,
+        )
+        .await?;
+      for (database_id, view_ids) in &view_collabs.databases {
+        databases
+          .entry(database_id.clone())
+          .or_default()
+          .extend(view_ids.iter().cloned());
+      }
+      transferred.push((item.view.layout.clone(), view_collabs));
+    }
+    let collabs = transferred
+      .iter()
+      .flat_map(|(_, view_collabs)| view_collabs.collabs.iter())
+      .collect::<Vec<_>>();
+    let objects = collabs
+      .iter()
+      .map(|(object_id, collab_type, encoded_collab)| {
+        self.get_folder_collab_params(
+          object_id.clone(),
+          collab_type.clone(),
+          encoded_collab.clone(),
+        )
+      })
+      .collect::<FlowyResult<Vec<_>>>()?;
+
+    // 3. Write the collabs into the target workspace.
+    let write_txn = db.write_txn();
+    for (object_id, _, encoded_collab) in &collabs {
+      write_txn
+        .flush_doc(
+          uid,
+          target_workspace_id,
+          object_id,
+          encoded_collab.state_vector.to_vec(),
+          encoded_collab.doc_state.to_vec(),
+        )
+        .map_err(internal_error)?;
+    }
+    write_txn.commit_transaction().map_err(internal_error)?;
+
+    if let Err(err) = self
+      .cloud_service
+      .batch_create_folder_collab_objects(target_workspace_id, objects)
+      .await
+    {
+      for (object_id, _, _) in &collabs {
+        if let Err(err) = db.delete_doc(uid, target_workspace_id, object_id).await {
+          error!(
+            "Failed to remove the transferred collab {}: {}",
+            object_id, err
+          );
+        }
+      }
+      return Err(err);
+    }
+    if !databases.is_empty() {
+      self.add_pending_database_views(target_workspace_id, databases)?;
+    }
+    Ok(())
+  }
+
+  fn add_pending_database_views(
+    &self,
+    workspace_id: &str,
+    databases: HashMap<String, Vec<String>>,
+  ) -> FlowyResult<()> {
+    let key = pending_database_views_key(self.user.user_id()?, workspace_id);
+    let mut pending: HashMap<String, Vec<String>> =
+      self.store_preferences.get_object(&key).unwrap_or_default();
+    for (database_id, view_ids) in databases {
+      pending.entry(database_id).or_default().extend(view_ids);
+    }
+    self.store_preferences.set_object(&key, &pending)?;
+    Ok(())
+  }
+
+  /// Returns the view ids of the databases, by the database ids, that were moved or copied into
+  /// the workspace from another one. They are returned once, so they should be tracked by the
+  /// databases of the workspace when it's opened.
+  pub fn take_pending_database_views(
+    &self,
+    workspace_id: &str,
+  ) -> FlowyResult<HashMap<String, Vec<String>>> {
+    let key = pending_database_views_key(self.user.user_id()?, workspace_id);
+    let pending = self.store_preferences.get_object(&key).unwrap_or_default();
+    self.store_preferences.remove(&key);
+    Ok(pending)
   }
 
   /// Update the view with the provided view_id using the specified function.
   async fn update_view<F>(&self, view_id: &str, f: F) -> FlowyResult<()>
   where
     F: FnOnce(ViewUpdate) -> Option<View>,
+  {
+    self.update_view_with(view_id, |_, update| f(update)).await
+  }
+
+  /// Like [Self::update_view], but `f` also gets the view read under the same write lock, so the
+  /// values computed from the current view, like its extra, don't overwrite a concurrent update.
+  async fn update_view_with<F>(&self, view_id: &str, f: F) -> FlowyResult<()>
+  where
+    F: FnOnce(&View, ViewUpdate) -> Option<View>,
   {
     let workspace_id = self.user.workspace_id()?;
     let value = match self.mutex_folder.load_full() {
@@ -1738,7 +3704,10 @@ impl FolderManager {
       Some(lock) => {
         let mut folder = lock.write().await;
         let old_view = folder.get_view(view_id);
-        let new_view = folder.update_view(view_id, f);
+        let new_view = match &old_view {
+          Some(old_view) => folder.update_view(view_id, |update| f(old_view, update)),
+          None => None,
+        };
 
         Some((old_view, new_view))
       },
@@ -1965,6 +3934,146 @@ pub(crate) fn get_workspace_public_view_pbs(workspace_id: &str, folder: &Folder)
     .collect()
 }
 
+/// A view to export, and the path of its files without the extensions.
+struct ExportViewItem {
+  view: Arc<View>,
+  parent_view_id: Option<String>,
+  file_stem: String,
+}
+
+/// Returns an error if another tag than `tag_id` already has the name.
+fn check_tag_name(tags: &[ViewTag], tag_id: &str, name: &str) -> FlowyResult<()> {
+  if name.trim().is_empty() {
+    return Err(FlowyError::invalid_data().with_context("The name of the tag is empty"));
+  }
+  if tags
+    .iter()
+    .any(|tag| tag.id != tag_id && tag.name.eq_ignore_ascii_case(name.trim()))
+  {
+    return Err(
+      FlowyError::invalid_data().with_context(format!("The tag {} already exists", name.trim())),
+    );
+  }
+  Ok(())
+}
+
+/// Returns the views of the batch, or an error if any of them doesn't exist, so a batch is
+/// applied to all the views or none of them.
+fn check_batch_views(folder: &Folder, view_ids: &[String]) -> FlowyResult<Vec<Arc<View>>> {
+  let mut views = Vec::with_capacity(view_ids.len());
+  let mut missing_view_ids = vec![];
+  for view_id in view_ids {
+    match folder.get_view(view_id) {
+      Some(view) => views.push(view),
+      None => missing_view_ids.push(view_id.as_str()),
+    }
+  }
+  if !missing_view_ids.is_empty() {
+    return Err(FlowyError::record_not_found().with_context(format!(
+      "Can't find the views: {}",
+      missing_view_ids.join(", ")
+    )));
+  }
+  Ok(views)
+}
+
+/// Removes the duplicated and empty ids, keeping the order of the ids.
+fn unique_view_ids(view_ids: Vec<String>) -> Vec<String> {
+  let mut seen = HashSet::new();
+  view_ids
+    .into_iter()
+    .filter(|view_id| !view_id.is_empty() && seen.insert(view_id.clone()))
+    .collect()
+}
+
+fn parent_view_ids_of(views: &[Arc<View>]) -> Vec<String> {
+  views
+    .iter()
+    .map(|view| view.parent_view_id.clone())
+    .collect()
+}
+
+/// A trash item whose retention expired, with its view and the descendants to purge.
+struct ExpiredTrash {
+  trash: TrashInfo,
+  views: Vec<Arc<View>>,
+}
+
+/// Collect the views and their descendants in the order of the folder. The files of the child
+/// views are in the directory named after the stem of their parent.
+fn collect_export_views(
+  folder: &Folder,
+  views: Vec<Arc<View>>,
+  parent_view_id: Option<&str>,
+  dir: &str,
+  filtered_view_ids: &[String],
+  items: &mut Vec<ExportViewItem>,
+) {
+  let mut file_names = HashSet::new();
+  for view in views {
+    let file_name = export_file_name(&mut file_names, &view.name);
+    let file_stem = if dir.is_empty() {
+      file_name
+    } else {
+      format!("{}/{}", dir, file_name)
+    };
+    let child_views = folder
+      .get_views_belong_to(&view.id)
+      .into_iter()
+      .filter(|view| !filtered_view_ids.contains(&view.id))
+      .collect();
+    let view_id = view.id.clone();
+    items.push(ExportViewItem {
+      view,
+      parent_view_id: parent_view_id.map(|id| id.to_string()),
+      file_stem: file_stem.clone(),
+    });
+    collect_export_views(
+      folder,
+      child_views,
+      Some(&view_id),
+      &file_stem,
+      filtered_view_ids,
+      items,
+    );
+  }
+}
+
+/// Returns the data file of the exported view, or `None` if the handler can't save the content of
+/// the view.
+async fn export_view_data_file(
+  handler: Arc<dyn FolderOperationHandler + Send + Sync>,
+  view_id: &str,
+  file_stem: &str,
+) -> FlowyResult<Option<ExportedFile>> {
+  let data = match handler.get_template_view_data(view_id, true).await {
+    Ok(data) => data,
+    Err(err) if err.code == ErrorCode::NotSupportYet => return Ok(None),
+    Err(err) => return Err(err),
+  };
+  let data = ExportedViewData {
+    data,
+    row_documents: handler.get_row_documents(view_id).await?,
+  };
+  Ok(Some(ExportedFile {
+    path: format!("{}{}", file_stem, EXPORT_VIEW_DATA_SUFFIX),
+    data: serde_json::to_vec(&data)?,
+  }))
+}
+
+/// Resolve a file listed in the manifest of an export. The files outside the export directory are
+/// rejected, so a crafted manifest can't read the other files of the device. The joined path is
+/// returned, so it still matches the paths of the imported pages.
+fn exported_file_path(export_dir: &Path, path: &str) -> FlowyResult<PathBuf> {
+  let file_path = export_dir.join(path);
+  match canonicalize_in_dir(export_dir, &file_path) {
+    Some(_) => Ok(file_path),
+    None => Err(
+      FlowyError::invalid_data().with_context(format!("The exported file {} is not found", path)),
+    ),
+  }
+}
+
 /// Get all the child views belong to the view id, including the child views of the child views.
 fn get_all_child_view_ids(folder: &Folder, view_id: &str) -> Vec<String> {
   let child_view_ids = folder
@@ -2027,3 +4136,15 @@ impl Display for FolderInitDataSource {
     }
   }
 }
+
+fn view_templates_key(uid: i64) -> String {
+  format!("view_templates:{}", uid)
+}
+
+fn pending_database_views_key(uid: i64, workspace_id: &str) -> String {
+  format!("pending_database_views:{}:{}", uid, workspace_id)
+}
+
+fn trash_retention_key(uid: i64, workspace_id: &str) -> String {
+  format!("trash_retention_days:{}:{}", uid, workspace_id)
+}
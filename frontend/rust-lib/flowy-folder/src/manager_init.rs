@@ -1,5 +1,6 @@
 use crate::manager::{FolderInitDataSource, FolderManager};
 use crate::manager_observer::*;
+use crate::tag::{observe_view_tag_ids, views_tag_ids};
 use crate::user_default::DefaultFolderBuilder;
 use collab::core::collab::DataSource;
 use collab::lock::RwLock;
@@ -109,13 +110,29 @@ impl FolderManager {
       },
     };
 
+    let (view_tags_tx, view_tags_rx) = tokio::sync::mpsc::unbounded_channel();
+    let view_tags_subscription =
+      observe_view_tag_ids(&mut folder.write().await.collab, view_tags_tx);
+    if let Ok(mut subscription) = self.view_tags_subscription.lock() {
+      *subscription = Some(view_tags_subscription);
+    }
+    subscribe_view_tags_changed(
+      workspace_id.clone(),
+      view_tags_rx,
+      Arc::downgrade(&folder),
+      self.folder_indexer.clone(),
+    );
+
     let folder_state_rx = {
+      let weak_folder = Arc::downgrade(&folder);
       let folder = folder.read().await;
       let folder_state_rx = folder.subscribe_sync_state();
       let index_content_rx = folder.subscribe_index_content();
-      self
-        .folder_indexer
-        .set_index_content_receiver(index_content_rx, workspace_id.clone());
+      self.folder_indexer.set_index_content_receiver(
+        index_content_rx,
+        weak_folder,
+        workspace_id.clone(),
+      );
       self.handle_index_folder(workspace_id.clone(), &folder);
       folder_state_rx
     };
@@ -139,11 +156,13 @@ impl FolderManager {
       weak_mutex_folder.clone(),
       Arc::downgrade(&self.user),
     );
+    self.batch_updated_views.clear();
     subscribe_folder_view_changed(
       workspace_id.clone(),
       view_rx,
       weak_mutex_folder.clone(),
       Arc::downgrade(&self.user),
+      self.batch_updated_views.clone(),
     );
 
     Ok(())
@@ -182,17 +201,20 @@ impl FolderManager {
       .store_preferences
       .get_object::<EncodedCollab>(&workspace_id);
 
-    if let Some(encoded_collab) = encoded_collab {
+    // Only the changes are indexed if the index is up to date with the saved collab. The index
+    // is empty when it's created again with a new schema.
+    if let Some(encoded_collab) = encoded_collab.filter(|_| self.folder_indexer.is_indexed()) {
       if let Ok(changes) = folder.calculate_view_changes(encoded_collab) {
         let folder_indexer = self.folder_indexer.clone();
 
         let views = folder.get_all_views();
+        let view_tags = views_tag_ids(&folder.collab, &views);
         let wid = workspace_id.clone();
 
         if !changes.is_empty() && !views.is_empty() {
           spawn_blocking(move || {
             // We index the changes
-            folder_indexer.index_view_changes(views, changes, wid);
+            folder_indexer.index_view_changes(views, view_tags, changes, wid);
           });
           index_all = false;
         }
@@ -201,6 +223,7 @@ impl FolderManager {
 
     if index_all {
       let views = folder.get_all_views();
+      let view_tags = views_tag_ids(&folder.collab, &views);
       let folder_indexer = self.folder_indexer.clone();
       let wid = workspace_id.clone();
 
@@ -210,7 +233,7 @@ impl FolderManager {
         let _ = folder_indexer.remove_indices_for_workspace(wid.clone());
 
         // We index all views from the workspace
-        folder_indexer.index_all_views(views, wid);
+        folder_indexer.index_all_views(views, view_tags, wid);
       });
     }
 
@@ -1,28 +1,79 @@
 use crate::entities::{
   view_pb_with_child_views, view_pb_without_child_views, ChildViewUpdatePB, FolderSnapshotStatePB,
-  FolderSyncStatePB, RepeatedTrashPB, RepeatedViewPB, SectionViewsPB, ViewPB, ViewSectionPB,
+  FolderSyncStatePB, RepeatedTrashPB, RepeatedViewPB, SectionViewsPB, ViewLockStatePB, ViewPB,
+  ViewSectionPB, ViewTagIdsPB,
 };
+use crate::lock::{view_lock, ViewLock};
 use crate::manager::{get_workspace_private_view_pbs, get_workspace_public_view_pbs, FolderUser};
 use crate::notification::{send_notification, FolderNotification};
+use crate::tag::views_tag_ids;
 use collab::core::collab_state::SyncState;
 use collab::lock::RwLock;
+use collab_folder::folder_diff::FolderViewChange;
 use collab_folder::{
   Folder, SectionChange, SectionChangeReceiver, TrashSectionChange, View, ViewChange,
   ViewChangeReceiver,
 };
+use flowy_search_pub::entities::FolderIndexManager;
 use lib_dispatch::prelude::af_spawn;
-use std::collections::HashSet;
-use std::sync::Weak;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, Weak};
+use tokio::sync::mpsc::UnboundedReceiver;
 use tokio_stream::wrappers::WatchStream;
 use tokio_stream::StreamExt;
 use tracing::{event, trace, Level};
 
+/// The views updated by the batches whose [ViewChange::DidUpdate] haven't been received yet. A
+/// batch sends one notification for all of its views, so the per-view notifications of these
+/// updates are skipped.
+///
+/// Only the views whose data is changed by a batch are added, because each of them gets exactly
+/// one [ViewChange::DidUpdate] when the batch's transaction is committed.
+#[derive(Clone, Default)]
+pub(crate) struct BatchUpdatedViews(Arc<Mutex<HashMap<String, usize>>>);
+
+impl BatchUpdatedViews {
+  pub(crate) fn add<T: AsRef<str>>(&self, view_ids: &[T]) {
+    if let Ok(mut views) = self.0.lock() {
+      for view_id in view_ids {
+        *views.entry(view_id.as_ref().to_string()).or_default() += 1;
+      }
+    }
+  }
+
+  /// Returns true if the update of the view was made by a batch.
+  fn take(&self, view_id: &str) -> bool {
+    let mut views = match self.0.lock() {
+      Ok(views) => views,
+      Err(_) => return false,
+    };
+    match views.get_mut(view_id) {
+      Some(count) if *count > 1 => {
+        *count -= 1;
+        true
+      },
+      Some(_) => {
+        views.remove(view_id);
+        true
+      },
+      None => false,
+    }
+  }
+
+  pub(crate) fn clear(&self) {
+    if let Ok(mut views) = self.0.lock() {
+      views.clear();
+    }
+  }
+}
+
 /// Listen on the [ViewChange] after create/delete/update events happened
 pub(crate) fn subscribe_folder_view_changed(
   workspace_id: String,
   mut rx: ViewChangeReceiver,
   weak_mutex_folder: Weak<RwLock<Folder>>,
   user: Weak<dyn FolderUser>,
+  batch_updated_views: BatchUpdatedViews,
 ) {
   af_spawn(async move {
     while let Ok(value) = rx.recv().await {
@@ -56,6 +107,14 @@ pub(crate) fn subscribe_folder_view_changed(
             }
           },
           ViewChange::DidUpdate { view } => {
+            if batch_updated_views.take(&view.id) {
+              trace!(
+                "Skip the notification of the view {} updated by a batch",
+                view.id
+              );
+              continue;
+            }
+            notify_view_lock_changed(&view.id, view_lock(view.extra.as_deref()));
             notify_view_did_change(view.clone());
             notify_child_views_changed(
               view_pb_without_child_views(view.clone()),
@@ -70,6 +129,48 @@ pub(crate) fn subscribe_folder_view_changed(
   });
 }
 
+/// Listen on the ids of the views whose tags are changed, on this device or by another device, to
+/// notify and index the new tags.
+pub(crate) fn subscribe_view_tags_changed(
+  workspace_id: String,
+  mut rx: UnboundedReceiver<Vec<String>>,
+  weak_mutex_folder: Weak<RwLock<Folder>>,
+  folder_indexer: Arc<dyn FolderIndexManager>,
+) {
+  af_spawn(async move {
+    while let Some(view_ids) = rx.recv().await {
+      let lock = match weak_mutex_folder.upgrade() {
+        Some(lock) => lock,
+        None => break,
+      };
+      let (views, view_tags) = {
+        let folder = lock.read().await;
+        let views = view_ids
+          .iter()
+          .filter_map(|view_id| folder.get_view(view_id))
+          .collect::<Vec<_>>();
+        let view_tags = views_tag_ids(&folder.collab, &views);
+        (views, view_tags)
+      };
+      for view in &views {
+        send_notification(&view.id, FolderNotification::DidUpdateViewTags)
+          .payload(ViewTagIdsPB {
+            view_id: view.id.clone(),
+            tag_ids: view_tags.get(&view.id).cloned().unwrap_or_default(),
+          })
+          .send();
+      }
+      let changes = views
+        .iter()
+        .map(|view| FolderViewChange::Updated {
+          view_id: view.id.clone(),
+        })
+        .collect();
+      folder_indexer.index_view_changes(views, view_tags, changes, workspace_id.clone());
+    }
+  });
+}
+
 pub(crate) fn subscribe_folder_snapshot_state_changed(
   workspace_id: String,
   weak_mutex_folder: Weak<RwLock<Folder>>,
@@ -255,6 +356,17 @@ fn notify_view_did_change(view: View) -> Option<()> {
   None
 }
 
+/// Notify the editors of the view when it's locked or unlocked, so they switch to read-only
+/// immediately.
+pub(crate) fn notify_view_lock_changed(view_id: &str, lock: Option<ViewLock>) {
+  send_notification(view_id, FolderNotification::DidUpdateViewLock)
+    .payload(ViewLockStatePB {
+      view_id: view_id.to_string(),
+      lock: lock.map(Into::into),
+    })
+    .send();
+}
+
 pub enum ChildViewChangeReason {
   Create,
   Delete,
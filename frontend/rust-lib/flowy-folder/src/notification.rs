@@ -36,6 +36,21 @@ pub enum FolderNotification {
 
   /// Trigger when the ROOT views (the first level) in section are updated
   DidUpdateSectionViews = 39,
+
+  /// Trigger when importing a zip file into the workspace
+  DidUpdateImportProgress = 40,
+
+  /// Trigger when the tags of the workspace are created, updated or deleted
+  DidUpdateWorkspaceTags = 41,
+
+  /// Trigger when the view is locked or unlocked
+  DidUpdateViewLock = 42,
+
+  /// Trigger once when a batch of views is moved, trashed, restored or updated
+  DidBatchUpdateViews = 43,
+
+  /// Trigger when the tags of the view are set, on this device or by another device
+  DidUpdateViewTags = 44,
 }
 
 impl std::convert::From<FolderNotification> for i32 {
@@ -63,6 +78,11 @@ impl std::convert::From<i32> for FolderNotification {
       37 => FolderNotification::DidUnfavoriteView,
       38 => FolderNotification::DidUpdateRecentViews,
       39 => FolderNotification::DidUpdateSectionViews,
+      40 => FolderNotification::DidUpdateImportProgress,
+      41 => FolderNotification::DidUpdateWorkspaceTags,
+      42 => FolderNotification::DidUpdateViewLock,
+      43 => FolderNotification::DidBatchUpdateViews,
+      44 => FolderNotification::DidUpdateViewTags,
       _ => FolderNotification::Unknown,
     }
   }
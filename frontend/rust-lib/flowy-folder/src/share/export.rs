@@ -0,0 +1,66 @@
+use collab_folder::{ViewIcon, ViewLayout};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::template::TemplateViewData;
+
+pub use flowy_folder_pub::export::{
+  export_attachment_file_name, export_file_name, ExportFormat, ExportedPage,
+};
+
+/// The manifest is written to the root of the export.
+pub const EXPORT_MANIFEST_FILE_NAME: &str = "manifest.json";
+pub const EXPORT_MANIFEST_VERSION: i32 = 1;
+/// The suffix of the file that keeps the field types and the cells of an exported database, so the
+/// database can be imported again.
+pub const EXPORT_DATABASE_DATA_SUFFIX: &str = ".data.csv";
+/// The suffix of the file that keeps the whole content of an exported view, like the blocks of a
+/// document or the layout settings, filters, sorts and row documents of a database view, so the
+/// view is imported again as it was.
+pub const EXPORT_VIEW_DATA_SUFFIX: &str = ".data.json";
+
+/// A file of an exported view. The path is relative to the root of the export and separated by
+/// `/`.
+#[derive(Clone, Debug)]
+pub struct ExportedFile {
+  pub path: String,
+  pub data: Vec<u8>,
+}
+
+/// The content of an exported view, in the format of the templates. The ids in `object_ids`, and
+/// the ids of the exported views, are replaced with new ids when it's imported.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportedViewData {
+  pub data: TemplateViewData,
+  /// The JSON of the documents of the rows keyed by the row ids, for the database views.
+  #[serde(default)]
+  pub row_documents: HashMap<String, String>,
+}
+
+/// Describes the exported views, so the export can be imported again with the same hierarchy.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportManifest {
+  pub version: i32,
+  pub workspace_id: String,
+  /// The view that is exported with its descendants, or None if the whole workspace is exported.
+  pub root_view_id: Option<String>,
+  pub document_format: ExportFormat,
+  pub exported_at: i64,
+  /// The views in the order of the folder. A parent view always comes before its children.
+  pub views: Vec<ExportedView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportedView {
+  pub view_id: String,
+  /// The parent view, or None for the views at the top level of the export.
+  pub parent_view_id: Option<String>,
+  pub name: String,
+  pub layout: ViewLayout,
+  pub icon: Option<ViewIcon>,
+  pub extra: Option<String>,
+  pub created_at: i64,
+  /// The exported files of the view, starting with its main file. It's empty if the view can't be
+  /// exported.
+  pub files: Vec<String>,
+}
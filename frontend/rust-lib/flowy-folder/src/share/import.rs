@@ -7,6 +7,8 @@ pub enum ImportType {
   Markdown = 2,
   AFDatabase = 3,
   CSV = 4,
+  /// The CSV that is exported by Notion, whose field types are inferred from the values.
+  NotionCSV = 5,
 }
 
 #[derive(Clone, Debug)]
@@ -1,3 +1,9 @@
+mod export;
 mod import;
+mod notion_import;
+mod static_site;
 
+pub use export::*;
 pub use import::*;
+pub use notion_import::*;
+pub use static_site::*;
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use collab_folder::ViewLayout;
+use flowy_folder_pub::cloud::gen_view_id;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Notion exports the rows of a database to this file, including the rows hidden by the filters.
+const ALL_ROWS_SUFFIX: &str = "_all";
+const MARKDOWN_EXTENSION: &str = "md";
+const CSV_EXTENSION: &str = "csv";
+
+lazy_static! {
+  /// Notion appends the id of the page to the names of the files and the directories.
+  static ref NOTION_ID_SUFFIX: Regex = Regex::new(r"\s+[0-9a-fA-F]{32}$").unwrap();
+}
+
+/// A page of the Notion export. A Markdown file becomes a document, a CSV file becomes a grid, and
+/// a directory without its page file becomes an empty document that contains the pages of the
+/// directory.
+#[derive(Debug, Clone)]
+pub struct NotionPage {
+  pub view_id: String,
+  pub name: String,
+  pub layout: ViewLayout,
+  /// The files of the page. The first file is imported, and the others are the other exports of
+  /// the same database that the pages may link to.
+  pub files: Vec<PathBuf>,
+  /// The index of the parent page, or None for the pages at the top level of the export.
+  pub parent: Option<usize>,
+}
+
+/// Returns the pages of the extracted export. A parent page always comes before its children.
+pub fn collect_notion_pages(root: &Path) -> io::Result<Vec<NotionPage>> {
+  let mut pages = vec![];
+  collect_dir_pages(root, None, &mut pages)?;
+  Ok(pages)
+}
+
+/// Returns the view ids of the pages by the paths of their files.
+pub fn notion_page_links(pages: &[NotionPage]) -> HashMap<PathBuf, String> {
+  pages
+    .iter()
+    .flat_map(|page| {
+      page
+        .files
+        .iter()
+        .map(|path| (path.clone(), page.view_id.clone()))
+    })
+    .collect()
+}
+
+/// Returns the name of the page without the id that Notion appends to it.
+pub fn notion_page_name(file_stem: &str) -> String {
+  NOTION_ID_SUFFIX.replace(file_stem, "").trim().to_string()
+}
+
+fn collect_dir_pages(
+  dir: &Path,
+  parent: Option<usize>,
+  pages: &mut Vec<NotionPage>,
+) -> io::Result<()> {
+  let mut files = vec![];
+  let mut dirs = vec![];
+  for entry in fs::read_dir(dir)? {
+    let path = entry?.path();
+    let is_hidden = path
+      .file_name()
+      .and_then(|name| name.to_str())
+      .map_or(true, |name| name.starts_with('.') || name == "__MACOSX");
+    if is_hidden {
+      continue;
+    }
+    if path.is_dir() {
+      dirs.push(path);
+    } else {
+      files.push(path);
+    }
+  }
+  files.sort();
+  dirs.sort();
+
+  // The stem of a page file is the name of the directory of its child pages.
+  let mut page_stems: Vec<(String, NotionPage)> = vec![];
+  for path in files {
+    let stem = match path.file_stem().and_then(|stem| stem.to_str()) {
+      Some(stem) => stem.to_string(),
+      None => continue,
+    };
+    match path.extension().and_then(|extension| extension.to_str()) {
+      Some(MARKDOWN_EXTENSION) => {
+        let page = new_page(&stem, ViewLayout::Document, path);
+        page_stems.push((stem, page));
+      },
+      Some(CSV_EXTENSION) => {
+        let (stem, is_all_rows) = match stem.strip_suffix(ALL_ROWS_SUFFIX) {
+          Some(stem) => (stem.to_string(), true),
+          None => (stem, false),
+        };
+        let database = page_stems
+          .iter_mut()
+          .find(|(page_stem, page)| page_stem == &stem && page.layout == ViewLayout::Grid);
+        match database {
+          // Import the export with all the rows.
+          Some((_, page)) if is_all_rows => page.files.insert(0, path),
+          Some((_, page)) => page.files.push(path),
+          None => {
+            let page = new_page(&stem, ViewLayout::Grid, path);
+            page_stems.push((stem, page));
+          },
+        }
+      },
+      _ => {},
+    }
+  }
+
+  for (stem, mut page) in page_stems {
+    page.parent = parent;
+    pages.push(page);
+    let index = pages.len() - 1;
+    if let Some(position) = dirs
+      .iter()
+      .position(|dir| dir.file_name().and_then(|name| name.to_str()) == Some(stem.as_str()))
+    {
+      let child_dir = dirs.remove(position);
+      collect_dir_pages(&child_dir, Some(index), pages)?;
+    }
+  }
+
+  // The directories without their page files.
+  for child_dir in dirs {
+    if !contains_page_files(&child_dir)? {
+      continue;
+    }
+    let name = child_dir
+      .file_name()
+      .and_then(|name| name.to_str())
+      .map(notion_page_name)
+      .unwrap_or_default();
+    pages.push(NotionPage {
+      view_id: gen_view_id().to_string(),
+      name,
+      layout: ViewLayout::Document,
+      files: vec![],
+      parent,
+    });
+    let index = pages.len() - 1;
+    collect_dir_pages(&child_dir, Some(index), pages)?;
+  }
+  Ok(())
+}
+
+fn new_page(stem: &str, layout: ViewLayout, path: PathBuf) -> NotionPage {
+  NotionPage {
+    view_id: gen_view_id().to_string(),
+    name: notion_page_name(stem),
+    layout,
+    files: vec![path],
+    parent: None,
+  }
+}
+
+fn contains_page_files(dir: &Path) -> io::Result<bool> {
+  for entry in fs::read_dir(dir)? {
+    let path = entry?.path();
+    if path.is_dir() {
+      if contains_page_files(&path)? {
+        return Ok(true);
+      }
+    } else if matches!(
+      path.extension().and_then(|extension| extension.to_str()),
+      Some(MARKDOWN_EXTENSION) | Some(CSV_EXTENSION)
+    ) {
+      return Ok(true);
+    }
+  }
+  Ok(false)
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use collab_folder::IconType;
+use flowy_folder_pub::entities::PublishViewInfo;
+use lib_infra::util::escape_html;
+
+use crate::share::{ExportedFile, ExportedPage};
+
+pub const STATIC_SITE_INDEX_FILE_NAME: &str = "index.html";
+pub const STATIC_SITE_STYLE_FILE_NAME: &str = "style.css";
+
+pub const STATIC_SITE_STYLE: &str = r#"body { margin: 0; display: flex; font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif; color: #1f2329; line-height: 1.6; }
+nav { width: 260px; min-height: 100vh; padding: 24px 12px; box-sizing: border-box; background: #f7f8fc; border-right: 1px solid #e5e5e5; }
+nav ul { list-style: none; margin: 0; padding-left: 12px; }
+nav > ul { padding-left: 0; }
+nav a { display: block; padding: 2px 8px; border-radius: 4px; color: inherit; text-decoration: none; }
+nav a:hover { background: #e8e9ef; }
+nav a.current { background: #e0f8ff; font-weight: 600; }
+main { flex: 1; max-width: 900px; padding: 40px 48px; }
+img { max-width: 100%; }
+pre { padding: 12px; background: #f2f3f5; border-radius: 6px; overflow-x: auto; }
+blockquote, aside { margin: 0; padding: 4px 16px; border-left: 4px solid #00bcf0; background: #f7f8fc; }
+table.database { border-collapse: collapse; width: 100%; }
+table.database th, table.database td { padding: 6px 10px; border: 1px solid #e5e5e5; text-align: left; vertical-align: top; }
+table.database th { background: #f7f8fc; }
+"#;
+
+/// The content of a page of the static site.
+#[derive(Clone, Debug, Default)]
+pub struct StaticPageContent {
+  /// The HTML of the page, without the head and the navigation.
+  pub body: String,
+  /// The files that the page links to. The paths are relative to the root of the site.
+  pub assets: Vec<ExportedFile>,
+}
+
+/// Returns the HTML document of the page, with the navigation of the site. The `pages` are the
+/// pages of the site by their view ids, and the views without a page are not linked.
+pub fn static_page_html(
+  view: &PublishViewInfo,
+  navigation: &[PublishViewInfo],
+  pages: &HashMap<String, ExportedPage>,
+  body: &str,
+) -> String {
+  let title = escape_html(&view.name);
+  let icon = emoji_of(view)
+    .map(|emoji| format!("{} ", escape_html(emoji)))
+    .unwrap_or_default();
+  format!(
+    "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"UTF-8\">\n\
+     <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+     <title>{title}</title>\n<link rel=\"stylesheet\" href=\"{style}\">\n</head>\n<body>\n\
+     <nav>{nav}</nav>\n<main>\n<h1>{icon}{title}</h1>\n{body}\n</main>\n</body>\n</html>\n",
+    title = title,
+    style = STATIC_SITE_STYLE_FILE_NAME,
+    nav = render_navigation(navigation, &view.view_id, pages),
+    icon = icon,
+    body = body,
+  )
+}
+
+/// The index of the site redirects to the page of the published view.
+pub fn static_site_index_html(path: &str) -> String {
+  let path = escape_html(path);
+  format!(
+    "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"UTF-8\">\n\
+     <meta http-equiv=\"refresh\" content=\"0; url={path}\">\n</head>\n<body>\n\
+     <a href=\"{path}\">{path}</a>\n</body>\n</html>\n",
+    path = path
+  )
+}
+
+fn render_navigation(
+  views: &[PublishViewInfo],
+  current_view_id: &str,
+  pages: &HashMap<String, ExportedPage>,
+) -> String {
+  let items = views
+    .iter()
+    .filter_map(|view| {
+      let icon = emoji_of(view)
+        .map(|emoji| format!("{} ", escape_html(emoji)))
+        .unwrap_or_default();
+      let name = format!("{}{}", icon, escape_html(&view.name));
+      let children = view
+        .child_views
+        .as_ref()
+        .map(|child_views| render_navigation(child_views, current_view_id, pages))
+        .unwrap_or_default();
+      let link = match pages.get(&view.view_id) {
+        Some(page) => {
+          let class = if view.view_id == current_view_id {
+            " class=\"current\""
+          } else {
+            ""
+          };
+          format!(
+            "<a href=\"{}\"{}>{}</a>",
+            escape_html(&page.path),
+            class,
+            name
+          )
+        },
+        // The views that are not published are only shown if they contain published views.
+        None if !children.is_empty() => format!("<span>{}</span>", name),
+        None => return None,
+      };
+      Some(format!("<li>{}{}</li>", link, children))
+    })
+    .collect::<String>();
+  if items.is_empty() {
+    String::new()
+  } else {
+    format!("<ul>{}</ul>", items)
+  }
+}
+
+fn emoji_of(view: &PublishViewInfo) -> Option<&str> {
+  view
+    .icon
+    .as_ref()
+    .filter(|icon| matches!(icon.ty, IconType::Emoji) && !icon.value.is_empty())
+    .map(|icon| icon.value.as_str())
+}
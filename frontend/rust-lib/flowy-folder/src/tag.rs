@@ -0,0 +1,143 @@
+use collab::preclude::encoding::serde::{from_any, to_any};
+use collab::preclude::{Collab, Map, MapRef, Observable, Out, ReadTxn, Subscription, WriteTxn};
+use collab_folder::View;
+use flowy_error::{internal_error, FlowyResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::util::get_extra_value;
+
+/// The tags of the workspace are stored in a map of the folder collab keyed by the tag ids, so the
+/// tags edited on different devices are merged. The map is a root-level shared type, which is the
+/// same map on every device, while the nested maps created on two devices at the same time replace
+/// each other. The tags written to the map of `collab.data` by the earlier versions are still read.
+///
+/// The ids of the tags of the views are stored in another root-level map keyed by the view ids,
+/// so setting the tags of a view doesn't overwrite the extra of the view edited on another device.
+/// The ids stored in the extra of the views by the earlier versions are read until the tags of the
+/// view are set again.
+pub const WORKSPACE_TAGS_KEY: &str = "workspace_tags";
+pub const VIEW_TAG_IDS_KEY: &str = "view_tag_ids";
+const LEGACY_VIEW_TAG_IDS_KEY: &str = "tag_ids";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewTag {
+  pub id: String,
+  pub name: String,
+  pub color: String,
+}
+
+/// Returns the tags stored in the folder collab, ordered by their names.
+pub fn workspace_tags(collab: &Collab) -> Vec<ViewTag> {
+  let txn = collab.transact();
+  let legacy_map = match collab.data.get(&txn, WORKSPACE_TAGS_KEY) {
+    Some(Out::YMap(map)) => Some(map),
+    _ => None,
+  };
+  let mut tags_by_id = HashMap::new();
+  for map in [legacy_map, txn.get_map(WORKSPACE_TAGS_KEY)]
+    .into_iter()
+    .flatten()
+  {
+    let tags = map.iter(&txn).filter_map(|(_, value)| match value {
+      Out::Any(any) => from_any::<ViewTag>(&any).ok(),
+      _ => None,
+    });
+    for tag in tags {
+      tags_by_id.insert(tag.id.clone(), tag);
+    }
+  }
+  let mut tags = tags_by_id.into_values().collect::<Vec<_>>();
+  tags.sort_by(|a, b| (a.name.to_lowercase(), &a.id).cmp(&(b.name.to_lowercase(), &b.id)));
+  tags
+}
+
+/// Writes the tags that changed from `old_tags` to `tags` into the folder collab and removes the
+/// tags that are gone. The other tags are left untouched.
+pub fn write_workspace_tags(
+  collab: &mut Collab,
+  old_tags: &[ViewTag],
+  tags: &[ViewTag],
+) -> FlowyResult<()> {
+  let data = collab.data.clone();
+  let mut txn = collab.transact_mut();
+  let legacy_map = match data.get(&txn, WORKSPACE_TAGS_KEY) {
+    Some(Out::YMap(map)) => Some(map),
+    _ => None,
+  };
+  let map: MapRef = txn.get_or_insert_map(WORKSPACE_TAGS_KEY);
+  for old_tag in old_tags {
+    if !tags.iter().any(|tag| tag.id == old_tag.id) {
+      map.remove(&mut txn, &old_tag.id);
+      if let Some(legacy_map) = &legacy_map {
+        legacy_map.remove(&mut txn, &old_tag.id);
+      }
+    }
+  }
+  for tag in tags {
+    if !old_tags.contains(tag) {
+      let any = to_any(tag).map_err(internal_error)?;
+      map.insert(&mut txn, tag.id.as_str(), any);
+    }
+  }
+  Ok(())
+}
+
+/// Returns the ids of the tags of the view.
+pub fn view_tag_ids(collab: &Collab, view: &View) -> Vec<String> {
+  let txn = collab.transact();
+  read_view_tag_ids(&txn, view)
+}
+
+/// Returns the ids of the tags of the views keyed by the view ids. The views without tags are
+/// left out.
+pub fn views_tag_ids(collab: &Collab, views: &[Arc<View>]) -> HashMap<String, Vec<String>> {
+  let txn = collab.transact();
+  views
+    .iter()
+    .map(|view| (view.id.clone(), read_view_tag_ids(&txn, view)))
+    .filter(|(_, tag_ids)| !tag_ids.is_empty())
+    .collect()
+}
+
+/// Replaces the ids of the tags of the view.
+pub fn write_view_tag_ids(
+  collab: &mut Collab,
+  view_id: &str,
+  tag_ids: &[String],
+) -> FlowyResult<()> {
+  let any = to_any(&tag_ids).map_err(internal_error)?;
+  let mut txn = collab.transact_mut();
+  let map: MapRef = txn.get_or_insert_map(VIEW_TAG_IDS_KEY);
+  map.insert(&mut txn, view_id, any);
+  Ok(())
+}
+
+/// Sends the ids of the views whose tags are changed, on this device or by another device, until
+/// the returned subscription is dropped.
+pub(crate) fn observe_view_tag_ids(
+  collab: &mut Collab,
+  tx: UnboundedSender<Vec<String>>,
+) -> Subscription {
+  let map: MapRef = collab.transact_mut().get_or_insert_map(VIEW_TAG_IDS_KEY);
+  map.observe(move |txn, event| {
+    let view_ids = event
+      .keys(txn)
+      .keys()
+      .map(|view_id| view_id.to_string())
+      .collect();
+    let _ = tx.send(view_ids);
+  })
+}
+
+fn read_view_tag_ids<T: ReadTxn>(txn: &T, view: &View) -> Vec<String> {
+  let value = txn
+    .get_map(VIEW_TAG_IDS_KEY)
+    .and_then(|map| map.get(txn, &view.id));
+  match value {
+    Some(Out::Any(any)) => from_any(&any).unwrap_or_default(),
+    _ => get_extra_value(view.extra.as_deref(), LEGACY_VIEW_TAG_IDS_KEY),
+  }
+}
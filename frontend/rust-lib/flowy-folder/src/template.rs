@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use collab_folder::{ViewIcon, ViewLayout};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The variables that are always available when creating views from a template. The other
+/// variables are provided by the caller.
+pub const TEMPLATE_VARIABLE_DATE: &str = "date";
+pub const TEMPLATE_VARIABLE_TIME: &str = "time";
+pub const TEMPLATE_VARIABLE_USER: &str = "user";
+
+/// A view and its descendants, saved to create copies of them later.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ViewTemplate {
+  pub id: String,
+  pub name: String,
+  pub created_at: i64,
+  /// Whether the rows of the databases are saved.
+  pub include_rows: bool,
+  /// The views in the order of the folder. A parent view always comes before its children, and
+  /// the first view is the root of the template.
+  pub views: Vec<TemplateView>,
+}
+
+impl ViewTemplate {
+  pub fn root_view(&self) -> Option<&TemplateView> {
+    self.views.first()
+  }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TemplateView {
+  pub view_id: String,
+  /// The parent view, or None for the root of the template.
+  pub parent_view_id: Option<String>,
+  pub name: String,
+  pub layout: ViewLayout,
+  pub icon: Option<ViewIcon>,
+  pub extra: Option<String>,
+  pub data: TemplateViewData,
+}
+
+/// The content of a view in a template.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TemplateViewData {
+  /// The JSON of the content. The ids in `object_ids`, and the ids of the views of the template,
+  /// are replaced with new ids before the view is created.
+  pub data: String,
+  /// The ids of the objects that the view owns, like the database and its rows.
+  pub object_ids: Vec<String>,
+}
+
+/// Replaces the `{{name}}` placeholders with the values of the variables. The placeholders
+/// without a value are kept as is.
+pub fn substitute_template_variables(text: &str, variables: &HashMap<String, String>) -> String {
+  let mut result = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find("{{") {
+    result.push_str(&rest[..start]);
+    let after_start = &rest[start + 2..];
+    match after_start.find("}}") {
+      Some(end) => {
+        let name = after_start[..end].trim();
+        match variables.get(name) {
+          Some(value) => result.push_str(value),
+          None => result.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after_start[end + 2..];
+      },
+      None => {
+        result.push_str(&rest[start..]);
+        rest = "";
+      },
+    }
+  }
+  result.push_str(rest);
+  result
+}
+
+/// Replaces the placeholders in the strings of the JSON. The strings that are JSON themselves,
+/// like the deltas of the documents, are substituted recursively, so the values are escaped
+/// correctly.
+pub fn substitute_template_variables_in_json(
+  json: &str,
+  variables: &HashMap<String, String>,
+) -> String {
+  if variables.is_empty() || !json.contains("{{") {
+    return json.to_string();
+  }
+  match serde_json::from_str::<Value>(json) {
+    Ok(mut value) => {
+      substitute_value(&mut value, variables);
+      value.to_string()
+    },
+    Err(_) => json.to_string(),
+  }
+}
+
+fn substitute_value(value: &mut Value, variables: &HashMap<String, String>) {
+  match value {
+    Value::String(s) if s.contains("{{") => {
+      *s = match serde_json::from_str::<Value>(s) {
+        Ok(mut nested) if nested.is_object() || nested.is_array() => {
+          substitute_value(&mut nested, variables);
+          nested.to_string()
+        },
+        _ => substitute_template_variables(s, variables),
+      };
+    },
+    Value::Array(values) => values
+      .iter_mut()
+      .for_each(|value| substitute_value(value, variables)),
+    Value::Object(map) => map
+      .values_mut()
+      .for_each(|value| substitute_value(value, variables)),
+    _ => {},
+  }
+}
+
+/// Replaces the ids in the text. The ids are UUIDs, so they don't appear in the text by accident.
+pub fn replace_template_ids(text: &str, ids: &HashMap<String, String>) -> String {
+  ids.iter().filter(|(old_id, _)| !old_id.is_empty()).fold(
+    text.to_string(),
+    |text, (old_id, new_id)| {
+      if text.contains(old_id.as_str()) {
+        text.replace(old_id.as_str(), new_id)
+      } else {
+        text
+      }
+    },
+  )
+}
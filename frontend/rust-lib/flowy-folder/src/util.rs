@@ -1,5 +1,8 @@
 use crate::entities::UserFolderPB;
 use flowy_error::{ErrorCode, FlowyError};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
 
 pub(crate) fn folder_not_init_error() -> FlowyError {
   FlowyError::internal().with_context("Folder not initialized")
@@ -11,3 +14,25 @@ pub(crate) fn workspace_data_not_sync_error(uid: i64, workspace_id: &str) -> Flo
     workspace_id: workspace_id.to_string(),
   })
 }
+
+/// Returns the value of the `key` in the JSON extra of a view, or the default if it's missing.
+pub(crate) fn get_extra_value<T: DeserializeOwned + Default>(extra: Option<&str>, key: &str) -> T {
+  extra
+    .and_then(|extra| serde_json::from_str::<Map<String, Value>>(extra).ok())
+    .and_then(|mut map| map.remove(key))
+    .and_then(|value| serde_json::from_value(value).ok())
+    .unwrap_or_default()
+}
+
+/// Returns the extra with the `key` set to `value`. The other keys of the extra, like the ones of
+/// the spaces, are kept.
+pub(crate) fn set_extra_value<T: Serialize>(extra: Option<&str>, key: &str, value: &T) -> String {
+  let mut map = extra
+    .and_then(|extra| serde_json::from_str::<Map<String, Value>>(extra).ok())
+    .unwrap_or_default();
+  map.insert(
+    key.to_string(),
+    serde_json::to_value(value).unwrap_or_default(),
+  );
+  Value::Object(map).to_string()
+}
@@ -6,16 +6,19 @@ use collab_folder::hierarchy_builder::NestedViewBuilder;
 pub use collab_folder::View;
 use collab_folder::ViewLayout;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use flowy_error::FlowyError;
+use flowy_folder_pub::entities::PublishPayload;
 
 use lib_infra::util::timestamp;
 
 use crate::entities::{CreateViewParams, ViewLayoutPB};
 use crate::manager::FolderUser;
-use crate::share::ImportType;
+use crate::share::{ExportFormat, ExportedFile, ExportedPage, ImportType, StaticPageContent};
+use crate::template::TemplateViewData;
 
 #[derive(Debug, Clone)]
 pub enum EncodedCollabWrapper {
@@ -39,6 +42,28 @@ pub struct DatabaseEncodedCollab {
 
 pub type ImportedData = (String, CollabType, EncodedCollab);
 
+/// What purging a view from the trash reclaims on this device.
+#[derive(Debug, Default, Clone)]
+pub struct ViewPurgeInfo {
+  /// The bytes of the collabs and the files of the view.
+  pub size: u64,
+  pub file_count: u64,
+}
+
+/// The collabs of a view to write into another workspace.
+#[derive(Debug, Default)]
+pub struct WorkspaceViewCollabs {
+  pub collabs: Vec<ImportedData>,
+  /// The view ids of the databases, by the database ids, to track in the other workspace.
+  pub databases: HashMap<String, Vec<String>>,
+  /// The urls of the files copied into the other workspace.
+  pub files: Vec<String>,
+  /// The ids of the collabs in the current workspace, removed when the view is moved.
+  pub source_object_ids: Vec<String>,
+  /// The urls of the files in the current workspace, removed when the view is moved.
+  pub source_files: Vec<String>,
+}
+
 /// The handler will be used to handler the folder operation for a specific
 /// view layout. Each [ViewLayout] will have a handler. So when creating a new
 /// view, the [ViewLayout] will be used to get the handler.
@@ -131,6 +156,114 @@ pub trait FolderOperationHandler {
     path: String,
   ) -> Result<(), FlowyError>;
 
+  /// Create a view by importing a Markdown or an HTML file. The `pages` maps the paths of the
+  /// imported pages to their view ids, so the links between the pages are kept. The files linked
+  /// from outside the `root` directory of the import are ignored.
+  async fn import_document_file(
+    &self,
+    _uid: i64,
+    _view_id: &str,
+    _path: &Path,
+    _root: &Path,
+    _pages: &HashMap<PathBuf, String>,
+  ) -> Result<Vec<ImportedData>, FlowyError> {
+    Err(FlowyError::not_support())
+  }
+
+  /// Export the view to the files whose paths start with `file_stem`. The `pages` are the exported
+  /// views by their ids, so the links between the views are kept. The paths are relative to the
+  /// root of the export and separated by `/`.
+  async fn export_view(
+    &self,
+    _view_id: &str,
+    _file_stem: &str,
+    _format: ExportFormat,
+    _pages: &HashMap<String, ExportedPage>,
+  ) -> Result<Vec<ExportedFile>, FlowyError> {
+    Err(FlowyError::not_support())
+  }
+
+  /// Render the publish payload of the view to a page of the static site. The `file_stem` is the
+  /// path of the page without the extension, and the `pages` are the pages of the site by their
+  /// view ids, so the mentions of the views become links to their pages.
+  async fn render_static_page(
+    &self,
+    _payload: PublishPayload,
+    _file_stem: &str,
+    _pages: &HashMap<String, ExportedPage>,
+  ) -> Result<StaticPageContent, FlowyError> {
+    Err(FlowyError::not_support())
+  }
+
+  /// Returns the content of the view to save it in a template. The rows of the databases are only
+  /// included if `include_rows` is true.
+  async fn get_template_view_data(
+    &self,
+    _view_id: &str,
+    _include_rows: bool,
+  ) -> Result<TemplateViewData, FlowyError> {
+    Err(FlowyError::not_support())
+  }
+
+  /// Create the view with the content of a template, whose ids were already replaced with new ids.
+  async fn create_view_from_template(
+    &self,
+    _uid: i64,
+    _view_id: &str,
+    _data: &str,
+  ) -> Result<Vec<ImportedData>, FlowyError> {
+    Err(FlowyError::not_support())
+  }
+
+  /// Returns the JSON of the documents of the rows of the view keyed by the row ids, to export
+  /// them with the view.
+  async fn get_row_documents(&self, _view_id: &str) -> Result<HashMap<String, String>, FlowyError> {
+    Ok(HashMap::new())
+  }
+
+  /// Create the documents of the rows from their JSON keyed by the row ids, whose ids were already
+  /// replaced with new ids.
+  async fn create_row_documents(
+    &self,
+    _documents: HashMap<String, String>,
+  ) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  /// Returns the collabs of the view to write them into the workspace, with the files of the view
+  /// copied into it. The `ids` map the ids of the current workspace to the ids in the other one,
+  /// the objects of the view get new ids that are added to `ids`. When `is_copy` is false, the
+  /// view is moved, so all the views sharing its objects must be moved with it.
+  async fn get_view_collabs_for_workspace(
+    &self,
+    _user: Arc<dyn FolderUser>,
+    _view_id: &str,
+    _workspace_id: &str,
+    _ids: &mut HashMap<String, String>,
+    _is_copy: bool,
+  ) -> Result<WorkspaceViewCollabs, FlowyError> {
+    Err(FlowyError::not_support())
+  }
+
+  /// Deletes the files managed by the storage among `urls`, like the files copied into another
+  /// workspace when the transfer of the view fails.
+  async fn delete_view_files(&self, _urls: Vec<String>) {}
+
+  /// Returns the collabs and the files that are deleted with the view when it's purged from the
+  /// trash.
+  async fn get_view_purge_info(
+    &self,
+    _user: Arc<dyn FolderUser>,
+    _view_id: &str,
+  ) -> Result<ViewPurgeInfo, FlowyError> {
+    Ok(ViewPurgeInfo::default())
+  }
+
+  /// Deletes the view with its collabs and files. Called when the view is purged from the trash.
+  async fn purge_view(&self, view_id: &str) -> Result<(), FlowyError> {
+    self.delete_view(view_id).await
+  }
+
   /// Called when the view is updated. The handler is the `old` registered handler.
   async fn did_update_view(&self, _old: &View, _new: &View) -> Result<(), FlowyError> {
     Ok(())
@@ -1,8 +1,10 @@
 use std::any::Any;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
 
 use collab::core::collab::IndexContentReceiver;
-use collab_folder::{folder_diff::FolderViewChange, View, ViewIcon, ViewLayout};
+use collab::lock::RwLock;
+use collab_folder::{folder_diff::FolderViewChange, Folder, View, ViewIcon, ViewLayout};
 use flowy_error::FlowyError;
 
 pub struct IndexableData {
@@ -11,6 +13,8 @@ pub struct IndexableData {
   pub icon: Option<ViewIcon>,
   pub layout: ViewLayout,
   pub workspace_id: String,
+  /// The ids of the tags of the view. `None` keeps the tags that are already indexed.
+  pub tags: Option<Vec<String>>,
 }
 
 impl IndexableData {
@@ -21,12 +25,12 @@ impl IndexableData {
       icon: view.icon.clone(),
       layout: view.layout.clone(),
       workspace_id: workspace_id.clone(),
+      tags: None,
     }
   }
 }
 
 pub trait IndexManager: Send + Sync {
-  fn set_index_content_receiver(&self, rx: IndexContentReceiver, workspace_id: String);
   fn add_index(&self, data: IndexableData) -> Result<(), FlowyError>;
   fn update_index(&self, data: IndexableData) -> Result<(), FlowyError>;
   fn remove_indices(&self, ids: Vec<String>) -> Result<(), FlowyError>;
@@ -37,10 +41,26 @@ pub trait IndexManager: Send + Sync {
 }
 
 pub trait FolderIndexManager: IndexManager {
-  fn index_all_views(&self, views: Vec<Arc<View>>, workspace_id: String);
+  /// Indexes the views created and updated in the folder collab. The updates don't carry the
+  /// tags of the views, so they're read from the `folder`.
+  fn set_index_content_receiver(
+    &self,
+    rx: IndexContentReceiver,
+    folder: Weak<RwLock<Folder>>,
+    workspace_id: String,
+  );
+  /// The `view_tags` are the ids of the tags of the views keyed by the view ids, which are stored
+  /// outside of the views.
+  fn index_all_views(
+    &self,
+    views: Vec<Arc<View>>,
+    view_tags: HashMap<String, Vec<String>>,
+    workspace_id: String,
+  );
   fn index_view_changes(
     &self,
     views: Vec<Arc<View>>,
+    view_tags: HashMap<String, Vec<String>>,
     changes: Vec<FolderViewChange>,
     workspace_id: String,
   );
@@ -62,6 +62,20 @@ impl SearchHandler for DocumentSearchHandler {
 
     for result in results {
       if let Some(view) = views.find(|v| v.id == result.object_id) {
+        // The document must have all the tags of the filter
+        let view_tags = self
+          .folder_manager
+          .get_view_tag_ids(&view.id)
+          .await
+          .unwrap_or_default();
+        if !filter
+          .tag_ids
+          .iter()
+          .all(|tag_id| view_tags.contains(tag_id))
+        {
+          continue;
+        }
+
         // If there is no View for the result, we don't add it to the results
         // If possible we will extract the icon to display for the result
         let icon: Option<ResultIconPB> = match view.icon.clone() {
@@ -4,4 +4,8 @@ use flowy_derive::ProtoBuf;
 pub struct SearchFilterPB {
   #[pb(index = 1, one_of)]
   pub workspace_id: Option<String>,
+
+  /// Only the views that have all the tags are returned.
+  #[pb(index = 2)]
+  pub tag_ids: Vec<String>,
 }
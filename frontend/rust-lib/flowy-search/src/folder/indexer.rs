@@ -11,19 +11,27 @@ use crate::{
   entities::{ResultIconTypePB, SearchFilterPB, SearchResultPB},
   folder::schema::{
     FolderSchema, FOLDER_ICON_FIELD_NAME, FOLDER_ICON_TY_FIELD_NAME, FOLDER_ID_FIELD_NAME,
-    FOLDER_TITLE_FIELD_NAME, FOLDER_WORKSPACE_ID_FIELD_NAME,
+    FOLDER_TAGS_FIELD_NAME, FOLDER_TITLE_FIELD_NAME, FOLDER_WORKSPACE_ID_FIELD_NAME,
   },
 };
 use collab::core::collab::{IndexContent, IndexContentReceiver};
-use collab_folder::{folder_diff::FolderViewChange, View, ViewIcon, ViewIndexContent, ViewLayout};
+use collab::lock::RwLock;
+use collab_folder::{
+  folder_diff::FolderViewChange, Folder, View, ViewIcon, ViewIndexContent, ViewLayout,
+};
 use flowy_error::{FlowyError, FlowyResult};
+use flowy_folder::tag::view_tag_ids;
 use flowy_search_pub::entities::{FolderIndexManager, IndexManager, IndexableData};
 use flowy_user::services::authenticate_user::AuthenticateUser;
 use lib_dispatch::prelude::af_spawn;
 use strsim::levenshtein;
 use tantivy::{
-  collector::TopDocs, directory::MmapDirectory, doc, query::QueryParser, schema::Field, Document,
-  Index, IndexReader, IndexWriter, TantivyDocument, Term,
+  collector::TopDocs,
+  directory::MmapDirectory,
+  doc,
+  query::{BooleanQuery, Occur, Query, QueryParser, TermQuery},
+  schema::{Field, IndexRecordOption, Value},
+  Document, Index, IndexReader, IndexWriter, TantivyDocument, TantivyError, Term,
 };
 
 use super::entities::FolderIndexData;
@@ -80,10 +88,21 @@ impl FolderIndexManagerImpl {
     // We open the existing or newly created folder_index directory
     // This is required by the Tantivy Index, as it will use it to store
     // and read index data
-    let index = match MmapDirectory::open(index_path) {
+    let index = match MmapDirectory::open(&index_path) {
       // We open or create an index that takes the directory r/w and the schema.
       Ok(dir) => match Index::open_or_create(dir, folder_schema.schema.clone()) {
         Ok(index) => index,
+        // The index was created with an older schema. It's created again and the views
+        // are indexed again when the folder is opened.
+        Err(TantivyError::SchemaError(_)) => {
+          match Self::recreate_index(&index_path, &folder_schema) {
+            Ok(index) => index,
+            Err(e) => {
+              tracing::error!("FolderIndexManager failed to recreate index: {:?}", e);
+              return FolderIndexManagerImpl::empty();
+            },
+          }
+        },
         Err(e) => {
           tracing::error!("FolderIndexManager failed to open index: {:?}", e);
           return FolderIndexManagerImpl::empty();
@@ -115,32 +134,27 @@ impl FolderIndexManagerImpl {
     }
   }
 
+  fn recreate_index(
+    index_path: &Path,
+    folder_schema: &FolderSchema,
+  ) -> Result<Index, TantivyError> {
+    tracing::info!("FolderIndexManager schema changed, recreating the index");
+    fs::remove_dir_all(index_path)?;
+    fs::create_dir_all(index_path)?;
+    let dir = MmapDirectory::open(index_path)?;
+    Index::open_or_create(dir, folder_schema.schema.clone())
+  }
+
   fn index_all(&self, indexes: Vec<IndexableData>) -> Result<(), FlowyError> {
     if indexes.is_empty() {
       return Ok(());
     }
 
     let mut index_writer = self.get_index_writer()?;
-    let folder_schema = self.get_folder_schema()?;
-
-    let id_field = folder_schema.schema.get_field(FOLDER_ID_FIELD_NAME)?;
-    let title_field = folder_schema.schema.get_field(FOLDER_TITLE_FIELD_NAME)?;
-    let icon_field = folder_schema.schema.get_field(FOLDER_ICON_FIELD_NAME)?;
-    let icon_ty_field = folder_schema.schema.get_field(FOLDER_ICON_TY_FIELD_NAME)?;
-    let workspace_id_field = folder_schema
-      .schema
-      .get_field(FOLDER_WORKSPACE_ID_FIELD_NAME)?;
-
     for data in indexes {
-      let (icon, icon_ty) = self.extract_icon(data.icon, data.layout);
-
-      let _ = index_writer.add_document(doc![
-        id_field => data.id.clone(),
-        title_field => data.data.clone(),
-        icon_field => icon.unwrap_or_default(),
-        icon_ty_field => icon_ty,
-        workspace_id_field => data.workspace_id.clone(),
-      ]);
+      let tags = data.tags.clone().unwrap_or_default();
+      let document = self.make_document(data, tags)?;
+      let _ = index_writer.add_document(document);
     }
 
     index_writer.commit()?;
@@ -165,6 +179,52 @@ impl FolderIndexManagerImpl {
     }
   }
 
+  fn make_document(&self, data: IndexableData, tags: Vec<String>) -> FlowyResult<TantivyDocument> {
+    let (id_field, title_field, icon_field, icon_ty_field, workspace_id_field, tags_field) =
+      self.get_schema_fields()?;
+    let (icon, icon_ty) = self.extract_icon(data.icon, data.layout);
+
+    let mut document = doc![
+      id_field => data.id,
+      title_field => data.data,
+      icon_field => icon.unwrap_or_default(),
+      icon_ty_field => icon_ty,
+      workspace_id_field => data.workspace_id,
+    ];
+    for tag in tags {
+      document.add_text(tags_field, tag);
+    }
+    Ok(document)
+  }
+
+  /// Returns the tags that are indexed for the view.
+  fn get_indexed_tags(&self, id: &str) -> FlowyResult<Vec<String>> {
+    let index_reader = self
+      .index_reader
+      .as_ref()
+      .ok_or_else(FlowyError::folder_index_manager_unavailable)?;
+    let (id_field, _, _, _, _, tags_field) = self.get_schema_fields()?;
+
+    // The reader must see the latest commit, the tags may have been updated just before
+    index_reader.reload()?;
+    let searcher = index_reader.searcher();
+    let query = TermQuery::new(
+      Term::from_field_text(id_field, id),
+      IndexRecordOption::Basic,
+    );
+    let mut tags = vec![];
+    for (_score, doc_address) in searcher.search(&query, &TopDocs::with_limit(1))? {
+      let retrieved_doc: TantivyDocument = searcher.doc(doc_address)?;
+      tags.extend(
+        retrieved_doc
+          .get_all(tags_field)
+          .filter_map(|value| value.as_str())
+          .map(|tag| tag.to_string()),
+      );
+    }
+    Ok(tags)
+  }
+
   fn get_index_writer(&self) -> FlowyResult<MutexGuard<IndexWriter>> {
     match &self.index_writer {
       Some(index_writer) => match index_writer.deref().lock() {
@@ -210,7 +270,7 @@ impl FolderIndexManagerImpl {
   pub fn search(
     &self,
     query: String,
-    _filter: Option<SearchFilterPB>,
+    filter: Option<SearchFilterPB>,
   ) -> Result<Vec<SearchResultPB>, FlowyError> {
     let folder_schema = self.get_folder_schema()?;
 
@@ -227,7 +287,22 @@ impl FolderIndexManagerImpl {
 
     let mut query_parser = QueryParser::for_index(&index.clone(), vec![title_field]);
     query_parser.set_field_fuzzy(title_field, true, distance, true);
-    let built_query = query_parser.parse_query(&query.clone())?;
+    let mut built_query = query_parser.parse_query(&query.clone())?;
+
+    // Narrow the results to the views that have all the tags of the filter
+    let tag_ids = filter.map(|filter| filter.tag_ids).unwrap_or_default();
+    if !tag_ids.is_empty() {
+      let tags_field = folder_schema.schema.get_field(FOLDER_TAGS_FIELD_NAME)?;
+      let mut sub_queries: Vec<(Occur, Box<dyn Query>)> = vec![(Occur::Must, built_query)];
+      for tag_id in tag_ids {
+        let tag_query = TermQuery::new(
+          Term::from_field_text(tags_field, &tag_id),
+          IndexRecordOption::Basic,
+        );
+        sub_queries.push((Occur::Must, Box::new(tag_query)));
+      }
+      built_query = Box::new(BooleanQuery::new(sub_queries));
+    }
 
     let searcher = index_reader.searcher();
     let mut search_results: Vec<SearchResultPB> = vec![];
@@ -260,7 +335,8 @@ impl FolderIndexManagerImpl {
     1.0 / (distance + 1.0)
   }
 
-  fn get_schema_fields(&self) -> Result<(Field, Field, Field, Field, Field), FlowyError> {
+  #[allow(clippy::type_complexity)]
+  fn get_schema_fields(&self) -> Result<(Field, Field, Field, Field, Field, Field), FlowyError> {
     let folder_schema = match self.folder_schema.clone() {
       Some(schema) => schema,
       _ => return Err(FlowyError::folder_index_manager_unavailable()),
@@ -273,6 +349,7 @@ impl FolderIndexManagerImpl {
     let workspace_id_field = folder_schema
       .schema
       .get_field(FOLDER_WORKSPACE_ID_FIELD_NAME)?;
+    let tags_field = folder_schema.schema.get_field(FOLDER_TAGS_FIELD_NAME)?;
 
     Ok((
       id_field,
@@ -280,6 +357,7 @@ impl FolderIndexManagerImpl {
       icon_field,
       icon_ty_field,
       workspace_id_field,
+      tags_field,
     ))
   }
 }
@@ -293,67 +371,24 @@ impl IndexManager for FolderIndexManagerImpl {
       .unwrap_or(false)
   }
 
-  fn set_index_content_receiver(&self, mut rx: IndexContentReceiver, workspace_id: String) {
-    let indexer = self.clone();
-    let wid = workspace_id.clone();
-    af_spawn(async move {
-      while let Ok(msg) = rx.recv().await {
-        match msg {
-          IndexContent::Create(value) => match serde_json::from_value::<ViewIndexContent>(value) {
-            Ok(view) => {
-              let _ = indexer.add_index(IndexableData {
-                id: view.id,
-                data: view.name,
-                icon: view.icon,
-                layout: view.layout,
-                workspace_id: wid.clone(),
-              });
-            },
-            Err(err) => tracing::error!("FolderIndexManager error deserialize: {:?}", err),
-          },
-          IndexContent::Update(value) => match serde_json::from_value::<ViewIndexContent>(value) {
-            Ok(view) => {
-              let _ = indexer.update_index(IndexableData {
-                id: view.id,
-                data: view.name,
-                icon: view.icon,
-                layout: view.layout,
-                workspace_id: wid.clone(),
-              });
-            },
-            Err(err) => tracing::error!("FolderIndexManager error deserialize: {:?}", err),
-          },
-          IndexContent::Delete(ids) => {
-            if let Err(e) = indexer.remove_indices(ids) {
-              tracing::error!("FolderIndexManager error deserialize: {:?}", e);
-            }
-          },
-        }
-      }
-    });
-  }
-
   fn update_index(&self, data: IndexableData) -> Result<(), FlowyError> {
-    let mut index_writer = self.get_index_writer()?;
+    // Keep the indexed tags when the tags of the view are unknown
+    let tags = match data.tags.clone() {
+      Some(tags) => tags,
+      None => self.get_indexed_tags(&data.id)?,
+    };
 
-    let (id_field, title_field, icon_field, icon_ty_field, workspace_id_field) =
-      self.get_schema_fields()?;
+    let mut index_writer = self.get_index_writer()?;
+    let (id_field, _, _, _, _, _) = self.get_schema_fields()?;
 
     let delete_term = Term::from_field_text(id_field, &data.id.clone());
 
     // Remove old index
     index_writer.delete_term(delete_term);
 
-    let (icon, icon_ty) = self.extract_icon(data.icon, data.layout);
-
     // Add new index
-    let _ = index_writer.add_document(doc![
-      id_field => data.id.clone(),
-      title_field => data.data,
-      icon_field => icon.unwrap_or_default(),
-      icon_ty_field => icon_ty,
-      workspace_id_field => data.workspace_id.clone(),
-    ]);
+    let document = self.make_document(data, tags)?;
+    let _ = index_writer.add_document(document);
 
     index_writer.commit()?;
 
@@ -378,19 +413,10 @@ impl IndexManager for FolderIndexManagerImpl {
   fn add_index(&self, data: IndexableData) -> Result<(), FlowyError> {
     let mut index_writer = self.get_index_writer()?;
 
-    let (id_field, title_field, icon_field, icon_ty_field, workspace_id_field) =
-      self.get_schema_fields()?;
-
-    let (icon, icon_ty) = self.extract_icon(data.icon, data.layout);
-
     // Add new index
-    let _ = index_writer.add_document(doc![
-      id_field => data.id,
-      title_field => data.data,
-      icon_field => icon.unwrap_or_default(),
-      icon_ty_field => icon_ty,
-      workspace_id_field => data.workspace_id,
-    ]);
+    let tags = data.tags.clone().unwrap_or_default();
+    let document = self.make_document(data, tags)?;
+    let _ = index_writer.add_document(document);
 
     index_writer.commit()?;
 
@@ -421,10 +447,64 @@ impl IndexManager for FolderIndexManagerImpl {
 }
 
 impl FolderIndexManager for FolderIndexManagerImpl {
-  fn index_all_views(&self, views: Vec<Arc<View>>, workspace_id: String) {
+  fn set_index_content_receiver(
+    &self,
+    mut rx: IndexContentReceiver,
+    folder: Weak<RwLock<Folder>>,
+    workspace_id: String,
+  ) {
+    let indexer = self.clone();
+    let wid = workspace_id.clone();
+    af_spawn(async move {
+      while let Ok(msg) = rx.recv().await {
+        match msg {
+          IndexContent::Create(value) => match serde_json::from_value::<ViewIndexContent>(value) {
+            Ok(view) => {
+              let tags = read_view_tag_ids(&folder, &view.id).await;
+              let _ = indexer.add_index(IndexableData {
+                id: view.id,
+                data: view.name,
+                icon: view.icon,
+                layout: view.layout,
+                workspace_id: wid.clone(),
+                tags,
+              });
+            },
+            Err(err) => tracing::error!("FolderIndexManager error deserialize: {:?}", err),
+          },
+          IndexContent::Update(value) => match serde_json::from_value::<ViewIndexContent>(value) {
+            Ok(view) => {
+              let tags = read_view_tag_ids(&folder, &view.id).await;
+              let _ = indexer.update_index(IndexableData {
+                id: view.id,
+                data: view.name,
+                icon: view.icon,
+                layout: view.layout,
+                workspace_id: wid.clone(),
+                tags,
+              });
+            },
+            Err(err) => tracing::error!("FolderIndexManager error deserialize: {:?}", err),
+          },
+          IndexContent::Delete(ids) => {
+            if let Err(e) = indexer.remove_indices(ids) {
+              tracing::error!("FolderIndexManager error deserialize: {:?}", e);
+            }
+          },
+        }
+      }
+    });
+  }
+
+  fn index_all_views(
+    &self,
+    views: Vec<Arc<View>>,
+    view_tags: HashMap<String, Vec<String>>,
+    workspace_id: String,
+  ) {
     let indexable_data = views
       .into_iter()
-      .map(|view| IndexableData::from_view(view, workspace_id.clone()))
+      .map(|view| indexable_data_from_view(view, &view_tags, workspace_id.clone()))
       .collect();
 
     let _ = self.index_all(indexable_data);
@@ -433,6 +513,7 @@ impl FolderIndexManager for FolderIndexManagerImpl {
   fn index_view_changes(
     &self,
     views: Vec<Arc<View>>,
+    view_tags: HashMap<String, Vec<String>>,
     changes: Vec<FolderViewChange>,
     workspace_id: String,
   ) {
@@ -442,14 +523,14 @@ impl FolderIndexManager for FolderIndexManagerImpl {
         FolderViewChange::Inserted { view_id } => {
           let view = views_iter.find(|view| view.id == view_id);
           if let Some(view) = view {
-            let indexable_data = IndexableData::from_view(view, workspace_id.clone());
+            let indexable_data = indexable_data_from_view(view, &view_tags, workspace_id.clone());
             let _ = self.add_index(indexable_data);
           }
         },
         FolderViewChange::Updated { view_id } => {
           let view = views_iter.find(|view| view.id == view_id);
           if let Some(view) = view {
-            let indexable_data = IndexableData::from_view(view, workspace_id.clone());
+            let indexable_data = indexable_data_from_view(view, &view_tags, workspace_id.clone());
             let _ = self.update_index(indexable_data);
           }
         },
@@ -460,3 +541,24 @@ impl FolderIndexManager for FolderIndexManagerImpl {
     }
   }
 }
+
+/// Returns the ids of the tags of the view in the folder, or `None` if the folder is closed or
+/// the view can't be found.
+async fn read_view_tag_ids(folder: &Weak<RwLock<Folder>>, view_id: &str) -> Option<Vec<String>> {
+  let folder = folder.upgrade()?;
+  let folder = folder.read().await;
+  let view = folder.get_view(view_id)?;
+  Some(view_tag_ids(&folder.collab, &view))
+}
+
+fn indexable_data_from_view(
+  view: Arc<View>,
+  view_tags: &HashMap<String, Vec<String>>,
+  workspace_id: String,
+) -> IndexableData {
+  let tags = view_tags.get(&view.id).cloned().unwrap_or_default();
+  IndexableData {
+    tags: Some(tags),
+    ..IndexableData::from_view(view, workspace_id)
+  }
+}
@@ -5,6 +5,7 @@ pub const FOLDER_TITLE_FIELD_NAME: &str = "title";
 pub const FOLDER_ICON_FIELD_NAME: &str = "icon";
 pub const FOLDER_ICON_TY_FIELD_NAME: &str = "icon_ty";
 pub const FOLDER_WORKSPACE_ID_FIELD_NAME: &str = "workspace_id";
+pub const FOLDER_TAGS_FIELD_NAME: &str = "tags";
 
 #[derive(Clone)]
 pub struct FolderSchema {
@@ -16,7 +17,10 @@ pub struct FolderSchema {
 /// from previously created index, causing tantivy to panic and search to stop functioning.
 ///
 /// If you need to change the schema, create a migration that removes the old index,
-/// and creates a new one with the new schema.
+/// and creates a new one with the new schema. An index whose schema doesn't match is
+/// removed and created again when it's opened, see [FolderIndexManagerImpl::new].
+///
+/// [FolderIndexManagerImpl::new]: crate::folder::indexer::FolderIndexManagerImpl::new
 ///
 impl FolderSchema {
   pub fn new() -> Self {
@@ -38,6 +42,11 @@ impl FolderSchema {
       FOLDER_WORKSPACE_ID_FIELD_NAME,
       tantivy::schema::TEXT | tantivy::schema::STORED,
     );
+    // A view has one value per tag id
+    schema_builder.add_text_field(
+      FOLDER_TAGS_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
 
     let schema = schema_builder.build();
 
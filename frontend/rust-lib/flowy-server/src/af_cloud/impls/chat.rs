@@ -1,5 +1,7 @@
 use crate::af_cloud::AFServer;
-use client_api::entity::ai_dto::{CompleteTextParams, CompletionType, RepeatedRelatedQuestion};
+use client_api::entity::ai_dto::{
+  CompleteTextParams, CompletionType, CustomPrompt, RepeatedRelatedQuestion,
+};
 use client_api::entity::chat_dto::{
   CreateAnswerMessageParams, CreateChatMessageParams, CreateChatParams, MessageCursor,
   RepeatedChatMessage,
@@ -16,6 +18,9 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::path::Path;
 
+const CUSTOM_PROMPT_SYSTEM: &str =
+  "You are a writing assistant. Follow the instructions of the user and reply with the result only.";
+
 pub(crate) struct AFCloudChatCloudServiceImpl<T> {
   pub inner: T,
 }
@@ -173,6 +178,30 @@ where
     Ok(stream.boxed())
   }
 
+  async fn stream_complete_with_prompt(
+    &self,
+    workspace_id: &str,
+    prompt: &str,
+  ) -> Result<StreamComplete, FlowyError> {
+    // The rendered prompt already contains the text to complete, so it's sent as the text and the
+    // custom system prompt replaces the one of the built-in completion types.
+    let params = CompleteTextParams {
+      text: prompt.to_string(),
+      completion_type: None,
+      custom_prompt: Some(CustomPrompt {
+        system: CUSTOM_PROMPT_SYSTEM.to_string(),
+      }),
+    };
+    let stream = self
+      .inner
+      .try_get_client()?
+      .stream_completion_text(workspace_id, params)
+      .await
+      .map_err(FlowyError::from)?
+      .map_err(FlowyError::from);
+    Ok(stream.boxed())
+  }
+
   async fn index_file(
     &self,
     _workspace_id: &str,
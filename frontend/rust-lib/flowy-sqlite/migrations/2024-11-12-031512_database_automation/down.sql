@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+drop table database_automation_rule_table;
+drop table database_automation_log_table;
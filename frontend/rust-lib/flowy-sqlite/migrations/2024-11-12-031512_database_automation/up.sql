@@ -0,0 +1,27 @@
+-- Automation rules that are attached to a database
+CREATE TABLE database_automation_rule_table
+(
+    rule_id        TEXT PRIMARY KEY NOT NULL,
+    database_id    TEXT             NOT NULL,
+    name           TEXT             NOT NULL DEFAULT '',
+    enabled        BOOLEAN          NOT NULL DEFAULT TRUE,
+    trigger_data   TEXT             NOT NULL,
+    condition_data TEXT,
+    actions_data   TEXT             NOT NULL,
+    created_at     BIGINT           NOT NULL,
+    updated_at     BIGINT           NOT NULL
+);
+CREATE INDEX idx_database_automation_rule_database_id ON database_automation_rule_table (database_id);
+
+-- Execution log of the automation rules
+CREATE TABLE database_automation_log_table
+(
+    log_id      TEXT PRIMARY KEY NOT NULL,
+    rule_id     TEXT             NOT NULL,
+    database_id TEXT             NOT NULL,
+    row_id      TEXT             NOT NULL,
+    status      INTEGER          NOT NULL,
+    message     TEXT             NOT NULL DEFAULT '',
+    executed_at BIGINT           NOT NULL
+);
+CREATE INDEX idx_database_automation_log_database_id ON database_automation_log_table (database_id, executed_at);
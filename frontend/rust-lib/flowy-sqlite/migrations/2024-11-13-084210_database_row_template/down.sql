@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+drop table database_view_row_template_table;
+drop table database_row_template_table;
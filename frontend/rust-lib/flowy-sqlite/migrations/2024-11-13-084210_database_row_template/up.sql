@@ -0,0 +1,20 @@
+-- Row templates that are attached to a database
+CREATE TABLE database_row_template_table
+(
+    template_id   TEXT PRIMARY KEY NOT NULL,
+    database_id   TEXT             NOT NULL,
+    name          TEXT             NOT NULL DEFAULT '',
+    cells_data    TEXT             NOT NULL,
+    document_data TEXT,
+    created_at    BIGINT           NOT NULL,
+    updated_at    BIGINT           NOT NULL
+);
+CREATE INDEX idx_database_row_template_database_id ON database_row_template_table (database_id);
+
+-- The default row template of each database view
+CREATE TABLE database_view_row_template_table
+(
+    view_id     TEXT PRIMARY KEY NOT NULL,
+    database_id TEXT             NOT NULL,
+    template_id TEXT             NOT NULL
+);
@@ -0,0 +1,12 @@
+-- User-defined prompts for AI completions. The prompts with an empty workspace_id are available in
+-- all the workspaces of the user.
+CREATE TABLE ai_prompt_table
+(
+    prompt_id    TEXT PRIMARY KEY NOT NULL,
+    workspace_id TEXT             NOT NULL DEFAULT '',
+    name         TEXT             NOT NULL,
+    template     TEXT             NOT NULL,
+    created_at   BIGINT           NOT NULL,
+    updated_at   BIGINT           NOT NULL
+);
+CREATE INDEX idx_ai_prompt_workspace_id ON ai_prompt_table (workspace_id);
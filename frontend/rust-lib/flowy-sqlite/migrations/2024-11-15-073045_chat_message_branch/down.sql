@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+drop table chat_active_branch_table;
+drop table chat_message_branch_table;
@@ -0,0 +1,20 @@
+-- The parent of the chat messages that are sent after a message is edited. The messages without a
+-- record follow the previous message of the chat.
+CREATE TABLE chat_message_branch_table
+(
+    message_id        BIGINT PRIMARY KEY NOT NULL,
+    chat_id           TEXT               NOT NULL,
+    parent_message_id BIGINT,
+    created_at        BIGINT             NOT NULL
+);
+CREATE INDEX idx_chat_message_branch_chat_id ON chat_message_branch_table (chat_id);
+
+-- The selected child of a message that has multiple children. The root messages of a chat use 0 as
+-- the parent_message_id.
+CREATE TABLE chat_active_branch_table
+(
+    chat_id           TEXT   NOT NULL,
+    parent_message_id BIGINT NOT NULL,
+    message_id        BIGINT NOT NULL,
+    PRIMARY KEY (chat_id, parent_message_id)
+);
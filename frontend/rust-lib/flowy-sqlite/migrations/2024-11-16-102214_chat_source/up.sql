@@ -0,0 +1,14 @@
+-- The workspace views that are attached to a chat as the retrieval sources of the local AI. The
+-- version is increased every time the view is indexed again.
+CREATE TABLE chat_source_table
+(
+    chat_id      TEXT    NOT NULL,
+    view_id      TEXT    NOT NULL,
+    name         TEXT    NOT NULL DEFAULT '',
+    source_type  INTEGER NOT NULL,
+    content_hash TEXT    NOT NULL DEFAULT '',
+    version      BIGINT  NOT NULL DEFAULT 0,
+    chunk_count  INTEGER NOT NULL DEFAULT 0,
+    indexed_at   BIGINT  NOT NULL DEFAULT 0,
+    PRIMARY KEY (chat_id, view_id)
+);
@@ -0,0 +1,2 @@
+DROP TABLE document_mention_table;
+DROP TABLE indexed_document_table;
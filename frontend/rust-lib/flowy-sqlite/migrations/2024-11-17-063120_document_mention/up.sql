@@ -0,0 +1,16 @@
+-- The page mentions of the documents. Each row is a mention of the view in a block of the document.
+CREATE TABLE document_mention_table
+(
+    document_id TEXT NOT NULL,
+    block_id    TEXT NOT NULL,
+    view_id     TEXT NOT NULL,
+    PRIMARY KEY (document_id, block_id, view_id)
+);
+CREATE INDEX idx_document_mention_view_id ON document_mention_table (view_id);
+
+-- The documents whose mentions have been indexed, including the documents without mentions.
+CREATE TABLE indexed_document_table
+(
+    document_id TEXT   NOT NULL PRIMARY KEY,
+    indexed_at  BIGINT NOT NULL DEFAULT 0
+);
@@ -0,0 +1,2 @@
+DROP TABLE document_task_table;
+DROP TABLE task_indexed_document_table;
@@ -0,0 +1,24 @@
+-- The todo blocks of the documents. Each row is a todo block of a document.
+CREATE TABLE document_task_table
+(
+    document_id TEXT    NOT NULL,
+    block_id    TEXT    NOT NULL,
+    -- the position of the block among the todo blocks of the document
+    position    INTEGER NOT NULL DEFAULT 0,
+    content     TEXT    NOT NULL DEFAULT '',
+    is_checked  BOOLEAN NOT NULL DEFAULT FALSE,
+    -- the earliest date mentioned in the todo, in seconds
+    due_at      BIGINT,
+    reminder_id TEXT,
+    -- the mentioned people, encoded as a JSON array
+    people      TEXT    NOT NULL DEFAULT '[]',
+    PRIMARY KEY (document_id, block_id)
+);
+CREATE INDEX idx_document_task_due_at ON document_task_table (due_at);
+
+-- The documents whose todo blocks have been indexed, including the documents without todo blocks.
+CREATE TABLE task_indexed_document_table
+(
+    document_id TEXT   NOT NULL PRIMARY KEY,
+    indexed_at  BIGINT NOT NULL DEFAULT 0
+);
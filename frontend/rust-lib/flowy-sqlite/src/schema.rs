@@ -1,5 +1,24 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    ai_prompt_table (prompt_id) {
+        prompt_id -> Text,
+        workspace_id -> Text,
+        name -> Text,
+        template -> Text,
+        created_at -> BigInt,
+        updated_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    chat_active_branch_table (chat_id, parent_message_id) {
+        chat_id -> Text,
+        parent_message_id -> BigInt,
+        message_id -> BigInt,
+    }
+}
+
 diesel::table! {
     chat_local_setting_table (chat_id) {
         chat_id -> Text,
@@ -8,6 +27,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    chat_message_branch_table (message_id) {
+        message_id -> BigInt,
+        chat_id -> Text,
+        parent_message_id -> Nullable<BigInt>,
+        created_at -> BigInt,
+    }
+}
+
 diesel::table! {
     chat_message_table (message_id) {
         message_id -> BigInt,
@@ -21,6 +49,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    chat_source_table (chat_id, view_id) {
+        chat_id -> Text,
+        view_id -> Text,
+        name -> Text,
+        source_type -> Integer,
+        content_hash -> Text,
+        version -> BigInt,
+        chunk_count -> Integer,
+        indexed_at -> BigInt,
+    }
+}
+
 diesel::table! {
     chat_table (chat_id) {
         chat_id -> Text,
@@ -45,6 +86,87 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    database_automation_log_table (log_id) {
+        log_id -> Text,
+        rule_id -> Text,
+        database_id -> Text,
+        row_id -> Text,
+        status -> Integer,
+        message -> Text,
+        executed_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    database_automation_rule_table (rule_id) {
+        rule_id -> Text,
+        database_id -> Text,
+        name -> Text,
+        enabled -> Bool,
+        trigger_data -> Text,
+        condition_data -> Nullable<Text>,
+        actions_data -> Text,
+        created_at -> BigInt,
+        updated_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    database_row_template_table (template_id) {
+        template_id -> Text,
+        database_id -> Text,
+        name -> Text,
+        cells_data -> Text,
+        document_data -> Nullable<Text>,
+        created_at -> BigInt,
+        updated_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    database_view_row_template_table (view_id) {
+        view_id -> Text,
+        database_id -> Text,
+        template_id -> Text,
+    }
+}
+
+diesel::table! {
+    document_mention_table (document_id, block_id, view_id) {
+        document_id -> Text,
+        block_id -> Text,
+        view_id -> Text,
+    }
+}
+
+diesel::table! {
+    document_task_table (document_id, block_id) {
+        document_id -> Text,
+        block_id -> Text,
+        position -> Integer,
+        content -> Text,
+        is_checked -> Bool,
+        due_at -> Nullable<BigInt>,
+        reminder_id -> Nullable<Text>,
+        people -> Text,
+    }
+}
+
+diesel::table! {
+    indexed_document_table (document_id) {
+        document_id -> Text,
+        indexed_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    task_indexed_document_table (document_id) {
+        document_id -> Text,
+        indexed_at -> BigInt,
+    }
+}
+
 diesel::table! {
     upload_file_part (upload_id, e_tag) {
         upload_id -> Text,
@@ -118,10 +240,22 @@ diesel::table! {
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
+  ai_prompt_table,
+  chat_active_branch_table,
   chat_local_setting_table,
+  chat_message_branch_table,
   chat_message_table,
+  chat_source_table,
   chat_table,
   collab_snapshot,
+  database_automation_log_table,
+  database_automation_rule_table,
+  database_row_template_table,
+  database_view_row_template_table,
+  document_mention_table,
+  document_task_table,
+  indexed_document_table,
+  task_indexed_document_table,
   upload_file_part,
   upload_file_table,
   user_data_migration_records,
@@ -20,6 +20,24 @@ pub trait StorageService: Send + Sync {
     local_file_path: &str,
   ) -> Result<(CreatedUpload, Option<FileProgressReceiver>), FlowyError>;
 
+  /// Copies the file behind `url` into `parent_dir` of the given workspace and returns the url
+  /// of the copy. Returns `None` when the url doesn't point to a file managed by the storage.
+  async fn copy_object(
+    &self,
+    url: &str,
+    workspace_id: &str,
+    parent_dir: &str,
+  ) -> FlowyResult<Option<String>>;
+
+  /// Returns the size of the file behind `url` on this device, which is 0 if the file is only
+  /// stored in the cloud. Returns `None` when the url doesn't point to a file managed by the storage.
+  async fn get_object_size(&self, url: &str) -> FlowyResult<Option<u64>>;
+
+  /// Returns the file name and the data of the file behind `url`. The file is read from this
+  /// device if it hasn't been uploaded yet. Returns `None` when the url doesn't point to a file
+  /// managed by the storage.
+  async fn get_object_data(&self, url: &str) -> FlowyResult<Option<(String, Vec<u8>)>>;
+
   async fn start_upload(&self, record: &BoxAny) -> Result<(), FlowyError>;
 
   async fn resume_upload(
@@ -420,6 +420,103 @@ impl StorageService for StorageServiceImpl {
     }
   }
 
+  async fn copy_object(
+    &self,
+    url: &str,
+    workspace_id: &str,
+    parent_dir: &str,
+  ) -> FlowyResult<Option<String>> {
+    let (file_name, data) = match self.get_object_data(url).await? {
+      Some(value) => value,
+      None => return Ok(None),
+    };
+
+    // The file is staged outside the temp storage because [Self::create_upload] copies it into
+    // the temp storage under the same file name.
+    let staging_dir = std::env::temp_dir().join(format!(
+      "appflowy_copy_{}",
+      chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    tokio::fs::create_dir_all(&staging_dir).await?;
+
+    let result = async {
+      let staging_file_path = staging_dir.join(file_name);
+      tokio::fs::write(&staging_file_path, &data).await?;
+      let (created_upload, _) = self
+        .create_upload(
+          workspace_id,
+          parent_dir,
+          &staging_file_path.to_string_lossy(),
+        )
+        .await?;
+      Ok::<_, FlowyError>(created_upload.url)
+    }
+    .await;
+
+    if let Err(err) = tokio::fs::remove_dir_all(&staging_dir).await {
+      error!("[File] remove copy staging dir failed: {}", err);
+    }
+    trace!(
+      "[File] copy object {} to {}:{}",
+      url,
+      workspace_id,
+      parent_dir
+    );
+    result.map(Some)
+  }
+
+  async fn get_object_data(&self, url: &str) -> FlowyResult<Option<(String, Vec<u8>)>> {
+    let (workspace_id, parent_dir, file_id) =
+      match self.cloud_service.parse_object_url_v1(url).await {
+        Some(value) => value,
+        None => return Ok(None),
+      };
+
+    // Prefer the local file if it hasn't been uploaded yet.
+    let local_file_path = {
+      let mut conn = self
+        .user_service
+        .sqlite_connection(self.user_service.user_id()?)?;
+      select_upload_file(&mut conn, &workspace_id, &parent_dir, &file_id)?
+        .map(|file| PathBuf::from(file.local_file_path))
+        .filter(|path| path.exists())
+    };
+    match local_file_path {
+      Some(local_file_path) => {
+        let file_name = local_file_path
+          .file_name()
+          .and_then(|name| name.to_str())
+          .map(|name| name.to_string())
+          .unwrap_or(file_id);
+        let data = tokio::fs::read(&local_file_path).await?;
+        Ok(Some((file_name, data)))
+      },
+      None => {
+        let object_value = self.cloud_service.get_object(url.to_string()).await?;
+        Ok(Some((file_id, object_value.raw.to_vec())))
+      },
+    }
+  }
+
+  async fn get_object_size(&self, url: &str) -> FlowyResult<Option<u64>> {
+    let (workspace_id, parent_dir, file_id) =
+      match self.cloud_service.parse_object_url_v1(url).await {
+        Some(value) => value,
+        None => return Ok(None),
+      };
+    let mut conn = self
+      .user_service
+      .sqlite_connection(self.user_service.user_id()?)?;
+    let size = match select_upload_file(&mut conn, &workspace_id, &parent_dir, &file_id)? {
+      None => 0,
+      Some(file) => tokio::fs::metadata(&file.local_file_path)
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or(0),
+    };
+    Ok(Some(size))
+  }
+
   async fn start_upload(&self, record: &BoxAny) -> Result<(), FlowyError> {
     let file_record = record.downcast_ref::<UploadFileTable>().ok_or_else(|| {
       FlowyError::internal().with_context("failed to downcast record to UploadFileTable")
@@ -239,6 +239,15 @@ pub fn batch_select_upload_file(
   Ok(results)
 }
 
+/// Returns the local paths of the files of all the upload records, which are kept until the files
+/// are uploaded.
+pub fn select_upload_file_paths(mut conn: DBConnection) -> FlowyResult<Vec<String>> {
+  let paths = upload_file_table::dsl::upload_file_table
+    .select(upload_file_table::local_file_path)
+    .load::<String>(&mut *conn)?;
+  Ok(paths)
+}
+
 pub fn select_upload_file(
   conn: &mut SqliteConnection,
   workspace_id: &str,
@@ -0,0 +1,85 @@
+use flowy_derive::ProtoBuf;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+use crate::services::backup::{BackupInfo, BackupSetting};
+
+#[derive(ProtoBuf, Default, Debug, Clone)]
+pub struct BackupSettingPB {
+  #[pb(index = 1)]
+  pub enabled: bool,
+
+  #[pb(index = 2)]
+  pub interval_hours: u32,
+
+  #[pb(index = 3)]
+  pub max_backups: u32,
+
+  #[pb(index = 4)]
+  pub encrypt: bool,
+}
+
+impl From<BackupSetting> for BackupSettingPB {
+  fn from(setting: BackupSetting) -> Self {
+    Self {
+      enabled: setting.enabled,
+      interval_hours: setting.interval_hours,
+      max_backups: setting.max_backups,
+      encrypt: setting.encrypt,
+    }
+  }
+}
+
+impl From<BackupSettingPB> for BackupSetting {
+  fn from(pb: BackupSettingPB) -> Self {
+    Self {
+      enabled: pb.enabled,
+      interval_hours: pb.interval_hours.max(1),
+      max_backups: pb.max_backups.max(1),
+      encrypt: pb.encrypt,
+    }
+  }
+}
+
+#[derive(ProtoBuf, Default, Debug, Clone)]
+pub struct UserBackupPB {
+  #[pb(index = 1)]
+  pub backup_id: String,
+
+  #[pb(index = 2)]
+  pub created_at: i64,
+
+  #[pb(index = 3)]
+  pub app_version: String,
+
+  #[pb(index = 4)]
+  pub size: u64,
+
+  #[pb(index = 5)]
+  pub encrypted: bool,
+}
+
+impl From<BackupInfo> for UserBackupPB {
+  fn from(info: BackupInfo) -> Self {
+    Self {
+      backup_id: info.backup_id,
+      created_at: info.created_at,
+      app_version: info.app_version,
+      size: info.size,
+      encrypted: info.encrypted,
+    }
+  }
+}
+
+#[derive(ProtoBuf, Default, Debug, Clone)]
+pub struct RepeatedUserBackupPB {
+  #[pb(index = 1)]
+  pub items: Vec<UserBackupPB>,
+}
+
+#[derive(ProtoBuf, Validate, Default)]
+pub struct RestoreBackupPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub backup_id: String,
+}
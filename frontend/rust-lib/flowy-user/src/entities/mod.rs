@@ -1,4 +1,5 @@
 pub use auth::*;
+pub use backup::*;
 pub use import_data::*;
 pub use realtime::*;
 pub use reminder::*;
@@ -7,6 +8,7 @@ pub use user_setting::*;
 pub use workspace::*;
 
 pub mod auth;
+mod backup;
 pub mod date_time;
 mod import_data;
 pub mod parser;
@@ -906,3 +906,61 @@ pub async fn notify_did_switch_plan_handler(
   manager.notify_did_switch_plan(success).await?;
   Ok(())
 }
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub async fn get_backup_setting_handler(
+  manager: AFPluginState<Weak<UserManager>>,
+) -> DataResult<BackupSettingPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let setting = manager.backup_service().await.get_backup_setting()?;
+  data_result_ok(setting.into())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub async fn set_backup_setting_handler(
+  data: AFPluginData<BackupSettingPB>,
+  manager: AFPluginState<Weak<UserManager>>,
+) -> Result<(), FlowyError> {
+  let setting = data.into_inner();
+  let manager = upgrade_manager(manager)?;
+  manager
+    .backup_service()
+    .await
+    .set_backup_setting(setting.into())?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub async fn create_backup_handler(
+  manager: AFPluginState<Weak<UserManager>>,
+) -> DataResult<UserBackupPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let backup = manager.backup_service().await.create_backup().await?;
+  data_result_ok(backup.into())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub async fn list_backups_handler(
+  manager: AFPluginState<Weak<UserManager>>,
+) -> DataResult<RepeatedUserBackupPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let backups = manager.backup_service().await.list_backups().await?;
+  data_result_ok(RepeatedUserBackupPB {
+    items: backups.into_iter().map(UserBackupPB::from).collect(),
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub async fn restore_backup_handler(
+  data: AFPluginData<RestoreBackupPB>,
+  manager: AFPluginState<Weak<UserManager>>,
+) -> Result<(), FlowyError> {
+  let data = data.try_into_inner()?;
+  let manager = upgrade_manager(manager)?;
+  manager
+    .backup_service()
+    .await
+    .restore_backup(&data.backup_id)
+    .await?;
+  Ok(())
+}
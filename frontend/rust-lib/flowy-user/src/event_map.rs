@@ -81,6 +81,12 @@ pub fn init(user_manager: Weak<UserManager>) -> AFPlugin {
     .event(UserEvent::UpdateWorkspaceSetting, update_workspace_setting)
     .event(UserEvent::GetWorkspaceSetting, get_workspace_setting)
     .event(UserEvent::NotifyDidSwitchPlan, notify_did_switch_plan_handler)
+    // Backup
+    .event(UserEvent::GetBackupSetting, get_backup_setting_handler)
+    .event(UserEvent::SetBackupSetting, set_backup_setting_handler)
+    .event(UserEvent::CreateBackup, create_backup_handler)
+    .event(UserEvent::ListBackups, list_backups_handler)
+    .event(UserEvent::RestoreBackup, restore_backup_handler)
 
 }
 
@@ -278,6 +284,23 @@ pub enum UserEvent {
 
   #[event()]
   DeleteAccount = 64,
+
+  #[event(output = "BackupSettingPB")]
+  GetBackupSetting = 65,
+
+  #[event(input = "BackupSettingPB")]
+  SetBackupSetting = 66,
+
+  /// Backs up the data of the current user immediately, regardless of the backup schedule.
+  #[event(output = "UserBackupPB")]
+  CreateBackup = 67,
+
+  #[event(output = "RepeatedUserBackupPB")]
+  ListBackups = 68,
+
+  /// The backup replaces the data of the user the next time the application launches.
+  #[event(input = "RestoreBackupPB")]
+  RestoreBackup = 69,
 }
 
 #[async_trait]
@@ -1,7 +1,8 @@
 use crate::migrations::session_migration::migrate_session_with_user_uuid;
+use crate::services::cloud_config::get_or_create_cloud_config;
 use crate::services::db::UserDB;
 use crate::services::entities::{UserConfig, UserPaths};
-use crate::services::sqlite_sql::user_sql::vacuum_database;
+use crate::services::sqlite_sql::user_sql::{select_user_profile, vacuum_database};
 use collab_integrate::CollabKVDB;
 
 use arc_swap::ArcSwapOption;
@@ -12,7 +13,7 @@ use flowy_sqlite::kv::KVStorePreferences;
 use flowy_sqlite::DBConnection;
 use flowy_user_pub::entities::UserWorkspace;
 use flowy_user_pub::session::Session;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Weak};
 use tracing::{error, info};
 
@@ -64,6 +65,13 @@ impl AuthenticateUser {
     Ok(session.user_id)
   }
 
+  /// Returns the name in the profile of the current user
+  pub fn user_name(&self) -> FlowyResult<String> {
+    let uid = self.user_id()?;
+    let profile = select_user_profile(uid, self.get_sqlite_connection(uid)?)?;
+    Ok(profile.name)
+  }
+
   pub fn device_id(&self) -> FlowyResult<String> {
     Ok(self.user_config.device_id.to_string())
   }
@@ -78,6 +86,12 @@ impl AuthenticateUser {
     Ok(session.user_workspace.workspace_database_id.clone())
   }
 
+  /// Returns the encrypt secret of the current user, which is created if the user doesn't have one
+  pub fn encrypt_secret(&self) -> FlowyResult<String> {
+    let uid = self.user_id()?;
+    Ok(get_or_create_cloud_config(uid, &self.store_preferences).encrypt_secret)
+  }
+
   pub fn get_collab_db(&self, uid: i64) -> FlowyResult<Weak<CollabKVDB>> {
     self
       .database
@@ -89,6 +103,17 @@ impl AuthenticateUser {
     self.database.get_connection(uid)
   }
 
+  /// Writes a consistent copy of the sqlite database and the collab database of the user to the
+  /// paths, while the databases are in use.
+  pub fn snapshot_databases(
+    &self,
+    uid: i64,
+    sqlite_db_path: &Path,
+    collab_db_path: &Path,
+  ) -> FlowyResult<()> {
+    self.database.snapshot(uid, sqlite_db_path, collab_db_path)
+  }
+
   pub fn get_index_path(&self) -> PathBuf {
     let uid = self.user_id().unwrap_or(0);
     PathBuf::from(self.user_paths.user_data_dir(uid)).join("indexes")
@@ -0,0 +1,72 @@
+use flowy_error::{FlowyError, FlowyResult};
+use lib_infra::async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// How often the user data is backed up and how many backups are kept. The scheduled backups are
+/// off until the user turns them on.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupSetting {
+  pub enabled: bool,
+  pub interval_hours: u32,
+  /// The oldest backups are removed when there are more backups than this.
+  pub max_backups: u32,
+  /// Encrypt the backups with the encryption secret of the user.
+  pub encrypt: bool,
+}
+
+impl Default for BackupSetting {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      interval_hours: 24,
+      max_backups: 7,
+      encrypt: false,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupInfo {
+  pub backup_id: String,
+  pub created_at: i64,
+  /// The version of the application that created the backup.
+  pub app_version: String,
+  /// The size of the archive in bytes.
+  pub size: u64,
+  pub encrypted: bool,
+  /// The MD5 checksum of the archive, used to verify it before restoring.
+  pub checksum: String,
+}
+
+/// Backs up the data of the current user: the collab database, the sqlite database and the
+/// local files.
+#[async_trait]
+pub trait UserBackupService: Send + Sync + 'static {
+  fn get_backup_setting(&self) -> FlowyResult<BackupSetting> {
+    Err(FlowyError::not_support())
+  }
+
+  fn set_backup_setting(&self, _setting: BackupSetting) -> FlowyResult<()> {
+    Err(FlowyError::not_support())
+  }
+
+  async fn create_backup(&self) -> FlowyResult<BackupInfo> {
+    Err(FlowyError::not_support())
+  }
+
+  /// Returns the backups of the current user, the newest first.
+  async fn list_backups(&self) -> FlowyResult<Vec<BackupInfo>> {
+    Err(FlowyError::not_support())
+  }
+
+  /// Verifies the backup and prepares it to replace the data of the user. The data is replaced
+  /// the next time the application launches, before any database is opened.
+  async fn restore_backup(&self, _backup_id: &str) -> FlowyResult<()> {
+    Err(FlowyError::not_support())
+  }
+}
+
+pub struct DefaultUserBackupService;
+
+#[async_trait]
+impl UserBackupService for DefaultUserBackupService {}
@@ -2,11 +2,14 @@ use std::path::{Path, PathBuf};
 use std::{fs, io, sync::Arc};
 
 use chrono::{Days, Local};
+use collab::preclude::updates::encoder::Encode;
+use collab::preclude::{Collab, ReadTxn, StateVector};
 use collab_integrate::{CollabKVAction, CollabKVDB, PersistenceError};
 use collab_plugins::local_storage::kv::KVTransactionDB;
 use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
-use flowy_error::FlowyError;
+use diesel::sql_query;
+use flowy_error::{internal_error, FlowyError};
 use flowy_sqlite::schema::user_workspace_table;
 use flowy_sqlite::ConnectionPool;
 use flowy_sqlite::{
@@ -22,6 +25,9 @@ use tracing::{error, event, info, instrument};
 use crate::services::sqlite_sql::user_sql::UserTable;
 use crate::services::sqlite_sql::workspace_sql::UserWorkspaceTable;
 
+/// The number of the collab objects that are written in one transaction of a snapshot.
+const SNAPSHOT_BATCH_SIZE: usize = 100;
+
 pub trait UserDBPath: Send + Sync + 'static {
   fn sqlite_db_path(&self, uid: i64) -> PathBuf;
   fn collab_db_path(&self, uid: i64) -> PathBuf;
@@ -95,6 +101,64 @@ impl UserDB {
     Ok(())
   }
 
+  /// Writes a consistent copy of the databases of the user while they are in use, instead of
+  /// copying their files, which may be in the middle of a write. The sqlite database is copied
+  /// with `VACUUM INTO`, and the collab objects of the workspaces of the user are copied into a
+  /// new collab database.
+  #[instrument(level = "debug", skip(self), err)]
+  pub(crate) fn snapshot(
+    &self,
+    user_id: i64,
+    sqlite_db_path: &Path,
+    collab_db_path: &Path,
+  ) -> Result<(), FlowyError> {
+    let mut conn = self.get_connection(user_id)?;
+    let path = sqlite_db_path.to_string_lossy().replace('\'', "''");
+    sql_query(format!("VACUUM INTO '{}'", path))
+      .execute(&mut *conn)
+      .map_err(internal_error)?;
+    let workspace_ids = user_workspace_table::dsl::user_workspace_table
+      .filter(user_workspace_table::uid.eq(user_id))
+      .select(user_workspace_table::id)
+      .load::<String>(&mut *conn)?;
+    drop(conn);
+
+    let collab_db = self.get_collab_db(user_id)?;
+    let snapshot_db = CollabKVDB::open(collab_db_path)?;
+    let read_txn = collab_db.read_txn();
+    for workspace_id in &workspace_ids {
+      let object_ids = read_txn
+        .get_all_object_ids(user_id, workspace_id)
+        .map(|iter| iter.collect::<Vec<String>>())
+        .unwrap_or_default();
+      // The collabs are written in batches, so the pending writes of a large workspace don't
+      // have to be kept in memory at once.
+      for object_ids in object_ids.chunks(SNAPSHOT_BATCH_SIZE) {
+        snapshot_db.with_write_txn(|write_txn| {
+          for object_id in object_ids {
+            let mut collab = Collab::new(user_id, object_id, "phantom", vec![], false);
+            read_txn.load_doc_with_txn(
+              user_id,
+              workspace_id,
+              object_id,
+              &mut collab.transact_mut(),
+            )?;
+            let txn = collab.transact();
+            write_txn.flush_doc(
+              user_id,
+              workspace_id,
+              object_id,
+              txn.state_vector().encode_v1(),
+              txn.encode_state_as_update_v1(&StateVector::default()),
+            )?;
+          }
+          Ok(())
+        })?;
+      }
+    }
+    Ok(())
+  }
+
   pub(crate) fn get_connection(&self, user_id: i64) -> Result<DBConnection, FlowyError> {
     let conn = self.get_pool(user_id)?.get()?;
     Ok(conn)
@@ -1,4 +1,5 @@
 pub mod authenticate_user;
+pub mod backup;
 pub(crate) mod billing_check;
 pub mod cloud_config;
 pub mod collab_interact;
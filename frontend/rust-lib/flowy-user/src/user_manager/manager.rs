@@ -36,6 +36,7 @@ use crate::migrations::workspace_and_favorite_v1::FavoriteV1AndWorkspaceArrayMig
 use crate::migrations::workspace_trash_v1::WorkspaceTrashMapToSectionMigration;
 use crate::migrations::AnonUser;
 use crate::services::authenticate_user::AuthenticateUser;
+use crate::services::backup::{DefaultUserBackupService, UserBackupService};
 use crate::services::cloud_config::get_cloud_config;
 use crate::services::collab_interact::{CollabInteract, DefaultCollabInteract};
 
@@ -55,6 +56,7 @@ pub struct UserManager {
   pub(crate) user_status_callback: RwLock<Arc<dyn UserStatusCallback>>,
   pub(crate) collab_builder: Weak<AppFlowyCollabBuilder>,
   pub(crate) collab_interact: RwLock<Arc<dyn CollabInteract>>,
+  pub(crate) backup_service: RwLock<Arc<dyn UserBackupService>>,
   pub(crate) user_workspace_service: Arc<dyn UserWorkspaceService>,
   auth_process: Mutex<Option<UserAuthProcess>>,
   pub(crate) authenticate_user: Arc<AuthenticateUser>,
@@ -81,6 +83,7 @@ impl UserManager {
       user_status_callback,
       collab_builder,
       collab_interact: RwLock::new(Arc::new(DefaultCollabInteract)),
+      backup_service: RwLock::new(Arc::new(DefaultUserBackupService)),
       auth_process: Default::default(),
       authenticate_user,
       refresh_user_profile_since,
@@ -116,6 +119,14 @@ impl UserManager {
     Arc::downgrade(&self.store_preferences)
   }
 
+  pub async fn set_backup_service(&self, backup_service: Arc<dyn UserBackupService>) {
+    *self.backup_service.write().await = backup_service;
+  }
+
+  pub async fn backup_service(&self) -> Arc<dyn UserBackupService> {
+    self.backup_service.read().await.clone()
+  }
+
   /// Initializes the user session, including data migrations and user awareness configuration. This function
   /// will be invoked each time the user opens the application.
   ///
@@ -67,6 +67,14 @@ where
   folders.into_iter().map(|(path, _)| path).collect()
 }
 
+/// Returns the canonical path of the file if it exists inside the `root` directory, after the `..`
+/// segments and the symbolic links are resolved. Returns None for the paths that escape the `root`.
+pub fn canonicalize_in_dir(root: &Path, path: &Path) -> Option<PathBuf> {
+  let root = root.canonicalize().ok()?;
+  let path = path.canonicalize().ok()?;
+  path.starts_with(&root).then_some(path)
+}
+
 pub fn zip_folder(src_path: impl AsRef<Path>, dest_path: &Path) -> io::Result<()> {
   let src_path = src_path.as_ref();
 
@@ -67,6 +67,22 @@ pub fn md5<T: AsRef<[u8]>>(data: T) -> String {
   let md5 = format!("{:x}", md5::compute(data));
   md5
 }
+
+/// Escapes the text to put it in the content or in the quoted attribute of an HTML element.
+pub fn escape_html(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OperatingSystem {
   Unknown,